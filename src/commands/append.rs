@@ -0,0 +1,52 @@
+use chrono::Local;
+use std::io::{IsTerminal, Read};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::{capture, storage};
+
+/// Add `text` (or stdin when it is `None` or `-`) to today's entry without
+/// opening the editor: as list items under `section`, or as a timestamped
+/// note in the capture section
+pub async fn run(
+    text: Option<String>,
+    section: Option<String>,
+    json: bool,
+    config: &Config,
+) -> Result<()> {
+    let text = match text.filter(|text| text != "-") {
+        Some(text) => text,
+        None => {
+            let mut input = String::new();
+            let stdin = std::io::stdin();
+            if !stdin.is_terminal() {
+                stdin.lock().read_to_string(&mut input)?;
+            }
+            input
+        }
+    };
+
+    let date = Local::now().date_naive();
+    storage::pull_for_date(config, date).await;
+    let (_, path) = match &section {
+        Some(section) => capture::append_to_today_section(config, section, &text).await?,
+        None => capture::append_to_today(config, &text).await?,
+    };
+    storage::push_for_date(config, date).await;
+
+    let section = section.unwrap_or_else(|| {
+        capture::CAPTURE_HEADER
+            .trim_start_matches('#')
+            .trim()
+            .to_string()
+    });
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "date": date, "path": path, "section": section })
+        );
+    } else {
+        println!("✓ Added to {} under {}", date.format("%Y-%m-%d"), section);
+    }
+    Ok(())
+}
@@ -1,8 +1,197 @@
 use crate::config::Config;
 use crate::error::{JournalError, Result};
+use crate::journal::credentials::{self, Provider};
 use crate::journal::oauth;
 
-pub async fn run(config: &Config) -> Result<()> {
+pub async fn run(provider: Provider, config: &Config) -> Result<()> {
+    match provider {
+        Provider::Google => google(config).await,
+        Provider::GitHub => github(config).await,
+        Provider::GitLab => gitlab(config).await,
+        Provider::Fitbit => fitbit(config).await,
+        Provider::Strava => strava(config).await,
+    }
+}
+
+pub fn status(json: bool, config: &Config) -> Result<()> {
+    let statuses: Vec<_> = Provider::ALL
+        .iter()
+        .map(|&provider| credentials::status(config, provider))
+        .collect();
+
+    if json {
+        println!("{:#}", serde_json::json!(statuses));
+        return Ok(());
+    }
+
+    for status in &statuses {
+        let Some(source) = &status.source else {
+            println!(
+                "✗ {}: not signed in (run `easy_journal auth {}`)",
+                status.provider, status.provider
+            );
+            continue;
+        };
+        if let Some(error) = &status.error {
+            println!("⚠ {}: {} ({})", status.provider, error, source);
+            continue;
+        }
+
+        println!("✓ {}: {}", status.provider, source);
+        if !status.scopes.is_empty() {
+            println!("    Scopes: {}", status.scopes.join(", "));
+        }
+        match status.expires_at {
+            Some(at) => println!(
+                "    Access token {} {}{}",
+                if at < chrono::Local::now() {
+                    "expired"
+                } else {
+                    "expires"
+                },
+                at.format("%Y-%m-%d %H:%M"),
+                if status.refreshable {
+                    " (refreshed automatically)"
+                } else {
+                    ""
+                }
+            ),
+            None if source.ends_with("_TOKEN") => {}
+            None => println!("    Access token does not expire"),
+        }
+        match status.last_fetch {
+            Some(at) => println!("    Last fetch: {}", at.format("%Y-%m-%d %H:%M")),
+            None => println!("    Last fetch: never"),
+        }
+    }
+    Ok(())
+}
+
+pub async fn revoke(provider: Provider, json: bool, config: &Config) -> Result<()> {
+    let revoked = credentials::revoke(config, provider).await?;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "provider": provider.name(),
+                "remote": revoked.remote,
+                "removed": revoked.removed,
+                "note": revoked.note,
+            })
+        );
+        return Ok(());
+    }
+
+    if !revoked.removed {
+        println!("No stored credentials for {}.", provider.name());
+    } else {
+        if revoked.remote {
+            println!("✓ Revoked the {} token", provider.name());
+        } else if let Some(note) = &revoked.note {
+            println!("⚠ Not revoked remotely: {}", note);
+        }
+        println!(
+            "✓ Deleted {}",
+            provider.token_storage_path(config).display()
+        );
+    }
+    match provider {
+        Provider::GitHub if config.github_config.token.is_some() => {
+            println!("   GITHUB_TOKEN is still set in the environment.")
+        }
+        Provider::GitLab if config.gitlab_config.token.is_some() => {
+            println!("   GITLAB_TOKEN is still set in the environment.")
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn github(config: &Config) -> Result<()> {
+    println!("🔐 GitHub Authentication Setup\n");
+
+    let device = oauth::github_device_code(&config.github_config).await?;
+    println!("📱 Open {} and enter the code:\n", device.verification_uri);
+    println!("   {}\n", device.user_code);
+    println!("   Waiting for approval...");
+
+    let token = oauth::github_poll_token(&config.github_config, &device).await?;
+    oauth::save_github_token(&config.github_config, &token)?;
+
+    println!("\n✨ Setup complete! Use --github to include GitHub issues and PRs.");
+    println!(
+        "   Token stored (encrypted) at: {}",
+        config.github_config.token_storage_path.display()
+    );
+    if config.github_config.token.is_some() {
+        println!("   Note: GITHUB_TOKEN is set and takes precedence over the stored token.");
+    }
+
+    Ok(())
+}
+
+async fn gitlab(config: &Config) -> Result<()> {
+    println!(
+        "🔐 GitLab Authentication Setup ({})\n",
+        config.gitlab_config.host
+    );
+
+    let login = oauth::gitlab_login(&config.gitlab_config).await?;
+    println!("📱 Open this URL in your browser to approve access:\n");
+    println!("   {}\n", login.url);
+    println!("   Waiting for the redirect...");
+    oauth::gitlab_complete(&config.gitlab_config, login).await?;
+
+    println!("\n✨ Setup complete! Use --gitlab to include GitLab issues and MRs.");
+    println!(
+        "   Token stored (encrypted) at: {}",
+        config.gitlab_config.token_storage_path.display()
+    );
+    if config.gitlab_config.token.is_some() {
+        println!("   Note: GITLAB_TOKEN is set and takes precedence over the stored token.");
+    }
+
+    Ok(())
+}
+
+async fn fitbit(config: &Config) -> Result<()> {
+    println!("🔐 Fitbit Authentication Setup\n");
+
+    let login = oauth::fitbit_login(&config.fitbit).await?;
+    println!("📱 Open this URL in your browser to approve access:\n");
+    println!("   {}\n", login.url);
+    println!("   Waiting for the redirect...");
+    oauth::fitbit_complete(&config.fitbit, login).await?;
+
+    println!("\n✨ Setup complete! Add fitbit to JOURNAL_SECTIONS to fill in sleep and activity.");
+    println!(
+        "   Token stored (encrypted) at: {}",
+        config.fitbit.token_storage_path.display()
+    );
+
+    Ok(())
+}
+
+async fn strava(config: &Config) -> Result<()> {
+    println!("🔐 Strava Authentication Setup\n");
+
+    let login = oauth::strava_login(&config.strava).await?;
+    println!("📱 Open this URL in your browser to approve access:\n");
+    println!("   {}\n", login.url);
+    println!("   Waiting for the redirect...");
+    oauth::strava_complete(&config.strava, login).await?;
+
+    println!("\n✨ Setup complete! Add strava to JOURNAL_SECTIONS to list your activities.");
+    println!(
+        "   Token stored (encrypted) at: {}",
+        config.strava.token_storage_path.display()
+    );
+
+    Ok(())
+}
+
+async fn google(config: &Config) -> Result<()> {
     println!("🔐 Google Tasks Authentication Setup\n");
 
     // Check if credentials are configured
@@ -30,12 +219,13 @@ pub async fn run(config: &Config) -> Result<()> {
         client_id.clone(),
         client_secret.clone(),
         &config.google_oauth.token_storage_path,
+        config.google_oauth.token_passphrase.as_deref(),
     )
     .await?;
 
     println!("\n✨ Setup complete! You can now use Google Tasks in your journal.");
     println!(
-        "   Token stored at: {}",
+        "   Token stored (encrypted) at: {}",
         config.google_oauth.token_storage_path.display()
     );
 
@@ -0,0 +1,81 @@
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::backup;
+
+/// BACKUP_PASSPHRASE, or one typed on the terminal (twice when `confirm`)
+pub(crate) fn passphrase(config: &Config, confirm: bool) -> Result<String> {
+    if let Some(passphrase) = &config.backup.passphrase {
+        return Ok(passphrase.clone());
+    }
+    if !std::io::stdin().is_terminal() {
+        return Err(JournalError::InvalidConfig(
+            "Set BACKUP_PASSPHRASE to use encrypted backups without a terminal".to_string(),
+        ));
+    }
+    let entered = rpassword::prompt_password("Backup passphrase: ")?;
+    if entered.is_empty() {
+        return Err(JournalError::InvalidArgument(
+            "The passphrase can't be empty".to_string(),
+        ));
+    }
+    if confirm && rpassword::prompt_password("Repeat the passphrase: ")? != entered {
+        return Err(JournalError::InvalidArgument(
+            "The passphrases don't match".to_string(),
+        ));
+    }
+    Ok(entered)
+}
+
+/// Archive the journal to the file `output`, or into `to` (default BACKUP_DIR)
+/// keeping only the newest `keep` archives there (default BACKUP_KEEP).
+/// `encrypt` seals the archive with a passphrase.
+pub fn run(
+    to: Option<PathBuf>,
+    output: Option<PathBuf>,
+    keep: Option<usize>,
+    encrypt: bool,
+    json: bool,
+    config: &Config,
+) -> Result<()> {
+    let passphrase = encrypt.then(|| passphrase(config, true)).transpose()?;
+    let (path, manifest, pruned) = match output {
+        Some(path) => {
+            let manifest = backup::create_at(config, &path, passphrase.as_deref())?;
+            (path, manifest, Vec::new())
+        }
+        None => {
+            let dir = to.unwrap_or_else(|| backup::default_dir(config));
+            let (path, manifest) = backup::create(config, &dir, passphrase.as_deref())?;
+            let pruned = backup::prune(&dir, keep.unwrap_or(config.backup.keep))?;
+            (path, manifest, pruned)
+        }
+    };
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "path": path,
+                "files": manifest.files.len(),
+                "latest_entry": manifest.latest_entry,
+                "encrypted": encrypt,
+                "pruned": pruned,
+            })
+        );
+        return Ok(());
+    }
+
+    println!(
+        "✓ Backed up {} files to {} ({}verified)",
+        manifest.files.len(),
+        path.display(),
+        if encrypt { "encrypted, " } else { "" }
+    );
+    for old in &pruned {
+        println!("  • Removed old backup {}", old.display());
+    }
+    Ok(())
+}
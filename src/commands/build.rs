@@ -0,0 +1,57 @@
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::book::{self, BrokenLink};
+
+fn print_links(links: &[BrokenLink]) {
+    for link in links {
+        println!(
+            "    {}:{} → {}",
+            link.file.display(),
+            link.line,
+            link.target
+        );
+    }
+}
+
+/// Check SUMMARY.md and the links between entries, then build the book with
+/// mdbook. Broken SUMMARY.md links stop the build; broken entry links are
+/// reported as warnings.
+pub fn run(open: bool, json: bool, config: &Config) -> Result<()> {
+    let summary = book::check_summary(config)?;
+    if !summary.is_empty() {
+        if json {
+            println!("{:#}", serde_json::json!({ "summary": summary }));
+        } else {
+            println!("✗ SUMMARY.md links to {} missing files:", summary.len());
+            print_links(&summary);
+        }
+        return Err(JournalError::BookFailed(format!(
+            "SUMMARY.md links to {} missing files",
+            summary.len()
+        )));
+    }
+    let entries = book::check_entries(config)?;
+
+    if !json {
+        println!("✓ SUMMARY.md");
+        if !entries.is_empty() {
+            println!("⚠ {} links to missing entries:", entries.len());
+            print_links(&entries);
+        }
+        eprintln!("📚 Building the book...");
+    }
+    let output = book::build(config, open)?;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "output": output.display().to_string(),
+                "broken_links": entries,
+            })
+        );
+    } else {
+        println!("✓ Built the book in {}", output.display());
+    }
+    Ok(())
+}
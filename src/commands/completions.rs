@@ -0,0 +1,63 @@
+use chrono::{Local, NaiveDate};
+use clap_complete::env::Shells;
+use clap_complete::{CompletionCandidate, Shell};
+use std::io;
+
+use crate::config::{self, Config};
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem;
+
+/// Print the script that registers completions for `cmd` with `shell`.
+///
+/// The script calls back into `easy_journal` (with `COMPLETE=<shell>` set)
+/// on every completion, so dates of recent entries are offered too.
+pub fn run(shell: Shell, cmd: &clap::Command) -> Result<()> {
+    let shells = Shells::builtins();
+    let completer = shells.completer(&shell.to_string()).ok_or_else(|| {
+        JournalError::InvalidArgument(format!("Completions are not supported for {}", shell))
+    })?;
+
+    let bin = cmd.get_name();
+    completer.write_registration("COMPLETE", bin, bin, bin, &mut io::stdout())?;
+    Ok(())
+}
+
+/// Entry dates offered when completing a date argument
+const RECENT_DATES: usize = 30;
+
+/// `today`, `yesterday` and the dates of the newest entries, newest first, for
+/// completing date arguments
+pub fn entry_dates() -> Vec<CompletionCandidate> {
+    let today = Local::now().date_naive();
+    let relative = |word: &str, date: NaiveDate| {
+        CompletionCandidate::new(word).help(Some(date.format("%Y-%m-%d").to_string().into()))
+    };
+    let mut candidates = vec![relative("today", today)];
+    candidates.extend(
+        today
+            .pred_opt()
+            .map(|yesterday| relative("yesterday", yesterday)),
+    );
+
+    let config = Config::new();
+    let entries = filesystem::list_entries(&config.journal_dir, config.layout).unwrap_or_default();
+    candidates.extend(
+        entries
+            .into_iter()
+            .rev()
+            .take(RECENT_DATES)
+            .map(|(date, _)| {
+                CompletionCandidate::new(date.format("%Y-%m-%d").to_string())
+                    .help(Some(date.format("%A").to_string().into()))
+            }),
+    );
+    candidates
+}
+
+/// Profiles from the config files, for completing `--profile`
+pub fn profiles() -> Vec<CompletionCandidate> {
+    config::available_profiles()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
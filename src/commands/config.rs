@@ -0,0 +1,178 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{self, Config};
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::write_atomic;
+
+/// Print the effective value of one setting
+pub fn get(key: &str, json: bool) -> Result<()> {
+    let var = config::setting_var(key)?;
+    let value = config::var(&var).ok();
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "setting": var, "value": value })
+        );
+    } else if let Some(value) = value {
+        println!("{}", value);
+    } else {
+        eprintln!("{} is not set", var);
+    }
+    Ok(())
+}
+
+/// Write a setting to the user config file, or the journal's `.easy_journal.toml`
+pub fn set(key: &str, value: &str, project: bool, json: bool, config: &Config) -> Result<()> {
+    let var = config::setting_var(key)?;
+    if project && config::is_command(&var) {
+        return Err(JournalError::InvalidArgument(format!(
+            "{} runs a command, so it's only read from the user config; drop --project",
+            var
+        )));
+    }
+    let path = if project {
+        config::project_config_path(&config.root)
+    } else {
+        config::user_config_path()
+    };
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(JournalError::at_path(&path)(e)),
+    };
+    // Where the current value comes from, to warn when the new one won't apply
+    let from_files = config_files().into_iter().find_map(|file| {
+        let content = fs::read_to_string(&file).ok()?;
+        let vars = config::config_file_vars(&content).ok()?;
+        let (_, value) = vars.into_iter().find(|(name, _)| name == &var)?;
+        Some((file, value))
+    });
+    let shadowed_by = match (config::var(&var).ok(), from_files) {
+        // A profile's values are loaded ahead of everything but the environment
+        _ if config.profile.is_some() => None,
+        (Some(current), from_files)
+            if from_files
+                .as_ref()
+                .is_none_or(|(_, value)| value != &current) =>
+        {
+            Some("the environment or a .env file".to_string())
+        }
+        (_, Some((file, _)))
+            if file != path && !(project && file == config::user_config_path()) =>
+        {
+            Some(file.display().to_string())
+        }
+        _ => None,
+    };
+
+    let profile = config.profile.as_deref();
+    let updated = config::set_in_config_file(&content, profile, key, &var, value)
+        .map_err(|e| JournalError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent).map_err(JournalError::at_path(parent))?;
+    }
+    write_atomic(&path, updated)?;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "setting": var,
+                "value": value,
+                "file": path,
+                "profile": config.profile,
+                "shadowed_by": shadowed_by,
+            })
+        );
+    } else {
+        match &config.profile {
+            Some(profile) => println!(
+                "✓ Set {} in [profiles.{}] of {}",
+                var,
+                profile,
+                path.display()
+            ),
+            None => println!("✓ Set {} in {}", var, path.display()),
+        }
+        if let Some(source) = shadowed_by {
+            println!(
+                "  {} is also set in {}, which takes precedence",
+                var, source
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The config files that exist, highest precedence first
+fn config_files() -> Vec<PathBuf> {
+    let mut files = vec![config::project_config_path(Path::new(""))];
+    if let Ok(home) = config::var("JOURNAL_HOME") {
+        files.push(config::project_config_path(Path::new(&home)));
+    }
+    files.push(config::user_config_path());
+    files.into_iter().filter(|file| file.exists()).collect()
+}
+
+/// Print the effective configuration: every setting that's set, secrets
+/// masked; with `all`, the unset ones too
+pub fn show(all: bool, json: bool, config: &Config) -> Result<()> {
+    let mut settings: Vec<(String, Option<String>)> = config::SETTINGS
+        .iter()
+        .map(|var| (var.to_string(), config::var(var).ok()))
+        .filter(|(_, value)| all || value.is_some())
+        .collect();
+    let mut custom: Vec<(String, Option<String>)> = config::vars()
+        .into_iter()
+        .filter(|(var, _)| var.starts_with("SECTION_") && var.ends_with("_COMMAND"))
+        .map(|(var, value)| (var, Some(value)))
+        .collect();
+    custom.sort();
+    settings.extend(custom);
+    let settings: Vec<(String, Option<String>)> = settings
+        .into_iter()
+        .map(|(var, value)| {
+            let value = value.map(|v| {
+                if config::is_secret(&var) {
+                    "********".to_string()
+                } else {
+                    v
+                }
+            });
+            (var, value)
+        })
+        .collect();
+    let files = config_files();
+
+    if json {
+        let values: serde_json::Map<String, serde_json::Value> = settings
+            .into_iter()
+            .map(|(var, value)| (var, serde_json::json!(value)))
+            .collect();
+        println!(
+            "{:#}",
+            serde_json::json!({ "profile": config.profile, "files": files, "settings": values })
+        );
+        return Ok(());
+    }
+
+    if let Some(profile) = &config.profile {
+        println!("# profile: {}", profile);
+    }
+    for file in &files {
+        println!("# {}", file.display());
+    }
+    let width = settings.iter().map(|(var, _)| var.len()).max().unwrap_or(0);
+    for (var, value) in settings {
+        println!(
+            "{:width$} = {}",
+            var,
+            value.as_deref().unwrap_or("(unset)"),
+            width = width
+        );
+    }
+    Ok(())
+}
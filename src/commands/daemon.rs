@@ -0,0 +1,29 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::{discord, matrix, scheduler, telegram};
+
+pub async fn run(config: &Config) -> Result<()> {
+    println!(
+        "⏰ Creating each day's entry at {}",
+        config.daemon.create_at.format("%H:%M")
+    );
+    if config.telegram.bot_token.is_some() {
+        println!("💬 Capturing Telegram messages into today's entry");
+        tokio::spawn(telegram::run(config.clone()));
+    }
+    if let Some(room) = &config.matrix.room {
+        println!(
+            "💬 Capturing Matrix messages from {} into today's entry",
+            room
+        );
+        tokio::spawn(matrix::run(config.clone()));
+    }
+    if config.discord.bot_token.is_some() {
+        println!("💬 Capturing Discord DMs into today's entry");
+        tokio::spawn(discord::run(config.clone()));
+    }
+    println!("Press Ctrl+C to stop");
+
+    scheduler::run_daily(config.clone()).await;
+    Ok(())
+}
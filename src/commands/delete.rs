@@ -0,0 +1,65 @@
+use std::io::{BufRead, IsTerminal, Write};
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::entry::JournalEntry;
+use crate::journal::{dates, filesystem, storage};
+
+/// Ask on the terminal whether to delete `path`; anything but y/yes keeps it
+fn confirm(path: &std::path::Path) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Err(JournalError::InvalidArgument(
+            "Not asking for confirmation without a terminal; pass --yes to delete".to_string(),
+        ));
+    }
+    eprint!("Delete {}? [y/N] ", path.display());
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Move the entry for `date_str` to the trash and remove it from SUMMARY.md,
+/// after asking unless `yes`
+pub async fn run(date_str: String, yes: bool, json: bool, config: &Config) -> Result<()> {
+    let date = dates::parse(&date_str)?;
+
+    if !JournalEntry::exists(date, config) {
+        return Err(JournalError::EntryNotFound(
+            date.format("%Y-%m-%d").to_string(),
+        ));
+    }
+
+    let entry_path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+    if !yes && !confirm(&entry_path)? {
+        if json {
+            println!(
+                "{:#}",
+                serde_json::json!({ "date": date, "deleted": false })
+            );
+        } else {
+            println!("Kept {}", entry_path.display());
+        }
+        return Ok(());
+    }
+
+    JournalEntry::delete(date, config)?;
+    storage::push_for_date(config, date).await;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "date": date, "deleted": true, "path": entry_path, "trash_dir": config.trash_dir })
+        );
+        return Ok(());
+    }
+
+    println!(
+        "🗑  Moved {} to {}",
+        entry_path.display(),
+        config.trash_dir.display()
+    );
+    println!("   Run 'easy_journal undo' to bring it back.");
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use chrono::Local;
+use std::io::BufRead;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::dictate::{self, Recorder};
+use crate::journal::{capture, storage};
+
+pub async fn run(seconds: Option<u64>, dry_run: bool, json: bool, config: &Config) -> Result<()> {
+    let recorder = Recorder::start()?;
+    match seconds {
+        Some(seconds) => {
+            eprintln!("🎙  Recording for {} seconds...", seconds);
+            std::thread::sleep(Duration::from_secs(seconds));
+        }
+        None => {
+            eprintln!("🎙  Recording... press Enter to stop");
+            let mut line = String::new();
+            std::io::stdin().lock().read_line(&mut line)?;
+        }
+    }
+    let audio = recorder.stop();
+    if audio.samples.is_empty() {
+        return Err(JournalError::DictationFailed(
+            "Nothing was recorded".to_string(),
+        ));
+    }
+
+    eprintln!("📝 Transcribing {:.0}s of audio...", audio.duration_secs());
+    let transcript = dictate::transcribe(&config.dictate, &audio)?;
+    if transcript.is_empty() {
+        return Err(JournalError::DictationFailed(
+            "No speech was recognized".to_string(),
+        ));
+    }
+
+    let date = Local::now().date_naive();
+    if !dry_run {
+        storage::pull_for_date(config, date).await;
+        capture::append_to_today(config, &transcript).await?;
+        storage::push_for_date(config, date).await;
+    }
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "date": date, "transcript": transcript, "saved": !dry_run })
+        );
+    } else if dry_run {
+        println!("{}", transcript);
+    } else {
+        println!("✓ Added to {}: {}", date.format("%Y-%m-%d"), transcript);
+    }
+    Ok(())
+}
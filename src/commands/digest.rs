@@ -0,0 +1,89 @@
+use chrono::{Days, Months, NaiveDate};
+use clap::ValueEnum;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::{dates, digest, email};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum DigestPeriod {
+    /// The 7 days ending on the date
+    Week,
+    /// The month ending on the date
+    Month,
+}
+
+impl DigestPeriod {
+    fn title(self) -> &'static str {
+        match self {
+            DigestPeriod::Week => "Weekly Digest",
+            DigestPeriod::Month => "Monthly Digest",
+        }
+    }
+
+    fn since(self, until: NaiveDate) -> NaiveDate {
+        match self {
+            DigestPeriod::Week => until - Days::new(6),
+            DigestPeriod::Month => until - Months::new(1) + Days::new(1),
+        }
+    }
+}
+
+pub async fn run(
+    period: DigestPeriod,
+    date_str: Option<String>,
+    send_email: bool,
+    json: bool,
+    config: &Config,
+) -> Result<()> {
+    let until = dates::parse_or_today(date_str.as_deref())?;
+    let digest = digest::build(
+        &config.journal_dir,
+        config.layout,
+        period.title(),
+        period.since(until),
+        until,
+        config.redact_strip,
+    )?;
+
+    // Don't fill the inbox with empty digests
+    let emailed = send_email && digest.entries > 0;
+    if emailed {
+        let subject = format!(
+            "{}: {} – {}",
+            digest.title,
+            digest.since.format("%b %-d"),
+            digest.until.format("%b %-d, %Y")
+        );
+        email::send(&config.email, &subject, &digest.markdown).await?;
+    }
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "since": digest.since,
+                "until": digest.until,
+                "entries": digest.entries,
+                "markdown": digest.markdown,
+                "emailed": emailed,
+            })
+        );
+    } else if emailed {
+        println!(
+            "✉️  Sent {} ({} entries) to {}",
+            digest.title.to_lowercase(),
+            digest.entries,
+            config.email.to.as_deref().unwrap_or_default()
+        );
+    } else if send_email {
+        println!(
+            "No entries between {} and {}, nothing sent.",
+            digest.since, digest.until
+        );
+    } else {
+        print!("{}", digest.markdown);
+    }
+
+    Ok(())
+}
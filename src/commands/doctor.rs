@@ -0,0 +1,40 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::doctor::{self, Outcome};
+
+/// Check the journal, templates, sign-ins and tools, with a hint for each problem
+pub async fn run(json: bool, config: &Config) -> Result<()> {
+    let checks = doctor::run(config).await;
+
+    if json {
+        println!("{:#}", serde_json::json!({ "checks": checks }));
+        return Ok(());
+    }
+
+    for check in &checks {
+        let symbol = match check.outcome {
+            Outcome::Pass => "✓",
+            Outcome::Warn => "⚠",
+            Outcome::Fail => "✗",
+            Outcome::Skip => "-",
+        };
+        println!("{} {}: {}", symbol, check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("    → {}", hint);
+        }
+    }
+
+    let count = |outcome| {
+        checks
+            .iter()
+            .filter(|check| check.outcome == outcome)
+            .count()
+    };
+    let (failed, warned) = (count(Outcome::Fail), count(Outcome::Warn));
+    if failed + warned == 0 {
+        println!("\n✓ Everything looks good");
+    } else {
+        println!("\n{} failed, {} warnings", failed, warned);
+    }
+    Ok(())
+}
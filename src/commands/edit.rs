@@ -0,0 +1,31 @@
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::dates;
+use crate::journal::filesystem;
+use crate::journal::hooks::{self, HookEvent};
+use crate::journal::storage;
+
+/// Open the existing entry for `date_str` (default: today) in the editor.
+/// Unlike `new`, nothing is fetched, carried over or added to SUMMARY.md.
+pub async fn run(date_str: Option<String>, json: bool, config: &Config) -> Result<()> {
+    let date = dates::parse_or_today(date_str.as_deref())?;
+
+    // Refresh the local cache from remote storage (no-op for local journals)
+    storage::pull_for_date(config, date).await;
+
+    let path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+    if !path.exists() {
+        return Err(JournalError::EntryNotFound(date.to_string()));
+    }
+
+    if json {
+        println!("{:#}", serde_json::json!({ "date": date, "path": path }));
+        return Ok(());
+    }
+
+    super::new::open_in_editor(&path.to_string_lossy())?;
+    hooks::notify(config, HookEvent::PostSave, date, &path);
+
+    storage::push_for_date(config, date).await;
+    Ok(())
+}
@@ -0,0 +1,277 @@
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::static_site::{self, Generator};
+use crate::journal::{dates, epub, filesystem, json_export, navigation, parser, pdf, redact};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ExportFormat {
+    /// Whole-journal structured dump (readable by `import --format json`)
+    Json,
+    /// One JSON object per line for each entry, for jq or a database
+    Jsonl,
+    /// E-book of a month, a year or the whole journal
+    Epub,
+    /// Printable document with a title page per day
+    Pdf,
+    /// Pages with front matter under `content/journal/` of a Zola site
+    Zola,
+    /// Pages with front matter under `content/journal/` of a Hugo site
+    Hugo,
+}
+
+/// Days to export, with a title and a name for the output file
+struct Period {
+    first: NaiveDate,
+    last: NaiveDate,
+    title: String,
+    slug: String,
+}
+
+/// The days of `--month YYYY-MM`, `--year YYYY` or `--from`/`--to` dates
+fn period(
+    month: Option<&str>,
+    year: Option<&str>,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<Option<Period>> {
+    let invalid = |value: &str, format: &str| {
+        JournalError::DateParse(format!(
+            "Invalid date format: {} (expected {})",
+            value, format
+        ))
+    };
+    if let Some(month) = month {
+        let first = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .map_err(|_| invalid(month, "YYYY-MM"))?;
+        let last = first
+            .checked_add_months(chrono::Months::new(1))
+            .and_then(|next| next.pred_opt())
+            .ok_or_else(|| invalid(month, "YYYY-MM"))?;
+        return Ok(Some(Period {
+            first,
+            last,
+            title: first.format("%B %Y").to_string(),
+            slug: month.to_string(),
+        }));
+    }
+    if let Some(year) = year {
+        let number: i32 = year.parse().map_err(|_| invalid(year, "YYYY"))?;
+        let first = NaiveDate::from_ymd_opt(number, 1, 1).ok_or_else(|| invalid(year, "YYYY"))?;
+        let last = NaiveDate::from_ymd_opt(number, 12, 31).ok_or_else(|| invalid(year, "YYYY"))?;
+        return Ok(Some(Period {
+            first,
+            last,
+            title: year.to_string(),
+            slug: year.to_string(),
+        }));
+    }
+    let from = from.map(dates::parse).transpose()?;
+    let to = to.map(dates::parse).transpose()?;
+    let (title, slug) = match (from, to) {
+        (None, None) => return Ok(None),
+        (Some(from), Some(to)) => (format!("{} to {}", from, to), format!("{}-to-{}", from, to)),
+        (Some(from), None) => (format!("{} onwards", from), format!("{}-onwards", from)),
+        (None, Some(to)) => (format!("until {}", to), format!("until-{}", to)),
+    };
+    let first = from.unwrap_or(NaiveDate::MIN);
+    let last = to.unwrap_or(NaiveDate::MAX);
+    if first > last {
+        return Err(JournalError::InvalidArgument(format!(
+            "--from {} is after --to {}",
+            first, last
+        )));
+    }
+    Ok(Some(Period {
+        first,
+        last,
+        title,
+        slug,
+    }))
+}
+
+/// Entries in `period` (and with `tag`, if given) as (date, path, markdown),
+/// redacted unless `keep_private`
+fn selected_entries(
+    config: &Config,
+    period: Option<&Period>,
+    tag: Option<&str>,
+    keep_private: bool,
+) -> Result<Vec<(NaiveDate, PathBuf, String)>> {
+    let tag = tag.map(|tag| tag.trim_start_matches('#').to_lowercase());
+    let mut entries = Vec::new();
+    for (date, path) in filesystem::list_entries(&config.journal_dir, config.layout)? {
+        if period.is_some_and(|period| !(period.first..=period.last).contains(&date)) {
+            continue;
+        }
+        let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+        // The previous/next links point at markdown files that exports don't have
+        let content = navigation::strip(&content);
+        let content = if keep_private {
+            content
+        } else {
+            redact::redact(&content, config.redact_strip)
+        };
+        if tag
+            .as_ref()
+            .is_some_and(|tag| !parser::extract_tags(&content).contains(tag))
+        {
+            continue;
+        }
+        entries.push((date, path, content));
+    }
+    Ok(entries)
+}
+
+/// Which entries to export; all of them by default
+pub struct Selection {
+    pub month: Option<String>,
+    pub year: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub tag: Option<String>,
+}
+
+pub fn run(
+    format: ExportFormat,
+    output: Option<PathBuf>,
+    keep_private: bool,
+    selection: Selection,
+    config: &Config,
+) -> Result<()> {
+    let Selection {
+        month,
+        year,
+        from,
+        to,
+        tag,
+    } = selection;
+    let period = period(
+        month.as_deref(),
+        year.as_deref(),
+        from.as_deref(),
+        to.as_deref(),
+    )?;
+
+    let rendered = match format {
+        ExportFormat::Json => {
+            if period.is_some() || tag.is_some() {
+                return Err(JournalError::InvalidArgument(
+                    "--month, --year, --from, --to and --tag don't apply to --format json"
+                        .to_string(),
+                ));
+            }
+            let mut dump = json_export::export_journal(&config.journal_dir, config.layout)?;
+            if !keep_private {
+                dump.redact(config.redact_strip);
+            }
+            serde_json::to_string_pretty(&dump).map_err(|e| {
+                JournalError::ExportFailed(format!("Failed to encode export: {}", e))
+            })?
+        }
+        ExportFormat::Jsonl => {
+            let mut lines = Vec::new();
+            for (date, _, content) in
+                selected_entries(config, period.as_ref(), tag.as_deref(), keep_private)?
+            {
+                let record = json_export::EntryRecord::from_content(date, &content);
+                lines.push(serde_json::to_string(&record).map_err(|e| {
+                    JournalError::ExportFailed(format!("Failed to encode export: {}", e))
+                })?);
+            }
+            lines.join("\n")
+        }
+        ExportFormat::Epub => {
+            let entries = selected_entries(config, period.as_ref(), tag.as_deref(), keep_private)?;
+            let (title, file_name) = match &period {
+                Some(period) => (
+                    format!("Journal: {}", period.title),
+                    format!("journal-{}.epub", period.slug),
+                ),
+                None => ("Journal".to_string(), "journal.epub".to_string()),
+            };
+            if entries.is_empty() {
+                return Err(JournalError::ExportFailed(format!(
+                    "No entries to export for {}",
+                    title
+                )));
+            }
+
+            let book = epub::build(&title, &entries)?;
+            let path = output.unwrap_or_else(|| PathBuf::from(file_name));
+            filesystem::write_atomic(&path, &book)?;
+            eprintln!("✓ Exported {} entries to {}", entries.len(), path.display());
+            return Ok(());
+        }
+        ExportFormat::Pdf => {
+            let entries: Vec<(NaiveDate, String)> =
+                selected_entries(config, period.as_ref(), tag.as_deref(), keep_private)?
+                    .into_iter()
+                    .map(|(date, _, content)| (date, content))
+                    .collect();
+            let (title, file_name) = match &period {
+                Some(period) => (
+                    format!("Journal: {}", period.title),
+                    format!("journal-{}.pdf", period.slug),
+                ),
+                None => ("Journal".to_string(), "journal.pdf".to_string()),
+            };
+            if entries.is_empty() {
+                return Err(JournalError::ExportFailed(format!(
+                    "No entries to export for {}",
+                    title
+                )));
+            }
+
+            let path = output.unwrap_or_else(|| PathBuf::from(file_name));
+            filesystem::write_atomic(&path, pdf::build(&title, &entries))?;
+            eprintln!("✓ Exported {} entries to {}", entries.len(), path.display());
+            return Ok(());
+        }
+        ExportFormat::Zola | ExportFormat::Hugo => {
+            let generator = match format {
+                ExportFormat::Zola => Generator::Zola,
+                _ => Generator::Hugo,
+            };
+            let entries: Vec<(NaiveDate, String)> =
+                selected_entries(config, period.as_ref(), tag.as_deref(), keep_private)?
+                    .into_iter()
+                    .map(|(date, _, content)| (date, content))
+                    .collect();
+            if entries.is_empty() {
+                return Err(JournalError::ExportFailed(
+                    "No entries to export".to_string(),
+                ));
+            }
+
+            let site = output.unwrap_or_else(|| PathBuf::from("site"));
+            for (relative, content) in static_site::pages(generator, &entries) {
+                let path = site.join(relative);
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent).map_err(JournalError::at_path(parent))?;
+                }
+                filesystem::write_atomic(&path, content)?;
+            }
+            eprintln!(
+                "✓ Exported {} entries to {}",
+                entries.len(),
+                site.join("content/journal").display()
+            );
+            return Ok(());
+        }
+    };
+
+    match output {
+        Some(path) => {
+            filesystem::write_atomic(&path, &rendered)?;
+            eprintln!("✓ Exported journal to {}", path.display());
+        }
+        None => println!("{}", rendered),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::{dates, grep};
+
+/// Where to look and what to print; every line of every entry by default
+pub struct Options {
+    pub section: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub ignore_case: bool,
+    pub only_matching: bool,
+}
+
+/// Print the lines matching `pattern` as `date:line: text`, one per line so the
+/// output can be piped; with `only_matching`, each match or its capture groups
+pub fn run(pattern: &str, options: Options, json: bool, config: &Config) -> Result<()> {
+    let Options {
+        section,
+        since,
+        until,
+        ignore_case,
+        only_matching,
+    } = options;
+    let regex = grep::pattern(pattern, ignore_case)?;
+    let matches = grep::grep(
+        &config.journal_dir,
+        config.layout,
+        &regex,
+        section.as_deref(),
+        since.as_deref().map(dates::parse).transpose()?,
+        until.as_deref().map(dates::parse).transpose()?,
+    )?;
+
+    if json {
+        println!("{:#}", serde_json::json!(matches));
+        return Ok(());
+    }
+
+    for found in &matches {
+        if only_matching {
+            for extracted in found.extracted() {
+                println!("{}:{}: {}", found.date, found.line, extracted);
+            }
+        } else {
+            println!("{}:{}: {}", found.date, found.line, found.text);
+        }
+    }
+    Ok(())
+}
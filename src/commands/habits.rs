@@ -0,0 +1,52 @@
+use chrono::{Days, Local, Weekday};
+use std::fs;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::{filesystem, habits};
+
+/// Recurring checkboxes with their streaks, weekly completion and a calendar
+/// of the last `weeks` weeks
+pub fn run(weeks: u32, json: bool, config: &Config) -> Result<()> {
+    let today = Local::now().date_naive();
+    let since = today.week(Weekday::Mon).first_day() - Days::new(7 * u64::from(weeks.max(1) - 1));
+
+    let mut entries = Vec::new();
+    for (date, path) in filesystem::list_entries(&config.journal_dir, config.layout)? {
+        if date > today {
+            continue;
+        }
+        let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+        entries.push((date, content));
+    }
+    let habits = habits::detect(&entries, since, today);
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "since": since, "until": today, "habits": habits })
+        );
+        return Ok(());
+    }
+
+    if habits.is_empty() {
+        println!(
+            "No habits found since {}: a checkbox has to come back on {} days and be checked off on {} of them",
+            since,
+            habits::MIN_DAYS,
+            habits::MIN_DONE
+        );
+        return Ok(());
+    }
+    for (i, habit) in habits.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "{}: streak {} days (longest {})",
+            habit.name, habit.current_streak, habit.longest_streak
+        );
+        print!("{}", habits::heatmap(habit, since, today));
+    }
+    Ok(())
+}
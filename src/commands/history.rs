@@ -0,0 +1,42 @@
+use std::fs;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::dates;
+use crate::journal::history;
+
+pub fn run(date_str: String, json: bool, config: &Config) -> Result<()> {
+    let date = dates::parse(&date_str)?;
+
+    let versions = history::list_versions(&config.history_dir, date)?;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "date": date, "versions": versions })
+        );
+        return Ok(());
+    }
+
+    if versions.is_empty() {
+        println!("No saved versions for {}", date.format("%Y-%m-%d"));
+        return Ok(());
+    }
+
+    println!("Saved versions for {}:", date.format("%Y-%m-%d"));
+    for snapshot in versions {
+        let size = fs::metadata(&snapshot.path).map(|m| m.len()).unwrap_or(0);
+        println!(
+            "  {:>3}  {}  ({} bytes)",
+            snapshot.version,
+            snapshot.taken_at.format("%Y-%m-%d %H:%M:%S"),
+            size
+        );
+    }
+    println!(
+        "\nRestore one with: easy_journal restore {} --version N",
+        date.format("%Y-%m-%d")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use clap::ValueEnum;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::import::{self, Conflict, ImportedEntry};
+use crate::journal::json_export::{self, ImportStats, JournalDump};
+use crate::journal::{dayone, jrnl, storage};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ImportFormat {
+    /// A dump written by `export --format json`
+    Json,
+    /// A jrnl journal file or `jrnl --export txt`
+    Jrnl,
+    /// A Day One JSON export (.zip, or an unzipped journal .json)
+    Dayone,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum ConflictPolicy {
+    /// Keep the existing entry
+    #[default]
+    Skip,
+    /// Replace the existing entry (the old version stays in history)
+    Overwrite,
+    /// Add the imported text to the existing entry's Journal section
+    Append,
+}
+
+impl ConflictPolicy {
+    fn conflict(self) -> Conflict {
+        match self {
+            ConflictPolicy::Skip => Conflict::Skip,
+            ConflictPolicy::Overwrite => Conflict::Overwrite,
+            ConflictPolicy::Append => Conflict::Append,
+        }
+    }
+}
+
+/// Write entries read from another journal app and upload the written days
+async fn import_entries(
+    entries: &[ImportedEntry],
+    file: &Path,
+    app: &str,
+    policy: ConflictPolicy,
+    config: &Config,
+) -> Result<ImportStats> {
+    if entries.is_empty() {
+        return Err(JournalError::ImportFailed(format!(
+            "{} has no {} entries",
+            file.display(),
+            app
+        )));
+    }
+    let (stats, dates) = import::write_days(entries, config, policy.conflict())?;
+    for date in dates {
+        storage::push_for_date(config, date).await;
+    }
+    Ok(stats)
+}
+
+pub async fn run(
+    format: ImportFormat,
+    file: PathBuf,
+    policy: ConflictPolicy,
+    json: bool,
+    config: &Config,
+) -> Result<()> {
+    let stats = match format {
+        ImportFormat::Json => {
+            if policy == ConflictPolicy::Append {
+                return Err(JournalError::InvalidArgument(
+                    "--on-conflict append is not supported for JSON dumps".to_string(),
+                ));
+            }
+            let content = fs::read_to_string(&file)?;
+            let dump: JournalDump = serde_json::from_str(&content).map_err(|e| {
+                JournalError::ImportFailed(format!(
+                    "{} is not a journal dump: {}",
+                    file.display(),
+                    e
+                ))
+            })?;
+
+            let overwrite = policy == ConflictPolicy::Overwrite;
+            let stats = json_export::import_journal(&dump, config, overwrite)?;
+            json_export::push_imported(&dump, config).await;
+            stats
+        }
+        ImportFormat::Jrnl => {
+            let text = fs::read_to_string(&file)?;
+            import_entries(&jrnl::parse(&text), &file, "jrnl", policy, config).await?
+        }
+        ImportFormat::Dayone => {
+            let entries = dayone::read(&file)?;
+            import_entries(&entries, &file, "Day One", policy, config).await?
+        }
+    };
+
+    if json {
+        println!("{:#}", serde_json::json!(stats));
+        return Ok(());
+    }
+
+    println!(
+        "✓ Imported {} new entries, overwrote {}, appended to {}, skipped {} existing",
+        stats.created, stats.overwritten, stats.appended, stats.skipped
+    );
+    if stats.skipped > 0 {
+        println!(
+            "   Use --on-conflict overwrite or append to import into existing entries (old versions stay in history)."
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use chrono::Local;
+use std::fs;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::entry::JournalEntry;
+use crate::journal::lint::{self, Issue};
+use crate::journal::{dates, filesystem, goals, storage, template};
+
+fn describe(issue: &Issue) -> String {
+    format!("{}: {}", issue.kind.label(), issue.message)
+}
+
+/// Lint one entry (`--date`) or every entry, optionally fixing trivial issues.
+/// Today's entry is also checked against the SECTION_GOALS writing goals.
+pub async fn run(date_str: Option<String>, fix: bool, json: bool, config: &Config) -> Result<()> {
+    let entries = match date_str {
+        Some(s) => {
+            let date = dates::parse(&s)?;
+            let path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+            if !path.exists() {
+                return Err(JournalError::EntryNotFound(s));
+            }
+            vec![(date, path)]
+        }
+        None => filesystem::list_entries(&config.journal_dir, config.layout)?,
+    };
+    let template = template::load_template(&config.template_path)?;
+    let today = Local::now().date_naive();
+
+    let mut files = Vec::new();
+    let mut fixed_count = 0;
+    for (date, path) in &entries {
+        if fix {
+            storage::pull_for_date(config, *date).await;
+        }
+        let content = fs::read_to_string(path).map_err(JournalError::at_path(path))?;
+        let mut issues = lint::check(&content, &template);
+        let mut fixed = false;
+        if fix && issues.iter().any(|issue| issue.fixable) {
+            let updated = lint::fix(&content);
+            if updated != content {
+                JournalEntry::save(*date, &updated, config)?;
+                storage::push_for_date(config, *date).await;
+                fixed_count += issues.iter().filter(|issue| issue.fixable).count();
+                fixed = true;
+                issues = lint::check(&updated, &template);
+            }
+        }
+        if *date == today {
+            let content = fs::read_to_string(path).map_err(JournalError::at_path(path))?;
+            issues.extend(lint::goal_issues(&goals::progress(
+                &content,
+                &config.section_goals,
+            )));
+        }
+        if !issues.is_empty() || fixed {
+            files.push((date, path, issues, fixed));
+        }
+    }
+
+    if json {
+        let files: Vec<serde_json::Value> = files
+            .iter()
+            .map(|(date, path, issues, fixed)| {
+                serde_json::json!({ "date": date, "path": path, "fixed": fixed, "issues": issues })
+            })
+            .collect();
+        println!(
+            "{:#}",
+            serde_json::json!({ "checked": entries.len(), "fixed": fixed_count, "files": files })
+        );
+        return Ok(());
+    }
+
+    let mut remaining = 0;
+    for (_, path, issues, fixed) in &files {
+        if *fixed {
+            println!("✓ Fixed {}", path.display());
+        }
+        for issue in issues {
+            remaining += 1;
+            match issue.line {
+                Some(line) => println!("{}:{}: {}", path.display(), line, describe(issue)),
+                None => println!("{}: {}", path.display(), describe(issue)),
+            }
+        }
+    }
+    if remaining == 0 {
+        println!("✓ No issues in {} entries", entries.len());
+    } else {
+        let fixable = files
+            .iter()
+            .flat_map(|(_, _, issues, _)| issues)
+            .filter(|issue| issue.fixable)
+            .count();
+        println!(
+            "{} issues in {} of {} entries{}",
+            remaining,
+            files
+                .iter()
+                .filter(|(_, _, issues, _)| !issues.is_empty())
+                .count(),
+            entries.len(),
+            if fixable > 0 {
+                format!(" ({} fixable with --fix)", fixable)
+            } else {
+                String::new()
+            }
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use chrono::{Duration, Local, NaiveDate};
+use std::fs;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::{dates, filesystem, parser};
+
+fn parse_date(value: &str, format: &str, expected: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, format).map_err(|_| {
+        JournalError::DateParse(format!(
+            "Invalid date format: {} (expected {})",
+            value, expected
+        ))
+    })
+}
+
+/// First and last day to list from `--month YYYY-MM`, `--since YYYY-MM-DD` or
+/// `--last N` days (today included); `None` for every entry
+fn range(
+    month: Option<&str>,
+    since: Option<&str>,
+    last: Option<u32>,
+) -> Result<Option<(NaiveDate, NaiveDate)>> {
+    let today = Local::now().date_naive();
+    if let Some(month) = month {
+        let first = parse_date(&format!("{}-01", month), "%Y-%m-%d", "YYYY-MM")?;
+        let end = first
+            .checked_add_months(chrono::Months::new(1))
+            .and_then(|next| next.pred_opt())
+            .unwrap_or(first);
+        return Ok(Some((first, end)));
+    }
+    if let Some(since) = since {
+        return Ok(Some((dates::parse(since)?, NaiveDate::MAX)));
+    }
+    if let Some(days) = last {
+        let first = today - Duration::days(i64::from(days.max(1)) - 1);
+        return Ok(Some((first, today)));
+    }
+    Ok(None)
+}
+
+/// List the entries on disk with their weekday, word count and open tasks
+pub fn run(
+    month: Option<String>,
+    since: Option<String>,
+    last: Option<u32>,
+    json: bool,
+    config: &Config,
+) -> Result<()> {
+    let range = range(month.as_deref(), since.as_deref(), last)?;
+    let mut rows = Vec::new();
+    for (date, path) in filesystem::list_entries(&config.journal_dir, config.layout)? {
+        if range.is_some_and(|(first, end)| date < first || date > end) {
+            continue;
+        }
+        let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+        let open_tasks = parser::extract_tasks(&content)
+            .iter()
+            .filter(|(_, done)| !done)
+            .count();
+        rows.push((date, path, parser::word_count(&content), open_tasks));
+    }
+
+    if json {
+        let entries: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|(date, path, words, open_tasks)| {
+                serde_json::json!({
+                    "date": date,
+                    "weekday": date.format("%A").to_string(),
+                    "path": path,
+                    "words": words,
+                    "open_tasks": open_tasks,
+                })
+            })
+            .collect();
+        println!("{:#}", serde_json::json!(entries));
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        println!("No entries");
+        return Ok(());
+    }
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|(date, _, words, open_tasks)| {
+            vec![
+                date.to_string(),
+                date.format("%a").to_string(),
+                words.to_string(),
+                match open_tasks {
+                    0 => "-".to_string(),
+                    count => count.to_string(),
+                },
+            ]
+        })
+        .collect();
+    super::query::print_table(&["Date", "Day", "Words", "Open tasks"], &cells);
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::{autocommit, dates, filesystem};
+
+pub fn run(date_str: String, patch: bool, json: bool, config: &Config) -> Result<()> {
+    let date = dates::parse(&date_str)?;
+
+    let entry_path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+    let log = autocommit::entry_log(&config.journal_dir, &entry_path, patch)?;
+
+    if json {
+        println!("{:#}", serde_json::json!({ "date": date, "commits": log }));
+        return Ok(());
+    }
+
+    if log.is_empty() {
+        println!(
+            "No commits touch the entry for {}.",
+            date.format("%Y-%m-%d")
+        );
+        if !config.git_autocommit {
+            println!("Set GIT_AUTOCOMMIT=1 to commit every save automatically.");
+        }
+        return Ok(());
+    }
+
+    println!("📜 Change history for {}:", date.format("%Y-%m-%d"));
+    for entry in log {
+        println!(
+            "  {}  {}  {} (+{} -{})",
+            entry.id,
+            entry.time.format("%Y-%m-%d %H:%M:%S"),
+            entry.message,
+            entry.insertions,
+            entry.deletions
+        );
+        if let Some(patch) = entry.patch {
+            println!("{}", patch);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::error::{JournalError, Result};
+
+/// Write `easy_journal.1` plus one page per subcommand into `dir`
+pub fn run(dir: PathBuf, cmd: clap::Command) -> Result<()> {
+    fs::create_dir_all(&dir).map_err(JournalError::at_path(&dir))?;
+    clap_mangen::generate_to(cmd, &dir).map_err(JournalError::at_path(&dir))?;
+
+    println!("✓ Wrote man pages to {}", dir.display());
+    Ok(())
+}
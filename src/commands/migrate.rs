@@ -0,0 +1,66 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::index::Index;
+use crate::journal::migrate;
+
+/// Bring the journal layout up to date, after copying it to a backup unless
+/// `no_backup`. With `dry_run`, only list the changes.
+pub fn run(dry_run: bool, no_backup: bool, json: bool, config: &Config) -> Result<()> {
+    let plan = migrate::plan(config)?;
+    let pending = plan
+        .changes
+        .iter()
+        .any(|change| !matches!(change, migrate::Change::Conflict { .. }));
+
+    let backup = if pending && !dry_run && !no_backup {
+        Some(migrate::backup(config)?)
+    } else {
+        None
+    };
+    if pending && !dry_run {
+        migrate::apply(config, &plan)?;
+        // Moved entries have new paths
+        let mut index = Index::open(&config.index_path)?;
+        index.rebuild(&config.journal_dir, config.layout)?;
+    }
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "from_version": plan.from_version,
+                "to_version": plan.to_version,
+                "changes": plan.changes,
+                "applied": pending && !dry_run,
+                "backup": backup,
+            })
+        );
+        return Ok(());
+    }
+
+    if plan.changes.is_empty() {
+        println!(
+            "✓ Journal layout is up to date (version {})",
+            plan.to_version
+        );
+        return Ok(());
+    }
+
+    println!(
+        "Journal layout version {} → {}",
+        plan.from_version, plan.to_version
+    );
+    for change in &plan.changes {
+        println!("  • {}", change.describe(config.layout));
+    }
+    if dry_run {
+        println!("\nDry run: nothing was changed");
+    } else if pending {
+        if let Some(backup) = backup {
+            println!("\n✓ Backed up the journal to {}", backup.display());
+        }
+        println!("✓ Migrated to layout version {}", plan.to_version);
+    }
+
+    Ok(())
+}
@@ -1,4 +1,46 @@
+pub mod append;
 pub mod auth;
+pub mod backup;
+pub mod build;
+pub mod completions;
+pub mod config;
+pub mod daemon;
+pub mod delete;
+pub mod dictate;
+pub mod digest;
+pub mod doctor;
+pub mod edit;
+pub mod export;
+pub mod grep;
+pub mod habits;
+pub mod history;
+pub mod import;
 pub mod init;
+pub mod lint;
+pub mod list;
+pub mod log;
+pub mod manpages;
+pub mod migrate;
+pub mod mood;
+pub mod nav;
 pub mod new;
+pub mod notify;
+pub mod on_this_day;
+pub mod prompts;
+pub mod proof;
+pub mod publish;
+pub mod query;
+pub mod reindex;
+pub mod resolve;
+pub mod restore;
+pub mod search;
 pub mod serve;
+pub mod show;
+pub mod stats;
+pub mod summarize;
+pub mod sync;
+pub mod tasks;
+pub mod timer;
+pub mod todo;
+pub mod undo;
+pub mod week;
@@ -0,0 +1,88 @@
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::{capture, storage};
+
+/// Footer fields to set; the named ones map to the default template's fields
+pub struct Values {
+    pub mood: Option<u32>,
+    pub energy: Option<u32>,
+    pub hours: Option<f32>,
+    pub sleep: Option<u32>,
+    /// `KEY=VALUE` for any other field
+    pub metrics: Vec<String>,
+}
+
+/// Log mood, energy and other footer fields into today's entry without opening it
+pub async fn run(values: Values, note: Option<String>, json: bool, config: &Config) -> Result<()> {
+    let Values {
+        mood,
+        energy,
+        hours,
+        sleep,
+        metrics,
+    } = values;
+    let mut values = Vec::new();
+    if let Some(mood) = mood {
+        values.push(("Mood".to_string(), mood.to_string()));
+    }
+    if let Some(energy) = energy {
+        values.push(("Energy Level".to_string(), energy.to_string()));
+    }
+    if let Some(hours) = hours {
+        if !(0.0..=24.0).contains(&hours) {
+            return Err(JournalError::InvalidArgument(format!(
+                "--hours must be from 0 to 24, got {}",
+                hours
+            )));
+        }
+        values.push(("Hours Worked".to_string(), hours.to_string()));
+    }
+    if let Some(sleep) = sleep {
+        values.push(("Sleep Score".to_string(), sleep.to_string()));
+    }
+    for metric in &metrics {
+        let (key, value) = metric
+            .split_once('=')
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+            .ok_or_else(|| {
+                JournalError::InvalidArgument(format!(
+                    "Expected --metric KEY=VALUE, got \"{}\"",
+                    metric
+                ))
+            })?;
+        values.push((key.to_string(), value.to_string()));
+    }
+    if values.is_empty() && note.is_none() {
+        return Err(JournalError::InvalidArgument(
+            "Nothing to log: give a mood, --energy, --hours, --sleep, --metric or --note"
+                .to_string(),
+        ));
+    }
+
+    let date = chrono::Local::now().date_naive();
+    storage::pull_for_date(config, date).await;
+    let (date, path) = capture::log_metrics(config, &values, note.as_deref()).await?;
+    storage::push_for_date(config, date).await;
+
+    if json {
+        let metrics: serde_json::Map<String, serde_json::Value> = values
+            .iter()
+            .map(|(key, value)| (key.clone(), serde_json::json!(value)))
+            .collect();
+        println!(
+            "{:#}",
+            serde_json::json!({ "date": date, "path": path, "metrics": metrics, "note": note })
+        );
+        return Ok(());
+    }
+
+    for (key, value) in &values {
+        println!("✓ {}: {}", key, value);
+    }
+    if let Some(note) = note {
+        println!("✓ Note: {}", note);
+    }
+    println!("  in {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::navigation;
+
+/// Add or refresh the previous/next links at the bottom of every entry
+pub fn run(json: bool, config: &Config) -> Result<()> {
+    let changed = navigation::backfill(config)?;
+
+    if json {
+        println!("{:#}", serde_json::json!({ "updated": changed }));
+        return Ok(());
+    }
+
+    if changed.is_empty() {
+        println!("✓ Every entry already links to its neighbours");
+    } else {
+        println!(
+            "✓ Updated the previous/next links of {} entries",
+            changed.len()
+        );
+    }
+    if !config.nav_links {
+        println!("Set NAV_LINKS=true to link new entries as they are created.");
+    }
+    Ok(())
+}
@@ -1,45 +1,342 @@
 use chrono::{Local, NaiveDate};
-use std::env;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Mutex;
+use std::time::Duration;
 
-use crate::config::Config;
+use crate::config::{self, Config};
 use crate::error::{JournalError, Result};
 use crate::journal::entry::JournalEntry;
+use crate::journal::hooks::{self, HookEvent};
+use crate::journal::rollover::{Decision, Rollover, RolloverReview};
+use crate::journal::sections::{JournalSection, SectionProgress};
+use crate::journal::{conflicts, dates, storage};
 
-pub async fn run(date_str: Option<String>, config: &Config) -> Result<()> {
+/// One spinner per section while a new entry's sections are fetched
+struct Spinners {
+    multi: MultiProgress,
+    bars: Mutex<HashMap<String, ProgressBar>>,
+}
+
+impl Spinners {
+    fn new() -> Self {
+        Spinners {
+            // Hidden automatically when stderr is not a terminal
+            multi: MultiProgress::with_draw_target(ProgressDrawTarget::stderr()),
+            bars: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl SectionProgress for Spinners {
+    fn started(&self, section: &dyn JournalSection) {
+        let bar = self.multi.add(ProgressBar::new_spinner());
+        bar.set_style(
+            ProgressStyle::with_template("{spinner:.cyan} {msg} ({elapsed})")
+                .expect("valid spinner template"),
+        );
+        bar.set_message(section.title().to_string());
+        bar.enable_steady_tick(Duration::from_millis(100));
+        self.bars
+            .lock()
+            .unwrap()
+            .insert(section.name().to_string(), bar);
+    }
+
+    fn finished(
+        &self,
+        section: &dyn JournalSection,
+        result: &Result<Option<String>>,
+        elapsed: Duration,
+    ) {
+        let Some(bar) = self.bars.lock().unwrap().remove(section.name()) else {
+            return;
+        };
+        let outcome = match result {
+            Ok(Some(_)) => "✓",
+            Ok(None) => "– nothing to add:",
+            Err(_) => "✗ failed:",
+        };
+        bar.set_style(ProgressStyle::with_template("{msg}").expect("valid spinner template"));
+        bar.finish_with_message(format!(
+            "{} {} ({:.1}s)",
+            outcome,
+            section.title(),
+            elapsed.as_secs_f64()
+        ));
+    }
+}
+
+/// Asks on the terminal about each carried-over task (`new --review`)
+pub struct RolloverPrompt;
+
+impl RolloverReview for RolloverPrompt {
+    fn review(&self, task: &str) -> Decision {
+        loop {
+            eprint!("↪ {}  [K]eep / [d]rop / de[f]er? ", task);
+            let _ = std::io::stderr().flush();
+            let mut answer = String::new();
+            if std::io::stdin().lock().read_line(&mut answer).unwrap_or(0) == 0 {
+                return Decision::Keep;
+            }
+            match answer.trim().to_lowercase().as_str() {
+                "" | "k" | "keep" => return Decision::Keep,
+                "d" | "drop" => return Decision::Drop,
+                "f" | "defer" => return Decision::Defer,
+                _ => eprintln!("Answer k, d or f"),
+            }
+        }
+    }
+}
+
+/// What `new` does once the entry is ready
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finish {
+    /// Open it in the editor
+    Edit,
+    /// Leave it for later (`--no-edit`)
+    NoEdit,
+    /// Print nothing but its path (`--print-path`)
+    PrintPath,
+}
+
+/// Point out sync conflicts so they don't go unnoticed
+fn warn_conflicts(config: &Config) {
+    if let Ok(conflicts) = conflicts::find_conflicts(&config.journal_dir, config.layout)
+        && !conflicts.is_empty()
+    {
+        tracing::warn!(
+            "{} sync conflict copies found; run 'easy_journal resolve' to merge them",
+            conflicts.len()
+        );
+    }
+}
+
+/// `rollover`, unless it's a review with nobody at a terminal to ask
+fn reviewable<'a>(rollover: &'a Rollover<'a>, json: bool) -> &'a Rollover<'a> {
+    match rollover {
+        Rollover::Review(_) if json || !std::io::stdin().is_terminal() => {
+            tracing::warn!("Not at a terminal; carrying over every task without review");
+            &Rollover::All
+        }
+        rollover => rollover,
+    }
+}
+
+/// Create (or open) the entry for `date_str` and open it in the editor,
+/// unless `finish` says otherwise.
+///
+/// With `json`, the entry is created without opening the editor and its
+/// path is printed as JSON instead. `rollover` decides which of the previous
+/// entry's unchecked tasks a newly created entry gets.
+pub async fn run(
+    date_str: Option<String>,
+    config: &Config,
+    show_progress: bool,
+    json: bool,
+    rollover: &Rollover<'_>,
+    finish: Finish,
+) -> Result<()> {
     // Determine the date
     let date = if let Some(date_str) = date_str {
-        NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-            .map_err(|e| JournalError::DateParse(format!("Invalid date format: {}", e)))?
+        dates::parse(&date_str)?
     } else {
         Local::now().date_naive()
     };
 
+    warn_conflicts(config);
+
+    // Refresh the local cache from remote storage (no-op for local journals)
+    storage::pull_for_date(config, date).await;
+
+    let existed = JournalEntry::exists(date, config);
+
+    let rollover = reviewable(rollover, json);
+
     // Create or get existing entry
-    let entry = JournalEntry::create(date, config).await?;
+    let entry = if show_progress && !json && finish != Finish::PrintPath {
+        JournalEntry::create_with_progress(date, config, &Spinners::new(), rollover).await?
+    } else {
+        JournalEntry::create_with_progress(date, config, &(), rollover).await?
+    };
 
-    let exists_msg = if JournalEntry::exists(date, config) {
+    if json {
+        storage::push_for_date(config, date).await;
+        println!(
+            "{:#}",
+            serde_json::json!({ "date": date, "path": entry.file_path, "created": !existed })
+        );
+        return Ok(());
+    }
+    if let Some(message) = ready_message(finish, date, &entry.file_path, existed) {
+        storage::push_for_date(config, date).await;
+        println!("{}", message);
+        return Ok(());
+    }
+
+    let exists_msg = if existed {
         "Opening existing entry"
     } else {
         "Created new entry"
     };
-
     println!("{} for {}", exists_msg, date.format("%Y-%m-%d"));
-    println!(
-        "Entry path: {:?} for entry date {:?}",
-        entry.file_path, entry.date
-    );
+    tracing::debug!(path = %entry.file_path.display(), "entry ready");
 
     // Open in editor
     open_in_editor(&entry.file_path.to_string_lossy())?;
+    hooks::notify(config, HookEvent::PostSave, date, &entry.file_path);
+
+    storage::push_for_date(config, date).await;
+
+    Ok(())
+}
 
+/// What `new` prints once the entry is ready instead of opening the editor;
+/// `None` when it opens the editor
+fn ready_message(finish: Finish, date: NaiveDate, path: &Path, existed: bool) -> Option<String> {
+    match finish {
+        Finish::Edit => None,
+        Finish::PrintPath => Some(path.display().to_string()),
+        Finish::NoEdit => Some(format!(
+            "{} for {}: {}",
+            if existed {
+                "Entry already exists"
+            } else {
+                "Created new entry"
+            },
+            date.format("%Y-%m-%d"),
+            path.display()
+        )),
+    }
+}
+
+/// An entry of a `new --from` range
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct RangeEntry {
+    date: NaiveDate,
+    path: PathBuf,
+    created: bool,
+}
+
+/// Most entries one `new --from` creates, so a mistyped year doesn't fill
+/// the journal with thousands of entries
+const MAX_RANGE_DAYS: usize = 366;
+
+/// The days from `from` to `to`, both included
+fn range_dates(from: NaiveDate, to: NaiveDate) -> Result<Vec<NaiveDate>> {
+    if from > to {
+        return Err(JournalError::InvalidArgument(format!(
+            "--from {} is after --to {}",
+            from, to
+        )));
+    }
+    let days = (to - from).num_days() as usize + 1;
+    if days > MAX_RANGE_DAYS {
+        return Err(JournalError::InvalidArgument(format!(
+            "--from {} to {} covers {} days; create at most {} at a time",
+            from, to, days, MAX_RANGE_DAYS
+        )));
+    }
+    Ok(from.iter_days().take_while(|date| *date <= to).collect())
+}
+
+/// Create the missing entries of `dates` in order, so each one carries over
+/// what the one before left unchecked; `on_created` sees each new entry.
+///
+/// Only the template and what is known about each date are filled in: the
+/// configured sections are skipped, as most of them fetch the current state
+/// (open tasks, issues) rather than that of the date, and need the network.
+async fn create_range(
+    dates: &[NaiveDate],
+    config: &Config,
+    rollover: &Rollover<'_>,
+    mut on_created: impl FnMut(&RangeEntry),
+) -> Result<Vec<RangeEntry>> {
+    let config = &Config {
+        sections: Vec::new(),
+        ..config.clone()
+    };
+    let mut entries = Vec::new();
+    for &date in dates {
+        storage::pull_for_date(config, date).await;
+        let existed = JournalEntry::exists(date, config);
+        let entry = JournalEntry::create_with_progress(date, config, &(), rollover).await?;
+        let entry = RangeEntry {
+            date,
+            path: entry.file_path,
+            created: !existed,
+        };
+        if entry.created {
+            storage::push_for_date(config, date).await;
+            on_created(&entry);
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// The line printed for a newly created entry of a range
+fn created_line(entry: &RangeEntry, finish: Finish) -> String {
+    match finish {
+        Finish::PrintPath => entry.path.display().to_string(),
+        Finish::Edit | Finish::NoEdit => format!("✓ Created {}", entry.date.format("%Y-%m-%d")),
+    }
+}
+
+/// Create every missing entry from `from` to `to` (default today) in date
+/// order, so each one carries over what the one before left unchecked.
+///
+/// Existing entries are left alone and nothing is opened in the editor.
+/// With `Finish::PrintPath` only the created entries' paths are printed.
+pub async fn run_range(
+    from: &str,
+    to: Option<&str>,
+    config: &Config,
+    json: bool,
+    rollover: &Rollover<'_>,
+    finish: Finish,
+) -> Result<()> {
+    let from = dates::parse(from)?;
+    let to = dates::parse_or_today(to)?;
+    let days = range_dates(from, to)?;
+
+    warn_conflicts(config);
+    let rollover = reviewable(rollover, json);
+
+    let entries = create_range(&days, config, rollover, |entry| {
+        if !json {
+            println!("{}", created_line(entry, finish));
+        }
+    })
+    .await?;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "from": from, "to": to, "entries": entries })
+        );
+    } else if finish != Finish::PrintPath {
+        let created = entries.iter().filter(|entry| entry.created).count();
+        println!(
+            "Created {} of {} entries from {} to {} ({} already existed)",
+            created,
+            entries.len(),
+            from.format("%Y-%m-%d"),
+            to.format("%Y-%m-%d"),
+            entries.len() - created
+        );
+    }
     Ok(())
 }
 
-fn open_in_editor(path: &str) -> Result<()> {
+pub(crate) fn open_in_editor(path: &str) -> Result<()> {
     // Try to get editor from environment variables
-    let editor = env::var("VISUAL")
-        .or_else(|_| env::var("EDITOR"))
+    let editor = config::var("VISUAL")
+        .or_else(|_| config::var("EDITOR"))
         .unwrap_or_else(|_| {
             // Try common editors as fallback
             if Command::new("code").arg("--version").output().is_ok() {
@@ -61,7 +358,7 @@ fn open_in_editor(path: &str) -> Result<()> {
         return Ok(());
     }
 
-    println!("Opening with editor: {}", editor);
+    tracing::info!("Opening with editor: {}", editor);
 
     let status = Command::new(&editor)
         .arg(path)
@@ -77,3 +374,174 @@ fn open_in_editor(path: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::HooksConfig;
+    use std::fs;
+
+    /// An initialized journal in a temp directory that fetches nothing and
+    /// runs no hooks
+    fn temp_config(name: &str) -> Config {
+        let dir =
+            std::env::temp_dir().join(format!("easy_journal_new_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut config = Config::default();
+        config.set_root(dir, Path::new("journal"));
+        config.sections = Vec::new();
+        config.hooks = HooksConfig::default();
+        config.git_autocommit = false;
+        config.nav_links = false;
+        config.storage.backend = "local".to_string();
+        fs::create_dir_all(&config.journal_dir).unwrap();
+        fs::write(config.journal_dir.join("SUMMARY.md"), "# Summary\n").unwrap();
+        config
+    }
+
+    #[test]
+    fn test_ready_message() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let path = Path::new("/journal/2025/12/29.md");
+        // Both scripted modes print instead of opening the editor
+        assert_eq!(
+            ready_message(Finish::PrintPath, date, path, false).as_deref(),
+            Some("/journal/2025/12/29.md")
+        );
+        assert_eq!(
+            ready_message(Finish::PrintPath, date, path, true).as_deref(),
+            Some("/journal/2025/12/29.md")
+        );
+        assert_eq!(
+            ready_message(Finish::NoEdit, date, path, false).as_deref(),
+            Some("Created new entry for 2025-12-29: /journal/2025/12/29.md")
+        );
+        assert_eq!(
+            ready_message(Finish::NoEdit, date, path, true).as_deref(),
+            Some("Entry already exists for 2025-12-29: /journal/2025/12/29.md")
+        );
+        assert_eq!(ready_message(Finish::Edit, date, path, false), None);
+    }
+
+    #[tokio::test]
+    async fn test_run_without_editor() {
+        let config = temp_config("run");
+        // Finishing without an editor returns before one is looked up
+        for finish in [Finish::NoEdit, Finish::PrintPath] {
+            run(
+                Some("2025-12-29".to_string()),
+                &config,
+                false,
+                false,
+                &Rollover::Skip,
+                finish,
+            )
+            .await
+            .unwrap();
+        }
+        assert!(config.journal_dir.join("2025/12/29.md").is_file());
+        fs::remove_dir_all(&config.root).unwrap();
+    }
+
+    #[test]
+    fn test_range_dates() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 12, d).unwrap();
+        assert_eq!(
+            range_dates(date(30), NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()).unwrap(),
+            vec![
+                date(30),
+                date(31),
+                NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+            ]
+        );
+        assert_eq!(range_dates(date(29), date(29)).unwrap(), vec![date(29)]);
+        assert!(matches!(
+            range_dates(date(30), date(29)),
+            Err(JournalError::InvalidArgument(_))
+        ));
+        let year_on = NaiveDate::from_ymd_opt(2026, 12, 29).unwrap();
+        assert_eq!(range_dates(date(29), year_on).unwrap().len(), 366);
+        assert!(range_dates(date(28), year_on).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_range() {
+        let mut config = temp_config("range");
+        config.sections = vec!["custom:probe".to_string()];
+        config
+            .custom_sections
+            .insert("probe".to_string(), "echo fetched-today".to_string());
+        let date = |d| NaiveDate::from_ymd_opt(2025, 12, d).unwrap();
+        let entry_path = |d| config.journal_dir.join(format!("2025/12/{:02}.md", d));
+        fs::create_dir_all(entry_path(1).parent().unwrap()).unwrap();
+        fs::write(
+            entry_path(1),
+            "# Monday\n\n## Goals for Today\n- [ ] Pack bags\n- [x] Book flights\n",
+        )
+        .unwrap();
+        fs::write(entry_path(4), "# Thursday\n\nWritten on the road\n").unwrap();
+
+        let mut printed = Vec::new();
+        let days = range_dates(date(1), date(5)).unwrap();
+        let entries = create_range(&days, &config, &Rollover::All, |entry| {
+            printed.push(created_line(entry, Finish::PrintPath))
+        })
+        .await
+        .unwrap();
+
+        let created: Vec<(NaiveDate, bool)> = entries
+            .iter()
+            .map(|entry| (entry.date, entry.created))
+            .collect();
+        assert_eq!(
+            created,
+            vec![
+                (date(1), false),
+                (date(2), true),
+                (date(3), true),
+                (date(4), false),
+                (date(5), true)
+            ]
+        );
+        // Only the new entries' paths
+        assert_eq!(
+            printed,
+            vec![
+                entry_path(2).display().to_string(),
+                entry_path(3).display().to_string(),
+                entry_path(5).display().to_string()
+            ]
+        );
+        // Existing entries are left alone
+        assert_eq!(
+            fs::read_to_string(entry_path(4)).unwrap(),
+            "# Thursday\n\nWritten on the road\n"
+        );
+        // Carried over in date order: the 3rd from the new 2nd, the 5th from
+        // the existing 4th, which has nothing left unchecked
+        for day in [2, 3] {
+            let content = fs::read_to_string(entry_path(day)).unwrap();
+            assert!(content.contains("- [ ] Pack bags"), "{}", day);
+            assert!(!content.contains("Book flights"), "{}", day);
+        }
+        assert!(
+            !fs::read_to_string(entry_path(5))
+                .unwrap()
+                .contains("Pack bags")
+        );
+
+        // No section content in backfilled entries, unlike a single new one
+        for day in [2, 3, 5] {
+            let content = fs::read_to_string(entry_path(day)).unwrap();
+            assert!(!content.contains("fetched-today"), "{}", day);
+        }
+        let single = JournalEntry::create(date(6), &config).await.unwrap();
+        assert!(
+            fs::read_to_string(single.file_path)
+                .unwrap()
+                .contains("fetched-today")
+        );
+
+        fs::remove_dir_all(&config.root).unwrap();
+    }
+}
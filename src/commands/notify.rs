@@ -0,0 +1,19 @@
+use chrono::Local;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::entry::JournalEntry;
+use crate::journal::notify;
+
+/// Send the reminder notification if today's entry doesn't exist (for cron)
+pub fn run(config: &Config) -> Result<()> {
+    let today = Local::now().date_naive();
+    if JournalEntry::exists(today, config) {
+        println!("✓ Already journaled today");
+        return Ok(());
+    }
+
+    notify::remind_to_journal()?;
+    println!("🔔 Sent a reminder to journal");
+    Ok(())
+}
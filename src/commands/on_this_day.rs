@@ -0,0 +1,44 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::dates;
+use crate::journal::on_this_day;
+
+/// Highlights of the entries written on the same day in earlier years
+pub fn run(date_str: Option<String>, json: bool, config: &Config) -> Result<()> {
+    let date = dates::parse_or_today(date_str.as_deref())?;
+    let memories = on_this_day::find(&config.journal_dir, config.layout, date)?;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "date": date, "entries": memories })
+        );
+        return Ok(());
+    }
+
+    if memories.is_empty() {
+        println!("No entries from {} in earlier years", date.format("%b %-d"));
+        return Ok(());
+    }
+    for (i, memory) in memories.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "{} · {} year{} ago",
+            memory.date.format("%a %Y-%m-%d"),
+            memory.years_ago,
+            if memory.years_ago == 1 { "" } else { "s" }
+        );
+        if memory.highlights.is_empty() {
+            println!("  (no highlights written down)");
+        }
+        for highlight in &memory.highlights {
+            println!("  {}", highlight.section);
+            for item in &highlight.items {
+                println!("    - {}", item);
+            }
+        }
+    }
+    Ok(())
+}
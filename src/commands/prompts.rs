@@ -0,0 +1,25 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::dates;
+use crate::journal::prompts::{self, Trigger};
+
+pub fn run(date_str: Option<String>, json: bool, config: &Config) -> Result<()> {
+    let date = dates::parse_or_today(date_str.as_deref())?;
+    let suggestions = prompts::suggest(&config.journal_dir, config.layout, date)?;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "date": date, "prompts": suggestions })
+        );
+        return Ok(());
+    }
+
+    for suggestion in &suggestions {
+        println!("✎ {}", suggestion.prompt);
+        if suggestion.trigger != Trigger::Daily {
+            println!("  {}", suggestion.reason);
+        }
+    }
+    Ok(())
+}
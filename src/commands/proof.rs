@@ -0,0 +1,92 @@
+use std::fs;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::dates;
+use crate::journal::filesystem;
+use crate::journal::proof::{self, Dictionary, Issue};
+
+fn describe(issue: &Issue) -> String {
+    match issue.suggestions.as_slice() {
+        [] => format!("{}: {}", issue.kind.label(), issue.message),
+        suggestions => format!(
+            "{}: {} (did you mean {}?)",
+            issue.kind.label(),
+            issue.message,
+            suggestions.join(", ")
+        ),
+    }
+}
+
+/// Print the entry with each issue marked under its line
+fn print_inline(content: &str, issues: &[Issue]) {
+    let width = content.lines().count().to_string().len();
+    for (index, line) in content.lines().enumerate() {
+        println!("{:>width$} | {}", index + 1, line);
+        for issue in issues.iter().filter(|i| i.line == index + 1) {
+            println!(
+                "{:>width$} | {}{} {}",
+                "",
+                " ".repeat(issue.column - 1),
+                "^".repeat(issue.length.max(1)),
+                describe(issue)
+            );
+        }
+    }
+}
+
+pub fn run(date_str: Option<String>, inline: bool, json: bool, config: &Config) -> Result<()> {
+    let date = dates::parse_or_today(date_str.as_deref())?;
+    let path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+    if !path.exists() {
+        return Err(JournalError::EntryNotFound(
+            date.format("%Y-%m-%d").to_string(),
+        ));
+    }
+    let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+
+    let mut dictionary = Dictionary::find(&config.proof)?;
+    match (&mut dictionary, &config.proof.words) {
+        (Some(dictionary), Some(words)) => {
+            dictionary.add_words(&fs::read_to_string(words).map_err(JournalError::at_path(words))?)
+        }
+        (None, _) if !json => eprintln!(
+            "No {} dictionary found, only checking style (set PROOF_DICTIONARY to a Hunspell .dic file)",
+            config.proof.language
+        ),
+        _ => {}
+    }
+
+    let issues = proof::check(
+        &content,
+        dictionary.as_ref(),
+        config.proof.max_sentence_words,
+    );
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "date": date,
+                "path": path,
+                "spelling_checked": dictionary.is_some(),
+                "issues": issues,
+            })
+        );
+    } else if issues.is_empty() {
+        println!("✓ No issues in {}", path.display());
+    } else if inline {
+        print_inline(&content, &issues);
+    } else {
+        for issue in &issues {
+            println!(
+                "{}:{}:{}: {}",
+                path.display(),
+                issue.line,
+                issue.column,
+                describe(issue)
+            );
+        }
+    }
+    Ok(())
+}
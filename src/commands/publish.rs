@@ -0,0 +1,38 @@
+use std::fs;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::publish::{self, Target};
+
+/// Build the book from a redacted copy of the journal and deploy it
+pub fn run(to: Option<String>, keep_private: bool, json: bool, config: &Config) -> Result<()> {
+    let spec = to
+        .or_else(|| config.publish.target.clone())
+        .ok_or_else(|| {
+            JournalError::InvalidConfig(
+                "No publish target: pass --to or set PUBLISH_TARGET".to_string(),
+            )
+        })?;
+    let target = Target::parse(&spec)?;
+
+    let staging = std::env::temp_dir().join(format!("easy_journal_publish_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&staging);
+    let result = publish::stage(config, &staging, keep_private).and_then(|_| {
+        eprintln!("📚 Building the book...");
+        let book = publish::build(&staging)?;
+        eprintln!("🚀 Publishing to {}...", target);
+        publish::deploy(config, &book, &target)
+    });
+    let _ = fs::remove_dir_all(&staging);
+    result?;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "target": target.to_string(), "redacted": !keep_private })
+        );
+    } else {
+        println!("✓ Published the journal to {}", target);
+    }
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use std::fs;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem;
+use crate::journal::query::{self, Rows};
+
+/// Print `rows` as aligned columns
+pub(crate) fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([header[i].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let line = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let header: Vec<String> = header.iter().map(|cell| cell.to_string()).collect();
+    let rule: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+    println!("{}", line(&header));
+    println!("{}", line(&rule));
+    for row in rows {
+        println!("{}", line(row));
+    }
+}
+
+/// Run a query over every entry
+pub fn run(query_str: &str, json: bool, config: &Config) -> Result<()> {
+    let expr = query::parse(query_str)?;
+    let mut entries = Vec::new();
+    for (date, path) in filesystem::list_entries(&config.journal_dir, config.layout)? {
+        let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+        entries.push((date, content));
+    }
+    let rows = query::run(&expr, &entries);
+
+    if json {
+        println!("{:#}", serde_json::json!(rows));
+        return Ok(());
+    }
+
+    match rows {
+        Rows::Entries(rows) if !rows.is_empty() => {
+            let cells: Vec<Vec<String>> = rows
+                .iter()
+                .map(|row| {
+                    vec![
+                        row.date.to_string(),
+                        row.words.to_string(),
+                        format!("{}/{}", row.done_tasks, row.done_tasks + row.open_tasks),
+                        row.tags
+                            .iter()
+                            .map(|tag| format!("#{}", tag))
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                    ]
+                })
+                .collect();
+            print_table(&["Date", "Words", "Tasks", "Tags"], &cells);
+            println!("\n{} entries", rows.len());
+        }
+        Rows::Tasks(rows) if !rows.is_empty() => {
+            let cells: Vec<Vec<String>> = rows
+                .iter()
+                .map(|row| {
+                    vec![
+                        row.date.to_string(),
+                        row.state.to_string(),
+                        row.text.clone(),
+                    ]
+                })
+                .collect();
+            print_table(&["Date", "State", "Task"], &cells);
+            println!("\n{} tasks", rows.len());
+        }
+        _ => println!("No matches."),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::index::Index;
+
+pub fn run(json: bool, config: &Config) -> Result<()> {
+    if !json {
+        println!("Rebuilding entry index...");
+    }
+
+    let mut index = Index::open(&config.index_path)?;
+    let count = index.rebuild(&config.journal_dir, config.layout)?;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "indexed": count, "index_path": config.index_path })
+        );
+        return Ok(());
+    }
+
+    println!(
+        "✓ Indexed {} entries into {}",
+        count,
+        config.index_path.display()
+    );
+
+    Ok(())
+}
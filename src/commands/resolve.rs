@@ -0,0 +1,98 @@
+use std::fs;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::conflicts;
+use crate::journal::dates;
+use crate::journal::entry::JournalEntry;
+use crate::journal::history;
+use crate::journal::lock::WriteLock;
+use crate::journal::storage;
+use crate::journal::trash;
+
+pub async fn run(
+    date_str: Option<String>,
+    dry_run: bool,
+    json: bool,
+    config: &Config,
+) -> Result<()> {
+    let date = date_str.map(|s| dates::parse(&s)).transpose()?;
+
+    let conflicts: Vec<_> = conflicts::find_conflicts(&config.journal_dir, config.layout)?
+        .into_iter()
+        .filter(|c| date.is_none_or(|d| c.date == d))
+        .collect();
+
+    if conflicts.is_empty() && !json {
+        println!("No sync conflicts found.");
+        return Ok(());
+    }
+
+    let mut resolved = Vec::new();
+    for conflict in conflicts {
+        let theirs = fs::read_to_string(&conflict.copy)?;
+        let is_new = !conflict.original.exists();
+        let merged = if !is_new {
+            let ours = fs::read_to_string(&conflict.original)?;
+
+            // The latest snapshot is the best guess at the common ancestor
+            let base = match history::list_versions(&config.history_dir, conflict.date)?.last() {
+                Some(snapshot) => Some(fs::read_to_string(&snapshot.path)?),
+                None => None,
+            };
+            conflicts::merge_entries(base.as_deref(), &ours, &theirs)
+        } else {
+            theirs
+        };
+
+        if json {
+            resolved.push(serde_json::json!({
+                "date": conflict.date,
+                "original": conflict.original,
+                "copy": conflict.copy,
+                "merged": dry_run.then_some(&merged),
+            }));
+        }
+
+        if dry_run {
+            if json {
+                continue;
+            }
+            println!(
+                "--- {} merged with {} ---",
+                conflict.original.display(),
+                conflict.copy.display()
+            );
+            println!("{}", merged);
+            continue;
+        }
+
+        JournalEntry::save(conflict.date, &merged, config)?;
+        {
+            let _lock = WriteLock::acquire(&config.lock_path)?;
+            trash::move_to_trash(&config.trash_dir, &conflict.copy, None)?;
+        }
+        if is_new {
+            JournalEntry::add_to_summary(conflict.date, config)?;
+        }
+        storage::push_for_date(config, conflict.date).await;
+
+        if json {
+            continue;
+        }
+        println!(
+            "✓ Merged {} into {}",
+            conflict.copy.display(),
+            conflict.original.display()
+        );
+    }
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "dry_run": dry_run, "resolved": resolved })
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::backup::{self, RestoreAction};
+use crate::journal::entry::JournalEntry;
+use crate::journal::index::Index;
+use crate::journal::{dates, filesystem, history, storage};
+
+pub async fn run(date_str: String, version: usize, json: bool, config: &Config) -> Result<()> {
+    let date = dates::parse(&date_str)?;
+
+    let content = history::load_version(&config.history_dir, date, version)?;
+    let entry_path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+
+    if !entry_path.exists() {
+        return Err(JournalError::HistoryFailed(format!(
+            "Entry for {} no longer exists at {}",
+            date.format("%Y-%m-%d"),
+            entry_path.display()
+        )));
+    }
+
+    // Saving keeps the current content so the restore itself can be undone
+    JournalEntry::save(date, &content, config)?;
+    storage::push_for_date(config, date).await;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "date": date, "version": version, "path": entry_path })
+        );
+        return Ok(());
+    }
+
+    println!(
+        "✓ Restored version {} of {}",
+        version,
+        date.format("%Y-%m-%d")
+    );
+
+    Ok(())
+}
+
+/// Restore entries, templates and config from a `backup` archive after checking
+/// it against its manifest and the journal's layout. Refuses to go back in time (a newer entry on disk,
+/// or files changed since the archive was made) unless `force`. The current
+/// journal is backed up first.
+pub async fn from_backup(
+    archive: &Path,
+    dry_run: bool,
+    force: bool,
+    json: bool,
+    config: &Config,
+) -> Result<()> {
+    let passphrase = if backup::is_encrypted(archive)? {
+        Some(super::backup::passphrase(config, false)?)
+    } else {
+        None
+    };
+    let (manifest, files) = backup::read_archive(archive, passphrase.as_deref())?;
+    backup::check(&manifest, &files)?;
+    backup::check_layout(config, &files)?;
+    let items = backup::plan_restore(config, &manifest, &files)?;
+    let changes: Vec<_> = items
+        .iter()
+        .filter(|item| item.action != RestoreAction::Unchanged)
+        .collect();
+    let newer_entry = backup::newer_entry(config, &manifest)?;
+    let newer_files = changes.iter().filter(|item| item.newer).count();
+
+    if !dry_run && !force {
+        if let Some(date) = newer_entry {
+            return Err(JournalError::BackupFailed(format!(
+                "The journal has an entry for {}, newer than anything in this backup (latest {}). Pass --force to restore anyway",
+                date,
+                manifest
+                    .latest_entry
+                    .map(|d| d.to_string())
+                    .unwrap_or_else(|| "none".to_string())
+            )));
+        }
+        if newer_files > 0 {
+            return Err(JournalError::BackupFailed(format!(
+                "{} files changed after this backup was made and would be overwritten. Pass --force to restore anyway",
+                newer_files
+            )));
+        }
+    }
+
+    let safety_backup = if !dry_run && !changes.is_empty() {
+        let (path, _) = backup::create(config, &backup::default_dir(config), None)?;
+        backup::restore(&items, &files)?;
+        let mut index = Index::open(&config.index_path)?;
+        index.rebuild(&config.journal_dir, config.layout)?;
+        for item in &changes {
+            if let Some((date, _)) = filesystem::list_entries(&config.journal_dir, config.layout)?
+                .into_iter()
+                .find(|(_, path)| *path == item.target)
+            {
+                storage::push_for_date(config, date).await;
+            }
+        }
+        Some(path)
+    } else {
+        None
+    };
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "archive": archive,
+                "created": manifest.created,
+                "latest_entry": manifest.latest_entry,
+                "changes": changes,
+                "newer_entry": newer_entry,
+                "restored": safety_backup.is_some(),
+                "safety_backup": safety_backup,
+            })
+        );
+        return Ok(());
+    }
+
+    println!(
+        "Backup from {} ({} files, verified)",
+        manifest.created.format("%Y-%m-%d %H:%M"),
+        manifest.files.len()
+    );
+    if changes.is_empty() {
+        println!("✓ The journal already matches this backup");
+        return Ok(());
+    }
+    for item in &changes {
+        let action = match item.action {
+            RestoreAction::Create => "create",
+            RestoreAction::Overwrite if item.newer => "overwrite (changed since the backup)",
+            _ => "overwrite",
+        };
+        println!("  • {}: {}", item.path, action);
+    }
+    if let Some(date) = newer_entry {
+        println!(
+            "⚠ The journal has a newer entry ({}) than this backup",
+            date
+        );
+    }
+
+    match safety_backup {
+        Some(path) => {
+            println!("\n✓ Saved the previous state to {}", path.display());
+            println!("✓ Restored {} files", changes.len());
+        }
+        None => println!("\nDry run: nothing was changed"),
+    }
+    Ok(())
+}
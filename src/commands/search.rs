@@ -0,0 +1,35 @@
+use std::collections::BTreeSet;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::search;
+
+/// Print every line of the entries containing `text`, optionally only under `section`
+pub fn run(text: &str, section: Option<&str>, json: bool, config: &Config) -> Result<()> {
+    let matches = search::search(&config.journal_dir, config.layout, text, section)?;
+
+    if json {
+        println!("{:#}", serde_json::json!(matches));
+        return Ok(());
+    }
+
+    if matches.is_empty() {
+        match section {
+            Some(section) => println!("No entries contain \"{}\" under {}", text, section),
+            None => println!("No entries contain \"{}\"", text),
+        }
+        return Ok(());
+    }
+    for found in &matches {
+        println!(
+            "{}  {}:{}: {}",
+            found.date,
+            found.path.display(),
+            found.line,
+            found.text
+        );
+    }
+    let entries: BTreeSet<_> = matches.iter().map(|found| found.date).collect();
+    println!("\n{} matches in {} entries", matches.len(), entries.len());
+    Ok(())
+}
@@ -1,23 +1,29 @@
 use axum::{
     Json, Router,
-    extract::{Query, State},
-    http::StatusCode,
-    response::{Html, IntoResponse},
-    routing::{get, post},
+    extract::{Query, Request, State},
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Response},
+    routing::{delete, get, post},
 };
-use chrono::{Local, NaiveDate};
+use chrono::Local;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::sync::Arc;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{JournalError, Result};
 use crate::journal::entry::JournalEntry;
 use crate::journal::filesystem;
+use crate::journal::goals::{self, SectionGoal};
+use crate::journal::index::{EntryFilter, Index};
+use crate::journal::{capture, dates, discord, matrix, redact, scheduler, storage, telegram};
 
 #[derive(Clone)]
 struct AppState {
     config: Arc<Config>,
+    /// Only viewing, with private text redacted (`serve --read-only`)
+    read_only: bool,
 }
 
 #[derive(Deserialize)]
@@ -36,6 +42,51 @@ struct EntryResponse {
     date: String,
     content: String,
     exists: bool,
+    /// Progress toward the SECTION_GOALS writing goals
+    goals: Vec<SectionGoal>,
+}
+
+#[derive(Deserialize)]
+struct CaptureRequest {
+    text: String,
+}
+
+#[derive(Serialize)]
+struct CaptureResponse {
+    date: String,
+    text: String,
+}
+
+#[derive(Serialize)]
+struct TaskResponse {
+    text: String,
+    done: bool,
+}
+
+#[derive(Serialize)]
+struct TodayTasksResponse {
+    date: String,
+    tasks: Vec<TaskResponse>,
+    /// Texts of the unchecked tasks, for a "Choose from List" action
+    open: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct CompleteRequest {
+    task: String,
+}
+
+#[derive(Serialize)]
+struct CompleteResponse {
+    date: String,
+    completed: String,
+}
+
+#[derive(Deserialize)]
+struct EntriesQuery {
+    since: Option<String>,
+    until: Option<String>,
+    tag: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -43,19 +94,69 @@ struct ErrorResponse {
     error: String,
 }
 
-pub async fn run(config: &Config) -> Result<()> {
+/// Serve the web UI; with `daemon`, also create each day's entry on schedule.
+/// With `read_only`, entries can only be viewed and private text is redacted.
+pub async fn run(config: &Config, daemon: bool, read_only: bool) -> Result<()> {
     let state = AppState {
         config: Arc::new(config.clone()),
+        read_only,
     };
 
+    if read_only {
+        let app = Router::new()
+            .route("/", get(serve_index))
+            .route("/api/entry", get(get_entry))
+            .route("/api/entries", get(list_entries))
+            .with_state(state);
+        return listen(app, "👀 Read-only journal").await;
+    }
+
+    if daemon {
+        println!(
+            "⏰ Creating each day's entry at {}",
+            config.daemon.create_at.format("%H:%M")
+        );
+        tokio::spawn(scheduler::run_daily(config.clone()));
+    }
+    if config.telegram.bot_token.is_some() {
+        println!("💬 Capturing Telegram messages into today's entry");
+        tokio::spawn(telegram::run(config.clone()));
+    }
+    if let Some(room) = &config.matrix.room {
+        println!(
+            "💬 Capturing Matrix messages from {} into today's entry",
+            room
+        );
+        tokio::spawn(matrix::run(config.clone()));
+    }
+    if config.discord.bot_token.is_some() {
+        println!("💬 Capturing Discord DMs into today's entry");
+        tokio::spawn(discord::run(config.clone()));
+    }
+
+    // Small endpoints for Shortcuts/Tasker automations and deleting entries,
+    // behind API_TOKEN
+    let automation = Router::new()
+        .route("/api/entry", delete(delete_entry))
+        .route("/api/capture", post(capture_note))
+        .route("/api/today/tasks", get(today_tasks))
+        .route("/api/today/complete", post(complete_task))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token));
+
     let app = Router::new()
         .route("/", get(serve_index))
         .route("/api/entry", get(get_entry))
         .route("/api/entry", post(create_entry))
+        .route("/api/entries", get(list_entries))
+        .merge(automation)
         .with_state(state);
 
+    listen(app, "🚀 Journal web server").await
+}
+
+async fn listen(app: Router, name: &str) -> Result<()> {
     let addr = "0.0.0.0:3030";
-    println!("🚀 Journal web server starting on http://{}", addr);
+    println!("{} starting on http://{}", name, addr);
     println!("📱 Access from your phone at http://<your-local-ip>:3030");
     println!("Press Ctrl+C to stop the server");
 
@@ -65,9 +166,8 @@ pub async fn run(config: &Config) -> Result<()> {
     Ok(())
 }
 
-async fn serve_index() -> Html<String> {
-    Html(
-        r#"<!DOCTYPE html>
+async fn serve_index(State(state): State<AppState>) -> Html<String> {
+    let page = r#"<!DOCTYPE html>
 <html lang="en">
 <head>
     <meta charset="UTF-8">
@@ -323,6 +423,26 @@ async fn serve_index() -> Html<String> {
             display: flex;
         }
 
+        .goals {
+            display: flex;
+            flex-wrap: wrap;
+            gap: 8px;
+            margin-top: 15px;
+        }
+
+        .goal {
+            padding: 4px 12px;
+            border-radius: 12px;
+            font-size: 13px;
+            background: #fff3cd;
+            color: #856404;
+        }
+
+        .goal.met {
+            background: #d4edda;
+            color: #155724;
+        }
+
         .loading-content {
             background: white;
             padding: 40px;
@@ -387,6 +507,8 @@ async fn serve_index() -> Html<String> {
             <input type="date" id="date" value="">
         </div>
 
+        <div id="goals" class="goals"></div>
+
         <div class="editor-container">
             <div class="editor-pane">
                 <label for="content" class="pane-header">Editor</label>
@@ -401,6 +523,7 @@ async fn serve_index() -> Html<String> {
         <div class="button-group">
             <button class="btn-secondary" onclick="loadEntry()">Load Entry</button>
             <button class="btn-primary" onclick="saveEntry()">Save Entry</button>
+            <button class="btn-secondary" onclick="deleteEntry()">Delete Entry</button>
         </div>
 
         <div id="message" class="message"></div>
@@ -446,6 +569,18 @@ async fn serve_index() -> Html<String> {
             }, 5000);
         }
 
+        // Sections that still need attention, from SECTION_GOALS
+        function renderGoals(goals) {
+            const container = document.getElementById('goals');
+            container.replaceChildren(...(goals || []).map((goal) => {
+                const chip = document.createElement('span');
+                const met = goal.written >= goal.needed;
+                chip.className = met ? 'goal met' : 'goal';
+                chip.textContent = `${met ? '✓' : '○'} ${goal.section} ${goal.written}/${goal.needed}`;
+                return chip;
+            }));
+        }
+
         function showLoading() {
             document.getElementById('loadingModal').classList.add('show');
         }
@@ -467,6 +602,7 @@ async fn serve_index() -> Html<String> {
                 if (response.ok) {
                     contentTextarea.value = data.content;
                     updatePreview();
+                    renderGoals(data.goals);
                     if (data.exists) {
                         showMessage('Entry loaded successfully', 'success');
                     } else {
@@ -504,6 +640,7 @@ async fn serve_index() -> Html<String> {
                 const data = await response.json();
 
                 if (response.ok) {
+                    renderGoals(data.goals);
                     showMessage('Entry saved successfully! 🎉', 'success');
                 } else {
                     showMessage(`Error: ${data.error}`, 'error');
@@ -513,6 +650,41 @@ async fn serve_index() -> Html<String> {
             }
         }
 
+        async function deleteEntry() {
+            const date = dateInput.value;
+
+            if (!confirm(`Move the entry for ${date} to the trash?`)) {
+                return;
+            }
+
+            try {
+                // Deleting needs the server's API_TOKEN, asked for once
+                const remove = (token) => fetch(`/api/entry?date=${date}`, {
+                    method: 'DELETE',
+                    headers: token ? { 'Authorization': `Bearer ${token}` } : {}
+                });
+                let response = await remove(localStorage.getItem('apiToken'));
+                if (response.status === 401) {
+                    const token = prompt('API token (API_TOKEN) to delete entries:');
+                    if (!token) {
+                        return;
+                    }
+                    localStorage.setItem('apiToken', token);
+                    response = await remove(token);
+                }
+                const data = await response.json();
+
+                if (response.ok) {
+                    showMessage('Entry moved to trash (run "easy_journal undo" to restore)', 'success');
+                    loadEntry();
+                } else {
+                    showMessage(`Error: ${data.error}`, 'error');
+                }
+            } catch (error) {
+                showMessage(`Failed to delete entry: ${error.message}`, 'error');
+            }
+        }
+
         // Keyboard shortcut: Ctrl+S or Cmd+S to save
         document.addEventListener('keydown', (e) => {
             if ((e.ctrlKey || e.metaKey) && e.key === 's') {
@@ -522,8 +694,25 @@ async fn serve_index() -> Html<String> {
         });
     </script>
 </body>
-</html>"#
-            .to_string(),
+</html>"#;
+
+    if !state.read_only {
+        return Html(page.to_string());
+    }
+    Html(
+        page.replace(
+            r#"<textarea id="content" placeholder="Write your journal entry here...">"#,
+            r#"<textarea id="content" readonly>"#,
+        )
+        .replace(
+            r#"<button class="btn-primary" onclick="saveEntry()">Save Entry</button>"#,
+            "",
+        )
+        .replace(
+            r#"<button class="btn-secondary" onclick="deleteEntry()">Delete Entry</button>"#,
+            "",
+        )
+        .replace("Fetching Reminders...", "Loading..."),
     )
 }
 
@@ -532,7 +721,7 @@ async fn get_entry(
     Query(params): Query<DateQuery>,
 ) -> impl IntoResponse {
     let date = match params.date {
-        Some(date_str) => match NaiveDate::parse_from_str(&date_str, "%Y-%m-%d") {
+        Some(date_str) => match dates::parse(&date_str) {
             Ok(d) => d,
             Err(_) => {
                 return (
@@ -547,11 +736,21 @@ async fn get_entry(
         None => Local::now().date_naive(),
     };
 
-    let entry_path = filesystem::get_entry_path(date, &state.config.journal_dir);
+    storage::pull_for_date(&state.config, date).await;
+
+    let entry_path =
+        filesystem::get_entry_path(date, &state.config.journal_dir, state.config.layout);
     let exists = entry_path.exists();
+    if state.read_only && !exists {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            format!("No entry for {}", date.format("%Y-%m-%d")),
+        );
+    }
 
     let content = if exists {
         match fs::read_to_string(&entry_path) {
+            Ok(c) if state.read_only => redact::redact(&c, state.config.redact_strip),
             Ok(c) => c,
             Err(e) => {
                 return (
@@ -571,17 +770,15 @@ async fn get_entry(
                 let previous_content =
                     JournalEntry::get_previous_content(date, &state.config).unwrap_or_default();
 
-                // Fetch all reminders (Apple + Google) concurrently
-                let all_reminders = crate::journal::reminders::merge_all_reminders(&state.config)
-                    .await
-                    .unwrap_or(None);
-
-                crate::journal::template::apply_variables(
+                // Fetch the configured sections concurrently
+                crate::journal::sections::render_entry(
                     &template,
                     date,
                     previous_content,
-                    all_reminders,
+                    &state.config,
+                    &(),
                 )
+                .await
             }
             Err(e) => {
                 return (
@@ -599,6 +796,7 @@ async fn get_entry(
         StatusCode::OK,
         Json(EntryResponse {
             date: date.format("%Y-%m-%d").to_string(),
+            goals: goals::progress(&content, &state.config.section_goals),
             content,
             exists,
         }),
@@ -611,7 +809,7 @@ async fn create_entry(
     Json(payload): Json<CreateEntryRequest>,
 ) -> impl IntoResponse {
     let date = match payload.date {
-        Some(date_str) => match NaiveDate::parse_from_str(&date_str, "%Y-%m-%d") {
+        Some(date_str) => match dates::parse(&date_str) {
             Ok(d) => d,
             Err(_) => {
                 return (
@@ -630,11 +828,13 @@ async fn create_entry(
     let year = date.format("%Y").to_string().parse::<u32>().unwrap();
     let month = date.format("%m").to_string().parse::<u32>().unwrap();
 
-    if let Err(e) = filesystem::ensure_year_dir(year, &state.config.journal_dir) {
+    if let Err(e) =
+        filesystem::ensure_entry_dir(date, &state.config.journal_dir, state.config.layout)
+    {
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
-                error: format!("Failed to create year directory: {}", e),
+                error: format!("Failed to create the entry directory: {}", e),
             }),
         )
             .into_response();
@@ -650,16 +850,6 @@ async fn create_entry(
             .into_response();
     }
 
-    if let Err(e) = filesystem::ensure_month_dir(year, month, &state.config.journal_dir) {
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("Failed to create month directory: {}", e),
-            }),
-        )
-            .into_response();
-    }
-
     if let Err(e) =
         filesystem::create_month_readme(year, month, &state.config.journal_dir, &state.config)
     {
@@ -672,10 +862,8 @@ async fn create_entry(
             .into_response();
     }
 
-    let entry_path = filesystem::get_entry_path(date, &state.config.journal_dir);
-
-    // Write the content
-    if let Err(e) = fs::write(&entry_path, &payload.content) {
+    // Write the content (previous versions go to history and the trash)
+    if let Err(e) = JournalEntry::save(date, &payload.content, &state.config) {
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
@@ -686,19 +874,222 @@ async fn create_entry(
     }
 
     // Update SUMMARY.md
-    let summary_path = state.config.journal_dir.join("SUMMARY.md");
-    if let Ok(mut summary) = crate::journal::summary::Summary::parse(&summary_path) {
-        summary.add_day_entry(date);
-        let _ = summary.write();
-    }
+    let _ = JournalEntry::add_to_summary(date, &state.config);
+
+    storage::push_for_date(&state.config, date).await;
 
     (
         StatusCode::OK,
         Json(EntryResponse {
             date: date.format("%Y-%m-%d").to_string(),
+            goals: goals::progress(&payload.content, &state.config.section_goals),
             content: payload.content,
             exists: true,
         }),
     )
         .into_response()
 }
+
+async fn delete_entry(
+    State(state): State<AppState>,
+    Query(params): Query<DateQuery>,
+) -> impl IntoResponse {
+    let Some(date) = params
+        .date
+        .and_then(|date_str| dates::parse(&date_str).ok())
+    else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "A valid date is required to delete an entry".to_string(),
+            }),
+        )
+            .into_response();
+    };
+
+    if !JournalEntry::exists(date, &state.config) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Entry not found".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    let result = JournalEntry::delete(date, &state.config);
+    if result.is_ok() {
+        storage::push_for_date(&state.config, date).await;
+    }
+
+    match result {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(EntryResponse {
+                date: date.format("%Y-%m-%d").to_string(),
+                content: String::new(),
+                exists: false,
+                goals: Vec::new(),
+            }),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to delete entry: {}", e),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+async fn list_entries(
+    State(state): State<AppState>,
+    Query(params): Query<EntriesQuery>,
+) -> impl IntoResponse {
+    let parse = |value: Option<String>| match value {
+        Some(date_str) => dates::parse(&date_str).map(Some).map_err(|_| ()),
+        None => Ok(None),
+    };
+
+    let (since, until) = match (parse(params.since), parse(params.until)) {
+        (Ok(since), Ok(until)) => (since, until),
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "Invalid date format".to_string(),
+                }),
+            )
+                .into_response();
+        }
+    };
+
+    let filter = EntryFilter {
+        since,
+        until,
+        tag: params.tag,
+    };
+
+    match Index::open(&state.config.index_path).and_then(|index| index.entries(&filter)) {
+        Ok(entries) => (StatusCode::OK, Json(entries)).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to query entry index: {}", e),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+fn error_response(status: StatusCode, error: impl Into<String>) -> Response {
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.into(),
+        }),
+    )
+        .into_response()
+}
+
+/// Compare in constant time so the token can't be guessed byte by byte
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Require `Authorization: Bearer <API_TOKEN>`
+async fn require_token(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let Some(expected) = state.config.api_token.as_deref() else {
+        return error_response(
+            StatusCode::FORBIDDEN,
+            "Set API_TOKEN to enable the automation endpoints",
+        );
+    };
+
+    let given = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match given {
+        Some(given) if tokens_match(given.trim(), expected) => next.run(request).await,
+        _ => error_response(StatusCode::UNAUTHORIZED, "Missing or wrong bearer token"),
+    }
+}
+
+fn journal_error_response(e: JournalError) -> Response {
+    let status = match e {
+        JournalError::InvalidArgument(_) => StatusCode::BAD_REQUEST,
+        JournalError::EntryNotFound(_) => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    error_response(status, e.to_string())
+}
+
+async fn capture_note(
+    State(state): State<AppState>,
+    Json(payload): Json<CaptureRequest>,
+) -> impl IntoResponse {
+    let today = Local::now().date_naive();
+    storage::pull_for_date(&state.config, today).await;
+
+    match capture::append_to_today(&state.config, &payload.text).await {
+        Ok((date, _)) => {
+            storage::push_for_date(&state.config, date).await;
+            Json(CaptureResponse {
+                date: date.format("%Y-%m-%d").to_string(),
+                text: payload.text.trim().to_string(),
+            })
+            .into_response()
+        }
+        Err(e) => journal_error_response(e),
+    }
+}
+
+async fn today_tasks(State(state): State<AppState>) -> impl IntoResponse {
+    let today = Local::now().date_naive();
+    storage::pull_for_date(&state.config, today).await;
+
+    match capture::today_tasks(&state.config) {
+        Ok(tasks) => Json(TodayTasksResponse {
+            date: today.format("%Y-%m-%d").to_string(),
+            open: tasks
+                .iter()
+                .filter(|(_, done)| !done)
+                .map(|(text, _)| text.clone())
+                .collect(),
+            tasks: tasks
+                .into_iter()
+                .map(|(text, done)| TaskResponse { text, done })
+                .collect(),
+        })
+        .into_response(),
+        Err(e) => journal_error_response(e),
+    }
+}
+
+async fn complete_task(
+    State(state): State<AppState>,
+    Json(payload): Json<CompleteRequest>,
+) -> impl IntoResponse {
+    let today = Local::now().date_naive();
+    storage::pull_for_date(&state.config, today).await;
+
+    match capture::complete_today(&state.config, &payload.task) {
+        Ok((date, task)) => {
+            storage::push_for_date(&state.config, date).await;
+            Json(CompleteResponse {
+                date: date.format("%Y-%m-%d").to_string(),
+                completed: task,
+            })
+            .into_response()
+        }
+        Err(e) => journal_error_response(e),
+    }
+}
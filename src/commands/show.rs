@@ -0,0 +1,47 @@
+use std::fs;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::{dates, filesystem, parser, storage, terminal};
+
+/// Print the entry for `date_str` (default: today), or one of its sections,
+/// optionally rendered for the terminal
+pub async fn run(
+    date_str: Option<String>,
+    section: Option<String>,
+    render: bool,
+    json: bool,
+    config: &Config,
+) -> Result<()> {
+    let date = dates::parse_or_today(date_str.as_deref())?;
+
+    // Refresh the local cache from remote storage (no-op for local journals)
+    storage::pull_for_date(config, date).await;
+
+    let path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+    if !path.exists() {
+        return Err(JournalError::EntryNotFound(date.to_string()));
+    }
+    let mut content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+    if let Some(section) = &section {
+        content = parser::extract_section(&content, section).ok_or_else(|| {
+            JournalError::InvalidArgument(format!(
+                "The entry for {} has no \"{}\" section, or it is empty",
+                date, section
+            ))
+        })?;
+        content.push('\n');
+    }
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "date": date, "path": path, "section": section, "content": content })
+        );
+    } else if render {
+        print!("{}", terminal::render(&content));
+    } else {
+        print!("{}", content);
+    }
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use chrono::Local;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::stats::{self, Trend};
+
+/// `6.8 average over 30 entries, recently 7.4 (↑ +0.9 from the entries before)`
+fn describe(trend: &Trend) -> String {
+    let mut text = format!(
+        "{:.1} average over {} {}",
+        trend.average,
+        trend.entries,
+        if trend.entries == 1 {
+            "entry"
+        } else {
+            "entries"
+        }
+    );
+    if let Some(previous) = trend.previous {
+        let change = trend.recent - previous;
+        let arrow = if change > 0.05 {
+            "↑"
+        } else if change < -0.05 {
+            "↓"
+        } else {
+            "→"
+        };
+        text.push_str(&format!(
+            ", recently {:.1} ({} {:+.1} from the entries before)",
+            trend.recent, arrow, change
+        ));
+    }
+    text
+}
+
+/// Streaks, entries per month, word counts, task completion and mood/energy trends
+pub fn run(json: bool, config: &Config) -> Result<()> {
+    let stats = stats::collect(
+        &config.journal_dir,
+        config.layout,
+        Local::now().date_naive(),
+    )?;
+
+    if json {
+        let mut value = serde_json::json!(stats);
+        value["completion_rate"] = serde_json::json!(stats.completion_rate());
+        println!("{:#}", value);
+        return Ok(());
+    }
+
+    if stats.entries == 0 {
+        println!("No entries");
+        return Ok(());
+    }
+    println!("Entries:        {}", stats.entries);
+    println!(
+        "Streak:         {} days (longest {})",
+        stats.current_streak, stats.longest_streak
+    );
+    println!("Average words:  {:.0}", stats.average_words);
+    match stats.completion_rate() {
+        Some(rate) => println!(
+            "Tasks:          {} of {} done ({:.0}%)",
+            stats.tasks_done,
+            stats.tasks_done + stats.tasks_open,
+            rate * 100.0
+        ),
+        None => println!("Tasks:          none"),
+    }
+    for (name, trend) in [("Mood", &stats.mood), ("Energy", &stats.energy)] {
+        if let Some(trend) = trend {
+            println!("{:<16}{}", format!("{}:", name), describe(trend));
+        }
+    }
+
+    println!();
+    let cells: Vec<Vec<String>> = stats
+        .per_month
+        .iter()
+        .map(|month| vec![month.month.clone(), month.entries.to_string()])
+        .collect();
+    super::query::print_table(&["Month", "Entries"], &cells);
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::weekly_summary::{self, week_bounds};
+use crate::journal::{dates, llm, storage};
+
+pub async fn run(
+    date_str: Option<String>,
+    dry_run: bool,
+    json: bool,
+    config: &Config,
+) -> Result<()> {
+    let date = dates::parse_or_today(date_str.as_deref())?;
+    let (monday, sunday) = week_bounds(date);
+
+    storage::pull_week(config, date).await;
+    let Some((entries, prompt)) = weekly_summary::build_prompt(config, date)? else {
+        println!(
+            "No entries between {} and {}, nothing to summarize.",
+            monday, sunday
+        );
+        return Ok(());
+    };
+
+    // Show exactly what would leave the machine
+    if dry_run {
+        if json {
+            println!(
+                "{:#}",
+                serde_json::json!({
+                    "week": date.format("%G-W%V").to_string(),
+                    "entries": entries,
+                    "endpoint": llm::endpoint(&config.llm).ok(),
+                    "prompt": prompt,
+                })
+            );
+        } else {
+            print!("{}", prompt);
+        }
+        return Ok(());
+    }
+
+    let endpoint = llm::endpoint(&config.llm)?;
+    eprintln!(
+        "Sending {} {} to {} ({})...",
+        entries,
+        if entries == 1 { "entry" } else { "entries" },
+        endpoint,
+        config.llm.model
+    );
+    let (summary, path) = weekly_summary::summarize(config, date, entries, &prompt).await?;
+    storage::push_week(config, date).await;
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "week": date.format("%G-W%V").to_string(),
+                "entries": entries,
+                "summary": summary.summary,
+                "themes": summary.themes,
+                "path": path,
+            })
+        );
+    } else {
+        println!("{}", summary.summary);
+        if !summary.themes.is_empty() {
+            println!("\nThemes: {}", summary.themes.join(", "));
+        }
+        println!("\n✓ Written to {}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use chrono::Local;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::autocommit;
+
+/// Commit every change in the journal as `journal: <date>`, then pull with
+/// rebase and push when asked to (`--push` or GIT_SYNC_PUSH).
+///
+/// `auto` is for the post_save hook: the date comes from the hook's
+/// JOURNAL_DATE and nothing is printed when it succeeds.
+pub fn run(push: bool, auto: bool, json: bool, config: &Config) -> Result<()> {
+    let date = std::env::var("JOURNAL_DATE")
+        .ok()
+        .filter(|_| auto)
+        .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
+    let message = format!("journal: {}", date);
+    let commit = autocommit::commit_all(&config.journal_dir, &message)?;
+
+    let push = push || config.sync.push;
+    let branch = if push {
+        let branch = match &config.sync.branch {
+            Some(branch) => branch.clone(),
+            None => autocommit::current_branch(&config.journal_dir)?,
+        };
+        autocommit::pull_and_push(&config.journal_dir, &config.sync.remote, &branch)?;
+        Some(branch)
+    } else {
+        None
+    };
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "commit": commit.map(|id| id.to_string()),
+                "message": message,
+                "pushed": branch.is_some(),
+                "remote": branch.as_ref().map(|_| &config.sync.remote),
+                "branch": branch,
+            })
+        );
+        return Ok(());
+    }
+    if auto {
+        return Ok(());
+    }
+
+    match commit {
+        Some(id) => println!("✓ Committed {} ({})", message, &id.to_string()[..8]),
+        None => println!("✓ Nothing to commit"),
+    }
+    if let Some(branch) = branch {
+        println!("✓ Pushed {} to {}", branch, config.sync.remote);
+    }
+    Ok(())
+}
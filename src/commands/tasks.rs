@@ -0,0 +1,52 @@
+use chrono::{Duration, Local};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::{digest, tasks};
+
+/// List the tasks left unchecked in the last `last` days, grouped by the entry
+/// and section they were last written in
+pub fn run(last: u32, json: bool, config: &Config) -> Result<()> {
+    let today = Local::now().date_naive();
+    let since = today - Duration::days(i64::from(last.max(1)) - 1);
+    let entries = digest::read_entries(&config.journal_dir, config.layout, since, today)?;
+    let open = tasks::open_tasks(&entries);
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({ "since": since, "until": today, "tasks": open })
+        );
+        return Ok(());
+    }
+
+    if open.is_empty() {
+        println!("No open tasks in the last {} days", last);
+        return Ok(());
+    }
+    let mut group = None;
+    for task in &open {
+        if group != Some((task.date, &task.section)) {
+            if group.is_some() {
+                println!();
+            }
+            println!(
+                "{} · {}",
+                task.date.format("%a %Y-%m-%d"),
+                if task.section.is_empty() {
+                    "(no section)"
+                } else {
+                    &task.section
+                }
+            );
+            group = Some((task.date, &task.section));
+        }
+        if task.since < task.date {
+            println!("  - [ ] {} (since {})", task.text, task.since);
+        } else {
+            println!("  - [ ] {}", task.text);
+        }
+    }
+    println!("\n{} open tasks in the last {} days", open.len(), last);
+    Ok(())
+}
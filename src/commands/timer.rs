@@ -0,0 +1,116 @@
+use chrono::Local;
+use std::fs;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::entry::JournalEntry;
+use crate::journal::parser::format_duration;
+use crate::journal::storage;
+use crate::journal::timer::{self, Session};
+
+/// Start timing `description`
+pub fn start(description: String, json: bool, config: &Config) -> Result<()> {
+    if let Some(running) = timer::load(&config.timer_path) {
+        return Err(JournalError::InvalidArgument(format!(
+            "\"{}\" has been running since {}; run `easy_journal timer stop` first",
+            running.description,
+            running.started.format("%H:%M")
+        )));
+    }
+    let description = description.trim().to_string();
+    if description.is_empty() {
+        return Err(JournalError::InvalidArgument(
+            "Describe what you're working on".to_string(),
+        ));
+    }
+
+    let session = Session {
+        description,
+        started: Local::now(),
+    };
+    timer::save(&config.timer_path, &session)?;
+
+    if json {
+        println!("{:#}", serde_json::json!(session));
+    } else {
+        println!(
+            "⏱ Started \"{}\" at {}",
+            session.description,
+            session.started.format("%H:%M")
+        );
+    }
+    Ok(())
+}
+
+/// Stop the running session and log it into the entry of the day it started
+pub async fn stop(json: bool, config: &Config) -> Result<()> {
+    let Some(session) = timer::load(&config.timer_path) else {
+        return Err(JournalError::InvalidArgument(
+            "No timer is running".to_string(),
+        ));
+    };
+    let stopped = Local::now();
+    let date = session.started.date_naive();
+
+    storage::pull_for_date(config, date).await;
+    let entry = JournalEntry::create(date, config).await?;
+    let content =
+        fs::read_to_string(&entry.file_path).map_err(JournalError::at_path(&entry.file_path))?;
+    let updated = timer::record(&content, &session, stopped);
+    let path = JournalEntry::save(date, &updated, config)?;
+    storage::push_for_date(config, date).await;
+    timer::clear(&config.timer_path)?;
+
+    let minutes = session.minutes(stopped);
+    let total = timer::logged_minutes(&updated);
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "description": session.description,
+                "started": session.started,
+                "stopped": stopped,
+                "minutes": minutes,
+                "total_minutes": total,
+                "path": path,
+            })
+        );
+    } else {
+        println!(
+            "✓ Logged \"{}\" ({}) to {}",
+            session.description,
+            format_duration(minutes),
+            path.display()
+        );
+        println!("  {} in the time log of {}", format_duration(total), date);
+    }
+    Ok(())
+}
+
+/// Show the running session
+pub fn status(json: bool, config: &Config) -> Result<()> {
+    let session = timer::load(&config.timer_path);
+    let now = Local::now();
+
+    if json {
+        println!(
+            "{:#}",
+            serde_json::json!({
+                "running": session,
+                "minutes": session.as_ref().map(|session| session.minutes(now)),
+            })
+        );
+        return Ok(());
+    }
+
+    match session {
+        Some(session) => println!(
+            "⏱ \"{}\" running since {} ({})",
+            session.description,
+            session.started.format("%H:%M"),
+            format_duration(session.minutes(now))
+        ),
+        None => println!("No timer is running."),
+    }
+    Ok(())
+}
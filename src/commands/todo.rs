@@ -0,0 +1,35 @@
+use chrono::Local;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::{capture, storage};
+
+/// Add a task to today's "Goals for Today", creating the entry if needed
+pub async fn add(text: &str, json: bool, config: &Config) -> Result<()> {
+    let today = Local::now().date_naive();
+    storage::pull_for_date(config, today).await;
+    let (date, task) = capture::add_task_today(config, text).await?;
+    storage::push_for_date(config, date).await;
+
+    if json {
+        println!("{:#}", serde_json::json!({ "date": date, "task": task }));
+    } else {
+        println!("✓ Added to {}: - [ ] {}", date.format("%Y-%m-%d"), task);
+    }
+    Ok(())
+}
+
+/// Check off the task of today's entry matching `pattern`
+pub async fn done(pattern: &str, json: bool, config: &Config) -> Result<()> {
+    let today = Local::now().date_naive();
+    storage::pull_for_date(config, today).await;
+    let (date, task) = capture::complete_today(config, pattern)?;
+    storage::push_for_date(config, date).await;
+
+    if json {
+        println!("{:#}", serde_json::json!({ "date": date, "task": task }));
+    } else {
+        println!("✓ Checked off in {}: {}", date.format("%Y-%m-%d"), task);
+    }
+    Ok(())
+}
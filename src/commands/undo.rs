@@ -0,0 +1,58 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::entry::JournalEntry;
+use crate::journal::lock::WriteLock;
+use crate::journal::trash::{self, OperationKind};
+use crate::journal::{autocommit, index, storage};
+
+pub async fn run(json: bool, config: &Config) -> Result<()> {
+    let record = {
+        let _lock = WriteLock::acquire(&config.lock_path)?;
+        let record = trash::undo_last(&config.trash_dir)?;
+        if let Some(record) = &record {
+            autocommit::record(
+                config,
+                &[&record.original_path],
+                &format!(
+                    "Undo {} of {}",
+                    record.kind.describe(),
+                    record.original_path.display()
+                ),
+            );
+        }
+        record
+    };
+
+    let Some(record) = record else {
+        if json {
+            println!("{:#}", serde_json::json!({ "undone": null }));
+        } else {
+            println!("Nothing to undo.");
+        }
+        return Ok(());
+    };
+
+    if let Some(date) = record.date {
+        let content = std::fs::read_to_string(&record.original_path)?;
+        index::record_entry(config, date, &record.original_path, &content);
+
+        if record.kind == OperationKind::Delete {
+            JournalEntry::add_to_summary(date, config)?;
+        }
+        storage::push_for_date(config, date).await;
+    }
+
+    if json {
+        println!("{:#}", serde_json::json!({ "undone": record }));
+        return Ok(());
+    }
+
+    println!(
+        "↩  Undid {} of {} (recorded {})",
+        record.kind.describe(),
+        record.original_path.display(),
+        record.recorded_at
+    );
+
+    Ok(())
+}
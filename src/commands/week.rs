@@ -0,0 +1,32 @@
+use crate::commands::new::open_in_editor;
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::{dates, storage, weekly_review};
+
+/// Create (or open) the weekly review for the week containing `date_str` and
+/// open it in the editor; with `json`, print its path instead
+pub async fn run(date_str: Option<String>, json: bool, config: &Config) -> Result<()> {
+    let date = dates::parse_or_today(date_str.as_deref())?;
+    let week = date.format("%G-W%V").to_string();
+
+    storage::pull_week(config, date).await;
+    let (path, created) = weekly_review::create(config, date)?;
+
+    if json {
+        storage::push_week(config, date).await;
+        println!(
+            "{:#}",
+            serde_json::json!({ "week": week, "path": path, "created": created })
+        );
+        return Ok(());
+    }
+
+    if created {
+        println!("Created the review of week {}", week);
+    } else {
+        println!("Opening the review of week {}", week);
+    }
+    open_in_editor(&path.to_string_lossy())?;
+    storage::push_week(config, date).await;
+    Ok(())
+}
@@ -1,15 +1,397 @@
+use chrono::NaiveTime;
+use std::collections::HashMap;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::Layout;
+use crate::journal::goals;
 
 #[derive(Clone)]
 pub struct Config {
+    /// The `[profiles.<name>]` the settings came from (`--profile`, EASY_JOURNAL_PROFILE)
+    pub profile: Option<String>,
+    /// Directory holding book.toml, the templates and `.easy_journal/` (JOURNAL_HOME,
+    /// default the current directory)
+    pub root: PathBuf,
+    /// How entries are arranged in `journal_dir` (JOURNAL_LAYOUT: nested, flat or weekly)
+    pub layout: Layout,
+    /// Entries, under `root` unless absolute (JOURNAL_DIR, default "journal")
     pub journal_dir: PathBuf,
     pub template_path: PathBuf,
     pub month_template_path: PathBuf,
     pub year_template_path: PathBuf,
+    pub week_template_path: PathBuf,
+    pub index_path: PathBuf,
+    pub history_dir: PathBuf,
+    pub trash_dir: PathBuf,
+    pub lock_path: PathBuf,
+    /// When each section last fetched successfully (shown by `auth status`)
+    pub last_fetch_path: PathBuf,
+    /// Tasks deferred while reviewing rollover (`new --review`)
+    pub deferred_path: PathBuf,
+    /// The running `timer` session
+    pub timer_path: PathBuf,
+    /// Journal layout version written by `migrate`
+    pub layout_version_path: PathBuf,
+    /// Commit every entry write to the journal's git repository
+    pub git_autocommit: bool,
+    /// Bearer token for the automation endpoints of `serve` (API_TOKEN); they are off without it
+    pub api_token: Option<String>,
+    /// Link each new entry to the previous and next ones at its bottom (NAV_LINKS)
+    pub nav_links: bool,
+    /// Drop private text from exports, digests and `serve --read-only` instead of
+    /// masking it with ▓▓▓ (REDACT_STRIP, see `journal/redact.rs`)
+    pub redact_strip: bool,
     pub google_oauth: GoogleOAuthConfig,
     pub github_config: GitHubConfig,
     pub gitlab_config: GitLabConfig,
+    pub storage: StorageConfig,
+    /// Generated sections in order (JOURNAL_SECTIONS, comma separated)
+    pub sections: Vec<String>,
+    /// Items each section of today's entry should have, checked by `lint` and
+    /// shown in the web UI (SECTION_GOALS, `Gratitude=3,Learning & Insights=1`)
+    pub section_goals: Vec<(String, usize)>,
+    /// Shell commands for `custom:<name>` sections (SECTION_<NAME>_COMMAND)
+    pub custom_sections: HashMap<String, String>,
+    pub weather: WeatherConfig,
+    pub fitbit: FitbitConfig,
+    pub apple_health: AppleHealthConfig,
+    pub oura: OuraConfig,
+    pub strava: StravaConfig,
+    pub wakatime: WakaTimeConfig,
+    pub toggl: TogglConfig,
+    pub lastfm: LastFmConfig,
+    pub read_later: ReadLaterConfig,
+    pub rss: RssConfig,
+    pub mastodon: MastodonConfig,
+    pub imap: ImapConfig,
+    pub oncall: OnCallConfig,
+    pub sentry: SentryConfig,
+    pub confluence: ConfluenceConfig,
+    pub hooks: HooksConfig,
+    pub daemon: DaemonConfig,
+    pub email: EmailConfig,
+    pub telegram: TelegramConfig,
+    pub matrix: MatrixConfig,
+    pub discord: DiscordConfig,
+    pub dictate: DictateConfig,
+    pub llm: LlmConfig,
+    pub proof: ProofConfig,
+    pub backup: BackupConfig,
+    pub publish: PublishConfig,
+    pub sync: SyncConfig,
+}
+
+/// Archives written by `easy_journal backup` (see `journal/backup.rs`)
+#[derive(Clone)]
+pub struct BackupConfig {
+    /// Where archives go without `--to` (BACKUP_DIR, default `.easy_journal/backups`)
+    pub dir: Option<PathBuf>,
+    /// Archives kept in that directory; older ones are deleted (BACKUP_KEEP, default 10)
+    pub keep: usize,
+    /// Passphrase for `backup --encrypt` and restoring encrypted archives
+    /// (BACKUP_PASSPHRASE); asked for on the terminal when unset
+    pub passphrase: Option<String>,
+}
+
+/// Where `easy_journal publish` deploys the book (see `journal/publish.rs`)
+#[derive(Clone)]
+pub struct PublishConfig {
+    /// `gh-pages[:<remote>]`, `rsync:<dest>`, `scp:<dest>` or a local path (PUBLISH_TARGET)
+    pub target: Option<String>,
+    /// Branch a `gh-pages` target pushes to (PUBLISH_BRANCH, default `gh-pages`)
+    pub branch: String,
+}
+
+/// Where `easy_journal sync` pushes the journal's git repository
+#[derive(Clone)]
+pub struct SyncConfig {
+    /// Remote to pull from and push to (GIT_REMOTE, default `origin`)
+    pub remote: String,
+    /// Branch to pull and push (GIT_BRANCH, default the checked-out branch)
+    pub branch: Option<String>,
+    /// Pull and push on every sync, not only with `--push` (GIT_SYNC_PUSH)
+    pub push: bool,
+}
+
+/// Spelling and style checks for `easy_journal proof` (see `journal/proof.rs`)
+#[derive(Clone)]
+pub struct ProofConfig {
+    /// Hunspell `.dic` file, with the `.aff` next to it (PROOF_DICTIONARY);
+    /// searched for by language when unset
+    pub dictionary: Option<PathBuf>,
+    /// Dictionary name to search for, e.g. en_GB (PROOF_LANGUAGE, default en_US)
+    pub language: String,
+    /// Extra accepted words, one per line (PROOF_WORDS)
+    pub words: Option<PathBuf>,
+    /// Sentences with more words are flagged (PROOF_MAX_SENTENCE_WORDS, default 40)
+    pub max_sentence_words: usize,
+}
+
+/// OpenAI-compatible chat endpoint for `easy_journal summarize` (see `journal/llm.rs`).
+/// Nothing is sent anywhere unless LLM_API_URL is set.
+#[derive(Clone)]
+pub struct LlmConfig {
+    /// Base URL, e.g. https://api.openai.com/v1 or http://localhost:8080/v1 for a
+    /// local llama.cpp server (LLM_API_URL)
+    pub api_url: Option<String>,
+    /// Bearer token; local servers usually don't need one (LLM_API_KEY)
+    pub api_key: Option<String>,
+    /// Model name (LLM_MODEL, default "gpt-4o-mini")
+    pub model: String,
+}
+
+/// Local speech-to-text for `easy_journal dictate` (see `journal/dictate.rs`)
+#[derive(Clone)]
+pub struct DictateConfig {
+    /// "whisper-cpp" runs the whisper.cpp binary (default), "whisper-rs" transcribes
+    /// in-process when built with that feature (DICTATE_BACKEND)
+    pub backend: String,
+    /// whisper.cpp command line tool (WHISPER_CPP_BIN, default "whisper-cli")
+    pub whisper_binary: String,
+    /// ggml model file, e.g. ggml-base.en.bin (WHISPER_MODEL)
+    pub model: Option<PathBuf>,
+    /// Spoken language code; auto-detected when unset (WHISPER_LANGUAGE)
+    pub language: Option<String>,
+}
+
+/// Bot that captures DMs into today's entry (see `journal/discord.rs`)
+#[derive(Clone, Default)]
+pub struct DiscordConfig {
+    /// Bot token from the Discord developer portal (DISCORD_BOT_TOKEN); the bot runs when it is set
+    pub bot_token: Option<String>,
+    /// The only user whose DMs are accepted (DISCORD_USER_ID)
+    pub user_id: Option<String>,
+}
+
+/// Matrix account that captures messages from a private room (see `journal/matrix.rs`)
+#[derive(Clone, Default)]
+pub struct MatrixConfig {
+    /// e.g. https://matrix.org (MATRIX_HOMESERVER)
+    pub homeserver: Option<String>,
+    /// Access token of the bot account (MATRIX_ACCESS_TOKEN)
+    pub access_token: Option<String>,
+    /// Room ID or alias to join (MATRIX_ROOM); the bot runs when it is set
+    pub room: Option<String>,
+    /// Your Matrix ID, e.g. @me:matrix.org (MATRIX_USER_ID); messages from
+    /// anyone else in the room are ignored
+    pub user_id: Option<String>,
+}
+
+/// Bot that captures messages into today's entry (see `journal/telegram.rs`)
+#[derive(Clone, Default)]
+pub struct TelegramConfig {
+    /// Token from @BotFather (TELEGRAM_BOT_TOKEN); the bot runs when it is set
+    pub bot_token: Option<String>,
+    /// The only chat whose messages are accepted (TELEGRAM_CHAT_ID)
+    pub chat_id: Option<i64>,
+}
+
+/// SMTP settings for `digest --email`
+#[derive(Clone)]
+pub struct EmailConfig {
+    pub smtp_host: Option<String>,
+    /// 465 uses implicit TLS, other ports STARTTLS (SMTP_PORT, default 587)
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    /// Sender address (DIGEST_EMAIL_FROM, defaults to the recipient)
+    pub from: Option<String>,
+    /// Recipient address (DIGEST_EMAIL_TO)
+    pub to: Option<String>,
+}
+
+/// Schedule for `easy_journal daemon` / `serve --daemon`
+#[derive(Clone)]
+pub struct DaemonConfig {
+    /// Local time to create each day's entry (DAEMON_CREATE_AT, "HH:MM", default 06:00)
+    pub create_at: NaiveTime,
+    /// Also rebuild the entry index after creating the entry (DAEMON_PREWARM)
+    pub prewarm: bool,
+    /// Send a desktop notification at this time if today's entry is still
+    /// missing or untouched (REMIND_AT, "HH:MM", off by default)
+    pub remind_at: Option<NaiveTime>,
+}
+
+/// Shell commands run on entry lifecycle events (see `journal/hooks.rs`)
+#[derive(Clone, Default)]
+pub struct HooksConfig {
+    /// Before a new entry is created; a failing hook aborts the creation
+    pub pre_new: Option<String>,
+    /// After a new entry has been created
+    pub post_new: Option<String>,
+    /// After an entry has been saved (web editor, restore, resolve, import, editor closed)
+    pub post_save: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct WeatherConfig {
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// Activities from Strava for the `strava` section
+#[derive(Clone)]
+pub struct StravaConfig {
+    /// API application from strava.com/settings/api (STRAVA_CLIENT_ID, STRAVA_CLIENT_SECRET)
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    /// Local port of the OAuth redirect, http://127.0.0.1:<port>/callback (STRAVA_REDIRECT_PORT)
+    pub redirect_port: u16,
+    pub token_storage_path: PathBuf,
+    /// TOKEN_PASSPHRASE, shared with the other token stores
+    pub token_passphrase: Option<String>,
+}
+
+/// Feeds for the `rss` section
+#[derive(Clone)]
+pub struct RssConfig {
+    /// Feed URLs, comma separated (RSS_FEEDS)
+    pub feeds: Vec<String>,
+    /// Items listed per entry (RSS_MAX_ITEMS, default 10)
+    pub max_items: usize,
+    /// Items listed per feed (RSS_PER_FEED, default 3)
+    pub per_feed: usize,
+    /// Items already listed in an entry, so they don't repeat the next day
+    pub seen_path: PathBuf,
+}
+
+/// Watched pages for the `confluence` section
+#[derive(Clone)]
+pub struct ConfluenceConfig {
+    /// e.g. https://acme.atlassian.net/wiki (CONFLUENCE_URL)
+    pub url: Option<String>,
+    /// Atlassian account email; leave unset to send the token as a Data Center
+    /// personal access token (CONFLUENCE_EMAIL)
+    pub email: Option<String>,
+    /// CONFLUENCE_API_TOKEN
+    pub api_token: Option<String>,
+}
+
+/// New and regressed issues for the `sentry` section
+#[derive(Clone)]
+pub struct SentryConfig {
+    /// Personal token with the event:read scope (SENTRY_AUTH_TOKEN)
+    pub auth_token: Option<String>,
+    /// Organization slug (SENTRY_ORG)
+    pub org: Option<String>,
+    /// Project slugs; all their issues instead of those assigned to you
+    /// (SENTRY_PROJECTS, comma separated)
+    pub projects: Vec<String>,
+    /// SENTRY_URL, default https://sentry.io (self-hosted instances)
+    pub url: String,
+}
+
+/// PagerDuty and/or Opsgenie for the `oncall` section
+#[derive(Clone)]
+pub struct OnCallConfig {
+    /// User API token, not an account key (PAGERDUTY_TOKEN)
+    pub pagerduty_token: Option<String>,
+    /// Incidents of these teams instead of only yours (PAGERDUTY_TEAM_IDS, comma separated)
+    pub pagerduty_team_ids: Vec<String>,
+    /// OPSGENIE_API_KEY
+    pub opsgenie_api_key: Option<String>,
+    /// Your Opsgenie login, matched against schedule rotations (OPSGENIE_USER)
+    pub opsgenie_user: Option<String>,
+    /// OPSGENIE_API_URL, default https://api.opsgenie.com (EU: https://api.eu.opsgenie.com)
+    pub opsgenie_api_url: String,
+    /// e.g. https://acme.app.opsgenie.com, used to link alerts (OPSGENIE_APP_URL)
+    pub opsgenie_app_url: Option<String>,
+}
+
+/// Mailbox for the `imap` section (flagged messages)
+#[derive(Clone)]
+pub struct ImapConfig {
+    /// Server with implicit TLS (IMAP_HOST)
+    pub host: Option<String>,
+    /// IMAP_PORT, default 993
+    pub port: u16,
+    pub username: Option<String>,
+    /// Usually an app password (IMAP_PASSWORD)
+    pub password: Option<String>,
+    /// Mailbox searched for flagged messages (IMAP_MAILBOX, default INBOX)
+    pub mailbox: String,
+    /// Messages listed, newest first (IMAP_MAX_ITEMS, default 20)
+    pub max_items: usize,
+    /// Link for each message, `{id}` is the Message-ID (IMAP_MESSAGE_URL,
+    /// default `message:%3C{id}%3E` for Apple Mail)
+    pub message_url: String,
+}
+
+/// Your own posts for the `mastodon` section
+#[derive(Clone)]
+pub struct MastodonConfig {
+    /// Instance URL, e.g. https://mastodon.social (MASTODON_INSTANCE)
+    pub instance: Option<String>,
+    /// Token with the read:accounts and read:statuses scopes (MASTODON_ACCESS_TOKEN)
+    pub access_token: Option<String>,
+}
+
+/// Read-later queue from Readwise Reader for the `read_later` section
+#[derive(Clone)]
+pub struct ReadLaterConfig {
+    /// Access token from readwise.io/access_token (READWISE_TOKEN)
+    pub readwise_token: Option<String>,
+    /// How many of the oldest unread items to list (READ_LATER_OLDEST, default 3)
+    pub oldest: usize,
+}
+
+/// Listening history from Last.fm for the `lastfm` section
+#[derive(Clone)]
+pub struct LastFmConfig {
+    /// API key from last.fm/api/account/create (LASTFM_API_KEY)
+    pub api_key: Option<String>,
+    /// Last.fm user name whose scrobbles are listed (LASTFM_USER)
+    pub user: Option<String>,
+}
+
+/// Tracked time from Toggl Track for the `toggl` section
+#[derive(Clone)]
+pub struct TogglConfig {
+    /// API token from the Toggl profile page (TOGGL_API_TOKEN)
+    pub api_token: Option<String>,
+}
+
+/// Coding time from WakaTime (or a compatible server such as Wakapi) for the
+/// `wakatime` section
+#[derive(Clone)]
+pub struct WakaTimeConfig {
+    /// WAKATIME_API_KEY
+    pub api_key: Option<String>,
+    /// WAKATIME_API_URL, default https://wakatime.com/api/v1
+    pub api_url: String,
+}
+
+/// Readiness and sleep from Oura for the `oura` section
+#[derive(Clone)]
+pub struct OuraConfig {
+    /// Personal access token from cloud.ouraring.com (OURA_TOKEN)
+    pub token: Option<String>,
+}
+
+/// Sleep from Apple Health for the `apple_health` section (macOS only)
+#[derive(Clone)]
+pub struct AppleHealthConfig {
+    /// Sleep samples exported by a Shortcut or companion app (JSON), or the
+    /// Health app's `export.xml` (HEALTH_SLEEP_FILE, default
+    /// `easy_journal_sleep.json` in the Shortcuts iCloud folder)
+    pub sleep_file: PathBuf,
+}
+
+/// Sleep and activity from Fitbit for the `fitbit` section
+#[derive(Clone)]
+pub struct FitbitConfig {
+    /// Application registered at dev.fitbit.com (FITBIT_CLIENT_ID, FITBIT_CLIENT_SECRET
+    /// only for "Server" applications)
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    /// Local port of the OAuth redirect, http://127.0.0.1:<port>/callback (FITBIT_REDIRECT_PORT)
+    pub redirect_port: u16,
+    pub token_storage_path: PathBuf,
+    /// TOKEN_PASSPHRASE, shared with the other token stores
+    pub token_passphrase: Option<String>,
 }
 
 #[derive(Clone)]
@@ -17,51 +399,1233 @@ pub struct GoogleOAuthConfig {
     pub client_id: Option<String>,
     pub client_secret: Option<String>,
     pub token_storage_path: PathBuf,
+    /// TOKEN_PASSPHRASE; the token file key comes from the OS keyring when unset
+    pub token_passphrase: Option<String>,
+    /// Start the sign-in flow during a fetch when the refresh token has expired
+    /// and a terminal is attached (GOOGLE_REAUTH_INLINE, default on)
+    pub reauth_inline: bool,
 }
 
 #[derive(Clone)]
 pub struct GitHubConfig {
+    /// GITHUB_TOKEN; takes precedence over the token stored by `auth github`
     pub token: Option<String>,
     pub enabled: bool,
+    /// OAuth app used by `auth github` (GITHUB_CLIENT_ID, or baked in at build
+    /// time via EASY_JOURNAL_GITHUB_CLIENT_ID)
+    pub client_id: Option<String>,
+    pub token_storage_path: PathBuf,
+    /// TOKEN_PASSPHRASE, shared with the Google token store
+    pub token_passphrase: Option<String>,
+    /// Repositories checked by the `github_ci` section, `owner/repo` comma
+    /// separated (GITHUB_CI_REPOS, default: your 30 most recently pushed)
+    pub ci_repos: Vec<String>,
 }
 
 #[derive(Clone)]
 pub struct GitLabConfig {
+    /// GITLAB_TOKEN; takes precedence over the token stored by `auth gitlab`
     pub token: Option<String>,
     pub host: String,
     pub enabled: bool,
+    /// Application used by `auth gitlab` (GITLAB_CLIENT_ID, GITLAB_CLIENT_SECRET
+    /// only for confidential applications)
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    /// Local port of the OAuth redirect, http://127.0.0.1:<port>/callback (GITLAB_REDIRECT_PORT)
+    pub redirect_port: u16,
+    pub token_storage_path: PathBuf,
+    /// TOKEN_PASSPHRASE, shared with the other token stores
+    pub token_passphrase: Option<String>,
+    /// Look up MR pipelines and failed pipelines on your branches
+    /// (GITLAB_PIPELINES, default true)
+    pub pipelines: bool,
+}
+
+/// Where the journal lives; `journal_dir` acts as the local cache for remote backends
+#[derive(Clone)]
+pub struct StorageConfig {
+    /// "local" (default), "webdav" or "s3"
+    pub backend: String,
+    pub webdav: WebDavConfig,
+    pub s3: S3Config,
+}
+
+#[derive(Clone)]
+pub struct WebDavConfig {
+    /// Collection URL that holds the journal, e.g. https://cloud.example.com/remote.php/dav/files/me/journal
+    pub url: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct S3Config {
+    pub endpoint: String,
+    pub bucket: Option<String>,
+    pub region: String,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    /// Key prefix inside the bucket, e.g. "journal/"
+    pub prefix: String,
 }
 
 impl Default for Config {
     fn default() -> Self {
-        let home_dir = env::var("HOME").unwrap_or_else(|_| String::from("."));
-        let token_path = PathBuf::from(&home_dir).join(".easy_journal_tokens.json");
+        let home_dir = var("HOME").unwrap_or_else(|_| String::from("."));
+        let profile = var(PROFILE_VAR)
+            .ok()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty());
+        // Each profile signs in to its own accounts
+        let token_file = |name: &str| {
+            let file = match &profile {
+                Some(profile) => format!(".easy_journal_{}_{}", profile, name),
+                None => format!(".easy_journal_{}", name),
+            };
+            PathBuf::from(&home_dir).join(file)
+        };
+        let token_path = token_file("tokens.json");
+        let github_token_path = token_file("github_token.json");
+        let gitlab_token_path = token_file("gitlab_token.json");
+        let fitbit_token_path = token_file("fitbit_token.json");
+        let strava_token_path = token_file("strava_token.json");
+        let shortcuts_sleep_path = PathBuf::from(&home_dir).join(
+            "Library/Mobile Documents/iCloud~is~workflow~my~workflows/Documents/easy_journal_sleep.json",
+        );
+        let token_passphrase = var("TOKEN_PASSPHRASE").ok().filter(|p| !p.is_empty());
+        let root = var("JOURNAL_HOME")
+            .ok()
+            .filter(|r| !r.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        let journal_dir = var("JOURNAL_DIR")
+            .ok()
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("journal"));
 
-        Self {
+        let mut config = Self {
+            profile,
+            root: PathBuf::new(),
             journal_dir: PathBuf::from("journal"),
+            layout: var("JOURNAL_LAYOUT")
+                .ok()
+                .and_then(|layout| Layout::parse(&layout))
+                .unwrap_or_default(),
             template_path: PathBuf::from("template.md"),
             month_template_path: PathBuf::from("month_template.md"),
             year_template_path: PathBuf::from("year_template.md"),
+            week_template_path: PathBuf::from("week_template.md"),
+            index_path: PathBuf::from(".easy_journal/index.db"),
+            history_dir: PathBuf::from(".easy_journal/history"),
+            trash_dir: PathBuf::from(".trash"),
+            lock_path: PathBuf::from(".easy_journal/write.lock"),
+            last_fetch_path: PathBuf::from(".easy_journal/last_fetch.json"),
+            deferred_path: PathBuf::from(".easy_journal/deferred.json"),
+            timer_path: PathBuf::from(".easy_journal/timer.json"),
+            layout_version_path: PathBuf::from(".easy_journal/layout_version"),
+            api_token: var("API_TOKEN").ok().filter(|t| !t.trim().is_empty()),
+            git_autocommit: var("GIT_AUTOCOMMIT")
+                .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true")),
+            nav_links: var("NAV_LINKS").is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true")),
+            redact_strip: var("REDACT_STRIP")
+                .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true")),
             google_oauth: GoogleOAuthConfig {
-                client_id: env::var("GOOGLE_CLIENT_ID").ok(),
-                client_secret: env::var("GOOGLE_CLIENT_SECRET").ok(),
+                client_id: var("GOOGLE_CLIENT_ID").ok(),
+                client_secret: var("GOOGLE_CLIENT_SECRET").ok(),
                 token_storage_path: token_path,
+                token_passphrase: token_passphrase.clone(),
+                reauth_inline: !var("GOOGLE_REAUTH_INLINE")
+                    .is_ok_and(|v| v == "0" || v.eq_ignore_ascii_case("false")),
             },
             github_config: GitHubConfig {
-                token: env::var("GITHUB_TOKEN").ok(),
+                token: var("GITHUB_TOKEN").ok(),
                 enabled: false,
+                client_id: var("GITHUB_CLIENT_ID")
+                    .ok()
+                    .filter(|id| !id.is_empty())
+                    .or_else(|| option_env!("EASY_JOURNAL_GITHUB_CLIENT_ID").map(String::from)),
+                token_storage_path: github_token_path,
+                token_passphrase: token_passphrase.clone(),
+                ci_repos: var("GITHUB_CI_REPOS")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(|repo| repo.trim().to_string())
+                    .filter(|repo| !repo.is_empty())
+                    .collect(),
             },
             gitlab_config: GitLabConfig {
-                token: env::var("GITLAB_TOKEN").ok(),
-                host: env::var("GITLAB_HOST").unwrap_or_else(|_| "https://gitlab.com".to_string()),
+                token: var("GITLAB_TOKEN").ok(),
+                host: var("GITLAB_HOST").unwrap_or_else(|_| "https://gitlab.com".to_string()),
                 enabled: false,
+                client_id: var("GITLAB_CLIENT_ID").ok().filter(|id| !id.is_empty()),
+                client_secret: var("GITLAB_CLIENT_SECRET").ok().filter(|s| !s.is_empty()),
+                redirect_port: var("GITLAB_REDIRECT_PORT")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(8976),
+                token_storage_path: gitlab_token_path,
+                token_passphrase: token_passphrase.clone(),
+                pipelines: !var("GITLAB_PIPELINES")
+                    .is_ok_and(|v| v == "0" || v.eq_ignore_ascii_case("false")),
             },
-        }
+            storage: StorageConfig {
+                backend: var("STORAGE_BACKEND").unwrap_or_else(|_| "local".to_string()),
+                webdav: WebDavConfig {
+                    url: var("WEBDAV_URL").ok(),
+                    username: var("WEBDAV_USERNAME").ok(),
+                    password: var("WEBDAV_PASSWORD").ok(),
+                },
+                s3: S3Config {
+                    endpoint: var("S3_ENDPOINT")
+                        .unwrap_or_else(|_| "https://s3.amazonaws.com".to_string()),
+                    bucket: var("S3_BUCKET").ok(),
+                    region: var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+                    access_key_id: var("S3_ACCESS_KEY_ID").ok(),
+                    secret_access_key: var("S3_SECRET_ACCESS_KEY").ok(),
+                    prefix: var("S3_PREFIX").unwrap_or_default(),
+                },
+            },
+            sections: var("JOURNAL_SECTIONS")
+                .unwrap_or_else(|_| "reminders,google_tasks,github,gitlab".to_string())
+                .split(',')
+                .map(|s| s.trim().to_lowercase())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            section_goals: var("SECTION_GOALS")
+                .map(|spec| goals::parse(&spec))
+                .unwrap_or_default(),
+            custom_sections: vars()
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    let name = key.strip_prefix("SECTION_")?.strip_suffix("_COMMAND")?;
+                    Some((name.to_lowercase(), value))
+                })
+                .collect(),
+            weather: WeatherConfig {
+                latitude: var("WEATHER_LATITUDE").ok().and_then(|v| v.parse().ok()),
+                longitude: var("WEATHER_LONGITUDE").ok().and_then(|v| v.parse().ok()),
+            },
+            fitbit: FitbitConfig {
+                client_id: var("FITBIT_CLIENT_ID").ok().filter(|id| !id.is_empty()),
+                client_secret: var("FITBIT_CLIENT_SECRET").ok().filter(|s| !s.is_empty()),
+                redirect_port: var("FITBIT_REDIRECT_PORT")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(8977),
+                token_storage_path: fitbit_token_path,
+                token_passphrase: token_passphrase.clone(),
+            },
+            strava: StravaConfig {
+                client_id: var("STRAVA_CLIENT_ID").ok().filter(|id| !id.is_empty()),
+                client_secret: var("STRAVA_CLIENT_SECRET").ok().filter(|s| !s.is_empty()),
+                redirect_port: var("STRAVA_REDIRECT_PORT")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(8978),
+                token_storage_path: strava_token_path,
+                token_passphrase,
+            },
+            wakatime: WakaTimeConfig {
+                api_key: var("WAKATIME_API_KEY").ok().filter(|k| !k.is_empty()),
+                api_url: var("WAKATIME_API_URL")
+                    .ok()
+                    .filter(|u| !u.is_empty())
+                    .unwrap_or_else(|| "https://wakatime.com/api/v1".to_string()),
+            },
+            toggl: TogglConfig {
+                api_token: var("TOGGL_API_TOKEN").ok().filter(|t| !t.is_empty()),
+            },
+            lastfm: LastFmConfig {
+                api_key: var("LASTFM_API_KEY").ok().filter(|k| !k.is_empty()),
+                user: var("LASTFM_USER").ok().filter(|u| !u.is_empty()),
+            },
+            rss: RssConfig {
+                feeds: var("RSS_FEEDS")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(|url| url.trim().to_string())
+                    .filter(|url| !url.is_empty())
+                    .collect(),
+                max_items: var("RSS_MAX_ITEMS")
+                    .ok()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(10),
+                per_feed: var("RSS_PER_FEED")
+                    .ok()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(3),
+                seen_path: PathBuf::from(".easy_journal/rss_seen.json"),
+            },
+            mastodon: MastodonConfig {
+                instance: var("MASTODON_INSTANCE").ok().filter(|i| !i.is_empty()),
+                access_token: var("MASTODON_ACCESS_TOKEN").ok().filter(|t| !t.is_empty()),
+            },
+            confluence: ConfluenceConfig {
+                url: var("CONFLUENCE_URL").ok().filter(|u| !u.is_empty()),
+                email: var("CONFLUENCE_EMAIL").ok().filter(|e| !e.is_empty()),
+                api_token: var("CONFLUENCE_API_TOKEN").ok().filter(|t| !t.is_empty()),
+            },
+            sentry: SentryConfig {
+                auth_token: var("SENTRY_AUTH_TOKEN").ok().filter(|t| !t.is_empty()),
+                org: var("SENTRY_ORG").ok().filter(|o| !o.is_empty()),
+                projects: var("SENTRY_PROJECTS")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(|project| project.trim().to_string())
+                    .filter(|project| !project.is_empty())
+                    .collect(),
+                url: var("SENTRY_URL")
+                    .ok()
+                    .filter(|u| !u.is_empty())
+                    .unwrap_or_else(|| "https://sentry.io".to_string()),
+            },
+            oncall: OnCallConfig {
+                pagerduty_token: var("PAGERDUTY_TOKEN").ok().filter(|t| !t.is_empty()),
+                pagerduty_team_ids: var("PAGERDUTY_TEAM_IDS")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(|id| id.trim().to_string())
+                    .filter(|id| !id.is_empty())
+                    .collect(),
+                opsgenie_api_key: var("OPSGENIE_API_KEY").ok().filter(|k| !k.is_empty()),
+                opsgenie_user: var("OPSGENIE_USER").ok().filter(|u| !u.is_empty()),
+                opsgenie_api_url: var("OPSGENIE_API_URL")
+                    .ok()
+                    .filter(|u| !u.is_empty())
+                    .unwrap_or_else(|| "https://api.opsgenie.com".to_string()),
+                opsgenie_app_url: var("OPSGENIE_APP_URL").ok().filter(|u| !u.is_empty()),
+            },
+            imap: ImapConfig {
+                host: var("IMAP_HOST").ok().filter(|h| !h.is_empty()),
+                port: var("IMAP_PORT")
+                    .ok()
+                    .and_then(|v| v.trim().parse().ok())
+                    .unwrap_or(993),
+                username: var("IMAP_USERNAME").ok().filter(|u| !u.is_empty()),
+                password: var("IMAP_PASSWORD").ok().filter(|p| !p.is_empty()),
+                mailbox: var("IMAP_MAILBOX")
+                    .ok()
+                    .filter(|m| !m.is_empty())
+                    .unwrap_or_else(|| "INBOX".to_string()),
+                max_items: var("IMAP_MAX_ITEMS")
+                    .ok()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(20),
+                message_url: var("IMAP_MESSAGE_URL")
+                    .ok()
+                    .filter(|u| !u.is_empty())
+                    .unwrap_or_else(|| "message:%3C{id}%3E".to_string()),
+            },
+            read_later: ReadLaterConfig {
+                readwise_token: var("READWISE_TOKEN").ok().filter(|t| !t.is_empty()),
+                oldest: var("READ_LATER_OLDEST")
+                    .ok()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(3),
+            },
+            oura: OuraConfig {
+                token: var("OURA_TOKEN").ok().filter(|t| !t.is_empty()),
+            },
+            apple_health: AppleHealthConfig {
+                sleep_file: var("HEALTH_SLEEP_FILE")
+                    .ok()
+                    .filter(|p| !p.is_empty())
+                    .map(PathBuf::from)
+                    .unwrap_or(shortcuts_sleep_path),
+            },
+            hooks: HooksConfig {
+                pre_new: var("HOOK_PRE_NEW").ok(),
+                post_new: var("HOOK_POST_NEW").ok(),
+                post_save: var("HOOK_POST_SAVE").ok(),
+            },
+            daemon: DaemonConfig {
+                create_at: var("DAEMON_CREATE_AT")
+                    .ok()
+                    .and_then(|v| NaiveTime::parse_from_str(v.trim(), "%H:%M").ok())
+                    .unwrap_or(NaiveTime::from_hms_opt(6, 0, 0).unwrap()),
+                prewarm: var("DAEMON_PREWARM")
+                    .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true")),
+                remind_at: var("REMIND_AT")
+                    .ok()
+                    .and_then(|v| NaiveTime::parse_from_str(v.trim(), "%H:%M").ok()),
+            },
+            email: EmailConfig {
+                smtp_host: var("SMTP_HOST").ok(),
+                smtp_port: var("SMTP_PORT")
+                    .ok()
+                    .and_then(|v| v.trim().parse().ok())
+                    .unwrap_or(587),
+                smtp_username: var("SMTP_USERNAME").ok(),
+                smtp_password: var("SMTP_PASSWORD").ok(),
+                from: var("DIGEST_EMAIL_FROM").ok(),
+                to: var("DIGEST_EMAIL_TO").ok(),
+            },
+            telegram: TelegramConfig {
+                bot_token: var("TELEGRAM_BOT_TOKEN").ok(),
+                chat_id: var("TELEGRAM_CHAT_ID")
+                    .ok()
+                    .and_then(|v| v.trim().parse().ok()),
+            },
+            matrix: MatrixConfig {
+                homeserver: var("MATRIX_HOMESERVER").ok(),
+                access_token: var("MATRIX_ACCESS_TOKEN").ok(),
+                room: var("MATRIX_ROOM").ok(),
+                user_id: var("MATRIX_USER_ID")
+                    .ok()
+                    .map(|u| u.trim().to_string())
+                    .filter(|u| !u.is_empty()),
+            },
+            dictate: DictateConfig {
+                backend: var("DICTATE_BACKEND").unwrap_or_else(|_| "whisper-cpp".to_string()),
+                whisper_binary: var("WHISPER_CPP_BIN")
+                    .unwrap_or_else(|_| "whisper-cli".to_string()),
+                model: var("WHISPER_MODEL").ok().map(PathBuf::from),
+                language: var("WHISPER_LANGUAGE").ok(),
+            },
+            discord: DiscordConfig {
+                bot_token: var("DISCORD_BOT_TOKEN").ok(),
+                user_id: var("DISCORD_USER_ID").ok(),
+            },
+            llm: LlmConfig {
+                api_url: var("LLM_API_URL").ok().filter(|v| !v.trim().is_empty()),
+                api_key: var("LLM_API_KEY").ok(),
+                model: var("LLM_MODEL").unwrap_or_else(|_| "gpt-4o-mini".to_string()),
+            },
+            proof: ProofConfig {
+                dictionary: var("PROOF_DICTIONARY").ok().map(PathBuf::from),
+                language: var("PROOF_LANGUAGE").unwrap_or_else(|_| "en_US".to_string()),
+                words: var("PROOF_WORDS").ok().map(PathBuf::from),
+                max_sentence_words: var("PROOF_MAX_SENTENCE_WORDS")
+                    .ok()
+                    .and_then(|v| v.trim().parse().ok())
+                    .unwrap_or(40),
+            },
+            backup: BackupConfig {
+                dir: var("BACKUP_DIR")
+                    .ok()
+                    .filter(|d| !d.is_empty())
+                    .map(PathBuf::from),
+                keep: var("BACKUP_KEEP")
+                    .ok()
+                    .and_then(|v| v.trim().parse().ok())
+                    .unwrap_or(10),
+                passphrase: var("BACKUP_PASSPHRASE").ok().filter(|p| !p.is_empty()),
+            },
+            publish: PublishConfig {
+                target: var("PUBLISH_TARGET").ok().filter(|t| !t.trim().is_empty()),
+                branch: var("PUBLISH_BRANCH")
+                    .ok()
+                    .filter(|b| !b.trim().is_empty())
+                    .unwrap_or_else(|| "gh-pages".to_string()),
+            },
+            sync: SyncConfig {
+                remote: var("GIT_REMOTE")
+                    .ok()
+                    .filter(|r| !r.trim().is_empty())
+                    .unwrap_or_else(|| "origin".to_string()),
+                branch: var("GIT_BRANCH").ok().filter(|b| !b.trim().is_empty()),
+                push: var("GIT_SYNC_PUSH")
+                    .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true")),
+            },
+        };
+        config.set_root(root, &journal_dir);
+        config
     }
 }
 
 impl Config {
+    /// Settings from the environment, after loading the `.env` and config files
+    /// (see `load_files`) if that hasn't happened yet
     pub fn new() -> Self {
+        if LOADED.read().unwrap_or_else(|e| e.into_inner()).is_none()
+            && let Err(e) = load_files(None)
+        {
+            tracing::warn!("{}", e);
+        }
         Self::default()
     }
+
+    /// Move the journal, templates and local state under `root`
+    pub fn set_root(&mut self, root: PathBuf, journal_dir: &Path) {
+        self.journal_dir = root.join(journal_dir);
+        self.template_path = root.join(configured_path("TEMPLATE_PATH", "template.md"));
+        self.month_template_path =
+            root.join(configured_path("MONTH_TEMPLATE_PATH", "month_template.md"));
+        self.year_template_path =
+            root.join(configured_path("YEAR_TEMPLATE_PATH", "year_template.md"));
+        self.week_template_path =
+            root.join(configured_path("WEEK_TEMPLATE_PATH", "week_template.md"));
+        self.index_path = root.join(".easy_journal/index.db");
+        self.history_dir = root.join(".easy_journal/history");
+        self.trash_dir = root.join(".trash");
+        self.lock_path = root.join(".easy_journal/write.lock");
+        self.last_fetch_path = root.join(".easy_journal/last_fetch.json");
+        self.deferred_path = root.join(".easy_journal/deferred.json");
+        self.timer_path = root.join(".easy_journal/timer.json");
+        self.layout_version_path = root.join(".easy_journal/layout_version");
+        self.rss.seen_path = root.join(".easy_journal/rss_seen.json");
+        self.root = root;
+    }
+}
+
+/// A path from setting `name`, or `default`; relative ones are under the journal root
+fn configured_path(name: &str, default: &str) -> PathBuf {
+    var(name)
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .map_or_else(|| PathBuf::from(default), PathBuf::from)
+}
+
+/// `~/.config/easy_journal/config.toml` (or under XDG_CONFIG_HOME)
+pub fn user_config_path() -> PathBuf {
+    env::var("XDG_CONFIG_HOME")
+        .ok()
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            PathBuf::from(env::var("HOME").unwrap_or_else(|_| String::from("."))).join(".config")
+        })
+        .join("easy_journal/config.toml")
+}
+
+/// The project-level config file of a directory; `init` writes one to mark the
+/// journal's root
+pub fn project_config_path(dir: &Path) -> PathBuf {
+    dir.join(".easy_journal.toml")
+}
+
+/// The nearest directory from `dir` up with a `.easy_journal.toml`, the way git
+/// finds `.git`
+pub fn discover_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|ancestor| project_config_path(ancestor).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Turn a config file into the environment variables it stands for: keys are
+/// upper-cased and joined with their tables, so `[gitlab] host` is GITLAB_HOST
+/// and `[journal] sections = ["weather", "github"]` is JOURNAL_SECTIONS.
+/// `[profiles.<name>]` tables are left to `profile_vars`.
+pub fn config_file_vars(content: &str) -> std::result::Result<Vec<(String, String)>, String> {
+    let mut table = parse_config_file(content)?;
+    table.remove("profiles");
+    let mut vars = Vec::new();
+    flatten("", &table, &mut vars)?;
+    Ok(vars)
+}
+
+/// The variables of `[profiles.<name>]` in a config file; `None` if it has no
+/// such profile
+pub fn profile_vars(
+    content: &str,
+    name: &str,
+) -> std::result::Result<Option<Vec<(String, String)>>, String> {
+    let table = parse_config_file(content)?;
+    let Some(profile) = table
+        .get("profiles")
+        .and_then(|profiles| profiles.get(name))
+    else {
+        return Ok(None);
+    };
+    let profile = profile
+        .as_table()
+        .ok_or_else(|| format!("profiles.{} must be a table", name))?;
+    let mut vars = Vec::new();
+    flatten("", profile, &mut vars)?;
+    Ok(Some(vars))
+}
+
+/// Names of the `[profiles.<name>]` tables in a config file
+pub fn profile_names(content: &str) -> Vec<String> {
+    parse_config_file(content)
+        .ok()
+        .and_then(|table| table.get("profiles")?.as_table().cloned())
+        .map(|profiles| profiles.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Profiles defined in the journal's `.easy_journal.toml` (found from the
+/// current directory) and the user config file, sorted and without duplicates
+pub fn available_profiles() -> Vec<String> {
+    let root = env::current_dir().ok().and_then(|dir| discover_root(&dir));
+    let mut names: Vec<String> = [
+        project_config_path(root.as_deref().unwrap_or(Path::new("."))),
+        user_config_path(),
+    ]
+    .iter()
+    .filter_map(|path| std::fs::read_to_string(path).ok())
+    .flat_map(|content| profile_names(&content))
+    .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn parse_config_file(content: &str) -> std::result::Result<toml::Table, String> {
+    content.parse().map_err(|e: toml::de::Error| e.to_string())
+}
+
+fn flatten(
+    prefix: &str,
+    table: &toml::Table,
+    vars: &mut Vec<(String, String)>,
+) -> std::result::Result<(), String> {
+    for (key, value) in table {
+        let name = format!("{}{}", prefix, key.to_uppercase().replace('-', "_"));
+        let scalar = |value: &toml::Value| match value {
+            toml::Value::String(s) => Some(s.clone()),
+            toml::Value::Integer(i) => Some(i.to_string()),
+            toml::Value::Float(f) => Some(f.to_string()),
+            toml::Value::Boolean(b) => Some(b.to_string()),
+            toml::Value::Datetime(d) => Some(d.to_string()),
+            toml::Value::Array(_) | toml::Value::Table(_) => None,
+        };
+        match value {
+            toml::Value::Table(inner) => flatten(&format!("{}_", name), inner, vars)?,
+            toml::Value::Array(items) => {
+                let items = items
+                    .iter()
+                    .map(scalar)
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| format!("{} must be a list of plain values", key))?;
+                vars.push((name, items.join(",")));
+            }
+            value => vars.push((name, scalar(value).unwrap_or_default())),
+        }
+    }
+    Ok(())
+}
+
+/// Values from the `.env` and config files, set by `load_files`; `None` until
+/// it has run, so `Config::new` doesn't load them again. Kept apart from the
+/// process environment, which is never changed.
+static LOADED: RwLock<Option<HashMap<String, String>>> = RwLock::new(None);
+
+/// Selects a `[profiles.<name>]` table of the config files, like `--profile`
+pub const PROFILE_VAR: &str = "EASY_JOURNAL_PROFILE";
+
+/// A setting: loaded from the `.env` and config files, else from the
+/// environment. Only values the environment doesn't have are loaded, besides
+/// the selected profile and the journal found above the current directory.
+pub fn var(name: &str) -> std::result::Result<String, env::VarError> {
+    let loaded = LOADED.read().unwrap_or_else(|e| e.into_inner());
+    match loaded.as_ref().and_then(|loaded| loaded.get(name)) {
+        Some(value) => Ok(value.clone()),
+        None => env::var(name),
+    }
+}
+
+/// Every setting with its value (see `var`), sorted by name
+pub fn vars() -> Vec<(String, String)> {
+    let mut vars: HashMap<String, String> = env::vars().collect();
+    if let Some(loaded) = LOADED.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        vars.extend(loaded.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+    let mut vars: Vec<(String, String)> = vars.into_iter().collect();
+    vars.sort();
+    vars
+}
+
+/// The values `load_files` loaded, passed to hooks and custom sections along
+/// with the environment
+pub fn loaded() -> Vec<(String, String)> {
+    LOADED
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(|loaded| loaded.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default()
+}
+
+/// The values gathered by `load_files`
+#[derive(Default)]
+struct Loaded(HashMap<String, String>);
+
+impl Loaded {
+    fn get(&self, name: &str) -> Option<String> {
+        self.0.get(name).cloned().or_else(|| env::var(name).ok())
+    }
+
+    /// Add the variables that aren't set yet
+    fn set_unset(&mut self, vars: impl IntoIterator<Item = (String, String)>) {
+        for (name, value) in vars {
+            if self.get(&name).is_none() {
+                self.0.insert(name, value);
+            }
+        }
+    }
+
+    /// Add the variables of a `.env` file that aren't set yet. A missing file is fine.
+    fn apply_env_file(&mut self, path: &Path, trusted: bool) {
+        if let Ok(vars) = dotenvy::from_path_iter(path) {
+            self.set_unset(trust_filter(vars.flatten(), path, trusted));
+        }
+    }
+
+    /// Add the variables of a config file that aren't set yet, so the
+    /// environment and `.env` files take precedence. A missing file is fine.
+    fn apply_config_file(&mut self, path: &Path, trusted: bool) -> Result<()> {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Ok(());
+        };
+        let vars = config_file_vars(&content)
+            .map_err(|e| JournalError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+        self.set_unset(trust_filter(vars, path, trusted));
+        Ok(())
+    }
+
+    /// Add the variables of profile `name` from the journal's
+    /// `.easy_journal.toml` and the user config file; fails if neither has it
+    fn apply_profile(&mut self, name: &str, root: Option<&Path>) -> Result<()> {
+        let files = [
+            (project_config_path(root.unwrap_or(Path::new("."))), false),
+            (user_config_path(), true),
+        ];
+        let mut found = false;
+        for (path, trusted) in &files {
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let vars = profile_vars(&content, name)
+                .map_err(|e| JournalError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+            if let Some(vars) = vars {
+                self.set_unset(trust_filter(vars, path, *trusted));
+                found = true;
+            }
+        }
+        if !found {
+            return Err(JournalError::InvalidConfig(format!(
+                "No [profiles.{}] in {} or {}",
+                name,
+                files[0].0.display(),
+                files[1].0.display()
+            )));
+        }
+        self.0.insert(PROFILE_VAR.to_string(), name.to_string());
+        Ok(())
+    }
+}
+
+/// Settings that run shell commands: hooks and custom sections
+pub fn is_command(var: &str) -> bool {
+    var.starts_with("HOOK_") || (var.starts_with("SECTION_") && var.ends_with("_COMMAND"))
+}
+
+/// Drop the command settings of a file the user didn't set up themselves (a
+/// `.easy_journal.toml` or a journal found by walking up from the current
+/// directory), so a cloned or unpacked journal can't run commands
+fn trust_filter(
+    vars: impl IntoIterator<Item = (String, String)>,
+    path: &Path,
+    trusted: bool,
+) -> impl Iterator<Item = (String, String)> {
+    vars.into_iter().filter(move |(name, _)| {
+        let keep = trusted || !is_command(name);
+        if !keep {
+            eprintln!(
+                "⚠ Ignoring {} from {}: hooks and section commands are only read from {}, \
+                 the environment or the .env of a journal saved by `init`",
+                name,
+                path.display(),
+                user_config_path().display()
+            );
+        }
+        keep
+    })
+}
+
+/// Load settings from, in order of precedence: the environment, the selected
+/// profile (`profile`, else EASY_JOURNAL_PROFILE), `./.env`, the journal
+/// found above the current directory, `~/.easy_journal.env`, the journal's
+/// `.env`, `./.easy_journal.toml`, the journal's `.easy_journal.toml` and
+/// `~/.config/easy_journal/config.toml`. They're read through `var`; the
+/// process environment is left alone.
+pub fn load_files(profile: Option<&str>) -> Result<()> {
+    let mut loaded = Loaded::default();
+    let result = load_into(&mut loaded, profile);
+    *LOADED.write().unwrap_or_else(|e| e.into_inner()) = Some(loaded.0);
+    result
+}
+
+fn load_into(loaded: &mut Loaded, profile: Option<&str>) -> Result<()> {
+    let discovered = env::current_dir().ok().and_then(|dir| discover_root(&dir));
+    let profile = profile
+        .map(str::to_string)
+        .or_else(|| env::var(PROFILE_VAR).ok())
+        .filter(|name| !name.trim().is_empty());
+    if let Some(profile) = &profile {
+        loaded.apply_profile(profile.trim(), discovered.as_deref())?;
+    }
+
+    let saved: Vec<_> = dotenvy::from_path_iter(user_env_path())
+        .map(|vars| vars.flatten().collect())
+        .unwrap_or_default();
+    // The .env files of a discovered journal may only run commands if `init` saved it
+    let trusted = discovered.as_ref().is_none_or(|root| {
+        saved
+            .iter()
+            .any(|(name, value)| name == "JOURNAL_HOME" && Path::new(value) == root)
+    });
+    // `./.env`, or the nearest one above, like `dotenvy::dotenv`
+    let dotenv = env::current_dir().ok().and_then(|dir| {
+        dir.ancestors()
+            .map(|dir| dir.join(".env"))
+            .find(|path| path.is_file())
+    });
+    if let Some(path) = dotenv {
+        loaded.apply_env_file(&path, trusted);
+    }
+    // The journal we're inside of comes before the location saved by `init <path>`
+    let discovered = discovered.filter(|_| loaded.get("JOURNAL_HOME").is_none());
+    if let Some(root) = &discovered {
+        loaded
+            .0
+            .insert("JOURNAL_HOME".to_string(), root.display().to_string());
+    }
+    // Then the location saved by `init <path>` and that journal's own .env
+    loaded.set_unset(saved.into_iter().filter(|(name, _)| {
+        discovered.is_none() || (name != "JOURNAL_HOME" && name != "JOURNAL_DIR")
+    }));
+    let home = loaded.get("JOURNAL_HOME");
+    if let Some(home) = &home {
+        loaded.apply_env_file(&Path::new(home).join(".env"), trusted);
+    }
+
+    loaded.apply_config_file(&project_config_path(Path::new(".")), false)?;
+    if let Some(home) = &home {
+        loaded.apply_config_file(&project_config_path(Path::new(home)), false)?;
+    }
+    loaded.apply_config_file(&user_config_path(), true)?;
+
+    // JOURNAL_HOME may only be known from the user config file
+    if home.is_none()
+        && let Some(home) = loaded.get("JOURNAL_HOME")
+    {
+        loaded.apply_env_file(&Path::new(&home).join(".env"), true);
+        loaded.apply_config_file(&project_config_path(Path::new(&home)), false)?;
+    }
+    Ok(())
+}
+
+/// Every setting `Config` reads, by environment variable. SECTION_<NAME>_COMMAND
+/// (custom sections) is accepted too.
+pub const SETTINGS: &[&str] = &[
+    "JOURNAL_HOME",
+    "JOURNAL_DIR",
+    "JOURNAL_LAYOUT",
+    "JOURNAL_SECTIONS",
+    "SECTION_GOALS",
+    "TEMPLATE_PATH",
+    "MONTH_TEMPLATE_PATH",
+    "YEAR_TEMPLATE_PATH",
+    "WEEK_TEMPLATE_PATH",
+    "GIT_AUTOCOMMIT",
+    "NAV_LINKS",
+    "REDACT_STRIP",
+    "API_TOKEN",
+    "TOKEN_PASSPHRASE",
+    "STORAGE_BACKEND",
+    "WEBDAV_URL",
+    "WEBDAV_USERNAME",
+    "WEBDAV_PASSWORD",
+    "S3_ENDPOINT",
+    "S3_BUCKET",
+    "S3_REGION",
+    "S3_ACCESS_KEY_ID",
+    "S3_SECRET_ACCESS_KEY",
+    "S3_PREFIX",
+    "GOOGLE_CLIENT_ID",
+    "GOOGLE_CLIENT_SECRET",
+    "GOOGLE_REAUTH_INLINE",
+    "GITHUB_TOKEN",
+    "GITHUB_CLIENT_ID",
+    "GITHUB_CI_REPOS",
+    "GITLAB_TOKEN",
+    "GITLAB_HOST",
+    "GITLAB_CLIENT_ID",
+    "GITLAB_CLIENT_SECRET",
+    "GITLAB_REDIRECT_PORT",
+    "GITLAB_PIPELINES",
+    "WEATHER_LATITUDE",
+    "WEATHER_LONGITUDE",
+    "FITBIT_CLIENT_ID",
+    "FITBIT_CLIENT_SECRET",
+    "FITBIT_REDIRECT_PORT",
+    "STRAVA_CLIENT_ID",
+    "STRAVA_CLIENT_SECRET",
+    "STRAVA_REDIRECT_PORT",
+    "HEALTH_SLEEP_FILE",
+    "OURA_TOKEN",
+    "WAKATIME_API_KEY",
+    "WAKATIME_API_URL",
+    "TOGGL_API_TOKEN",
+    "LASTFM_API_KEY",
+    "LASTFM_USER",
+    "READWISE_TOKEN",
+    "READ_LATER_OLDEST",
+    "RSS_FEEDS",
+    "RSS_MAX_ITEMS",
+    "RSS_PER_FEED",
+    "MASTODON_INSTANCE",
+    "MASTODON_ACCESS_TOKEN",
+    "IMAP_HOST",
+    "IMAP_PORT",
+    "IMAP_USERNAME",
+    "IMAP_PASSWORD",
+    "IMAP_MAILBOX",
+    "IMAP_MAX_ITEMS",
+    "IMAP_MESSAGE_URL",
+    "PAGERDUTY_TOKEN",
+    "PAGERDUTY_TEAM_IDS",
+    "OPSGENIE_API_KEY",
+    "OPSGENIE_USER",
+    "OPSGENIE_API_URL",
+    "OPSGENIE_APP_URL",
+    "SENTRY_AUTH_TOKEN",
+    "SENTRY_ORG",
+    "SENTRY_PROJECTS",
+    "SENTRY_URL",
+    "CONFLUENCE_URL",
+    "CONFLUENCE_EMAIL",
+    "CONFLUENCE_API_TOKEN",
+    "HOOK_PRE_NEW",
+    "HOOK_POST_NEW",
+    "HOOK_POST_SAVE",
+    "DAEMON_CREATE_AT",
+    "DAEMON_PREWARM",
+    "REMIND_AT",
+    "SMTP_HOST",
+    "SMTP_PORT",
+    "SMTP_USERNAME",
+    "SMTP_PASSWORD",
+    "DIGEST_EMAIL_FROM",
+    "DIGEST_EMAIL_TO",
+    "TELEGRAM_BOT_TOKEN",
+    "TELEGRAM_CHAT_ID",
+    "MATRIX_HOMESERVER",
+    "MATRIX_ACCESS_TOKEN",
+    "MATRIX_ROOM",
+    "MATRIX_USER_ID",
+    "DISCORD_BOT_TOKEN",
+    "DISCORD_USER_ID",
+    "DICTATE_BACKEND",
+    "WHISPER_CPP_BIN",
+    "WHISPER_MODEL",
+    "WHISPER_LANGUAGE",
+    "LLM_API_URL",
+    "LLM_API_KEY",
+    "LLM_MODEL",
+    "PROOF_DICTIONARY",
+    "PROOF_LANGUAGE",
+    "PROOF_WORDS",
+    "PROOF_MAX_SENTENCE_WORDS",
+    "BACKUP_DIR",
+    "BACKUP_KEEP",
+    "BACKUP_PASSPHRASE",
+    "PUBLISH_TARGET",
+    "PUBLISH_BRANCH",
+    "GIT_REMOTE",
+    "GIT_BRANCH",
+    "GIT_SYNC_PUSH",
+];
+
+/// The environment variable of a setting: `gitlab.host`, `gitlab_host` and
+/// GITLAB_HOST are all GITLAB_HOST. Fails for settings `Config` doesn't read.
+pub fn setting_var(key: &str) -> Result<String> {
+    let var = key.trim().to_uppercase().replace(['.', '-'], "_");
+    let custom_section = var
+        .strip_prefix("SECTION_")
+        .and_then(|name| name.strip_suffix("_COMMAND"))
+        .is_some_and(|name| !name.is_empty());
+    if SETTINGS.contains(&var.as_str()) || custom_section {
+        Ok(var)
+    } else {
+        Err(JournalError::InvalidConfig(format!(
+            "Unknown setting \"{}\"; see `easy_journal config show --all`",
+            key.trim()
+        )))
+    }
+}
+
+/// Credentials, which `config show` masks
+pub fn is_secret(var: &str) -> bool {
+    ["TOKEN", "SECRET", "PASSWORD", "PASSPHRASE", "API_KEY"]
+        .iter()
+        .any(|word| var.contains(word))
+}
+
+/// Where `var` is set in a config file: the key path of the first entry that
+/// `config_file_vars` would turn into it
+fn find_setting(table: &dyn toml_edit::TableLike, prefix: &str, var: &str) -> Option<Vec<String>> {
+    table.iter().find_map(|(key, item)| {
+        if prefix.is_empty() && key == "profiles" {
+            return None;
+        }
+        let name = format!("{}{}", prefix, key.to_uppercase().replace('-', "_"));
+        match item.as_table_like() {
+            Some(inner) => find_setting(inner, &format!("{}_", name), var).map(|mut path| {
+                path.insert(0, key.to_string());
+                path
+            }),
+            None => (name == var).then(|| vec![key.to_string()]),
+        }
+    })
+}
+
+/// Set `key` (whose variable is `var`) to `value` in a config file, keeping its
+/// comments and layout. An existing entry for the variable is updated in place;
+/// otherwise `gitlab.host` goes under `[gitlab]` and `gitlab_host` at the top.
+/// With a `profile`, all of that happens inside `[profiles.<profile>]`.
+pub fn set_in_config_file(
+    content: &str,
+    profile: Option<&str>,
+    key: &str,
+    var: &str,
+    value: &str,
+) -> std::result::Result<String, String> {
+    let mut document: toml_edit::DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| e.to_string())?;
+    let base: Vec<String> = profile
+        .map(|name| vec!["profiles".to_string(), name.to_string()])
+        .unwrap_or_default();
+    let scope = base.iter().try_fold(
+        document.as_table() as &dyn toml_edit::TableLike,
+        |table, segment| table.get(segment).and_then(|item| item.as_table_like()),
+    );
+    let found = scope.and_then(|table| find_setting(table, "", var));
+    let path: Vec<String> = base
+        .iter()
+        .cloned()
+        .chain(found.unwrap_or_else(|| {
+            key.trim()
+                .split('.')
+                .map(|segment| segment.trim().to_lowercase())
+                .collect()
+        }))
+        .collect();
+
+    let (name, tables) = path.split_last().ok_or("Empty setting")?;
+    let mut table: &mut dyn toml_edit::TableLike = document.as_table_mut();
+    for segment in tables {
+        let item = table.entry(segment).or_insert_with(|| {
+            let mut inner = toml_edit::Table::new();
+            inner.set_implicit(true);
+            toml_edit::Item::Table(inner)
+        });
+        table = item
+            .as_table_like_mut()
+            .ok_or_else(|| format!("{} is not a table", segment))?;
+    }
+
+    let existing = table.get(name).and_then(|item| item.as_value());
+    let mut typed: toml_edit::Value = if existing.is_some_and(|v| v.is_array()) {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .collect::<toml_edit::Array>()
+            .into()
+    } else if let Ok(flag) = value.parse::<bool>() {
+        flag.into()
+    } else if let Ok(number) = value.parse::<i64>() {
+        number.into()
+    } else if let Ok(number) = value.parse::<f64>()
+        && number.is_finite()
+    {
+        number.into()
+    } else {
+        value.into()
+    };
+    if let Some(existing) = existing {
+        *typed.decor_mut() = existing.decor().clone();
+    }
+    table.insert(name, toml_edit::Item::Value(typed));
+    Ok(document.to_string())
+}
+
+/// Settings shared by every directory, e.g. JOURNAL_HOME written by `init <path>`.
+/// Loaded after `.env`, which takes precedence.
+pub fn user_env_path() -> PathBuf {
+    PathBuf::from(env::var("HOME").unwrap_or_else(|_| String::from("."))).join(".easy_journal.env")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_file_vars() {
+        let content = r#"
+[journal]
+home = "~/Documents/journal"
+sections = ["weather", "github", "custom:standup"]
+
+[gitlab]
+host = "https://gitlab.example.com"
+
+[section.standup]
+command = "cat standup.md"
+
+[weather]
+latitude = 52.52
+
+"#;
+        let mut vars = config_file_vars(content).unwrap();
+        vars.sort();
+        assert_eq!(
+            vars,
+            vec![
+                (
+                    "GITLAB_HOST".to_string(),
+                    "https://gitlab.example.com".to_string()
+                ),
+                (
+                    "JOURNAL_HOME".to_string(),
+                    "~/Documents/journal".to_string()
+                ),
+                (
+                    "JOURNAL_SECTIONS".to_string(),
+                    "weather,github,custom:standup".to_string()
+                ),
+                (
+                    "SECTION_STANDUP_COMMAND".to_string(),
+                    "cat standup.md".to_string()
+                ),
+                ("WEATHER_LATITUDE".to_string(), "52.52".to_string()),
+            ]
+        );
+        assert!(
+            config_file_vars(
+                "[journal]
+sections = [[1]]
+"
+            )
+            .is_err()
+        );
+        assert!(config_file_vars("not toml").is_err());
+    }
+
+    #[test]
+    fn test_discover_root() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_root_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("journal/2025/12")).unwrap();
+        assert_eq!(discover_root(&dir.join("journal/2025/12")), None);
+
+        std::fs::write(project_config_path(&dir), "[journal]\ndir = \"journal\"\n").unwrap();
+        assert_eq!(
+            discover_root(&dir.join("journal/2025/12")),
+            Some(dir.clone())
+        );
+        assert_eq!(discover_root(&dir), Some(dir.clone()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_loaded_precedence() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_loaded_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(".env"), "EASY_JOURNAL_TEST_A=from-env-file\n").unwrap();
+        std::fs::write(
+            project_config_path(&dir),
+            "[easy_journal_test]\na = \"from-toml\"\nb = \"from-toml\"\n\
+             [hook]\npost_new = \"touch pwned\"\n\
+             [section.probe]\ncommand = \"touch pwned\"\n",
+        )
+        .unwrap();
+
+        let mut loaded = Loaded::default();
+        loaded.apply_env_file(&dir.join(".env"), true);
+        loaded
+            .apply_config_file(&project_config_path(&dir), false)
+            .unwrap();
+        assert_eq!(
+            loaded.get("EASY_JOURNAL_TEST_A").as_deref(),
+            Some("from-env-file")
+        );
+        assert_eq!(
+            loaded.get("EASY_JOURNAL_TEST_B").as_deref(),
+            Some("from-toml")
+        );
+        // A project file can't run commands, the user config can
+        assert!(!loaded.0.contains_key("HOOK_POST_NEW"));
+        assert!(!loaded.0.contains_key("SECTION_PROBE_COMMAND"));
+        loaded
+            .apply_config_file(&project_config_path(&dir), true)
+            .unwrap();
+        assert_eq!(
+            loaded.0.get("HOOK_POST_NEW").map(String::as_str),
+            Some("touch pwned")
+        );
+        assert!(is_command("SECTION_PROBE_COMMAND"));
+        assert!(!is_command("SECTION_GOALS"));
+        // The process environment is left alone
+        assert!(env::var_os("EASY_JOURNAL_TEST_A").is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_set_in_config_file() {
+        assert_eq!(setting_var("gitlab.host").unwrap(), "GITLAB_HOST");
+        assert_eq!(
+            setting_var("section.standup.command").unwrap(),
+            "SECTION_STANDUP_COMMAND"
+        );
+        assert!(setting_var("gitlab.hots").is_err());
+
+        let content = "# Mine\n[journal]\ndir = \"entries\" # moved\nsections = [\"weather\"]\n";
+        let set = |content: &str, key: &str, value: &str| {
+            set_in_config_file(content, None, key, &setting_var(key).unwrap(), value).unwrap()
+        };
+        let updated = set(content, "journal_dir", "notes");
+        assert_eq!(
+            updated,
+            "# Mine\n[journal]\ndir = \"notes\" # moved\nsections = [\"weather\"]\n"
+        );
+        let updated = set(&updated, "JOURNAL_SECTIONS", "weather, github");
+        assert!(updated.contains("sections = [\"weather\", \"github\"]"));
+        let updated = set(&updated, "gitlab.host", "https://gitlab.example.com");
+        let updated = set(&updated, "nav_links", "true");
+        let updated = set(&updated, "weather.latitude", "52.52");
+
+        let mut vars = config_file_vars(&updated).unwrap();
+        vars.sort();
+        assert_eq!(
+            vars,
+            vec![
+                (
+                    "GITLAB_HOST".to_string(),
+                    "https://gitlab.example.com".to_string()
+                ),
+                ("JOURNAL_DIR".to_string(), "notes".to_string()),
+                ("JOURNAL_SECTIONS".to_string(), "weather,github".to_string()),
+                ("NAV_LINKS".to_string(), "true".to_string()),
+                ("WEATHER_LATITUDE".to_string(), "52.52".to_string()),
+            ]
+        );
+        assert!(updated.starts_with("nav_links = true\n# Mine\n[journal]"));
+
+        let updated =
+            set_in_config_file(&updated, Some("work"), "journal_dir", "JOURNAL_DIR", "work")
+                .unwrap();
+        assert!(updated.contains("[profiles.work]\njournal_dir = \"work\""));
+        assert!(
+            config_file_vars(&updated)
+                .unwrap()
+                .contains(&("JOURNAL_DIR".to_string(), "notes".to_string()))
+        );
+        assert_eq!(
+            profile_vars(&updated, "work").unwrap(),
+            Some(vec![("JOURNAL_DIR".to_string(), "work".to_string())])
+        );
+        assert_eq!(profile_vars(&updated, "home").unwrap(), None);
+        assert_eq!(profile_names(&updated), vec!["work".to_string()]);
+        assert!(profile_names("not toml").is_empty());
+    }
 }
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,9 +6,21 @@ pub enum JournalError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("{}: {source}", path.display())]
+    FileIo {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
     #[error("Failed to parse date: {0}")]
     DateParse(String),
 
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("No entry exists for {0}")]
+    EntryNotFound(String),
+
     #[error("Template file not found at {0}")]
     _TemplateNotFound(PathBuf),
 
@@ -19,7 +31,7 @@ pub enum JournalError {
     EditorFailed(String),
 
     #[error("Invalid configuration: {0}")]
-    _InvalidConfig(String),
+    InvalidConfig(String),
 
     #[error("Failed to fetch reminders: {0}")]
     RemindersFailed(String),
@@ -30,6 +42,12 @@ pub enum JournalError {
     #[error("OAuth authentication failed: {0}")]
     OAuthFailed(String),
 
+    #[error("Sign-in expired: {0}")]
+    ReauthRequired(String),
+
+    #[error("Token storage error: {0}")]
+    SecretsFailed(String),
+
     #[error("Failed to fetch Google Tasks: {0}")]
     GoogleTasksFailed(String),
 
@@ -38,6 +56,504 @@ pub enum JournalError {
 
     #[error("Failed to fetch GitLab items: {0}")]
     GitLabFailed(String),
+
+    #[error("{provider} returned HTTP {status}: {message}")]
+    HttpStatus {
+        provider: String,
+        status: u16,
+        message: String,
+    },
+
+    #[error("Failed to fetch weather: {0}")]
+    WeatherFailed(String),
+
+    #[error("Failed to fetch Fitbit data: {0}")]
+    FitbitFailed(String),
+
+    #[error("Failed to fetch Strava activities: {0}")]
+    StravaFailed(String),
+
+    #[error("Failed to fetch WakaTime summary: {0}")]
+    WakaTimeFailed(String),
+
+    #[error("Failed to fetch Toggl time entries: {0}")]
+    TogglFailed(String),
+
+    #[error("Failed to fetch Last.fm listening history: {0}")]
+    LastFmFailed(String),
+
+    #[error("Failed to fetch feeds: {0}")]
+    RssFailed(String),
+
+    #[error("Failed to fetch Mastodon posts: {0}")]
+    MastodonFailed(String),
+
+    #[error("Failed to fetch Confluence pages: {0}")]
+    ConfluenceFailed(String),
+
+    #[error("Failed to fetch Sentry issues: {0}")]
+    SentryFailed(String),
+
+    #[error("Failed to fetch on-call status: {0}")]
+    OnCallFailed(String),
+
+    #[error("Failed to fetch flagged email: {0}")]
+    ImapFailed(String),
+
+    #[error("Failed to fetch the read-later queue: {0}")]
+    ReadLaterFailed(String),
+
+    #[error("Failed to fetch Oura data: {0}")]
+    OuraFailed(String),
+
+    #[error("Failed to read Apple Health data: {0}")]
+    HealthFailed(String),
+
+    #[error("Custom section failed: {0}")]
+    SectionFailed(String),
+
+    #[error("Entry index error: {0}")]
+    IndexFailed(String),
+
+    #[error("Entry history error: {0}")]
+    HistoryFailed(String),
+
+    #[error("Trash error: {0}")]
+    TrashFailed(String),
+
+    #[error("Could not acquire write lock: {0}")]
+    LockFailed(String),
+
+    #[error("Remote storage error: {0}")]
+    StorageFailed(String),
+
+    #[error("Git error: {0}")]
+    GitFailed(String),
+
+    #[error("Export failed: {0}")]
+    ExportFailed(String),
+
+    #[error("Import failed: {0}")]
+    ImportFailed(String),
+
+    #[error("Backup failed: {0}")]
+    BackupFailed(String),
+
+    #[error("Publish failed: {0}")]
+    PublishFailed(String),
+
+    #[error("Book build failed: {0}")]
+    BookFailed(String),
+
+    #[error("Hook failed: {0}")]
+    HookFailed(String),
+
+    #[error("Could not show notification: {0}")]
+    NotifyFailed(String),
+
+    #[error("Failed to send email: {0}")]
+    EmailFailed(String),
+
+    #[error("Telegram bot error: {0}")]
+    TelegramFailed(String),
+
+    #[error("Matrix bot error: {0}")]
+    MatrixFailed(String),
+
+    #[error("Discord bot error: {0}")]
+    DiscordFailed(String),
+
+    #[error("Dictation failed: {0}")]
+    DictationFailed(String),
+
+    #[error("LLM request failed: {0}")]
+    LlmFailed(String),
 }
 
 pub type Result<T> = std::result::Result<T, JournalError>;
+
+impl JournalError {
+    /// Wrap an IO error with the path it happened on, for use with `map_err`
+    pub fn at_path(path: &Path) -> impl FnOnce(std::io::Error) -> JournalError + '_ {
+        move |source| JournalError::FileIo {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    /// Process exit code for this error.
+    ///
+    /// | Code | Meaning |
+    /// |------|---------|
+    /// | 1 | Other failure (editor, pre_new hook, ...) |
+    /// | 2 | Invalid date or argument |
+    /// | 3 | Entry or file not found |
+    /// | 4 | Missing or invalid configuration |
+    /// | 5 | Authentication rejected |
+    /// | 6 | Provider or remote storage failure |
+    /// | 7 | Filesystem error |
+    /// | 8 | Journal is locked by another process |
+    /// | 9 | Journal data error (index, history, trash, git, export/import) |
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            JournalError::EditorFailed(_)
+            | JournalError::HookFailed(_)
+            | JournalError::NotifyFailed(_)
+            | JournalError::DictationFailed(_)
+            | JournalError::BookFailed(_) => 1,
+            JournalError::DateParse(_) | JournalError::InvalidArgument(_) => 2,
+            JournalError::EntryNotFound(_) | JournalError::_TemplateNotFound(_) => 3,
+            JournalError::InvalidConfig(_) | JournalError::OAuthConfigMissing(_) => 4,
+            JournalError::OAuthFailed(_)
+            | JournalError::ReauthRequired(_)
+            | JournalError::SecretsFailed(_) => 5,
+            JournalError::HttpStatus {
+                status: 401 | 403, ..
+            } => 5,
+            JournalError::RemindersFailed(_)
+            | JournalError::GoogleTasksFailed(_)
+            | JournalError::GitHubFailed(_)
+            | JournalError::GitLabFailed(_)
+            | JournalError::WeatherFailed(_)
+            | JournalError::FitbitFailed(_)
+            | JournalError::StravaFailed(_)
+            | JournalError::WakaTimeFailed(_)
+            | JournalError::TogglFailed(_)
+            | JournalError::LastFmFailed(_)
+            | JournalError::RssFailed(_)
+            | JournalError::MastodonFailed(_)
+            | JournalError::ImapFailed(_)
+            | JournalError::OnCallFailed(_)
+            | JournalError::SentryFailed(_)
+            | JournalError::ConfluenceFailed(_)
+            | JournalError::ReadLaterFailed(_)
+            | JournalError::OuraFailed(_)
+            | JournalError::HealthFailed(_)
+            | JournalError::SectionFailed(_)
+            | JournalError::HttpStatus { .. }
+            | JournalError::StorageFailed(_)
+            | JournalError::EmailFailed(_)
+            | JournalError::TelegramFailed(_)
+            | JournalError::MatrixFailed(_)
+            | JournalError::DiscordFailed(_)
+            | JournalError::PublishFailed(_)
+            | JournalError::LlmFailed(_) => 6,
+            JournalError::Io(_) | JournalError::FileIo { .. } => 7,
+            JournalError::LockFailed(_) => 8,
+            JournalError::_SummaryParse(_)
+            | JournalError::IndexFailed(_)
+            | JournalError::HistoryFailed(_)
+            | JournalError::TrashFailed(_)
+            | JournalError::GitFailed(_)
+            | JournalError::ExportFailed(_)
+            | JournalError::ImportFailed(_)
+            | JournalError::BackupFailed(_) => 9,
+        }
+    }
+
+    /// Stable identifier for the error variant (used by `--error-format json`)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JournalError::Io(_) | JournalError::FileIo { .. } => "io",
+            JournalError::DateParse(_) => "date_parse",
+            JournalError::InvalidArgument(_) => "invalid_argument",
+            JournalError::EntryNotFound(_) => "entry_not_found",
+            JournalError::_TemplateNotFound(_) => "template_not_found",
+            JournalError::_SummaryParse(_) => "summary_parse",
+            JournalError::EditorFailed(_) => "editor_failed",
+            JournalError::InvalidConfig(_) => "invalid_config",
+            JournalError::RemindersFailed(_) => "reminders_failed",
+            JournalError::OAuthConfigMissing(_) => "oauth_config_missing",
+            JournalError::OAuthFailed(_) => "oauth_failed",
+            JournalError::ReauthRequired(_) => "reauth_required",
+            JournalError::SecretsFailed(_) => "secrets_failed",
+            JournalError::GoogleTasksFailed(_) => "google_tasks_failed",
+            JournalError::GitHubFailed(_) => "github_failed",
+            JournalError::GitLabFailed(_) => "gitlab_failed",
+            JournalError::HttpStatus { .. } => "http_status",
+            JournalError::WeatherFailed(_) => "weather_failed",
+            JournalError::FitbitFailed(_) => "fitbit_failed",
+            JournalError::StravaFailed(_) => "strava_failed",
+            JournalError::WakaTimeFailed(_) => "wakatime_failed",
+            JournalError::TogglFailed(_) => "toggl_failed",
+            JournalError::LastFmFailed(_) => "lastfm_failed",
+            JournalError::RssFailed(_) => "rss_failed",
+            JournalError::MastodonFailed(_) => "mastodon_failed",
+            JournalError::ImapFailed(_) => "imap_failed",
+            JournalError::OnCallFailed(_) => "oncall_failed",
+            JournalError::SentryFailed(_) => "sentry_failed",
+            JournalError::ConfluenceFailed(_) => "confluence_failed",
+            JournalError::ReadLaterFailed(_) => "read_later_failed",
+            JournalError::OuraFailed(_) => "oura_failed",
+            JournalError::HealthFailed(_) => "health_failed",
+            JournalError::SectionFailed(_) => "section_failed",
+            JournalError::IndexFailed(_) => "index_failed",
+            JournalError::HistoryFailed(_) => "history_failed",
+            JournalError::TrashFailed(_) => "trash_failed",
+            JournalError::LockFailed(_) => "lock_failed",
+            JournalError::StorageFailed(_) => "storage_failed",
+            JournalError::GitFailed(_) => "git_failed",
+            JournalError::ExportFailed(_) => "export_failed",
+            JournalError::ImportFailed(_) => "import_failed",
+            JournalError::BackupFailed(_) => "backup_failed",
+            JournalError::PublishFailed(_) => "publish_failed",
+            JournalError::BookFailed(_) => "book_failed",
+            JournalError::HookFailed(_) => "hook_failed",
+            JournalError::NotifyFailed(_) => "notify_failed",
+            JournalError::EmailFailed(_) => "email_failed",
+            JournalError::TelegramFailed(_) => "telegram_failed",
+            JournalError::MatrixFailed(_) => "matrix_failed",
+            JournalError::DiscordFailed(_) => "discord_failed",
+            JournalError::DictationFailed(_) => "dictation_failed",
+            JournalError::LlmFailed(_) => "llm_failed",
+        }
+    }
+
+    /// Suggestion for fixing the error, when there is an obvious one
+    pub fn hint(&self) -> Option<String> {
+        let hint = match self {
+            JournalError::DateParse(_) => {
+                "Use YYYY-MM-DD (e.g. 2025-12-29), today, yesterday, tomorrow, -3 (days ago), a weekday such as monday, or last/next friday"
+            }
+            JournalError::EntryNotFound(date) => {
+                return Some(format!("Create it with `easy_journal new --date {}`", date));
+            }
+            JournalError::EditorFailed(_) => "Set the EDITOR or VISUAL environment variable",
+            JournalError::OAuthConfigMissing(message) if message.contains("GITHUB_CLIENT_ID") => {
+                "Set GITHUB_CLIENT_ID in .env, or use a personal access token in GITHUB_TOKEN"
+            }
+            JournalError::OAuthConfigMissing(message) if message.contains("FITBIT_CLIENT_ID") => {
+                "Register an application at https://dev.fitbit.com/apps (redirect URL http://127.0.0.1:8977/callback) and set FITBIT_CLIENT_ID in .env"
+            }
+            JournalError::OAuthConfigMissing(message) if message.contains("STRAVA_CLIENT_ID") => {
+                "Create an API application at https://www.strava.com/settings/api (callback domain 127.0.0.1) and set STRAVA_CLIENT_ID and STRAVA_CLIENT_SECRET in .env"
+            }
+            JournalError::OAuthConfigMissing(message) if message.contains("GITLAB_CLIENT_ID") => {
+                "Set GITLAB_CLIENT_ID in .env (redirect URI http://127.0.0.1:8976/callback), or use GITLAB_TOKEN"
+            }
+            JournalError::OAuthFailed(message) if message.contains("GitHub") => {
+                "Run `easy_journal auth github` again, or set GITHUB_TOKEN in .env"
+            }
+            JournalError::OAuthFailed(message) if message.contains("GitLab") => {
+                "Run `easy_journal auth gitlab` again, or set GITLAB_TOKEN in .env"
+            }
+            JournalError::OAuthFailed(message) if message.contains("Fitbit") => {
+                "Run `easy_journal auth fitbit` again"
+            }
+            JournalError::OAuthFailed(message) if message.contains("Strava") => {
+                "Run `easy_journal auth strava` again"
+            }
+            JournalError::OAuthConfigMissing(_)
+            | JournalError::OAuthFailed(_)
+            | JournalError::GoogleTasksFailed(_) => "Run `easy_journal auth google`",
+            JournalError::ReauthRequired(message) if message.contains("GitLab") => {
+                "Run `easy_journal auth gitlab` to sign in again"
+            }
+            JournalError::ReauthRequired(message) if message.contains("Fitbit") => {
+                "Run `easy_journal auth fitbit` to sign in again"
+            }
+            JournalError::ReauthRequired(message) if message.contains("Strava") => {
+                "Run `easy_journal auth strava` to sign in again"
+            }
+            JournalError::ReauthRequired(_) => "Run `easy_journal auth google` to sign in again",
+            JournalError::SecretsFailed(_) => {
+                "Set TOKEN_PASSPHRASE in .env, or run `easy_journal auth` for the provider again"
+            }
+            JournalError::HttpStatus {
+                provider,
+                status: 401 | 403,
+                ..
+            } => match provider.as_str() {
+                "GitHub" => {
+                    "Run `easy_journal auth github` again, or check GITHUB_TOKEN in .env (needs the repo and read:org scopes)"
+                }
+                "GitLab" => {
+                    "Run `easy_journal auth gitlab` again, or check GITLAB_TOKEN in .env (needs the read_api scope)"
+                }
+                "WebDAV" => "Check WEBDAV_USERNAME and WEBDAV_PASSWORD in .env",
+                "S3" => "Check S3_ACCESS_KEY_ID and S3_SECRET_ACCESS_KEY in .env",
+                "Matrix" => "Check MATRIX_ACCESS_TOKEN in .env",
+                "Discord" => "Check DISCORD_BOT_TOKEN in .env",
+                "LLM" => "Check LLM_API_KEY in .env",
+                "Fitbit" => "Run `easy_journal auth fitbit` again",
+                "Oura" => "Check OURA_TOKEN in .env",
+                "WakaTime" => "Check WAKATIME_API_KEY in .env",
+                "Toggl" => "Check TOGGL_API_TOKEN in .env",
+                "Last.fm" => "Check LASTFM_API_KEY in .env",
+                "Readwise" => "Check READWISE_TOKEN in .env",
+                "Mastodon" => "Check MASTODON_ACCESS_TOKEN in .env",
+                "PagerDuty" => "Check PAGERDUTY_TOKEN in .env (a user API token)",
+                "Opsgenie" => "Check OPSGENIE_API_KEY in .env",
+                "Confluence" => "Check CONFLUENCE_EMAIL and CONFLUENCE_API_TOKEN in .env",
+                "Sentry" => "Check SENTRY_AUTH_TOKEN in .env (needs the event:read scope)",
+                "Strava" => "Run `easy_journal auth strava` again",
+                _ => return None,
+            },
+            JournalError::GitHubFailed(_) => {
+                "Run `easy_journal auth github`, or check GITHUB_TOKEN in .env"
+            }
+            JournalError::GitLabFailed(_) => {
+                "Run `easy_journal auth gitlab`, or check GITLAB_TOKEN and GITLAB_HOST in .env"
+            }
+            JournalError::WeatherFailed(_) => {
+                "Check WEATHER_LATITUDE and WEATHER_LONGITUDE in .env"
+            }
+            JournalError::FitbitFailed(_) => {
+                "Run `easy_journal auth fitbit`, or check FITBIT_CLIENT_ID in .env"
+            }
+            JournalError::StravaFailed(_) => {
+                "Run `easy_journal auth strava`, or check STRAVA_CLIENT_ID in .env"
+            }
+            JournalError::WakaTimeFailed(_) => {
+                "Check WAKATIME_API_KEY and WAKATIME_API_URL in .env"
+            }
+            JournalError::TogglFailed(_) => "Check TOGGL_API_TOKEN in .env",
+            JournalError::LastFmFailed(_) => "Check LASTFM_API_KEY and LASTFM_USER in .env",
+            JournalError::RssFailed(_) => "Check the feed URLs in RSS_FEEDS",
+            JournalError::MastodonFailed(_) => {
+                "Check MASTODON_INSTANCE and MASTODON_ACCESS_TOKEN in .env"
+            }
+            JournalError::ConfluenceFailed(_) => {
+                "Check CONFLUENCE_URL (including /wiki on Cloud) and CONFLUENCE_API_TOKEN in .env"
+            }
+            JournalError::SentryFailed(_) => {
+                "Check SENTRY_AUTH_TOKEN, SENTRY_ORG and SENTRY_PROJECTS in .env"
+            }
+            JournalError::OnCallFailed(_) => {
+                "Check PAGERDUTY_TOKEN, or OPSGENIE_API_KEY and OPSGENIE_USER, in .env"
+            }
+            JournalError::ImapFailed(_) => {
+                "Check IMAP_HOST, IMAP_USERNAME and IMAP_PASSWORD in .env (Gmail and iCloud need an app password)"
+            }
+            JournalError::ReadLaterFailed(_) => "Check READWISE_TOKEN in .env",
+            JournalError::OuraFailed(_) => "Check OURA_TOKEN in .env",
+            JournalError::HealthFailed(_) => {
+                "Check that HEALTH_SLEEP_FILE points to a sleep export (JSON or export.xml)"
+            }
+            JournalError::StorageFailed(_) => "Check STORAGE_BACKEND and its settings in .env",
+            JournalError::LockFailed(_) => {
+                "Another easy_journal process is writing to the journal; try again when it finishes"
+            }
+            JournalError::IndexFailed(_) => "Run `easy_journal reindex` to rebuild the index",
+            JournalError::HookFailed(_) => "Check the HOOK_* commands in .env",
+            JournalError::BookFailed(message) if message.contains("Could not run") => {
+                "Install mdbook with `cargo install mdbook`"
+            }
+            JournalError::NotifyFailed(_) => {
+                "Notifications need a running notification service (Linux) or permission for your terminal (macOS)"
+            }
+            JournalError::EmailFailed(_) => {
+                "Check SMTP_HOST, SMTP_PORT, SMTP_USERNAME and SMTP_PASSWORD in .env"
+            }
+            JournalError::TelegramFailed(_) => "Check TELEGRAM_BOT_TOKEN in .env",
+            JournalError::MatrixFailed(_) => "Check MATRIX_HOMESERVER and MATRIX_ROOM in .env",
+            JournalError::DictationFailed(_) => {
+                "Check the microphone and WHISPER_MODEL / WHISPER_CPP_BIN in .env"
+            }
+            JournalError::LlmFailed(_) => "Check LLM_API_URL and LLM_MODEL in .env",
+            JournalError::DiscordFailed(_) => {
+                "Check DISCORD_BOT_TOKEN in .env and enable the Message Content intent for the bot"
+            }
+            _ => return None,
+        };
+        Some(hint.to_string())
+    }
+
+    /// Machine-readable form of the error for `--error-format json`
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut value = serde_json::json!({
+            "error": self.kind(),
+            "message": self.to_string(),
+            "exit_code": self.exit_code(),
+            "hint": self.hint(),
+        });
+
+        match self {
+            JournalError::FileIo { path, .. } | JournalError::_TemplateNotFound(path) => {
+                value["path"] = path.display().to_string().into();
+            }
+            JournalError::HttpStatus {
+                provider, status, ..
+            } => {
+                value["provider"] = provider.as_str().into();
+                value["status"] = (*status).into();
+            }
+            _ => {}
+        }
+        value
+    }
+}
+
+/// Turn a non-success HTTP response into [`JournalError::HttpStatus`]
+pub(crate) async fn check_response(
+    response: reqwest::Response,
+    provider: &str,
+) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let url = response.url().path().to_string();
+    let body = response.text().await.unwrap_or_default();
+    let message = match body.trim() {
+        "" => url,
+        body => format!("{} ({})", url, body.chars().take(200).collect::<String>()),
+    };
+    Err(JournalError::HttpStatus {
+        provider: provider.to_string(),
+        status: status.as_u16(),
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_and_hints() {
+        let unauthorized = JournalError::HttpStatus {
+            provider: "GitHub".to_string(),
+            status: 401,
+            message: "/issues".to_string(),
+        };
+        assert_eq!(unauthorized.exit_code(), 5);
+        assert!(unauthorized.hint().unwrap().contains("GITHUB_TOKEN"));
+
+        let unavailable = JournalError::HttpStatus {
+            provider: "GitHub".to_string(),
+            status: 503,
+            message: "/issues".to_string(),
+        };
+        assert_eq!(unavailable.exit_code(), 6);
+        assert!(unavailable.hint().is_none());
+
+        let missing = JournalError::EntryNotFound("2025-12-29".to_string());
+        assert_eq!(missing.exit_code(), 3);
+        assert_eq!(
+            missing.hint().unwrap(),
+            "Create it with `easy_journal new --date 2025-12-29`"
+        );
+    }
+
+    #[test]
+    fn test_json_report() {
+        let error = JournalError::FileIo {
+            path: PathBuf::from("template.md"),
+            source: std::io::Error::from(std::io::ErrorKind::PermissionDenied),
+        };
+        let json = error.to_json();
+
+        assert_eq!(json["error"], "io");
+        assert_eq!(json["exit_code"], 7);
+        assert_eq!(json["path"], "template.md");
+        assert!(json["hint"].is_null());
+        assert!(
+            json["message"]
+                .as_str()
+                .unwrap()
+                .starts_with("template.md: ")
+        );
+    }
+}
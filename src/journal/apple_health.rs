@@ -0,0 +1,276 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime};
+use serde::Deserialize;
+use std::env;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use tokio::task;
+
+use crate::config::AppleHealthConfig;
+use crate::error::{JournalError, Result};
+use crate::journal::parser::format_duration;
+
+/// Record type of sleep samples in the Health app's `export.xml`
+const SLEEP_RECORD: &str = "HKCategoryTypeIdentifierSleepAnalysis";
+
+/// One sleep sample, as written by the "Find Health Samples" Shortcuts action
+/// or a companion app
+#[derive(Deserialize, Debug, Clone)]
+struct Sample {
+    #[serde(alias = "startDate")]
+    start: String,
+    #[serde(alias = "endDate")]
+    end: String,
+    /// Sleep stage, e.g. `Core`, `Deep`, `REM`, `Awake`, `In Bed`, or the
+    /// HealthKit name `HKCategoryValueSleepAnalysisAsleepCore`
+    value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    InBed,
+    Awake,
+    /// Asleep without stage data (older devices, third-party apps)
+    Asleep,
+    Core,
+    Deep,
+    Rem,
+}
+
+impl Stage {
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.to_lowercase().replace([' ', '_'], "");
+        let value = value
+            .strip_prefix("hkcategoryvaluesleepanalysis")
+            .unwrap_or(&value);
+        match value.strip_prefix("asleep").unwrap_or(value) {
+            "inbed" => Some(Stage::InBed),
+            "awake" => Some(Stage::Awake),
+            "" | "unspecified" => Some(Stage::Asleep),
+            "core" => Some(Stage::Core),
+            "deep" => Some(Stage::Deep),
+            "rem" => Some(Stage::Rem),
+            _ => None,
+        }
+    }
+
+    fn is_asleep(self) -> bool {
+        !matches!(self, Stage::InBed | Stage::Awake)
+    }
+}
+
+/// `2025-12-29T07:08:00+01:00`, `2025-12-29 07:08:00 +0100` (export.xml) or a
+/// local time without offset, as local time
+fn parse_time(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S %z"))
+        .map(|at| at.with_timezone(&Local).naive_local())
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok())
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok())
+}
+
+/// Value of `name="..."` in an XML element
+fn attribute<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let start = line.find(&format!(" {}=\"", name))? + name.len() + 3;
+    let len = line[start..].find('"')?;
+    Some(&line[start..start + len])
+}
+
+fn read_export_xml(path: &Path) -> Result<Vec<Sample>> {
+    let file = std::fs::File::open(path).map_err(|e| JournalError::FileIo {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+
+    // export.xml easily runs to hundreds of MB; scan it line by line for the
+    // sleep records instead of parsing the whole document
+    let mut samples = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| JournalError::FileIo {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        if !line.contains(SLEEP_RECORD) {
+            continue;
+        }
+        if let (Some(start), Some(end), Some(value)) = (
+            attribute(&line, "startDate"),
+            attribute(&line, "endDate"),
+            attribute(&line, "value"),
+        ) {
+            samples.push(Sample {
+                start: start.to_string(),
+                end: end.to_string(),
+                value: value.to_string(),
+            });
+        }
+    }
+    Ok(samples)
+}
+
+fn read_samples(path: &Path) -> Result<Vec<Sample>> {
+    if path.extension().is_some_and(|ext| ext == "xml") {
+        return read_export_xml(path);
+    }
+    let content = std::fs::read_to_string(path).map_err(|e| JournalError::FileIo {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    serde_json::from_str(&content).map_err(|e| {
+        JournalError::HealthFailed(format!(
+            "{} is not a list of sleep samples: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Total minutes covered by `intervals`, counting overlaps (several sources
+/// recording the same night) once
+fn covered_minutes(mut intervals: Vec<(NaiveDateTime, NaiveDateTime)>) -> u32 {
+    intervals.sort();
+    let mut total = Duration::zero();
+    let mut current: Option<(NaiveDateTime, NaiveDateTime)> = None;
+    for (start, end) in intervals {
+        current = match current {
+            Some((from, to)) if start <= to => Some((from, to.max(end))),
+            Some((from, to)) => {
+                total += to - from;
+                Some((start, end))
+            }
+            None => Some((start, end)),
+        };
+    }
+    if let Some((from, to)) = current {
+        total += to - from;
+    }
+    total.num_minutes() as u32
+}
+
+/// Summary of the night before `date` (noon to noon): `**Sleep Time**` for the
+/// footer and a line with bedtime, wake time and stages
+fn summarize_night(samples: &[Sample], date: NaiveDate) -> Option<String> {
+    let window_end = date.and_hms_opt(12, 0, 0)?;
+    let window_start = window_end - Duration::days(1);
+
+    let night: Vec<(Stage, NaiveDateTime, NaiveDateTime)> = samples
+        .iter()
+        .filter_map(|sample| {
+            let stage = Stage::parse(&sample.value)?;
+            let start = parse_time(&sample.start)?.max(window_start);
+            let end = parse_time(&sample.end)?.min(window_end);
+            (start < end).then_some((stage, start, end))
+        })
+        .collect();
+
+    let asleep = |filter: &dyn Fn(Stage) -> bool| {
+        covered_minutes(
+            night
+                .iter()
+                .filter(|(stage, ..)| filter(*stage))
+                .map(|&(_, start, end)| (start, end))
+                .collect(),
+        )
+    };
+    let total = asleep(&|stage| stage.is_asleep());
+    if total == 0 {
+        return None;
+    }
+
+    let asleep_samples = night.iter().filter(|(stage, ..)| stage.is_asleep());
+    let fell_asleep = asleep_samples.clone().map(|&(_, start, _)| start).min()?;
+    let woke_up = asleep_samples.map(|&(_, _, end)| end).max()?;
+
+    let stages: Vec<String> = [
+        ("Core", Stage::Core),
+        ("Deep", Stage::Deep),
+        ("REM", Stage::Rem),
+    ]
+    .into_iter()
+    .map(|(label, stage)| (label, asleep(&|s| s == stage)))
+    .filter(|&(_, minutes)| minutes > 0)
+    .map(|(label, minutes)| format!("{} {}", label, format_duration(minutes)))
+    .collect();
+
+    let mut summary = format!(
+        "- 😴 Slept {} – {}",
+        fell_asleep.format("%H:%M"),
+        woke_up.format("%H:%M")
+    );
+    if !stages.is_empty() {
+        summary.push_str(&format!(" ({})", stages.join(", ")));
+    }
+    Some(format!(
+        "**Sleep Time**: {}\n{}",
+        format_duration(total),
+        summary
+    ))
+}
+
+fn read_night(config: &AppleHealthConfig, date: NaiveDate) -> Result<Option<String>> {
+    if env::consts::OS != "macos" {
+        return Ok(None);
+    }
+    if !config.sleep_file.exists() {
+        tracing::debug!(
+            path = %config.sleep_file.display(),
+            "No Apple Health sleep export; run the export Shortcut or set HEALTH_SLEEP_FILE"
+        );
+        return Ok(None);
+    }
+    Ok(summarize_night(&read_samples(&config.sleep_file)?, date))
+}
+
+/// Last night's sleep from the local Apple Health export; nothing leaves the machine
+pub async fn fetch_sleep(config: &AppleHealthConfig, date: NaiveDate) -> Result<Option<String>> {
+    let config = config.clone();
+    task::spawn_blocking(move || read_night(&config, date))
+        .await
+        .map_err(|e| JournalError::HealthFailed(format!("Task join error: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_night() {
+        let samples: Vec<Sample> = serde_json::from_str(
+            r#"[
+                {"startDate": "2025-12-28T23:10:00", "endDate": "2025-12-29T07:15:00", "value": "In Bed"},
+                {"startDate": "2025-12-28T23:30:00", "endDate": "2025-12-29T02:00:00", "value": "Core"},
+                {"startDate": "2025-12-29T02:00:00", "endDate": "2025-12-29T03:00:00", "value": "Deep"},
+                {"startDate": "2025-12-29T03:00:00", "endDate": "2025-12-29T03:20:00", "value": "Awake"},
+                {"startDate": "2025-12-29T03:20:00", "endDate": "2025-12-29T05:00:00", "value": "HKCategoryValueSleepAnalysisAsleepREM"},
+                {"startDate": "2025-12-29T04:30:00", "endDate": "2025-12-29T07:00:00", "value": "Asleep"},
+                {"startDate": "2025-12-27T23:00:00", "endDate": "2025-12-28T07:00:00", "value": "Asleep"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            summarize_night(&samples, NaiveDate::from_ymd_opt(2025, 12, 29).unwrap()).unwrap(),
+            "**Sleep Time**: 7h 10m\n\
+             - 😴 Slept 23:30 – 07:00 (Core 2h 30m, Deep 1h 00m, REM 1h 40m)"
+        );
+        assert_eq!(
+            summarize_night(&samples, NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_export_xml_attributes() {
+        let line = r#"  <Record type="HKCategoryTypeIdentifierSleepAnalysis" sourceName="Watch" startDate="2025-12-28 23:30:00 +0100" endDate="2025-12-29 02:00:00 +0100" value="HKCategoryValueSleepAnalysisAsleepCore"/>"#;
+        assert_eq!(
+            attribute(line, "startDate"),
+            Some("2025-12-28 23:30:00 +0100")
+        );
+        assert_eq!(
+            attribute(line, "value").and_then(Stage::parse),
+            Some(Stage::Core)
+        );
+        assert!(parse_time(attribute(line, "endDate").unwrap()).is_some());
+    }
+}
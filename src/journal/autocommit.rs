@@ -0,0 +1,402 @@
+use chrono::{DateTime, Local, TimeZone};
+use git2::{
+    DiffFormat, DiffOptions, Index, IndexAddOption, Oid, Repository, Signature, Sort, Tree,
+};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+
+/// A commit that touched a given entry
+#[derive(Serialize)]
+pub struct LogEntry {
+    pub id: String,
+    pub time: DateTime<Local>,
+    pub message: String,
+    pub insertions: usize,
+    pub deletions: usize,
+    /// Unified diff of the entry, when requested
+    pub patch: Option<String>,
+}
+
+fn git_error(e: git2::Error) -> JournalError {
+    JournalError::GitFailed(e.message().to_string())
+}
+
+fn open_repo(journal_dir: &Path) -> Result<Repository> {
+    Repository::discover(journal_dir).map_err(|_| {
+        JournalError::GitFailed(format!(
+            "{} is not inside a git repository",
+            journal_dir.display()
+        ))
+    })
+}
+
+/// Path of `path` relative to the repository work tree (the file itself may be gone)
+fn repo_relative(repo: &Repository, path: &Path) -> Result<PathBuf> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| JournalError::GitFailed("Repository has no work tree".to_string()))?;
+    let workdir = fs::canonicalize(workdir)?;
+
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| JournalError::GitFailed(format!("Not a file: {}", path.display())))?;
+    let absolute = fs::canonicalize(parent)?.join(file_name);
+
+    absolute
+        .strip_prefix(&workdir)
+        .map(Path::to_path_buf)
+        .map_err(|_| {
+            JournalError::GitFailed(format!("{} is outside the repository", path.display()))
+        })
+}
+
+/// Stage `paths` (removing the ones that no longer exist) and commit them.
+///
+/// Returns `None` when nothing changed.
+pub fn commit_paths(journal_dir: &Path, paths: &[&Path], message: &str) -> Result<Option<Oid>> {
+    let repo = open_repo(journal_dir)?;
+    let mut index = repo.index().map_err(git_error)?;
+
+    for path in paths {
+        let relative = repo_relative(&repo, path)?;
+        if path.exists() {
+            index.add_path(&relative).map_err(git_error)?;
+        } else if index.get_path(&relative, 0).is_some() {
+            index.remove_path(&relative).map_err(git_error)?;
+        }
+    }
+    commit_index(&repo, &mut index, message)
+}
+
+/// Stage every change under `journal_dir`, deletions included, and commit it.
+///
+/// Returns `None` when nothing changed.
+pub fn commit_all(journal_dir: &Path, message: &str) -> Result<Option<Oid>> {
+    let repo = open_repo(journal_dir)?;
+    let relative = repo_relative(&repo, &fs::canonicalize(journal_dir)?)?;
+    let pathspec = match relative.to_str() {
+        Some("") => "*".to_string(),
+        _ => relative.display().to_string(),
+    };
+
+    let mut index = repo.index().map_err(git_error)?;
+    index
+        .add_all([&pathspec], IndexAddOption::DEFAULT, None)
+        .map_err(git_error)?;
+    index.update_all([&pathspec], None).map_err(git_error)?;
+    commit_index(&repo, &mut index, message)
+}
+
+/// Name of the checked-out branch
+pub fn current_branch(journal_dir: &Path) -> Result<String> {
+    let repo = open_repo(journal_dir)?;
+    let head = repo.head().map_err(git_error)?;
+    head.shorthand()
+        .filter(|_| head.is_branch())
+        .map(str::to_string)
+        .ok_or_else(|| JournalError::GitFailed("HEAD is not on a branch".to_string()))
+}
+
+/// `git pull --rebase` and then `git push` `branch` to `remote`; there's
+/// nothing to pull while the remote doesn't have the branch yet. These shell
+/// out to git since git2 is built without network transports.
+pub fn pull_and_push(journal_dir: &Path, remote: &str, branch: &str) -> Result<()> {
+    // `ls-remote --exit-code` exits with 2 when the remote has no such branch
+    let listed = git(
+        journal_dir,
+        &["ls-remote", "--exit-code", "--heads", remote, branch],
+    )?;
+    if listed.status.code() != Some(2) {
+        check(&listed, "ls-remote")?;
+        run_git(
+            journal_dir,
+            &["pull", "--rebase", "--quiet", remote, branch],
+        )?;
+    }
+    run_git(journal_dir, &["push", "--quiet", remote, branch])
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<Output> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| JournalError::GitFailed(format!("Failed to run git: {}", e)))
+}
+
+/// Fail with what git printed to stderr when it exited unsuccessfully
+fn check(output: &Output, command: &str) -> Result<()> {
+    if !output.status.success() {
+        return Err(JournalError::GitFailed(format!(
+            "git {} failed: {}",
+            command,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Run git in `dir`, failing with what it printed to stderr
+fn run_git(dir: &Path, args: &[&str]) -> Result<()> {
+    check(&git(dir, args)?, args[0])
+}
+
+/// Commit the staged `index` on top of HEAD; `None` if the tree didn't change
+fn commit_index(repo: &Repository, index: &mut Index, message: &str) -> Result<Option<Oid>> {
+    index.write().map_err(git_error)?;
+
+    let tree_id = index.write_tree().map_err(git_error)?;
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    if parent.as_ref().is_some_and(|p| p.tree_id() == tree_id) {
+        return Ok(None);
+    }
+
+    let tree = repo.find_tree(tree_id).map_err(git_error)?;
+    let signature = repo
+        .signature()
+        .or_else(|_| Signature::now("easy_journal", "easy_journal@localhost"))
+        .map_err(git_error)?;
+    let parents: Vec<_> = parent.iter().collect();
+
+    let id = repo
+        .commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .map_err(git_error)?;
+    Ok(Some(id))
+}
+
+/// Create a repository in `dir` (or reuse an existing one), point `origin` at
+/// `remote` if given and commit every file not ignored.
+///
+/// Returns `None` when there was nothing new to commit.
+pub fn init_repo(dir: &Path, remote: Option<&str>, message: &str) -> Result<Option<Oid>> {
+    let repo = match Repository::open(dir) {
+        Ok(repo) => repo,
+        Err(_) => Repository::init(dir).map_err(git_error)?,
+    };
+
+    if let Some(url) = remote {
+        match repo.find_remote("origin") {
+            Ok(origin) if origin.url() == Some(url) => {}
+            Ok(_) => repo.remote_set_url("origin", url).map_err(git_error)?,
+            Err(_) => {
+                repo.remote("origin", url).map_err(git_error)?;
+            }
+        }
+    }
+
+    let mut index = repo.index().map_err(git_error)?;
+    index
+        .add_all(["*"], IndexAddOption::DEFAULT, None)
+        .map_err(git_error)?;
+    commit_index(&repo, &mut index, message)
+}
+
+/// Commit `paths` when `GIT_AUTOCOMMIT` is enabled; failures only warn
+pub fn record(config: &Config, paths: &[&Path], message: &str) {
+    if !config.git_autocommit {
+        return;
+    }
+    if let Err(e) = commit_paths(&config.journal_dir, paths, message) {
+        tracing::warn!("Could not commit journal change: {}", e);
+    }
+}
+
+fn blob_at(tree: Option<&Tree>, path: &Path) -> Option<Oid> {
+    tree?.get_path(path).ok().map(|entry| entry.id())
+}
+
+/// Commits that changed `path`, newest first
+pub fn entry_log(journal_dir: &Path, path: &Path, with_patch: bool) -> Result<Vec<LogEntry>> {
+    let repo = open_repo(journal_dir)?;
+    let relative = repo_relative(&repo, path)?;
+
+    let mut walk = repo.revwalk().map_err(git_error)?;
+    if walk.push_head().is_err() {
+        // No commits yet
+        return Ok(Vec::new());
+    }
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(git_error)?;
+
+    let mut log = Vec::new();
+    for id in walk {
+        let commit = repo
+            .find_commit(id.map_err(git_error)?)
+            .map_err(git_error)?;
+        let tree = commit.tree().map_err(git_error)?;
+        let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+
+        if blob_at(Some(&tree), &relative) == blob_at(parent_tree.as_ref(), &relative) {
+            continue;
+        }
+
+        let mut options = DiffOptions::new();
+        options.pathspec(&relative);
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))
+            .map_err(git_error)?;
+        let stats = diff.stats().map_err(git_error)?;
+
+        let patch = if with_patch {
+            let mut text = String::new();
+            diff.print(DiffFormat::Patch, |_, _, line| {
+                if matches!(line.origin(), '+' | '-' | ' ') {
+                    text.push(line.origin());
+                }
+                text.push_str(&String::from_utf8_lossy(line.content()));
+                true
+            })
+            .map_err(git_error)?;
+            Some(text)
+        } else {
+            None
+        };
+
+        log.push(LogEntry {
+            id: commit.id().to_string()[..8].to_string(),
+            time: Local
+                .timestamp_opt(commit.time().seconds(), 0)
+                .single()
+                .unwrap_or_else(Local::now),
+            message: commit.summary().unwrap_or_default().to_string(),
+            insertions: stats.insertions(),
+            deletions: stats.deletions(),
+            patch,
+        });
+    }
+
+    Ok(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_and_log() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_git_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("journal/2025/12")).unwrap();
+        Repository::init(&dir).unwrap();
+
+        let journal_dir = dir.join("journal");
+        let entry = journal_dir.join("2025/12/29.md");
+        let other = journal_dir.join("2025/12/30.md");
+
+        fs::write(&entry, "first\n").unwrap();
+        assert!(
+            commit_paths(&journal_dir, &[&entry], "Create 2025-12-29")
+                .unwrap()
+                .is_some()
+        );
+
+        // Nothing changed: no empty commit
+        assert!(
+            commit_paths(&journal_dir, &[&entry], "Update 2025-12-29")
+                .unwrap()
+                .is_none()
+        );
+
+        fs::write(&other, "other\n").unwrap();
+        commit_paths(&journal_dir, &[&other], "Create 2025-12-30").unwrap();
+
+        fs::write(&entry, "first\nsecond\n").unwrap();
+        commit_paths(&journal_dir, &[&entry], "Update 2025-12-29").unwrap();
+
+        let log = entry_log(&journal_dir, &entry, true).unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].insertions, 1);
+        assert!(log[0].patch.as_ref().unwrap().contains("+second"));
+
+        fs::remove_file(&entry).unwrap();
+        commit_paths(&journal_dir, &[&entry], "Delete 2025-12-29").unwrap();
+        assert_eq!(entry_log(&journal_dir, &entry, false).unwrap().len(), 3);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_commit_all() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_sync_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("journal/2025/12")).unwrap();
+        Repository::init(&dir).unwrap();
+
+        let journal_dir = dir.join("journal");
+        fs::write(journal_dir.join("2025/12/29.md"), "first\n").unwrap();
+        fs::write(journal_dir.join("SUMMARY.md"), "# Summary\n").unwrap();
+        fs::write(dir.join("notes.txt"), "outside the journal\n").unwrap();
+        assert!(
+            commit_all(&journal_dir, "journal: 2025-12-29")
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            commit_all(&journal_dir, "journal: 2025-12-29")
+                .unwrap()
+                .is_none()
+        );
+
+        let repo = Repository::open(&dir).unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        assert!(tree.get_path(Path::new("journal/2025/12/29.md")).is_ok());
+        assert!(tree.get_path(Path::new("notes.txt")).is_err());
+
+        fs::remove_file(journal_dir.join("2025/12/29.md")).unwrap();
+        commit_all(&journal_dir, "journal: 2025-12-30").unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        assert!(tree.get_path(Path::new("journal/2025/12/29.md")).is_err());
+        assert!(tree.get_path(Path::new("journal/SUMMARY.md")).is_ok());
+        assert!(current_branch(&journal_dir).is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_init_repo() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_init_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("journal")).unwrap();
+        fs::write(dir.join("journal/README.md"), "# Journal\n").unwrap();
+        fs::write(dir.join(".gitignore"), ".env\n").unwrap();
+        fs::write(dir.join(".env"), "GITHUB_TOKEN=secret\n").unwrap();
+
+        let remote = "git@example.com:me/journal.git";
+        assert!(
+            init_repo(&dir, Some(remote), "Initialize journal")
+                .unwrap()
+                .is_some()
+        );
+        // Running it again adds nothing
+        assert!(
+            init_repo(&dir, Some(remote), "Initialize journal")
+                .unwrap()
+                .is_none()
+        );
+
+        let repo = Repository::open(&dir).unwrap();
+        assert_eq!(repo.find_remote("origin").unwrap().url(), Some(remote));
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        assert!(tree.get_path(Path::new("journal/README.md")).is_ok());
+        assert!(tree.get_path(Path::new(".env")).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
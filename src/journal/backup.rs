@@ -0,0 +1,631 @@
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::{self, Layout, write_atomic};
+use crate::journal::secrets;
+
+/// Archive format written by this version
+pub const FORMAT_VERSION: u32 = 1;
+/// First member of every archive
+pub const MANIFEST_NAME: &str = "manifest.json";
+/// Archive names are `easy_journal-YYYYMMDD-HHMMSS[-N].tar.zst[.enc]`
+const ARCHIVE_PREFIX: &str = "easy_journal-";
+const ARCHIVE_SUFFIX: &str = ".tar.zst";
+const ENCRYPTED_SUFFIX: &str = ".enc";
+/// Start of an encrypted archive, followed by `secrets::seal_bytes` output
+const ENCRYPTED_MAGIC: &[u8] = b"EJBACKUP1\n";
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+/// Path inside the archive and content of each file
+pub type ArchiveFiles = Vec<(String, Vec<u8>)>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// Path inside the archive: `journal/...` for the journal directory,
+    /// otherwise relative to the journal root
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: u32,
+    pub created: DateTime<Local>,
+    pub app_version: String,
+    /// Newest daily entry in the archive
+    pub latest_entry: Option<NaiveDate>,
+    pub files: Vec<ManifestFile>,
+}
+
+fn backup_error(context: &str) -> impl FnOnce(std::io::Error) -> JournalError + '_ {
+    move |e| JournalError::BackupFailed(format!("{}: {}", context, e))
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// Where `backup` writes when no `--to` is given
+pub fn default_dir(config: &Config) -> PathBuf {
+    config
+        .backup
+        .dir
+        .clone()
+        .unwrap_or_else(|| config.root.join(".easy_journal/backups"))
+}
+
+fn collect_dir(dir: &Path, prefix: &str, files: &mut Vec<(String, PathBuf)>) -> Result<()> {
+    for item in fs::read_dir(dir).map_err(JournalError::at_path(dir))? {
+        let path = item?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        // Hidden files hold tokens, the index and the backups themselves
+        if name.starts_with('.') {
+            continue;
+        }
+        let archive_path = format!("{}/{}", prefix, name);
+        if path.is_dir() {
+            collect_dir(&path, &archive_path, files)?;
+        } else {
+            files.push((archive_path, path));
+        }
+    }
+    Ok(())
+}
+
+/// Files that go into a backup, with their paths inside the archive
+pub fn collect(config: &Config) -> Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    if config.journal_dir.exists() {
+        collect_dir(&config.journal_dir, "journal", &mut files)?;
+    }
+    // The built book, when the journal directory is the root itself
+    let book = config.root.join("book");
+    files.retain(|(_, path)| !path.starts_with(&book));
+    for (name, path) in archive_extras(config) {
+        if path.is_file() {
+            files.push((name.to_string(), path));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Templates and configuration kept next to the journal directory. `.env`
+/// holds tokens and is left out.
+pub fn archive_extras(config: &Config) -> [(&'static str, PathBuf); 5] {
+    [
+        ("template.md", config.template_path.clone()),
+        ("month_template.md", config.month_template_path.clone()),
+        ("year_template.md", config.year_template_path.clone()),
+        ("week_template.md", config.week_template_path.clone()),
+        ("book.toml", config.root.join("book.toml")),
+    ]
+}
+
+/// Write a timestamped archive of the journal, templates and config to `dir`
+/// and check it can be read back. With a passphrase the archive is encrypted
+/// and named `.tar.zst.enc`. Returns its path and manifest.
+pub fn create(
+    config: &Config,
+    dir: &Path,
+    passphrase: Option<&str>,
+) -> Result<(PathBuf, Manifest)> {
+    let (manifest, contents) = snapshot(config)?;
+
+    fs::create_dir_all(dir).map_err(JournalError::at_path(dir))?;
+    let stamp = manifest.created.format("%Y%m%d-%H%M%S").to_string();
+    let suffix = match passphrase {
+        Some(_) => format!("{}{}", ARCHIVE_SUFFIX, ENCRYPTED_SUFFIX),
+        None => ARCHIVE_SUFFIX.to_string(),
+    };
+    let mut archive_path = dir.join(format!("{}{}{}", ARCHIVE_PREFIX, stamp, suffix));
+    // Another backup in the same second, e.g. the one `restore --from` takes
+    let mut n = 1;
+    while archive_path.exists() {
+        n += 1;
+        archive_path = dir.join(format!("{}{}-{}{}", ARCHIVE_PREFIX, stamp, n, suffix));
+    }
+
+    write(&archive_path, &manifest, &contents, passphrase)?;
+    Ok((archive_path, manifest))
+}
+
+/// Like [`create`], but to the file at `path`. It is gzip-compressed when the
+/// name ends in `.tar.gz` or `.tgz` (before any `.enc`), zstd otherwise.
+pub fn create_at(config: &Config, path: &Path, passphrase: Option<&str>) -> Result<Manifest> {
+    let (manifest, contents) = snapshot(config)?;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(JournalError::at_path(parent))?;
+    }
+    write(path, &manifest, &contents, passphrase)?;
+    Ok(manifest)
+}
+
+/// The manifest and content of every file that goes into a backup
+fn snapshot(config: &Config) -> Result<(Manifest, ArchiveFiles)> {
+    let mut contents = Vec::new();
+    for (name, path) in collect(config)? {
+        let content = fs::read(&path).map_err(JournalError::at_path(&path))?;
+        contents.push((name, content));
+    }
+
+    let manifest = Manifest {
+        format: FORMAT_VERSION,
+        created: Local::now(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        latest_entry: filesystem::list_entries(&config.journal_dir, config.layout)?
+            .last()
+            .map(|(date, _)| *date),
+        files: contents
+            .iter()
+            .map(|(name, content)| ManifestFile {
+                path: name.to_string(),
+                size: content.len() as u64,
+                sha256: sha256_hex(content),
+            })
+            .collect(),
+    };
+    Ok((manifest, contents))
+}
+
+fn is_gzip_name(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let name = name.strip_suffix(ENCRYPTED_SUFFIX).unwrap_or(name);
+    name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+/// Write the archive next to `path` first and move it there once it reads back
+fn write(
+    path: &Path,
+    manifest: &Manifest,
+    contents: &[(String, Vec<u8>)],
+    passphrase: Option<&str>,
+) -> Result<()> {
+    let manifest_json = serde_json::to_vec_pretty(manifest)
+        .map_err(|e| JournalError::BackupFailed(format!("Failed to encode manifest: {}", e)))?;
+    let mut builder = tar::Builder::new(Vec::new());
+    let mtime = manifest.created.timestamp().max(0) as u64;
+    for (name, content) in std::iter::once((MANIFEST_NAME, manifest_json.as_slice())).chain(
+        contents
+            .iter()
+            .map(|(name, content)| (name.as_str(), content.as_slice())),
+    ) {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, name, content)
+            .map_err(backup_error("Failed to write archive"))?;
+    }
+    let tar = builder
+        .into_inner()
+        .map_err(backup_error("Failed to write archive"))?;
+
+    let compressed = if is_gzip_name(path) {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(&tar)
+            .and_then(|_| encoder.finish())
+            .map_err(backup_error("Failed to compress"))?
+    } else {
+        zstd::encode_all(tar.as_slice(), 0).map_err(backup_error("Failed to compress"))?
+    };
+    let data = match passphrase {
+        Some(passphrase) => {
+            let mut data = ENCRYPTED_MAGIC.to_vec();
+            data.extend(secrets::seal_bytes(
+                &compressed,
+                passphrase,
+                ENCRYPTED_MAGIC,
+            )?);
+            data
+        }
+        None => compressed,
+    };
+
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    fs::write(&partial, &data).map_err(JournalError::at_path(&partial))?;
+    if let Err(e) = verify(&partial, passphrase) {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    fs::rename(&partial, path).map_err(JournalError::at_path(path))?;
+    Ok(())
+}
+
+/// Whether the archive at `path` was written with a passphrase
+pub fn is_encrypted(path: &Path) -> Result<bool> {
+    let mut start = [0u8; ENCRYPTED_MAGIC.len()];
+    let mut file = fs::File::open(path).map_err(JournalError::at_path(path))?;
+    let read = file.read(&mut start).map_err(JournalError::at_path(path))?;
+    Ok(start[..read] == *ENCRYPTED_MAGIC)
+}
+
+/// The manifest and every file of an archive, in archive order. Encrypted
+/// archives need the passphrase they were written with.
+pub fn read_archive(path: &Path, passphrase: Option<&str>) -> Result<(Manifest, ArchiveFiles)> {
+    let mut data = fs::read(path).map_err(JournalError::at_path(path))?;
+    if let Some(sealed) = data.strip_prefix(ENCRYPTED_MAGIC) {
+        let passphrase = passphrase.ok_or_else(|| {
+            JournalError::BackupFailed(format!("{} is encrypted", path.display()))
+        })?;
+        data = secrets::open_bytes(sealed, passphrase, ENCRYPTED_MAGIC)?;
+    }
+    let reader: Box<dyn Read + '_> = if data.starts_with(GZIP_MAGIC) {
+        Box::new(flate2::read::GzDecoder::new(data.as_slice()))
+    } else {
+        Box::new(zstd::Decoder::new(data.as_slice()).map_err(backup_error("Not a zstd archive"))?)
+    };
+    let mut archive = tar::Archive::new(reader);
+
+    let mut manifest = None;
+    let mut files = Vec::new();
+    for entry in archive
+        .entries()
+        .map_err(backup_error("Failed to read archive"))?
+    {
+        let mut entry = entry.map_err(backup_error("Failed to read archive"))?;
+        let name = entry
+            .path()
+            .map_err(backup_error("Bad path in archive"))?
+            .to_string_lossy()
+            .to_string();
+        let mut content = Vec::new();
+        entry
+            .read_to_end(&mut content)
+            .map_err(backup_error("Failed to read archive"))?;
+        if name == MANIFEST_NAME {
+            manifest =
+                Some(serde_json::from_slice::<Manifest>(&content).map_err(|e| {
+                    JournalError::BackupFailed(format!("Unreadable manifest: {}", e))
+                })?);
+        } else {
+            files.push((name, content));
+        }
+    }
+
+    let manifest = manifest.ok_or_else(|| {
+        JournalError::BackupFailed(format!("{} has no {}", path.display(), MANIFEST_NAME))
+    })?;
+    Ok((manifest, files))
+}
+
+/// Check every file of an archive against its manifest
+pub fn verify(path: &Path, passphrase: Option<&str>) -> Result<Manifest> {
+    let (manifest, files) = read_archive(path, passphrase)?;
+    check(&manifest, &files)?;
+    Ok(manifest)
+}
+
+/// Whether `files` are exactly the ones listed in `manifest`, with their checksums
+pub fn check(manifest: &Manifest, files: &[(String, Vec<u8>)]) -> Result<()> {
+    if manifest.format > FORMAT_VERSION {
+        return Err(JournalError::BackupFailed(format!(
+            "Archive format {} is newer than this easy_journal supports ({})",
+            manifest.format, FORMAT_VERSION
+        )));
+    }
+    if files.len() != manifest.files.len() {
+        return Err(JournalError::BackupFailed(format!(
+            "Archive has {} files, its manifest lists {}",
+            files.len(),
+            manifest.files.len()
+        )));
+    }
+    for listed in &manifest.files {
+        let Some((_, content)) = files.iter().find(|(name, _)| *name == listed.path) else {
+            return Err(JournalError::BackupFailed(format!(
+                "{} is missing from the archive",
+                listed.path
+            )));
+        };
+        if content.len() as u64 != listed.size || sha256_hex(content) != listed.sha256 {
+            return Err(JournalError::BackupFailed(format!(
+                "{} does not match its checksum",
+                listed.path
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Backup archives in `dir`, oldest first
+pub fn list(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut archives: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(JournalError::at_path(dir))?
+        .filter_map(|item| item.ok().map(|item| item.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.strip_suffix(ENCRYPTED_SUFFIX).unwrap_or(n))
+                .is_some_and(|n| n.starts_with(ARCHIVE_PREFIX) && n.ends_with(ARCHIVE_SUFFIX))
+        })
+        .collect();
+    archives.sort();
+    Ok(archives)
+}
+
+/// Delete all but the newest `keep` archives in `dir` (0 keeps them all)
+pub fn prune(dir: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+    let archives = list(dir)?;
+    if keep == 0 || archives.len() <= keep {
+        return Ok(Vec::new());
+    }
+    let removed = archives[..archives.len() - keep].to_vec();
+    for path in &removed {
+        fs::remove_file(path).map_err(JournalError::at_path(path))?;
+    }
+    Ok(removed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreAction {
+    Create,
+    Overwrite,
+    Unchanged,
+}
+
+/// What restoring one archived file would do
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreItem {
+    pub path: String,
+    pub target: PathBuf,
+    pub action: RestoreAction,
+    /// The file on disk changed after the archive was made
+    pub newer: bool,
+}
+
+/// Where an archived file goes in the current journal
+fn restore_target(config: &Config, archive_path: &str) -> Result<PathBuf> {
+    let invalid =
+        || JournalError::BackupFailed(format!("Unexpected path in archive: {}", archive_path));
+    if let Some(relative) = archive_path.strip_prefix("journal/") {
+        let relative = Path::new(relative);
+        if relative
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)))
+        {
+            return Err(invalid());
+        }
+        return Ok(config.journal_dir.join(relative));
+    }
+    // Archives made before `.env` was left out
+    if archive_path == ".env" {
+        return Ok(config.root.join(".env"));
+    }
+    archive_extras(config)
+        .into_iter()
+        .find(|(name, _)| *name == archive_path)
+        .map(|(_, path)| path)
+        .ok_or_else(invalid)
+}
+
+/// Check that an archive holds a journal whose entries are where the
+/// configured layout puts them, before anything is unpacked
+pub fn check_layout(config: &Config, files: &[(String, Vec<u8>)]) -> Result<()> {
+    let journal: Vec<&str> = files
+        .iter()
+        .filter_map(|(path, _)| path.strip_prefix("journal/"))
+        .collect();
+    if journal.is_empty() {
+        return Err(JournalError::BackupFailed(
+            "The archive has no journal directory".to_string(),
+        ));
+    }
+
+    let other_layout = |path: &str| {
+        Layout::ALL
+            .into_iter()
+            .find(|layout| layout.entry_date(path).is_some())
+            .filter(|layout| *layout != config.layout)
+    };
+    let misplaced: Vec<(&str, Layout)> = journal
+        .into_iter()
+        .filter(|path| config.layout.entry_date(path).is_none())
+        .filter_map(|path| Some((path, other_layout(path)?)))
+        .collect();
+    if let Some((example, layout)) = misplaced.first() {
+        return Err(JournalError::BackupFailed(format!(
+            "{} entries in the archive use the {} layout (e.g. {}), but this journal is {}. Set JOURNAL_LAYOUT={} to restore it, then run `easy_journal migrate`",
+            misplaced.len(),
+            layout,
+            example,
+            config.layout,
+            layout
+        )));
+    }
+    Ok(())
+}
+
+/// Compare an archive with the files on disk. Files that exist only on disk
+/// are left alone by a restore and not listed.
+pub fn plan_restore(
+    config: &Config,
+    manifest: &Manifest,
+    files: &[(String, Vec<u8>)],
+) -> Result<Vec<RestoreItem>> {
+    let created = std::time::SystemTime::from(manifest.created);
+    files
+        .iter()
+        .map(|(path, content)| {
+            let target = restore_target(config, path)?;
+            let (action, newer) = match fs::read(&target) {
+                Err(_) => (RestoreAction::Create, false),
+                Ok(current) if current == *content => (RestoreAction::Unchanged, false),
+                Ok(_) => {
+                    let modified = fs::metadata(&target).and_then(|m| m.modified()).ok();
+                    (
+                        RestoreAction::Overwrite,
+                        modified.is_some_and(|modified| modified > created),
+                    )
+                }
+            };
+            Ok(RestoreItem {
+                path: path.clone(),
+                target,
+                action,
+                newer,
+            })
+        })
+        .collect()
+}
+
+/// The newest entry on disk, if it is newer than anything in the archive
+pub fn newer_entry(config: &Config, manifest: &Manifest) -> Result<Option<NaiveDate>> {
+    let latest = filesystem::list_entries(&config.journal_dir, config.layout)?
+        .last()
+        .map(|(date, _)| *date);
+    Ok(latest.filter(|latest| {
+        manifest
+            .latest_entry
+            .is_none_or(|archived| *latest > archived)
+    }))
+}
+
+/// Write the archived content of every item that is created or overwritten
+pub fn restore(items: &[RestoreItem], files: &[(String, Vec<u8>)]) -> Result<()> {
+    for item in items {
+        if item.action == RestoreAction::Unchanged {
+            continue;
+        }
+        if let Some((_, content)) = files.iter().find(|(path, _)| *path == item.path) {
+            if let Some(parent) = item.target.parent() {
+                fs::create_dir_all(parent).map_err(JournalError::at_path(parent))?;
+            }
+            write_atomic(&item.target, content)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_and_restore() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_backup_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut config = Config::default();
+        config.set_root(dir.join("root"), Path::new("journal"));
+        fs::create_dir_all(dir.join("root/journal/2025/12")).unwrap();
+        fs::write(dir.join("root/journal/2025/12/29.md"), "# Monday\n").unwrap();
+        fs::write(dir.join("root/journal/SUMMARY.md"), "# Summary\n").unwrap();
+        fs::write(dir.join("root/template.md"), "# {{date}}\n").unwrap();
+
+        fs::write(dir.join("root/.env"), "GITHUB_TOKEN=secret\n").unwrap();
+        fs::create_dir_all(dir.join("root/journal/.easy_journal")).unwrap();
+        fs::write(dir.join("root/journal/.easy_journal/index.db"), "").unwrap();
+
+        let backups = dir.join("backups");
+        let (path, manifest) = create(&config, &backups, None).unwrap();
+        assert_eq!(
+            manifest
+                .files
+                .iter()
+                .map(|f| f.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["journal/2025/12/29.md", "journal/SUMMARY.md", "template.md"]
+        );
+        assert_eq!(manifest.latest_entry, NaiveDate::from_ymd_opt(2025, 12, 29));
+
+        let (read, mut files) = read_archive(&path, None).unwrap();
+        assert_eq!(read.files, manifest.files);
+        check_layout(&config, &files).unwrap();
+        let mut flat = files.clone();
+        flat.push(("journal/2025-12-30.md".to_string(), Vec::new()));
+        assert!(check_layout(&config, &flat).is_err());
+        assert!(check_layout(&config, &files[2..]).is_err());
+        files[0].1 = b"# Tampered\n".to_vec();
+        assert!(check(&read, &files).is_err());
+
+        fs::write(backups.join("easy_journal-20240101-000000.tar.zst"), "").unwrap();
+        assert_eq!(
+            prune(&backups, 1).unwrap(),
+            vec![backups.join("easy_journal-20240101-000000.tar.zst")]
+        );
+        assert_eq!(list(&backups).unwrap(), vec![path.clone()]);
+
+        // Restore: one file edited, one deleted, one only on disk
+        fs::write(dir.join("root/journal/2025/12/29.md"), "# Edited\n").unwrap();
+        fs::remove_file(dir.join("root/template.md")).unwrap();
+        fs::write(dir.join("root/journal/2025/12/30.md"), "# Tuesday\n").unwrap();
+        let manifest = verify(&path, None).unwrap();
+        let (_, files) = read_archive(&path, None).unwrap();
+        let items = plan_restore(&config, &manifest, &files).unwrap();
+        let actions: Vec<(&str, RestoreAction, bool)> = items
+            .iter()
+            .map(|item| (item.path.as_str(), item.action, item.newer))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("journal/2025/12/29.md", RestoreAction::Overwrite, true),
+                ("journal/SUMMARY.md", RestoreAction::Unchanged, false),
+                ("template.md", RestoreAction::Create, false),
+            ]
+        );
+        assert_eq!(
+            newer_entry(&config, &manifest).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 12, 30)
+        );
+        restore(&items, &files).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("root/journal/2025/12/29.md")).unwrap(),
+            "# Monday\n"
+        );
+        assert!(dir.join("root/template.md").exists());
+
+        // Onto a fresh machine: nothing but the archive
+        let mut fresh = Config::default();
+        fresh.set_root(dir.join("fresh"), Path::new("journal"));
+        let items = plan_restore(&fresh, &manifest, &files).unwrap();
+        assert!(
+            items
+                .iter()
+                .all(|item| item.action == RestoreAction::Create)
+        );
+        restore(&items, &files).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("fresh/journal/2025/12/29.md")).unwrap(),
+            "# Monday\n"
+        );
+        assert!(dir.join("fresh/template.md").exists());
+
+        // Encrypted and gzip-compressed
+        let gz = dir.join("backup-2025-12.tar.gz");
+        create_at(&config, &gz, Some("correct horse")).unwrap();
+        assert!(is_encrypted(&gz).unwrap());
+        assert!(!is_encrypted(&path).unwrap());
+        assert!(read_archive(&gz, None).is_err());
+        assert!(read_archive(&gz, Some("wrong")).is_err());
+        let (_, files) = read_archive(&gz, Some("correct horse")).unwrap();
+        assert_eq!(files.len(), 4);
+        let tgz = dir.join("plain.tgz");
+        create_at(&config, &tgz, None).unwrap();
+        assert!(fs::read(&tgz).unwrap().starts_with(GZIP_MAGIC));
+        assert_eq!(verify(&tgz, None).unwrap().files.len(), 4);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
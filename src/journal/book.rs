@@ -0,0 +1,199 @@
+use pulldown_cmark::{Event, Parser, Tag};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::summary::Summary;
+
+/// A link to a markdown file that doesn't exist
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BrokenLink {
+    /// File with the link, relative to the journal directory
+    pub file: PathBuf,
+    pub line: usize,
+    pub target: String,
+}
+
+/// Relative `.md` link targets in `content` with their line numbers, without
+/// any `#anchor`. URLs and absolute paths are left out.
+fn markdown_links(content: &str) -> Vec<(usize, String)> {
+    let mut links = Vec::new();
+    for (event, range) in Parser::new(content).into_offset_iter() {
+        let Event::Start(Tag::Link { dest_url, .. }) = event else {
+            continue;
+        };
+        let target = dest_url.split('#').next().unwrap_or_default();
+        if target.contains("://") || target.starts_with('/') || !target.ends_with(".md") {
+            continue;
+        }
+        let line = content[..range.start].matches('\n').count() + 1;
+        links.push((line, target.to_string()));
+    }
+    links
+}
+
+fn broken_in(journal_dir: &Path, path: &Path) -> Result<Vec<BrokenLink>> {
+    let content = fs::read_to_string(path).map_err(JournalError::at_path(path))?;
+    let dir = path.parent().unwrap_or(journal_dir);
+    let file = path.strip_prefix(journal_dir).unwrap_or(path);
+    Ok(markdown_links(&content)
+        .into_iter()
+        .filter(|(_, target)| !dir.join(target).is_file())
+        .map(|(line, target)| BrokenLink {
+            file: file.to_path_buf(),
+            line,
+            target,
+        })
+        .collect())
+}
+
+/// Links in SUMMARY.md to missing files, after checking that it parses.
+/// mdbook would create an empty chapter for each of them.
+pub fn check_summary(config: &Config) -> Result<Vec<BrokenLink>> {
+    let path = config.journal_dir.join("SUMMARY.md");
+    if !path.exists() {
+        return Err(JournalError::InvalidConfig(format!(
+            "No SUMMARY.md in {}; run `easy_journal init` first",
+            config.journal_dir.display()
+        )));
+    }
+    Summary::parse(&path, config.layout)?;
+    broken_in(&config.journal_dir, &path)
+}
+
+/// Links between entries (and other pages) to missing markdown files, such as
+/// navigation links to a deleted day
+pub fn check_entries(config: &Config) -> Result<Vec<BrokenLink>> {
+    let mut files = Vec::new();
+    collect_markdown(&config.journal_dir, &mut files)?;
+    files.sort();
+
+    let summary = config.journal_dir.join("SUMMARY.md");
+    let mut broken = Vec::new();
+    for path in files.iter().filter(|path| **path != summary) {
+        broken.extend(broken_in(&config.journal_dir, path)?);
+    }
+    Ok(broken)
+}
+
+fn collect_markdown(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for item in fs::read_dir(dir).map_err(JournalError::at_path(dir))? {
+        let path = item?.path();
+        if path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .starts_with('.')
+        {
+            continue;
+        }
+        if path.is_dir() {
+            collect_markdown(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The directory holding book.toml (the current one when unset)
+fn book_root(config: &Config) -> &Path {
+    if config.root.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        config.root.as_path()
+    }
+}
+
+/// Build the book with `mdbook build` using the journal's book.toml, opening it
+/// in the browser with `open`. Returns the output directory (`build-dir`).
+pub fn build(config: &Config, open: bool) -> Result<PathBuf> {
+    let root = book_root(config);
+    let book_toml = root.join("book.toml");
+    let content = fs::read_to_string(&book_toml).map_err(|_| {
+        JournalError::InvalidConfig(format!(
+            "No book.toml in {}; run `easy_journal init` first",
+            root.display()
+        ))
+    })?;
+    let build_dir = toml::from_str::<toml::Value>(&content)
+        .map_err(|e| JournalError::InvalidConfig(format!("Invalid book.toml: {}", e)))?
+        .get("build")
+        .and_then(|build| build.get("build-dir"))
+        .and_then(|dir| dir.as_str())
+        .unwrap_or("book")
+        .to_string();
+
+    let mut command = Command::new("mdbook");
+    command.arg("build").arg(root);
+    if open {
+        command.arg("--open");
+    }
+    tracing::debug!(?command, "building the book");
+    let output = command
+        .output()
+        .map_err(|e| JournalError::BookFailed(format!("Could not run mdbook: {}", e)))?;
+    if !output.status.success() {
+        return Err(JournalError::BookFailed(format!(
+            "mdbook build failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(root.join(build_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::filesystem::Layout;
+
+    #[test]
+    fn test_broken_links() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_book_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("2025/12")).unwrap();
+        fs::write(
+            dir.join("SUMMARY.md"),
+            "# Summary\n\n- [2025](2025/README.md)\n  - [December](2025/12/README.md)\n    - [Monday, 29](2025/12/29.md)\n    - [Tuesday, 30](2025/12/30.md)\n",
+        )
+        .unwrap();
+        fs::write(dir.join("2025/README.md"), "# 2025\n").unwrap();
+        fs::write(dir.join("2025/12/README.md"), "# December\n").unwrap();
+        fs::write(
+            dir.join("2025/12/29.md"),
+            "# Monday\n\nSee [notes](../../notes.md#top) and [docs](https://example.com/a.md).\n\n[← 2025-12-28](28.md) | [2025-12-30 →](30.md) <!-- nav -->\n",
+        )
+        .unwrap();
+
+        let config = Config {
+            journal_dir: dir.clone(),
+            layout: Layout::Nested,
+            ..Config::default()
+        };
+        let summary = check_summary(&config).unwrap();
+        assert_eq!(
+            summary,
+            vec![BrokenLink {
+                file: PathBuf::from("SUMMARY.md"),
+                line: 6,
+                target: "2025/12/30.md".to_string(),
+            }]
+        );
+
+        let entries = check_entries(&config).unwrap();
+        let targets: Vec<(usize, &str)> = entries
+            .iter()
+            .map(|link| (link.line, link.target.as_str()))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![(3, "../../notes.md"), (5, "28.md"), (5, "30.md")]
+        );
+        assert_eq!(entries[0].file, PathBuf::from("2025/12/29.md"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
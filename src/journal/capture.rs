@@ -0,0 +1,406 @@
+use chrono::{Local, NaiveDate};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::entry::JournalEntry;
+use crate::journal::{filesystem, navigation, parser, search, template};
+
+/// Heading of the section that captured notes are appended to
+pub const CAPTURE_HEADER: &str = "## Captured";
+
+/// Section that `todo add` puts tasks in
+pub const GOALS_SECTION: &str = "Goals for Today";
+
+/// Add `line` at the end of the capture section (see [`append_to_section`])
+pub fn insert_capture(content: &str, line: &str) -> String {
+    append_to_section(content, CAPTURE_HEADER, line)
+}
+
+/// Add `line` at the end of the `header` section. The section is created
+/// before the `---` metrics footer (or at the end) when the entry has none.
+pub fn append_to_section(content: &str, header: &str, line: &str) -> String {
+    navigation::keep_at_bottom(content, |content| append_above_nav(content, header, line))
+}
+
+fn append_above_nav(content: &str, header: &str, line: &str) -> String {
+    let mut lines: Vec<&str> = content.lines().collect();
+
+    match lines.iter().position(|l| l.trim() == header) {
+        Some(start) => {
+            let mut end = lines[start + 1..]
+                .iter()
+                .position(|l| l.trim_start().starts_with('#') || l.trim() == "---")
+                .map_or(lines.len(), |i| start + 1 + i);
+            while end > start + 1 && lines[end - 1].trim().is_empty() {
+                end -= 1;
+            }
+            lines.insert(end, line);
+        }
+        None => {
+            let footer = lines.iter().rposition(|l| l.trim() == "---");
+            let at = footer.unwrap_or(lines.len());
+            let mut section = vec![header, line, ""];
+            if at > 0 && !lines[at - 1].trim().is_empty() {
+                section.insert(0, "");
+            }
+            if footer.is_none() {
+                section.pop();
+            }
+            lines.splice(at..at, section);
+        }
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    updated
+}
+
+/// Format a captured note as `- HH:MM note`, indenting continuation lines
+fn capture_line(text: &str) -> String {
+    let time = Local::now().format("%H:%M");
+    let note = text.trim().lines().collect::<Vec<_>>().join("\n  ");
+    format!("- {} {}", time, note)
+}
+
+/// Append a note to today's capture section, creating the entry first when needed
+pub async fn append_to_today(config: &Config, text: &str) -> Result<(NaiveDate, PathBuf)> {
+    if text.trim().is_empty() {
+        return Err(JournalError::InvalidArgument(
+            "Nothing to capture".to_string(),
+        ));
+    }
+
+    let date = Local::now().date_naive();
+    let entry = JournalEntry::create(date, config).await?;
+    let content =
+        fs::read_to_string(&entry.file_path).map_err(JournalError::at_path(&entry.file_path))?;
+
+    let updated = insert_capture(&content, &capture_line(text));
+    let path = JournalEntry::save(date, &updated, config)?;
+    tracing::debug!(%date, "captured note");
+    Ok((date, path))
+}
+
+/// Text as list items for [`append_to_today_section`]: one per non-empty line,
+/// lines that already are list items kept as they are
+fn list_items(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            if line.starts_with("- ") || line.starts_with("* ") {
+                line.to_string()
+            } else {
+                format!("- {}", line)
+            }
+        })
+        .collect()
+}
+
+/// Append `text` to the `section` of today's entry as list items, creating the
+/// entry first when needed. A section the entry lacks is added before the footer.
+pub async fn append_to_today_section(
+    config: &Config,
+    section: &str,
+    text: &str,
+) -> Result<(NaiveDate, PathBuf)> {
+    let items = list_items(text);
+    if items.is_empty() {
+        return Err(JournalError::InvalidArgument(
+            "Nothing to append".to_string(),
+        ));
+    }
+
+    let date = Local::now().date_naive();
+    let entry = JournalEntry::create(date, config).await?;
+    let mut updated =
+        fs::read_to_string(&entry.file_path).map_err(JournalError::at_path(&entry.file_path))?;
+    for item in &items {
+        updated = parser::append_item(&updated, section, item).unwrap_or_else(|| {
+            let header = format!("## {}", section.trim().trim_start_matches('#').trim());
+            append_to_section(&updated, &header, item)
+        });
+    }
+
+    let path = JournalEntry::save(date, &updated, config)?;
+    tracing::debug!(%date, section, "appended to section");
+    Ok((date, path))
+}
+
+/// Add `- [ ] text` to the goals: the first empty `- [ ]` left by the
+/// template is filled in, otherwise the task goes after the others. An entry
+/// without the section gets one before the footer.
+pub fn add_task(content: &str, text: &str) -> String {
+    let task = format!("- [ ] {}", text.trim());
+    let placeholder = search::section_lines(content, Some(GOALS_SECTION))
+        .into_iter()
+        .find(|(_, line)| parser::parse_task(line).is_some_and(|(text, _)| text.is_empty()));
+    if let Some((number, line)) = placeholder {
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        lines[number - 1] = format!("{}{}", indent, task);
+        let mut updated = lines.join("\n");
+        if content.ends_with('\n') {
+            updated.push('\n');
+        }
+        return updated;
+    }
+    parser::append_item(content, GOALS_SECTION, &task)
+        .unwrap_or_else(|| append_to_section(content, &format!("## {}", GOALS_SECTION), &task))
+}
+
+/// Add a task to today's goals (see [`add_task`]), creating the entry first
+/// when needed. Returns the task's text.
+pub async fn add_task_today(config: &Config, text: &str) -> Result<(NaiveDate, String)> {
+    let text = text.trim();
+    let text = text
+        .strip_prefix("- [ ]")
+        .or_else(|| text.strip_prefix("[ ]"))
+        .unwrap_or(text)
+        .trim();
+    if text.is_empty() || text.contains('\n') {
+        return Err(JournalError::InvalidArgument(
+            "A task is one line of text".to_string(),
+        ));
+    }
+
+    let date = Local::now().date_naive();
+    let entry = JournalEntry::create(date, config).await?;
+    let content =
+        fs::read_to_string(&entry.file_path).map_err(JournalError::at_path(&entry.file_path))?;
+
+    JournalEntry::save(date, &add_task(&content, text), config)?;
+    tracing::debug!(%date, "added task");
+    Ok((date, text.to_string()))
+}
+
+/// Set footer fields of today's entry, creating the entry first when needed.
+/// Values outside the range the template gives a field are refused. A `note`
+/// goes into the capture section along with the values.
+pub async fn log_metrics(
+    config: &Config,
+    metrics: &[(String, String)],
+    note: Option<&str>,
+) -> Result<(NaiveDate, PathBuf)> {
+    let template = template::load_template(&config.template_path)?;
+    for (key, value) in metrics {
+        template::check_metric(&template, key, value)?;
+    }
+
+    let date = Local::now().date_naive();
+    let entry = JournalEntry::create(date, config).await?;
+    let mut updated =
+        fs::read_to_string(&entry.file_path).map_err(JournalError::at_path(&entry.file_path))?;
+
+    for (key, value) in metrics {
+        updated = parser::set_metric(&updated, key, value);
+    }
+    if let Some(note) = note.filter(|note| !note.trim().is_empty()) {
+        let values = metrics
+            .iter()
+            .map(|(key, value)| format!("{} {}", key, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let text = if values.is_empty() {
+            note.to_string()
+        } else {
+            format!("{} ({})", note.trim(), values)
+        };
+        updated = insert_capture(&updated, &capture_line(&text));
+    }
+
+    let path = JournalEntry::save(date, &updated, config)?;
+    tracing::debug!(%date, "logged metrics");
+    Ok((date, path))
+}
+
+/// Check off the unchecked task matching `query`: an exact match, otherwise
+/// the only task containing it (case-insensitive). Returns the updated
+/// content and the task's text.
+pub fn complete_task(content: &str, query: &str) -> Result<(String, String)> {
+    let query = query.trim();
+    let open: Vec<(usize, String)> = content
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| match parser::parse_task(line) {
+            Some((text, false)) if !text.is_empty() => Some((i, text)),
+            _ => None,
+        })
+        .collect();
+
+    let needle = query.to_lowercase();
+    let matches: Vec<&(usize, String)> = match open.iter().find(|(_, text)| text == query) {
+        Some(exact) => vec![exact],
+        None => open
+            .iter()
+            .filter(|(_, text)| text.to_lowercase().contains(&needle))
+            .collect(),
+    };
+
+    let (line_no, text) = match matches.as_slice() {
+        [] => {
+            return Err(JournalError::InvalidArgument(format!(
+                "No unchecked task matches '{}'",
+                query
+            )));
+        }
+        [single] => single,
+        several => {
+            return Err(JournalError::InvalidArgument(format!(
+                "'{}' matches {} tasks; be more specific",
+                query,
+                several.len()
+            )));
+        }
+    };
+
+    let mut updated: Vec<String> = content.lines().map(str::to_string).collect();
+    updated[*line_no] = updated[*line_no].replacen("- [ ]", "- [x]", 1);
+    let mut updated = updated.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    Ok((updated, text.clone()))
+}
+
+/// Check off a task in today's entry (see [`complete_task`])
+pub fn complete_today(config: &Config, query: &str) -> Result<(NaiveDate, String)> {
+    let date = Local::now().date_naive();
+    let path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+    if !path.exists() {
+        return Err(JournalError::EntryNotFound(
+            date.format("%Y-%m-%d").to_string(),
+        ));
+    }
+    let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+
+    let (updated, task) = complete_task(&content, query)?;
+    JournalEntry::save(date, &updated, config)?;
+    Ok((date, task))
+}
+
+/// All tasks of today's entry as (text, done); empty when it doesn't exist yet
+pub fn today_tasks(config: &Config) -> Result<Vec<(String, bool)>> {
+    let path = filesystem::get_entry_path(
+        Local::now().date_naive(),
+        &config.journal_dir,
+        config.layout,
+    );
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+    Ok(parser::extract_tasks(&content))
+}
+
+/// The "Goals for Today" list of today's entry, if it exists and has any
+pub fn today_goals(config: &Config) -> Result<Option<String>> {
+    let path = filesystem::get_entry_path(
+        Local::now().date_naive(),
+        &config.journal_dir,
+        config.layout,
+    );
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+    let Some(goals) = parser::extract_section(&content, GOALS_SECTION) else {
+        return Ok(None);
+    };
+
+    // Leave out the template's empty `- [ ]` placeholders
+    let goals: Vec<&str> = goals
+        .lines()
+        .filter(|line| parser::parse_task(line).is_none_or(|(text, _)| !text.is_empty()))
+        .collect();
+    Ok((!goals.is_empty()).then(|| goals.join("\n")))
+}
+
+/// Unchecked tasks in today's "Goals for Today", if the entry exists and has any
+pub fn today_open_tasks(config: &Config) -> Result<Option<String>> {
+    let path = filesystem::get_entry_path(
+        Local::now().date_naive(),
+        &config.journal_dir,
+        config.layout,
+    );
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+    let Some(tasks) = parser::extract_unchecked_tasks(&content) else {
+        return Ok(None);
+    };
+
+    let tasks: Vec<&str> = tasks
+        .lines()
+        .filter(|line| parser::parse_task(line).is_some_and(|(text, _)| !text.is_empty()))
+        .collect();
+    Ok((!tasks.is_empty()).then(|| tasks.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_capture_creates_section_before_footer() {
+        let content = "# Monday\n\n## Gratitude & Wins\n- Coffee\n\n---\n\n**Mood(1-10)**: 7\n";
+        let updated = insert_capture(content, "- 09:15 Idea");
+        assert_eq!(
+            updated,
+            "# Monday\n\n## Gratitude & Wins\n- Coffee\n\n## Captured\n- 09:15 Idea\n\n---\n\n**Mood(1-10)**: 7\n"
+        );
+
+        let updated = insert_capture(&updated, "- 10:00 Another");
+        assert!(updated.contains("## Captured\n- 09:15 Idea\n- 10:00 Another\n\n---"));
+    }
+
+    #[test]
+    fn test_complete_task() {
+        let content =
+            "## Goals for Today\n- [ ] Write report\n- [ ] Review report draft\n- [x] Call Bob\n";
+
+        let (updated, task) = complete_task(content, "Write report").unwrap();
+        assert_eq!(task, "Write report");
+        assert_eq!(
+            updated,
+            "## Goals for Today\n- [x] Write report\n- [ ] Review report draft\n- [x] Call Bob\n"
+        );
+
+        let (_, task) = complete_task(content, "DRAFT").unwrap();
+        assert_eq!(task, "Review report draft");
+
+        assert!(complete_task(content, "report").is_err());
+        assert!(complete_task(content, "Call Bob").is_err());
+    }
+
+    #[test]
+    fn test_add_task() {
+        let content =
+            "# Monday\n\n## Goals for Today\n- [x] Standup\n- [ ]\n- [ ]\n\n## Notes\n- [ ]\n";
+        let updated = add_task(content, "Email accountant");
+        assert_eq!(
+            updated,
+            "# Monday\n\n## Goals for Today\n- [x] Standup\n- [ ] Email accountant\n- [ ]\n\n## Notes\n- [ ]\n"
+        );
+        let updated = add_task(&add_task(&updated, "Call bank"), "Book flights");
+        assert!(updated.contains(
+            "- [ ] Email accountant\n- [ ] Call bank\n- [ ] Book flights\n\n## Notes\n- [ ]\n"
+        ));
+
+        assert_eq!(
+            add_task("# Monday\n\n---\n\n**Mood**:\n", "Call bank"),
+            "# Monday\n\n## Goals for Today\n- [ ] Call bank\n\n---\n\n**Mood**:\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_capture_without_footer() {
+        assert_eq!(
+            insert_capture("# Monday\n\n## Notes\n-", "- 09:15 Idea"),
+            "# Monday\n\n## Notes\n-\n\n## Captured\n- 09:15 Idea\n"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use chrono::NaiveDate;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::journal::filesystem::{self, Layout};
+use crate::journal::parser::{self, Section};
+
+/// A conflict copy created by a sync tool next to the original entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub date: NaiveDate,
+    pub original: PathBuf,
+    pub copy: PathBuf,
+}
+
+/// Recognise conflict copies from Dropbox/Nextcloud ("29 (conflicted copy 2025-12-29).md")
+/// and Syncthing ("29.sync-conflict-20251229-101500-ABCDEFG.md"), returning the
+/// name of the original ("29.md")
+fn conflict_original(file_name: &str) -> Option<String> {
+    let stem = file_name.strip_suffix(".md")?;
+    let original = match stem.split_once(".sync-conflict-") {
+        Some((original, _)) => original,
+        None if stem.to_lowercase().contains("conflict") => stem.split_once(" (")?.0,
+        None => return None,
+    };
+    Some(format!("{}.md", original))
+}
+
+/// Find all sync conflict copies of daily entries under the journal directory
+pub fn find_conflicts(journal_dir: &Path, layout: Layout) -> Result<Vec<Conflict>> {
+    let mut conflicts = Vec::new();
+    for (relative, copy) in filesystem::journal_files(journal_dir)? {
+        let (dir, name) = relative.rsplit_once('/').unwrap_or(("", &relative));
+        let Some(original) = conflict_original(name) else {
+            continue;
+        };
+        let original = if dir.is_empty() {
+            original
+        } else {
+            format!("{}/{}", dir, original)
+        };
+        if let Some(date) = layout.entry_date(&original) {
+            conflicts.push(Conflict {
+                date,
+                original: filesystem::get_entry_path(date, journal_dir, layout),
+                copy,
+            });
+        }
+    }
+
+    conflicts.sort_by(|a, b| (a.date, &a.copy).cmp(&(b.date, &b.copy)));
+    Ok(conflicts)
+}
+
+/// Identity of a line for matching across versions: tasks ignore their
+/// checked state and metrics ignore their value
+fn line_key(line: &str) -> String {
+    if let Some((text, _)) = parser::parse_task(line) {
+        format!("task:{}", text)
+    } else if let Some((key, _)) = parser::parse_metric(line) {
+        format!("metric:{}", key)
+    } else {
+        format!("line:{}", line.trim())
+    }
+}
+
+fn find_line<'a>(lines: &'a [String], key: &str) -> Option<&'a String> {
+    lines.iter().find(|l| line_key(l) == key)
+}
+
+/// Pick a metric value: a side that changed it from the base wins; if both
+/// changed it, keep ours unless it is blank
+fn merge_metric(base: Option<&String>, ours: &str, theirs: &str) -> String {
+    let value = |line: &str| {
+        parser::parse_metric(line)
+            .map(|(_, v)| v)
+            .unwrap_or_default()
+    };
+    let (ours_value, theirs_value) = (value(ours), value(theirs));
+    let base_value = base.map(|l| value(l));
+
+    if ours_value == theirs_value || base_value.as_deref() == Some(theirs_value.as_str()) {
+        ours.to_string()
+    } else if base_value.as_deref() == Some(ours_value.as_str()) || ours_value.is_empty() {
+        theirs.to_string()
+    } else {
+        ours.to_string()
+    }
+}
+
+fn merge_lines(base: Option<&[String]>, ours: &[String], theirs: &[String]) -> Vec<String> {
+    let in_base = |key: &str| base.is_some_and(|b| find_line(b, key).is_some());
+    let mut merged: Vec<String> = Vec::new();
+
+    for line in ours {
+        if line.trim().is_empty() {
+            merged.push(line.clone());
+            continue;
+        }
+
+        let key = line_key(line);
+        let Some(their_line) = find_line(theirs, &key) else {
+            // Deleted on their side since the common base
+            if !in_base(&key) {
+                merged.push(line.clone());
+            }
+            continue;
+        };
+
+        if let (Some((text, ours_checked)), Some((_, theirs_checked))) =
+            (parser::parse_task(line), parser::parse_task(their_line))
+        {
+            // Prefer checked states
+            if !ours_checked && theirs_checked && !text.is_empty() {
+                merged.push(line.replacen("- [ ]", "- [x]", 1));
+                continue;
+            }
+        } else if parser::parse_metric(line).is_some() {
+            let base_line = base.and_then(|b| find_line(b, &key));
+            merged.push(merge_metric(base_line, line, their_line));
+            continue;
+        }
+
+        merged.push(line.clone());
+    }
+
+    // Union: add lines that only exist on their side (unless we deleted them)
+    for line in theirs {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let key = line_key(line);
+        if find_line(ours, &key).is_some() || in_base(&key) {
+            continue;
+        }
+
+        // Insert after the last non-blank line so trailing spacing is kept
+        let pos = merged
+            .iter()
+            .rposition(|l| !l.trim().is_empty())
+            .map(|i| i + 1)
+            .unwrap_or(merged.len());
+        merged.insert(pos, line.clone());
+    }
+
+    merged
+}
+
+/// Section-aware 3-way merge of two versions of an entry.
+///
+/// `base` is the last known common version (if any). Bullets from both sides
+/// are unioned, checked tasks win over unchecked ones, and lines removed on
+/// one side since the base stay removed.
+pub fn merge_entries(base: Option<&str>, ours: &str, theirs: &str) -> String {
+    let base_sections = base.map(parser::split_sections);
+    let ours_sections = parser::split_sections(ours);
+    let theirs_sections = parser::split_sections(theirs);
+
+    let find = |sections: &[Section], header: &str| -> Option<Section> {
+        sections.iter().find(|s| s.header == header).cloned()
+    };
+
+    let mut merged: Vec<Section> = Vec::new();
+    for section in &ours_sections {
+        let base_section = base_sections
+            .as_deref()
+            .and_then(|b| find(b, &section.header));
+
+        let lines = match find(&theirs_sections, &section.header) {
+            Some(theirs) => merge_lines(
+                base_section.as_ref().map(|s| s.lines.as_slice()),
+                &section.lines,
+                &theirs.lines,
+            ),
+            None => section.lines.clone(),
+        };
+
+        merged.push(Section {
+            header: section.header.clone(),
+            lines,
+        });
+    }
+
+    // Sections only present in their version (and not deleted by us)
+    for section in &theirs_sections {
+        let known = find(&ours_sections, &section.header).is_some()
+            || base_sections
+                .as_deref()
+                .is_some_and(|b| find(b, &section.header).is_some());
+        if !known {
+            merged.push(section.clone());
+        }
+    }
+
+    parser::join_sections(&merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_conflict_original() {
+        let original = |name| conflict_original(name);
+        assert_eq!(
+            original("29 (conflicted copy 2025-12-29).md").as_deref(),
+            Some("29.md")
+        );
+        assert_eq!(
+            original("05 (Nick's conflicted copy).md").as_deref(),
+            Some("05.md")
+        );
+        assert_eq!(
+            original("29.sync-conflict-20251229-101500-ABCDEFG.md").as_deref(),
+            Some("29.md")
+        );
+        assert_eq!(
+            original("2025-12-29 (conflicted copy).md").as_deref(),
+            Some("2025-12-29.md")
+        );
+        assert_eq!(original("29.md"), None);
+        assert_eq!(original("README.md"), None);
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let dir =
+            std::env::temp_dir().join(format!("easy_journal_conflicts_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("2025/12")).unwrap();
+        fs::write(dir.join("2025/12/29.md"), "").unwrap();
+        fs::write(dir.join("2025/12/29 (conflicted copy).md"), "").unwrap();
+
+        fs::write(dir.join("2025-12-30 (conflicted copy).md"), "").unwrap();
+
+        let conflicts = find_conflicts(&dir, Layout::Nested).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0].date,
+            NaiveDate::from_ymd_opt(2025, 12, 29).unwrap()
+        );
+        assert_eq!(conflicts[0].original, dir.join("2025/12/29.md"));
+        let flat = find_conflicts(&dir, Layout::Flat).unwrap();
+        assert_eq!(flat.len(), 1);
+        assert_eq!(flat[0].original, dir.join("2025-12-30.md"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_merge_prefers_checked_and_unions_bullets() {
+        let ours = "## Goals for Today\n- [ ] Write report\n- [x] Run\n\n## Notes\n- From laptop\n";
+        let theirs = "## Goals for Today\n- [x] Write report\n- [ ] Run\n- [ ] Call mom\n\n## Notes\n- From phone\n";
+
+        let merged = merge_entries(None, ours, theirs);
+        assert!(merged.contains("- [x] Write report"));
+        assert!(merged.contains("- [x] Run"));
+        assert!(merged.contains("- [ ] Call mom"));
+        assert!(merged.contains("- From laptop"));
+        assert!(merged.contains("- From phone"));
+        assert!(merged.find("Call mom").unwrap() < merged.find("## Notes").unwrap());
+    }
+
+    #[test]
+    fn test_merge_respects_deletions_since_base() {
+        let base = "## Notes\n- Keep\n- Remove me\n\n**Mood**: 5\n";
+        let ours = "## Notes\n- Keep\n\n**Mood**: 5\n";
+        let theirs = "## Notes\n- Keep\n- Remove me\n- New\n\n**Mood**: 8\n";
+
+        let merged = merge_entries(Some(base), ours, theirs);
+        assert!(!merged.contains("Remove me"));
+        assert!(merged.contains("- New"));
+        assert!(merged.contains("**Mood**: 8"));
+    }
+
+    #[test]
+    fn test_merge_adds_new_sections() {
+        let ours = "## Goals for Today\n- [ ] A\n";
+        let theirs = "## Goals for Today\n- [ ] A\n\n## Evening\n- Walk\n";
+
+        let merged = merge_entries(None, ours, theirs);
+        assert!(merged.contains("## Evening\n- Walk"));
+    }
+}
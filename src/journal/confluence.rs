@@ -0,0 +1,164 @@
+use chrono::{Duration, NaiveDate};
+use serde::Deserialize;
+
+use crate::config::ConfluenceConfig;
+use crate::error::{JournalError, Result, check_response};
+
+/// Pages listed at most
+const LIMIT: &str = "50";
+
+#[derive(Deserialize, Debug)]
+struct SearchResponse {
+    results: Vec<Page>,
+    #[serde(rename = "_links")]
+    links: Option<Links>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Links {
+    base: Option<String>,
+    webui: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Page {
+    title: String,
+    #[serde(rename = "_links")]
+    links: Links,
+    space: Option<Space>,
+    version: Option<Version>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Space {
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Version {
+    by: Option<User>,
+    message: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    display_name: String,
+}
+
+fn format_pages(response: &SearchResponse, fallback_base: &str) -> Option<String> {
+    if response.results.is_empty() {
+        return None;
+    }
+    let base = response
+        .links
+        .as_ref()
+        .and_then(|l| l.base.as_deref())
+        .unwrap_or(fallback_base)
+        .trim_end_matches('/');
+
+    let lines: Vec<String> = response
+        .results
+        .iter()
+        .map(|page| {
+            let title = match &page.links.webui {
+                Some(path) => format!("[{}]({}{})", page.title, base, path),
+                None => page.title.clone(),
+            };
+            let mut line = format!("- 📄 {}", title);
+            if let Some(space) = &page.space {
+                line.push_str(&format!(" — {}", space.name));
+            }
+            if let Some(version) = &page.version {
+                if let Some(by) = &version.by {
+                    line.push_str(&format!(", edited by {}", by.display_name));
+                }
+                if let Some(message) = version.message.as_deref().filter(|m| !m.is_empty()) {
+                    line.push_str(&format!(" (\"{}\")", message));
+                }
+            }
+            line
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
+/// Pages you watch that changed since the start of the day before `date`
+pub async fn fetch_updated_pages(
+    config: &ConfluenceConfig,
+    date: NaiveDate,
+) -> Result<Option<String>> {
+    let (Some(url), Some(token)) = (&config.url, &config.api_token) else {
+        tracing::debug!("CONFLUENCE_URL/CONFLUENCE_API_TOKEN not set");
+        return Ok(None);
+    };
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| {
+            JournalError::ConfluenceFailed(format!("Failed to build HTTP client: {}", e))
+        })?;
+
+    let cql = format!(
+        "watcher = currentUser() and type = page and lastmodified >= \"{}\" order by lastmodified desc",
+        (date - Duration::days(1)).format("%Y-%m-%d")
+    );
+    let request = client
+        .get(format!(
+            "{}/rest/api/content/search",
+            url.trim_end_matches('/')
+        ))
+        .query(&[
+            ("cql", cql.as_str()),
+            ("expand", "space,version"),
+            ("limit", LIMIT),
+        ]);
+    // Cloud uses email + API token, Data Center a personal access token
+    let request = match &config.email {
+        Some(email) => request.basic_auth(email, Some(token)),
+        None => request.bearer_auth(token),
+    };
+    let response = request
+        .send()
+        .await
+        .map_err(|e| JournalError::ConfluenceFailed(format!("Request failed: {}", e)))?;
+    let pages: SearchResponse = check_response(response, "Confluence")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::ConfluenceFailed(format!("Failed to parse response: {}", e)))?;
+
+    Ok(format_pages(&pages, url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_pages() {
+        let response: SearchResponse = serde_json::from_str(
+            r#"{
+                "results": [
+                    {"id": "1", "type": "page", "title": "Release process",
+                     "_links": {"webui": "/spaces/ENG/pages/1/Release+process"},
+                     "space": {"key": "ENG", "name": "Engineering"},
+                     "version": {"number": 7, "by": {"displayName": "Jane Doe"}, "message": "Add rollback steps"}},
+                    {"id": "2", "type": "page", "title": "On-call guide",
+                     "_links": {"webui": "/spaces/OPS/pages/2"},
+                     "space": {"key": "OPS", "name": "Operations"},
+                     "version": {"number": 2, "by": {"displayName": "Sam"}, "message": ""}}
+                ],
+                "_links": {"base": "https://acme.atlassian.net/wiki"}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            format_pages(&response, "https://unused").unwrap(),
+            "- 📄 [Release process](https://acme.atlassian.net/wiki/spaces/ENG/pages/1/Release+process) — Engineering, edited by Jane Doe (\"Add rollback steps\")\n\
+             - 📄 [On-call guide](https://acme.atlassian.net/wiki/spaces/OPS/pages/2) — Operations, edited by Sam"
+        );
+    }
+}
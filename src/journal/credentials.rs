@@ -0,0 +1,352 @@
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::write_atomic;
+use crate::journal::oauth::{self, EncryptedTokenStorage};
+
+/// A provider `auth` can sign in to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    Google,
+    GitHub,
+    GitLab,
+    Fitbit,
+    Strava,
+}
+
+impl Provider {
+    pub const ALL: [Provider; 5] = [
+        Provider::Google,
+        Provider::GitHub,
+        Provider::GitLab,
+        Provider::Fitbit,
+        Provider::Strava,
+    ];
+
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "google" => Ok(Provider::Google),
+            "github" => Ok(Provider::GitHub),
+            "gitlab" => Ok(Provider::GitLab),
+            "fitbit" => Ok(Provider::Fitbit),
+            "strava" => Ok(Provider::Strava),
+            _ => Err(JournalError::InvalidArgument(format!(
+                "Unknown provider: {}. Use 'google', 'github', 'gitlab', 'fitbit' or 'strava'.",
+                name
+            ))),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Provider::Google => "google",
+            Provider::GitHub => "github",
+            Provider::GitLab => "gitlab",
+            Provider::Fitbit => "fitbit",
+            Provider::Strava => "strava",
+        }
+    }
+
+    /// The journal section that uses the credentials
+    pub fn section(self) -> &'static str {
+        match self {
+            Provider::Google => "google_tasks",
+            Provider::GitHub => "github",
+            Provider::GitLab => "gitlab",
+            Provider::Fitbit => "fitbit",
+            Provider::Strava => "strava",
+        }
+    }
+
+    pub fn token_storage_path(self, config: &Config) -> &Path {
+        match self {
+            Provider::Google => &config.google_oauth.token_storage_path,
+            Provider::GitHub => &config.github_config.token_storage_path,
+            Provider::GitLab => &config.gitlab_config.token_storage_path,
+            Provider::Fitbit => &config.fitbit.token_storage_path,
+            Provider::Strava => &config.strava.token_storage_path,
+        }
+    }
+}
+
+/// What `auth status` reports for one provider
+#[derive(Debug, Clone, Serialize)]
+pub struct Status {
+    pub provider: &'static str,
+    /// Where the credentials come from (an env var or the token file); `None` when signed out
+    pub source: Option<String>,
+    pub scopes: Vec<String>,
+    /// When the access token expires; `None` when it doesn't or is unknown
+    pub expires_at: Option<DateTime<Local>>,
+    /// Whether an expired access token can be renewed without signing in again
+    pub refreshable: bool,
+    /// Last time the provider's section fetched successfully in this journal
+    pub last_fetch: Option<DateTime<Local>>,
+    /// Why the stored credentials could not be read
+    pub error: Option<String>,
+}
+
+fn timestamp(secs: i64) -> Option<DateTime<Local>> {
+    DateTime::from_timestamp(secs, 0).map(|at| at.with_timezone(&Local))
+}
+
+fn split_scopes(scope: Option<&str>) -> Vec<String> {
+    scope
+        .unwrap_or_default()
+        .split([' ', ','])
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn status_from_env(provider: Provider, var: &str) -> Status {
+    Status {
+        provider: provider.name(),
+        source: Some(var.to_string()),
+        scopes: Vec::new(),
+        expires_at: None,
+        refreshable: false,
+        last_fetch: None,
+        error: None,
+    }
+}
+
+/// Status of a provider's credentials, read without any network requests
+pub fn status(config: &Config, provider: Provider) -> Status {
+    let path = provider.token_storage_path(config);
+    let mut status = Status {
+        provider: provider.name(),
+        source: None,
+        scopes: Vec::new(),
+        expires_at: None,
+        refreshable: false,
+        last_fetch: load_fetches(&config.last_fetch_path)
+            .get(provider.section())
+            .copied(),
+        error: None,
+    };
+
+    let result = match provider {
+        Provider::GitHub if config.github_config.token.is_some() => {
+            return Status {
+                last_fetch: status.last_fetch,
+                ..status_from_env(provider, "GITHUB_TOKEN")
+            };
+        }
+        Provider::GitLab if config.gitlab_config.token.is_some() => {
+            return Status {
+                last_fetch: status.last_fetch,
+                ..status_from_env(provider, "GITLAB_TOKEN")
+            };
+        }
+        _ if !path.exists() => return status,
+        Provider::Google => {
+            EncryptedTokenStorage::open(path, config.google_oauth.token_passphrase.as_deref()).map(
+                |storage| {
+                    let tokens = storage.into_tokens();
+                    tokens.into_iter().next().map(|(scopes, token)| {
+                        status.scopes = scopes;
+                        status.expires_at = token
+                            .expires_at
+                            .and_then(|at| timestamp(at.unix_timestamp()));
+                        status.refreshable = token.refresh_token.is_some();
+                    })
+                },
+            )
+        }
+        Provider::GitHub => oauth::load_github_token(&config.github_config)
+            .map(|token| token.map(|token| status.scopes = split_scopes(token.scope.as_deref()))),
+        Provider::GitLab => oauth::load_gitlab_token(&config.gitlab_config).map(|token| {
+            token.map(|token| {
+                status.scopes = split_scopes(token.scope.as_deref());
+                status.expires_at = token.expires_at.and_then(timestamp);
+                status.refreshable = token.refresh_token.is_some();
+            })
+        }),
+        Provider::Fitbit => oauth::load_fitbit_token(&config.fitbit).map(|token| {
+            token.map(|token| {
+                status.scopes = split_scopes(token.scope.as_deref());
+                status.expires_at = timestamp(token.expires_at);
+                status.refreshable = true;
+            })
+        }),
+        Provider::Strava => oauth::load_strava_token(&config.strava).map(|token| {
+            token.map(|token| {
+                status.scopes = split_scopes(token.scope.as_deref());
+                status.expires_at = timestamp(token.expires_at);
+                status.refreshable = true;
+            })
+        }),
+    };
+
+    match result {
+        Ok(Some(())) => status.source = Some(path.display().to_string()),
+        Ok(None) => {}
+        Err(e) => {
+            status.source = Some(path.display().to_string());
+            status.error = Some(e.to_string());
+        }
+    }
+    status
+}
+
+/// Outcome of [`revoke`]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Revoked {
+    /// The provider invalidated the token
+    pub remote: bool,
+    /// The token file was deleted
+    pub removed: bool,
+    /// Why the token could not be revoked remotely
+    pub note: Option<String>,
+}
+
+/// Revoke a provider's stored token where the provider allows it, then delete
+/// the token file. The file is deleted even when the remote revocation fails.
+pub async fn revoke(config: &Config, provider: Provider) -> Result<Revoked> {
+    let path = provider.token_storage_path(config);
+    let mut revoked = Revoked::default();
+    if !path.exists() {
+        return Ok(revoked);
+    }
+
+    let remote = match provider {
+        Provider::Google => {
+            match EncryptedTokenStorage::open(path, config.google_oauth.token_passphrase.as_deref())
+            {
+                Ok(storage) => {
+                    let tokens = storage.into_tokens();
+                    let mut result = Ok(());
+                    for (_, token) in tokens {
+                        if let Some(token) = token.refresh_token.or(token.access_token) {
+                            result = result.and(oauth::revoke_google_token(&token).await);
+                        }
+                    }
+                    result
+                }
+                Err(e) => Err(e),
+            }
+        }
+        Provider::GitHub => Err(JournalError::OAuthFailed(
+            "GitHub tokens can only be revoked at https://github.com/settings/applications"
+                .to_string(),
+        )),
+        Provider::GitLab => match oauth::load_gitlab_token(&config.gitlab_config) {
+            Ok(Some(token)) => oauth::revoke_gitlab_token(&config.gitlab_config, &token).await,
+            Ok(None) => Err(JournalError::OAuthFailed(
+                "The stored GitLab token is for another host".to_string(),
+            )),
+            Err(e) => Err(e),
+        },
+        Provider::Fitbit => match oauth::load_fitbit_token(&config.fitbit) {
+            Ok(Some(token)) => oauth::revoke_fitbit_token(&config.fitbit, &token).await,
+            Ok(None) => Ok(()),
+            Err(e) => Err(e),
+        },
+        Provider::Strava => match oauth::load_strava_token(&config.strava) {
+            Ok(Some(token)) => oauth::revoke_strava_token(&token).await,
+            Ok(None) => Ok(()),
+            Err(e) => Err(e),
+        },
+    };
+    match remote {
+        Ok(()) => revoked.remote = true,
+        Err(e) => {
+            tracing::warn!(provider = provider.name(), "Could not revoke token: {}", e);
+            revoked.note = Some(e.to_string());
+        }
+    }
+
+    std::fs::remove_file(path).map_err(|e| JournalError::FileIo {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    revoked.removed = true;
+    forget_fetch(&config.last_fetch_path, provider.section());
+    Ok(revoked)
+}
+
+fn load_fetches(path: &Path) -> BTreeMap<String, DateTime<Local>> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_fetches(path: &Path, fetches: &BTreeMap<String, DateTime<Local>>) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent).map_err(|e| JournalError::FileIo {
+            path: parent.to_path_buf(),
+            source: e,
+        })?;
+    }
+    let content = serde_json::to_string_pretty(fetches).map_err(|e| JournalError::FileIo {
+        path: path.to_path_buf(),
+        source: e.into(),
+    })?;
+    write_atomic(path, content)
+}
+
+/// Remember that these sections fetched successfully just now
+pub fn record_fetches(path: &Path, sections: &[&str]) {
+    if sections.is_empty() {
+        return;
+    }
+    let mut fetches = load_fetches(path);
+    let now = Local::now();
+    for section in sections {
+        fetches.insert(section.to_string(), now);
+    }
+    if let Err(e) = save_fetches(path, &fetches) {
+        tracing::warn!("Could not record section fetches: {}", e);
+    }
+}
+
+fn forget_fetch(path: &Path, section: &str) {
+    let mut fetches = load_fetches(path);
+    if fetches.remove(section).is_some()
+        && let Err(e) = save_fetches(path, &fetches)
+    {
+        tracing::warn!("Could not update section fetches: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_forget_fetches() {
+        let dir = std::env::temp_dir().join(format!("ej_last_fetch_{}", std::process::id()));
+        let path = dir.join(".easy_journal/last_fetch.json");
+
+        record_fetches(&path, &["github", "weather"]);
+        let fetches = load_fetches(&path);
+        assert!(fetches.contains_key("github"));
+        assert!(fetches.contains_key("weather"));
+
+        forget_fetch(&path, "github");
+        assert!(!load_fetches(&path).contains_key("github"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_split_scopes() {
+        assert_eq!(
+            split_scopes(Some("repo,read:org")),
+            vec!["repo", "read:org"]
+        );
+        assert_eq!(
+            split_scopes(Some("read_api openid")),
+            vec!["read_api", "openid"]
+        );
+        assert!(split_scopes(None).is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+use chrono::{Datelike, Days, Local, NaiveDate, Weekday};
+
+use crate::error::{JournalError, Result};
+
+/// Days from `from` back to the last `weekday`, `from` itself counting as 0
+fn days_since(from: NaiveDate, weekday: Weekday) -> u64 {
+    u64::from((from.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7)
+}
+
+/// Resolve a date argument against `today`: `YYYY-MM-DD`, `today`,
+/// `yesterday`, `tomorrow`, `-3` or `+2` days, a weekday (`monday`, `fri`:
+/// the latest one, today included), `last friday` (before today) or
+/// `next friday` (after today). Words ignore case.
+pub fn resolve(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase();
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Some(date);
+    }
+    match input.as_str() {
+        "today" => return Some(today),
+        "yesterday" => return today.pred_opt(),
+        "tomorrow" => return today.succ_opt(),
+        _ => {}
+    }
+    if let Some(days) = input.strip_prefix('-').and_then(|n| n.parse::<u64>().ok()) {
+        return today.checked_sub_days(Days::new(days));
+    }
+    if let Some(days) = input.strip_prefix('+').and_then(|n| n.parse::<u64>().ok()) {
+        return today.checked_add_days(Days::new(days));
+    }
+
+    let words: Vec<&str> = input.split_whitespace().collect();
+    match words.as_slice() {
+        [day] => {
+            let weekday = day.parse::<Weekday>().ok()?;
+            today.checked_sub_days(Days::new(days_since(today, weekday)))
+        }
+        ["last", day] => {
+            let weekday = day.parse::<Weekday>().ok()?;
+            let yesterday = today.pred_opt()?;
+            yesterday.checked_sub_days(Days::new(days_since(yesterday, weekday)))
+        }
+        ["next", day] => {
+            let weekday = day.parse::<Weekday>().ok()?;
+            let ahead =
+                (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+            today.checked_add_days(Days::new(u64::from(if ahead == 0 { 7 } else { ahead })))
+        }
+        _ => None,
+    }
+}
+
+/// A date argument (see [`resolve`]) relative to today
+pub fn parse(input: &str) -> Result<NaiveDate> {
+    resolve(input, Local::now().date_naive())
+        .ok_or_else(|| JournalError::DateParse(format!("Invalid date: \"{}\"", input.trim())))
+}
+
+/// [`parse`] for an optional argument, defaulting to today
+pub fn parse_or_today(input: Option<&str>) -> Result<NaiveDate> {
+    input.map_or_else(|| Ok(Local::now().date_naive()), parse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        // A Wednesday
+        let today = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d);
+        let cases = [
+            ("2025-12-24", date(12, 24)),
+            ("Today", Some(today)),
+            ("yesterday", date(12, 30)),
+            ("tomorrow", NaiveDate::from_ymd_opt(2026, 1, 1)),
+            ("-3", date(12, 28)),
+            ("+1", NaiveDate::from_ymd_opt(2026, 1, 1)),
+            ("monday", date(12, 29)),
+            ("wed", Some(today)),
+            ("friday", date(12, 26)),
+            ("last wednesday", date(12, 24)),
+            ("last  Friday", date(12, 26)),
+            ("next wednesday", NaiveDate::from_ymd_opt(2026, 1, 7)),
+            ("next monday", NaiveDate::from_ymd_opt(2026, 1, 5)),
+            ("2025-13-01", None),
+            ("someday", None),
+            ("last week", None),
+            ("-x", None),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(resolve(input, today), expected, "{}", input);
+        }
+    }
+}
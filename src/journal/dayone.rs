@@ -0,0 +1,217 @@
+use chrono::{DateTime, Local, Utc};
+use serde::Deserialize;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use crate::error::{JournalError, Result};
+use crate::journal::import::{ASSETS_DIR, Attachment, ImportedEntry};
+
+/// A journal in a Day One JSON export (`Journal.json`)
+#[derive(Debug, Deserialize)]
+struct Export {
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    creation_date: DateTime<Utc>,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    starred: bool,
+    #[serde(default)]
+    photos: Vec<Photo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Photo {
+    identifier: String,
+    md5: String,
+    #[serde(rename = "type", default = "default_photo_type")]
+    kind: String,
+}
+
+fn default_photo_type() -> String {
+    "jpeg".to_string()
+}
+
+fn import_error(file: &str, e: impl std::fmt::Display) -> JournalError {
+    JournalError::ImportFailed(format!("{} is not a Day One export: {}", file, e))
+}
+
+/// Read a Day One export: the `.zip` as downloaded, or an unzipped journal
+/// `.json` with its `photos/` folder next to it. Every journal of a zip is read.
+pub fn read(path: &Path) -> Result<Vec<ImportedEntry>> {
+    let is_zip = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"));
+    if !is_zip {
+        let json = fs::read_to_string(path).map_err(JournalError::at_path(path))?;
+        let photos = path.parent().unwrap_or(Path::new(".")).join("photos");
+        return entries(&path.display().to_string(), &json, |name| {
+            fs::read(photos.join(name)).ok()
+        });
+    }
+
+    let file = fs::File::open(path).map_err(JournalError::at_path(path))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| import_error(&path.display().to_string(), e))?;
+    let journals: Vec<String> = archive
+        .file_names()
+        .filter(|name| name.ends_with(".json") && !name.contains('/'))
+        .map(str::to_string)
+        .collect();
+    if journals.is_empty() {
+        return Err(import_error(
+            &path.display().to_string(),
+            "the zip has no journal .json",
+        ));
+    }
+
+    let mut all = Vec::new();
+    for journal in journals {
+        let mut json = String::new();
+        archive
+            .by_name(&journal)
+            .and_then(|mut file| Ok(file.read_to_string(&mut json)?))
+            .map_err(|e| import_error(&journal, e))?;
+        let found = entries(&journal, &json, |name| {
+            let mut data = Vec::new();
+            let mut file = archive.by_name(&format!("photos/{}", name)).ok()?;
+            file.read_to_end(&mut data).ok()?;
+            Some(data)
+        })?;
+        all.extend(found);
+    }
+    Ok(all)
+}
+
+/// Day One names photos by the hex md5 of their content and a short type such
+/// as `jpeg`; anything else could point outside `assets/`
+fn is_photo_name(md5: &str, kind: &str) -> bool {
+    !md5.is_empty()
+        && md5.chars().all(|c| c.is_ascii_hexdigit())
+        && (1..=5).contains(&kind.len())
+        && kind.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Entries of one Day One journal, dated in the local timezone. A `# ` first
+/// line becomes the title. Photos are attached as `assets/<md5>.<type>` and
+/// their `dayone-moment://` links point there; photos the text doesn't show
+/// are added below it. `load_photo` returns the bytes of `photos/<name>`.
+fn entries(
+    file: &str,
+    json: &str,
+    mut load_photo: impl FnMut(&str) -> Option<Vec<u8>>,
+) -> Result<Vec<ImportedEntry>> {
+    let export: Export = serde_json::from_str(json).map_err(|e| import_error(file, e))?;
+
+    let mut imported = Vec::new();
+    for entry in export.entries {
+        let created = entry.creation_date.with_timezone(&Local);
+        let text = entry.text.trim();
+        let (title, mut body) = match text.split_once('\n') {
+            Some((first, rest)) if first.starts_with("# ") => (first, rest.trim().to_string()),
+            None if text.starts_with("# ") => (text, String::new()),
+            _ => ("", text.to_string()),
+        };
+
+        let mut attachments = Vec::new();
+        for photo in &entry.photos {
+            if !is_photo_name(&photo.md5, &photo.kind) {
+                tracing::warn!(
+                    "{}: skipping photo {} with an unexpected name",
+                    file,
+                    photo.identifier
+                );
+                continue;
+            }
+            let name = format!("{}.{}", photo.md5, photo.kind);
+            let Some(data) = load_photo(&name) else {
+                continue;
+            };
+            let link = format!("{}/{}", ASSETS_DIR, name);
+            let moment = format!("dayone-moment://{}", photo.identifier);
+            if body.contains(&moment) {
+                body = body.replace(&moment, &link);
+            } else {
+                body = format!("{}\n\n![]({})", body, link).trim().to_string();
+            }
+            attachments.push(Attachment { name, data });
+        }
+
+        imported.push(ImportedEntry {
+            date: created.date_naive(),
+            time: Some(created.time()),
+            title: title.trim_start_matches("# ").trim().to_string(),
+            body,
+            tags: entry.tags,
+            starred: entry.starred,
+            attachments,
+        });
+    }
+    imported.sort_by_key(|entry| (entry.date, entry.time));
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries() {
+        let json = r##"{
+            "metadata": {"version": "1.0"},
+            "entries": [
+                {
+                    "uuid": "B",
+                    "creationDate": "2025-12-30T18:00:00Z",
+                    "text": "Quiet day.",
+                    "photos": [
+                        {"identifier": "P2", "md5": "bad", "type": "png"},
+                        {"identifier": "P4", "md5": "../../../evil", "type": "sh"},
+                        {"identifier": "P5", "md5": "abc", "type": "png/../../x"}
+                    ]
+                },
+                {
+                    "uuid": "A",
+                    "creationDate": "2025-12-29T09:30:00Z",
+                    "text": "# Hike\n\nCold but clear.\n\n![](dayone-moment://P1)",
+                    "tags": ["outdoors", "road trip"],
+                    "starred": true,
+                    "photos": [
+                        {"identifier": "P1", "md5": "abc", "type": "jpeg"},
+                        {"identifier": "P3", "md5": "def", "type": "heic"}
+                    ]
+                }
+            ]
+        }"##;
+        let imported = entries("Journal.json", json, |name| {
+            (name != "bad.png").then(|| name.as_bytes().to_vec())
+        })
+        .unwrap();
+
+        assert_eq!(imported.len(), 2);
+        let hike = &imported[0];
+        assert_eq!(hike.title, "Hike");
+        assert_eq!(
+            hike.body,
+            "Cold but clear.\n\n![](assets/abc.jpeg)\n\n![](assets/def.heic)"
+        );
+        assert_eq!(hike.tags, vec!["outdoors", "road trip"]);
+        assert!(hike.starred);
+        assert_eq!(hike.attachments.len(), 2);
+        assert_eq!(hike.attachments[0].name, "abc.jpeg");
+        assert_eq!(hike.attachments[0].data, b"abc.jpeg");
+
+        assert_eq!(imported[1].title, "");
+        assert_eq!(imported[1].body, "Quiet day.");
+        assert!(imported[1].attachments.is_empty());
+
+        assert!(entries("Journal.json", "{}", |_| None).is_err());
+    }
+}
@@ -0,0 +1,351 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::DictateConfig;
+use crate::error::{JournalError, Result};
+
+/// Sample rate whisper models expect
+pub const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Recorded mono audio
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audio {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl Audio {
+    pub fn duration_secs(&self) -> f32 {
+        self.samples.len() as f32 / self.sample_rate as f32
+    }
+
+    /// Linearly resample to `rate`
+    pub fn resample(&self, rate: u32) -> Audio {
+        if rate == self.sample_rate || self.samples.is_empty() {
+            return Audio {
+                samples: self.samples.clone(),
+                sample_rate: rate,
+            };
+        }
+
+        let ratio = self.sample_rate as f64 / rate as f64;
+        let len = (self.samples.len() as f64 / ratio) as usize;
+        let samples = (0..len)
+            .map(|i| {
+                let position = i as f64 * ratio;
+                let index = position as usize;
+                let fraction = (position - index as f64) as f32;
+                let current = self.samples[index];
+                let next = self.samples.get(index + 1).copied().unwrap_or(current);
+                current + (next - current) * fraction
+            })
+            .collect();
+        Audio {
+            samples,
+            sample_rate: rate,
+        }
+    }
+
+    /// Encode as a 16-bit PCM mono WAV file
+    pub fn to_wav(&self) -> Vec<u8> {
+        let data_len = self.samples.len() as u32 * 2;
+        let mut wav = Vec::with_capacity(44 + data_len as usize);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+        wav.extend_from_slice(&self.sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(self.sample_rate * 2).to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        for sample in &self.samples {
+            let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            wav.extend_from_slice(&value.to_le_bytes());
+        }
+        wav
+    }
+}
+
+#[cfg(feature = "microphone")]
+mod microphone {
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use std::sync::{Arc, Mutex};
+
+    use super::Audio;
+    use crate::error::{JournalError, Result};
+
+    /// Records the default input device until stopped
+    pub struct Recorder {
+        stream: cpal::Stream,
+        samples: Arc<Mutex<Vec<f32>>>,
+        sample_rate: u32,
+    }
+
+    fn stream_error(e: cpal::StreamError) {
+        tracing::warn!("Microphone error: {}", e);
+    }
+
+    /// Mix interleaved frames down to mono and keep them
+    fn push(sink: &Mutex<Vec<f32>>, data: impl Iterator<Item = f32>, channels: usize) {
+        let data: Vec<f32> = data.collect();
+        if let Ok(mut samples) = sink.lock() {
+            samples.extend(
+                data.chunks(channels)
+                    .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32),
+            );
+        }
+    }
+
+    impl Recorder {
+        pub fn start() -> Result<Self> {
+            let failed = |what: &str, e: &dyn std::fmt::Display| {
+                JournalError::DictationFailed(format!("{}: {}", what, e))
+            };
+
+            let device = cpal::default_host()
+                .default_input_device()
+                .ok_or_else(|| JournalError::DictationFailed("No microphone found".to_string()))?;
+            let supported = device
+                .default_input_config()
+                .map_err(|e| failed("Microphone unavailable", &e))?;
+            let sample_rate = supported.sample_rate().0;
+            let channels = supported.channels() as usize;
+            let config = supported.config();
+
+            let samples = Arc::new(Mutex::new(Vec::new()));
+            let sink = samples.clone();
+            let stream = match supported.sample_format() {
+                cpal::SampleFormat::F32 => device.build_input_stream(
+                    &config,
+                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                        push(&sink, data.iter().copied(), channels)
+                    },
+                    stream_error,
+                    None,
+                ),
+                cpal::SampleFormat::I16 => device.build_input_stream(
+                    &config,
+                    move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                        push(
+                            &sink,
+                            data.iter().map(|s| *s as f32 / i16::MAX as f32),
+                            channels,
+                        )
+                    },
+                    stream_error,
+                    None,
+                ),
+                cpal::SampleFormat::U16 => device.build_input_stream(
+                    &config,
+                    move |data: &[u16], _: &cpal::InputCallbackInfo| {
+                        push(
+                            &sink,
+                            data.iter().map(|s| *s as f32 / u16::MAX as f32 * 2.0 - 1.0),
+                            channels,
+                        )
+                    },
+                    stream_error,
+                    None,
+                ),
+                format => {
+                    return Err(JournalError::DictationFailed(format!(
+                        "Unsupported microphone sample format {:?}",
+                        format
+                    )));
+                }
+            }
+            .map_err(|e| failed("Could not open the microphone", &e))?;
+
+            stream
+                .play()
+                .map_err(|e| failed("Could not start recording", &e))?;
+            tracing::debug!(sample_rate, channels, "recording");
+
+            Ok(Recorder {
+                stream,
+                samples,
+                sample_rate,
+            })
+        }
+
+        pub fn stop(self) -> Audio {
+            drop(self.stream);
+            let samples = self
+                .samples
+                .lock()
+                .map(|mut samples| std::mem::take(&mut *samples))
+                .unwrap_or_default();
+            Audio {
+                samples,
+                sample_rate: self.sample_rate,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "microphone")]
+pub use microphone::Recorder;
+
+/// Stand-in when built without the `microphone` feature
+#[cfg(not(feature = "microphone"))]
+pub struct Recorder;
+
+#[cfg(not(feature = "microphone"))]
+impl Recorder {
+    pub fn start() -> Result<Self> {
+        Err(JournalError::DictationFailed(
+            "easy_journal was built without the `microphone` feature; reinstall with `--features microphone`"
+                .to_string(),
+        ))
+    }
+
+    pub fn stop(self) -> Audio {
+        Audio {
+            samples: Vec::new(),
+            sample_rate: WHISPER_SAMPLE_RATE,
+        }
+    }
+}
+
+fn model_path(config: &DictateConfig) -> Result<&Path> {
+    config
+        .model
+        .as_deref()
+        .ok_or_else(|| JournalError::InvalidConfig("WHISPER_MODEL not set".to_string()))
+}
+
+/// Run the whisper.cpp command line tool on a WAV file
+fn transcribe_with_binary(config: &DictateConfig, audio: &Audio) -> Result<String> {
+    let model = model_path(config)?;
+    let wav_path =
+        std::env::temp_dir().join(format!("easy_journal_dictation_{}.wav", std::process::id()));
+    fs::write(&wav_path, audio.to_wav()).map_err(JournalError::at_path(&wav_path))?;
+
+    let mut command = Command::new(&config.whisper_binary);
+    command
+        .arg("-m")
+        .arg(model)
+        .arg("-f")
+        .arg(&wav_path)
+        .arg("-nt");
+    if let Some(language) = &config.language {
+        command.arg("-l").arg(language);
+    }
+    tracing::debug!(binary = config.whisper_binary.as_str(), "transcribing");
+    let output = command.output();
+    let _ = fs::remove_file(&wav_path);
+
+    let output = output.map_err(|e| {
+        JournalError::DictationFailed(format!("Failed to run {}: {}", config.whisper_binary, e))
+    })?;
+    if !output.status.success() {
+        return Err(JournalError::DictationFailed(format!(
+            "{} exited with {}: {}",
+            config.whisper_binary,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let transcript = String::from_utf8_lossy(&output.stdout);
+    Ok(transcript
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" "))
+}
+
+#[cfg(feature = "whisper-rs")]
+fn transcribe_in_process(config: &DictateConfig, audio: &Audio) -> Result<String> {
+    use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+    let failed = |e: whisper_rs::WhisperError| JournalError::DictationFailed(e.to_string());
+    let model = model_path(config)?;
+    let context = WhisperContext::new_with_params(
+        &model.to_string_lossy(),
+        WhisperContextParameters::default(),
+    )
+    .map_err(failed)?;
+    let mut state = context.create_state().map_err(failed)?;
+
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_language(config.language.as_deref());
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_special(false);
+    params.set_print_timestamps(false);
+    state.full(params, &audio.samples).map_err(failed)?;
+
+    let mut segments = Vec::new();
+    for i in 0..state.full_n_segments().map_err(failed)? {
+        segments.push(
+            state
+                .full_get_segment_text(i)
+                .map_err(failed)?
+                .trim()
+                .to_string(),
+        );
+    }
+    Ok(segments.join(" "))
+}
+
+#[cfg(not(feature = "whisper-rs"))]
+fn transcribe_in_process(_config: &DictateConfig, _audio: &Audio) -> Result<String> {
+    Err(JournalError::InvalidConfig(
+        "DICTATE_BACKEND=whisper-rs needs easy_journal built with the `whisper-rs` feature"
+            .to_string(),
+    ))
+}
+
+/// Transcribe a recording with the backend set in `DICTATE_BACKEND`
+pub fn transcribe(config: &DictateConfig, audio: &Audio) -> Result<String> {
+    let audio = audio.resample(WHISPER_SAMPLE_RATE);
+    match config.backend.as_str() {
+        "whisper-cpp" => transcribe_with_binary(config, &audio),
+        "whisper-rs" => transcribe_in_process(config, &audio),
+        other => Err(JournalError::InvalidConfig(format!(
+            "Unknown DICTATE_BACKEND '{}' (use whisper-cpp or whisper-rs)",
+            other
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resample() {
+        let audio = Audio {
+            samples: vec![0.0, 1.0, 0.0, -1.0],
+            sample_rate: 32_000,
+        };
+        let resampled = audio.resample(16_000);
+        assert_eq!(resampled.samples, vec![0.0, 0.0]);
+        assert_eq!(resampled.sample_rate, 16_000);
+
+        let upsampled = audio.resample(64_000);
+        assert_eq!(upsampled.samples.len(), 8);
+        assert_eq!(upsampled.samples[1], 0.5);
+    }
+
+    #[test]
+    fn test_to_wav_header() {
+        let audio = Audio {
+            samples: vec![0.0, 1.0, -1.0],
+            sample_rate: 16_000,
+        };
+        let wav = audio.to_wav();
+        assert_eq!(wav.len(), 44 + 6);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(i16::from_le_bytes([wav[46], wav[47]]), i16::MAX);
+    }
+}
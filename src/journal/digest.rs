@@ -0,0 +1,322 @@
+use chrono::NaiveDate;
+use pulldown_cmark::{Options, Parser, html};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::{self, Layout};
+use crate::journal::{parser, redact};
+
+/// A rendered review of the entries in a date range
+#[derive(Debug, Clone)]
+pub struct Digest {
+    pub title: String,
+    pub since: NaiveDate,
+    pub until: NaiveDate,
+    pub entries: usize,
+    pub markdown: String,
+}
+
+/// Template lines left untouched, e.g. `-`, `- [ ]` or an empty `**Mood**:` metric
+pub(crate) fn is_placeholder(line: &str) -> bool {
+    matches!(line.trim(), "" | "-" | "---" | "- [ ]" | "- [x]" | "- [X]")
+        || parser::parse_metric(line).is_some_and(|(_, value)| value.is_empty())
+}
+
+fn header_level(header: &str) -> usize {
+    header
+        .trim_start()
+        .chars()
+        .take_while(|c| *c == '#')
+        .count()
+}
+
+/// Entry content for the digest: the title is dropped, headers are demoted
+/// below the day heading and sections that were never filled in are removed
+pub(crate) fn entry_body(content: &str) -> String {
+    let sections: Vec<parser::Section> = parser::split_sections(content)
+        .into_iter()
+        .filter(|s| header_level(&s.header) != 1)
+        .collect();
+    let filled: Vec<bool> = sections
+        .iter()
+        .map(|s| s.lines.iter().any(|l| !is_placeholder(l)))
+        .collect();
+
+    let mut kept = Vec::new();
+    for (i, section) in sections.iter().enumerate() {
+        let level = header_level(&section.header);
+        // Keep a header when it or one of its subsections has content
+        let has_content = filled[i]
+            || sections[i + 1..]
+                .iter()
+                .zip(&filled[i + 1..])
+                .take_while(|(s, _)| level > 0 && header_level(&s.header) > level)
+                .any(|(_, filled)| *filled);
+        if !has_content {
+            continue;
+        }
+
+        let header = if section.header.is_empty() {
+            String::new()
+        } else {
+            format!("##{}", section.header.trim_start())
+        };
+        let mut lines: Vec<String> = section
+            .lines
+            .iter()
+            .filter(|l| l.trim().is_empty() || !is_placeholder(l))
+            .cloned()
+            .collect();
+        while lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
+        }
+        lines.push(String::new());
+        kept.push(parser::Section { header, lines });
+    }
+
+    parser::join_sections(&kept).trim().to_string()
+}
+
+/// Read the entries from `since` to `until` (inclusive), oldest first
+pub(crate) fn read_entries(
+    journal_dir: &Path,
+    layout: Layout,
+    since: NaiveDate,
+    until: NaiveDate,
+) -> Result<Vec<(NaiveDate, String)>> {
+    filesystem::list_entries(journal_dir, layout)?
+        .into_iter()
+        .filter(|(date, _)| (since..=until).contains(date))
+        .map(|(date, path)| {
+            fs::read_to_string(&path)
+                .map(|content| (date, content))
+                .map_err(JournalError::at_path(&path))
+        })
+        .collect()
+}
+
+/// Build the digest for the entries from `since` to `until` (inclusive), with
+/// private text redacted
+pub fn build(
+    journal_dir: &Path,
+    layout: Layout,
+    title: &str,
+    since: NaiveDate,
+    until: NaiveDate,
+    strip_private: bool,
+) -> Result<Digest> {
+    let entries: Vec<(NaiveDate, String)> = read_entries(journal_dir, layout, since, until)?
+        .into_iter()
+        .map(|(date, content)| (date, redact::redact(&content, strip_private)))
+        .collect();
+
+    let heading = format!(
+        "# {}: {} – {}",
+        title,
+        since.format("%b %-d"),
+        until.format("%b %-d, %Y")
+    );
+
+    if entries.is_empty() {
+        return Ok(Digest {
+            title: title.to_string(),
+            since,
+            until,
+            entries: 0,
+            markdown: format!("{}\n\n_No entries in this period._\n", heading),
+        });
+    }
+
+    let mut words = 0;
+    let mut tags: Vec<(String, usize)> = Vec::new();
+    // Unfinished tasks are carried into the next day's entry, so the latest
+    // state of each task wins
+    let mut tasks: HashMap<String, (NaiveDate, bool)> = HashMap::new();
+    for (date, content) in &entries {
+        words += parser::word_count(content);
+        for tag in parser::extract_tags(content) {
+            match tags.iter_mut().find(|(t, _)| *t == tag) {
+                Some((_, count)) => *count += 1,
+                None => tags.push((tag, 1)),
+            }
+        }
+        for (text, done) in parser::extract_tasks(content) {
+            tasks.insert(text, (*date, done));
+        }
+    }
+    tags.sort_by_key(|(_, count)| Reverse(*count));
+
+    let done = tasks.values().filter(|(_, done)| *done).count();
+    let mut open: Vec<(&String, NaiveDate)> = tasks
+        .iter()
+        .filter(|(_, (_, done))| !done)
+        .map(|(text, (date, _))| (text, *date))
+        .collect();
+    open.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+
+    let mut markdown = format!(
+        "{}\n\n{} {} · {} words · {} of {} tasks done\n",
+        heading,
+        entries.len(),
+        if entries.len() == 1 {
+            "entry"
+        } else {
+            "entries"
+        },
+        words,
+        done,
+        tasks.len()
+    );
+
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|(tag, count)| format!("#{} ({})", tag, count))
+            .collect();
+        markdown.push_str(&format!("\n**Tags**: {}\n", tags.join(", ")));
+    }
+
+    if !open.is_empty() {
+        markdown.push_str("\n## Open Tasks\n");
+        for (text, date) in open {
+            markdown.push_str(&format!(
+                "- [ ] {} _({})_\n",
+                text,
+                date.format("%a %b %-d")
+            ));
+        }
+    }
+
+    markdown.push_str("\n## Entries\n");
+    for (date, content) in &entries {
+        markdown.push_str(&format!("\n### {}\n", date.format("%A, %B %-d")));
+        let body = entry_body(content);
+        if !body.is_empty() {
+            markdown.push('\n');
+            markdown.push_str(&body);
+            markdown.push('\n');
+        }
+    }
+
+    Ok(Digest {
+        title: title.to_string(),
+        since,
+        until,
+        entries: entries.len(),
+        markdown,
+    })
+}
+
+/// Render digest markdown as a standalone HTML document (for email)
+pub fn to_html(markdown: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut body = String::new();
+    html::push_html(&mut body, Parser::new_ext(markdown, options));
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"></head>\n\
+         <body style=\"font-family: -apple-system, 'Segoe UI', sans-serif; max-width: 40em; margin: auto; line-height: 1.5\">\n\
+         {}</body>\n</html>\n",
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_body_drops_unfilled_sections() {
+        let content = "# 2025-12-29 - Monday\n\n## Goals for Today\n- [ ]\n- [ ]\n\n\
+                       ## Work Accomplished\n\n### Morning\n- Fixed the build\n\n### Afternoon\n-\n\n\
+                       ## Gratitude & Wins\n-\n\n---\n\n**Mood(1-10)**:\n";
+
+        assert_eq!(
+            entry_body(content),
+            "#### Work Accomplished\n\n##### Morning\n- Fixed the build"
+        );
+    }
+
+    #[test]
+    fn test_build_digest() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_digest_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let write = |date: NaiveDate, content: &str| {
+            let path = filesystem::get_entry_path(date, &dir, Layout::Nested);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        let monday = NaiveDate::from_ymd_opt(2025, 12, 22).unwrap();
+        let tuesday = NaiveDate::from_ymd_opt(2025, 12, 23).unwrap();
+        write(
+            monday,
+            "# Monday\n\n## Goals\n- [ ] Write report\n- [ ] Call Bob\n\n#family\n",
+        );
+        write(
+            tuesday,
+            "# Tuesday\n\n## Goals\n- [x] Write report\n- [ ] Call Bob\n",
+        );
+        write(
+            NaiveDate::from_ymd_opt(2025, 12, 29).unwrap(),
+            "# Outside\n",
+        );
+
+        let digest = build(
+            &dir,
+            Layout::Nested,
+            "Weekly Digest",
+            monday,
+            monday + chrono::Days::new(6),
+            false,
+        )
+        .unwrap();
+        assert_eq!(digest.entries, 2);
+        assert!(
+            digest
+                .markdown
+                .starts_with("# Weekly Digest: Dec 22 – Dec 28, 2025\n")
+        );
+        assert!(
+            digest
+                .markdown
+                .contains("2 entries · 28 words · 1 of 2 tasks done")
+        );
+        assert!(digest.markdown.contains("**Tags**: #family (1)"));
+        assert!(
+            digest
+                .markdown
+                .contains("## Open Tasks\n- [ ] Call Bob _(Tue Dec 23)_\n")
+        );
+        assert!(
+            digest
+                .markdown
+                .contains("### Monday, December 22\n\n#### Goals\n")
+        );
+        assert!(!digest.markdown.contains("Outside"));
+
+        let empty = build(
+            &dir,
+            Layout::Nested,
+            "Weekly Digest",
+            tuesday + chrono::Days::new(1),
+            tuesday + chrono::Days::new(3),
+            false,
+        )
+        .unwrap();
+        assert_eq!(empty.entries, 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_to_html() {
+        let html = to_html("# Digest\n\n- [ ] Open\n- [x] Done\n");
+        assert!(html.contains("<h1>Digest</h1>"));
+        assert!(html.contains("<input disabled=\"\" type=\"checkbox\"/>"));
+        assert!(html.contains("checked=\"\""));
+    }
+}
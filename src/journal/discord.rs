@@ -0,0 +1,402 @@
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::capture;
+
+const API_URL: &str = "https://discord.com/api/v10";
+const GATEWAY_URL: &str = "wss://gateway.discord.gg/?v=10&encoding=json";
+
+/// DIRECT_MESSAGES | MESSAGE_CONTENT
+const INTENTS: u64 = (1 << 12) | (1 << 15);
+
+/// Wait before reconnecting to the gateway
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+
+const SAVED: &str = "✅";
+const FAILED: &str = "❌";
+
+/// A gateway payload: `op` code, dispatch sequence number and event name
+#[derive(Deserialize, Debug)]
+struct GatewayEvent {
+    op: u8,
+    #[serde(default)]
+    d: serde_json::Value,
+    s: Option<u64>,
+    t: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Ready {
+    application: Application,
+}
+
+#[derive(Deserialize, Debug)]
+struct Application {
+    id: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct MessageCreate {
+    id: String,
+    channel_id: String,
+    guild_id: Option<String>,
+    author: User,
+    #[serde(default)]
+    content: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct User {
+    id: String,
+    #[serde(default)]
+    bot: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct Interaction {
+    id: String,
+    token: String,
+    data: Option<InteractionData>,
+    /// Set for interactions in DMs
+    user: Option<User>,
+    /// Set for interactions in servers
+    member: Option<Member>,
+}
+
+#[derive(Deserialize, Debug)]
+struct InteractionData {
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Member {
+    user: User,
+}
+
+/// Percent-encode an emoji for a reaction URL
+fn encode(emoji: &str) -> String {
+    emoji.bytes().map(|b| format!("%{:02X}", b)).collect()
+}
+
+struct Rest {
+    client: reqwest::Client,
+    token: String,
+}
+
+impl Rest {
+    fn new(token: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent("DiscordBot (https://github.com/nmstuart20/easy_journal, 0.2)")
+            .build()
+            .map_err(|e| {
+                JournalError::DiscordFailed(format!("Failed to build HTTP client: {}", e))
+            })?;
+        Ok(Rest {
+            client,
+            token: token.to_string(),
+        })
+    }
+
+    /// Send a REST request. Errors leave out the URL, which can contain an
+    /// interaction token.
+    async fn send(&self, request: reqwest::RequestBuilder, what: &str) -> Result<()> {
+        let response = request
+            .header("Authorization", format!("Bot {}", self.token))
+            .send()
+            .await
+            .map_err(|e| JournalError::DiscordFailed(format!("{}: {}", what, e.without_url())))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(JournalError::HttpStatus {
+            provider: "Discord".to_string(),
+            status: status.as_u16(),
+            message: format!("{}: {}", what, body.chars().take(200).collect::<String>()),
+        })
+    }
+
+    async fn react(&self, channel_id: &str, message_id: &str, emoji: &str) -> Result<()> {
+        let url = format!(
+            "{}/channels/{}/messages/{}/reactions/{}/@me",
+            API_URL,
+            channel_id,
+            message_id,
+            encode(emoji)
+        );
+        self.send(self.client.put(url), "Adding reaction").await
+    }
+
+    async fn message(&self, channel_id: &str, content: &str) -> Result<()> {
+        let url = format!("{}/channels/{}/messages", API_URL, channel_id);
+        self.send(
+            self.client
+                .post(url)
+                .json(&serde_json::json!({ "content": content })),
+            "Sending message",
+        )
+        .await
+    }
+
+    /// Register the `/today` command, usable in DMs with the bot
+    async fn register_commands(&self, application_id: &str) -> Result<()> {
+        let url = format!("{}/applications/{}/commands", API_URL, application_id);
+        let commands = serde_json::json!([{
+            "name": "today",
+            "description": "Show today's unchecked tasks",
+            "type": 1,
+            "contexts": [1],
+        }]);
+        self.send(self.client.put(url).json(&commands), "Registering commands")
+            .await
+    }
+
+    /// Answer an interaction with a message only the user can see
+    async fn respond(&self, interaction: &Interaction, content: &str) -> Result<()> {
+        let url = format!(
+            "{}/interactions/{}/{}/callback",
+            API_URL, interaction.id, interaction.token
+        );
+        let response = serde_json::json!({
+            "type": 4,
+            "data": { "content": content, "flags": 64 },
+        });
+        self.send(self.client.post(url).json(&response), "Answering command")
+            .await
+    }
+}
+
+fn open_tasks_message(tasks: Option<String>) -> String {
+    match tasks {
+        Some(tasks) => format!("Unchecked tasks for today:\n{}", tasks),
+        None => "No unchecked tasks for today. 🎉".to_string(),
+    }
+}
+
+fn is_owner(config: &Config, user_id: &str) -> bool {
+    config.discord.user_id.as_deref() == Some(user_id)
+}
+
+/// Capture a DM from the owner and confirm it with a reaction
+async fn handle_message(rest: &Rest, config: &Config, message: MessageCreate) -> Result<()> {
+    // Only direct messages from people, never our own replies
+    if message.guild_id.is_some() || message.author.bot {
+        return Ok(());
+    }
+    if config.discord.user_id.is_none() {
+        let hint = format!(
+            "Set DISCORD_USER_ID={} and restart easy_journal to use this bot.",
+            message.author.id
+        );
+        return rest.message(&message.channel_id, &hint).await;
+    }
+    if !is_owner(config, &message.author.id) {
+        debug!(
+            user = message.author.id.as_str(),
+            "ignoring DM from another user"
+        );
+        return Ok(());
+    }
+
+    match capture::append_to_today(config, &message.content).await {
+        Ok(_) => rest.react(&message.channel_id, &message.id, SAVED).await,
+        Err(e) => {
+            rest.react(&message.channel_id, &message.id, FAILED).await?;
+            rest.message(&message.channel_id, &format!("Could not add it: {}", e))
+                .await
+        }
+    }
+}
+
+async fn handle_interaction(rest: &Rest, config: &Config, interaction: Interaction) -> Result<()> {
+    let user_id = interaction
+        .user
+        .as_ref()
+        .or(interaction.member.as_ref().map(|m| &m.user))
+        .map(|u| u.id.as_str())
+        .unwrap_or_default();
+
+    let content = match interaction.data.as_ref().map(|d| d.name.as_str()) {
+        _ if !is_owner(config, user_id) => "This journal isn't yours.".to_string(),
+        Some("today") => match capture::today_open_tasks(config) {
+            Ok(tasks) => open_tasks_message(tasks),
+            Err(e) => format!("✗ {}", e),
+        },
+        _ => "Unknown command".to_string(),
+    };
+    rest.respond(&interaction, &content).await
+}
+
+async fn dispatch(
+    rest: &Rest,
+    config: &Config,
+    event: &str,
+    data: serde_json::Value,
+) -> Result<()> {
+    let parse_error = |e: serde_json::Error| {
+        JournalError::DiscordFailed(format!("Unexpected {} payload: {}", event, e))
+    };
+
+    match event {
+        "READY" => {
+            let ready: Ready = serde_json::from_value(data).map_err(parse_error)?;
+            info!("Discord bot connected");
+            rest.register_commands(&ready.application.id).await
+        }
+        "MESSAGE_CREATE" => {
+            let message = serde_json::from_value(data).map_err(parse_error)?;
+            handle_message(rest, config, message).await
+        }
+        "INTERACTION_CREATE" => {
+            let interaction = serde_json::from_value(data).map_err(parse_error)?;
+            handle_interaction(rest, config, interaction).await
+        }
+        _ => Ok(()),
+    }
+}
+
+/// One gateway session: identify, heartbeat and handle events until the
+/// connection closes or Discord asks us to reconnect
+async fn session(rest: &Rest, config: &Config, token: &str) -> Result<()> {
+    let gateway_error = |e| JournalError::DiscordFailed(format!("Gateway: {}", e));
+    let (mut socket, _) = tokio_tungstenite::connect_async(GATEWAY_URL)
+        .await
+        .map_err(gateway_error)?;
+
+    let identify = serde_json::json!({
+        "op": 2,
+        "d": {
+            "token": token,
+            "intents": INTENTS,
+            "properties": { "os": std::env::consts::OS, "browser": "easy_journal", "device": "easy_journal" },
+        },
+    });
+
+    let mut heartbeat: Option<tokio::time::Interval> = None;
+    let mut sequence: Option<u64> = None;
+
+    loop {
+        let message = match &mut heartbeat {
+            Some(interval) => tokio::select! {
+                _ = interval.tick() => {
+                    let beat = serde_json::json!({ "op": 1, "d": sequence });
+                    socket.send(WsMessage::text(beat.to_string())).await.map_err(gateway_error)?;
+                    continue;
+                }
+                message = socket.next() => message,
+            },
+            None => socket.next().await,
+        };
+
+        let text = match message {
+            Some(Ok(WsMessage::Text(text))) => text,
+            Some(Ok(WsMessage::Close(frame))) => {
+                return Err(JournalError::DiscordFailed(format!(
+                    "Gateway closed the connection: {:?}",
+                    frame
+                )));
+            }
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(gateway_error(e)),
+            None => return Ok(()),
+        };
+
+        let event: GatewayEvent = match serde_json::from_str(&text) {
+            Ok(event) => event,
+            Err(e) => {
+                debug!("Ignoring gateway payload: {}", e);
+                continue;
+            }
+        };
+        if event.s.is_some() {
+            sequence = event.s;
+        }
+
+        match event.op {
+            // Hello: start heartbeating and identify
+            10 => {
+                let interval_ms = event.d["heartbeat_interval"].as_u64().unwrap_or(41_250);
+                heartbeat = Some(tokio::time::interval(Duration::from_millis(interval_ms)));
+                socket
+                    .send(WsMessage::text(identify.to_string()))
+                    .await
+                    .map_err(gateway_error)?;
+            }
+            // Heartbeat requested right away
+            1 => {
+                let beat = serde_json::json!({ "op": 1, "d": sequence });
+                socket
+                    .send(WsMessage::text(beat.to_string()))
+                    .await
+                    .map_err(gateway_error)?;
+            }
+            // Reconnect or invalid session: start over with a fresh session
+            7 | 9 => return Ok(()),
+            0 => {
+                if let Some(name) = event.t
+                    && let Err(e) = dispatch(rest, config, &name, event.d).await
+                {
+                    warn!("{}", e);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Capture DMs sent to the bot into today's entry, forever.
+///
+/// Only DMs from `DISCORD_USER_ID` are accepted.
+pub async fn run(config: Config) {
+    let Some(token) = config.discord.bot_token.clone() else {
+        return;
+    };
+    let rest = match Rest::new(&token) {
+        Ok(rest) => rest,
+        Err(e) => {
+            warn!("{}", e);
+            return;
+        }
+    };
+
+    loop {
+        match session(&rest, &config, &token).await {
+            Ok(()) => debug!("Discord gateway session ended, reconnecting"),
+            Err(e) => {
+                warn!("{}", e);
+                tokio::time::sleep(RETRY_DELAY).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dm() {
+        let data = serde_json::json!({
+            "id": "2",
+            "channel_id": "1",
+            "author": { "id": "42", "username": "me" },
+            "content": "Idea for the talk",
+        });
+        let message: MessageCreate = serde_json::from_value(data).unwrap();
+        assert!(message.guild_id.is_none());
+        assert!(!message.author.bot);
+        assert_eq!(message.content, "Idea for the talk");
+    }
+
+    #[test]
+    fn test_encode_emoji() {
+        assert_eq!(encode(SAVED), "%E2%9C%85");
+    }
+}
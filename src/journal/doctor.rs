@@ -0,0 +1,408 @@
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::{self, Config};
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::credentials::{self, Provider};
+use crate::journal::summary::Summary;
+use crate::journal::{filesystem, google_tasks, oauth, sections, template};
+
+/// How long the sign-in and Reminders checks may take each
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Variables every daily template can use, besides the section placeholders
+const ENTRY_VARIABLES: &[&str] = &[
+    "date",
+    "day_of_week",
+    "year",
+    "month",
+    "month_num",
+    "day",
+    "month_goals",
+    "on_this_day",
+    "reminders",
+];
+const MONTH_VARIABLES: &[&str] = &["year", "month", "month_num", "date"];
+const YEAR_VARIABLES: &[&str] = &["year", "date"];
+const WEEK_VARIABLES: &[&str] = &[
+    "week",
+    "year",
+    "start",
+    "end",
+    "date",
+    "entries",
+    "wins",
+    "open_tasks",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Pass,
+    /// Works, but probably not as intended
+    Warn,
+    Fail,
+    /// Doesn't apply to this setup
+    Skip,
+}
+
+/// Result of one `doctor` check
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub outcome: Outcome,
+    pub detail: String,
+    /// What to do about a warning or failure
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn new(name: &str, outcome: Outcome, detail: impl Into<String>) -> Self {
+        Check {
+            name: name.to_string(),
+            outcome,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+/// Run every check; the sign-ins are verified concurrently
+pub async fn run(config: &Config) -> Vec<Check> {
+    let mut checks = vec![journal_dir(config), summary(config)];
+    checks.extend(templates(config));
+    checks.extend(
+        futures::future::join_all(Provider::ALL.map(|provider| sign_in(config, provider))).await,
+    );
+    checks.push(reminders(config).await);
+    checks.push(editor());
+    checks
+}
+
+fn journal_dir(config: &Config) -> Check {
+    let dir = &config.journal_dir;
+    if !dir.is_dir() {
+        return Check::new(
+            "Journal directory",
+            Outcome::Fail,
+            format!("{} does not exist", dir.display()),
+        )
+        .hint("Run `easy_journal init`, or set JOURNAL_HOME to an existing journal");
+    }
+    let probe = dir.join(".easy_journal_doctor");
+    match fs::write(&probe, "").and_then(|_| fs::remove_file(&probe)) {
+        Ok(()) => Check::new(
+            "Journal directory",
+            Outcome::Pass,
+            dir.display().to_string(),
+        ),
+        Err(e) => Check::new(
+            "Journal directory",
+            Outcome::Fail,
+            format!("{} is not writable: {}", dir.display(), e),
+        )
+        .hint("Fix the directory's permissions"),
+    }
+}
+
+fn summary(config: &Config) -> Check {
+    let path = config.journal_dir.join("SUMMARY.md");
+    if !path.exists() {
+        return Check::new("SUMMARY.md", Outcome::Fail, "missing")
+            .hint("Run `easy_journal init` to create it");
+    }
+    let summary = match Summary::parse(&path, config.layout) {
+        Ok(summary) => summary,
+        Err(e) => return Check::new("SUMMARY.md", Outcome::Fail, e.to_string()),
+    };
+    let listed: Vec<_> = summary
+        .tree()
+        .into_iter()
+        .flat_map(|year| year.months)
+        .flat_map(|month| month.days)
+        .collect();
+    let entries = filesystem::list_entries(&config.journal_dir, config.layout).unwrap_or_default();
+    let missing = entries
+        .iter()
+        .filter(|(date, _)| !listed.contains(date))
+        .count();
+    if missing > 0 {
+        return Check::new(
+            "SUMMARY.md",
+            Outcome::Warn,
+            format!("{} of {} entries are not listed", missing, entries.len()),
+        )
+        .hint("Run `easy_journal migrate` to add them");
+    }
+    Check::new(
+        "SUMMARY.md",
+        Outcome::Pass,
+        format!("{} entries listed", listed.len()),
+    )
+}
+
+/// Unclosed `{{` and placeholders that nothing fills in a template
+pub fn template_problems(content: &str, known: &[&str]) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            problems.push("`{{` without a closing `}}`".to_string());
+            break;
+        };
+        let name = &rest[start + 2..start + end];
+        if !known.contains(&name) {
+            problems.push(format!("{{{{{}}}}} is never filled in", name));
+        }
+        rest = &rest[start + end + 2..];
+    }
+    problems
+}
+
+fn templates(config: &Config) -> Vec<Check> {
+    let configured = sections::configured_sections(config);
+    let mut entry_variables = ENTRY_VARIABLES.to_vec();
+    entry_variables.extend(configured.iter().map(|section| section.name()));
+
+    [
+        ("Entry template", &config.template_path, entry_variables),
+        (
+            "Month template",
+            &config.month_template_path,
+            MONTH_VARIABLES.to_vec(),
+        ),
+        (
+            "Year template",
+            &config.year_template_path,
+            YEAR_VARIABLES.to_vec(),
+        ),
+        (
+            "Week template",
+            &config.week_template_path,
+            WEEK_VARIABLES.to_vec(),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, path, known)| template_check(name, path, &known))
+    .collect()
+}
+
+fn template_check(name: &str, path: &Path, known: &[&str]) -> Check {
+    if !path.exists() {
+        return Check::new(name, Outcome::Pass, "built-in default");
+    }
+    let content = match template::load_template(path) {
+        Ok(content) => content,
+        Err(e) => {
+            return Check::new(name, Outcome::Fail, e.to_string())
+                .hint("Make it a readable UTF-8 file, or delete it to use the default");
+        }
+    };
+    let problems = template_problems(&content, known);
+    if problems.is_empty() {
+        return Check::new(name, Outcome::Pass, path.display().to_string());
+    }
+    Check::new(
+        name,
+        Outcome::Warn,
+        format!("{}: {}", path.display(), problems.join(", ")),
+    )
+    .hint("Add the section to JOURNAL_SECTIONS, or fix the placeholder")
+}
+
+/// One cheap authenticated request with the provider's credentials
+async fn verify(config: &Config, provider: Provider) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .timeout(TIMEOUT)
+        .build()
+        .map_err(|e| JournalError::OAuthFailed(format!("Failed to build HTTP client: {}", e)))?;
+    let (request, name) = match provider {
+        Provider::Google => return google_tasks::check_sign_in(&config.google_oauth).await,
+        Provider::GitHub => {
+            let token = oauth::github_token(&config.github_config)?.unwrap_or_default();
+            (
+                client.get("https://api.github.com/user").bearer_auth(token),
+                "GitHub",
+            )
+        }
+        Provider::GitLab => {
+            let Some(auth) = oauth::gitlab_auth(&config.gitlab_config).await? else {
+                return Ok(());
+            };
+            let url = format!(
+                "{}/api/v4/user",
+                config.gitlab_config.host.trim_end_matches('/')
+            );
+            (auth.apply(client.get(url)), "GitLab")
+        }
+        Provider::Fitbit => {
+            let token = oauth::fitbit_access_token(&config.fitbit)
+                .await?
+                .unwrap_or_default();
+            (
+                client
+                    .get("https://api.fitbit.com/1/user/-/profile.json")
+                    .bearer_auth(token),
+                "Fitbit",
+            )
+        }
+        Provider::Strava => {
+            let token = oauth::strava_access_token(&config.strava)
+                .await?
+                .unwrap_or_default();
+            (
+                client
+                    .get("https://www.strava.com/api/v3/athlete")
+                    .bearer_auth(token),
+                "Strava",
+            )
+        }
+    };
+    let response = request
+        .send()
+        .await
+        .map_err(|e| JournalError::OAuthFailed(format!("{} request failed: {}", name, e)))?;
+    check_response(response, name).await?;
+    Ok(())
+}
+
+async fn sign_in(config: &Config, provider: Provider) -> Check {
+    let name = format!("{} sign-in", provider.name());
+    let status = credentials::status(config, provider);
+    let sign_in_hint = format!("Run `easy_journal auth {}`", provider.name());
+    let Some(source) = status.source else {
+        return if config.sections.iter().any(|s| s == provider.section()) {
+            Check::new(
+                &name,
+                Outcome::Fail,
+                format!(
+                    "not signed in, but {} is in JOURNAL_SECTIONS",
+                    provider.section()
+                ),
+            )
+            .hint(sign_in_hint)
+        } else {
+            Check::new(&name, Outcome::Skip, "not signed in")
+        };
+    };
+    if let Some(error) = status.error {
+        return Check::new(&name, Outcome::Fail, format!("{} ({})", error, source))
+            .hint(sign_in_hint);
+    }
+    match tokio::time::timeout(TIMEOUT, verify(config, provider)).await {
+        Ok(Ok(())) => Check::new(&name, Outcome::Pass, source),
+        Ok(Err(e)) => {
+            let hint = e.hint().unwrap_or(sign_in_hint);
+            Check::new(&name, Outcome::Fail, e.to_string()).hint(hint)
+        }
+        Err(_) => {
+            Check::new(&name, Outcome::Fail, "timed out").hint("Check your network connection")
+        }
+    }
+}
+
+async fn reminders(config: &Config) -> Check {
+    let name = "Apple Reminders";
+    if env::consts::OS != "macos" {
+        return Check::new(name, Outcome::Skip, "only available on macOS");
+    }
+    if !config
+        .sections
+        .iter()
+        .any(|s| s == "reminders" || s == "apple_reminders")
+    {
+        return Check::new(name, Outcome::Skip, "reminders is not in JOURNAL_SECTIONS");
+    }
+    let output = tokio::process::Command::new("osascript")
+        .args(["-e", "tell application \"Reminders\" to count lists"])
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(TIMEOUT, output).await {
+        Ok(Ok(output)) if output.status.success() => {
+            Check::new(name, Outcome::Pass, "osascript can read Reminders")
+        }
+        Ok(Ok(output)) => {
+            let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            let hint = if error.contains("-1743") {
+                "Allow your terminal to control Reminders in System Settings → Privacy & Security → Automation"
+            } else {
+                "Open Reminders once, then run `easy_journal doctor` again"
+            };
+            Check::new(name, Outcome::Fail, error).hint(hint)
+        }
+        Ok(Err(e)) => Check::new(name, Outcome::Fail, format!("Could not run osascript: {}", e)),
+        Err(_) => Check::new(name, Outcome::Fail, "osascript timed out")
+            .hint("Answer the permission prompt, or allow your terminal in System Settings → Privacy & Security → Automation"),
+    }
+}
+
+/// `program` itself if it is a path, else where it is on PATH
+fn find_program(program: &str) -> Option<PathBuf> {
+    if program.contains('/') {
+        let path = PathBuf::from(program);
+        return path.is_file().then_some(path);
+    }
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|path| path.is_file())
+}
+
+/// The editor `new` opens: VISUAL, EDITOR, or the first of code, vim, nano and vi
+fn editor() -> Check {
+    let name = "Editor";
+    for var in ["VISUAL", "EDITOR"] {
+        let Ok(command) = config::var(var) else {
+            continue;
+        };
+        let program = command.split_whitespace().next().unwrap_or_default();
+        return match find_program(program) {
+            Some(path) => Check::new(name, Outcome::Pass, format!("{} ({})", path.display(), var)),
+            None => Check::new(
+                name,
+                Outcome::Fail,
+                format!("{} is `{}`, which was not found", var, command),
+            )
+            .hint(format!("Set {} to an installed editor", var)),
+        };
+    }
+    match ["code", "vim", "nano", "vi"]
+        .iter()
+        .find_map(|program| find_program(program))
+    {
+        Some(path) => Check::new(
+            name,
+            Outcome::Pass,
+            format!("{} (EDITOR is not set)", path.display()),
+        ),
+        None => Check::new(name, Outcome::Fail, "no editor found")
+            .hint("Set the EDITOR or VISUAL environment variable"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_problems() {
+        let template =
+            "# {{day_of_week}}, {{date}} {{ date }}\n\n{{weather}}\n{{github}}\n{{reminders";
+        assert_eq!(
+            template_problems(template, &["day_of_week", "date", "weather"]),
+            vec![
+                "{{ date }} is never filled in".to_string(),
+                "{{github}} is never filled in".to_string(),
+                "`{{` without a closing `}}`".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::config::EmailConfig;
+use crate::error::{JournalError, Result};
+use crate::journal::digest;
+
+fn mailbox(address: &str, var: &str) -> Result<Mailbox> {
+    address
+        .parse()
+        .map_err(|e| JournalError::InvalidConfig(format!("{} '{}': {}", var, address, e)))
+}
+
+/// Send markdown as an email with a plain text and an HTML part
+pub async fn send(config: &EmailConfig, subject: &str, markdown: &str) -> Result<()> {
+    let host = config
+        .smtp_host
+        .as_deref()
+        .ok_or_else(|| JournalError::InvalidConfig("SMTP_HOST not set".to_string()))?;
+    let to = config
+        .to
+        .as_deref()
+        .ok_or_else(|| JournalError::InvalidConfig("DIGEST_EMAIL_TO not set".to_string()))?;
+    let from = config.from.as_deref().unwrap_or(to);
+
+    let message = Message::builder()
+        .from(mailbox(from, "DIGEST_EMAIL_FROM")?)
+        .to(mailbox(to, "DIGEST_EMAIL_TO")?)
+        .subject(subject)
+        .multipart(MultiPart::alternative_plain_html(
+            markdown.to_string(),
+            digest::to_html(markdown),
+        ))
+        .map_err(|e| JournalError::EmailFailed(format!("Failed to build message: {}", e)))?;
+
+    // Port 465 is implicit TLS, everything else upgrades with STARTTLS
+    let builder = if config.smtp_port == 465 {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(host)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+    }
+    .map_err(|e| JournalError::EmailFailed(format!("Failed to set up TLS for {}: {}", host, e)))?
+    .port(config.smtp_port);
+
+    let builder = match (&config.smtp_username, &config.smtp_password) {
+        (Some(username), Some(password)) => {
+            builder.credentials(Credentials::new(username.clone(), password.clone()))
+        }
+        _ => builder,
+    };
+
+    tracing::debug!(host, port = config.smtp_port, to, "sending email");
+    builder
+        .build()
+        .send(message)
+        .await
+        .map_err(|e| JournalError::EmailFailed(format!("{}:{}: {}", host, config.smtp_port, e)))?;
+    Ok(())
+}
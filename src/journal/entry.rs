@@ -1,10 +1,18 @@
 use chrono::{Duration, NaiveDate};
 use std::fs;
 use std::path::PathBuf;
+use tracing::{instrument, warn};
 
 use crate::config::Config;
 use crate::error::Result;
-use crate::journal::{filesystem, git_integrations, parser, reminders, summary, template};
+use crate::journal::hooks::{self, HookEvent};
+use crate::journal::lock::WriteLock;
+use crate::journal::rollover::{self, Rollover};
+use crate::journal::sections::SectionProgress;
+use crate::journal::trash::{self, OperationKind};
+use crate::journal::{
+    autocommit, filesystem, history, index, navigation, parser, sections, summary, template,
+};
 
 pub struct JournalEntry {
     pub date: NaiveDate,
@@ -13,55 +21,87 @@ pub struct JournalEntry {
 
 impl JournalEntry {
     pub async fn create(date: NaiveDate, config: &Config) -> Result<Self> {
+        Self::create_with_progress(date, config, &(), &Rollover::All).await
+    }
+
+    /// Like [`JournalEntry::create`], reporting section fetch progress to `progress`
+    /// and carrying over the previous entry's tasks as `rollover` says
+    #[instrument(skip(config, progress, rollover))]
+    pub async fn create_with_progress(
+        date: NaiveDate,
+        config: &Config,
+        progress: &dyn SectionProgress,
+        rollover: &Rollover<'_>,
+    ) -> Result<Self> {
         let year = date.format("%Y").to_string().parse::<u32>().unwrap();
         let month = date.format("%m").to_string().parse::<u32>().unwrap();
+        let entry_path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
 
-        // Ensure year and month directories exist
-        filesystem::ensure_year_dir(year, &config.journal_dir)?;
+        if !entry_path.exists() {
+            hooks::run(config, HookEvent::PreNew, date, &entry_path)?;
+        }
+
+        // Ensure the entry's directory and the year and month pages exist
+        filesystem::ensure_entry_dir(date, &config.journal_dir, config.layout)?;
         filesystem::create_year_readme(year, &config.journal_dir, config)?;
-        filesystem::ensure_month_dir(year, month, &config.journal_dir)?;
         filesystem::create_month_readme(year, month, &config.journal_dir, config)?;
 
-        // Get entry path
-        let entry_path = filesystem::get_entry_path(date, &config.journal_dir);
-
         // Create entry file if it doesn't exist
         if !entry_path.exists() {
             let template_content = template::load_template(&config.template_path)?;
 
             // Get previous entry's unchecked tasks and "Tomorrow's Focus" content
             let previous_content = Self::get_previous_content(date, config)?;
+            let previous_content = rollover::apply(previous_content, date, config, rollover)?;
+
+            // Fetch the configured sections concurrently and fill the template
+            let mut content =
+                sections::render_entry(&template_content, date, previous_content, config, progress)
+                    .await;
+            if config.nav_links {
+                let entries = filesystem::list_entries(&config.journal_dir, config.layout)?;
+                let (previous, next) = navigation::neighbours(date, &entries);
+                content = navigation::with_links(&content, previous, next, config.layout);
+            }
 
-            // Fetch reminders and git integrations concurrently
-            let reminders_task = reminders::merge_all_reminders(config);
-            let git_integrations_task = git_integrations::merge_git_integrations(config);
+            {
+                let _lock = WriteLock::acquire(&config.lock_path)?;
 
-            let (all_reminders, git_items) = tokio::join!(reminders_task, git_integrations_task);
+                // Another writer (e.g. the web server) may have created it meanwhile
+                if entry_path.exists() {
+                    return Ok(JournalEntry {
+                        date,
+                        file_path: entry_path,
+                    });
+                }
 
-            let all_reminders = all_reminders.unwrap_or(None);
-            let git_items = git_items.unwrap_or(None);
+                filesystem::write_atomic(&entry_path, &content)?;
+                index::record_entry(config, date, &entry_path, &content);
+            }
 
-            // Combine reminders and git integrations into single reminders section
-            let combined_reminders = match (all_reminders, git_items) {
-                (Some(rem), Some(git)) => Some(format!("{}\n\n{}", rem, git)),
-                (Some(rem), None) => Some(rem),
-                (None, Some(git)) => Some(git),
-                (None, None) => None,
-            };
+            // Update SUMMARY.md
+            Self::add_to_summary(date, config)?;
 
-            let content = template::apply_variables(
-                &template_content,
-                date,
-                previous_content,
-                combined_reminders,
-            );
-            fs::write(&entry_path, content)?;
+            if config.nav_links
+                && let Err(e) = navigation::link_neighbours(config, date)
+            {
+                warn!("Could not link the neighbouring entries: {}", e);
+            }
 
-            // Update SUMMARY.md
-            let summary_path = config.journal_dir.join("SUMMARY.md");
-            let mut summary = summary::Summary::parse(&summary_path)?;
-            summary.add_day_entry(date);
-            summary.write()?;
+            {
+                let _lock = WriteLock::acquire(&config.lock_path)?;
+                let year_readme =
+                    filesystem::get_year_path(year, &config.journal_dir, config.layout);
+                let month_readme =
+                    filesystem::get_month_path(year, month, &config.journal_dir, config.layout);
+                autocommit::record(
+                    config,
+                    &[&entry_path, &year_readme, &month_readme],
+                    &format!("Create {}", date.format("%Y-%m-%d")),
+                );
+            }
+
+            hooks::notify(config, HookEvent::PostNew, date, &entry_path);
         }
 
         Ok(JournalEntry {
@@ -70,8 +110,95 @@ impl JournalEntry {
         })
     }
 
+    /// Write new content for an entry, keeping the previous version in the
+    /// history store and the trash so it can be restored
+    #[instrument(skip(content, config))]
+    pub fn save(date: NaiveDate, content: &str, config: &Config) -> Result<PathBuf> {
+        let entry_path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+        let lock = WriteLock::acquire(&config.lock_path)?;
+
+        history::snapshot_before_write(config, date, &entry_path, content)?;
+        trash::record_overwrite(
+            &config.trash_dir,
+            OperationKind::Overwrite,
+            &entry_path,
+            Some(date),
+            content,
+        )?;
+
+        filesystem::write_atomic(&entry_path, content)?;
+        index::record_entry(config, date, &entry_path, content);
+        autocommit::record(
+            config,
+            &[&entry_path],
+            &format!("Update {}", date.format("%Y-%m-%d")),
+        );
+        drop(lock);
+
+        hooks::notify(config, HookEvent::PostSave, date, &entry_path);
+        Ok(entry_path)
+    }
+
+    /// Register the entry in SUMMARY.md (recording the previous SUMMARY.md for undo)
+    #[instrument(skip(config))]
+    pub fn add_to_summary(date: NaiveDate, config: &Config) -> Result<()> {
+        let summary_path = config.journal_dir.join("SUMMARY.md");
+        let _lock = WriteLock::acquire(&config.lock_path)?;
+        let mut summary = summary::Summary::parse(&summary_path, config.layout)?;
+        summary.add_day_entry(date);
+
+        trash::record_overwrite(
+            &config.trash_dir,
+            OperationKind::SummaryRewrite,
+            summary.path(),
+            None,
+            &summary.render(),
+        )?;
+        summary.write()?;
+
+        autocommit::record(
+            config,
+            &[&summary_path],
+            &format!("Add {} to SUMMARY.md", date.format("%Y-%m-%d")),
+        );
+        Ok(())
+    }
+
+    /// Move an entry to the trash and drop it from SUMMARY.md and the index.
+    ///
+    /// The SUMMARY.md change isn't recorded separately: undoing the deletion
+    /// adds the entry back.
+    #[instrument(skip(config))]
+    pub fn delete(date: NaiveDate, config: &Config) -> Result<PathBuf> {
+        let entry_path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+        let summary_path = config.journal_dir.join("SUMMARY.md");
+        let _lock = WriteLock::acquire(&config.lock_path)?;
+        trash::move_to_trash(&config.trash_dir, &entry_path, Some(date))?;
+
+        if summary_path.exists() {
+            let mut summary = summary::Summary::parse(&summary_path, config.layout)?;
+            if summary.remove_day_entry(date) {
+                summary.write()?;
+            }
+        }
+
+        if let Err(e) =
+            index::Index::open(&config.index_path).and_then(|index| index.remove_entry(date))
+        {
+            warn!("Could not update entry index: {}", e);
+        }
+
+        autocommit::record(
+            config,
+            &[&entry_path, &summary_path],
+            &format!("Delete {}", date.format("%Y-%m-%d")),
+        );
+
+        Ok(entry_path)
+    }
+
     pub fn exists(date: NaiveDate, config: &Config) -> bool {
-        let entry_path = filesystem::get_entry_path(date, &config.journal_dir);
+        let entry_path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
         entry_path.exists()
     }
 
@@ -80,7 +207,8 @@ impl JournalEntry {
         // Search backwards up to 30 days
         for days_back in 1..=30 {
             if let Some(prev_date) = date.checked_sub_signed(Duration::days(days_back)) {
-                let entry_path = filesystem::get_entry_path(prev_date, &config.journal_dir);
+                let entry_path =
+                    filesystem::get_entry_path(prev_date, &config.journal_dir, config.layout);
                 if entry_path.exists() {
                     return Some(entry_path);
                 }
@@ -0,0 +1,426 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, html};
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::error::{JournalError, Result};
+
+const STYLE: &str = "body { font-family: serif; line-height: 1.5; }\n\
+h1 { font-size: 1.4em; }\n\
+h2 { font-size: 1.15em; margin-top: 1.5em; }\n\
+img { max-width: 100%; }\n\
+ul.contains-task-list { list-style: none; padding-left: 1em; }\n\
+.cover { margin: 0; padding: 0; text-align: center; }\n\
+.cover img { height: 100%; }\n";
+
+/// An image referenced by an entry, stored under `images/`
+struct Image {
+    source: PathBuf,
+    href: String,
+    media_type: &'static str,
+}
+
+struct Chapter {
+    date: NaiveDate,
+    href: String,
+    body: String,
+}
+
+fn export_error(e: impl std::fmt::Display) -> JournalError {
+    JournalError::ExportFailed(format!("Failed to write EPUB: {}", e))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn image_type(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()?.to_lowercase().as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "svg" => Some("image/svg+xml"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// The book path of a local image next to the entry, adding it on first use;
+/// remote and missing images keep their link
+fn embed(dest: &str, entry_dir: &Path, images: &mut Vec<Image>) -> Option<String> {
+    if dest.contains("://") || dest.starts_with("data:") {
+        return None;
+    }
+    let relative = dest.split(['#', '?']).next()?.replace("%20", " ");
+    let source = entry_dir.join(relative);
+    if let Some(image) = images.iter().find(|image| image.source == source) {
+        return Some(format!("../{}", image.href));
+    }
+    let media_type = image_type(&source)?;
+    if !source.is_file() {
+        return None;
+    }
+    let extension = source.extension()?.to_str()?.to_lowercase();
+    let href = format!("images/{}.{}", images.len() + 1, extension);
+    images.push(Image {
+        source,
+        href: href.clone(),
+        media_type,
+    });
+    Some(format!("../{}", href))
+}
+
+/// Entry markdown as XHTML, with local images pointing into the book. Raw HTML
+/// other than `<br>` is shown as text (comments are dropped) so the page stays
+/// well-formed.
+fn render(content: &str, entry_dir: &Path, images: &mut Vec<Image>) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let events = Parser::new_ext(content, options).filter_map(|event| match event {
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => {
+            let dest_url = match embed(&dest_url, entry_dir, images) {
+                Some(href) => CowStr::from(href),
+                None => dest_url,
+            };
+            Some(Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }))
+        }
+        Event::InlineHtml(raw) if matches!(raw.trim(), "<br>" | "<br/>" | "<br />") => {
+            Some(Event::HardBreak)
+        }
+        Event::Html(raw) | Event::InlineHtml(raw) => {
+            (!raw.trim_start().starts_with("<!--")).then_some(Event::Text(raw))
+        }
+        event => Some(event),
+    });
+    let mut body = String::new();
+    html::push_html(&mut body, events);
+    body
+}
+
+/// An XHTML document; `stylesheet` is the path of style.css from it
+fn page(title: &str, stylesheet: &str, body: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE html>\n\
+         <html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\">\n\
+         <head>\n<title>{}</title>\n<link rel=\"stylesheet\" type=\"text/css\" href=\"{}\"/>\n</head>\n\
+         <body>\n{}</body>\n</html>\n",
+        escape(title),
+        stylesheet,
+        body
+    )
+}
+
+fn chapter_title(date: NaiveDate) -> String {
+    date.format("%A, %B %-d, %Y").to_string()
+}
+
+fn cover_svg(title: &str, subtitle: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <svg xmlns=\"http://www.w3.org/2000/svg\" width=\"600\" height=\"800\" viewBox=\"0 0 600 800\">\n\
+         <rect width=\"600\" height=\"800\" fill=\"#2d3748\"/>\n\
+         <rect x=\"40\" y=\"40\" width=\"520\" height=\"720\" fill=\"none\" stroke=\"#e2e8f0\" stroke-width=\"2\"/>\n\
+         <text x=\"300\" y=\"360\" fill=\"#f7fafc\" font-family=\"serif\" font-size=\"44\" text-anchor=\"middle\">{}</text>\n\
+         <text x=\"300\" y=\"420\" fill=\"#cbd5e0\" font-family=\"serif\" font-size=\"24\" text-anchor=\"middle\">{}</text>\n\
+         </svg>\n",
+        escape(title),
+        escape(subtitle)
+    )
+}
+
+/// Table of contents; entries are grouped by month when there is more than one
+fn nav(title: &str, chapters: &[Chapter]) -> String {
+    let link = |chapter: &Chapter| {
+        format!(
+            "<li><a href=\"{}\">{}</a></li>\n",
+            chapter.href,
+            escape(&chapter_title(chapter.date))
+        )
+    };
+    let mut months: Vec<&[Chapter]> = chapters
+        .chunk_by(|a, b| (a.date.year(), a.date.month()) == (b.date.year(), b.date.month()))
+        .collect();
+    let mut list = String::new();
+    if months.len() == 1 {
+        list.extend(months.remove(0).iter().map(link));
+    } else {
+        for month in months {
+            list.push_str(&format!(
+                "<li><a href=\"{}\">{}</a>\n<ol>\n",
+                month[0].href,
+                month[0].date.format("%B %Y")
+            ));
+            list.extend(month.iter().map(link));
+            list.push_str("</ol></li>\n");
+        }
+    }
+    page(
+        title,
+        "style.css",
+        &format!(
+            "<nav epub:type=\"toc\" id=\"toc\">\n<h1>{}</h1>\n<ol>\n{}</ol>\n</nav>\n",
+            escape(title),
+            list
+        ),
+    )
+}
+
+/// EPUB 2 table of contents, for older readers
+fn ncx(identifier: &str, title: &str, chapters: &[Chapter]) -> String {
+    let points: String = chapters
+        .iter()
+        .enumerate()
+        .map(|(index, chapter)| {
+            format!(
+                "<navPoint id=\"p{0}\" playOrder=\"{0}\"><navLabel><text>{1}</text></navLabel><content src=\"{2}\"/></navPoint>\n",
+                index + 1,
+                escape(&chapter_title(chapter.date)),
+                chapter.href
+            )
+        })
+        .collect();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <ncx xmlns=\"http://www.daisy.org/z3986/2005/ncx/\" version=\"2005-1\">\n\
+         <head><meta name=\"dtb:uid\" content=\"{}\"/></head>\n\
+         <docTitle><text>{}</text></docTitle>\n\
+         <navMap>\n{}</navMap>\n</ncx>\n",
+        escape(identifier),
+        escape(title),
+        points
+    )
+}
+
+fn opf(identifier: &str, title: &str, chapters: &[Chapter], images: &[Image]) -> String {
+    let mut manifest = String::from(
+        "<item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n\
+         <item id=\"ncx\" href=\"toc.ncx\" media-type=\"application/x-dtbncx+xml\"/>\n\
+         <item id=\"style\" href=\"style.css\" media-type=\"text/css\"/>\n\
+         <item id=\"cover-image\" href=\"cover.svg\" media-type=\"image/svg+xml\" properties=\"cover-image\"/>\n\
+         <item id=\"cover\" href=\"cover.xhtml\" media-type=\"application/xhtml+xml\"/>\n",
+    );
+    let mut spine = String::from("<itemref idref=\"cover\"/>\n<itemref idref=\"nav\"/>\n");
+    for chapter in chapters {
+        let id = chapter.date.format("e%Y-%m-%d");
+        manifest.push_str(&format!(
+            "<item id=\"{}\" href=\"{}\" media-type=\"application/xhtml+xml\"/>\n",
+            id, chapter.href
+        ));
+        spine.push_str(&format!("<itemref idref=\"{}\"/>\n", id));
+    }
+    for (index, image) in images.iter().enumerate() {
+        manifest.push_str(&format!(
+            "<item id=\"img{}\" href=\"{}\" media-type=\"{}\"/>\n",
+            index + 1,
+            image.href,
+            image.media_type
+        ));
+    }
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\" unique-identifier=\"book-id\">\n\
+         <metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n\
+         <dc:identifier id=\"book-id\">{}</dc:identifier>\n\
+         <dc:title>{}</dc:title>\n\
+         <dc:language>en</dc:language>\n\
+         <meta property=\"dcterms:modified\">{}</meta>\n\
+         <meta name=\"cover\" content=\"cover-image\"/>\n\
+         </metadata>\n\
+         <manifest>\n{}</manifest>\n\
+         <spine toc=\"ncx\">\n{}</spine>\n\
+         </package>\n",
+        escape(identifier),
+        escape(title),
+        Utc::now().format("%Y-%m-%dT%H:%M:%SZ"),
+        manifest,
+        spine
+    )
+}
+
+/// Compile entries (date, file, markdown; oldest first) into an EPUB 3 book
+/// with a cover, a table of contents and the local images they show
+pub fn build(title: &str, entries: &[(NaiveDate, PathBuf, String)]) -> Result<Vec<u8>> {
+    let (Some((first, _, _)), Some((last, _, _))) = (entries.first(), entries.last()) else {
+        return Err(JournalError::ExportFailed(
+            "No entries to put in the book".to_string(),
+        ));
+    };
+    let identifier = format!("urn:easy-journal:{}:{}", first, last);
+    let subtitle = if first == last {
+        first.format("%B %-d, %Y").to_string()
+    } else {
+        format!(
+            "{} – {}",
+            first.format("%B %-d, %Y"),
+            last.format("%B %-d, %Y")
+        )
+    };
+
+    let mut images = Vec::new();
+    let chapters: Vec<Chapter> = entries
+        .iter()
+        .map(|(date, path, content)| Chapter {
+            date: *date,
+            href: date.format("text/%Y-%m-%d.xhtml").to_string(),
+            body: render(
+                content,
+                path.parent().unwrap_or(Path::new(".")),
+                &mut images,
+            ),
+        })
+        .collect();
+
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let mut add = |name: &str, content: &[u8], options: SimpleFileOptions| -> Result<()> {
+        zip.start_file(name, options).map_err(export_error)?;
+        zip.write_all(content).map_err(export_error)
+    };
+
+    // Readers identify the format by an uncompressed first member
+    add("mimetype", b"application/epub+zip", stored)?;
+    add(
+        "META-INF/container.xml",
+        b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+          <container version=\"1.0\" xmlns=\"urn:oasis:names:tc:opendocument:xmlns:container\">\n\
+          <rootfiles><rootfile full-path=\"OEBPS/content.opf\" media-type=\"application/oebps-package+xml\"/></rootfiles>\n\
+          </container>\n",
+        deflated,
+    )?;
+    add(
+        "OEBPS/content.opf",
+        opf(&identifier, title, &chapters, &images).as_bytes(),
+        deflated,
+    )?;
+    add(
+        "OEBPS/toc.ncx",
+        ncx(&identifier, title, &chapters).as_bytes(),
+        deflated,
+    )?;
+    add(
+        "OEBPS/nav.xhtml",
+        nav(title, &chapters).as_bytes(),
+        deflated,
+    )?;
+    add("OEBPS/style.css", STYLE.as_bytes(), deflated)?;
+    add(
+        "OEBPS/cover.svg",
+        cover_svg(title, &subtitle).as_bytes(),
+        deflated,
+    )?;
+    add(
+        "OEBPS/cover.xhtml",
+        page(
+            title,
+            "style.css",
+            &format!(
+                "<div class=\"cover\"><img src=\"cover.svg\" alt=\"{}\"/></div>\n",
+                escape(title)
+            ),
+        )
+        .as_bytes(),
+        deflated,
+    )?;
+    for chapter in &chapters {
+        add(
+            &format!("OEBPS/{}", chapter.href),
+            page(&chapter_title(chapter.date), "../style.css", &chapter.body).as_bytes(),
+            deflated,
+        )?;
+    }
+    for image in &images {
+        let content = fs::read(&image.source).map_err(JournalError::at_path(&image.source))?;
+        add(&format!("OEBPS/{}", image.href), &content, stored)?;
+    }
+
+    Ok(zip.finish().map_err(export_error)?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_build() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_epub_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("2025/12/img")).unwrap();
+        fs::write(dir.join("2025/12/img/hike.png"), b"\x89PNG").unwrap();
+
+        let date = |day| NaiveDate::from_ymd_opt(2025, 12, day).unwrap();
+        let entries = vec![
+            (
+                date(28),
+                dir.join("2025/12/28.md"),
+                "# Sunday\n\n![Hike](img/hike.png)\n![Remote](https://example.com/a.png)\n<!-- private -->\n".to_string(),
+            ),
+            (
+                date(29),
+                dir.join("2025/12/29.md"),
+                "# Monday & more\n\n- [x] Done <b>bold</b>\n".to_string(),
+            ),
+        ];
+        let book = build("Journal: December 2025", &entries).unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(book)).unwrap();
+        let names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        assert_eq!(names[0], "mimetype");
+        assert_eq!(
+            archive.by_index(0).unwrap().compression(),
+            CompressionMethod::Stored
+        );
+        for name in [
+            "OEBPS/content.opf",
+            "OEBPS/nav.xhtml",
+            "OEBPS/toc.ncx",
+            "OEBPS/cover.svg",
+            "OEBPS/text/2025-12-28.xhtml",
+            "OEBPS/text/2025-12-29.xhtml",
+            "OEBPS/images/1.png",
+        ] {
+            assert!(names.iter().any(|n| n == name), "{} missing", name);
+        }
+
+        let read = |archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>, name: &str| {
+            let mut content = String::new();
+            archive
+                .by_name(name)
+                .unwrap()
+                .read_to_string(&mut content)
+                .unwrap();
+            content
+        };
+        let sunday = read(&mut archive, "OEBPS/text/2025-12-28.xhtml");
+        assert!(sunday.contains("src=\"../images/1.png\""));
+        assert!(sunday.contains("src=\"https://example.com/a.png\""));
+        assert!(!sunday.contains("private"));
+        let monday = read(&mut archive, "OEBPS/text/2025-12-29.xhtml");
+        assert!(monday.contains("Monday &amp; more"));
+        assert!(monday.contains("&lt;b&gt;bold"));
+        let nav = read(&mut archive, "OEBPS/nav.xhtml");
+        assert!(nav.contains("<a href=\"text/2025-12-28.xhtml\">Sunday, December 28, 2025</a>"));
+        let opf = read(&mut archive, "OEBPS/content.opf");
+        assert!(opf.contains("href=\"images/1.png\" media-type=\"image/png\""));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,67 +1,288 @@
 use chrono::NaiveDate;
-use std::fs;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{JournalError, Result};
 use crate::journal::template;
 
-pub fn ensure_year_dir(year: u32, base_path: &Path) -> Result<PathBuf> {
-    let year_path = base_path.join(year.to_string());
-    fs::create_dir_all(&year_path)?;
-    Ok(year_path)
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// How entries are arranged under the journal directory (JOURNAL_LAYOUT)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Layout {
+    /// `2025/12/29.md`, with `2025/README.md` and `2025/12/README.md`
+    #[default]
+    Nested,
+    /// `2025-12-29.md`, with `2025.md` and `2025-12.md`, like most daily-note apps
+    Flat,
+    /// `2026/W01/2025-12-29.md` by ISO week, with `2025/README.md` and `2025/12.md`
+    Weekly,
+}
+
+impl Layout {
+    pub const ALL: [Layout; 3] = [Layout::Nested, Layout::Flat, Layout::Weekly];
+
+    pub fn parse(name: &str) -> Option<Layout> {
+        match name.trim().to_lowercase().as_str() {
+            "nested" => Some(Layout::Nested),
+            "flat" => Some(Layout::Flat),
+            "weekly" => Some(Layout::Weekly),
+            _ => None,
+        }
+    }
+
+    /// Path of the entry for `date`, relative to the journal directory
+    pub fn entry(self, date: NaiveDate) -> String {
+        match self {
+            Layout::Nested => date.format("%Y/%m/%d.md").to_string(),
+            Layout::Flat => date.format("%Y-%m-%d.md").to_string(),
+            Layout::Weekly => date.format("%G/W%V/%Y-%m-%d.md").to_string(),
+        }
+    }
+
+    /// Path of a year's overview page, relative to the journal directory
+    pub fn year_page(self, year: u32) -> String {
+        match self {
+            Layout::Flat => format!("{}.md", year),
+            Layout::Nested | Layout::Weekly => format!("{}/README.md", year),
+        }
+    }
+
+    /// Path of a month's overview page, relative to the journal directory
+    pub fn month_page(self, year: u32, month: u32) -> String {
+        match self {
+            Layout::Nested => format!("{}/{:02}/README.md", year, month),
+            Layout::Flat => format!("{}-{:02}.md", year, month),
+            Layout::Weekly => format!("{}/{:02}.md", year, month),
+        }
+    }
+
+    /// The date of the entry at `path` (relative, `/`-separated), if it is
+    /// where this layout puts one
+    pub fn entry_date(self, path: &str) -> Option<NaiveDate> {
+        let date = match self {
+            Layout::Nested => {
+                let mut parts = path.strip_suffix(".md")?.split('/');
+                let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+                NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)?
+            }
+            Layout::Flat | Layout::Weekly => {
+                NaiveDate::parse_from_str(path.rsplit('/').next()?.strip_suffix(".md")?, "%Y-%m-%d")
+                    .ok()?
+            }
+        };
+        (self.entry(date) == path).then_some(date)
+    }
+
+    /// The year and month of the overview page at `path`, in any layout
+    pub fn month_of_page(path: &str) -> Option<(u32, u32)> {
+        let (year, month) = match path.strip_suffix("/README.md") {
+            Some(dir) => dir.split_once('/')?,
+            None => path.strip_suffix(".md")?.split_once(['-', '/'])?,
+        };
+        let (year, month) = (year.parse().ok()?, month.parse().ok()?);
+        (1..=12).contains(&month).then_some((year, month))
+    }
+}
+
+impl std::fmt::Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Layout::Nested => "nested",
+            Layout::Flat => "flat",
+            Layout::Weekly => "weekly",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Write a file atomically: the content goes to a temp file in the same
+/// directory which is then renamed over the target, so a crash or panic
+/// mid-write never leaves a half-written file behind
+pub fn write_atomic(path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+    write_atomic_as(path, content.as_ref(), false)
+}
+
+/// Like [`write_atomic`], but only the owner can read the file (mode 0600 on
+/// Unix), which is set on the temp file before anything is written to it
+pub fn write_private(path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+    write_atomic_as(path, content.as_ref(), true)
+}
+
+fn write_atomic_as(path: &Path, content: &[u8], private: bool) -> Result<()> {
+    tracing::debug!(path = %path.display(), bytes = content.len(), "writing");
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp_path = dir.join(format!(
+        ".{}.{}-{}.tmp",
+        file_name,
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    let result = options
+        .open(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(content)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result.map_err(JournalError::at_path(path))
+}
+
+/// Copy a directory tree into `to`, creating it if needed
+pub fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to).map_err(JournalError::at_path(to))?;
+    for item in fs::read_dir(from).map_err(JournalError::at_path(from))? {
+        let path = item?.path();
+        let target = to.join(path.file_name().unwrap_or_default());
+        if path.is_dir() {
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target).map_err(JournalError::at_path(&path))?;
+        }
+    }
+    Ok(())
+}
+
+/// Create the directory the entry for `date` goes in
+pub fn ensure_entry_dir(date: NaiveDate, base_path: &Path, layout: Layout) -> Result<PathBuf> {
+    let entry_path = get_entry_path(date, base_path, layout);
+    let dir = entry_path.parent().unwrap_or(base_path).to_path_buf();
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Path of a month's overview page (its README in the nested layout)
+pub fn get_month_path(year: u32, month: u32, base_path: &Path, layout: Layout) -> PathBuf {
+    base_path.join(layout.month_page(year, month))
 }
 
-pub fn ensure_month_dir(year: u32, month: u32, base_path: &Path) -> Result<PathBuf> {
-    let month_path = base_path
-        .join(year.to_string())
-        .join(format!("{:02}", month));
-    fs::create_dir_all(&month_path)?;
-    Ok(month_path)
+/// Path of a year's overview page (its README in the nested layout)
+pub fn get_year_path(year: u32, base_path: &Path, layout: Layout) -> PathBuf {
+    base_path.join(layout.year_page(year))
 }
 
 pub fn create_month_readme(year: u32, month: u32, base_path: &Path, config: &Config) -> Result<()> {
-    let month_path = base_path
-        .join(year.to_string())
-        .join(format!("{:02}", month));
-    let readme_path = month_path.join("README.md");
+    let readme_path = get_month_path(year, month, base_path, config.layout);
 
     // Don't overwrite existing README
     if readme_path.exists() {
         return Ok(());
     }
+    if let Some(parent) = readme_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
 
     // Load and apply month template
     let template_content = template::load_month_template(&config.month_template_path)?;
     let content = template::apply_month_variables(&template_content, year, month);
 
-    fs::write(readme_path, content)?;
+    write_atomic(&readme_path, content)?;
     Ok(())
 }
 
 pub fn create_year_readme(year: u32, base_path: &Path, config: &Config) -> Result<()> {
-    let year_path = base_path.join(year.to_string());
-    let readme_path = year_path.join("README.md");
+    let readme_path = get_year_path(year, base_path, config.layout);
 
     // Don't overwrite existing README
     if readme_path.exists() {
         return Ok(());
     }
+    if let Some(parent) = readme_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
 
     // Load and apply year template
     let template_content = template::load_year_template(&config.year_template_path)?;
     let content = template::apply_year_variables(&template_content, year);
 
-    fs::write(readme_path, content)?;
+    write_atomic(&readme_path, content)?;
     Ok(())
 }
 
-pub fn get_entry_path(date: NaiveDate, base_path: &Path) -> PathBuf {
-    let year = date.format("%Y").to_string();
-    let month = date.format("%m").to_string();
-    let day = date.format("%d").to_string();
+pub fn get_entry_path(date: NaiveDate, base_path: &Path, layout: Layout) -> PathBuf {
+    base_path.join(layout.entry(date))
+}
+
+/// Path of the weekly file for the ISO week containing `date`, e.g. `weeks/2025-W52.md`
+pub fn get_week_path(date: NaiveDate, base_path: &Path) -> PathBuf {
+    base_path.join(week_page(date))
+}
+
+/// Relative path of the weekly file for the ISO week containing `date`
+pub fn week_page(date: NaiveDate) -> String {
+    date.format("weeks/%G-W%V.md").to_string()
+}
+
+/// Monday of the week a `weeks/2025-W52.md` path is for
+pub fn week_of_page(path: &str) -> Option<NaiveDate> {
+    let week = path.strip_prefix("weeks/")?.strip_suffix(".md")?;
+    NaiveDate::parse_from_str(&format!("{}-1", week), "%G-W%V-%u").ok()
+}
 
-    base_path.join(year).join(month).join(format!("{}.md", day))
+/// List all daily entries under the journal directory, oldest first. Only
+/// files where `layout` puts entries count.
+pub fn list_entries(base_path: &Path, layout: Layout) -> Result<Vec<(NaiveDate, PathBuf)>> {
+    let mut entries: Vec<(NaiveDate, PathBuf)> = journal_files(base_path)?
+        .into_iter()
+        .filter_map(|(relative, path)| Some((layout.entry_date(&relative)?, path)))
+        .collect();
+    entries.sort_by_key(|(date, _)| *date);
+    Ok(entries)
+}
+
+/// Files in the journal directory and the year, month and week directories
+/// under it (at most two deep), with their `/`-separated relative paths
+pub(crate) fn journal_files(base_path: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    if !base_path.exists() {
+        return Ok(files);
+    }
+
+    let mut dirs = vec![(base_path.to_path_buf(), String::new())];
+    while let Some((dir, prefix)) = dirs.pop() {
+        for item in fs::read_dir(&dir)? {
+            let path = item?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let relative = format!("{}{}", prefix, name);
+            if path.is_dir() {
+                let numbered = name.trim_start_matches('W').parse::<u32>().is_ok();
+                if numbered && prefix.matches('/').count() < 2 {
+                    dirs.push((path.clone(), format!("{}/", relative)));
+                }
+            } else {
+                files.push((relative, path));
+            }
+        }
+    }
+    Ok(files)
 }
 
 #[cfg(test)]
@@ -71,7 +292,121 @@ mod tests {
     #[test]
     fn test_get_entry_path() {
         let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
-        let path = get_entry_path(date, Path::new("journal"));
+        let path = get_entry_path(date, Path::new("journal"), Layout::Nested);
         assert_eq!(path, PathBuf::from("journal/2025/12/29.md"));
     }
+
+    #[test]
+    fn test_layouts() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let paths: Vec<(String, String, String)> = Layout::ALL
+            .iter()
+            .map(|layout| {
+                (
+                    layout.entry(date),
+                    layout.year_page(2025),
+                    layout.month_page(2025, 12),
+                )
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                (
+                    "2025/12/29.md".to_string(),
+                    "2025/README.md".to_string(),
+                    "2025/12/README.md".to_string()
+                ),
+                (
+                    "2025-12-29.md".to_string(),
+                    "2025.md".to_string(),
+                    "2025-12.md".to_string()
+                ),
+                (
+                    "2026/W01/2025-12-29.md".to_string(),
+                    "2025/README.md".to_string(),
+                    "2025/12.md".to_string()
+                ),
+            ]
+        );
+        for layout in Layout::ALL {
+            assert_eq!(layout.entry_date(&layout.entry(date)), Some(date));
+            assert_eq!(
+                Layout::month_of_page(&layout.month_page(2025, 12)),
+                Some((2025, 12))
+            );
+        }
+        assert_eq!(Layout::Nested.entry_date("2025-12-29.md"), None);
+        assert_eq!(Layout::Flat.entry_date("2025/12/29.md"), None);
+        assert_eq!(Layout::Weekly.entry_date("2025/W01/2025-12-29.md"), None);
+        assert_eq!(Layout::month_of_page("2025.md"), None);
+        assert_eq!(Layout::parse(" Flat"), Some(Layout::Flat));
+    }
+
+    #[test]
+    fn test_get_week_path() {
+        // Dec 29, 2025 is in the first ISO week of 2026
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let path = get_week_path(date, Path::new("journal"));
+        assert_eq!(path, PathBuf::from("journal/weeks/2026-W01.md"));
+    }
+
+    #[test]
+    fn test_list_entries() {
+        let base = std::env::temp_dir().join(format!("easy_journal_list_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        fs::create_dir_all(base.join("2025/12")).unwrap();
+        fs::create_dir_all(base.join("2026/01")).unwrap();
+        fs::write(base.join("2025/12/README.md"), "").unwrap();
+        fs::write(base.join("2025/12/30.md"), "").unwrap();
+        fs::write(base.join("2026/01/02.md"), "").unwrap();
+        fs::write(base.join("SUMMARY.md"), "").unwrap();
+
+        fs::write(base.join("2025-12-31.md"), "").unwrap();
+        let entries = list_entries(&base, Layout::Nested).unwrap();
+        let dates: Vec<NaiveDate> = entries.iter().map(|(d, _)| *d).collect();
+        assert_eq!(
+            dates,
+            vec![
+                NaiveDate::from_ymd_opt(2025, 12, 30).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 1, 2).unwrap(),
+            ]
+        );
+        let flat = list_entries(&base, Layout::Flat).unwrap();
+        assert_eq!(
+            flat,
+            vec![(
+                NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+                base.join("2025-12-31.md")
+            )]
+        );
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_atomic_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("29.md");
+
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+
+        // No temp files are left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let secret = dir.join("token.json");
+            write_private(&secret, "secret").unwrap();
+            let mode = fs::metadata(&secret).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,189 @@
+use chrono::{Duration, NaiveDate};
+use serde::Deserialize;
+
+use crate::config::FitbitConfig;
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::oauth;
+use crate::journal::parser::format_duration;
+
+#[derive(Deserialize, Debug)]
+struct SleepResponse {
+    #[serde(default)]
+    sleep: Vec<SleepLog>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SleepLog {
+    is_main_sleep: bool,
+    minutes_asleep: u32,
+    efficiency: u32,
+    start_time: String,
+    end_time: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ActivityResponse {
+    summary: ActivitySummary,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct ActivitySummary {
+    #[serde(default)]
+    steps: u32,
+    #[serde(default)]
+    fairly_active_minutes: u32,
+    #[serde(default)]
+    very_active_minutes: u32,
+    #[serde(default)]
+    calories_out: u32,
+}
+
+fn client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::FitbitFailed(format!("Failed to build HTTP client: {}", e)))
+}
+
+async fn get<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    token: &str,
+    url: &str,
+) -> Result<T> {
+    let response = client
+        .get(url)
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|e| JournalError::FitbitFailed(format!("Request failed: {}", e)))?;
+    check_response(response, "Fitbit")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::FitbitFailed(format!("Failed to parse response: {}", e)))
+}
+
+/// `9432` → `9,432`
+fn thousands(value: u32) -> String {
+    let digits = value.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// `2025-12-29T23:41:30.000` → `23:41`
+fn clock(timestamp: &str) -> &str {
+    timestamp
+        .split_once('T')
+        .map(|(_, time)| time.get(..5).unwrap_or(time))
+        .unwrap_or(timestamp)
+}
+
+/// Footer metrics and summary lines for the entry.
+///
+/// The Fitbit Web API doesn't expose the app's Sleep Score, so the main
+/// sleep's efficiency (0-100) fills `**Sleep Score**`.
+fn format_day(sleep: &[SleepLog], activity: Option<&ActivitySummary>) -> Option<String> {
+    let mut lines = Vec::new();
+
+    if let Some(main) = sleep.iter().find(|s| s.is_main_sleep).or(sleep.first()) {
+        let asleep: u32 = sleep.iter().map(|s| s.minutes_asleep).sum();
+        lines.push(format!("**Sleep Score**: {}", main.efficiency));
+        lines.push(format!("**Sleep Time**: {}", format_duration(asleep)));
+        lines.push(format!(
+            "- 😴 Slept {} – {} ({} asleep, {}% efficiency)",
+            clock(&main.start_time),
+            clock(&main.end_time),
+            format_duration(main.minutes_asleep),
+            main.efficiency
+        ));
+    }
+
+    if let Some(activity) = activity.filter(|a| a.steps > 0 || a.calories_out > 0) {
+        lines.push(format!(
+            "- 🏃 Yesterday: {} steps, {} active minutes, {} kcal",
+            thousands(activity.steps),
+            activity.fairly_active_minutes + activity.very_active_minutes,
+            thousands(activity.calories_out)
+        ));
+    }
+
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Last night's sleep (logged on `date`) and the previous day's activity
+pub async fn fetch_fitbit(config: &FitbitConfig, date: NaiveDate) -> Result<Option<String>> {
+    let Some(token) = oauth::fitbit_access_token(config).await? else {
+        if config.client_id.is_some() {
+            return Err(JournalError::FitbitFailed(
+                "Not signed in. Run `easy_journal auth fitbit`.".to_string(),
+            ));
+        }
+        tracing::debug!("Fitbit not configured; run `easy_journal auth fitbit`");
+        return Ok(None);
+    };
+
+    let client = client()?;
+    let sleep_url = format!(
+        "https://api.fitbit.com/1.2/user/-/sleep/date/{}.json",
+        date.format("%Y-%m-%d")
+    );
+    let activity_url = format!(
+        "https://api.fitbit.com/1/user/-/activities/date/{}.json",
+        (date - Duration::days(1)).format("%Y-%m-%d")
+    );
+    let (sleep, activity) = tokio::join!(
+        get::<SleepResponse>(&client, &token, &sleep_url),
+        get::<ActivityResponse>(&client, &token, &activity_url)
+    );
+
+    let sleep = sleep?;
+    let activity = match activity {
+        Ok(activity) => Some(activity.summary),
+        Err(e) => {
+            tracing::warn!("Could not fetch Fitbit activity: {}", e);
+            None
+        }
+    };
+    Ok(format_day(&sleep.sleep, activity.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_day() {
+        let sleep: SleepResponse = serde_json::from_str(
+            r#"{"sleep": [
+                {"isMainSleep": false, "minutesAsleep": 25, "efficiency": 90,
+                 "startTime": "2025-12-28T15:10:00.000", "endTime": "2025-12-28T15:40:00.000"},
+                {"isMainSleep": true, "minutesAsleep": 412, "efficiency": 93,
+                 "startTime": "2025-12-28T23:41:30.000", "endTime": "2025-12-29T07:08:00.000"}
+            ]}"#,
+        )
+        .unwrap();
+        let activity = ActivitySummary {
+            steps: 9432,
+            fairly_active_minutes: 20,
+            very_active_minutes: 28,
+            calories_out: 2310,
+        };
+
+        assert_eq!(
+            format_day(&sleep.sleep, Some(&activity)).unwrap(),
+            "**Sleep Score**: 93\n\
+             **Sleep Time**: 7h 17m\n\
+             - 😴 Slept 23:41 – 07:08 (6h 52m asleep, 93% efficiency)\n\
+             - 🏃 Yesterday: 9,432 steps, 48 active minutes, 2,310 kcal"
+        );
+        assert_eq!(format_day(&[], Some(&ActivitySummary::default())), None);
+    }
+}
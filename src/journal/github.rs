@@ -1,5 +1,6 @@
 use crate::config::GitHubConfig;
-use crate::error::{JournalError, Result};
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::oauth;
 use serde::Deserialize;
 
 #[derive(Debug, Clone)]
@@ -50,12 +51,13 @@ struct GitHubSearchResponse {
 pub async fn fetch_github_items(config: &GitHubConfig) -> Result<Option<String>> {
     // Early return if not enabled
     if !config.enabled {
+        tracing::debug!("GitHub disabled; pass --github to include it");
         return Ok(None);
     }
 
-    let token = config.token.as_ref().ok_or_else(|| {
+    let token = oauth::github_token(config)?.ok_or_else(|| {
         JournalError::GitHubFailed(
-            "GITHUB_TOKEN not set. Set the environment variable and use --github flag.".to_string(),
+            "Not signed in. Run `easy_journal auth github` or set GITHUB_TOKEN.".to_string(),
         )
     })?;
 
@@ -149,6 +151,7 @@ async fn fetch_assigned_issues(client: &reqwest::Client, token: &str) -> Result<
         .map_err(|e| {
             JournalError::GitHubFailed(format!("Failed to fetch assigned issues: {}", e))
         })?;
+    let response = check_response(response, "GitHub").await?;
 
     let issues: Vec<GitHubApiIssue> = response.json().await.map_err(|e| {
         JournalError::GitHubFailed(format!("Failed to parse assigned issues: {}", e))
@@ -197,6 +200,7 @@ async fn fetch_created_issues(client: &reqwest::Client, token: &str) -> Result<V
         .map_err(|e| {
             JournalError::GitHubFailed(format!("Failed to fetch created issues: {}", e))
         })?;
+    let response = check_response(response, "GitHub").await?;
 
     let issues: Vec<GitHubApiIssue> = response.json().await.map_err(|e| {
         JournalError::GitHubFailed(format!("Failed to parse created issues: {}", e))
@@ -243,6 +247,7 @@ async fn fetch_assigned_prs(client: &reqwest::Client, token: &str) -> Result<Vec
         .send()
         .await
         .map_err(|e| JournalError::GitHubFailed(format!("Failed to fetch assigned PRs: {}", e)))?;
+    let response = check_response(response, "GitHub").await?;
 
     let issues: Vec<GitHubApiIssue> = response
         .json()
@@ -289,6 +294,7 @@ async fn fetch_review_requests(client: &reqwest::Client, token: &str) -> Result<
         .map_err(|e| {
             JournalError::GitHubFailed(format!("Failed to fetch review requests: {}", e))
         })?;
+    let response = check_response(response, "GitHub").await?;
 
     let search_response: GitHubSearchResponse = response.json().await.map_err(|e| {
         JournalError::GitHubFailed(format!("Failed to parse review requests: {}", e))
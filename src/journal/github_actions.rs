@@ -0,0 +1,254 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::config::GitHubConfig;
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::oauth;
+
+/// Recently pushed repositories checked when GITHUB_CI_REPOS is not set
+const RECENT_REPOS: &str = "30";
+
+#[derive(Deserialize, Debug)]
+struct User {
+    login: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Repo {
+    full_name: String,
+    owner: User,
+}
+
+#[derive(Deserialize, Debug)]
+struct RunsResponse {
+    workflow_runs: Vec<WorkflowRun>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct WorkflowRun {
+    workflow_id: u64,
+    /// Workflow name
+    name: Option<String>,
+    display_title: Option<String>,
+    head_branch: Option<String>,
+    html_url: String,
+    status: Option<String>,
+    conclusion: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+/// The latest completed run of each workflow on each branch, kept only if it failed.
+/// A failure followed by a green run on the same branch is already fixed.
+fn still_failing(runs: Vec<WorkflowRun>) -> Vec<WorkflowRun> {
+    let mut latest: HashMap<(u64, Option<String>), WorkflowRun> = HashMap::new();
+    for run in runs
+        .into_iter()
+        .filter(|run| run.status.as_deref() == Some("completed"))
+    {
+        let key = (run.workflow_id, run.head_branch.clone());
+        if latest
+            .get(&key)
+            .is_none_or(|seen| seen.created_at < run.created_at)
+        {
+            latest.insert(key, run);
+        }
+    }
+
+    let mut failing: Vec<WorkflowRun> = latest
+        .into_values()
+        .filter(|run| {
+            matches!(
+                run.conclusion.as_deref(),
+                Some("failure" | "timed_out" | "startup_failure")
+            )
+        })
+        .collect();
+    failing.sort_by_key(|run| std::cmp::Reverse(run.created_at));
+    failing
+}
+
+fn format_runs(failing: &[(String, WorkflowRun)]) -> Option<String> {
+    if failing.is_empty() {
+        return None;
+    }
+    Some(
+        failing
+            .iter()
+            .map(|(repo, run)| {
+                let mut line = format!(
+                    "- [ ] ❌ [{}] {}",
+                    repo,
+                    run.name.as_deref().unwrap_or("Workflow")
+                );
+                if let Some(branch) = &run.head_branch {
+                    line.push_str(&format!(" on `{}`", branch));
+                }
+                line.push_str(&format!(
+                    ": [{}]({})",
+                    run.display_title.as_deref().unwrap_or("run"),
+                    run.html_url
+                ));
+                match run.conclusion.as_deref() {
+                    Some("timed_out") => line.push_str(" (timed out)"),
+                    Some("startup_failure") => line.push_str(" (startup failure)"),
+                    _ => {}
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+async fn get<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    token: &str,
+    url: &str,
+    query: &[(&str, &str)],
+) -> Result<T> {
+    let response = client
+        .get(url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .query(query)
+        .send()
+        .await
+        .map_err(|e| JournalError::GitHubFailed(format!("Request failed: {}", e)))?;
+    check_response(response, "GitHub")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::GitHubFailed(format!("Failed to parse response: {}", e)))
+}
+
+/// Runs created since `since` in `repo`, only those started by `actor` if given
+async fn fetch_runs(
+    client: &reqwest::Client,
+    token: &str,
+    repo: &str,
+    since: &str,
+    actor: Option<&str>,
+) -> Result<Vec<WorkflowRun>> {
+    let mut query = vec![("created", since), ("per_page", "100")];
+    if let Some(actor) = actor {
+        query.push(("actor", actor));
+    }
+    let runs: RunsResponse = get(
+        client,
+        token,
+        &format!("https://api.github.com/repos/{}/actions/runs", repo),
+        &query,
+    )
+    .await?;
+    Ok(runs.workflow_runs)
+}
+
+/// Workflow runs from the last 24 hours that failed and haven't gone green since,
+/// on repositories you own or started by you elsewhere
+pub async fn fetch_failed_runs(config: &GitHubConfig) -> Result<Option<String>> {
+    if !config.enabled {
+        tracing::debug!("GitHub disabled; pass --github to include it");
+        return Ok(None);
+    }
+
+    let token = oauth::github_token(config)?.ok_or_else(|| {
+        JournalError::GitHubFailed(
+            "Not signed in. Run `easy_journal auth github` or set GITHUB_TOKEN.".to_string(),
+        )
+    })?;
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::GitHubFailed(format!("Failed to build HTTP client: {}", e)))?;
+
+    let user: User = get(&client, &token, "https://api.github.com/user", &[]).await?;
+
+    // (repo, actor filter): every run on your own repositories, only yours elsewhere
+    let repos: Vec<(String, Option<&str>)> = if config.ci_repos.is_empty() {
+        let repos: Vec<Repo> = get(
+            &client,
+            &token,
+            "https://api.github.com/user/repos",
+            &[("sort", "pushed"), ("per_page", RECENT_REPOS)],
+        )
+        .await?;
+        repos
+            .into_iter()
+            .map(|repo| {
+                let actor = (repo.owner.login != user.login).then_some(user.login.as_str());
+                (repo.full_name, actor)
+            })
+            .collect()
+    } else {
+        config
+            .ci_repos
+            .iter()
+            .map(|repo| (repo.clone(), None))
+            .collect()
+    };
+
+    let since = format!(
+        ">={}",
+        (Utc::now() - Duration::hours(24)).format("%Y-%m-%dT%H:%M:%SZ")
+    );
+    let results = futures::future::join_all(
+        repos
+            .iter()
+            .map(|(repo, actor)| fetch_runs(&client, &token, repo, &since, *actor)),
+    )
+    .await;
+
+    let mut failing = Vec::new();
+    for ((repo, _), result) in repos.iter().zip(results) {
+        match result {
+            Ok(runs) => failing.extend(still_failing(runs).into_iter().map(|r| (repo.clone(), r))),
+            // Actions disabled, or no access to its runs
+            Err(e) => tracing::debug!(repo = repo.as_str(), "Skipping workflow runs: {}", e),
+        }
+    }
+    failing.sort_by_key(|(_, run)| std::cmp::Reverse(run.created_at));
+
+    Ok(format_runs(&failing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_still_failing() {
+        let response: RunsResponse = serde_json::from_str(
+            r#"{"workflow_runs": [
+                {"workflow_id": 1, "name": "CI", "display_title": "Bump deps", "head_branch": "main",
+                 "html_url": "https://github.com/me/app/actions/runs/3", "status": "completed",
+                 "conclusion": "failure", "created_at": "2025-12-28T10:00:00Z"},
+                {"workflow_id": 1, "name": "CI", "display_title": "Flaky", "head_branch": "fix",
+                 "html_url": "https://github.com/me/app/actions/runs/2", "status": "completed",
+                 "conclusion": "failure", "created_at": "2025-12-28T09:00:00Z"},
+                {"workflow_id": 1, "name": "CI", "display_title": "Retry", "head_branch": "fix",
+                 "html_url": "https://github.com/me/app/actions/runs/4", "status": "completed",
+                 "conclusion": "success", "created_at": "2025-12-28T11:00:00Z"},
+                {"workflow_id": 2, "name": "Nightly", "display_title": "Nightly", "head_branch": "main",
+                 "html_url": "https://github.com/me/app/actions/runs/1", "status": "completed",
+                 "conclusion": "timed_out", "created_at": "2025-12-28T02:00:00Z"},
+                {"workflow_id": 2, "name": "Nightly", "display_title": "Nightly", "head_branch": "main",
+                 "html_url": "https://github.com/me/app/actions/runs/5", "status": "in_progress",
+                 "conclusion": null, "created_at": "2025-12-28T12:00:00Z"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let failing: Vec<(String, WorkflowRun)> = still_failing(response.workflow_runs)
+            .into_iter()
+            .map(|run| ("me/app".to_string(), run))
+            .collect();
+        assert_eq!(
+            format_runs(&failing).unwrap(),
+            "- [ ] ❌ [me/app] CI on `main`: [Bump deps](https://github.com/me/app/actions/runs/3)\n\
+             - [ ] ❌ [me/app] Nightly on `main`: [Nightly](https://github.com/me/app/actions/runs/1) (timed out)"
+        );
+        assert_eq!(format_runs(&[]), None);
+    }
+}
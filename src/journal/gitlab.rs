@@ -1,6 +1,12 @@
 use crate::config::GitLabConfig;
-use crate::error::{JournalError, Result};
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::oauth::{self, GitLabAuth};
+use chrono::{Duration, Utc};
 use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Projects checked for failed pipelines on your branches, most recently active first
+const PIPELINE_PROJECTS: &str = "20";
 
 #[derive(Debug, Clone)]
 pub struct GitLabItem {
@@ -11,6 +17,32 @@ pub struct GitLabItem {
     pub labels: Vec<String>,
     pub due_date: Option<String>,
     pub item_type: GitLabItemType,
+    /// MRs only
+    pub project_id: Option<u64>,
+    /// Head pipeline of an MR, when pipelines are looked up
+    pub pipeline: Option<GitLabPipeline>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GitLabPipeline {
+    pub id: u64,
+    pub status: String,
+    pub web_url: String,
+    #[serde(rename = "ref", default)]
+    pub git_ref: String,
+}
+
+impl GitLabPipeline {
+    fn failed(&self) -> bool {
+        self.status == "failed"
+    }
+}
+
+/// A failed pipeline on one of your branches
+#[derive(Debug, Clone)]
+pub struct BranchPipeline {
+    pub project: String,
+    pub pipeline: GitLabPipeline,
 }
 
 #[derive(Debug, Clone)]
@@ -35,18 +67,36 @@ struct GitLabApiMR {
     title: String,
     web_url: String,
     iid: u64,
+    project_id: u64,
     labels: Vec<String>,
 }
 
+#[derive(Deserialize, Debug)]
+struct GitLabApiMRDetail {
+    head_pipeline: Option<GitLabPipeline>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GitLabApiUser {
+    username: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct GitLabApiProject {
+    id: u64,
+    path_with_namespace: String,
+}
+
 pub async fn fetch_gitlab_items(config: &GitLabConfig) -> Result<Option<String>> {
     // Early return if not enabled
     if !config.enabled {
+        tracing::debug!("GitLab disabled; pass --gitlab to include it");
         return Ok(None);
     }
 
-    let token = config.token.as_ref().ok_or_else(|| {
+    let auth = oauth::gitlab_auth(config).await?.ok_or_else(|| {
         JournalError::GitLabFailed(
-            "GITLAB_TOKEN not set. Set the environment variable and use --gitlab flag.".to_string(),
+            "Not signed in. Run `easy_journal auth gitlab` or set GITLAB_TOKEN.".to_string(),
         )
     })?;
 
@@ -57,31 +107,31 @@ pub async fn fetch_gitlab_items(config: &GitLabConfig) -> Result<Option<String>>
 
     // Fetch data concurrently using spawn_blocking for blocking operations
     let config_clone = config.clone();
-    let token_clone = token.clone();
+    let auth_clone = auth.clone();
     let client_clone1 = client.clone();
     let assigned_issues_task = tokio::task::spawn(async move {
-        fetch_assigned_issues(&client_clone1, &config_clone.host, &token_clone).await
+        fetch_assigned_issues(&client_clone1, &config_clone.host, &auth_clone).await
     });
 
     let config_clone = config.clone();
-    let token_clone = token.clone();
+    let auth_clone = auth.clone();
     let client_clone2 = client.clone();
     let created_issues_task = tokio::task::spawn(async move {
-        fetch_created_issues(&client_clone2, &config_clone.host, &token_clone).await
+        fetch_created_issues(&client_clone2, &config_clone.host, &auth_clone).await
     });
 
     let config_clone = config.clone();
-    let token_clone = token.clone();
+    let auth_clone = auth.clone();
     let client_clone3 = client.clone();
     let assigned_mrs_task = tokio::task::spawn(async move {
-        fetch_assigned_mrs(&client_clone3, &config_clone.host, &token_clone).await
+        fetch_assigned_mrs(&client_clone3, &config_clone.host, &auth_clone).await
     });
 
     let config_clone = config.clone();
-    let token_clone = token.clone();
+    let auth_clone = auth.clone();
     let client_clone4 = client.clone();
     let review_requests_task = tokio::task::spawn(async move {
-        fetch_review_requests(&client_clone4, &config_clone.host, &token_clone).await
+        fetch_review_requests(&client_clone4, &config_clone.host, &auth_clone).await
     });
 
     let (assigned_issues, created_issues, assigned_mrs, review_requests) = tokio::join!(
@@ -117,29 +167,177 @@ pub async fn fetch_gitlab_items(config: &GitLabConfig) -> Result<Option<String>>
         all_items.extend(items);
     }
 
-    if all_items.is_empty() {
+    let mut branch_pipelines = Vec::new();
+    if config.pipelines {
+        let (_, branches) = tokio::join!(
+            attach_pipelines(&client, &config.host, &auth, &mut all_items),
+            fetch_failed_branch_pipelines(&client, &config.host, &auth)
+        );
+        match branches {
+            Ok(branches) => branch_pipelines = branches,
+            Err(e) => tracing::warn!("Could not fetch GitLab pipelines: {}", e),
+        }
+    }
+
+    let mut sections = Vec::new();
+    if let Some(failing) = format_failing_pipelines(&all_items, &branch_pipelines) {
+        sections.push(failing);
+    }
+    if !all_items.is_empty() {
+        sections.push(format_gitlab_items(all_items));
+    }
+
+    if sections.is_empty() {
         Ok(None)
     } else {
-        Ok(Some(format_gitlab_items(all_items)))
+        Ok(Some(sections.join("\n\n")))
     }
 }
 
+/// Fill in the head pipeline of every MR; MRs whose lookup fails keep none
+async fn attach_pipelines(
+    client: &reqwest::Client,
+    host: &str,
+    auth: &GitLabAuth,
+    items: &mut [GitLabItem],
+) {
+    let lookups = items.iter().map(|item| async move {
+        let project_id = item.project_id?;
+        let url = format!(
+            "{}/api/v4/projects/{}/merge_requests/{}",
+            host.trim_end_matches('/'),
+            project_id,
+            item.iid
+        );
+        let response = auth.apply(client.get(&url)).send().await.ok()?;
+        let detail: GitLabApiMRDetail = check_response(response, "GitLab")
+            .await
+            .ok()?
+            .json()
+            .await
+            .ok()?;
+        detail.head_pipeline
+    });
+    let pipelines = futures::future::join_all(lookups).await;
+    for (item, pipeline) in items.iter_mut().zip(pipelines) {
+        item.pipeline = pipeline;
+    }
+}
+
+/// Branches you pushed to in the last day whose latest pipeline failed
+async fn fetch_failed_branch_pipelines(
+    client: &reqwest::Client,
+    host: &str,
+    auth: &GitLabAuth,
+) -> Result<Vec<BranchPipeline>> {
+    let api = format!("{}/api/v4", host.trim_end_matches('/'));
+    let since = (Utc::now() - Duration::hours(24))
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string();
+
+    let response = auth
+        .apply(client.get(format!("{}/user", api)))
+        .send()
+        .await
+        .map_err(|e| JournalError::GitLabFailed(format!("Failed to fetch user: {}", e)))?;
+    let user: GitLabApiUser = check_response(response, "GitLab")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::GitLabFailed(format!("Failed to parse user: {}", e)))?;
+
+    let response = auth
+        .apply(client.get(format!("{}/projects", api)))
+        .query(&[
+            ("membership", "true"),
+            ("simple", "true"),
+            ("order_by", "last_activity_at"),
+            ("last_activity_after", since.as_str()),
+            ("per_page", PIPELINE_PROJECTS),
+        ])
+        .send()
+        .await
+        .map_err(|e| JournalError::GitLabFailed(format!("Failed to fetch projects: {}", e)))?;
+    let projects: Vec<GitLabApiProject> = check_response(response, "GitLab")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::GitLabFailed(format!("Failed to parse projects: {}", e)))?;
+
+    let lookups = projects.iter().map(|project| {
+        let url = format!("{}/projects/{}/pipelines", api, project.id);
+        let (username, since) = (&user.username, &since);
+        async move {
+            let response = auth
+                .apply(client.get(&url))
+                .query(&[
+                    ("username", username.as_str()),
+                    ("updated_after", since.as_str()),
+                    ("per_page", "100"),
+                ])
+                .send()
+                .await
+                .ok()?;
+            check_response(response, "GitLab")
+                .await
+                .ok()?
+                .json::<Vec<GitLabPipeline>>()
+                .await
+                .ok()
+        }
+    });
+    let results = futures::future::join_all(lookups).await;
+
+    let mut failing = Vec::new();
+    for (project, pipelines) in projects.iter().zip(results) {
+        failing.extend(
+            latest_failed(pipelines.unwrap_or_default())
+                .into_iter()
+                .map(|pipeline| BranchPipeline {
+                    project: project.path_with_namespace.clone(),
+                    pipeline,
+                }),
+        );
+    }
+    Ok(failing)
+}
+
+/// The latest pipeline of each branch, kept only if it failed. MR pipelines
+/// (`refs/merge-requests/...`) are left to the MR items.
+fn latest_failed(pipelines: Vec<GitLabPipeline>) -> Vec<GitLabPipeline> {
+    let mut latest: HashMap<String, GitLabPipeline> = HashMap::new();
+    for pipeline in pipelines
+        .into_iter()
+        .filter(|p| !p.git_ref.starts_with("refs/merge-requests/"))
+    {
+        if latest
+            .get(&pipeline.git_ref)
+            .is_none_or(|seen| seen.id < pipeline.id)
+        {
+            latest.insert(pipeline.git_ref.clone(), pipeline);
+        }
+    }
+    let mut failed: Vec<GitLabPipeline> = latest.into_values().filter(|p| p.failed()).collect();
+    failed.sort_by_key(|p| std::cmp::Reverse(p.id));
+    failed
+}
+
 async fn fetch_assigned_issues(
     client: &reqwest::Client,
     host: &str,
-    token: &str,
+    auth: &GitLabAuth,
 ) -> Result<Vec<GitLabItem>> {
     let url = format!("{}/api/v4/issues", host.trim_end_matches('/'));
 
-    let response = client
-        .get(&url)
-        .header("PRIVATE-TOKEN", token)
+    let response = auth
+        .apply(client.get(&url))
         .query(&[("scope", "assigned_to_me"), ("state", "opened")])
         .send()
         .await
         .map_err(|e| {
             JournalError::GitLabFailed(format!("Failed to fetch assigned issues: {}", e))
         })?;
+    let response = check_response(response, "GitLab").await?;
 
     let issues: Vec<GitLabApiIssue> = response.json().await.map_err(|e| {
         JournalError::GitLabFailed(format!("Failed to parse assigned issues: {}", e))
@@ -157,6 +355,8 @@ async fn fetch_assigned_issues(
                 labels: issue.labels,
                 due_date: issue.due_date,
                 item_type: GitLabItemType::AssignedIssue,
+                project_id: None,
+                pipeline: None,
             }
         })
         .collect();
@@ -167,19 +367,19 @@ async fn fetch_assigned_issues(
 async fn fetch_created_issues(
     client: &reqwest::Client,
     host: &str,
-    token: &str,
+    auth: &GitLabAuth,
 ) -> Result<Vec<GitLabItem>> {
     let url = format!("{}/api/v4/issues", host.trim_end_matches('/'));
 
-    let response = client
-        .get(&url)
-        .header("PRIVATE-TOKEN", token)
+    let response = auth
+        .apply(client.get(&url))
         .query(&[("scope", "created_by_me"), ("state", "opened")])
         .send()
         .await
         .map_err(|e| {
             JournalError::GitLabFailed(format!("Failed to fetch created issues: {}", e))
         })?;
+    let response = check_response(response, "GitLab").await?;
 
     let issues: Vec<GitLabApiIssue> = response.json().await.map_err(|e| {
         JournalError::GitLabFailed(format!("Failed to parse created issues: {}", e))
@@ -197,6 +397,8 @@ async fn fetch_created_issues(
                 labels: issue.labels,
                 due_date: issue.due_date,
                 item_type: GitLabItemType::CreatedIssue,
+                project_id: None,
+                pipeline: None,
             }
         })
         .collect();
@@ -207,17 +409,17 @@ async fn fetch_created_issues(
 async fn fetch_assigned_mrs(
     client: &reqwest::Client,
     host: &str,
-    token: &str,
+    auth: &GitLabAuth,
 ) -> Result<Vec<GitLabItem>> {
     let url = format!("{}/api/v4/merge_requests", host.trim_end_matches('/'));
 
-    let response = client
-        .get(&url)
-        .header("PRIVATE-TOKEN", token)
+    let response = auth
+        .apply(client.get(&url))
         .query(&[("scope", "assigned_to_me"), ("state", "opened")])
         .send()
         .await
         .map_err(|e| JournalError::GitLabFailed(format!("Failed to fetch assigned MRs: {}", e)))?;
+    let response = check_response(response, "GitLab").await?;
 
     let mrs: Vec<GitLabApiMR> = response
         .json()
@@ -236,6 +438,8 @@ async fn fetch_assigned_mrs(
                 labels: mr.labels,
                 due_date: None,
                 item_type: GitLabItemType::AssignedMR,
+                project_id: Some(mr.project_id),
+                pipeline: None,
             }
         })
         .collect();
@@ -246,19 +450,19 @@ async fn fetch_assigned_mrs(
 async fn fetch_review_requests(
     client: &reqwest::Client,
     host: &str,
-    token: &str,
+    auth: &GitLabAuth,
 ) -> Result<Vec<GitLabItem>> {
     let url = format!("{}/api/v4/merge_requests", host.trim_end_matches('/'));
 
-    let response = client
-        .get(&url)
-        .header("PRIVATE-TOKEN", token)
+    let response = auth
+        .apply(client.get(&url))
         .query(&[("scope", "reviews_for_me"), ("state", "opened")])
         .send()
         .await
         .map_err(|e| {
             JournalError::GitLabFailed(format!("Failed to fetch review requests: {}", e))
         })?;
+    let response = check_response(response, "GitLab").await?;
 
     let mrs: Vec<GitLabApiMR> = response.json().await.map_err(|e| {
         JournalError::GitLabFailed(format!("Failed to parse review requests: {}", e))
@@ -276,6 +480,8 @@ async fn fetch_review_requests(
                 labels: mr.labels,
                 due_date: None,
                 item_type: GitLabItemType::ReviewRequest,
+                project_id: Some(mr.project_id),
+                pipeline: None,
             }
         })
         .collect();
@@ -301,6 +507,38 @@ fn extract_project_from_url(url: &str) -> String {
     "unknown".to_string()
 }
 
+/// Red MR and branch pipelines, listed before everything else
+fn format_failing_pipelines(items: &[GitLabItem], branches: &[BranchPipeline]) -> Option<String> {
+    let mut output = String::new();
+    let mut seen = Vec::new();
+    for item in items {
+        if let Some(pipeline) = item.pipeline.as_ref().filter(|p| p.failed())
+            && !seen.contains(&pipeline.id)
+        {
+            seen.push(pipeline.id);
+            output.push_str(&format!(
+                "- [ ] ❌ [{}] {} (!{})\n      {}\n",
+                item.project, item.title, item.iid, pipeline.web_url
+            ));
+        }
+    }
+    for branch in branches {
+        if !seen.contains(&branch.pipeline.id) {
+            seen.push(branch.pipeline.id);
+            output.push_str(&format!(
+                "- [ ] ❌ [{}] `{}`\n      {}\n",
+                branch.project, branch.pipeline.git_ref, branch.pipeline.web_url
+            ));
+        }
+    }
+
+    if output.is_empty() {
+        None
+    } else {
+        Some(format!("#### Failing Pipelines\n{}", output))
+    }
+}
+
 fn format_gitlab_items(items: Vec<GitLabItem>) -> String {
     // Group by type
     let mut assigned_issues = Vec::new();
@@ -352,10 +590,20 @@ fn format_section(title: &str, items: Vec<GitLabItem>) -> String {
             .map(|d| format!(" - Due: {}", d))
             .unwrap_or_default();
 
+        let pipeline = match item.pipeline.as_ref().map(|p| p.status.as_str()) {
+            Some("failed") => " ❌ pipeline failed",
+            Some("success") => " ✅",
+            Some("running" | "pending" | "created" | "preparing" | "waiting_for_resource") => {
+                " 🔄 pipeline running"
+            }
+            Some("manual") => " ▶️ pipeline waiting for a manual job",
+            _ => "",
+        };
+
         // Main line
         output.push_str(&format!(
-            "- [ ] [{}] {} (!{}){}{}\n",
-            item.project, item.title, item.iid, labels, due
+            "- [ ] [{}] {} (!{}){}{}{}\n",
+            item.project, item.title, item.iid, labels, due, pipeline
         ));
 
         // URL on second line (indented)
@@ -389,6 +637,8 @@ mod tests {
                 labels: vec!["bug".to_string(), "urgent".to_string()],
                 due_date: Some("2026-01-15".to_string()),
                 item_type: GitLabItemType::AssignedIssue,
+                project_id: None,
+                pipeline: None,
             },
             GitLabItem {
                 title: "Add feature".to_string(),
@@ -398,6 +648,8 @@ mod tests {
                 labels: vec![],
                 due_date: None,
                 item_type: GitLabItemType::ReviewRequest,
+                project_id: Some(7),
+                pipeline: None,
             },
         ];
 
@@ -420,6 +672,8 @@ mod tests {
             labels: vec!["test".to_string()],
             due_date: None,
             item_type: GitLabItemType::AssignedIssue,
+            project_id: None,
+            pipeline: None,
         }];
 
         let output = format_section("Test Section", items);
@@ -427,4 +681,52 @@ mod tests {
         assert!(output.contains("- [ ] [group/project] Test issue (!1) [test]"));
         assert!(output.contains("      https://gitlab.com/group/project/-/issues/1"));
     }
+
+    #[test]
+    fn test_failing_pipelines() {
+        let pipelines: Vec<GitLabPipeline> = serde_json::from_str(
+            r#"[
+                {"id": 12, "status": "success", "ref": "fix-login", "web_url": "https://gitlab.com/group/project/-/pipelines/12"},
+                {"id": 11, "status": "failed", "ref": "fix-login", "web_url": "https://gitlab.com/group/project/-/pipelines/11"},
+                {"id": 10, "status": "failed", "ref": "main", "web_url": "https://gitlab.com/group/project/-/pipelines/10"},
+                {"id": 9, "status": "failed", "ref": "refs/merge-requests/456/head", "web_url": "https://gitlab.com/group/project/-/pipelines/9"}
+            ]"#,
+        )
+        .unwrap();
+        let branches: Vec<BranchPipeline> = latest_failed(pipelines)
+            .into_iter()
+            .map(|pipeline| BranchPipeline {
+                project: "group/project".to_string(),
+                pipeline,
+            })
+            .collect();
+
+        let mr = GitLabItem {
+            title: "Add feature".to_string(),
+            url: "https://gitlab.com/group/project/-/merge_requests/456".to_string(),
+            iid: 456,
+            project: "group/project".to_string(),
+            labels: vec![],
+            due_date: None,
+            item_type: GitLabItemType::AssignedMR,
+            project_id: Some(7),
+            pipeline: Some(GitLabPipeline {
+                id: 9,
+                status: "failed".to_string(),
+                web_url: "https://gitlab.com/group/project/-/pipelines/9".to_string(),
+                git_ref: "refs/merge-requests/456/head".to_string(),
+            }),
+        };
+
+        assert_eq!(
+            format_failing_pipelines(std::slice::from_ref(&mr), &branches).unwrap(),
+            "#### Failing Pipelines\n\
+             - [ ] ❌ [group/project] Add feature (!456)\n      https://gitlab.com/group/project/-/pipelines/9\n\
+             - [ ] ❌ [group/project] `main`\n      https://gitlab.com/group/project/-/pipelines/10\n"
+        );
+        assert!(
+            format_section("Assigned MRs", vec![mr])
+                .contains("Add feature (!456) ❌ pipeline failed")
+        );
+    }
 }
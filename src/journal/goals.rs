@@ -0,0 +1,115 @@
+use serde::Serialize;
+
+use crate::journal::parser;
+
+/// How far a section of an entry is toward its writing goal
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SectionGoal {
+    pub section: String,
+    pub needed: usize,
+    pub written: usize,
+    /// 1-based line of the section's heading; `None` if the entry doesn't have it
+    pub line: Option<usize>,
+}
+
+impl SectionGoal {
+    pub fn is_met(&self) -> bool {
+        self.written >= self.needed
+    }
+}
+
+/// Parse `Learning & Insights=1,Gratitude=3` (SECTION_GOALS) into (section, items needed)
+pub fn parse(spec: &str) -> Vec<(String, usize)> {
+    spec.split(',')
+        .filter_map(|goal| {
+            let (section, needed) = goal.rsplit_once('=')?;
+            let section = section.trim().trim_start_matches('#').trim();
+            let needed = needed.trim().trim_start_matches(">=").trim().parse().ok()?;
+            (!section.is_empty()).then(|| (section.to_string(), needed))
+        })
+        .collect()
+}
+
+/// A line that counts toward a goal: a bullet, task or line of prose with
+/// something written in it (empty template bullets and comments don't count)
+fn is_item(line: &str) -> bool {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with("<!--") || trimmed == "---" {
+        return false;
+    }
+    let text = match parser::parse_task(trimmed) {
+        Some((text, _)) => text,
+        None => trimmed
+            .trim_start_matches(['-', '*', '+'])
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .trim_start_matches(['.', ')'])
+            .trim()
+            .to_string(),
+    };
+    !text.is_empty()
+}
+
+/// Progress of `content` toward each goal, in the order the goals are given
+pub fn progress(content: &str, goals: &[(String, usize)]) -> Vec<SectionGoal> {
+    let lines: Vec<&str> = content.lines().collect();
+    goals
+        .iter()
+        .map(|(section, needed)| {
+            let heading = lines.iter().position(|line| {
+                line.trim_start().starts_with('#')
+                    && line
+                        .trim()
+                        .trim_start_matches('#')
+                        .trim()
+                        .eq_ignore_ascii_case(section)
+            });
+            let written = heading.map_or(0, |heading| {
+                lines[heading + 1..]
+                    .iter()
+                    .take_while(|line| !line.trim_start().starts_with('#') && line.trim() != "---")
+                    .filter(|line| is_item(line))
+                    .count()
+            });
+            SectionGoal {
+                section: section.clone(),
+                needed: *needed,
+                written,
+                line: heading.map(|heading| heading + 1),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let goals = parse("Learning & Insights=1, Gratitude=3,Broken, ## Notes = 2");
+        assert_eq!(
+            goals,
+            vec![
+                ("Learning & Insights".to_string(), 1),
+                ("Gratitude".to_string(), 3),
+                ("Notes".to_string(), 2),
+            ]
+        );
+
+        let content = "# 2025-12-29\n\n## Learning & Insights\n- Borrow checker tricks\n- \n\n## Gratitude\n1. Coffee\n2.\n- [ ] \n<!-- three things -->\n";
+        let progress = progress(content, &goals);
+        assert_eq!(
+            progress[0],
+            SectionGoal {
+                section: "Learning & Insights".to_string(),
+                needed: 1,
+                written: 1,
+                line: Some(3),
+            }
+        );
+        assert!(progress[0].is_met());
+        assert_eq!((progress[1].written, progress[1].line), (1, Some(7)));
+        assert!(!progress[1].is_met());
+        assert_eq!((progress[2].written, progress[2].line), (0, None));
+    }
+}
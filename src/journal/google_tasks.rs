@@ -1,13 +1,17 @@
 use google_tasks1::TasksHub;
 use google_tasks1::hyper;
 use google_tasks1::hyper_rustls;
+use std::io::IsTerminal;
 
 use crate::config::GoogleOAuthConfig;
 use crate::error::{JournalError, Result};
 use crate::journal::oauth;
 
-/// Fetch all incomplete Google Tasks and format as markdown checkboxes
-pub async fn fetch_google_tasks(oauth_config: &GoogleOAuthConfig) -> Result<Option<String>> {
+type Hub = TasksHub<hyper_rustls::HttpsConnector<hyper::client::HttpConnector>>;
+
+/// Tasks API client for the stored sign-in. An expired or revoked sign-in is
+/// renewed in the browser when `reauth` is set and someone is at a terminal.
+async fn connect(oauth_config: &GoogleOAuthConfig, reauth: bool) -> Result<Hub> {
     // Check if OAuth is configured
     let client_id = oauth_config.client_id.as_ref().ok_or_else(|| {
         JournalError::OAuthConfigMissing(
@@ -28,11 +32,38 @@ pub async fn fetch_google_tasks(oauth_config: &GoogleOAuthConfig) -> Result<Opti
         ));
     }
 
+    // Catch an expired or revoked refresh token before yup-oauth2 would fall
+    // back to the browser sign-in; re-run it inline only with a terminal attached
+    match oauth::refresh_google_token(
+        client_id,
+        client_secret,
+        &oauth_config.token_storage_path,
+        oauth_config.token_passphrase.as_deref(),
+    )
+    .await
+    {
+        Err(JournalError::ReauthRequired(reason)) if reauth && interactive() => {
+            tracing::warn!(
+                "Google sign-in expired: {}; starting the sign-in flow",
+                reason
+            );
+            oauth::authenticate_google(
+                client_id.clone(),
+                client_secret.clone(),
+                &oauth_config.token_storage_path,
+                oauth_config.token_passphrase.as_deref(),
+            )
+            .await?;
+        }
+        result => result?,
+    }
+
     // Load authenticator from stored tokens
     let auth = oauth::load_authenticator(
         client_id.clone(),
         client_secret.clone(),
         &oauth_config.token_storage_path,
+        oauth_config.token_passphrase.as_deref(),
     )
     .await?;
 
@@ -47,8 +78,28 @@ pub async fn fetch_google_tasks(oauth_config: &GoogleOAuthConfig) -> Result<Opti
         .build();
     let client = hyper::Client::builder().build(https);
 
-    // Create Tasks API hub
-    let hub = TasksHub::new(client, auth);
+    Ok(TasksHub::new(client, auth))
+}
+
+/// Make sure the stored sign-in works by listing one task list, without
+/// falling back to the browser sign-in
+pub async fn check_sign_in(oauth_config: &GoogleOAuthConfig) -> Result<()> {
+    connect(oauth_config, false)
+        .await?
+        .tasklists()
+        .list()
+        .max_results(1)
+        .doit()
+        .await
+        .map_err(|e| {
+            JournalError::GoogleTasksFailed(format!("Failed to fetch task lists: {}", e))
+        })?;
+    Ok(())
+}
+
+/// Fetch all incomplete Google Tasks and format as markdown checkboxes
+pub async fn fetch_google_tasks(oauth_config: &GoogleOAuthConfig) -> Result<Option<String>> {
+    let hub = connect(oauth_config, oauth_config.reauth_inline).await?;
 
     // Fetch all task lists
     let task_lists = hub
@@ -96,6 +147,11 @@ pub async fn fetch_google_tasks(oauth_config: &GoogleOAuthConfig) -> Result<Opti
     }
 }
 
+/// Whether someone is at a terminal to complete a browser sign-in
+fn interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
 /// Format tasks as markdown checkboxes
 fn format_tasks(tasks: Vec<String>) -> String {
     tasks
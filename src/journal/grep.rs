@@ -0,0 +1,135 @@
+use chrono::NaiveDate;
+use regex::Regex;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::{self, Layout};
+use crate::journal::search;
+
+/// Every match on a line: the matched text followed by its capture groups,
+/// `None` for a group that didn't take part
+pub type Captures = Vec<Vec<Option<String>>>;
+
+/// A line of an entry the pattern matched
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GrepMatch {
+    pub date: NaiveDate,
+    pub path: PathBuf,
+    /// 1-based
+    pub line: usize,
+    pub text: String,
+    pub captures: Captures,
+}
+
+impl GrepMatch {
+    /// What `--only-matching` prints for each match: its capture groups
+    /// separated by tabs, or the matched text when the pattern has none
+    pub fn extracted(&self) -> Vec<String> {
+        self.captures
+            .iter()
+            .map(|groups| match groups.split_first() {
+                Some((whole, [])) => whole.clone().unwrap_or_default(),
+                Some((_, groups)) => groups
+                    .iter()
+                    .map(|group| group.as_deref().unwrap_or_default())
+                    .collect::<Vec<_>>()
+                    .join("\t"),
+                None => String::new(),
+            })
+            .collect()
+    }
+}
+
+/// Compile `pattern`, optionally ignoring case
+pub fn pattern(pattern: &str, ignore_case: bool) -> Result<Regex> {
+    regex::RegexBuilder::new(pattern)
+        .case_insensitive(ignore_case)
+        .build()
+        .map_err(|e| JournalError::InvalidArgument(format!("Invalid pattern: {}", e)))
+}
+
+/// 1-based numbers, text and captures of the lines of `content` matching
+/// `regex`, only under `section` if given
+pub fn find(content: &str, regex: &Regex, section: Option<&str>) -> Vec<(usize, String, Captures)> {
+    search::section_lines(content, section)
+        .into_iter()
+        .filter_map(|(number, line)| {
+            let captures: Captures = regex
+                .captures_iter(line)
+                .map(|caps| {
+                    caps.iter()
+                        .map(|group| group.map(|m| m.as_str().to_string()))
+                        .collect()
+                })
+                .collect();
+            (!captures.is_empty()).then(|| (number, line.trim().to_string(), captures))
+        })
+        .collect()
+}
+
+/// Every matching line of the entries from `since` to `until` (either open),
+/// oldest entry first
+pub fn grep(
+    journal_dir: &Path,
+    layout: Layout,
+    regex: &Regex,
+    section: Option<&str>,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+) -> Result<Vec<GrepMatch>> {
+    let mut matches = Vec::new();
+    for (date, path) in filesystem::list_entries(journal_dir, layout)? {
+        if since.is_some_and(|since| date < since) || until.is_some_and(|until| date > until) {
+            continue;
+        }
+        let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+        for (line, text, captures) in find(&content, regex, section) {
+            matches.push(GrepMatch {
+                date,
+                path: path.clone(),
+                line,
+                text,
+                captures,
+            });
+        }
+    }
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_and_extract() {
+        let content = "# Monday\n\n## Work Accomplished\n- Standup: 15 min, retro: 45 min\n- Wrote docs\n\n## Notes\n- Skipped standup\n\n---\n\n**Mood**: 7/10\n";
+        let regex = pattern(r"(standup|retro): (\d+) min", true).unwrap();
+        let found = find(content, &regex, Some("work accomplished"));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, 4);
+
+        let found = GrepMatch {
+            date: NaiveDate::from_ymd_opt(2025, 12, 22).unwrap(),
+            path: PathBuf::from("2025/12/22.md"),
+            line: found[0].0,
+            text: found[0].1.clone(),
+            captures: found[0].2.clone(),
+        };
+        assert_eq!(found.extracted(), vec!["Standup\t15", "retro\t45"]);
+
+        let regex = pattern(r"\*\*Mood\*\*: \d+", false).unwrap();
+        let found = find(content, &regex, None);
+        assert_eq!(
+            found,
+            vec![(
+                12,
+                "**Mood**: 7/10".to_string(),
+                vec![vec![Some("**Mood**: 7".to_string())]]
+            )]
+        );
+        assert!(find(content, &pattern("STANDUP", false).unwrap(), None).is_empty());
+        assert!(pattern("(unclosed", false).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+use chrono::{Days, NaiveDate, Weekday};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::journal::parser;
+
+/// Days a task has to appear on in the window to count as a habit
+pub const MIN_DAYS: usize = 3;
+
+/// Days it has to be checked off on, so a task carried over until it gets
+/// done once isn't taken for a habit
+pub const MIN_DONE: usize = 2;
+
+/// Completion of a habit in one ISO week
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeekRate {
+    /// `2025-W52`
+    pub week: String,
+    pub done: usize,
+    /// Days of the week so far (7 for past weeks)
+    pub days: usize,
+}
+
+impl WeekRate {
+    pub fn percent(&self) -> f32 {
+        if self.days == 0 {
+            0.0
+        } else {
+            self.done as f32 * 100.0 / self.days as f32
+        }
+    }
+}
+
+/// A checkbox that keeps coming back, such as `- [ ] Run 3 miles`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Habit {
+    /// Task text as last written
+    pub name: String,
+    /// Days it was checked off
+    pub done: Vec<NaiveDate>,
+    /// Days it was left unchecked
+    pub missed: Vec<NaiveDate>,
+    /// Consecutive days checked off ending today, or yesterday if today isn't yet
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    pub weeks: Vec<WeekRate>,
+}
+
+fn longest_run(done: &[NaiveDate]) -> usize {
+    let mut longest = 0;
+    let mut run = 0;
+    for (i, date) in done.iter().enumerate() {
+        run = match i.checked_sub(1).map(|prev| done[prev]) {
+            Some(prev) if date.pred_opt() == Some(prev) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+    }
+    longest
+}
+
+fn current_run(done: &[NaiveDate], today: NaiveDate) -> usize {
+    let mut day = if done.contains(&today) {
+        today
+    } else {
+        today.pred_opt().unwrap_or(today)
+    };
+    let mut run = 0;
+    while done.contains(&day) {
+        run += 1;
+        day = day.pred_opt().unwrap_or(day);
+    }
+    run
+}
+
+/// Tasks in `entries` (oldest first) that appear on at least `MIN_DAYS` days
+/// from `since` to `today` and were checked off on `MIN_DONE` of them, most
+/// tracked first. Tasks are matched ignoring case; a day counts as done when
+/// any of its copies is checked. Streaks use every entry, the weekly rates the
+/// weeks from `since`.
+pub fn detect(entries: &[(NaiveDate, String)], since: NaiveDate, today: NaiveDate) -> Vec<Habit> {
+    // Lower-cased text → (latest spelling, done per day)
+    let mut tasks: BTreeMap<String, (String, BTreeMap<NaiveDate, bool>)> = BTreeMap::new();
+    for (date, content) in entries {
+        for (text, checked) in parser::extract_tasks(content) {
+            let (name, days) = tasks.entry(text.to_lowercase()).or_default();
+            *name = text;
+            *days.entry(*date).or_default() |= checked;
+        }
+    }
+
+    let mut habits: Vec<Habit> = tasks
+        .into_values()
+        .filter(|(_, days)| {
+            let window = || days.range(since..=today);
+            window().count() >= MIN_DAYS && window().filter(|(_, done)| **done).count() >= MIN_DONE
+        })
+        .map(|(name, days)| {
+            let done: Vec<NaiveDate> = days
+                .iter()
+                .filter(|(_, d)| **d)
+                .map(|(date, _)| *date)
+                .collect();
+            let missed = days
+                .iter()
+                .filter(|(_, d)| !**d)
+                .map(|(date, _)| *date)
+                .collect();
+            Habit {
+                current_streak: current_run(&done, today),
+                longest_streak: longest_run(&done),
+                weeks: week_rates(&done, since, today),
+                name,
+                done,
+                missed,
+            }
+        })
+        .collect();
+    habits.sort_by_key(|habit| {
+        let tracked = |dates: &[NaiveDate]| dates.iter().filter(|d| **d >= since).count();
+        std::cmp::Reverse(tracked(&habit.done) + tracked(&habit.missed))
+    });
+    habits
+}
+
+fn week_rates(done: &[NaiveDate], since: NaiveDate, today: NaiveDate) -> Vec<WeekRate> {
+    let mut weeks = Vec::new();
+    let mut monday = since.week(Weekday::Mon).first_day();
+    while monday <= today {
+        let sunday = monday + Days::new(6);
+        weeks.push(WeekRate {
+            week: monday.format("%G-W%V").to_string(),
+            done: done
+                .iter()
+                .filter(|d| (monday..=sunday.min(today)).contains(*d))
+                .count(),
+            days: ((sunday.min(today) - monday).num_days() + 1) as usize,
+        });
+        monday = monday + Days::new(7);
+    }
+    weeks
+}
+
+/// One row per week from `since` to `today`: █ checked off, ░ left unchecked,
+/// · not written down, followed by the week's completion
+pub fn heatmap(habit: &Habit, since: NaiveDate, today: NaiveDate) -> String {
+    let mut out = String::from("          M T W T F S S\n");
+    let mut monday = since.week(Weekday::Mon).first_day();
+    for rate in week_rates(&habit.done, since, today) {
+        let cells: Vec<&str> = (0..7)
+            .map(|offset| {
+                let day = monday + Days::new(offset);
+                if day > today {
+                    " "
+                } else if habit.done.contains(&day) {
+                    "█"
+                } else if habit.missed.contains(&day) {
+                    "░"
+                } else {
+                    "·"
+                }
+            })
+            .collect();
+        out.push_str(&format!(
+            "{}  {}  {}/{} {:>3.0}%\n",
+            rate.week,
+            cells.join(" "),
+            rate.done,
+            rate.days,
+            rate.percent()
+        ));
+        monday = monday + Days::new(7);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_heatmap() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 12, d).unwrap();
+        let entry = |run: bool, read: bool| {
+            format!(
+                "# Day\n\n## Goals for Today\n- [{}] Run 3 miles\n- [{}] read 20 pages\n- [ ] Call bank\n",
+                if run { "x" } else { " " },
+                if read { "x" } else { " " }
+            )
+        };
+        let entries = vec![
+            (date(19), entry(true, false)),
+            (date(20), entry(true, false)),
+            (date(22), entry(false, true)),
+            (date(23), entry(true, true)),
+            (
+                date(24),
+                entry(true, false).replace("read 20 pages", "Read 20 pages"),
+            ),
+        ];
+        let habits = detect(&entries[..2], date(15), date(20));
+        assert!(habits.is_empty());
+
+        let habits = detect(&entries, date(15), date(24));
+        let names: Vec<&str> = habits.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, vec!["Read 20 pages", "Run 3 miles"]);
+
+        let run = &habits[1];
+        assert_eq!((run.current_streak, run.longest_streak), (2, 2));
+        assert_eq!(
+            run.weeks,
+            vec![
+                WeekRate {
+                    week: "2025-W51".to_string(),
+                    done: 2,
+                    days: 7
+                },
+                WeekRate {
+                    week: "2025-W52".to_string(),
+                    done: 2,
+                    days: 3
+                },
+            ]
+        );
+        assert_eq!(
+            heatmap(run, date(15), date(24)),
+            "          M T W T F S S\n2025-W51  · · · · █ █ ·  2/7  29%\n2025-W52  ░ █ █          2/3  67%\n"
+        );
+    }
+}
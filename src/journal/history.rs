@@ -0,0 +1,168 @@
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S%.3f";
+
+/// A saved copy of an entry taken before it was overwritten
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    pub version: usize,
+    pub taken_at: NaiveDateTime,
+    pub path: PathBuf,
+}
+
+fn entry_history_dir(history_dir: &Path, date: NaiveDate) -> PathBuf {
+    history_dir.join(date.format("%Y-%m-%d").to_string())
+}
+
+/// Copy the current content of an entry into the history store.
+///
+/// Returns `None` when there is nothing to snapshot (missing or empty entry,
+/// or content identical to the newest snapshot).
+pub fn snapshot(history_dir: &Path, date: NaiveDate, entry_path: &Path) -> Result<Option<PathBuf>> {
+    if !entry_path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(entry_path)?;
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
+
+    if let Some(latest) = list_versions(history_dir, date)?.last()
+        && fs::read_to_string(&latest.path)? == content
+    {
+        return Ok(None);
+    }
+
+    let dir = entry_history_dir(history_dir, date);
+    fs::create_dir_all(&dir)?;
+
+    let mut snapshot_path = dir.join(format!("{}.md", Local::now().format(TIMESTAMP_FORMAT)));
+    while snapshot_path.exists() {
+        // Two snapshots within the same millisecond; wait for a fresh timestamp
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        snapshot_path = dir.join(format!("{}.md", Local::now().format(TIMESTAMP_FORMAT)));
+    }
+    fs::write(&snapshot_path, content)?;
+    Ok(Some(snapshot_path))
+}
+
+/// Snapshot an entry before it gets overwritten with `new_content`
+pub fn snapshot_before_write(
+    config: &Config,
+    date: NaiveDate,
+    entry_path: &Path,
+    new_content: &str,
+) -> Result<()> {
+    if entry_path.exists() && fs::read_to_string(entry_path)? != new_content {
+        snapshot(&config.history_dir, date, entry_path)?;
+    }
+    Ok(())
+}
+
+/// List all snapshots for an entry, oldest first (version 1 is the oldest)
+pub fn list_versions(history_dir: &Path, date: NaiveDate) -> Result<Vec<Snapshot>> {
+    let dir = entry_history_dir(history_dir, date);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots: Vec<(NaiveDateTime, PathBuf)> = Vec::new();
+    for file in fs::read_dir(&dir)? {
+        let path = file?.path();
+        let taken_at = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| NaiveDateTime::parse_from_str(s, TIMESTAMP_FORMAT).ok());
+
+        if let Some(taken_at) = taken_at {
+            snapshots.push((taken_at, path));
+        }
+    }
+
+    snapshots.sort();
+    Ok(snapshots
+        .into_iter()
+        .enumerate()
+        .map(|(i, (taken_at, path))| Snapshot {
+            version: i + 1,
+            taken_at,
+            path,
+        })
+        .collect())
+}
+
+/// Load the content of a specific snapshot version
+pub fn load_version(history_dir: &Path, date: NaiveDate, version: usize) -> Result<String> {
+    let snapshot = list_versions(history_dir, date)?
+        .into_iter()
+        .find(|s| s.version == version)
+        .ok_or_else(|| {
+            JournalError::HistoryFailed(format!(
+                "No version {} in history for {}",
+                version,
+                date.format("%Y-%m-%d")
+            ))
+        })?;
+
+    Ok(fs::read_to_string(snapshot.path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "easy_journal_history_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_snapshot_and_list_versions() {
+        let dir = temp_dir("list");
+        let history_dir = dir.join("history");
+        let entry_path = dir.join("29.md");
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+
+        fs::write(&entry_path, "first").unwrap();
+        assert!(snapshot(&history_dir, date, &entry_path).unwrap().is_some());
+
+        // Identical content is not snapshotted twice
+        assert!(snapshot(&history_dir, date, &entry_path).unwrap().is_none());
+
+        fs::write(&entry_path, "second").unwrap();
+        assert!(snapshot(&history_dir, date, &entry_path).unwrap().is_some());
+
+        let versions = list_versions(&history_dir, date).unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].version, 1);
+        assert_eq!(load_version(&history_dir, date, 1).unwrap(), "first");
+        assert_eq!(load_version(&history_dir, date, 2).unwrap(), "second");
+        assert!(load_version(&history_dir, date, 3).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_missing_entry() {
+        let dir = temp_dir("missing");
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+
+        let result = snapshot(&dir.join("history"), date, &dir.join("missing.md")).unwrap();
+        assert!(result.is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
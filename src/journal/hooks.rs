@@ -0,0 +1,120 @@
+use chrono::NaiveDate;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::{self, Config};
+use crate::error::{JournalError, Result};
+
+/// Entry lifecycle events that can trigger a hook
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HookEvent {
+    PreNew,
+    PostNew,
+    PostSave,
+}
+
+impl HookEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookEvent::PreNew => "pre_new",
+            HookEvent::PostNew => "post_new",
+            HookEvent::PostSave => "post_save",
+        }
+    }
+
+    fn command<'a>(&self, config: &'a Config) -> Option<&'a str> {
+        let command = match self {
+            HookEvent::PreNew => &config.hooks.pre_new,
+            HookEvent::PostNew => &config.hooks.post_new,
+            HookEvent::PostSave => &config.hooks.post_save,
+        };
+        command.as_deref().filter(|c| !c.trim().is_empty())
+    }
+}
+
+/// Run the hook configured for `event`, if any.
+///
+/// The command runs through `sh -c` in the current directory with
+/// `JOURNAL_EVENT`, `JOURNAL_DATE`, `JOURNAL_ENTRY_PATH` and `JOURNAL_DIR` set.
+/// Returns an error when it can't be started or exits unsuccessfully.
+pub fn run(config: &Config, event: HookEvent, date: NaiveDate, entry_path: &Path) -> Result<()> {
+    let Some(command) = event.command(config) else {
+        return Ok(());
+    };
+    tracing::debug!(event = event.name(), command, "running hook");
+
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .envs(config::loaded())
+        .env("JOURNAL_EVENT", event.name())
+        .env("JOURNAL_DATE", date.format("%Y-%m-%d").to_string())
+        .env("JOURNAL_ENTRY_PATH", entry_path)
+        .env("JOURNAL_DIR", &config.journal_dir)
+        .status()
+        .map_err(|e| {
+            JournalError::HookFailed(format!("Failed to run {} hook: {}", event.name(), e))
+        })?;
+
+    if !status.success() {
+        return Err(JournalError::HookFailed(format!(
+            "{} hook '{}' exited with {}",
+            event.name(),
+            command,
+            status
+        )));
+    }
+    Ok(())
+}
+
+/// Run a post-event hook; failures only warn since the change already happened
+pub fn notify(config: &Config, event: HookEvent, date: NaiveDate, entry_path: &Path) {
+    if let Err(e) = run(config, event, date, entry_path) {
+        tracing::warn!("{}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::HooksConfig;
+    use std::fs;
+
+    #[test]
+    fn test_hook_environment_and_failure() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_hooks_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let out = dir.join("out.txt");
+
+        let config = Config {
+            hooks: HooksConfig {
+                pre_new: Some("exit 3".to_string()),
+                post_new: None,
+                post_save: Some(format!(
+                    "echo \"$JOURNAL_EVENT $JOURNAL_DATE $JOURNAL_ENTRY_PATH\" > {}",
+                    out.display()
+                )),
+            },
+            ..Config::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let entry = Path::new("journal/2025/12/29.md");
+
+        run(&config, HookEvent::PostSave, date, entry).unwrap();
+        assert_eq!(
+            fs::read_to_string(&out).unwrap(),
+            "post_save 2025-12-29 journal/2025/12/29.md\n"
+        );
+
+        assert!(matches!(
+            run(&config, HookEvent::PreNew, date, entry),
+            Err(JournalError::HookFailed(_))
+        ));
+
+        // Unconfigured hooks are a no-op
+        run(&config, HookEvent::PostNew, date, entry).unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
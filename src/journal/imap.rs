@@ -0,0 +1,416 @@
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+use chrono::{DateTime, FixedOffset};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use crate::config::ImapConfig;
+use crate::error::{JournalError, Result};
+
+/// Give up on a server that stops answering
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// An untagged `*` response, with any `{n}` literals pulled out of the line
+#[derive(Debug, Default)]
+struct Untagged {
+    text: String,
+    literals: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Message {
+    from: String,
+    subject: String,
+    date: Option<DateTime<FixedOffset>>,
+    /// Without the angle brackets
+    message_id: Option<String>,
+}
+
+fn failed(context: &str, e: impl std::fmt::Display) -> JournalError {
+    JournalError::ImapFailed(format!("{}: {}", context, e))
+}
+
+/// `"..."` with backslashes and quotes escaped
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Read responses up to the line tagged `tag`; errors unless it is `OK`
+async fn read_response<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    tag: &str,
+) -> Result<Vec<Untagged>> {
+    let mut responses: Vec<Untagged> = Vec::new();
+    // A line ending in a literal continues after the literal's bytes
+    let mut continued = false;
+    loop {
+        let mut line = Vec::new();
+        let read = reader
+            .read_until(b'\n', &mut line)
+            .await
+            .map_err(|e| failed("Read failed", e))?;
+        if read == 0 {
+            return Err(JournalError::ImapFailed(
+                "Server closed the connection".to_string(),
+            ));
+        }
+        let line = String::from_utf8_lossy(&line).trim_end().to_string();
+
+        if !continued {
+            if let Some(status) = line.strip_prefix(tag).and_then(|l| l.strip_prefix(' ')) {
+                return if status.starts_with("OK") {
+                    Ok(responses)
+                } else {
+                    Err(JournalError::ImapFailed(status.to_string()))
+                };
+            }
+            responses.push(Untagged::default());
+        }
+        let Some(current) = responses.last_mut() else {
+            continue;
+        };
+        current.text.push_str(&line);
+
+        let literal = line
+            .strip_suffix('}')
+            .and_then(|l| l.rsplit_once('{'))
+            .and_then(|(_, n)| n.parse::<usize>().ok());
+        continued = literal.is_some();
+        if let Some(len) = literal {
+            let mut bytes = vec![0; len];
+            reader
+                .read_exact(&mut bytes)
+                .await
+                .map_err(|e| failed("Read failed", e))?;
+            current.literals.push(bytes);
+        }
+    }
+}
+
+struct Session<S> {
+    stream: BufReader<S>,
+    next_tag: u32,
+}
+
+impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> Session<S> {
+    async fn command(&mut self, command: &str) -> Result<Vec<Untagged>> {
+        self.next_tag += 1;
+        let tag = format!("A{}", self.next_tag);
+        self.stream
+            .get_mut()
+            .write_all(format!("{} {}\r\n", tag, command).as_bytes())
+            .await
+            .map_err(|e| failed("Write failed", e))?;
+        read_response(&mut self.stream, &tag).await
+    }
+}
+
+/// Decode RFC 2047 encoded words (`=?UTF-8?B?...?=`) in a header value
+fn decode_header(value: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = value;
+    let mut after_word = false;
+    while let Some(start) = rest.find("=?") {
+        let word = &rest[start + 2..];
+        let parts: Vec<&str> = word.splitn(3, '?').collect();
+        let end = parts.get(2).and_then(|text| text.find("?="));
+        let (Some(end), [charset, encoding, text]) = (end, parts.as_slice()) else {
+            break;
+        };
+        let text = &text[..end];
+
+        // Whitespace between two encoded words is dropped
+        let between = &rest[..start];
+        if !(after_word && between.trim().is_empty()) {
+            decoded.push_str(between);
+        }
+
+        let bytes = match encoding.to_ascii_uppercase().as_str() {
+            "B" => STANDARD
+                .decode(text)
+                .or_else(|_| STANDARD_NO_PAD.decode(text.trim_end_matches('=')))
+                .unwrap_or_else(|_| text.as_bytes().to_vec()),
+            _ => decode_q(text),
+        };
+        if charset.eq_ignore_ascii_case("iso-8859-1") || charset.eq_ignore_ascii_case("latin1") {
+            decoded.extend(bytes.iter().map(|&b| b as char));
+        } else {
+            decoded.push_str(&String::from_utf8_lossy(&bytes));
+        }
+
+        let consumed = 2 + charset.len() + 1 + encoding.len() + 1 + end + 2;
+        rest = &rest[start + consumed..];
+        after_word = true;
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Q encoding: `_` is a space, `=XX` a hex byte
+fn decode_q(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'_' => decoded.push(b' '),
+            b'=' => match bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 2;
+                }
+                None => decoded.push(b'='),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    decoded
+}
+
+/// `Jane Doe` from `"Jane Doe" <jane@example.com>`, or the address alone
+fn sender_name(from: &str) -> String {
+    match from.split_once('<') {
+        Some((name, address)) => {
+            let name = name.trim().trim_matches('"').trim();
+            if name.is_empty() {
+                address.trim_end_matches('>').trim().to_string()
+            } else {
+                name.to_string()
+            }
+        }
+        None => from.trim().to_string(),
+    }
+}
+
+fn parse_message(headers: &[u8]) -> Message {
+    let headers = String::from_utf8_lossy(headers);
+    // Unfold continuation lines first
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for line in headers.lines() {
+        if line.starts_with([' ', '\t'])
+            && let Some((_, value)) = fields.last_mut()
+        {
+            value.push(' ');
+            value.push_str(line.trim());
+        } else if let Some((name, value)) = line.split_once(':') {
+            fields.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    Message {
+        from: sender_name(&decode_header(field("from").unwrap_or_default())),
+        subject: field("subject")
+            .map(decode_header)
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| "(no subject)".to_string()),
+        date: field("date").and_then(|d| DateTime::parse_from_rfc2822(d).ok()),
+        message_id: field("message-id")
+            .map(|id| {
+                id.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+            .filter(|id| !id.is_empty()),
+    }
+}
+
+/// One follow-up checkbox per message, newest first
+fn format_messages(
+    messages: &mut [Message],
+    total: usize,
+    mailbox: &str,
+    message_url: &str,
+) -> Option<String> {
+    if messages.is_empty() {
+        return None;
+    }
+    messages.sort_by_key(|m| std::cmp::Reverse(m.date));
+
+    let mut lines: Vec<String> = messages
+        .iter()
+        .map(|message| {
+            let subject = match &message.message_id {
+                Some(id) => format!("[{}]({})", message.subject, message_url.replace("{id}", id)),
+                None => message.subject.clone(),
+            };
+            let mut line = format!("- [ ] ✉️ {} — {}", subject, message.from);
+            if let Some(date) = message.date {
+                line.push_str(&format!(" ({})", date.format("%b %-d")));
+            }
+            line
+        })
+        .collect();
+    if total > messages.len() {
+        lines.push(format!(
+            "- 📬 {} more flagged in {}",
+            total - messages.len(),
+            mailbox
+        ));
+    }
+    Some(lines.join("\n"))
+}
+
+async fn fetch_messages<S>(
+    session: &mut Session<S>,
+    config: &ImapConfig,
+    username: &str,
+    password: &str,
+) -> Result<(Vec<Message>, usize)>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    session
+        .command(&format!("LOGIN {} {}", quote(username), quote(password)))
+        .await?;
+    session
+        .command(&format!("EXAMINE {}", quote(&config.mailbox)))
+        .await?;
+
+    let mut uids: Vec<u32> = session
+        .command("UID SEARCH FLAGGED UNDELETED")
+        .await?
+        .iter()
+        .filter_map(|response| response.text.strip_prefix("* SEARCH"))
+        .flat_map(|uids| uids.split_whitespace().filter_map(|uid| uid.parse().ok()))
+        .collect();
+    uids.sort_unstable();
+    let total = uids.len();
+
+    // Higher UIDs arrived later
+    let newest: Vec<String> = uids
+        .iter()
+        .rev()
+        .take(config.max_items)
+        .map(u32::to_string)
+        .collect();
+    let mut messages = Vec::new();
+    if !newest.is_empty() {
+        let responses = session
+            .command(&format!(
+                "UID FETCH {} (BODY.PEEK[HEADER.FIELDS (FROM SUBJECT DATE MESSAGE-ID)])",
+                newest.join(",")
+            ))
+            .await?;
+        messages = responses
+            .iter()
+            .filter(|response| response.text.contains(" FETCH "))
+            .filter_map(|response| response.literals.first())
+            .map(|headers| parse_message(headers))
+            .collect();
+    }
+
+    // The server may already have hung up; nothing left to lose
+    let _ = session.command("LOGOUT").await;
+    Ok((messages, total))
+}
+
+/// Flagged (starred) messages in IMAP_MAILBOX as follow-up checkboxes
+pub async fn fetch_flagged(config: &ImapConfig) -> Result<Option<String>> {
+    let (Some(host), Some(username), Some(password)) =
+        (&config.host, &config.username, &config.password)
+    else {
+        tracing::debug!("IMAP_HOST/IMAP_USERNAME/IMAP_PASSWORD not set");
+        return Ok(None);
+    };
+
+    let connect = async {
+        let tcp = TcpStream::connect((host.as_str(), config.port))
+            .await
+            .map_err(|e| failed(&format!("Could not connect to {}:{}", host, config.port), e))?;
+        let connector = tokio_native_tls::native_tls::TlsConnector::new()
+            .map_err(|e| failed("TLS setup failed", e))?;
+        let tls = tokio_native_tls::TlsConnector::from(connector)
+            .connect(host, tcp)
+            .await
+            .map_err(|e| failed(&format!("TLS handshake with {} failed", host), e))?;
+
+        let mut session = Session {
+            stream: BufReader::new(tls),
+            next_tag: 0,
+        };
+        let mut greeting = String::new();
+        session
+            .stream
+            .read_line(&mut greeting)
+            .await
+            .map_err(|e| failed("Read failed", e))?;
+        if !greeting.starts_with("* OK") && !greeting.starts_with("* PREAUTH") {
+            return Err(JournalError::ImapFailed(format!(
+                "Unexpected greeting: {}",
+                greeting.trim_end()
+            )));
+        }
+        fetch_messages(&mut session, config, username, password).await
+    };
+    let (mut messages, total) = tokio::time::timeout(TIMEOUT, connect)
+        .await
+        .map_err(|_| failed(host, "timed out"))??;
+
+    Ok(format_messages(
+        &mut messages,
+        total,
+        &config.mailbox,
+        &config.message_url,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fetch_response(seq: u32, uid: u32, headers: &str) -> String {
+        format!(
+            "* {} FETCH (UID {} BODY[HEADER.FIELDS (FROM SUBJECT DATE MESSAGE-ID)] {{{}}}\r\n{})\r\n",
+            seq,
+            uid,
+            headers.len(),
+            headers
+        )
+    }
+
+    #[tokio::test]
+    async fn test_read_and_format_flagged() {
+        let server = format!(
+            "{}{}A4 OK Fetch completed\r\n",
+            fetch_response(
+                2,
+                7,
+                "From: =?UTF-8?Q?Ren=C3=A9e?= <renee@example.com>\r\n\
+                 Subject: =?UTF-8?B?UXVhcnRlcmx5?=\r\n =?UTF-8?B?IHJlcG9ydA==?=\r\n\
+                 Date: Sun, 28 Dec 2025 09:15:00 +0000\r\n\
+                 Message-ID: <abc@example.com>\r\n\r\n"
+            ),
+            fetch_response(3, 9, "From: bob@example.com\r\nSubject: \r\n\r\n")
+        );
+        let mut reader = BufReader::new(server.as_bytes());
+        let responses = read_response(&mut reader, "A4").await.unwrap();
+        assert_eq!(responses.len(), 2);
+
+        let mut messages: Vec<Message> = responses
+            .iter()
+            .filter_map(|r| r.literals.first())
+            .map(|headers| parse_message(headers))
+            .collect();
+        assert_eq!(
+            format_messages(&mut messages, 5, "INBOX", "message:%3C{id}%3E").unwrap(),
+            "- [ ] ✉️ [Quarterly report](message:%3Cabc@example.com%3E) — Renée (Dec 28)\n\
+             - [ ] ✉️ (no subject) — bob@example.com\n\
+             - 📬 3 more flagged in INBOX"
+        );
+
+        let mut reader = BufReader::new(&b"* BYE\r\nA1 NO [AUTHENTICATIONFAILED] Invalid\r\n"[..]);
+        assert!(read_response(&mut reader, "A1").await.is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path};
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::entry::JournalEntry;
+use crate::journal::json_export::ImportStats;
+use crate::journal::{capture, filesystem, lint, navigation, template};
+
+/// Heading of the section imported entries go into
+pub const JOURNAL_HEADER: &str = "## Journal";
+
+/// Folder next to an entry that holds its imported attachments
+pub const ASSETS_DIR: &str = "assets";
+
+/// What to do with a day that already has an entry
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Conflict {
+    Skip,
+    Overwrite,
+    /// Add the imported entries to the existing entry's `## Journal` section
+    Append,
+}
+
+/// A file stored in the entry's `assets/` folder
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+impl Attachment {
+    /// Whether `name` is a plain file name, so the attachment can't be
+    /// written outside `assets/`
+    pub fn is_safe_name(name: &str) -> bool {
+        let mut components = Path::new(name).components();
+        matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        )
+    }
+}
+
+/// An entry from another journal app; a day can have several
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportedEntry {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+    pub title: String,
+    pub body: String,
+    pub tags: Vec<String>,
+    pub starred: bool,
+    pub attachments: Vec<Attachment>,
+}
+
+/// An entry as a `### 09:30 Title` heading followed by its body and tags
+fn to_markdown(entry: &ImportedEntry) -> String {
+    let mut heading = String::from("###");
+    if let Some(time) = entry.time {
+        heading.push_str(&time.format(" %H:%M").to_string());
+    }
+    if !entry.title.is_empty() {
+        heading.push(' ');
+        heading.push_str(&entry.title);
+    }
+    if entry.starred {
+        heading.push_str(" #starred");
+    }
+
+    let mut parts = vec![heading];
+    if !entry.body.is_empty() {
+        parts.push(entry.body.clone());
+    }
+    if !entry.tags.is_empty() {
+        let tags: Vec<String> = entry
+            .tags
+            .iter()
+            .map(|tag| format!("#{}", tag.trim().replace(' ', "-")))
+            .collect();
+        parts.push(tags.join(" "));
+    }
+    parts.join("\n\n")
+}
+
+/// The template without the `{{variables}}` filled in by sections and
+/// providers; lines holding only variables are dropped
+fn skeleton(template: &str) -> String {
+    let mut lines = Vec::new();
+    for line in template.lines() {
+        let variables = lint::variables(line);
+        if variables.is_empty() {
+            lines.push(line.to_string());
+            continue;
+        }
+        let mut stripped = line.to_string();
+        for variable in variables {
+            stripped = stripped.replacen(variable, "", 1);
+        }
+        if !stripped.trim().is_empty() {
+            lines.push(stripped.trim_end().to_string());
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Add `markdown` at the end of the `## Journal` section, creating it when
+/// the entry has none. Unlike `capture::append_to_section`, the section's own
+/// `###` headings don't end it.
+pub fn append_entries(content: &str, markdown: &str) -> String {
+    let has_section = content.lines().any(|line| line.trim() == JOURNAL_HEADER);
+    if !has_section {
+        return capture::append_to_section(content, JOURNAL_HEADER, markdown);
+    }
+    navigation::keep_at_bottom(content, |content| {
+        let mut lines: Vec<&str> = content.lines().collect();
+        let start = lines
+            .iter()
+            .position(|line| line.trim() == JOURNAL_HEADER)
+            .unwrap_or_default();
+        let mut end = lines[start + 1..]
+            .iter()
+            .position(|line| {
+                let line = line.trim();
+                line == "---" || line.starts_with("# ") || line.starts_with("## ")
+            })
+            .map_or(lines.len(), |i| start + 1 + i);
+        while end > start + 1 && lines[end - 1].trim().is_empty() {
+            end -= 1;
+        }
+        let at_start = end == start + 1;
+        lines.insert(end, markdown);
+        if !at_start {
+            lines.insert(end, "");
+        }
+        let mut updated = lines.join("\n");
+        updated.push('\n');
+        updated
+    })
+}
+
+/// The daily template filled for `date` with the day's entries in its
+/// `## Journal` section
+pub fn day_content(template: &str, date: NaiveDate, entries: &[&ImportedEntry]) -> String {
+    let skeleton = skeleton(&template::apply_variables(template, date, None, None));
+    append_entries(&skeleton, &join(entries))
+}
+
+fn join(entries: &[&ImportedEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| to_markdown(entry))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Write one journal entry per day, with the day's attachments in `assets/`
+/// next to it, and add each day to SUMMARY.md. Returns the stats and the
+/// dates that were written.
+pub fn write_days(
+    entries: &[ImportedEntry],
+    config: &Config,
+    conflict: Conflict,
+) -> Result<(ImportStats, Vec<NaiveDate>)> {
+    let template = template::load_template(&config.template_path)?;
+    let mut days: BTreeMap<NaiveDate, Vec<&ImportedEntry>> = BTreeMap::new();
+    for entry in entries {
+        days.entry(entry.date).or_default().push(entry);
+    }
+
+    let mut stats = ImportStats::default();
+    let mut written = Vec::new();
+    for (date, day) in days {
+        let exists = JournalEntry::exists(date, config);
+        if exists && conflict == Conflict::Skip {
+            stats.skipped += 1;
+            continue;
+        }
+
+        let (year, month) = (date.year() as u32, date.month());
+        let dir = filesystem::ensure_entry_dir(date, &config.journal_dir, config.layout)?;
+        filesystem::create_year_readme(year, &config.journal_dir, config)?;
+        filesystem::create_month_readme(year, month, &config.journal_dir, config)?;
+
+        for attachment in day.iter().flat_map(|entry| &entry.attachments) {
+            if !Attachment::is_safe_name(&attachment.name) {
+                return Err(JournalError::ImportFailed(format!(
+                    "Attachment name {:?} is not a plain file name",
+                    attachment.name
+                )));
+            }
+            let path = dir.join(ASSETS_DIR).join(&attachment.name);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(JournalError::at_path(parent))?;
+            }
+            fs::write(&path, &attachment.data).map_err(JournalError::at_path(&path))?;
+        }
+
+        let content = if exists && conflict == Conflict::Append {
+            let path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+            let existing = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+            stats.appended += 1;
+            append_entries(&existing, &join(&day))
+        } else {
+            if exists {
+                stats.overwritten += 1;
+            } else {
+                stats.created += 1;
+            }
+            day_content(&template, date, &day)
+        };
+        JournalEntry::save(date, &content, config)?;
+        JournalEntry::add_to_summary(date, config)?;
+        written.push(date);
+    }
+    Ok((stats, written))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_content() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let morning = ImportedEntry {
+            date,
+            time: NaiveTime::from_hms_opt(9, 30, 0),
+            title: "Standup".to_string(),
+            body: "Shipped the export.".to_string(),
+            tags: vec!["work".to_string(), "road trip".to_string()],
+            ..ImportedEntry::default()
+        };
+        let evening = ImportedEntry {
+            date,
+            title: "Evening".to_string(),
+            starred: true,
+            ..ImportedEntry::default()
+        };
+        let template = "# {{day_of_week}}, {{date}}\n\n## Notes\n{{weather}}\n\n---\n**Mood**: \n";
+        let content = day_content(template, date, &[&morning]);
+        assert_eq!(
+            content,
+            "# Monday, 2025-12-29\n\n## Notes\n\n## Journal\n### 09:30 Standup\n\nShipped the export.\n\n#work #road-trip\n\n---\n**Mood**: \n"
+        );
+        assert_eq!(
+            append_entries(&content, &join(&[&evening])),
+            "# Monday, 2025-12-29\n\n## Notes\n\n## Journal\n### 09:30 Standup\n\nShipped the export.\n\n#work #road-trip\n\n### Evening #starred\n\n---\n**Mood**: \n"
+        );
+
+        assert!(Attachment::is_safe_name("abc.jpeg"));
+        for name in [
+            "",
+            "../evil.sh",
+            "/etc/passwd",
+            "photos/abc.jpeg",
+            "..",
+            ".",
+        ] {
+            assert!(!Attachment::is_safe_name(name), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use chrono::{Local, NaiveDate};
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::{self, Layout};
+use crate::journal::parser;
+
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS entries (
+    date TEXT PRIMARY KEY,
+    path TEXT NOT NULL,
+    word_count INTEGER NOT NULL,
+    open_tasks INTEGER NOT NULL,
+    done_tasks INTEGER NOT NULL,
+    indexed_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tags (
+    date TEXT NOT NULL REFERENCES entries(date) ON DELETE CASCADE,
+    tag TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS metrics (
+    date TEXT NOT NULL REFERENCES entries(date) ON DELETE CASCADE,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tasks (
+    date TEXT NOT NULL REFERENCES entries(date) ON DELETE CASCADE,
+    text TEXT NOT NULL,
+    checked INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_tags_tag ON tags(tag);
+CREATE INDEX IF NOT EXISTS idx_metrics_key ON metrics(key);
+"#;
+
+/// Metadata about a single indexed entry
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IndexedEntry {
+    pub date: String,
+    pub path: String,
+    pub word_count: u64,
+    pub open_tasks: u64,
+    pub done_tasks: u64,
+    pub tags: Vec<String>,
+}
+
+/// Filters for querying indexed entries
+#[derive(Debug, Default)]
+pub struct EntryFilter {
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub tag: Option<String>,
+}
+
+/// SQLite index of entry metadata (dates, word counts, tags, metrics, tasks)
+pub struct Index {
+    conn: Connection,
+}
+
+impl Index {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(path).map_err(index_error)?;
+        conn.execute_batch("PRAGMA foreign_keys = ON;")
+            .map_err(index_error)?;
+        conn.execute_batch(SCHEMA).map_err(index_error)?;
+
+        Ok(Index { conn })
+    }
+
+    #[cfg(test)]
+    fn open_in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory().map_err(index_error)?;
+        conn.execute_batch("PRAGMA foreign_keys = ON;")
+            .map_err(index_error)?;
+        conn.execute_batch(SCHEMA).map_err(index_error)?;
+        Ok(Index { conn })
+    }
+
+    /// Insert or replace the metadata for one entry
+    pub fn update_entry(&mut self, date: NaiveDate, path: &Path, content: &str) -> Result<()> {
+        let date_str = date.format("%Y-%m-%d").to_string();
+        let tasks = parser::extract_tasks(content);
+        let done = tasks.iter().filter(|(_, checked)| *checked).count();
+        let open = tasks.len() - done;
+
+        let tx = self.conn.transaction().map_err(index_error)?;
+        tx.execute("DELETE FROM entries WHERE date = ?1", params![date_str])
+            .map_err(index_error)?;
+        tx.execute(
+            "INSERT INTO entries (date, path, word_count, open_tasks, done_tasks, indexed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                date_str,
+                path.to_string_lossy(),
+                parser::word_count(content) as i64,
+                open as i64,
+                done as i64,
+                Local::now().to_rfc3339(),
+            ],
+        )
+        .map_err(index_error)?;
+
+        for tag in parser::extract_tags(content) {
+            tx.execute(
+                "INSERT INTO tags (date, tag) VALUES (?1, ?2)",
+                params![date_str, tag],
+            )
+            .map_err(index_error)?;
+        }
+
+        for (key, value) in parser::extract_metrics(content) {
+            tx.execute(
+                "INSERT INTO metrics (date, key, value) VALUES (?1, ?2, ?3)",
+                params![date_str, key, value],
+            )
+            .map_err(index_error)?;
+        }
+
+        for (text, checked) in tasks {
+            tx.execute(
+                "INSERT INTO tasks (date, text, checked) VALUES (?1, ?2, ?3)",
+                params![date_str, text, checked],
+            )
+            .map_err(index_error)?;
+        }
+
+        tx.commit().map_err(index_error)
+    }
+
+    /// Remove an entry (and its tags, metrics and tasks) from the index
+    pub fn remove_entry(&self, date: NaiveDate) -> Result<()> {
+        self.conn
+            .execute(
+                "DELETE FROM entries WHERE date = ?1",
+                params![date.format("%Y-%m-%d").to_string()],
+            )
+            .map_err(index_error)?;
+        Ok(())
+    }
+
+    /// Drop everything and re-index all entries found in the journal directory
+    pub fn rebuild(&mut self, journal_dir: &Path, layout: Layout) -> Result<usize> {
+        self.conn
+            .execute("DELETE FROM entries", [])
+            .map_err(index_error)?;
+
+        let entries = filesystem::list_entries(journal_dir, layout)?;
+        for (date, path) in &entries {
+            let content = fs::read_to_string(path).map_err(JournalError::at_path(path))?;
+            self.update_entry(*date, path, &content)?;
+        }
+
+        Ok(entries.len())
+    }
+
+    /// Query indexed entries, newest first
+    pub fn entries(&self, filter: &EntryFilter) -> Result<Vec<IndexedEntry>> {
+        let since = filter.since.map(|d| d.format("%Y-%m-%d").to_string());
+        let until = filter.until.map(|d| d.format("%Y-%m-%d").to_string());
+        let tag = filter
+            .tag
+            .as_ref()
+            .map(|t| t.trim_start_matches('#').to_lowercase());
+
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT date, path, word_count, open_tasks, done_tasks FROM entries
+                 WHERE (?1 IS NULL OR date >= ?1)
+                   AND (?2 IS NULL OR date <= ?2)
+                   AND (?3 IS NULL OR date IN (SELECT date FROM tags WHERE tag = ?3))
+                 ORDER BY date DESC",
+            )
+            .map_err(index_error)?;
+
+        let rows = stmt
+            .query_map(params![since, until, tag], |row| {
+                Ok(IndexedEntry {
+                    date: row.get(0)?,
+                    path: row.get(1)?,
+                    word_count: row.get::<_, i64>(2)? as u64,
+                    open_tasks: row.get::<_, i64>(3)? as u64,
+                    done_tasks: row.get::<_, i64>(4)? as u64,
+                    tags: Vec::new(),
+                })
+            })
+            .map_err(index_error)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let mut entry = row.map_err(index_error)?;
+            entry.tags = self.tags_for(&entry.date)?;
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    fn tags_for(&self, date: &str) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT tag FROM tags WHERE date = ?1 ORDER BY tag")
+            .map_err(index_error)?;
+        let rows = stmt
+            .query_map(params![date], |row| row.get(0))
+            .map_err(index_error)?;
+        rows.collect::<rusqlite::Result<Vec<String>>>()
+            .map_err(index_error)
+    }
+}
+
+/// Record an entry write in the index (non-blocking on error)
+pub fn record_entry(config: &Config, date: NaiveDate, path: &Path, content: &str) {
+    let result = Index::open(&config.index_path)
+        .and_then(|mut index| index.update_entry(date, path, content));
+
+    if let Err(e) = result {
+        tracing::warn!("Could not update entry index: {}", e);
+    }
+}
+
+fn index_error(e: rusqlite::Error) -> JournalError {
+    JournalError::IndexFailed(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRY: &str = r#"# 2025-12-29 - Monday
+
+## Goals for Today
+- [ ] Write tests #work
+- [x] Go for a run #health
+
+---
+
+**Mood(1-10)**: 7
+**Energy Level(1-10)**:
+"#;
+
+    #[test]
+    fn test_update_and_query_entry() {
+        let mut index = Index::open_in_memory().unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        index
+            .update_entry(date, Path::new("journal/2025/12/29.md"), ENTRY)
+            .unwrap();
+
+        let entries = index.entries(&EntryFilter::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].date, "2025-12-29");
+        assert_eq!(entries[0].open_tasks, 1);
+        assert_eq!(entries[0].done_tasks, 1);
+        assert_eq!(entries[0].tags, vec!["health", "work"]);
+    }
+
+    #[test]
+    fn test_update_replaces_previous_metadata() {
+        let mut index = Index::open_in_memory().unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let path = Path::new("journal/2025/12/29.md");
+        index.update_entry(date, path, ENTRY).unwrap();
+        index.update_entry(date, path, "- [x] Only task").unwrap();
+
+        let entries = index.entries(&EntryFilter::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].open_tasks, 0);
+        assert!(entries[0].tags.is_empty());
+    }
+
+    #[test]
+    fn test_filter_by_tag_and_date() {
+        let mut index = Index::open_in_memory().unwrap();
+        let first = NaiveDate::from_ymd_opt(2025, 12, 28).unwrap();
+        let second = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        index
+            .update_entry(first, Path::new("a.md"), "Ran today #health")
+            .unwrap();
+        index
+            .update_entry(second, Path::new("b.md"), ENTRY)
+            .unwrap();
+
+        let filter = EntryFilter {
+            tag: Some("#work".to_string()),
+            ..Default::default()
+        };
+        let entries = index.entries(&filter).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].date, "2025-12-29");
+
+        let filter = EntryFilter {
+            until: Some(first),
+            ..Default::default()
+        };
+        let entries = index.entries(&filter).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].date, "2025-12-28");
+
+        index.remove_entry(first).unwrap();
+        assert_eq!(index.entries(&EntryFilter::default()).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::journal::import::ImportedEntry;
+
+/// Time formats jrnl writes after the date, depending on its `timeformat`
+const TIME_FORMATS: [&str; 4] = ["%H:%M", "%H:%M:%S", "%I:%M %p", "%I:%M:%S %p"];
+
+/// Date, time and title of a `[2025-12-29 09:30] Title` line
+fn parse_header(line: &str) -> Option<(NaiveDate, Option<NaiveTime>, &str)> {
+    let (stamp, title) = line.strip_prefix('[')?.split_once(']')?;
+    let stamp = stamp.trim();
+    let date = NaiveDate::parse_from_str(stamp.get(..10)?, "%Y-%m-%d").ok()?;
+    let time = match stamp[10..].trim() {
+        "" => None,
+        time => Some(
+            TIME_FORMATS
+                .iter()
+                .find_map(|format| NaiveTime::parse_from_str(time, format).ok())?,
+        ),
+    };
+    Some((date, time, title.trim()))
+}
+
+/// Split a jrnl journal file (or `jrnl --export txt`) into entries at each
+/// timestamp line. Text before the first timestamp is ignored. A `*` at either
+/// end of the title stars the entry, and `@tags` become `#tags`.
+pub fn parse(text: &str) -> Vec<ImportedEntry> {
+    let mut entries: Vec<ImportedEntry> = Vec::new();
+    for line in text.lines() {
+        if let Some((date, time, title)) = parse_header(line) {
+            let star = title
+                .strip_suffix('*')
+                .filter(|rest| !rest.contains('*'))
+                .or_else(|| title.strip_prefix('*').filter(|rest| !rest.contains('*')));
+            entries.push(ImportedEntry {
+                date,
+                time,
+                title: convert_tags(star.unwrap_or(title).trim()),
+                starred: star.is_some(),
+                ..ImportedEntry::default()
+            });
+        } else if let Some(entry) = entries.last_mut() {
+            entry.body.push_str(line);
+            entry.body.push('\n');
+        }
+    }
+    for entry in &mut entries {
+        entry.body = convert_tags(entry.body.trim_matches('\n').trim_end());
+    }
+    entries
+}
+
+/// jrnl's `@tags` written as `#tags`
+fn convert_tags(text: &str) -> String {
+    let mut converted = String::with_capacity(text.len());
+    let mut previous: Option<char> = None;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let starts_word = previous.is_none_or(|p| p.is_whitespace() || p == '(');
+        if c == '@' && starts_word && chars.peek().is_some_and(|next| next.is_alphanumeric()) {
+            converted.push('#');
+        } else {
+            converted.push(c);
+        }
+        previous = Some(c);
+    }
+    converted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "[2025-12-29 09:30] Standup with @team\nShipped the export.\n\nMore notes. Mail me@example.com\n\n[2025-12-29 09:15 PM] Evening *\nTired.\n[2025-12-30] No time\n[not a date] stays in the body\n";
+        let entries = parse(text);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].title, "Standup with #team");
+        assert_eq!(
+            entries[0].body,
+            "Shipped the export.\n\nMore notes. Mail me@example.com"
+        );
+        assert_eq!(entries[1].time, NaiveTime::from_hms_opt(21, 15, 0));
+        assert!(entries[1].starred);
+        assert_eq!(entries[1].title, "Evening");
+        assert_eq!(
+            entries[2].date,
+            NaiveDate::from_ymd_opt(2025, 12, 30).unwrap()
+        );
+        assert_eq!(entries[2].time, None);
+        assert_eq!(entries[2].body, "[not a date] stays in the body");
+    }
+}
@@ -0,0 +1,403 @@
+use chrono::{Datelike, Local, NaiveDate};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fs;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::entry::JournalEntry;
+use crate::journal::filesystem::{self, Layout};
+use crate::journal::lock::WriteLock;
+use crate::journal::parser::{self, Section};
+use crate::journal::summary::{Summary, SummaryYear};
+use crate::journal::trash::{self, OperationKind};
+use crate::journal::{redact, storage};
+
+/// Bumped whenever the dump layout changes incompatibly
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Whole-journal dump: raw content for a lossless round trip plus parsed
+/// structure for programmatic processing
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JournalDump {
+    pub version: u32,
+    pub exported_at: String,
+    pub summary: Option<SummaryDump>,
+    #[serde(default)]
+    pub readmes: Vec<ReadmeDump>,
+    pub entries: Vec<EntryDump>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryDump {
+    pub content: String,
+    #[serde(default)]
+    pub years: Vec<SummaryYear>,
+}
+
+/// A year or month README, keyed by its path relative to the journal directory
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadmeDump {
+    pub path: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntryDump {
+    pub date: NaiveDate,
+    /// Raw markdown; when absent on import the entry is rebuilt from `sections`
+    pub content: Option<String>,
+    #[serde(default)]
+    pub sections: Vec<Section>,
+    #[serde(default)]
+    pub tasks: Vec<TaskDump>,
+    #[serde(default)]
+    pub metrics: Vec<MetricDump>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub word_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TaskDump {
+    pub text: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MetricDump {
+    pub key: String,
+    pub value: String,
+}
+
+/// One entry as a flat record (`export --format jsonl`): sections by title and
+/// footer metrics by name (without a `(1-10)`-style range), in entry order
+#[derive(Debug, Serialize)]
+pub struct EntryRecord {
+    pub date: NaiveDate,
+    /// The `# ` heading
+    pub title: Option<String>,
+    #[serde(serialize_with = "ordered_map")]
+    pub sections: Vec<(String, String)>,
+    pub tasks: Vec<TaskDump>,
+    /// Only fields that have a value
+    #[serde(serialize_with = "ordered_map")]
+    pub metrics: Vec<(String, String)>,
+    pub tags: Vec<String>,
+    pub word_count: usize,
+}
+
+/// Serialize pairs as a JSON object, keeping their order
+fn ordered_map<S: Serializer>(
+    pairs: &[(String, String)],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_map(pairs.iter().map(|(key, value)| (key, value)))
+}
+
+impl EntryRecord {
+    pub fn from_content(date: NaiveDate, content: &str) -> Self {
+        EntryRecord {
+            date,
+            title: content
+                .lines()
+                .find_map(|line| line.strip_prefix("# "))
+                .map(|title| title.trim().to_string()),
+            sections: parser::section_texts(content),
+            tasks: parser::extract_tasks(content)
+                .into_iter()
+                .map(|(text, done)| TaskDump { text, done })
+                .collect(),
+            metrics: parser::extract_metrics(content)
+                .into_iter()
+                .filter(|(_, value)| !value.is_empty())
+                .map(|(key, value)| {
+                    let name = key.split('(').next().unwrap_or_default().trim();
+                    (name.to_string(), value)
+                })
+                .collect(),
+            tags: parser::extract_tags(content),
+            word_count: parser::word_count(content),
+        }
+    }
+}
+
+/// What an import did
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct ImportStats {
+    pub created: usize,
+    pub overwritten: usize,
+    pub appended: usize,
+    pub skipped: usize,
+}
+
+impl JournalDump {
+    /// Redact private text in every entry, README and the summary (see `redact::redact`)
+    pub fn redact(&mut self, strip: bool) {
+        for entry in &mut self.entries {
+            let content = redact::redact(&entry.markdown(), strip);
+            *entry = EntryDump::from_content(entry.date, content);
+        }
+        for readme in &mut self.readmes {
+            readme.content = redact::redact(&readme.content, strip);
+        }
+        if let Some(summary) = &mut self.summary {
+            summary.content = redact::redact(&summary.content, strip);
+        }
+    }
+}
+
+impl EntryDump {
+    pub fn from_content(date: NaiveDate, content: String) -> Self {
+        EntryDump {
+            date,
+            sections: parser::split_sections(&content),
+            tasks: parser::extract_tasks(&content)
+                .into_iter()
+                .map(|(text, done)| TaskDump { text, done })
+                .collect(),
+            metrics: parser::extract_metrics(&content)
+                .into_iter()
+                .map(|(key, value)| MetricDump { key, value })
+                .collect(),
+            tags: parser::extract_tags(&content),
+            word_count: parser::word_count(&content),
+            content: Some(content),
+        }
+    }
+
+    fn markdown(&self) -> String {
+        match &self.content {
+            Some(content) => content.clone(),
+            None => parser::join_sections(&self.sections),
+        }
+    }
+}
+
+/// Dump every entry, README and SUMMARY.md under the journal directory
+pub fn export_journal(journal_dir: &Path, layout: Layout) -> Result<JournalDump> {
+    let mut entries = Vec::new();
+    for (date, path) in filesystem::list_entries(journal_dir, layout)? {
+        entries.push(EntryDump::from_content(date, fs::read_to_string(path)?));
+    }
+
+    let summary_path = journal_dir.join("SUMMARY.md");
+    let summary = if summary_path.exists() {
+        Some(SummaryDump {
+            content: fs::read_to_string(&summary_path)?,
+            years: Summary::parse(&summary_path, layout)?.tree(),
+        })
+    } else {
+        None
+    };
+
+    let mut readmes = Vec::new();
+    let mut dates: Vec<NaiveDate> = entries.iter().map(|e| e.date).collect();
+    dates.dedup_by_key(|d| d.format("%Y-%m").to_string());
+    for date in dates {
+        for relative in [
+            layout.year_page(date.year() as u32),
+            layout.month_page(date.year() as u32, date.month()),
+        ] {
+            let path = journal_dir.join(&relative);
+            if path.exists() && !readmes.iter().any(|r: &ReadmeDump| r.path == relative) {
+                readmes.push(ReadmeDump {
+                    content: fs::read_to_string(path)?,
+                    path: relative,
+                });
+            }
+        }
+    }
+
+    Ok(JournalDump {
+        version: FORMAT_VERSION,
+        exported_at: Local::now().to_rfc3339(),
+        summary,
+        readmes,
+        entries,
+    })
+}
+
+/// Write a dump back into the journal directory.
+///
+/// Existing entries are kept unless `overwrite` is set, in which case the old
+/// content goes through the usual history/trash path. SUMMARY.md is replaced
+/// by the dumped one when there is none yet (or when overwriting); otherwise
+/// the imported dates are added to the existing one.
+pub fn import_journal(dump: &JournalDump, config: &Config, overwrite: bool) -> Result<ImportStats> {
+    if dump.version > FORMAT_VERSION {
+        return Err(JournalError::ImportFailed(format!(
+            "Dump format version {} is newer than supported version {}",
+            dump.version, FORMAT_VERSION
+        )));
+    }
+
+    let mut stats = ImportStats::default();
+    for entry in &dump.entries {
+        let exists = JournalEntry::exists(entry.date, config);
+        if exists && !overwrite {
+            stats.skipped += 1;
+            continue;
+        }
+
+        let path = filesystem::get_entry_path(entry.date, &config.journal_dir, config.layout);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        JournalEntry::save(entry.date, &entry.markdown(), config)?;
+
+        if exists {
+            stats.overwritten += 1;
+        } else {
+            stats.created += 1;
+        }
+    }
+
+    for readme in &dump.readmes {
+        let path = config.journal_dir.join(&readme.path);
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            filesystem::write_atomic(&path, &readme.content)?;
+        }
+    }
+
+    let summary_path = config.journal_dir.join("SUMMARY.md");
+    match &dump.summary {
+        Some(summary) if overwrite || !summary_path.exists() => {
+            let path = summary_path;
+            let _lock = WriteLock::acquire(&config.lock_path)?;
+            trash::record_overwrite(
+                &config.trash_dir,
+                OperationKind::SummaryRewrite,
+                &path,
+                None,
+                &summary.content,
+            )?;
+            filesystem::write_atomic(&path, &summary.content)?;
+        }
+        _ => {
+            for entry in &dump.entries {
+                JournalEntry::add_to_summary(entry.date, config)?;
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Upload imported entries when a remote storage backend is configured
+pub async fn push_imported(dump: &JournalDump, config: &Config) {
+    for entry in &dump.entries {
+        storage::push_for_date(config, entry.date).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRY: &str = "# Monday, December 29, 2025\n\n## Goals for Today\n- [x] Ship export #work\n- [ ] Write tests\n\n---\n**Mood**: 7\n";
+    const SUMMARY: &str = "# Summary\n\n---\n\n# [2025](2025/README.md)\n- [December](2025/12/README.md)\n  - [29 - Monday](2025/12/29.md)\n";
+
+    fn temp_config(name: &str) -> Config {
+        let dir =
+            std::env::temp_dir().join(format!("easy_journal_json_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        Config {
+            journal_dir: dir.join("journal"),
+            index_path: dir.join("index.db"),
+            history_dir: dir.join("history"),
+            trash_dir: dir.join("trash"),
+            lock_path: dir.join("write.lock"),
+            git_autocommit: false,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_entry_dump_structure() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let dump = EntryDump::from_content(date, ENTRY.to_string());
+
+        assert_eq!(dump.tasks.len(), 2);
+        assert!(dump.tasks[0].done);
+        assert_eq!(dump.metrics[0].key, "Mood");
+        assert_eq!(dump.metrics[0].value, "7");
+        assert_eq!(dump.tags, vec!["work".to_string()]);
+        assert!(
+            dump.sections
+                .iter()
+                .any(|s| s.header == "## Goals for Today")
+        );
+    }
+
+    #[test]
+    fn test_entry_record() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let content = "# Monday\n\n## Work Accomplished\n- Shipped\n\n### Morning\n- Standup\n\n## Notes\n\n---\n\n**Mood(1-10)**: 7\n**Energy Level**:\n[← 2025-12-28](../28.md) <!-- nav -->\n";
+        let record = EntryRecord::from_content(date, content);
+
+        assert_eq!(
+            serde_json::to_value(&record).unwrap(),
+            serde_json::json!({
+                "date": "2025-12-29",
+                "title": "Monday",
+                "sections": {
+                    "Work Accomplished": "- Shipped",
+                    "Morning": "- Standup",
+                    "Notes": "",
+                },
+                "tasks": [],
+                "metrics": { "Mood": "7" },
+                "tags": [],
+                "word_count": 23,
+            })
+        );
+        // Sections keep the entry's order
+        let line = serde_json::to_string(&record).unwrap();
+        assert!(line.find("Work Accomplished") < line.find("Notes"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let source = temp_config("source");
+        fs::create_dir_all(source.journal_dir.join("2025/12")).unwrap();
+        fs::write(source.journal_dir.join("2025/12/29.md"), ENTRY).unwrap();
+        fs::write(source.journal_dir.join("2025/12/README.md"), "# December\n").unwrap();
+        fs::write(source.journal_dir.join("SUMMARY.md"), SUMMARY).unwrap();
+
+        let dump = export_journal(&source.journal_dir, source.layout).unwrap();
+        let summary = dump.summary.as_ref().unwrap();
+        assert_eq!(summary.years[0].year, 2025);
+        assert_eq!(summary.years[0].months[0].days.len(), 1);
+
+        let json = serde_json::to_string(&dump).unwrap();
+        let parsed: JournalDump = serde_json::from_str(&json).unwrap();
+
+        let target = temp_config("target");
+        let stats = import_journal(&parsed, &target, false).unwrap();
+        assert_eq!(stats.created, 1);
+        assert_eq!(
+            fs::read_to_string(target.journal_dir.join("2025/12/29.md")).unwrap(),
+            ENTRY
+        );
+        assert_eq!(
+            fs::read_to_string(target.journal_dir.join("SUMMARY.md")).unwrap(),
+            SUMMARY
+        );
+        assert!(target.journal_dir.join("2025/12/README.md").exists());
+
+        // Existing entries are skipped unless overwriting
+        let stats = import_journal(&parsed, &target, false).unwrap();
+        assert_eq!(stats.skipped, 1);
+
+        for config in [source, target] {
+            fs::remove_dir_all(config.journal_dir.parent().unwrap()).unwrap();
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use chrono::{Duration, Local, NaiveDate, TimeZone};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::config::LastFmConfig;
+use crate::error::{JournalError, Result, check_response};
+
+const API_URL: &str = "https://ws.audioscrobbler.com/2.0/";
+/// Scrobbles per page (the API maximum)
+const PAGE_SIZE: &str = "200";
+/// Stop paging after this many pages (1,000 tracks is a long day)
+const MAX_PAGES: u32 = 5;
+const TOP_TRACKS: usize = 5;
+
+#[derive(Deserialize, Debug)]
+struct RecentTracksResponse {
+    recenttracks: RecentTracks,
+}
+
+#[derive(Deserialize, Debug)]
+struct RecentTracks {
+    #[serde(default)]
+    track: Vec<Scrobble>,
+    #[serde(rename = "@attr")]
+    attr: PageInfo,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    total_pages: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Scrobble {
+    name: String,
+    artist: Text,
+    /// Missing for the track playing right now
+    date: Option<Text>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Text {
+    #[serde(rename = "#text")]
+    text: String,
+}
+
+/// Counts sorted by plays, ties by key so the output is stable
+fn ranked<K: Ord>(counts: HashMap<K, usize>) -> Vec<(K, usize)> {
+    let mut ranked: Vec<_> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked
+}
+
+fn format_listening(scrobbles: &[Scrobble]) -> Option<String> {
+    let played: Vec<&Scrobble> = scrobbles.iter().filter(|s| s.date.is_some()).collect();
+    if played.is_empty() {
+        return None;
+    }
+
+    let mut artists: HashMap<&str, usize> = HashMap::new();
+    let mut tracks: HashMap<(&str, &str), usize> = HashMap::new();
+    for scrobble in &played {
+        *artists.entry(&scrobble.artist.text).or_default() += 1;
+        *tracks
+            .entry((&scrobble.artist.text, &scrobble.name))
+            .or_default() += 1;
+    }
+
+    let top_artists: Vec<String> = ranked(artists)
+        .into_iter()
+        .take(3)
+        .map(|(artist, _)| artist.to_string())
+        .collect();
+    let mut lines = vec![format!(
+        "- 🎧 {} tracks played yesterday, mostly {}",
+        played.len(),
+        top_artists.join(", ")
+    )];
+
+    for ((artist, name), plays) in ranked(tracks).into_iter().take(TOP_TRACKS) {
+        if plays > 1 {
+            lines.push(format!("- 🎵 {} – {} ({} plays)", artist, name, plays));
+        } else {
+            lines.push(format!("- 🎵 {} – {}", artist, name));
+        }
+    }
+    Some(lines.join("\n"))
+}
+
+/// What was scrobbled to Last.fm on the day before `date`
+pub async fn fetch_listening(config: &LastFmConfig, date: NaiveDate) -> Result<Option<String>> {
+    let (Some(api_key), Some(user)) = (&config.api_key, &config.user) else {
+        tracing::debug!("LASTFM_API_KEY/LASTFM_USER not set");
+        return Ok(None);
+    };
+
+    let timestamp = |day: NaiveDate| {
+        Local
+            .from_local_datetime(&day.and_hms_opt(0, 0, 0).unwrap_or_default())
+            .earliest()
+            .map(|at| at.timestamp().to_string())
+            .unwrap_or_default()
+    };
+    let from = timestamp(date - Duration::days(1));
+    let to = timestamp(date);
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::LastFmFailed(format!("Failed to build HTTP client: {}", e)))?;
+
+    let mut scrobbles = Vec::new();
+    let mut page = 1;
+    loop {
+        let page_str = page.to_string();
+        let response = client
+            .get(API_URL)
+            .query(&[
+                ("method", "user.getrecenttracks"),
+                ("user", user.as_str()),
+                ("api_key", api_key.as_str()),
+                ("format", "json"),
+                ("from", from.as_str()),
+                ("to", to.as_str()),
+                ("limit", PAGE_SIZE),
+                ("page", page_str.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| JournalError::LastFmFailed(format!("Request failed: {}", e)))?;
+        let recent: RecentTracksResponse = check_response(response, "Last.fm")
+            .await?
+            .json()
+            .await
+            .map_err(|e| JournalError::LastFmFailed(format!("Failed to parse response: {}", e)))?;
+
+        let total_pages: u32 = recent.recenttracks.attr.total_pages.parse().unwrap_or(1);
+        scrobbles.extend(recent.recenttracks.track);
+        if page >= total_pages.min(MAX_PAGES) {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(format_listening(&scrobbles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_listening() {
+        let response: RecentTracksResponse = serde_json::from_str(
+            r##"{"recenttracks": {"track": [
+                {"name": "Now Playing", "artist": {"#text": "Someone"}, "@attr": {"nowplaying": "true"}},
+                {"name": "Teardrop", "artist": {"#text": "Massive Attack"}, "date": {"uts": "1", "#text": ""}},
+                {"name": "Angel", "artist": {"#text": "Massive Attack"}, "date": {"uts": "2", "#text": ""}},
+                {"name": "Teardrop", "artist": {"#text": "Massive Attack"}, "date": {"uts": "3", "#text": ""}},
+                {"name": "Roads", "artist": {"#text": "Portishead"}, "date": {"uts": "4", "#text": ""}}
+            ], "@attr": {"totalPages": "1", "total": "4"}}}"##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            format_listening(&response.recenttracks.track).unwrap(),
+            "- 🎧 4 tracks played yesterday, mostly Massive Attack, Portishead\n\
+             - 🎵 Massive Attack – Teardrop (2 plays)\n\
+             - 🎵 Massive Attack – Angel\n\
+             - 🎵 Portishead – Roads"
+        );
+    }
+}
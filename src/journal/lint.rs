@@ -0,0 +1,322 @@
+use serde::Serialize;
+
+use crate::journal::goals::SectionGoal;
+use crate::journal::{navigation, parser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// A `##` section of the template is not in the entry
+    MissingSection,
+    /// A `{{variable}}` that was never filled in
+    LeftoverVariable,
+    MalformedCheckbox,
+    /// A footer line that is not `**Key**: value`
+    MalformedMetric,
+    /// A footer field of the template is not in the entry
+    MissingMetric,
+    /// A value outside the `(1-10)`-style range of its field
+    MetricOutOfRange,
+    /// A section with fewer items than its SECTION_GOALS goal
+    UnmetGoal,
+}
+
+impl IssueKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            IssueKind::MissingSection => "missing section",
+            IssueKind::LeftoverVariable => "leftover variable",
+            IssueKind::MalformedCheckbox => "malformed checkbox",
+            IssueKind::MalformedMetric => "malformed metric",
+            IssueKind::MissingMetric => "missing metric",
+            IssueKind::MetricOutOfRange => "metric out of range",
+            IssueKind::UnmetGoal => "section goal",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Issue {
+    pub kind: IssueKind,
+    /// 1-based; `None` for things missing from the whole entry
+    pub line: Option<usize>,
+    pub message: String,
+    /// `fix` can repair it
+    pub fixable: bool,
+}
+
+/// Issues for the sections of `progress` that haven't reached their goal
+pub fn goal_issues(progress: &[SectionGoal]) -> Vec<Issue> {
+    progress
+        .iter()
+        .filter(|goal| !goal.is_met())
+        .map(|goal| Issue {
+            kind: IssueKind::UnmetGoal,
+            line: goal.line,
+            message: format!(
+                "\"{}\" has {} of {} items",
+                goal.section, goal.written, goal.needed
+            ),
+            fixable: false,
+        })
+        .collect()
+}
+
+/// `##` headings of the template, without the ones built from variables
+pub fn expected_sections(template: &str) -> Vec<String> {
+    template
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("## ") && !line.contains("{{"))
+        .map(|line| line.trim_start_matches('#').trim().to_string())
+        .collect()
+}
+
+/// Footer field names of the template
+pub fn expected_metrics(template: &str) -> Vec<String> {
+    footer(template)
+        .filter_map(|(_, line)| parser::parse_metric(line))
+        .map(|(key, _)| key)
+        .collect()
+}
+
+/// Lines after the last `---` rule, with their 0-based index
+fn footer(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    let start = content
+        .lines()
+        .collect::<Vec<_>>()
+        .iter()
+        .rposition(|line| line.trim() == "---")
+        .map_or(usize::MAX, |index| index + 1);
+    content
+        .lines()
+        .enumerate()
+        .skip(start)
+        .filter(|(_, line)| !navigation::is_nav_line(line))
+}
+
+/// The `- [ ] ` / `- [x] ` form of a checkbox line that is written some other
+/// way, e.g. `-[ ]`, `- []`, `[x] task` or `- [ ]task`
+fn normalize_checkbox(line: &str) -> Option<String> {
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let trimmed = line.trim();
+    let after_dash = trimmed.strip_prefix('-').unwrap_or(trimmed);
+    let rest = after_dash.trim_start().strip_prefix('[')?;
+    let close = rest.find(']').filter(|close| *close <= 3)?;
+    let mark = match rest[..close].trim() {
+        "" => " ",
+        mark @ ("x" | "X") => mark,
+        _ => return None,
+    };
+    let text = &rest[close + 1..];
+    // `[x]: url` and `[x](url)` are links, not checkboxes
+    if text.starts_with(':') || text.starts_with('(') {
+        return None;
+    }
+
+    let normalized = format!("{}- [{}] {}", indent, mark, text.trim());
+    let normalized = normalized.trim_end();
+    (normalized != line.trim_end()).then(|| normalized.to_string())
+}
+
+/// `**Key:** value` and `**Key**:value` style lines in `**Key**: value` form
+fn normalize_metric(line: &str) -> Option<String> {
+    let rest = line.trim().strip_prefix("**")?;
+    let (key, value) = rest.split_once(":**")?;
+    Some(
+        format!("**{}**: {}", key.trim(), value.trim())
+            .trim_end()
+            .to_string(),
+    )
+}
+
+/// The `(min-max)` range in a field name like `Mood(1-10)`
+fn metric_range(key: &str) -> Option<(f64, f64)> {
+    let inner = key.split_once('(')?.1.split_once(')')?.0;
+    let (min, max) = inner.split_once('-')?;
+    Some((min.trim().parse().ok()?, max.trim().parse().ok()?))
+}
+
+/// Spans of `{{...}}` in a line
+pub(crate) fn variables(line: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        found.push(&rest[start..start + end + 2]);
+        rest = &rest[start + end + 2..];
+    }
+    found
+}
+
+/// Check an entry against the sections and footer fields of its template
+pub fn check(content: &str, template: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    let headings: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('#'))
+        .map(|line| line.trim_start_matches('#').trim().to_lowercase())
+        .collect();
+    for section in expected_sections(template) {
+        if !headings.contains(&section.to_lowercase()) {
+            issues.push(Issue {
+                kind: IssueKind::MissingSection,
+                line: None,
+                message: format!("no \"## {}\" section", section),
+                fixable: false,
+            });
+        }
+    }
+
+    let mut in_code = false;
+    for (index, line) in content.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        if in_code {
+            continue;
+        }
+        for variable in variables(line) {
+            issues.push(Issue {
+                kind: IssueKind::LeftoverVariable,
+                line: Some(index + 1),
+                message: format!("{} was not filled in", variable),
+                fixable: true,
+            });
+        }
+        if let Some(normalized) = normalize_checkbox(line) {
+            issues.push(Issue {
+                kind: IssueKind::MalformedCheckbox,
+                line: Some(index + 1),
+                message: format!("write it as \"{}\"", normalized.trim()),
+                fixable: true,
+            });
+        }
+    }
+
+    let mut present = Vec::new();
+    for (index, line) in footer(content) {
+        if !line.trim().starts_with("**") {
+            continue;
+        }
+        let Some((key, value)) = parser::parse_metric(line) else {
+            let normalized = normalize_metric(line);
+            issues.push(Issue {
+                kind: IssueKind::MalformedMetric,
+                line: Some(index + 1),
+                message: match &normalized {
+                    Some(normalized) => format!("write it as \"{}\"", normalized),
+                    None => "expected \"**Key**: value\"".to_string(),
+                },
+                fixable: normalized.is_some(),
+            });
+            if let Some((key, _)) = normalized.as_deref().and_then(parser::parse_metric) {
+                present.push(parser::metric_name(&key));
+            }
+            continue;
+        };
+        present.push(parser::metric_name(&key));
+        if let Some((min, max)) = metric_range(&key)
+            && !value.is_empty()
+            && !value
+                .parse::<f64>()
+                .is_ok_and(|value| (min..=max).contains(&value))
+        {
+            issues.push(Issue {
+                kind: IssueKind::MetricOutOfRange,
+                line: Some(index + 1),
+                message: format!("{} should be a number from {} to {}", key, min, max),
+                fixable: false,
+            });
+        }
+    }
+    for key in expected_metrics(template) {
+        if !present.contains(&parser::metric_name(&key)) {
+            issues.push(Issue {
+                kind: IssueKind::MissingMetric,
+                line: None,
+                message: format!("no \"**{}**:\" field", key),
+                fixable: false,
+            });
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+/// Repair the fixable issues: normalize checkboxes and footer fields and drop
+/// leftover `{{variables}}`
+pub fn fix(content: &str) -> String {
+    let footer_start = footer(content)
+        .next()
+        .map_or(usize::MAX, |(index, _)| index);
+    let mut in_code = false;
+    let mut fixed: Vec<String> = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        if in_code {
+            fixed.push(line.to_string());
+            continue;
+        }
+        let mut line = line.to_string();
+        for variable in variables(&line.clone()) {
+            line = line.replacen(variable, "", 1);
+        }
+        let line = line.trim_end().to_string();
+        let line = normalize_checkbox(&line).unwrap_or(line);
+        let line = if index >= footer_start && parser::parse_metric(&line).is_none() {
+            normalize_metric(&line).unwrap_or(line)
+        } else {
+            line
+        };
+        fixed.push(line);
+    }
+    let mut fixed = fixed.join("\n");
+    if content.ends_with('\n') {
+        fixed.push('\n');
+    }
+    fixed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_and_fix() {
+        let template = "# {{date}}\n\n## Reminders\n{{reminders}}\n\n## Goals for Today\n- [ ]\n\n## Tomorrow's Focus\n-\n\n---\n\n**Mood(1-10)**:\n**Energy Level**:\n";
+        let content = "# 2025-12-29\n\n## Goals for Today\n-[ ] Write report\n- [x] Call bank\n[X]Fix bug\n- [ ]\nSee [x]: not a task {{weather}}\n\n```\n{{kept}}\n```\n\n---\n\n**Mood(1-10)**: 12\n**Hours:** 8\n";
+
+        let issues = check(content, template);
+        let found: Vec<(IssueKind, Option<usize>)> =
+            issues.iter().map(|i| (i.kind, i.line)).collect();
+        assert_eq!(
+            found,
+            vec![
+                (IssueKind::MissingSection, None),
+                (IssueKind::MissingSection, None),
+                (IssueKind::MissingMetric, None),
+                (IssueKind::MalformedCheckbox, Some(4)),
+                (IssueKind::MalformedCheckbox, Some(6)),
+                (IssueKind::LeftoverVariable, Some(8)),
+                (IssueKind::MetricOutOfRange, Some(16)),
+                (IssueKind::MalformedMetric, Some(17)),
+            ]
+        );
+        assert_eq!(issues[0].message, "no \"## Reminders\" section");
+
+        let fixed = fix(content);
+        assert_eq!(
+            fixed,
+            "# 2025-12-29\n\n## Goals for Today\n- [ ] Write report\n- [x] Call bank\n- [X] Fix bug\n- [ ]\nSee [x]: not a task\n\n```\n{{kept}}\n```\n\n---\n\n**Mood(1-10)**: 12\n**Hours**: 8\n"
+        );
+        assert!(check(&fixed, template).iter().all(|issue| !issue.fixable));
+    }
+}
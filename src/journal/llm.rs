@@ -0,0 +1,82 @@
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::config::LlmConfig;
+use crate::error::{JournalError, Result, check_response};
+
+/// Local models can take a while to answer
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Deserialize, Debug)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Choice {
+    message: ChatMessage,
+}
+
+#[derive(Deserialize, Debug)]
+struct ChatMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// The endpoint requests go to; errors when LLM_API_URL is not set so nothing
+/// is ever sent without explicit configuration
+pub fn endpoint(config: &LlmConfig) -> Result<String> {
+    let url = config.api_url.as_deref().ok_or_else(|| {
+        JournalError::InvalidConfig(
+            "LLM_API_URL not set; summaries are only generated when an endpoint is configured"
+                .to_string(),
+        )
+    })?;
+    Ok(format!("{}/chat/completions", url.trim_end_matches('/')))
+}
+
+/// Send a system and a user message to an OpenAI-compatible chat completions
+/// endpoint and return the reply
+pub async fn chat(config: &LlmConfig, system: &str, prompt: &str) -> Result<String> {
+    let url = endpoint(config)?;
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| JournalError::LlmFailed(format!("Failed to build HTTP client: {}", e)))?;
+
+    let mut request = client.post(&url).json(&serde_json::json!({
+        "model": config.model,
+        "messages": [
+            { "role": "system", "content": system },
+            { "role": "user", "content": prompt },
+        ],
+        "temperature": 0.3,
+    }));
+    if let Some(key) = &config.api_key {
+        request = request.bearer_auth(key);
+    }
+
+    tracing::debug!(
+        url = url.as_str(),
+        model = config.model.as_str(),
+        "chat request"
+    );
+    let response = request
+        .send()
+        .await
+        .map_err(|e| JournalError::LlmFailed(format!("Request to {} failed: {}", url, e)))?;
+    let response: ChatResponse = check_response(response, "LLM")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::LlmFailed(format!("Failed to parse response: {}", e)))?;
+
+    response
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|choice| choice.message.content)
+        .filter(|content| !content.trim().is_empty())
+        .ok_or_else(|| JournalError::LlmFailed("The model returned an empty reply".to_string()))
+}
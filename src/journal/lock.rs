@@ -0,0 +1,90 @@
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::error::{JournalError, Result};
+
+/// How long to wait for another writer before giving up
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Exclusive advisory lock held while entries or SUMMARY.md are written.
+///
+/// The OS releases the lock if the process dies, so a crashed writer never
+/// leaves a stale lock behind. The lock is released when this value is dropped.
+pub struct WriteLock {
+    file: File,
+}
+
+impl WriteLock {
+    pub fn acquire(path: &Path) -> Result<Self> {
+        Self::acquire_with_timeout(path, LOCK_TIMEOUT)
+    }
+
+    pub fn acquire_with_timeout(path: &Path, timeout: Duration) -> Result<Self> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(path)?;
+
+        let start = Instant::now();
+        loop {
+            match file.try_lock() {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) => {
+                    if start.elapsed() > timeout {
+                        let holder = fs::read_to_string(path).unwrap_or_default();
+                        return Err(JournalError::LockFailed(format!(
+                            "Timed out waiting for {} (held by process {})",
+                            path.display(),
+                            holder.trim()
+                        )));
+                    }
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                Err(TryLockError::Error(e)) => return Err(JournalError::Io(e)),
+            }
+        }
+
+        // Record the holder to make a long wait easier to diagnose
+        file.set_len(0)?;
+        write!(file, "{}", std::process::id())?;
+
+        Ok(WriteLock { file })
+    }
+}
+
+impl Drop for WriteLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_is_exclusive_until_dropped() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_lock_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let lock_path = dir.join("write.lock");
+
+        let first = WriteLock::acquire(&lock_path).unwrap();
+        let second = WriteLock::acquire_with_timeout(&lock_path, Duration::from_millis(100));
+        assert!(matches!(second, Err(JournalError::LockFailed(_))));
+
+        drop(first);
+        assert!(WriteLock::acquire_with_timeout(&lock_path, Duration::from_millis(100)).is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
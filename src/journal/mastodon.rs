@@ -0,0 +1,214 @@
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate};
+use serde::Deserialize;
+
+use crate::config::MastodonConfig;
+use crate::error::{JournalError, Result, check_response};
+
+/// Statuses per page (the API maximum)
+const PAGE_SIZE: &str = "40";
+/// Stop paging after this many pages
+const MAX_PAGES: usize = 10;
+
+#[derive(Deserialize, Debug)]
+struct Account {
+    id: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Status {
+    id: String,
+    created_at: DateTime<FixedOffset>,
+    url: Option<String>,
+    /// HTML
+    content: String,
+    /// Content warning
+    #[serde(default)]
+    spoiler_text: String,
+    visibility: String,
+    in_reply_to_id: Option<String>,
+    #[serde(default)]
+    media_attachments: Vec<serde_json::Value>,
+}
+
+impl Status {
+    fn posted_on(&self) -> NaiveDate {
+        self.created_at.with_timezone(&Local).date_naive()
+    }
+}
+
+/// Plain text of a status: paragraphs and line breaks kept, tags dropped
+fn html_to_text(html: &str) -> String {
+    let html = html
+        .replace("</p><p>", "\n\n")
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n");
+
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// Posts from the day before `date`, oldest first, each as a quote under its time
+fn format_posts(statuses: &[Status], date: NaiveDate) -> Option<String> {
+    let yesterday = date - Duration::days(1);
+    let mut posts: Vec<&Status> = statuses
+        .iter()
+        .filter(|s| s.posted_on() == yesterday && s.visibility != "direct")
+        .collect();
+    if posts.is_empty() {
+        return None;
+    }
+    posts.sort_by_key(|s| s.created_at);
+
+    let blocks: Vec<String> = posts
+        .iter()
+        .map(|status| {
+            let time = status.created_at.with_timezone(&Local).format("%H:%M");
+            let mut heading = match &status.url {
+                Some(url) => format!("- [{}]({})", time, url),
+                None => format!("- {}", time),
+            };
+            if status.in_reply_to_id.is_some() {
+                heading.push_str(" ↩️ reply");
+            }
+            if !status.media_attachments.is_empty() {
+                heading.push_str(&format!(" 📎 {}", status.media_attachments.len()));
+            }
+
+            let mut lines = vec![heading];
+            if !status.spoiler_text.is_empty() {
+                lines.push(format!("  > CW: {}", status.spoiler_text));
+            }
+            for line in html_to_text(&status.content).lines() {
+                lines.push(format!("  > {}", line).trim_end().to_string());
+            }
+            lines.join("\n")
+        })
+        .collect();
+    Some(blocks.join("\n"))
+}
+
+/// Your own posts (no boosts or direct messages) from the day before `date`
+pub async fn fetch_posts(config: &MastodonConfig, date: NaiveDate) -> Result<Option<String>> {
+    let (Some(instance), Some(token)) = (&config.instance, &config.access_token) else {
+        tracing::debug!("MASTODON_INSTANCE/MASTODON_ACCESS_TOKEN not set");
+        return Ok(None);
+    };
+    let api = format!("{}/api/v1", instance.trim_end_matches('/'));
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::MastodonFailed(format!("Failed to build HTTP client: {}", e)))?;
+
+    let response = client
+        .get(format!("{}/accounts/verify_credentials", api))
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|e| JournalError::MastodonFailed(format!("Request failed: {}", e)))?;
+    let account: Account = check_response(response, "Mastodon")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::MastodonFailed(format!("Failed to parse account: {}", e)))?;
+
+    // Statuses come newest first; page back until we're before yesterday
+    let yesterday = date - Duration::days(1);
+    let mut statuses: Vec<Status> = Vec::new();
+    let mut max_id: Option<String> = None;
+    for _ in 0..MAX_PAGES {
+        let mut request = client
+            .get(format!("{}/accounts/{}/statuses", api, account.id))
+            .bearer_auth(token)
+            .query(&[("exclude_reblogs", "true"), ("limit", PAGE_SIZE)]);
+        if let Some(max_id) = &max_id {
+            request = request.query(&[("max_id", max_id)]);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| JournalError::MastodonFailed(format!("Request failed: {}", e)))?;
+        let page: Vec<Status> = check_response(response, "Mastodon")
+            .await?
+            .json()
+            .await
+            .map_err(|e| {
+                JournalError::MastodonFailed(format!("Failed to parse statuses: {}", e))
+            })?;
+
+        let Some(last) = page.last() else {
+            break;
+        };
+        let done = last.posted_on() < yesterday;
+        max_id = Some(last.id.clone());
+        statuses.extend(page);
+        if done {
+            break;
+        }
+    }
+
+    Ok(format_posts(&statuses, date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_posts() {
+        let statuses: Vec<Status> = serde_json::from_str(
+            r#"[
+                {"id": "4", "created_at": "2025-12-29T12:00:00.000Z", "url": "https://mastodon.example/@me/4",
+                 "content": "<p>Today already</p>", "visibility": "public", "in_reply_to_id": null},
+                {"id": "3", "created_at": "2025-12-28T13:00:00.000Z", "url": "https://mastodon.example/@me/3",
+                 "content": "<p>Shipped it &amp; <a href=\"https://example.com\">wrote it up</a></p><p>More<br />soon</p>",
+                 "spoiler_text": "", "visibility": "public", "in_reply_to_id": null,
+                 "media_attachments": [{"type": "image"}]},
+                {"id": "2", "created_at": "2025-12-28T12:30:00.000Z", "url": "https://mastodon.example/@me/2",
+                 "content": "<p>psst</p>", "visibility": "direct", "in_reply_to_id": null},
+                {"id": "1", "created_at": "2025-12-28T12:00:00.000Z", "url": "https://mastodon.example/@me/1",
+                 "content": "<p><span class=\"h-card\">@friend</span> agreed</p>", "spoiler_text": "food",
+                 "visibility": "unlisted", "in_reply_to_id": "0"}
+            ]"#,
+        )
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        // Times are shown in local time
+        let time = |status: &Status| {
+            status
+                .created_at
+                .with_timezone(&Local)
+                .format("%H:%M")
+                .to_string()
+        };
+
+        assert_eq!(
+            format_posts(&statuses, date).unwrap(),
+            format!(
+                "- [{}](https://mastodon.example/@me/1) ↩️ reply\n  > CW: food\n  > @friend agreed\n\
+                 - [{}](https://mastodon.example/@me/3) 📎 1\n  > Shipped it & wrote it up\n  >\n  > More\n  > soon",
+                time(&statuses[3]),
+                time(&statuses[1])
+            )
+        );
+        assert_eq!(format_posts(&statuses, date + Duration::days(5)), None);
+    }
+}
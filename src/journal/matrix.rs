@@ -0,0 +1,328 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::config::{Config, MatrixConfig};
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::capture;
+
+/// Milliseconds the homeserver holds a `/sync` request open when there is nothing new
+const SYNC_TIMEOUT_MS: u64 = 30_000;
+
+/// Wait after a failed sync before trying again
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+
+const HELP: &str = "Anything posted in this room is added to today's journal entry.\n\
+                    !today — show today's goals";
+
+#[derive(Deserialize, Debug)]
+struct JoinResponse {
+    room_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct WhoAmI {
+    user_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct SyncResponse {
+    next_batch: String,
+    #[serde(default)]
+    rooms: SyncRooms,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct SyncRooms {
+    #[serde(default)]
+    join: HashMap<String, JoinedRoom>,
+}
+
+#[derive(Deserialize, Debug)]
+struct JoinedRoom {
+    timeline: Timeline,
+}
+
+#[derive(Deserialize, Debug)]
+struct Timeline {
+    #[serde(default)]
+    events: Vec<RoomEvent>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RoomEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    sender: String,
+    #[serde(default)]
+    content: serde_json::Value,
+}
+
+/// A text message posted by `owner`, not by the bot
+fn message_text<'a>(event: &'a RoomEvent, own_user_id: &str, owner: &str) -> Option<&'a str> {
+    if event.event_type != "m.room.message" || event.sender == own_user_id || event.sender != owner
+    {
+        return None;
+    }
+    // Notices are sent by bots (including this one); answering them could loop
+    if event.content.get("msgtype")?.as_str()? != "m.text" {
+        return None;
+    }
+    event.content.get("body")?.as_str()
+}
+
+struct Client {
+    http: reqwest::Client,
+    homeserver: String,
+    token: String,
+    transaction: AtomicU64,
+}
+
+impl Client {
+    fn new(config: &MatrixConfig) -> Result<Self> {
+        let (Some(homeserver), Some(token)) = (&config.homeserver, &config.access_token) else {
+            return Err(JournalError::InvalidConfig(
+                "MATRIX_HOMESERVER and MATRIX_ACCESS_TOKEN must be set".to_string(),
+            ));
+        };
+        let http = reqwest::Client::builder()
+            .user_agent("easy_journal")
+            .timeout(Duration::from_millis(SYNC_TIMEOUT_MS + 10_000))
+            .build()
+            .map_err(|e| {
+                JournalError::MatrixFailed(format!("Failed to build HTTP client: {}", e))
+            })?;
+
+        Ok(Client {
+            http,
+            homeserver: homeserver.trim_end_matches('/').to_string(),
+            token: token.clone(),
+            transaction: AtomicU64::new(chrono::Utc::now().timestamp_millis() as u64),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/_matrix/client/v3/{}", self.homeserver, path)
+    }
+
+    async fn send_request(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let response = request
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .map_err(|e| JournalError::MatrixFailed(format!("Request failed: {}", e)))?;
+        check_response(response, "Matrix").await
+    }
+
+    async fn json<T: serde::de::DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T> {
+        self.send_request(request)
+            .await?
+            .json()
+            .await
+            .map_err(|e| JournalError::MatrixFailed(format!("Failed to parse response: {}", e)))
+    }
+
+    async fn whoami(&self) -> Result<String> {
+        let whoami: WhoAmI = self.json(self.http.get(self.url("account/whoami"))).await?;
+        Ok(whoami.user_id)
+    }
+
+    /// Join the room (a no-op when already joined) and return its ID
+    async fn join(&self, room: &str) -> Result<String> {
+        let path = format!("join/{}", encode(room));
+        let joined: JoinResponse = self
+            .json(self.http.post(self.url(&path)).json(&serde_json::json!({})))
+            .await?;
+        Ok(joined.room_id)
+    }
+
+    async fn sync(&self, room_id: &str, since: Option<&str>) -> Result<SyncResponse> {
+        let filter = serde_json::json!({
+            "room": {
+                "rooms": [room_id],
+                "timeline": { "limit": 50 },
+                "state": { "lazy_load_members": true },
+                "ephemeral": { "not_types": ["*"] },
+                "account_data": { "not_types": ["*"] },
+            },
+            "presence": { "not_types": ["*"] },
+            "account_data": { "not_types": ["*"] },
+        })
+        .to_string();
+
+        let mut query = vec![("filter", filter)];
+        if let Some(since) = since {
+            query.push(("since", since.to_string()));
+            query.push(("timeout", SYNC_TIMEOUT_MS.to_string()));
+        }
+        self.json(self.http.get(self.url("sync")).query(&query))
+            .await
+    }
+
+    /// Post a notice (bots use notices so other bots don't answer them)
+    async fn notice(&self, room_id: &str, text: &str) -> Result<()> {
+        let transaction = self.transaction.fetch_add(1, Ordering::Relaxed);
+        let path = format!(
+            "rooms/{}/send/m.room.message/ej{}",
+            encode(room_id),
+            transaction
+        );
+        self.send_request(self.http.put(self.url(&path)).json(&serde_json::json!({
+            "msgtype": "m.notice",
+            "body": text,
+        })))
+        .await?;
+        Ok(())
+    }
+}
+
+/// Percent-encode a room ID or alias for use in a URL path
+fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn goals_message(goals: Option<String>) -> String {
+    match goals {
+        Some(goals) => format!("Goals for today:\n{}", goals),
+        None => "No goals for today yet.".to_string(),
+    }
+}
+
+/// Answer a message: `!today`, `!help`, or a note to capture
+async fn respond(config: &Config, text: &str) -> String {
+    match text.trim() {
+        "!help" => HELP.to_string(),
+        "!today" => match capture::today_goals(config) {
+            Ok(goals) => goals_message(goals),
+            Err(e) => format!("✗ {}", e),
+        },
+        _ => match capture::append_to_today(config, text).await {
+            Ok((date, _)) => format!("✓ Added to {}", date.format("%Y-%m-%d")),
+            Err(e) => format!("✗ Could not add it: {}", e),
+        },
+    }
+}
+
+/// Post today's goals into the room, e.g. right after the daemon created the entry
+pub async fn post_goals(config: &Config) -> Result<()> {
+    let Some(room) = &config.matrix.room else {
+        return Ok(());
+    };
+    let client = Client::new(&config.matrix)?;
+    let room_id = client.join(room).await?;
+    client
+        .notice(&room_id, &goals_message(capture::today_goals(config)?))
+        .await
+}
+
+async fn listen(config: &Config, client: &Client, room: &str, owner: &str) -> Result<()> {
+    let own_user_id = client.whoami().await?;
+    let room_id = client.join(room).await?;
+
+    // Skip what was posted while we weren't listening
+    let mut since = client.sync(&room_id, None).await?.next_batch;
+    info!(room = room_id.as_str(), "Matrix bot listening for messages");
+
+    loop {
+        let sync = client.sync(&room_id, Some(&since)).await?;
+        since = sync.next_batch;
+
+        let Some(joined) = sync.rooms.join.get(&room_id) else {
+            continue;
+        };
+        for event in &joined.timeline.events {
+            let Some(text) = message_text(event, &own_user_id, owner) else {
+                continue;
+            };
+            debug!(sender = event.sender.as_str(), "received message");
+            let reply = respond(config, text).await;
+            if let Err(e) = client.notice(&room_id, &reply).await {
+                warn!("{}", e);
+            }
+        }
+    }
+}
+
+/// Capture messages `MATRIX_USER_ID` posts in `MATRIX_ROOM` into today's
+/// entry, forever
+pub async fn run(config: Config) {
+    let Some(room) = config.matrix.room.clone() else {
+        return;
+    };
+    let Some(owner) = config.matrix.user_id.clone() else {
+        warn!("Set MATRIX_USER_ID to your Matrix ID (e.g. @me:matrix.org) to use the Matrix bot");
+        return;
+    };
+    let client = match Client::new(&config.matrix) {
+        Ok(client) => client,
+        Err(e) => {
+            warn!("{}", e);
+            return;
+        }
+    };
+
+    loop {
+        if let Err(e) = listen(&config, &client, &room, &owner).await {
+            warn!("{}", e);
+        }
+        tokio::time::sleep(RETRY_DELAY).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(sender: &str, content: serde_json::Value) -> RoomEvent {
+        RoomEvent {
+            event_type: "m.room.message".to_string(),
+            sender: sender.to_string(),
+            content,
+        }
+    }
+
+    #[test]
+    fn test_message_text_skips_own_messages_and_notices() {
+        let me = "@journal:example.org";
+        let owner = "@me:example.org";
+        let text = event(
+            owner,
+            serde_json::json!({"msgtype": "m.text", "body": "Idea"}),
+        );
+        assert_eq!(message_text(&text, me, owner), Some("Idea"));
+
+        let own = event(me, serde_json::json!({"msgtype": "m.text", "body": "Idea"}));
+        assert_eq!(message_text(&own, me, owner), None);
+
+        let notice = event(
+            owner,
+            serde_json::json!({"msgtype": "m.notice", "body": "✓"}),
+        );
+        assert_eq!(message_text(&notice, me, owner), None);
+
+        let stranger = event(
+            "@guest:example.org",
+            serde_json::json!({"msgtype": "m.text", "body": "Hi"}),
+        );
+        assert_eq!(message_text(&stranger, me, owner), None);
+    }
+
+    #[test]
+    fn test_encode_room_alias() {
+        assert_eq!(encode("#journal:example.org"), "%23journal%3Aexample.org");
+        assert_eq!(encode("!abc:example.org"), "%21abc%3Aexample.org");
+    }
+}
@@ -0,0 +1,343 @@
+use chrono::{Datelike, Local, NaiveDate};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::{self, Layout, write_atomic};
+use crate::journal::summary::Summary;
+
+/// Layout written by this version:
+/// 1. entries where the configured [`Layout`] puts them (before: `YYYY-MM-DD.md`
+///    files anywhere)
+/// 2. a page for every year and month, and SUMMARY.md listing every entry
+///    with linked year headers
+pub const LAYOUT_VERSION: u32 = 2;
+
+/// One step of bringing a journal up to [`LAYOUT_VERSION`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    /// An entry moved to where the configured layout puts it
+    MoveEntry {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    /// An entry left alone because the one where the layout puts it exists too
+    Conflict {
+        path: PathBuf,
+        existing: PathBuf,
+    },
+    CreateYearReadme {
+        year: u32,
+    },
+    CreateMonthReadme {
+        year: u32,
+        month: u32,
+    },
+    /// SUMMARY.md regenerated from the entries, keeping its introduction
+    RewriteSummary,
+}
+
+impl Change {
+    pub fn describe(&self, layout: Layout) -> String {
+        match self {
+            Change::MoveEntry { from, to } => {
+                format!("Move {} to {}", from.display(), to.display())
+            }
+            Change::Conflict { path, existing } => format!(
+                "Skip {}: {} already exists, merge them by hand",
+                path.display(),
+                existing.display()
+            ),
+            Change::CreateYearReadme { year } => format!("Create {}", layout.year_page(*year)),
+            Change::CreateMonthReadme { year, month } => {
+                format!("Create {}", layout.month_page(*year, *month))
+            }
+            Change::RewriteSummary => "Rewrite SUMMARY.md".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Plan {
+    /// Version recorded by the last migration, or detected from the files
+    pub from_version: u32,
+    pub to_version: u32,
+    pub changes: Vec<Change>,
+}
+
+/// Date of the entry at `relative` if it isn't where `layout` puts it: an
+/// entry of another layout, or a `YYYY-MM-DD.md` file anywhere
+fn misplaced_date(relative: &str, layout: Layout) -> Option<NaiveDate> {
+    if layout.entry_date(relative).is_some() {
+        return None;
+    }
+    Layout::ALL
+        .iter()
+        .find_map(|other| other.entry_date(relative))
+        .or_else(|| {
+            let name = relative.rsplit('/').next()?.strip_suffix(".md")?;
+            NaiveDate::parse_from_str(name, "%Y-%m-%d").ok()
+        })
+}
+
+/// Entries in the journal directory and the year, month and week directories
+/// that aren't where `layout` puts them
+fn misplaced_entries(journal_dir: &Path, layout: Layout) -> Result<Vec<(NaiveDate, PathBuf)>> {
+    let mut found: Vec<(NaiveDate, PathBuf)> = filesystem::journal_files(journal_dir)?
+        .into_iter()
+        .filter_map(|(relative, path)| Some((misplaced_date(&relative, layout)?, path)))
+        .collect();
+    found.sort();
+    Ok(found)
+}
+
+fn read_version(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// What it takes to bring the journal up to [`LAYOUT_VERSION`]
+pub fn plan(config: &Config) -> Result<Plan> {
+    let journal_dir = &config.journal_dir;
+    let mut changes = Vec::new();
+
+    let misplaced = misplaced_entries(journal_dir, config.layout)?;
+    let mut dates: Vec<NaiveDate> = Vec::new();
+    for (date, path) in &misplaced {
+        let to = filesystem::get_entry_path(*date, journal_dir, config.layout);
+        // Two misplaced copies of a day: the first one moves
+        if to.exists() || dates.contains(date) {
+            changes.push(Change::Conflict {
+                path: path.clone(),
+                existing: to,
+            });
+        } else {
+            changes.push(Change::MoveEntry {
+                from: path.clone(),
+                to,
+            });
+            dates.push(*date);
+        }
+    }
+    dates.extend(
+        filesystem::list_entries(journal_dir, config.layout)?
+            .into_iter()
+            .map(|(date, _)| date),
+    );
+    dates.sort();
+    dates.dedup();
+
+    let mut months: Vec<(u32, u32)> = dates
+        .iter()
+        .map(|date| (date.year() as u32, date.month()))
+        .collect();
+    months.dedup();
+    let mut years: Vec<u32> = months.iter().map(|(year, _)| *year).collect();
+    years.dedup();
+    for year in years {
+        if !filesystem::get_year_path(year, journal_dir, config.layout).exists() {
+            changes.push(Change::CreateYearReadme { year });
+        }
+    }
+    for (year, month) in months {
+        if !filesystem::get_month_path(year, month, journal_dir, config.layout).exists() {
+            changes.push(Change::CreateMonthReadme { year, month });
+        }
+    }
+
+    let summary_path = journal_dir.join("SUMMARY.md");
+    if summary_path.exists() {
+        let current =
+            fs::read_to_string(&summary_path).map_err(JournalError::at_path(&summary_path))?;
+        if summary_outdated(&current, &dates, config.layout) {
+            changes.push(Change::RewriteSummary);
+        }
+    } else if !dates.is_empty() {
+        changes.push(Change::RewriteSummary);
+    }
+
+    let from_version =
+        read_version(&config.layout_version_path).unwrap_or(if misplaced.is_empty() {
+            if changes.is_empty() {
+                LAYOUT_VERSION
+            } else {
+                1
+            }
+        } else {
+            0
+        });
+
+    Ok(Plan {
+        from_version,
+        to_version: LAYOUT_VERSION,
+        changes,
+    })
+}
+
+/// Whether SUMMARY.md misses any of `dates`, links entries where `layout`
+/// doesn't put them or still has plain `# 2025` year headers
+fn summary_outdated(current: &str, dates: &[NaiveDate], layout: Layout) -> bool {
+    let plain_headers = current.lines().any(|line| {
+        line.trim()
+            .strip_prefix("# ")
+            .is_some_and(|year| year.parse::<u32>().is_ok())
+    });
+    let listed: Vec<NaiveDate> = Summary::from_content(Path::new("SUMMARY.md"), current, layout)
+        .tree()
+        .into_iter()
+        .flat_map(|year| year.months)
+        .flat_map(|month| month.days)
+        .collect();
+    let relinked = listed
+        .iter()
+        .any(|date| !current.contains(&format!("]({})", layout.entry(*date))));
+    plain_headers || relinked || dates.iter().any(|date| !listed.contains(date))
+}
+
+/// SUMMARY.md as it should be: the existing one (if any) with every date added
+fn summary_for(summary_path: &Path, dates: &[NaiveDate], layout: Layout) -> Result<String> {
+    let mut summary = if summary_path.exists() {
+        Summary::parse(summary_path, layout)?
+    } else {
+        Summary::from_content(
+            summary_path,
+            "# Summary\n\n[Introduction](README.md)\n\n---\n",
+            layout,
+        )
+    };
+    for date in dates {
+        summary.add_day_entry(*date);
+    }
+    Ok(summary.render())
+}
+
+/// Copy the journal directory to `.easy_journal/backups/` before migrating
+pub fn backup(config: &Config) -> Result<PathBuf> {
+    let backup_dir = config
+        .root
+        .join(".easy_journal/backups")
+        .join(format!("journal-{}", Local::now().format("%Y%m%d-%H%M%S")));
+    filesystem::copy_dir(&config.journal_dir, &backup_dir)?;
+    Ok(backup_dir)
+}
+
+/// Carry out `plan` and record the new layout version
+pub fn apply(config: &Config, plan: &Plan) -> Result<()> {
+    let journal_dir = &config.journal_dir;
+    let mut dates = Vec::new();
+    for change in &plan.changes {
+        match change {
+            Change::MoveEntry { from, to } => {
+                if let Some(parent) = to.parent() {
+                    fs::create_dir_all(parent).map_err(JournalError::at_path(parent))?;
+                }
+                fs::rename(from, to).map_err(JournalError::at_path(from))?;
+            }
+            Change::Conflict { .. } => {}
+            Change::CreateYearReadme { year } => {
+                filesystem::create_year_readme(*year, journal_dir, config)?;
+            }
+            Change::CreateMonthReadme { year, month } => {
+                filesystem::create_month_readme(*year, *month, journal_dir, config)?;
+            }
+            Change::RewriteSummary => {
+                if dates.is_empty() {
+                    dates = filesystem::list_entries(journal_dir, config.layout)?
+                        .into_iter()
+                        .map(|(date, _)| date)
+                        .collect();
+                }
+                let summary_path = journal_dir.join("SUMMARY.md");
+                write_atomic(
+                    &summary_path,
+                    summary_for(&summary_path, &dates, config.layout)?,
+                )?;
+            }
+        }
+    }
+
+    if let Some(parent) = config.layout_version_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent).map_err(JournalError::at_path(parent))?;
+    }
+    write_atomic(
+        &config.layout_version_path,
+        format!("{}\n", plan.to_version),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_and_apply() {
+        let dir = std::env::temp_dir().join(format!("easy_journal_migrate_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut config = Config::default();
+        config.set_root(dir.clone(), Path::new("journal"));
+        fs::create_dir_all(dir.join("journal/2025/12")).unwrap();
+        fs::write(dir.join("journal/2025-12-28.md"), "# old\n").unwrap();
+        fs::write(dir.join("journal/2025/12/2025-12-29.md"), "# dup\n").unwrap();
+        fs::write(dir.join("journal/2025/12/29.md"), "# new\n").unwrap();
+        fs::write(
+            dir.join("journal/SUMMARY.md"),
+            "# Summary\n\n[Introduction](README.md)\n\n---\n\n# 2025\n- [December](2025/12/README.md)\n  - [29 - Monday](2025/12/29.md)\n",
+        )
+        .unwrap();
+
+        let plan = plan(&config).unwrap();
+        assert_eq!(plan.from_version, 0);
+        assert_eq!(
+            plan.changes,
+            vec![
+                Change::MoveEntry {
+                    from: dir.join("journal/2025-12-28.md"),
+                    to: dir.join("journal/2025/12/28.md"),
+                },
+                Change::Conflict {
+                    path: dir.join("journal/2025/12/2025-12-29.md"),
+                    existing: dir.join("journal/2025/12/29.md"),
+                },
+                Change::CreateYearReadme { year: 2025 },
+                Change::CreateMonthReadme {
+                    year: 2025,
+                    month: 12
+                },
+                Change::RewriteSummary,
+            ]
+        );
+
+        apply(&config, &plan).unwrap();
+        let summary = fs::read_to_string(dir.join("journal/SUMMARY.md")).unwrap();
+        assert!(summary.contains("# [2025](2025/README.md)"));
+        assert!(summary.contains("  - [28 - Sunday](2025/12/28.md)"));
+
+        // Only the conflict is left, and the version is recorded
+        let again = super::plan(&config).unwrap();
+        assert_eq!(again.from_version, LAYOUT_VERSION);
+        assert_eq!(again.changes.len(), 1);
+
+        // Switching layouts moves the entries and relinks SUMMARY.md
+        config.layout = Layout::Flat;
+        let flat = super::plan(&config).unwrap();
+        assert!(flat.changes.contains(&Change::MoveEntry {
+            from: dir.join("journal/2025/12/28.md"),
+            to: dir.join("journal/2025-12-28.md"),
+        }));
+        assert!(flat.changes.contains(&Change::CreateMonthReadme {
+            year: 2025,
+            month: 12
+        }));
+        apply(&config, &flat).unwrap();
+        assert!(dir.join("journal/2025-12-29.md").exists());
+        assert!(dir.join("journal/2025-12.md").exists());
+        let summary = fs::read_to_string(dir.join("journal/SUMMARY.md")).unwrap();
+        assert!(summary.contains("  - [28 - Sunday](2025-12-28.md)"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,11 +1,81 @@
+pub mod apple_health;
+pub mod autocommit;
+pub mod backup;
+pub mod book;
+pub mod capture;
+pub mod conflicts;
+pub mod confluence;
+pub mod credentials;
+pub mod dates;
+pub mod dayone;
+pub mod dictate;
+pub mod digest;
+pub mod discord;
+pub mod doctor;
+pub mod email;
 pub mod entry;
+pub mod epub;
 pub mod filesystem;
-pub mod git_integrations;
+pub mod fitbit;
 pub mod github;
+pub mod github_actions;
 pub mod gitlab;
+pub mod goals;
 pub mod google_tasks;
+pub mod grep;
+pub mod habits;
+pub mod history;
+pub mod hooks;
+pub mod imap;
+pub mod import;
+pub mod index;
+pub mod jrnl;
+pub mod json_export;
+pub mod lastfm;
+pub mod lint;
+pub mod llm;
+pub mod lock;
+pub mod mastodon;
+pub mod matrix;
+pub mod migrate;
+pub mod navigation;
+pub mod notify;
 pub mod oauth;
+pub mod on_this_day;
+pub mod oncall;
+pub mod oura;
 pub mod parser;
+pub mod pdf;
+pub mod presets;
+pub mod prompts;
+pub mod proof;
+pub mod publish;
+pub mod query;
+pub mod read_later;
+pub mod redact;
 pub mod reminders;
+pub mod rollover;
+pub mod rss;
+pub mod s3;
+pub mod scheduler;
+pub mod search;
+pub mod secrets;
+pub mod sections;
+pub mod sentry;
+pub mod static_site;
+pub mod stats;
+pub mod storage;
+pub mod strava;
 pub mod summary;
+pub mod tasks;
+pub mod telegram;
 pub mod template;
+pub mod terminal;
+pub mod timer;
+pub mod toggl;
+pub mod trash;
+pub mod wakatime;
+pub mod weather;
+pub mod webdav;
+pub mod weekly_review;
+pub mod weekly_summary;
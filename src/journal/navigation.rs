@@ -0,0 +1,157 @@
+use chrono::NaiveDate;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::entry::JournalEntry;
+use crate::journal::filesystem::{self, Layout};
+
+/// Ends the previous/next line at the bottom of an entry, so it can be found
+/// and replaced
+pub const MARKER: &str = "<!-- nav -->";
+
+pub fn is_nav_line(line: &str) -> bool {
+    line.trim_end().ends_with(MARKER)
+}
+
+/// Link from one entry to another; every entry is as deep as the others
+pub(crate) fn link(date: NaiveDate, layout: Layout) -> String {
+    let path = layout.entry(date);
+    format!("{}{}", "../".repeat(path.matches('/').count()), path)
+}
+
+/// `[← 2025-12-28](../../2025/12/28.md) | [2025-12-30 →](../../2025/12/30.md) <!-- nav -->`
+pub fn nav_line(
+    previous: Option<NaiveDate>,
+    next: Option<NaiveDate>,
+    layout: Layout,
+) -> Option<String> {
+    let links: Vec<String> = [
+        previous.map(|date| format!("[← {}]({})", date, link(date, layout))),
+        next.map(|date| format!("[{} →]({})", date, link(date, layout))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    (!links.is_empty()).then(|| format!("{} {}", links.join(" | "), MARKER))
+}
+
+/// `content` without its navigation line
+pub fn strip(content: &str) -> String {
+    if !content.lines().any(is_nav_line) {
+        return content.to_string();
+    }
+    let mut stripped = content
+        .lines()
+        .filter(|line| !is_nav_line(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string();
+    stripped.push('\n');
+    stripped
+}
+
+/// `content` with its navigation line (re)placed at the bottom
+pub fn with_links(
+    content: &str,
+    previous: Option<NaiveDate>,
+    next: Option<NaiveDate>,
+    layout: Layout,
+) -> String {
+    let mut updated = strip(content);
+    if let Some(line) = nav_line(previous, next, layout) {
+        updated = format!("{}\n\n{}\n", updated.trim_end(), line);
+    }
+    updated
+}
+
+/// Apply `edit` to `content` without its navigation line, then put the line
+/// back at the bottom, so footer edits never land below it
+pub fn keep_at_bottom(content: &str, edit: impl FnOnce(&str) -> String) -> String {
+    let Some(line) = content.lines().find(|line| is_nav_line(line)) else {
+        return edit(content);
+    };
+    let edited = edit(&strip(content));
+    format!("{}\n\n{}\n", edited.trim_end(), line)
+}
+
+/// The existing entries right before and after `date`
+pub fn neighbours(
+    date: NaiveDate,
+    entries: &[(NaiveDate, PathBuf)],
+) -> (Option<NaiveDate>, Option<NaiveDate>) {
+    let previous = entries.iter().map(|(d, _)| *d).filter(|d| *d < date).max();
+    let next = entries.iter().map(|(d, _)| *d).filter(|d| *d > date).min();
+    (previous, next)
+}
+
+/// Point the entries around `date` at it (a new entry), saving the ones that change
+pub fn link_neighbours(config: &Config, date: NaiveDate) -> Result<()> {
+    let entries = filesystem::list_entries(&config.journal_dir, config.layout)?;
+    let (previous, next) = neighbours(date, &entries);
+    for neighbour in [previous, next].into_iter().flatten() {
+        let path = filesystem::get_entry_path(neighbour, &config.journal_dir, config.layout);
+        let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+        let (before, after) = neighbours(neighbour, &entries);
+        let updated = with_links(&content, before, after, config.layout);
+        if updated != content {
+            JournalEntry::save(neighbour, &updated, config)?;
+        }
+    }
+    Ok(())
+}
+
+/// Add or refresh the navigation line of every entry; returns the entries changed
+pub fn backfill(config: &Config) -> Result<Vec<NaiveDate>> {
+    let entries = filesystem::list_entries(&config.journal_dir, config.layout)?;
+    let mut changed = Vec::new();
+    for (date, path) in &entries {
+        let content = fs::read_to_string(path).map_err(JournalError::at_path(path))?;
+        let (previous, next) = neighbours(*date, &entries);
+        let updated = with_links(&content, previous, next, config.layout);
+        if updated != content {
+            JournalEntry::save(*date, &updated, config)?;
+            changed.push(*date);
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_links() {
+        let date = |day| NaiveDate::from_ymd_opt(2025, 12, day).unwrap();
+        let entries = vec![
+            (date(27), PathBuf::from("27.md")),
+            (date(29), PathBuf::from("29.md")),
+            (date(31), PathBuf::from("31.md")),
+        ];
+        assert_eq!(
+            neighbours(date(29), &entries),
+            (Some(date(27)), Some(date(31)))
+        );
+        assert_eq!(neighbours(date(31), &entries), (Some(date(29)), None));
+
+        let content = "# Monday\n\n---\n\n**Mood**: 7\n";
+        let linked = with_links(content, Some(date(27)), None, Layout::Nested);
+        assert_eq!(
+            linked,
+            "# Monday\n\n---\n\n**Mood**: 7\n\n[← 2025-12-27](../../2025/12/27.md) <!-- nav -->\n"
+        );
+        let relinked = with_links(&linked, Some(date(27)), Some(date(31)), Layout::Nested);
+        assert_eq!(
+            relinked,
+            "# Monday\n\n---\n\n**Mood**: 7\n\n[← 2025-12-27](../../2025/12/27.md) | [2025-12-31 →](../../2025/12/31.md) <!-- nav -->\n"
+        );
+        assert_eq!(strip(&relinked), content);
+        assert_eq!(
+            nav_line(Some(date(27)), None, Layout::Flat).unwrap(),
+            "[← 2025-12-27](2025-12-27.md) <!-- nav -->"
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use notify_rust::Notification;
+
+use crate::error::{JournalError, Result};
+
+/// Show a native desktop notification
+pub fn send(summary: &str, body: &str) -> Result<()> {
+    Notification::new()
+        .appname("easy_journal")
+        .summary(summary)
+        .body(body)
+        .show()
+        .map(|_| ())
+        .map_err(|e| JournalError::NotifyFailed(e.to_string()))
+}
+
+/// The "you haven't journaled today" reminder
+pub fn remind_to_journal() -> Result<()> {
+    send(
+        "You haven't journaled today",
+        "Run `easy_journal` to write today's entry.",
+    )
+}
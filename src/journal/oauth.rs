@@ -1,26 +1,173 @@
-use std::path::Path;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use yup_oauth2::authenticator::Authenticator;
+use yup_oauth2::authenticator_delegate::InstalledFlowDelegate;
+use yup_oauth2::storage::{TokenInfo, TokenStorage};
 use yup_oauth2::{InstalledFlowAuthenticator, InstalledFlowReturnMethod};
 
-use crate::error::{JournalError, Result};
+use crate::config::{FitbitConfig, GitHubConfig, GitLabConfig, StravaConfig};
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::secrets::{self, Sealed};
+
+/// One entry of the token file, same shape yup-oauth2 uses for its plaintext file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct StoredToken {
+    scopes: Vec<String>,
+    token: TokenInfo,
+}
+
+/// Token storage that keeps the token file encrypted at rest; a plaintext
+/// file left by older versions is encrypted the first time it is opened
+pub struct EncryptedTokenStorage {
+    path: PathBuf,
+    passphrase: Option<String>,
+    tokens: Mutex<Vec<StoredToken>>,
+}
+
+impl EncryptedTokenStorage {
+    pub fn open(path: &Path, passphrase: Option<&str>) -> Result<Self> {
+        let storage = Self {
+            path: path.to_path_buf(),
+            passphrase: passphrase.map(String::from),
+            tokens: Mutex::new(Vec::new()),
+        };
+        if !path.exists() {
+            return Ok(storage);
+        }
+
+        let content = std::fs::read(path).map_err(|e| JournalError::FileIo {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        let tokens = if let Ok(sealed) = serde_json::from_slice::<Sealed>(&content) {
+            let plaintext = secrets::open(&sealed, passphrase)?;
+            serde_json::from_slice(&plaintext)
+                .map_err(|e| JournalError::SecretsFailed(format!("Invalid token data: {}", e)))?
+        } else if let Ok(tokens) = serde_json::from_slice::<Vec<StoredToken>>(&content) {
+            storage.save(&tokens)?;
+            tracing::info!(path = %path.display(), "Encrypted plaintext token file");
+            tokens
+        } else {
+            return Err(JournalError::SecretsFailed(format!(
+                "{} is neither an encrypted nor a plaintext token file",
+                path.display()
+            )));
+        };
+
+        Ok(Self {
+            tokens: Mutex::new(tokens),
+            ..storage
+        })
+    }
+
+    /// The stored tokens with their scopes
+    pub fn into_tokens(self) -> Vec<(Vec<String>, TokenInfo)> {
+        self.tokens
+            .into_inner()
+            .into_iter()
+            .map(|stored| (stored.scopes, stored.token))
+            .collect()
+    }
+
+    fn save(&self, tokens: &[StoredToken]) -> Result<()> {
+        let plaintext = serde_json::to_vec(tokens).map_err(|e| {
+            JournalError::SecretsFailed(format!("Failed to serialize tokens: {}", e))
+        })?;
+        secrets::write_sealed(&self.path, &plaintext, self.passphrase.as_deref())
+    }
+}
+
+#[async_trait]
+impl TokenStorage for EncryptedTokenStorage {
+    async fn set(&self, scopes: &[&str], token: TokenInfo) -> anyhow::Result<()> {
+        let mut tokens = self.tokens.lock().await;
+        let mut scopes: Vec<String> = scopes.iter().map(|s| s.to_string()).collect();
+        scopes.sort();
+        match tokens.iter_mut().find(|stored| stored.scopes == scopes) {
+            Some(stored) => stored.token = token,
+            None => tokens.push(StoredToken { scopes, token }),
+        }
+        self.save(&tokens)?;
+        Ok(())
+    }
+
+    async fn get(&self, scopes: &[&str]) -> Option<TokenInfo> {
+        let tokens = self.tokens.lock().await;
+        tokens
+            .iter()
+            .find(|stored| scopes.iter().all(|s| stored.scopes.iter().any(|t| t == s)))
+            .map(|stored| stored.token.clone())
+    }
+}
+
+/// Scope the Google Tasks section uses
+const GOOGLE_TASKS_SCOPE: &str = "https://www.googleapis.com/auth/tasks.readonly";
+
+/// Refuses to start the browser sign-in, so a failed refresh during a fetch
+/// returns an error instead of waiting for a redirect that never comes
+struct NoInteractiveSignIn;
+
+impl InstalledFlowDelegate for NoInteractiveSignIn {
+    fn present_user_url<'a>(
+        &'a self,
+        _url: &'a str,
+        _need_code: bool,
+    ) -> Pin<Box<dyn Future<Output = std::result::Result<String, String>> + Send + 'a>> {
+        Box::pin(async {
+            Err("Google sign-in required; run `easy_journal auth google`".to_string())
+        })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct GoogleRefreshResponse {
+    access_token: String,
+    expires_in: Option<i64>,
+    id_token: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OAuthErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// The `error_description` (or `error`) of an OAuth error body when the grant
+/// itself is no longer valid, i.e. the refresh token expired or was revoked
+fn invalid_grant(body: &str) -> Option<String> {
+    let error: OAuthErrorResponse = serde_json::from_str(body).ok()?;
+    (error.error == "invalid_grant").then(|| error.error_description.unwrap_or(error.error))
+}
+
+fn google_secret(client_id: String, client_secret: String) -> yup_oauth2::ApplicationSecret {
+    yup_oauth2::ApplicationSecret {
+        client_id,
+        client_secret,
+        auth_uri: "https://accounts.google.com/o/oauth2/auth".to_string(),
+        token_uri: "https://oauth2.googleapis.com/token".to_string(),
+        ..Default::default()
+    }
+}
 
 /// Run OAuth flow and store refresh token
 pub async fn authenticate_google(
     client_id: String,
     client_secret: String,
     token_storage_path: &Path,
+    passphrase: Option<&str>,
 ) -> Result<()> {
-    let app_secret = yup_oauth2::ApplicationSecret {
-        client_id,
-        client_secret,
-        auth_uri: "https://accounts.google.com/o/oauth2/auth".to_string(),
-        token_uri: "https://oauth2.googleapis.com/token".to_string(),
-        ..Default::default()
-    };
+    let app_secret = google_secret(client_id, client_secret);
+    let storage = EncryptedTokenStorage::open(token_storage_path, passphrase)?;
 
     let auth =
         InstalledFlowAuthenticator::builder(app_secret, InstalledFlowReturnMethod::HTTPRedirect)
-            .persist_tokens_to_disk(token_storage_path)
+            .with_storage(Box::new(storage))
             .build()
             .await
             .map_err(|e| {
@@ -28,38 +175,1108 @@ pub async fn authenticate_google(
             })?;
 
     // Request scope for Google Tasks (read-only)
-    auth.token(&["https://www.googleapis.com/auth/tasks.readonly"])
+    auth.token(&[GOOGLE_TASKS_SCOPE])
         .await
         .map_err(|e| JournalError::OAuthFailed(format!("Failed to get token: {}", e)))?;
 
-    println!(
-        "✅ Authentication successful! Tokens stored at: {}",
+    tracing::info!(
+        "Authentication successful, tokens stored encrypted at {}",
         token_storage_path.display()
     );
     Ok(())
 }
 
-/// Load existing authenticator from stored tokens
+/// Load existing authenticator from stored tokens, decrypting them transparently
 pub async fn load_authenticator(
     client_id: String,
     client_secret: String,
     token_storage_path: &Path,
+    passphrase: Option<&str>,
 ) -> Result<
     Authenticator<
         yup_oauth2::hyper_rustls::HttpsConnector<yup_oauth2::hyper::client::HttpConnector>,
     >,
 > {
-    let app_secret = yup_oauth2::ApplicationSecret {
-        client_id,
-        client_secret,
-        auth_uri: "https://accounts.google.com/o/oauth2/auth".to_string(),
-        token_uri: "https://oauth2.googleapis.com/token".to_string(),
-        ..Default::default()
-    };
+    let app_secret = google_secret(client_id, client_secret);
+    let storage = EncryptedTokenStorage::open(token_storage_path, passphrase)?;
 
     InstalledFlowAuthenticator::builder(app_secret, InstalledFlowReturnMethod::HTTPRedirect)
-        .persist_tokens_to_disk(token_storage_path)
+        .with_storage(Box::new(storage))
+        .flow_delegate(Box::new(NoInteractiveSignIn))
         .build()
         .await
         .map_err(|e| JournalError::OAuthFailed(format!("Failed to load authenticator: {}", e)))
 }
+
+/// Refresh the stored Google access token if it has expired.
+///
+/// yup-oauth2 treats any failed refresh as a reason to start the browser
+/// sign-in, so this runs first to tell an expired or revoked refresh token
+/// (`ReauthRequired`) apart from network trouble.
+pub async fn refresh_google_token(
+    client_id: &str,
+    client_secret: &str,
+    token_storage_path: &Path,
+    passphrase: Option<&str>,
+) -> Result<()> {
+    let storage = EncryptedTokenStorage::open(token_storage_path, passphrase)?;
+    let token = storage.get(&[GOOGLE_TASKS_SCOPE]).await.ok_or_else(|| {
+        JournalError::ReauthRequired("no stored Google token for the Tasks scope".to_string())
+    })?;
+    if !token.is_expired() {
+        return Ok(());
+    }
+    let refresh_token = token.refresh_token.clone().ok_or_else(|| {
+        JournalError::ReauthRequired(
+            "the Google access token expired and there is no refresh token".to_string(),
+        )
+    })?;
+
+    tracing::debug!("Refreshing Google access token");
+    let response = http_client()?
+        .post("https://oauth2.googleapis.com/token")
+        .form(&[
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("refresh_token", refresh_token.as_str()),
+            ("grant_type", "refresh_token"),
+        ])
+        .send()
+        .await
+        .map_err(|e| JournalError::GoogleTasksFailed(format!("Token refresh failed: {}", e)))?;
+
+    if response.status() == reqwest::StatusCode::BAD_REQUEST
+        || response.status() == reqwest::StatusCode::UNAUTHORIZED
+    {
+        let status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+        return Err(match invalid_grant(&body) {
+            Some(reason) => JournalError::ReauthRequired(format!(
+                "the Google refresh token is no longer valid ({})",
+                reason
+            )),
+            None => JournalError::HttpStatus {
+                provider: "Google".to_string(),
+                status,
+                message: body.chars().take(200).collect(),
+            },
+        });
+    }
+    let reply: GoogleRefreshResponse = check_response(response, "Google")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::GoogleTasksFailed(format!("Failed to parse token: {}", e)))?;
+
+    let refreshed = TokenInfo {
+        access_token: Some(reply.access_token),
+        refresh_token: Some(refresh_token),
+        expires_at: reply
+            .expires_in
+            .map(|secs| time::OffsetDateTime::now_utc() + time::Duration::seconds(secs)),
+        id_token: reply.id_token.or(token.id_token),
+    };
+    storage
+        .set(&[GOOGLE_TASKS_SCOPE], refreshed)
+        .await
+        .map_err(|e| JournalError::SecretsFailed(e.to_string()))
+}
+
+/// Revoke a Google access or refresh token (revoking the refresh token ends the grant)
+pub async fn revoke_google_token(token: &str) -> Result<()> {
+    let response = http_client()?
+        .post("https://oauth2.googleapis.com/revoke")
+        .form(&[("token", token)])
+        .send()
+        .await
+        .map_err(|e| JournalError::OAuthFailed(format!("Google revoke request failed: {}", e)))?;
+    check_response(response, "Google").await?;
+    Ok(())
+}
+
+/// Scopes the GitHub section needs (same as a classic personal access token)
+const GITHUB_SCOPES: &str = "repo read:org";
+
+/// Codes returned when starting GitHub's device flow
+#[derive(Deserialize, Debug)]
+pub struct DeviceCode {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+#[derive(Deserialize, Debug)]
+struct DeviceTokenResponse {
+    access_token: Option<String>,
+    scope: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+    interval: Option<u64>,
+}
+
+/// A GitHub token from the device flow, as stored on disk
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GitHubToken {
+    pub access_token: String,
+    /// Granted scopes, comma separated
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+/// Store a token encrypted, like the Google tokens
+fn save_token<T: Serialize>(passphrase: Option<&str>, path: &Path, token: &T) -> Result<()> {
+    let plaintext = serde_json::to_vec(token)
+        .map_err(|e| JournalError::SecretsFailed(format!("Failed to serialize token: {}", e)))?;
+    secrets::write_sealed(path, &plaintext, passphrase)
+}
+
+/// The token stored by `save_token` at `path`, if any
+fn load_token<T: DeserializeOwned>(
+    provider: &str,
+    passphrase: Option<&str>,
+    path: &Path,
+) -> Result<Option<T>> {
+    let Some(plaintext) = secrets::read_sealed(path, passphrase)? else {
+        return Ok(None);
+    };
+    serde_json::from_slice(&plaintext)
+        .map(Some)
+        .map_err(|e| JournalError::SecretsFailed(format!("Invalid {} token data: {}", provider, e)))
+}
+
+/// A failed token refresh: providers answer an expired, revoked or already
+/// used refresh token with 400/401, which only signing in again fixes
+fn refresh_error(provider: &str, e: JournalError) -> JournalError {
+    match e {
+        JournalError::HttpStatus {
+            status: 400 | 401, ..
+        } => JournalError::ReauthRequired(format!(
+            "the {} refresh token is no longer valid: {}",
+            provider, e
+        )),
+        JournalError::HttpStatus { .. } => {
+            JournalError::OAuthFailed(format!("{} token refresh failed: {}", provider, e))
+        }
+        e => e,
+    }
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::OAuthFailed(format!("Failed to build HTTP client: {}", e)))
+}
+
+fn github_client_id(config: &GitHubConfig) -> Result<&str> {
+    config.client_id.as_deref().ok_or_else(|| {
+        JournalError::OAuthConfigMissing(
+            "GITHUB_CLIENT_ID not set. Register a GitHub OAuth app with device flow enabled \
+             and set its client ID, or use GITHUB_TOKEN instead."
+                .to_string(),
+        )
+    })
+}
+
+/// Start GitHub's device flow; the user enters `user_code` at `verification_uri`
+pub async fn github_device_code(config: &GitHubConfig) -> Result<DeviceCode> {
+    let client_id = github_client_id(config)?;
+    let response = http_client()?
+        .post("https://github.com/login/device/code")
+        .header("Accept", "application/json")
+        .form(&[("client_id", client_id), ("scope", GITHUB_SCOPES)])
+        .send()
+        .await
+        .map_err(|e| {
+            JournalError::OAuthFailed(format!("GitHub device code request failed: {}", e))
+        })?;
+
+    check_response(response, "GitHub")
+        .await?
+        .json()
+        .await
+        .map_err(|e| {
+            JournalError::OAuthFailed(format!("Failed to parse GitHub device code: {}", e))
+        })
+}
+
+/// Poll until the user has approved the device code, then return the access token
+pub async fn github_poll_token(config: &GitHubConfig, device: &DeviceCode) -> Result<GitHubToken> {
+    let client_id = github_client_id(config)?;
+    let client = http_client()?;
+    let deadline = Instant::now() + Duration::from_secs(device.expires_in);
+    let mut interval = Duration::from_secs(device.interval.max(1));
+
+    while Instant::now() < deadline {
+        tokio::time::sleep(interval).await;
+
+        let response = client
+            .post("https://github.com/login/oauth/access_token")
+            .header("Accept", "application/json")
+            .form(&[
+                ("client_id", client_id),
+                ("device_code", device.device_code.as_str()),
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+            ])
+            .send()
+            .await
+            .map_err(|e| {
+                JournalError::OAuthFailed(format!("GitHub token request failed: {}", e))
+            })?;
+        let reply: DeviceTokenResponse = check_response(response, "GitHub")
+            .await?
+            .json()
+            .await
+            .map_err(|e| {
+                JournalError::OAuthFailed(format!("Failed to parse GitHub token: {}", e))
+            })?;
+
+        if let Some(access_token) = reply.access_token {
+            return Ok(GitHubToken {
+                access_token,
+                scope: reply.scope,
+            });
+        }
+        match reply.error.as_deref() {
+            Some("authorization_pending") => {}
+            Some("slow_down") => {
+                interval = Duration::from_secs(reply.interval.unwrap_or(interval.as_secs() + 5));
+            }
+            Some(error) => {
+                return Err(JournalError::OAuthFailed(format!(
+                    "GitHub authorization failed: {}",
+                    reply.error_description.as_deref().unwrap_or(error)
+                )));
+            }
+            None => {
+                return Err(JournalError::OAuthFailed(
+                    "GitHub returned neither a token nor an error".to_string(),
+                ));
+            }
+        }
+    }
+
+    Err(JournalError::OAuthFailed(
+        "GitHub device code expired before it was approved".to_string(),
+    ))
+}
+
+/// Store the token from `auth github`
+pub fn save_github_token(config: &GitHubConfig, token: &GitHubToken) -> Result<()> {
+    save_token(
+        config.token_passphrase.as_deref(),
+        &config.token_storage_path,
+        token,
+    )
+}
+
+/// The token stored by `auth github`, if any
+pub fn load_github_token(config: &GitHubConfig) -> Result<Option<GitHubToken>> {
+    load_token(
+        "GitHub",
+        config.token_passphrase.as_deref(),
+        &config.token_storage_path,
+    )
+}
+
+/// The GitHub token to use: GITHUB_TOKEN, else the one stored by `auth github`
+pub fn github_token(config: &GitHubConfig) -> Result<Option<String>> {
+    if let Some(token) = &config.token {
+        return Ok(Some(token.clone()));
+    }
+    Ok(load_github_token(config)?.map(|stored| stored.access_token))
+}
+
+/// Scope the GitLab section needs
+const GITLAB_SCOPES: &str = "read_api";
+
+/// Refresh GitLab access tokens this long before they expire
+const GITLAB_REFRESH_MARGIN_SECS: i64 = 60;
+
+/// How a GitLab request is authenticated
+#[derive(Debug, Clone, PartialEq)]
+pub enum GitLabAuth {
+    /// GITLAB_TOKEN (personal, project or group access token)
+    PrivateToken(String),
+    /// OAuth access token from `auth gitlab`
+    Bearer(String),
+}
+
+impl GitLabAuth {
+    pub fn apply(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            GitLabAuth::PrivateToken(token) => request.header("PRIVATE-TOKEN", token),
+            GitLabAuth::Bearer(token) => request.bearer_auth(token),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GitLabToken {
+    /// The GitLab instance the token belongs to
+    pub host: String,
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    /// Unix timestamp; `None` when the token doesn't expire
+    pub expires_at: Option<i64>,
+    /// Granted scopes, space separated
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GitLabTokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: Option<i64>,
+    scope: Option<String>,
+}
+
+/// An authorization code flow (with PKCE) waiting for the browser redirect
+/// to http://127.0.0.1:<port>/callback
+pub struct BrowserLogin {
+    /// Open this URL in the browser to approve access
+    pub url: String,
+    /// Redirect URI the code was issued for, needed again for the token request
+    pub redirect_uri: String,
+    /// PKCE verifier to send with the token request
+    pub verifier: String,
+    provider: &'static str,
+    state: String,
+    listener: tokio::net::TcpListener,
+}
+
+fn gitlab_host(config: &GitLabConfig) -> &str {
+    config.host.trim_end_matches('/')
+}
+
+/// S256 code challenge for a PKCE verifier
+fn pkce_challenge(verifier: &str) -> String {
+    use base64::Engine;
+    use sha2::{Digest, Sha256};
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// Pull `code` out of the redirect's request line, checking `state`
+fn parse_callback(provider: &str, request_line: &str, state: &str) -> Result<String> {
+    let target = request_line.split_whitespace().nth(1).unwrap_or_default();
+    let url = reqwest::Url::parse(&format!("http://127.0.0.1{}", target))
+        .map_err(|e| JournalError::OAuthFailed(format!("Invalid {} redirect: {}", provider, e)))?;
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    if let Some(error) = param("error") {
+        let description = param("error_description").unwrap_or(error);
+        return Err(JournalError::OAuthFailed(format!(
+            "{} authorization failed: {}",
+            provider, description
+        )));
+    }
+    if param("state").as_deref() != Some(state) {
+        return Err(JournalError::OAuthFailed(format!(
+            "{} redirect has a mismatched state",
+            provider
+        )));
+    }
+    param("code").ok_or_else(|| {
+        JournalError::OAuthFailed(format!(
+            "{} redirect is missing the authorization code",
+            provider
+        ))
+    })
+}
+
+impl BrowserLogin {
+    /// Listen for the redirect on `port`, then build the authorization URL
+    pub async fn start(
+        provider: &'static str,
+        authorize_url: &str,
+        client_id: &str,
+        scopes: &str,
+        port: u16,
+    ) -> Result<Self> {
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+            .await
+            .map_err(|e| {
+                JournalError::OAuthFailed(format!(
+                    "Cannot listen for the {} redirect on port {}: {}",
+                    provider, port, e
+                ))
+            })?;
+
+        let redirect_uri = format!("http://127.0.0.1:{}/callback", port);
+        let verifier = secrets::random_token::<32>()?;
+        let state = secrets::random_token::<16>()?;
+        let url = reqwest::Url::parse_with_params(
+            authorize_url,
+            &[
+                ("client_id", client_id),
+                ("redirect_uri", redirect_uri.as_str()),
+                ("response_type", "code"),
+                ("scope", scopes),
+                ("state", state.as_str()),
+                ("code_challenge", pkce_challenge(&verifier).as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(|e| {
+            JournalError::OAuthFailed(format!("Invalid {} authorization URL: {}", provider, e))
+        })?;
+
+        Ok(Self {
+            url: url.to_string(),
+            redirect_uri,
+            verifier,
+            provider,
+            state,
+            listener,
+        })
+    }
+
+    /// Wait for the browser redirect and return the authorization code
+    pub async fn wait_for_code(&self) -> Result<String> {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let provider = self.provider;
+        let (stream, _) = self.listener.accept().await.map_err(|e| {
+            JournalError::OAuthFailed(format!(
+                "Failed to receive the {} redirect: {}",
+                provider, e
+            ))
+        })?;
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).await.map_err(|e| {
+            JournalError::OAuthFailed(format!("Failed to read the {} redirect: {}", provider, e))
+        })?;
+
+        let code = parse_callback(provider, &request_line, &self.state);
+        let body = match &code {
+            Ok(_) => format!("Signed in to {}. You can close this window.", provider),
+            Err(_) => format!(
+                "{} sign-in failed. Check the terminal for details.",
+                provider
+            ),
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        // The browser page is a courtesy; the terminal reports the outcome
+        let _ = reader.get_mut().write_all(response.as_bytes()).await;
+        code
+    }
+}
+
+/// Start GitLab's authorization code flow; listens for the redirect before
+/// the URL is handed out
+pub async fn gitlab_login(config: &GitLabConfig) -> Result<BrowserLogin> {
+    let client_id = config.client_id.as_deref().ok_or_else(|| {
+        JournalError::OAuthConfigMissing(
+            "GITLAB_CLIENT_ID not set. Register an application in GitLab with the read_api scope \
+             and set its application ID, or use GITLAB_TOKEN instead."
+                .to_string(),
+        )
+    })?;
+    BrowserLogin::start(
+        "GitLab",
+        &format!("{}/oauth/authorize", gitlab_host(config)),
+        client_id,
+        GITLAB_SCOPES,
+        config.redirect_port,
+    )
+    .await
+}
+
+/// Wait for the GitLab redirect, exchange the code and store the tokens
+pub async fn gitlab_complete(config: &GitLabConfig, login: BrowserLogin) -> Result<()> {
+    let code = login.wait_for_code().await?;
+    let token = request_gitlab_token(
+        config,
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", login.redirect_uri.as_str()),
+            ("code_verifier", login.verifier.as_str()),
+        ],
+    )
+    .await?;
+    save_gitlab_token(config, &token)
+}
+
+async fn request_gitlab_token(
+    config: &GitLabConfig,
+    params: &[(&str, &str)],
+) -> Result<GitLabToken> {
+    let client_id = config
+        .client_id
+        .as_deref()
+        .ok_or_else(|| JournalError::OAuthConfigMissing("GITLAB_CLIENT_ID not set".to_string()))?;
+    let mut form = vec![("client_id", client_id)];
+    if let Some(secret) = &config.client_secret {
+        form.push(("client_secret", secret));
+    }
+    form.extend_from_slice(params);
+
+    let response = http_client()?
+        .post(format!("{}/oauth/token", gitlab_host(config)))
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| JournalError::OAuthFailed(format!("GitLab token request failed: {}", e)))?;
+    let reply: GitLabTokenResponse = check_response(response, "GitLab")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::OAuthFailed(format!("Failed to parse GitLab token: {}", e)))?;
+
+    Ok(GitLabToken {
+        host: gitlab_host(config).to_string(),
+        access_token: reply.access_token,
+        refresh_token: reply.refresh_token,
+        expires_at: reply
+            .expires_in
+            .map(|secs| chrono::Utc::now().timestamp() + secs),
+        scope: reply.scope,
+    })
+}
+
+fn save_gitlab_token(config: &GitLabConfig, token: &GitLabToken) -> Result<()> {
+    save_token(
+        config.token_passphrase.as_deref(),
+        &config.token_storage_path,
+        token,
+    )
+}
+
+/// The token stored by `auth gitlab` for the configured host, if any
+pub fn load_gitlab_token(config: &GitLabConfig) -> Result<Option<GitLabToken>> {
+    let Some(token) = load_token::<GitLabToken>(
+        "GitLab",
+        config.token_passphrase.as_deref(),
+        &config.token_storage_path,
+    )?
+    else {
+        return Ok(None);
+    };
+
+    if token.host != gitlab_host(config) {
+        tracing::warn!(
+            stored = token.host.as_str(),
+            host = gitlab_host(config),
+            "Stored GitLab token is for another host; run `easy_journal auth gitlab` again"
+        );
+        return Ok(None);
+    }
+    Ok(Some(token))
+}
+
+/// Revoke the stored GitLab token on the server
+pub async fn revoke_gitlab_token(config: &GitLabConfig, token: &GitLabToken) -> Result<()> {
+    let client_id = config.client_id.as_deref().unwrap_or_default();
+    let response = http_client()?
+        .post(format!("{}/oauth/revoke", gitlab_host(config)))
+        .form(&[
+            ("client_id", client_id),
+            ("token", token.access_token.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(|e| JournalError::OAuthFailed(format!("GitLab revoke request failed: {}", e)))?;
+    check_response(response, "GitLab").await?;
+    Ok(())
+}
+
+/// How to authenticate GitLab requests: GITLAB_TOKEN, else the token stored
+/// by `auth gitlab`, refreshed first when it is about to expire
+pub async fn gitlab_auth(config: &GitLabConfig) -> Result<Option<GitLabAuth>> {
+    if let Some(token) = &config.token {
+        return Ok(Some(GitLabAuth::PrivateToken(token.clone())));
+    }
+    let Some(token) = load_gitlab_token(config)? else {
+        return Ok(None);
+    };
+
+    let expiring = token
+        .expires_at
+        .is_some_and(|at| at - chrono::Utc::now().timestamp() < GITLAB_REFRESH_MARGIN_SECS);
+    if !expiring {
+        return Ok(Some(GitLabAuth::Bearer(token.access_token)));
+    }
+
+    let refresh_token = token.refresh_token.ok_or_else(|| {
+        JournalError::OAuthFailed("GitLab token expired and cannot be refreshed".to_string())
+    })?;
+    tracing::debug!("Refreshing GitLab access token");
+    let redirect_uri = format!("http://127.0.0.1:{}/callback", config.redirect_port);
+    let refreshed = request_gitlab_token(
+        config,
+        &[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token.as_str()),
+            ("redirect_uri", redirect_uri.as_str()),
+        ],
+    )
+    .await
+    .map_err(|e| refresh_error("GitLab", e))?;
+    save_gitlab_token(config, &refreshed)?;
+    Ok(Some(GitLabAuth::Bearer(refreshed.access_token)))
+}
+
+const FITBIT_AUTHORIZE_URL: &str = "https://www.fitbit.com/oauth2/authorize";
+const FITBIT_TOKEN_URL: &str = "https://api.fitbit.com/oauth2/token";
+const FITBIT_REVOKE_URL: &str = "https://api.fitbit.com/oauth2/revoke";
+const FITBIT_SCOPES: &str = "sleep activity";
+
+/// Refresh Fitbit access tokens this long before they expire
+const FITBIT_REFRESH_MARGIN_SECS: i64 = 60;
+
+/// A Fitbit token as stored on disk; refresh tokens are single-use, so every
+/// refresh stores the new pair
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FitbitToken {
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Unix timestamp
+    pub expires_at: i64,
+    /// Granted scopes, space separated
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct FitbitTokenResponse {
+    access_token: String,
+    refresh_token: String,
+    expires_in: i64,
+    scope: Option<String>,
+}
+
+fn fitbit_client_id(config: &FitbitConfig) -> Result<&str> {
+    config.client_id.as_deref().ok_or_else(|| {
+        JournalError::OAuthConfigMissing(
+            "FITBIT_CLIENT_ID not set. Register an application at dev.fitbit.com first."
+                .to_string(),
+        )
+    })
+}
+
+/// Token and revoke requests authenticate with Basic auth for "Server"
+/// applications and with `client_id` in the form otherwise
+fn fitbit_basic_auth(
+    request: reqwest::RequestBuilder,
+    config: &FitbitConfig,
+) -> Result<reqwest::RequestBuilder> {
+    let client_id = fitbit_client_id(config)?;
+    Ok(match &config.client_secret {
+        Some(secret) => request.basic_auth(client_id, Some(secret)),
+        None => request,
+    })
+}
+
+/// Start Fitbit's authorization code flow; listens for the redirect before
+/// the URL is handed out
+pub async fn fitbit_login(config: &FitbitConfig) -> Result<BrowserLogin> {
+    BrowserLogin::start(
+        "Fitbit",
+        FITBIT_AUTHORIZE_URL,
+        fitbit_client_id(config)?,
+        FITBIT_SCOPES,
+        config.redirect_port,
+    )
+    .await
+}
+
+/// Wait for the Fitbit redirect, exchange the code and store the tokens
+pub async fn fitbit_complete(config: &FitbitConfig, login: BrowserLogin) -> Result<()> {
+    let code = login.wait_for_code().await?;
+    let token = request_fitbit_token(
+        config,
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", login.redirect_uri.as_str()),
+            ("code_verifier", login.verifier.as_str()),
+        ],
+    )
+    .await?;
+    save_fitbit_token(config, &token)
+}
+
+async fn request_fitbit_token(
+    config: &FitbitConfig,
+    params: &[(&str, &str)],
+) -> Result<FitbitToken> {
+    let mut form = vec![("client_id", fitbit_client_id(config)?)];
+    form.extend_from_slice(params);
+
+    let response = fitbit_basic_auth(http_client()?.post(FITBIT_TOKEN_URL), config)?
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| JournalError::OAuthFailed(format!("Fitbit token request failed: {}", e)))?;
+    let reply: FitbitTokenResponse = check_response(response, "Fitbit")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::OAuthFailed(format!("Failed to parse Fitbit token: {}", e)))?;
+
+    Ok(FitbitToken {
+        access_token: reply.access_token,
+        refresh_token: reply.refresh_token,
+        expires_at: chrono::Utc::now().timestamp() + reply.expires_in,
+        scope: reply.scope,
+    })
+}
+
+fn save_fitbit_token(config: &FitbitConfig, token: &FitbitToken) -> Result<()> {
+    save_token(
+        config.token_passphrase.as_deref(),
+        &config.token_storage_path,
+        token,
+    )
+}
+
+/// The token stored by `auth fitbit`, if any
+pub fn load_fitbit_token(config: &FitbitConfig) -> Result<Option<FitbitToken>> {
+    load_token(
+        "Fitbit",
+        config.token_passphrase.as_deref(),
+        &config.token_storage_path,
+    )
+}
+
+/// The access token stored by `auth fitbit`, refreshing (and storing) it when it is about to expire
+pub async fn fitbit_access_token(config: &FitbitConfig) -> Result<Option<String>> {
+    let Some(token) = load_fitbit_token(config)? else {
+        return Ok(None);
+    };
+    if token.expires_at - chrono::Utc::now().timestamp() > FITBIT_REFRESH_MARGIN_SECS {
+        return Ok(Some(token.access_token));
+    }
+
+    tracing::debug!("Refreshing Fitbit access token");
+    let refreshed = request_fitbit_token(
+        config,
+        &[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", token.refresh_token.as_str()),
+        ],
+    )
+    .await
+    .map_err(|e| refresh_error("Fitbit", e))?;
+    save_fitbit_token(config, &refreshed)?;
+    Ok(Some(refreshed.access_token))
+}
+
+/// Revoke the stored Fitbit token
+pub async fn revoke_fitbit_token(config: &FitbitConfig, token: &FitbitToken) -> Result<()> {
+    let response = fitbit_basic_auth(http_client()?.post(FITBIT_REVOKE_URL), config)?
+        .form(&[
+            ("client_id", fitbit_client_id(config)?),
+            ("token", token.refresh_token.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(|e| JournalError::OAuthFailed(format!("Fitbit revoke request failed: {}", e)))?;
+    check_response(response, "Fitbit").await?;
+    Ok(())
+}
+
+const STRAVA_AUTHORIZE_URL: &str = "https://www.strava.com/oauth/authorize";
+const STRAVA_TOKEN_URL: &str = "https://www.strava.com/oauth/token";
+const STRAVA_DEAUTHORIZE_URL: &str = "https://www.strava.com/oauth/deauthorize";
+const STRAVA_SCOPES: &str = "activity:read_all";
+
+/// Refresh Strava access tokens this long before they expire
+const STRAVA_REFRESH_MARGIN_SECS: i64 = 60;
+
+/// A Strava token as stored on disk
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StravaToken {
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Unix timestamp
+    pub expires_at: i64,
+    /// Granted scopes, comma separated
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StravaTokenResponse {
+    access_token: String,
+    refresh_token: String,
+    expires_at: i64,
+}
+
+/// Strava requires the client secret even for the code exchange
+fn strava_client(config: &StravaConfig) -> Result<(&str, &str)> {
+    match (config.client_id.as_deref(), config.client_secret.as_deref()) {
+        (Some(id), Some(secret)) => Ok((id, secret)),
+        _ => Err(JournalError::OAuthConfigMissing(
+            "STRAVA_CLIENT_ID and STRAVA_CLIENT_SECRET not set. Create an API application at strava.com/settings/api first."
+                .to_string(),
+        )),
+    }
+}
+
+/// Start Strava's authorization code flow; listens for the redirect before
+/// the URL is handed out
+pub async fn strava_login(config: &StravaConfig) -> Result<BrowserLogin> {
+    let (client_id, _) = strava_client(config)?;
+    BrowserLogin::start(
+        "Strava",
+        STRAVA_AUTHORIZE_URL,
+        client_id,
+        STRAVA_SCOPES,
+        config.redirect_port,
+    )
+    .await
+}
+
+/// Wait for the Strava redirect, exchange the code and store the tokens
+pub async fn strava_complete(config: &StravaConfig, login: BrowserLogin) -> Result<()> {
+    let code = login.wait_for_code().await?;
+    let token = request_strava_token(
+        config,
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+        ],
+        // Strava reports the granted scopes on the redirect, not in the token reply
+        Some(STRAVA_SCOPES.to_string()),
+    )
+    .await?;
+    save_strava_token(config, &token)
+}
+
+async fn request_strava_token(
+    config: &StravaConfig,
+    params: &[(&str, &str)],
+    scope: Option<String>,
+) -> Result<StravaToken> {
+    let (client_id, client_secret) = strava_client(config)?;
+    let mut form = vec![("client_id", client_id), ("client_secret", client_secret)];
+    form.extend_from_slice(params);
+
+    let response = http_client()?
+        .post(STRAVA_TOKEN_URL)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| JournalError::OAuthFailed(format!("Strava token request failed: {}", e)))?;
+    let reply: StravaTokenResponse = check_response(response, "Strava")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::OAuthFailed(format!("Failed to parse Strava token: {}", e)))?;
+
+    Ok(StravaToken {
+        access_token: reply.access_token,
+        refresh_token: reply.refresh_token,
+        expires_at: reply.expires_at,
+        scope,
+    })
+}
+
+fn save_strava_token(config: &StravaConfig, token: &StravaToken) -> Result<()> {
+    save_token(
+        config.token_passphrase.as_deref(),
+        &config.token_storage_path,
+        token,
+    )
+}
+
+/// The token stored by `auth strava`, if any
+pub fn load_strava_token(config: &StravaConfig) -> Result<Option<StravaToken>> {
+    load_token(
+        "Strava",
+        config.token_passphrase.as_deref(),
+        &config.token_storage_path,
+    )
+}
+
+/// The access token stored by `auth strava`, refreshing (and storing) it when it is about to expire
+pub async fn strava_access_token(config: &StravaConfig) -> Result<Option<String>> {
+    let Some(token) = load_strava_token(config)? else {
+        return Ok(None);
+    };
+    if token.expires_at - chrono::Utc::now().timestamp() > STRAVA_REFRESH_MARGIN_SECS {
+        return Ok(Some(token.access_token));
+    }
+
+    tracing::debug!("Refreshing Strava access token");
+    let refreshed = request_strava_token(
+        config,
+        &[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", token.refresh_token.as_str()),
+        ],
+        token.scope,
+    )
+    .await
+    .map_err(|e| refresh_error("Strava", e))?;
+    save_strava_token(config, &refreshed)?;
+    Ok(Some(refreshed.access_token))
+}
+
+/// Revoke the app's access to the Strava account
+pub async fn revoke_strava_token(token: &StravaToken) -> Result<()> {
+    let response = http_client()?
+        .post(STRAVA_DEAUTHORIZE_URL)
+        .form(&[("access_token", token.access_token.as_str())])
+        .send()
+        .await
+        .map_err(|e| JournalError::OAuthFailed(format!("Strava revoke request failed: {}", e)))?;
+    check_response(response, "Strava").await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_plaintext_token_file_is_migrated() {
+        let dir = std::env::temp_dir().join(format!("ej_tokens_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tokens.json");
+        std::fs::write(
+            &path,
+            r#"[{"scopes":["a","b"],"token":{"access_token":"at","refresh_token":"refresh-secret","expires_at":null,"id_token":null}}]"#,
+        )
+        .unwrap();
+
+        let storage = EncryptedTokenStorage::open(&path, Some("secret")).unwrap();
+        let on_disk = std::fs::read_to_string(&path).unwrap();
+        assert!(!on_disk.contains("refresh-secret"));
+        assert!(serde_json::from_str::<Sealed>(&on_disk).is_ok());
+
+        let token = storage.get(&["a"]).await.unwrap();
+        assert_eq!(token.refresh_token.as_deref(), Some("refresh-secret"));
+        assert!(storage.get(&["c"]).await.is_none());
+
+        // Reopening decrypts what was written
+        let reopened = EncryptedTokenStorage::open(&path, Some("secret")).unwrap();
+        assert!(reopened.get(&["b"]).await.is_some());
+        assert!(EncryptedTokenStorage::open(&path, Some("wrong")).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_github_token_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("ej_github_token_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut config = GitHubConfig {
+            token: None,
+            enabled: true,
+            client_id: None,
+            token_storage_path: dir.join("github.json"),
+            token_passphrase: Some("secret".to_string()),
+            ci_repos: Vec::new(),
+        };
+
+        assert_eq!(github_token(&config).unwrap(), None);
+        let token = GitHubToken {
+            access_token: "gho_stored".to_string(),
+            scope: Some("repo,read:org".to_string()),
+        };
+        save_github_token(&config, &token).unwrap();
+        assert!(
+            !std::fs::read_to_string(&config.token_storage_path)
+                .unwrap()
+                .contains("gho_stored")
+        );
+        assert_eq!(load_github_token(&config).unwrap(), Some(token));
+        assert_eq!(
+            github_token(&config).unwrap().as_deref(),
+            Some("gho_stored")
+        );
+
+        // GITHUB_TOKEN wins over the stored token
+        config.token = Some("ghp_env".to_string());
+        assert_eq!(github_token(&config).unwrap().as_deref(), Some("ghp_env"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_grant_is_detected() {
+        assert_eq!(
+            invalid_grant(
+                r#"{"error":"invalid_grant","error_description":"Token has been expired or revoked."}"#
+            )
+            .as_deref(),
+            Some("Token has been expired or revoked.")
+        );
+        assert_eq!(invalid_grant(r#"{"error":"invalid_client"}"#), None);
+        assert_eq!(invalid_grant("Bad Request"), None);
+    }
+
+    #[test]
+    fn test_refresh_error() {
+        let status = |status| JournalError::HttpStatus {
+            provider: "Strava".to_string(),
+            status,
+            message: "Bad Request".to_string(),
+        };
+        assert!(matches!(
+            refresh_error("Strava", status(400)),
+            JournalError::ReauthRequired(_)
+        ));
+        assert!(matches!(
+            refresh_error("Strava", status(503)),
+            JournalError::OAuthFailed(_)
+        ));
+        assert!(matches!(
+            refresh_error("Strava", JournalError::OAuthFailed("offline".to_string())),
+            JournalError::OAuthFailed(message) if message == "offline"
+        ));
+    }
+
+    #[test]
+    fn test_pkce_challenge() {
+        // RFC 7636, appendix B
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_parse_gitlab_callback() {
+        assert_eq!(
+            parse_callback(
+                "GitLab",
+                "GET /callback?code=abc123&state=xyz HTTP/1.1\r\n",
+                "xyz"
+            )
+            .unwrap(),
+            "abc123"
+        );
+        assert!(
+            parse_callback(
+                "GitLab",
+                "GET /callback?code=abc123&state=other HTTP/1.1",
+                "xyz"
+            )
+            .is_err()
+        );
+
+        let denied = parse_callback(
+            "GitLab",
+            "GET /callback?error=access_denied&error_description=The+user+denied HTTP/1.1",
+            "xyz",
+        )
+        .unwrap_err();
+        assert!(denied.to_string().contains("The user denied"));
+    }
+}
@@ -0,0 +1,138 @@
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::{self, Layout};
+use crate::journal::navigation;
+use crate::journal::weekly_review::{self, section_items};
+
+/// Daily sections shown for each earlier entry
+pub const HIGHLIGHT_SECTIONS: &[&str] = &[weekly_review::WINS_SECTION, "Learning & Insights"];
+
+/// Items written under one of the highlight sections
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Highlight {
+    pub section: String,
+    pub items: Vec<String>,
+}
+
+/// An entry from the same day in an earlier year
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Memory {
+    pub date: NaiveDate,
+    pub years_ago: i32,
+    /// Only the highlight sections that were filled in
+    pub highlights: Vec<Highlight>,
+}
+
+fn highlights(content: &str) -> Vec<Highlight> {
+    HIGHLIGHT_SECTIONS
+        .iter()
+        .map(|section| Highlight {
+            section: section.to_string(),
+            items: section_items(content, section),
+        })
+        .filter(|highlight| !highlight.items.is_empty())
+        .collect()
+}
+
+/// Entries written on the month and day of `date` in earlier years, newest first
+pub fn find(journal_dir: &Path, layout: Layout, date: NaiveDate) -> Result<Vec<Memory>> {
+    let mut memories = Vec::new();
+    for (day, path) in filesystem::list_entries(journal_dir, layout)?
+        .into_iter()
+        .rev()
+    {
+        if day.year() >= date.year() || (day.month(), day.day()) != (date.month(), date.day()) {
+            continue;
+        }
+        let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+        memories.push(Memory {
+            date: day,
+            years_ago: date.year() - day.year(),
+            highlights: highlights(&content),
+        });
+    }
+    Ok(memories)
+}
+
+/// Render memories as a markdown list for `{{on_this_day}}`, each linked
+/// from the new entry and followed by its highlights
+pub fn to_markdown(memories: &[Memory], layout: Layout) -> String {
+    let mut lines = Vec::new();
+    for memory in memories {
+        lines.push(format!(
+            "- [{}]({}) · {} year{} ago",
+            memory.date.format("%A, %b %-d %Y"),
+            navigation::link(memory.date, layout),
+            memory.years_ago,
+            if memory.years_ago == 1 { "" } else { "s" }
+        ));
+        for highlight in &memory.highlights {
+            lines.extend(highlight.items.iter().map(|item| format!("  - {}", item)));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_and_to_markdown() {
+        let dir =
+            std::env::temp_dir().join(format!("easy_journal_on_this_day_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let write = |date: &str, content: &str| {
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+            let path = filesystem::get_entry_path(date, &dir, Layout::Nested);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write(
+            "2023-12-22",
+            "# Friday\n\n## Learning & Insights\n-\n\n## Gratitude & Wins\n1. Snow day\n",
+        );
+        write(
+            "2024-12-22",
+            "# Sunday\n\n## Learning & Insights\n- Rust lifetimes click now\n\n## Gratitude & Wins\n-\n",
+        );
+        write(
+            "2024-12-23",
+            "# Monday\n\n## Gratitude & Wins\n- Not today\n",
+        );
+        write("2025-12-22", "# Monday\n");
+
+        let memories = find(
+            &dir,
+            Layout::Nested,
+            NaiveDate::from_ymd_opt(2025, 12, 22).unwrap(),
+        )
+        .unwrap();
+        let dates: Vec<(String, i32)> = memories
+            .iter()
+            .map(|memory| (memory.date.to_string(), memory.years_ago))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![("2024-12-22".to_string(), 1), ("2023-12-22".to_string(), 2)]
+        );
+        assert_eq!(
+            memories[1].highlights,
+            vec![Highlight {
+                section: "Gratitude & Wins".to_string(),
+                items: vec!["Snow day".to_string()],
+            }]
+        );
+
+        assert_eq!(
+            to_markdown(&memories, Layout::Nested),
+            "- [Sunday, Dec 22 2024](../../2024/12/22.md) · 1 year ago\n  - Rust lifetimes click now\n- [Friday, Dec 22 2023](../../2023/12/22.md) · 2 years ago\n  - Snow day"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,486 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use serde::Deserialize;
+
+use crate::config::OnCallConfig;
+use crate::error::{JournalError, Result, check_response};
+
+const PAGERDUTY_API: &str = "https://api.pagerduty.com";
+/// How far ahead shift changes are listed
+const LOOKAHEAD_DAYS: i64 = 7;
+
+/// A stretch of time you are on call for one schedule. `None` ends mean
+/// always on call (an escalation policy without a schedule).
+#[derive(Debug, Clone, PartialEq)]
+struct Shift {
+    schedule: String,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+struct Incident {
+    title: String,
+    status: String,
+    /// Urgency or priority
+    severity: Option<String>,
+    service: Option<String>,
+    url: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+fn local_midnight(day: NaiveDate) -> DateTime<Utc> {
+    Local
+        .from_local_datetime(&day.and_hms_opt(0, 0, 0).unwrap_or_default())
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+        .unwrap_or_default()
+}
+
+fn format_time(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local)
+        .format("%a %b %-d %H:%M")
+        .to_string()
+}
+
+/// Whether you're on call on `date`, shift changes in the week after, and incidents
+fn format_oncall(shifts: &[Shift], incidents: &[Incident], date: NaiveDate) -> String {
+    let day_start = local_midnight(date);
+    let day_end = local_midnight(date + Duration::days(1));
+    let horizon = local_midnight(date + Duration::days(LOOKAHEAD_DAYS));
+
+    let mut shifts: Vec<&Shift> = shifts.iter().collect();
+    shifts.sort_by_key(|s| s.start);
+    shifts.dedup();
+
+    let mut lines = Vec::new();
+    let today: Vec<&Shift> = shifts
+        .iter()
+        .copied()
+        .filter(|s| s.start.is_none_or(|at| at < day_end) && s.end.is_none_or(|at| at > day_start))
+        .collect();
+    if today.is_empty() {
+        lines.push("- 📟 Not on call today".to_string());
+    }
+    for shift in &today {
+        let mut line = format!("- 📟 On call today: {}", shift.schedule);
+        let mut bounds = Vec::new();
+        if let Some(start) = shift.start.filter(|at| *at > day_start) {
+            bounds.push(format!("from {}", format_time(start)));
+        }
+        if let Some(end) = shift.end.filter(|at| *at < horizon) {
+            bounds.push(format!("until {}", format_time(end)));
+        }
+        if !bounds.is_empty() {
+            line.push_str(&format!(" ({})", bounds.join(", ")));
+        }
+        lines.push(line);
+    }
+
+    for shift in shifts
+        .iter()
+        .filter(|s| s.start.is_some_and(|at| at >= day_end && at < horizon))
+    {
+        let mut line = format!("- 🔁 Next shift: {}", shift.schedule);
+        if let Some(start) = shift.start {
+            line.push_str(&format!(", {}", format_time(start)));
+        }
+        if let Some(end) = shift.end {
+            line.push_str(&format!(" – {}", format_time(end)));
+        }
+        lines.push(line);
+    }
+
+    let mut incidents: Vec<&Incident> = incidents.iter().collect();
+    incidents.sort_by_key(|i| std::cmp::Reverse(i.created_at));
+    for incident in incidents {
+        let title = match &incident.url {
+            Some(url) => format!("[{}]({})", incident.title, url),
+            None => incident.title.clone(),
+        };
+        let mut line = format!("- 🚨 {}", title);
+        if let Some(service) = &incident.service {
+            line.push_str(&format!(" — {}", service));
+        }
+        line.push_str(&format!(", {}", incident.status));
+        if let Some(severity) = &incident.severity {
+            line.push_str(&format!(" ({})", severity));
+        }
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+#[derive(Deserialize, Debug)]
+struct PdUserResponse {
+    user: PdRef,
+}
+
+#[derive(Deserialize, Debug)]
+struct PdRef {
+    id: String,
+    summary: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct PdOnCallsResponse {
+    oncalls: Vec<PdOnCall>,
+}
+
+#[derive(Deserialize, Debug)]
+struct PdOnCall {
+    escalation_policy: PdRef,
+    schedule: Option<PdRef>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct PdIncidentsResponse {
+    incidents: Vec<PdIncident>,
+}
+
+#[derive(Deserialize, Debug)]
+struct PdIncident {
+    incident_number: u64,
+    title: String,
+    status: String,
+    urgency: Option<String>,
+    html_url: String,
+    created_at: DateTime<Utc>,
+    service: Option<PdRef>,
+}
+
+async fn pagerduty_get<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    token: &str,
+    path: &str,
+    query: &[(&str, String)],
+) -> Result<T> {
+    let response = client
+        .get(format!("{}{}", PAGERDUTY_API, path))
+        .header(
+            reqwest::header::AUTHORIZATION,
+            format!("Token token={}", token),
+        )
+        .header(reqwest::header::ACCEPT, "application/json")
+        .query(query)
+        .send()
+        .await
+        .map_err(|e| JournalError::OnCallFailed(format!("Request failed: {}", e)))?;
+    check_response(response, "PagerDuty")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::OnCallFailed(format!("Failed to parse response: {}", e)))
+}
+
+async fn fetch_pagerduty(
+    client: &reqwest::Client,
+    config: &OnCallConfig,
+    token: &str,
+    date: NaiveDate,
+) -> Result<(Vec<Shift>, Vec<Incident>)> {
+    // A user API token knows who "me" is
+    let me: PdUserResponse = pagerduty_get(client, token, "/users/me", &[]).await?;
+
+    let oncalls: PdOnCallsResponse = pagerduty_get(
+        client,
+        token,
+        "/oncalls",
+        &[
+            ("user_ids[]", me.user.id.clone()),
+            ("since", local_midnight(date).to_rfc3339()),
+            (
+                "until",
+                local_midnight(date + Duration::days(LOOKAHEAD_DAYS)).to_rfc3339(),
+            ),
+            ("limit", "100".to_string()),
+        ],
+    )
+    .await?;
+    let shifts = oncalls
+        .oncalls
+        .into_iter()
+        .map(|oncall| Shift {
+            schedule: oncall
+                .schedule
+                .and_then(|s| s.summary)
+                .or(oncall.escalation_policy.summary)
+                .unwrap_or_else(|| "On call".to_string()),
+            start: oncall.start,
+            end: oncall.end,
+        })
+        .collect();
+
+    let now = Utc::now();
+    let mut query = vec![
+        ("since", (now - Duration::hours(24)).to_rfc3339()),
+        ("until", now.to_rfc3339()),
+        ("limit", "100".to_string()),
+    ];
+    if config.pagerduty_team_ids.is_empty() {
+        query.push(("user_ids[]", me.user.id.clone()));
+    } else {
+        query.extend(
+            config
+                .pagerduty_team_ids
+                .iter()
+                .map(|team| ("team_ids[]", team.clone())),
+        );
+    }
+    let incidents: PdIncidentsResponse = pagerduty_get(client, token, "/incidents", &query).await?;
+    let incidents = incidents
+        .incidents
+        .into_iter()
+        .map(|incident| Incident {
+            title: format!("#{} {}", incident.incident_number, incident.title),
+            status: incident.status,
+            severity: incident.urgency,
+            service: incident.service.and_then(|s| s.summary),
+            url: Some(incident.html_url),
+            created_at: incident.created_at,
+        })
+        .collect();
+
+    Ok((shifts, incidents))
+}
+
+#[derive(Deserialize, Debug)]
+struct OgList<T> {
+    data: Vec<T>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OgSchedule {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct OgTimelineResponse {
+    data: OgTimeline,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct OgTimeline {
+    final_timeline: OgFinalTimeline,
+}
+
+#[derive(Deserialize, Debug)]
+struct OgFinalTimeline {
+    #[serde(default)]
+    rotations: Vec<OgRotation>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OgRotation {
+    #[serde(default)]
+    periods: Vec<OgPeriod>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct OgPeriod {
+    start_date: DateTime<Utc>,
+    end_date: DateTime<Utc>,
+    recipient: Option<OgRecipient>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OgRecipient {
+    name: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct OgAlert {
+    id: String,
+    tiny_id: String,
+    message: String,
+    status: String,
+    priority: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+async fn opsgenie_get<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    config: &OnCallConfig,
+    key: &str,
+    path: &str,
+    query: &[(&str, String)],
+) -> Result<T> {
+    let response = client
+        .get(format!(
+            "{}{}",
+            config.opsgenie_api_url.trim_end_matches('/'),
+            path
+        ))
+        .header(reqwest::header::AUTHORIZATION, format!("GenieKey {}", key))
+        .query(query)
+        .send()
+        .await
+        .map_err(|e| JournalError::OnCallFailed(format!("Request failed: {}", e)))?;
+    check_response(response, "Opsgenie")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::OnCallFailed(format!("Failed to parse response: {}", e)))
+}
+
+async fn fetch_opsgenie(
+    client: &reqwest::Client,
+    config: &OnCallConfig,
+    key: &str,
+    date: NaiveDate,
+) -> Result<(Vec<Shift>, Vec<Incident>)> {
+    let user = config.opsgenie_user.as_deref().ok_or_else(|| {
+        JournalError::OnCallFailed("OPSGENIE_USER not set (your Opsgenie login email)".to_string())
+    })?;
+
+    let schedules: OgList<OgSchedule> =
+        opsgenie_get(client, config, key, "/v2/schedules", &[]).await?;
+    let mut shifts = Vec::new();
+    for schedule in schedules.data {
+        let timeline: OgTimelineResponse = opsgenie_get(
+            client,
+            config,
+            key,
+            &format!("/v2/schedules/{}/timeline", schedule.id),
+            &[
+                ("date", local_midnight(date).to_rfc3339()),
+                ("interval", LOOKAHEAD_DAYS.to_string()),
+                ("intervalUnit", "days".to_string()),
+            ],
+        )
+        .await?;
+        shifts.extend(
+            timeline
+                .data
+                .final_timeline
+                .rotations
+                .into_iter()
+                .flat_map(|rotation| rotation.periods)
+                .filter(|period| {
+                    period
+                        .recipient
+                        .as_ref()
+                        .and_then(|r| r.name.as_deref())
+                        .is_some_and(|name| name.eq_ignore_ascii_case(user))
+                })
+                .map(|period| Shift {
+                    schedule: schedule.name.clone(),
+                    start: Some(period.start_date),
+                    end: Some(period.end_date),
+                }),
+        );
+    }
+
+    let since = (Utc::now() - Duration::hours(24)).timestamp_millis();
+    let alerts: OgList<OgAlert> = opsgenie_get(
+        client,
+        config,
+        key,
+        "/v2/alerts",
+        &[
+            ("query", format!("createdAt > {}", since)),
+            ("limit", "50".to_string()),
+            ("sort", "createdAt".to_string()),
+        ],
+    )
+    .await?;
+    let incidents = alerts
+        .data
+        .into_iter()
+        .map(|alert| Incident {
+            title: format!("#{} {}", alert.tiny_id, alert.message),
+            status: alert.status,
+            severity: alert.priority,
+            service: None,
+            url: config.opsgenie_app_url.as_ref().map(|app| {
+                format!(
+                    "{}/alert/detail/{}/details",
+                    app.trim_end_matches('/'),
+                    alert.id
+                )
+            }),
+            created_at: alert.created_at,
+        })
+        .collect();
+
+    Ok((shifts, incidents))
+}
+
+/// On-call status and recent incidents from PagerDuty and/or Opsgenie
+pub async fn fetch_oncall(config: &OnCallConfig, date: NaiveDate) -> Result<Option<String>> {
+    if config.pagerduty_token.is_none() && config.opsgenie_api_key.is_none() {
+        tracing::debug!("PAGERDUTY_TOKEN/OPSGENIE_API_KEY not set");
+        return Ok(None);
+    }
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::OnCallFailed(format!("Failed to build HTTP client: {}", e)))?;
+
+    let mut shifts = Vec::new();
+    let mut incidents = Vec::new();
+    if let Some(token) = &config.pagerduty_token {
+        let (s, i) = fetch_pagerduty(&client, config, token, date).await?;
+        shifts.extend(s);
+        incidents.extend(i);
+    }
+    if let Some(key) = &config.opsgenie_api_key {
+        let (s, i) = fetch_opsgenie(&client, config, key, date).await?;
+        shifts.extend(s);
+        incidents.extend(i);
+    }
+
+    Ok(Some(format_oncall(&shifts, &incidents, date)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_oncall() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let at = |day: u32, hour: i64| {
+            local_midnight(NaiveDate::from_ymd_opt(2025, 12, day).unwrap()) + Duration::hours(hour)
+        };
+        let shift = |start, end| Shift {
+            schedule: "Platform primary".to_string(),
+            start: Some(start),
+            end: Some(end),
+        };
+        // The current shift is listed once per escalation level
+        let shifts = vec![
+            shift(at(26, 9), at(30, 9)),
+            shift(at(26, 9), at(30, 9)),
+            shift(at(31, 9), at(31, 9) + Duration::days(7)),
+        ];
+        let incidents = vec![Incident {
+            title: "#42 Checkout latency".to_string(),
+            status: "resolved".to_string(),
+            severity: Some("high".to_string()),
+            service: Some("Checkout API".to_string()),
+            url: Some("https://acme.pagerduty.com/incidents/Q1".to_string()),
+            created_at: at(28, 22),
+        }];
+
+        assert_eq!(
+            format_oncall(&shifts, &incidents, date),
+            format!(
+                "- 📟 On call today: Platform primary (until {})\n\
+                 - 🔁 Next shift: Platform primary, {} – {}\n\
+                 - 🚨 [#42 Checkout latency](https://acme.pagerduty.com/incidents/Q1) — Checkout API, resolved (high)",
+                format_time(at(30, 9)),
+                format_time(at(31, 9)),
+                format_time(at(31, 9) + Duration::days(7))
+            )
+        );
+        assert_eq!(format_oncall(&[], &[], date), "- 📟 Not on call today");
+    }
+}
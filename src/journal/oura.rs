@@ -0,0 +1,164 @@
+use chrono::{Duration, NaiveDate};
+use serde::Deserialize;
+
+use crate::config::OuraConfig;
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::parser::format_duration;
+
+const API_URL: &str = "https://api.ouraring.com/v2/usercollection";
+
+#[derive(Deserialize, Debug)]
+struct Collection<T> {
+    #[serde(default = "Vec::new")]
+    data: Vec<T>,
+}
+
+/// `daily_readiness` and `daily_sleep` entries
+#[derive(Deserialize, Debug)]
+struct DailyScore {
+    day: NaiveDate,
+    score: Option<u32>,
+}
+
+/// A `sleep` period; durations are in seconds
+#[derive(Deserialize, Debug)]
+struct SleepPeriod {
+    day: NaiveDate,
+    /// `long_sleep` for the main sleep of the night, `sleep`/`rest`/`late_nap` otherwise
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    total_sleep_duration: Option<u32>,
+    deep_sleep_duration: Option<u32>,
+    rem_sleep_duration: Option<u32>,
+    average_hrv: Option<u32>,
+    lowest_heart_rate: Option<u32>,
+}
+
+/// The entries of an Oura collection for `date`
+async fn get<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    token: &str,
+    collection: &str,
+    date: NaiveDate,
+) -> Result<Vec<T>> {
+    // The sleep endpoints treat end_date as exclusive
+    let start = date.format("%Y-%m-%d").to_string();
+    let end = (date + Duration::days(1)).format("%Y-%m-%d").to_string();
+    let response = client
+        .get(format!("{}/{}", API_URL, collection))
+        .bearer_auth(token)
+        .query(&[("start_date", start.as_str()), ("end_date", end.as_str())])
+        .send()
+        .await
+        .map_err(|e| JournalError::OuraFailed(format!("Request failed: {}", e)))?;
+    let collection: Collection<T> = check_response(response, "Oura")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::OuraFailed(format!("Failed to parse response: {}", e)))?;
+    Ok(collection.data)
+}
+
+fn score_for(scores: &[DailyScore], date: NaiveDate) -> Option<u32> {
+    scores.iter().find(|s| s.day == date).and_then(|s| s.score)
+}
+
+/// Footer metrics, then the "Body" summary line
+fn format_body(
+    readiness: Option<u32>,
+    sleep_score: Option<u32>,
+    sleep: Option<&SleepPeriod>,
+) -> Option<String> {
+    let mut lines = Vec::new();
+    if let Some(readiness) = readiness {
+        lines.push(format!("**Readiness**: {}", readiness));
+    }
+    if let Some(score) = sleep_score {
+        lines.push(format!("**Sleep Score**: {}", score));
+    }
+
+    if let Some(sleep) = sleep {
+        if let Some(hrv) = sleep.average_hrv {
+            lines.push(format!("**HRV**: {} ms", hrv));
+        }
+        if let Some(total) = sleep.total_sleep_duration {
+            lines.push(format!("**Sleep Time**: {}", format_duration(total / 60)));
+
+            let mut details = Vec::new();
+            if let Some(deep) = sleep.deep_sleep_duration {
+                details.push(format!("deep {}", format_duration(deep / 60)));
+            }
+            if let Some(rem) = sleep.rem_sleep_duration {
+                details.push(format!("REM {}", format_duration(rem / 60)));
+            }
+            if let Some(hr) = sleep.lowest_heart_rate {
+                details.push(format!("lowest HR {} bpm", hr));
+            }
+            let mut line = format!("- 💤 Slept {}", format_duration(total / 60));
+            if !details.is_empty() {
+                line.push_str(&format!(" ({})", details.join(", ")));
+            }
+            lines.push(line);
+        }
+    }
+
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Readiness, sleep score and HRV for `date` from the Oura API. Oura dates a
+/// night by the day you woke up, so this is last night's sleep.
+pub async fn fetch_oura(config: &OuraConfig, date: NaiveDate) -> Result<Option<String>> {
+    let Some(token) = &config.token else {
+        tracing::debug!("OURA_TOKEN not set");
+        return Ok(None);
+    };
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::OuraFailed(format!("Failed to build HTTP client: {}", e)))?;
+
+    let (readiness, daily_sleep, periods) = tokio::try_join!(
+        get::<DailyScore>(&client, token, "daily_readiness", date),
+        get::<DailyScore>(&client, token, "daily_sleep", date),
+        get::<SleepPeriod>(&client, token, "sleep", date),
+    )?;
+
+    let night: Vec<&SleepPeriod> = periods.iter().filter(|p| p.day == date).collect();
+    let main_sleep = night
+        .iter()
+        .find(|p| p.kind.as_deref() == Some("long_sleep"))
+        .or_else(|| night.iter().max_by_key(|p| p.total_sleep_duration))
+        .copied();
+
+    Ok(format_body(
+        score_for(&readiness, date),
+        score_for(&daily_sleep, date),
+        main_sleep,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_body() {
+        let periods: Collection<SleepPeriod> = serde_json::from_str(
+            r#"{"data": [{"day": "2025-12-29", "type": "long_sleep",
+                "total_sleep_duration": 26100, "deep_sleep_duration": 4800,
+                "rem_sleep_duration": 6000, "average_hrv": 45, "lowest_heart_rate": 52}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            format_body(Some(82), Some(78), periods.data.first()).unwrap(),
+            "**Readiness**: 82\n\
+             **Sleep Score**: 78\n\
+             **HRV**: 45 ms\n\
+             **Sleep Time**: 7h 15m\n\
+             - 💤 Slept 7h 15m (deep 1h 20m, REM 1h 40m, lowest HR 52 bpm)"
+        );
+        assert_eq!(format_body(None, None, None), None);
+    }
+}
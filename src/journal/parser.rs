@@ -1,3 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+use crate::journal::navigation;
+
 /// Extract content from a markdown section
 pub fn extract_section(content: &str, section_header: &str) -> Option<String> {
     let lines: Vec<&str> = content.lines().collect();
@@ -57,6 +61,362 @@ pub fn extract_unchecked_tasks(content: &str) -> Option<String> {
     }
 }
 
+/// A markdown section: its `#` header line (empty for text before the first
+/// header) and the lines that follow it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Section {
+    pub header: String,
+    pub lines: Vec<String>,
+}
+
+/// Split an entry into sections at every markdown header
+pub fn split_sections(content: &str) -> Vec<Section> {
+    let mut sections = vec![Section {
+        header: String::new(),
+        lines: Vec::new(),
+    }];
+
+    for line in content.lines() {
+        if line.trim_start().starts_with('#') {
+            sections.push(Section {
+                header: line.to_string(),
+                lines: Vec::new(),
+            });
+        } else if let Some(section) = sections.last_mut() {
+            section.lines.push(line.to_string());
+        }
+    }
+
+    // Drop an empty preamble
+    if sections[0].lines.iter().all(|l| l.trim().is_empty()) {
+        sections.remove(0);
+    }
+
+    sections
+}
+
+/// Title and text of every `##` (or deeper) section, for structured exports.
+///
+/// The footer after the last `---` and the navigation line are left out, and
+/// the text of a title that comes up again is added to the first one.
+pub fn section_texts(content: &str) -> Vec<(String, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let footer = lines
+        .iter()
+        .rposition(|line| line.trim() == "---")
+        .unwrap_or(lines.len());
+
+    let mut texts: Vec<(String, String)> = Vec::new();
+    // Index into `texts` of the section being read
+    let mut current = None;
+    for line in &lines[..footer] {
+        let trimmed = line.trim_start();
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if level > 0 && trimmed[level..].starts_with(' ') {
+            let title = trimmed[level..].trim();
+            current = (level >= 2).then(|| {
+                texts
+                    .iter()
+                    .position(|(existing, _)| existing == title)
+                    .unwrap_or_else(|| {
+                        texts.push((title.to_string(), String::new()));
+                        texts.len() - 1
+                    })
+            });
+        } else if let Some(i) = current
+            && !navigation::is_nav_line(line)
+        {
+            texts[i].1.push_str(line);
+            texts[i].1.push('\n');
+        }
+    }
+
+    for (_, text) in &mut texts {
+        *text = text.trim().to_string();
+    }
+    texts
+}
+
+/// Render sections back into markdown
+pub fn join_sections(sections: &[Section]) -> String {
+    let mut content = String::new();
+    for section in sections {
+        if !section.header.is_empty() {
+            content.push_str(&section.header);
+            content.push('\n');
+        }
+        for line in &section.lines {
+            content.push_str(line);
+            content.push('\n');
+        }
+    }
+    content
+}
+
+/// Parse a checkbox line into (text, checked)
+pub fn parse_task(line: &str) -> Option<(String, bool)> {
+    let trimmed = line.trim();
+    let (text, checked) = if let Some(text) = trimmed.strip_prefix("- [ ]") {
+        (text, false)
+    } else if let Some(text) = trimmed
+        .strip_prefix("- [x]")
+        .or_else(|| trimmed.strip_prefix("- [X]"))
+    {
+        (text, true)
+    } else {
+        return None;
+    };
+    Some((text.trim().to_string(), checked))
+}
+
+/// Parse a `**Key**: value` footer line into (key, value)
+pub fn parse_metric(line: &str) -> Option<(String, String)> {
+    let rest = line.trim().strip_prefix("**")?;
+    let (key, value) = rest.split_once("**:")?;
+    Some((key.trim().to_string(), value.trim().to_string()))
+}
+
+/// Metric name without case or a `(1-10)`-style range, so `Sleep Score(1-100)`
+/// matches `Sleep Score`
+pub fn metric_name(key: &str) -> String {
+    key.split('(')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+/// Duration in the style of the footer fields, `7h 05m`
+pub fn format_duration(minutes: u32) -> String {
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+/// Hours for `**Hours Worked**`: `7.5`, `7.25`, `8`
+pub fn format_hours(seconds: i64) -> String {
+    let hours = format!("{:.2}", seconds as f64 / 3600.0);
+    hours
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Minutes in a [`format_duration`]-style `7h 05m`
+pub fn parse_duration(text: &str) -> Option<u32> {
+    let (hours, minutes) = text.trim().split_once('h')?;
+    let minutes = minutes.trim().strip_suffix('m')?;
+    Some(hours.trim().parse::<u32>().ok()? * 60 + minutes.trim().parse::<u32>().ok()?)
+}
+
+/// Fill the empty `**Label**:` lines of `template` with the matching
+/// `**Label**: value` lines of `content`. Returns the filled template and the
+/// rest of `content` (the lines that were not used).
+pub fn fill_metrics(template: &str, content: &str) -> (String, String) {
+    let values: Vec<(String, String)> = content
+        .lines()
+        .filter_map(parse_metric)
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(key, value)| (metric_name(&key), value))
+        .collect();
+    if values.is_empty() {
+        return (template.to_string(), content.to_string());
+    }
+
+    let mut used = Vec::new();
+    let filled: Vec<String> = template
+        .split('\n')
+        .map(|line| {
+            let Some((key, value)) = parse_metric(line) else {
+                return line.to_string();
+            };
+            let name = metric_name(&key);
+            match values.iter().find(|(n, _)| *n == name) {
+                Some((_, new_value)) if value.is_empty() => {
+                    used.push(name);
+                    format!("{} {}", line.trim_end(), new_value)
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect();
+
+    let rest: Vec<&str> = content
+        .lines()
+        .filter(|line| {
+            !parse_metric(line).is_some_and(|(key, _)| used.contains(&metric_name(&key)))
+        })
+        .collect();
+    (filled.join("\n"), rest.join("\n").trim().to_string())
+}
+
+/// Set the `**Key**: value` field matching `key` (by [`metric_name`], keeping
+/// the entry's spelling of it), adding it to the end of the `---` footer, or
+/// a new footer, when the entry doesn't have one
+pub fn set_metric(content: &str, key: &str, value: &str) -> String {
+    navigation::keep_at_bottom(content, |content| set_metric_above_nav(content, key, value))
+}
+
+fn set_metric_above_nav(content: &str, key: &str, value: &str) -> String {
+    let name = metric_name(key);
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    let existing = lines.iter().rposition(|line| {
+        parse_metric(line).is_some_and(|(existing, _)| metric_name(&existing) == name)
+    });
+    match existing {
+        Some(index) => {
+            let (existing, _) = parse_metric(&lines[index]).unwrap_or_default();
+            lines[index] = format!("**{}**: {}", existing, value);
+        }
+        None => {
+            let field = format!("**{}**: {}", key, value);
+            match lines.iter().rposition(|line| line.trim() == "---") {
+                Some(footer) => {
+                    let end = lines
+                        .iter()
+                        .rposition(|line| !line.trim().is_empty())
+                        .filter(|last| *last > footer)
+                        .map_or(footer + 1, |last| last + 1);
+                    if end == footer + 1 {
+                        lines.insert(end, String::new());
+                        lines.insert(end + 1, field);
+                    } else {
+                        lines.insert(end, field);
+                    }
+                }
+                None => {
+                    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+                        lines.pop();
+                    }
+                    lines.extend([String::new(), "---".to_string(), String::new(), field]);
+                }
+            }
+        }
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    updated
+}
+
+/// Insert `text` right below the first `##` heading containing `heading`;
+/// `None` when there is no such heading
+pub fn insert_under_heading(content: &str, heading: &str, text: &str) -> Option<String> {
+    let mut lines: Vec<&str> = content.split('\n').collect();
+    let index = lines.iter().position(|line| {
+        let trimmed = line.trim();
+        trimmed.starts_with("##") && trimmed.contains(heading)
+    })?;
+    lines.insert(index + 1, text);
+    Some(lines.join("\n"))
+}
+
+/// Add `item` to the end of the text directly under the heading titled
+/// `section` (any level, ignoring case and leading `#`s), before its first
+/// subheading or the footer's `---`. A lone `-` placeholder left by the
+/// template is replaced. `None` when there is no such heading.
+pub fn append_item(content: &str, section: &str, item: &str) -> Option<String> {
+    let wanted = section.trim().trim_start_matches('#').trim();
+    let position = |lines: &[&str]| {
+        lines.iter().position(|line| {
+            let trimmed = line.trim_start();
+            let title = trimmed.trim_start_matches('#');
+            title.len() < trimmed.len() && title.trim().eq_ignore_ascii_case(wanted)
+        })
+    };
+    position(&content.lines().collect::<Vec<_>>())?;
+
+    Some(navigation::keep_at_bottom(content, |content| {
+        let mut lines: Vec<&str> = content.lines().collect();
+        let Some(start) = position(&lines) else {
+            return content.to_string();
+        };
+        let mut end = lines[start + 1..]
+            .iter()
+            .position(|l| l.trim_start().starts_with('#') || l.trim() == "---")
+            .map_or(lines.len(), |i| start + 1 + i);
+        while end > start + 1 && lines[end - 1].trim().is_empty() {
+            end -= 1;
+        }
+        if end > start + 1 && lines[end - 1].trim() == "-" {
+            lines[end - 1] = item;
+        } else {
+            lines.insert(end, item);
+        }
+
+        let mut updated = lines.join("\n");
+        updated.push('\n');
+        updated
+    }))
+}
+
+/// Extract all checkbox items in the entry as (text, checked) pairs
+pub fn extract_tasks(content: &str) -> Vec<(String, bool)> {
+    content
+        .lines()
+        .filter_map(parse_task)
+        // Skip empty template placeholders
+        .filter(|(text, _)| !text.is_empty())
+        .collect()
+}
+
+/// Checkbox items with the title of the section they are in ("" before the
+/// first header) as (section, text, checked)
+pub fn section_tasks(content: &str) -> Vec<(String, String, bool)> {
+    split_sections(content)
+        .into_iter()
+        .flat_map(|section| {
+            let title = section
+                .header
+                .trim()
+                .trim_start_matches('#')
+                .trim()
+                .to_string();
+            section
+                .lines
+                .iter()
+                .filter_map(|line| parse_task(line))
+                .filter(|(text, _)| !text.is_empty())
+                .map(|(text, checked)| (title.clone(), text, checked))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Extract footer metrics written as `**Key**: value` lines
+pub fn extract_metrics(content: &str) -> Vec<(String, String)> {
+    content.lines().filter_map(parse_metric).collect()
+}
+
+/// Extract `#tags` from the entry body (markdown headers are ignored)
+pub fn extract_tags(content: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for word in content.split_whitespace() {
+        let Some(tag) = word.strip_prefix('#') else {
+            continue;
+        };
+        let tag: String = tag
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+
+        // Tags must start with a letter so "#123" issue references don't count
+        if tag.chars().next().is_some_and(|c| c.is_alphabetic()) {
+            let tag = tag.to_lowercase();
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+
+    tags
+}
+
+/// Count the words in an entry
+pub fn word_count(content: &str) -> usize {
+    content.split_whitespace().count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,4 +513,98 @@ Some notes here
         let unchecked = extract_unchecked_tasks(content);
         assert!(unchecked.is_none());
     }
+
+    #[test]
+    fn test_extract_tasks() {
+        let content =
+            "## Goals for Today\n- [ ] Open task\n- [x] Done task\n- [ ]\n- plain bullet\n";
+
+        let tasks = extract_tasks(content);
+        assert_eq!(
+            tasks,
+            vec![
+                ("Open task".to_string(), false),
+                ("Done task".to_string(), true)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_metrics() {
+        let content = "---\n\n**Mood(1-10)**: 7\n**Energy Level**:\n**Hours Worked**: 8.5\n";
+
+        let metrics = extract_metrics(content);
+        assert_eq!(metrics.len(), 3);
+        assert_eq!(metrics[0], ("Mood(1-10)".to_string(), "7".to_string()));
+        assert_eq!(metrics[1], ("Energy Level".to_string(), String::new()));
+        assert_eq!(metrics[2], ("Hours Worked".to_string(), "8.5".to_string()));
+    }
+
+    #[test]
+    fn test_fill_metrics() {
+        let template = "---\n\n**Sleep Score(1-100)**:\n\n**Sleep Time**:\n**Mood**: 7\n";
+        let content = "**Sleep Score**: 88\n**Sleep Time**: 7h 5m\n**Mood**: 3\n- 9,000 steps";
+
+        let (filled, rest) = fill_metrics(template, content);
+        assert_eq!(
+            filled,
+            "---\n\n**Sleep Score(1-100)**: 88\n\n**Sleep Time**: 7h 5m\n**Mood**: 7\n"
+        );
+        // Filled metrics are consumed; values the template already has are kept
+        assert_eq!(rest, "**Mood**: 3\n- 9,000 steps");
+    }
+
+    #[test]
+    fn test_set_metric() {
+        let content = "## Notes\n- Ok\n\n---\n\n**Mood(1-10)**:\n**Energy Level**: 3\n";
+        let updated = set_metric(content, "Mood", "7");
+        let updated = set_metric(&updated, "Energy Level", "5");
+        let updated = set_metric(&updated, "Hours Worked", "6");
+        assert_eq!(
+            updated,
+            "## Notes\n- Ok\n\n---\n\n**Mood(1-10)**: 7\n**Energy Level**: 5\n**Hours Worked**: 6\n"
+        );
+
+        assert_eq!(
+            set_metric("## Notes\n- Ok\n\n", "Mood", "4"),
+            "## Notes\n- Ok\n\n---\n\n**Mood**: 4\n"
+        );
+    }
+
+    #[test]
+    fn test_extract_tags() {
+        let content = "## Notes\nWent running #health #Health, reviewed PR #123 for #work-stuff.";
+
+        let tags = extract_tags(content);
+        assert_eq!(tags, vec!["health".to_string(), "work-stuff".to_string()]);
+    }
+
+    #[test]
+    fn test_append_item() {
+        let content = "# 2025-12-29\n\n## Work Accomplished\n\n### Morning\n-\n\n## Notes\n- First\n\n---\n\n**Mood**:\n\n[← 2025-12-28](28.md) <!-- nav -->\n";
+
+        let updated =
+            append_item(content, "work accomplished", "- Shipped the billing fix").unwrap();
+        assert!(
+            updated.contains("## Work Accomplished\n- Shipped the billing fix\n\n### Morning\n-\n")
+        );
+        let updated = append_item(&updated, "### Morning", "- Standup").unwrap();
+        assert!(updated.contains("### Morning\n- Standup\n\n## Notes"));
+        let updated = append_item(&updated, "Notes", "- Second").unwrap();
+        assert!(updated.contains("## Notes\n- First\n- Second\n\n---"));
+        assert!(updated.ends_with("**Mood**:\n\n[← 2025-12-28](28.md) <!-- nav -->\n"));
+
+        assert_eq!(append_item(content, "Gratitude", "- Coffee"), None);
+    }
+
+    #[test]
+    fn test_split_and_join_sections() {
+        let content = "# 2025-12-29\n\n## Goals for Today\n- [ ] Task\n\n## Notes\nText\n";
+
+        let sections = split_sections(content);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[1].header, "## Goals for Today");
+        assert_eq!(sections[1].lines, vec!["- [ ] Task", ""]);
+        assert_eq!(join_sections(&sections), content);
+    }
 }
@@ -0,0 +1,679 @@
+use chrono::NaiveDate;
+use pdf_writer::types::{ActionType, AnnotationType};
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+/// A4 in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const BODY_SIZE: f32 = 11.0;
+const CODE_SIZE: f32 = 9.5;
+const INDENT: f32 = 18.0;
+const LINK_COLOR: [f32; 3] = [0.1, 0.3, 0.75];
+
+/// Advance widths of Helvetica for `' '..='~'`, in 1/1000 em
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// One of the standard PDF fonts, which readers have built in
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Font {
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+    Mono,
+}
+
+impl Font {
+    const ALL: [Font; 5] = [
+        Font::Regular,
+        Font::Bold,
+        Font::Italic,
+        Font::BoldItalic,
+        Font::Mono,
+    ];
+
+    fn resource(self) -> Name<'static> {
+        Name(match self {
+            Font::Regular => b"F1",
+            Font::Bold => b"F2",
+            Font::Italic => b"F3",
+            Font::BoldItalic => b"F4",
+            Font::Mono => b"F5",
+        })
+    }
+
+    fn base_font(self) -> Name<'static> {
+        Name(match self {
+            Font::Regular => b"Helvetica",
+            Font::Bold => b"Helvetica-Bold",
+            Font::Italic => b"Helvetica-Oblique",
+            Font::BoldItalic => b"Helvetica-BoldOblique",
+            Font::Mono => b"Courier",
+        })
+    }
+
+    /// Width of WinAnsi-encoded `text`. Bold is approximated as slightly wider
+    /// Helvetica, which errs on the side of wrapping early.
+    fn width(self, text: &[u8], size: f32) -> f32 {
+        let units: f32 = text
+            .iter()
+            .map(|&byte| match (self, byte) {
+                (Font::Mono, _) => 600.0,
+                (_, 32..=126) => f32::from(HELVETICA_WIDTHS[usize::from(byte - 32)]),
+                _ => 556.0,
+            })
+            .sum();
+        let scale = match self {
+            Font::Bold | Font::BoldItalic => 1.06,
+            _ => 1.0,
+        };
+        units * scale * size / 1000.0
+    }
+}
+
+/// `text` in WinAnsiEncoding; characters the standard fonts can't show
+/// (emoji, arrows, most non-Latin scripts) are dropped
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .filter_map(|c| match c {
+            ' '..='~' => Some(c as u8),
+            '\t' => Some(b' '),
+            '\u{a0}'..='\u{ff}' => Some(c as u32 as u8),
+            '€' => Some(0x80),
+            '‚' => Some(0x82),
+            'ƒ' => Some(0x83),
+            '„' => Some(0x84),
+            '…' => Some(0x85),
+            '†' => Some(0x86),
+            '‡' => Some(0x87),
+            'ˆ' => Some(0x88),
+            '‰' => Some(0x89),
+            'Š' => Some(0x8a),
+            '‹' => Some(0x8b),
+            'Œ' => Some(0x8c),
+            'Ž' => Some(0x8e),
+            '‘' => Some(0x91),
+            '’' => Some(0x92),
+            '“' => Some(0x93),
+            '”' => Some(0x94),
+            '•' => Some(0x95),
+            '–' => Some(0x96),
+            '—' => Some(0x97),
+            '˜' => Some(0x98),
+            '™' => Some(0x99),
+            'š' => Some(0x9a),
+            '›' => Some(0x9b),
+            'œ' => Some(0x9c),
+            'ž' => Some(0x9e),
+            'Ÿ' => Some(0x9f),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Item {
+    Text {
+        x: f32,
+        y: f32,
+        font: Font,
+        size: f32,
+        text: Vec<u8>,
+        link: bool,
+    },
+    Checkbox {
+        x: f32,
+        y: f32,
+        size: f32,
+        checked: bool,
+    },
+    Rule {
+        y: f32,
+    },
+}
+
+#[derive(Debug, Default)]
+struct Page {
+    items: Vec<Item>,
+    /// Clickable areas and the URLs they open
+    links: Vec<(Rect, String)>,
+}
+
+impl Page {
+    fn content(&self) -> Vec<u8> {
+        let mut content = Content::new();
+        for item in &self.items {
+            match item {
+                Item::Text {
+                    x,
+                    y,
+                    font,
+                    size,
+                    text,
+                    link,
+                } => {
+                    if *link {
+                        let [r, g, b] = LINK_COLOR;
+                        content.set_fill_rgb(r, g, b);
+                    }
+                    content
+                        .begin_text()
+                        .set_font(font.resource(), *size)
+                        .next_line(*x, *y)
+                        .show(Str(text))
+                        .end_text();
+                    if *link {
+                        content.set_fill_gray(0.0);
+                    }
+                }
+                Item::Checkbox {
+                    x,
+                    y,
+                    size,
+                    checked,
+                } => {
+                    content
+                        .set_line_width(0.8)
+                        .rect(*x, *y, *size, *size)
+                        .stroke();
+                    if *checked {
+                        content
+                            .move_to(x + size * 0.2, y + size * 0.5)
+                            .line_to(x + size * 0.42, y + size * 0.2)
+                            .line_to(x + size * 0.85, y + size * 0.85)
+                            .stroke();
+                    }
+                }
+                Item::Rule { y } => {
+                    content
+                        .set_stroke_gray(0.7)
+                        .set_line_width(0.5)
+                        .move_to(MARGIN, *y)
+                        .line_to(PAGE_WIDTH - MARGIN, *y)
+                        .stroke()
+                        .set_stroke_gray(0.0);
+                }
+            }
+        }
+        content.finish()
+    }
+}
+
+/// Lays markdown out on pages, wrapping words at the right margin
+struct Typesetter {
+    pages: Vec<Page>,
+    x: f32,
+    /// Baseline of the current line
+    y: f32,
+    /// Whether anything was placed on the current line yet
+    line_open: bool,
+    indent: f32,
+    size: f32,
+    bold: usize,
+    italic: usize,
+    code: bool,
+    /// URL of the link being typeset, for external links
+    link: Option<String>,
+    /// A space is due before the next word on the line
+    space: bool,
+}
+
+impl Typesetter {
+    fn new() -> Self {
+        Typesetter {
+            pages: Vec::new(),
+            x: MARGIN,
+            y: PAGE_HEIGHT - MARGIN,
+            line_open: false,
+            indent: 0.0,
+            size: BODY_SIZE,
+            bold: 0,
+            italic: 0,
+            code: false,
+            link: None,
+            space: false,
+        }
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(Page::default());
+        self.y = PAGE_HEIGHT - MARGIN;
+        self.line_open = false;
+    }
+
+    fn page(&mut self) -> &mut Page {
+        if self.pages.is_empty() {
+            self.new_page();
+        }
+        self.pages.last_mut().expect("a page was just added")
+    }
+
+    fn font(&self) -> Font {
+        match (self.code, self.bold > 0, self.italic > 0) {
+            (true, _, _) => Font::Mono,
+            (_, true, true) => Font::BoldItalic,
+            (_, true, false) => Font::Bold,
+            (_, false, true) => Font::Italic,
+            _ => Font::Regular,
+        }
+    }
+
+    /// Start a new line unless one is open, moving to the next page when full
+    fn line(&mut self) {
+        if self.line_open {
+            return;
+        }
+        let height = self.size * 1.45;
+        if self.pages.is_empty() || self.y - height < MARGIN {
+            self.new_page();
+        }
+        self.y -= height;
+        self.x = MARGIN + self.indent;
+        self.line_open = true;
+        self.space = false;
+    }
+
+    /// Close the current line and leave `gap` points before the next block
+    fn end_block(&mut self, gap: f32) {
+        if self.line_open {
+            self.y -= gap;
+        }
+        self.line_open = false;
+    }
+
+    fn place(&mut self, x: f32, text: Vec<u8>, font: Font) -> f32 {
+        let width = font.width(&text, self.size);
+        let (y, size, link) = (self.y, self.size, self.link.clone());
+        let page = self.page();
+        if let Some(url) = &link {
+            let rect = Rect::new(x, y - size * 0.25, x + width, y + size * 0.85);
+            page.links.push((rect, url.clone()));
+        }
+        page.items.push(Item::Text {
+            x,
+            y,
+            font,
+            size,
+            text,
+            link: link.is_some(),
+        });
+        width
+    }
+
+    fn word(&mut self, word: &str) {
+        let text = encode(word);
+        if text.is_empty() {
+            return;
+        }
+        let font = self.font();
+        self.line();
+        let line_start = MARGIN + self.indent;
+        let space = if self.space && self.x > line_start {
+            font.width(b" ", self.size)
+        } else {
+            0.0
+        };
+        if self.x + space + font.width(&text, self.size) > PAGE_WIDTH - MARGIN
+            && self.x > line_start
+        {
+            self.line_open = false;
+            self.line();
+        } else {
+            self.x += space;
+        }
+        self.x += self.place(self.x, text, font);
+        self.space = false;
+    }
+
+    fn text(&mut self, text: &str) {
+        for (i, word) in text.split(' ').enumerate() {
+            if i > 0 {
+                self.space = true;
+            }
+            self.word(word);
+        }
+    }
+
+    /// A line of a code block, kept as it is and cut into pieces that fit
+    fn code_line(&mut self, line: &str) {
+        let text = encode(line);
+        let per_line = ((PAGE_WIDTH - 2.0 * MARGIN - self.indent) / (0.6 * self.size)) as usize;
+        let mut chunks = text.chunks(per_line.max(1)).peekable();
+        if chunks.peek().is_none() {
+            self.line();
+        }
+        for chunk in chunks {
+            self.line_open = false;
+            self.line();
+            self.place(self.x, chunk.to_vec(), Font::Mono);
+        }
+        self.line_open = false;
+    }
+
+    /// A list marker or checkbox in the margin left of the current line
+    fn marker(&mut self, marker: &Marker) {
+        self.line();
+        let (x, y, size) = (MARGIN + self.indent, self.y, self.size);
+        match marker {
+            Marker::Text(text) => {
+                let text = encode(text);
+                let width = Font::Regular.width(&text, size);
+                self.place(x - 5.0 - width, text, Font::Regular);
+            }
+            Marker::Checkbox(checked) => self.page().items.push(Item::Checkbox {
+                x: x - 5.0 - size * 0.75,
+                y: y - size * 0.05,
+                size: size * 0.75,
+                checked: *checked,
+            }),
+        }
+    }
+
+    fn centered(&mut self, text: &str, font: Font, size: f32, y: f32) {
+        let text = encode(text);
+        let width = font.width(&text, size);
+        self.page().items.push(Item::Text {
+            x: (PAGE_WIDTH - width) / 2.0,
+            y,
+            font,
+            size,
+            text,
+            link: false,
+        });
+    }
+
+    fn title_page(&mut self, title: &str, subtitle: &str) {
+        self.new_page();
+        let y = PAGE_HEIGHT * 0.55;
+        self.centered(title, Font::Bold, 28.0, y);
+        self.centered(subtitle, Font::Regular, 16.0, y - 34.0);
+    }
+
+    fn markdown(&mut self, markdown: &str) {
+        let options = Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH;
+        // Numbers of the open ordered lists, `None` for bullet lists
+        let mut lists: Vec<Option<u64>> = Vec::new();
+        // A list item's marker, drawn with its first line unless a checkbox replaces it
+        let mut marker: Option<Marker> = None;
+        let mut in_code_block = false;
+
+        for event in Parser::new_ext(markdown, options) {
+            if let Some(pending) = marker.take() {
+                match &event {
+                    Event::TaskListMarker(checked) => {
+                        self.marker(&Marker::Checkbox(*checked));
+                        continue;
+                    }
+                    // In loose lists the checkbox follows the paragraph start
+                    Event::Start(Tag::Paragraph) => {
+                        marker = Some(pending);
+                        continue;
+                    }
+                    _ => self.marker(&pending),
+                }
+            }
+
+            match event {
+                Event::Start(Tag::Heading { level, .. }) => {
+                    self.end_block(0.0);
+                    self.y -= 6.0;
+                    self.size = match level {
+                        HeadingLevel::H1 => 20.0,
+                        HeadingLevel::H2 => 15.0,
+                        _ => 12.5,
+                    };
+                    self.bold += 1;
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    self.bold -= 1;
+                    self.end_block(4.0);
+                    self.size = BODY_SIZE;
+                }
+                Event::End(TagEnd::Paragraph) => {
+                    let gap = if lists.is_empty() { 6.0 } else { 0.0 };
+                    self.end_block(gap);
+                }
+                Event::Start(Tag::BlockQuote(_)) => {
+                    self.end_block(0.0);
+                    self.indent += INDENT;
+                    self.italic += 1;
+                }
+                Event::End(TagEnd::BlockQuote(_)) => {
+                    self.end_block(0.0);
+                    self.indent -= INDENT;
+                    self.italic -= 1;
+                }
+                Event::Start(Tag::List(first)) => {
+                    self.end_block(0.0);
+                    lists.push(first);
+                    self.indent += INDENT;
+                }
+                Event::End(TagEnd::List(_)) => {
+                    lists.pop();
+                    self.indent -= INDENT;
+                    let gap = if lists.is_empty() { 6.0 } else { 0.0 };
+                    self.end_block(gap);
+                }
+                Event::Start(Tag::Item) => {
+                    self.end_block(0.0);
+                    marker = Some(match lists.last_mut() {
+                        Some(Some(number)) => {
+                            *number += 1;
+                            Marker::Text(format!("{}.", *number - 1))
+                        }
+                        _ => Marker::Text("•".to_string()),
+                    });
+                }
+                Event::End(TagEnd::Item) => self.end_block(0.0),
+                Event::Start(Tag::CodeBlock(_)) => {
+                    self.end_block(0.0);
+                    in_code_block = true;
+                    self.size = CODE_SIZE;
+                }
+                Event::End(TagEnd::CodeBlock) => {
+                    in_code_block = false;
+                    self.line_open = true;
+                    self.end_block(6.0);
+                    self.size = BODY_SIZE;
+                }
+                Event::Start(Tag::Emphasis) => self.italic += 1,
+                Event::End(TagEnd::Emphasis) => self.italic -= 1,
+                Event::Start(Tag::Strong) => self.bold += 1,
+                Event::End(TagEnd::Strong) => self.bold -= 1,
+                // Relative links point at files that aren't in the PDF
+                Event::Start(Tag::Link { dest_url, .. })
+                    if dest_url.contains("://") || dest_url.starts_with("mailto:") =>
+                {
+                    self.link = Some(dest_url.to_string());
+                }
+                Event::End(TagEnd::Link) => self.link = None,
+                Event::Text(text) if in_code_block => {
+                    for line in text.lines() {
+                        self.code_line(line);
+                    }
+                }
+                Event::Text(text) => self.text(&text),
+                Event::Code(code) => {
+                    self.code = true;
+                    self.text(&code);
+                    self.code = false;
+                }
+                Event::SoftBreak => self.space = true,
+                Event::HardBreak => self.line_open = false,
+                Event::Rule => {
+                    self.end_block(0.0);
+                    self.line();
+                    let y = self.y + self.size * 0.35;
+                    self.page().items.push(Item::Rule { y });
+                    self.end_block(6.0);
+                }
+                _ => {}
+            }
+        }
+        self.end_block(0.0);
+    }
+}
+
+enum Marker {
+    Text(String),
+    Checkbox(bool),
+}
+
+/// A printable PDF of `entries` (date and markdown, oldest first): a cover
+/// page with `title`, then a title page and the entry for each day, with a
+/// bookmark per day
+pub fn build(title: &str, entries: &[(NaiveDate, String)]) -> Vec<u8> {
+    let mut typesetter = Typesetter::new();
+    let range = match (entries.first(), entries.last()) {
+        (Some((first, _)), Some((last, _))) if first != last => format!(
+            "{} – {}",
+            first.format("%-d %B %Y"),
+            last.format("%-d %B %Y")
+        ),
+        (Some((first, _)), _) => first.format("%-d %B %Y").to_string(),
+        _ => String::new(),
+    };
+    typesetter.title_page(title, &range);
+
+    let mut days = Vec::new();
+    for (date, content) in entries {
+        days.push((*date, typesetter.pages.len()));
+        typesetter.title_page(
+            &date.format("%A").to_string(),
+            &date.format("%-d %B %Y").to_string(),
+        );
+        typesetter.new_page();
+        typesetter.markdown(content);
+    }
+
+    write(title, &typesetter.pages, &days)
+}
+
+/// Serialize the pages, with an outline entry for each (date, page index)
+fn write(title: &str, pages: &[Page], days: &[(NaiveDate, usize)]) -> Vec<u8> {
+    let mut next_id = Ref::new(1);
+    let catalog_id = next_id.bump();
+    let page_tree_id = next_id.bump();
+    let outline_id = next_id.bump();
+    let info_id = next_id.bump();
+    let font_ids: Vec<Ref> = Font::ALL.iter().map(|_| next_id.bump()).collect();
+    let page_ids: Vec<(Ref, Ref)> = pages
+        .iter()
+        .map(|_| (next_id.bump(), next_id.bump()))
+        .collect();
+    let item_ids: Vec<Ref> = days.iter().map(|_| next_id.bump()).collect();
+
+    let mut pdf = Pdf::new();
+    let mut catalog = pdf.catalog(catalog_id);
+    catalog.pages(page_tree_id);
+    if !days.is_empty() {
+        catalog.outlines(outline_id);
+    }
+    catalog.finish();
+    pdf.document_info(info_id)
+        .title(TextStr(title))
+        .producer(TextStr("easy_journal"));
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().map(|(page_id, _)| *page_id))
+        .count(pages.len() as i32);
+    for (font, font_id) in Font::ALL.iter().zip(&font_ids) {
+        pdf.type1_font(*font_id)
+            .base_font(font.base_font())
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+    }
+
+    for (page, (page_id, content_id)) in pages.iter().zip(&page_ids) {
+        let mut writer = pdf.page(*page_id);
+        writer
+            .parent(page_tree_id)
+            .media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+            .contents(*content_id);
+        {
+            let mut resources = writer.resources();
+            let mut fonts = resources.fonts();
+            for (font, font_id) in Font::ALL.iter().zip(&font_ids) {
+                fonts.pair(font.resource(), *font_id);
+            }
+        }
+        if !page.links.is_empty() {
+            let mut annotations = writer.annotations();
+            for (rect, url) in &page.links {
+                let mut annotation = annotations.push();
+                annotation
+                    .subtype(AnnotationType::Link)
+                    .rect(*rect)
+                    .border(0.0, 0.0, 0.0, None);
+                annotation
+                    .action()
+                    .action_type(ActionType::Uri)
+                    .uri(Str(url.as_bytes()));
+            }
+        }
+        writer.finish();
+        pdf.stream(*content_id, &page.content());
+    }
+
+    if let (Some(first), Some(last)) = (item_ids.first(), item_ids.last()) {
+        pdf.outline(outline_id)
+            .first(*first)
+            .last(*last)
+            .count(days.len() as i32);
+        for (i, ((date, page), item_id)) in days.iter().zip(&item_ids).enumerate() {
+            let mut item = pdf.outline_item(*item_id);
+            item.title(TextStr(&date.format("%Y-%m-%d %A").to_string()))
+                .parent(outline_id);
+            if i > 0 {
+                item.prev(item_ids[i - 1]);
+            }
+            if let Some(next) = item_ids.get(i + 1) {
+                item.next(*next);
+            }
+            item.dest()
+                .page(page_ids[*page].0)
+                .xyz(0.0, PAGE_HEIGHT, None);
+        }
+    }
+
+    pdf.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let date = |day| NaiveDate::from_ymd_opt(2025, 12, day).unwrap();
+        let long = "word ".repeat(1200);
+        let entries = vec![
+            (
+                date(29),
+                format!(
+                    "# Monday\n\n## Goals for Today\n- [x] Done\n- [ ] Open\n\n{}\n\nSee [the docs](https://example.com/docs) or [yesterday](../28.md) 🎉\n",
+                    long
+                ),
+            ),
+            (date(30), "# Tuesday\n\n- Caf\u{e9} – ok\n".to_string()),
+        ];
+        let pdf = build("Journal", &entries);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with(b"%PDF-"));
+        // Cover, then a title page and the entry for each day; the long
+        // paragraph runs over onto a second page
+        assert!(text.contains("/Count 6"));
+        assert!(text.contains("/URI (https://example.com/docs)"));
+        assert!(!text.contains("28.md"));
+        assert!(text.contains("(2025-12-30 Tuesday)"));
+
+        assert_eq!(encode("Café – ok 🎉"), b"Caf\xe9 \x96 ok ".to_vec());
+    }
+}
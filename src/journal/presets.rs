@@ -0,0 +1,421 @@
+/// Daily, month and year templates written together by `init --preset`
+pub struct Templates {
+    pub daily: &'static str,
+    pub month: &'static str,
+    pub year: &'static str,
+}
+
+/// The full default layout: work log, reflections and a metrics footer
+pub const DEFAULT: Templates = Templates {
+    daily: r#"# {{date}} - {{day_of_week}}
+
+## Reminders
+{{reminders}}
+
+## Goals for Today
+- [ ]
+- [ ]
+- [ ]
+
+## Work Accomplished
+
+### Morning
+-
+
+### Afternoon
+-
+
+### Evening
+-
+
+## Learning & Insights
+-
+
+## Challenges & Blockers
+-
+
+## Gratitude & Wins
+-
+
+## Tomorrow's Focus
+-
+
+---
+
+**Sleep Score(1-100)**:
+
+**Sleep Time**:
+
+**Mood(1-10)**:
+
+**Energy Level(1-10)**:
+
+**Hours Worked**:
+"#,
+    month: r#"# {{month}} {{year}}
+
+## Goals for this month
+- [ ]
+- [ ]
+- [ ]
+
+## Key Projects & Focus Areas
+
+### Project 1
+
+
+### Project 2
+
+
+## Reflections & Learnings
+
+
+## Highlights & Accomplishments
+
+
+---
+
+**Month Started**:
+**Month Rating (1-10)**:
+"#,
+    year: r#"# Year in Review: {{year}}
+
+## Goals for the Year
+
+### Professional Goals
+- [ ]
+- [ ]
+- [ ]
+
+### Personal Goals
+- [ ]
+- [ ]
+- [ ]
+
+### Health & Wellness Goals
+- [ ]
+- [ ]
+
+## Themes or Focus Areas
+
+### Theme 1:
+
+
+### Theme 2:
+
+
+### Theme 3:
+
+
+## Highlights & Accomplishments
+
+### Q1 (Jan-Mar)
+
+
+### Q2 (Apr-Jun)
+
+
+### Q3 (Jul-Sep)
+
+
+### Q4 (Oct-Dec)
+
+
+## Challenges & Growth
+
+
+## Lessons Learned
+
+
+---
+
+**Year Started**:
+**Overall Year Rating (1-10)**:
+"#,
+};
+
+/// Just goals, notes and tomorrow's focus
+pub const MINIMAL: Templates = Templates {
+    daily: r#"# {{date}} - {{day_of_week}}
+
+{{reminders}}
+
+## Goals for Today
+- [ ]
+
+## Notes
+-
+
+## Tomorrow's Focus
+-
+"#,
+    month: r#"# {{month}} {{year}}
+
+## Goals for this month
+- [ ]
+
+## Notes
+
+"#,
+    year: r#"# {{year}}
+
+## Goals for the Year
+- [ ]
+
+## Notes
+
+"#,
+};
+
+/// Gratitude and intentions in the morning, wins and improvements at night
+pub const FIVE_MINUTE_JOURNAL: Templates = Templates {
+    daily: r#"# {{date}} - {{day_of_week}}
+
+## Morning
+
+### I am grateful for...
+1.
+2.
+3.
+
+### Goals for Today: what would make today great?
+- [ ]
+- [ ]
+- [ ]
+
+### Daily affirmation: I am...
+-
+
+## Reminders
+{{reminders}}
+
+## Evening
+
+### Gratitude & Wins: three amazing things that happened today
+1.
+2.
+3.
+
+### How could I have made today even better?
+-
+
+## Tomorrow's Focus
+-
+
+---
+
+**Mood(1-10)**:
+"#,
+    month: r#"# {{month}} {{year}}
+
+## Goals for this month
+- [ ]
+- [ ]
+- [ ]
+
+## What I was most grateful for
+
+
+## Best moments
+
+
+## What I'd do differently
+
+
+---
+
+**Month Rating (1-10)**:
+"#,
+    year: r#"# {{year}}
+
+## Goals for the Year
+- [ ]
+- [ ]
+- [ ]
+
+## What I was most grateful for
+
+
+## Best moments
+
+
+## Who I became this year
+
+
+---
+
+**Overall Year Rating (1-10)**:
+"#,
+};
+
+/// Yesterday / today / blockers, ready to paste into a standup
+pub const ENGINEER_STANDUP: Templates = Templates {
+    daily: r#"# {{date}} - {{day_of_week}}
+
+## Reminders
+{{reminders}}
+
+## Yesterday
+-
+
+## Goals for Today
+- [ ]
+- [ ]
+
+## Challenges & Blockers
+-
+
+## Work Accomplished
+-
+
+## Learning & Insights
+-
+
+## Tomorrow's Focus
+-
+
+---
+
+**Hours Worked**:
+"#,
+    month: r#"# {{month}} {{year}}
+
+## Goals for this month
+- [ ]
+- [ ]
+
+## Shipped
+
+
+## Incidents & Lessons
+
+
+## Next month
+
+
+---
+
+**Month Rating (1-10)**:
+"#,
+    year: r#"# {{year}} Engineering Review
+
+## Goals for the Year
+- [ ]
+- [ ]
+
+## Shipped
+
+### Q1 (Jan-Mar)
+
+
+### Q2 (Apr-Jun)
+
+
+### Q3 (Jul-Sep)
+
+
+### Q4 (Oct-Dec)
+
+
+## Skills & Growth
+
+
+## Lessons Learned
+
+"#,
+};
+
+/// Rapid logging of tasks, events and notes, with monthly and future logs
+pub const BULLET_JOURNAL: Templates = Templates {
+    daily: r#"# {{date}} - {{day_of_week}}
+
+{{reminders}}
+
+## Goals for Today
+- [ ]
+
+## Events
+-
+
+## Notes
+-
+
+## Tomorrow's Focus
+-
+"#,
+    month: r#"# {{month}} {{year}}
+
+## Calendar
+
+
+## Goals for this month
+- [ ]
+
+## Migrated
+
+
+"#,
+    year: r#"# Future Log {{year}}
+
+## Goals for the Year
+- [ ]
+
+## January
+
+
+## February
+
+
+## March
+
+
+## April
+
+
+## May
+
+
+## June
+
+
+## July
+
+
+## August
+
+
+## September
+
+
+## October
+
+
+## November
+
+
+## December
+
+"#,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_keep_carried_over_sections() {
+        for preset in [
+            DEFAULT,
+            MINIMAL,
+            FIVE_MINUTE_JOURNAL,
+            ENGINEER_STANDUP,
+            BULLET_JOURNAL,
+        ] {
+            // Unchecked goals and tomorrow's focus carry over, month goals feed `{{month_goals}}`
+            assert!(preset.daily.contains("Goals for Today"));
+            assert!(preset.daily.contains("## Tomorrow's Focus"));
+            assert!(preset.daily.contains("{{reminders}}"));
+            assert!(preset.month.contains("## Goals for this month"));
+        }
+    }
+}
@@ -0,0 +1,390 @@
+use chrono::{Datelike, Days, NaiveDate};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::error::Result;
+use crate::journal::filesystem::Layout;
+use crate::journal::{digest, parser};
+
+/// How far back entries are read when looking for patterns
+pub const LOOKBACK_DAYS: u64 = 14;
+
+/// Days with "Challenges & Blockers" filled in before a retrospective is suggested
+const BLOCKER_STREAK: usize = 3;
+
+/// Times an unchecked task has to be carried over before it is called out
+const STALLED_TASK_DAYS: usize = 3;
+
+/// Days without an entry that count as a break
+const GAP_DAYS: i64 = 4;
+
+/// Mood or energy (out of 10) at or below this is considered low
+const LOW_SCORE: f32 = 4.0;
+
+/// Drop in average mood (out of 10) that counts as a dip
+const MOOD_DROP: f32 = 2.0;
+
+/// What about recent entries made a prompt come up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    RecurringBlockers,
+    MoodDip,
+    LowEnergy,
+    StalledTask,
+    Gap,
+    Daily,
+}
+
+impl Trigger {
+    fn prompts(self) -> &'static [&'static str] {
+        match self {
+            Trigger::RecurringBlockers => &[
+                "The same kind of blocker keeps coming up. What do they have in common, and what would remove the root cause?",
+                "Look back at this week's blockers: which one could you have escalated or dropped earlier?",
+                "If a friend described these blockers to you, what would you tell them to try first?",
+            ],
+            Trigger::MoodDip => &[
+                "Name three small things that went right today, however minor.",
+                "Who or what are you grateful for right now, and why?",
+                "What is one thing that reliably lifts your mood, and can you make room for it today?",
+            ],
+            Trigger::LowEnergy => &[
+                "Your energy has been low for a few days. What is draining it, and what could you say no to?",
+                "How have you been sleeping, eating and moving lately? What is one thing to change this week?",
+            ],
+            Trigger::StalledTask => &[
+                "What is really stopping you from finishing it, and does it still matter?",
+                "What is the smallest next step, and can it be done in 15 minutes?",
+            ],
+            Trigger::Gap => &[
+                "What happened while you weren't writing that you want to remember?",
+                "What has changed since your last entry?",
+            ],
+            Trigger::Daily => &[
+                "What are you looking forward to today?",
+                "What did you learn recently that surprised you?",
+                "What would make today a good day?",
+                "What is one thing you want to do differently than yesterday?",
+                "What conversation has stayed on your mind, and why?",
+                "What are you avoiding, and what is the first step to face it?",
+                "What are you proud of from the past week?",
+            ],
+        }
+    }
+
+    /// Pick a prompt for `date`; the same day always gets the same prompt
+    fn prompt_for(self, date: NaiveDate) -> &'static str {
+        let prompts = self.prompts();
+        prompts[date.num_days_from_ce().unsigned_abs() as usize % prompts.len()]
+    }
+}
+
+/// A prompt to write about, with the reason it was picked
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Suggestion {
+    pub trigger: Trigger,
+    pub prompt: String,
+    pub reason: String,
+}
+
+/// The parts of an entry the rules look at
+#[derive(Debug, Clone, PartialEq)]
+struct DayState {
+    date: NaiveDate,
+    blockers: bool,
+    mood: Option<f32>,
+    energy: Option<f32>,
+    open_tasks: Vec<String>,
+}
+
+/// Read a score like `7`, `7/10` or `3/5 - tired` as a value out of 10
+pub(crate) fn parse_score(value: &str) -> Option<f32> {
+    let number = |s: &str| -> Option<f32> {
+        let digits: String = s
+            .trim()
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        digits.parse().ok()
+    };
+    let score = number(value)?;
+    let scale = value
+        .split_once('/')
+        .and_then(|(_, max)| number(max))
+        .filter(|max| *max > 0.0)
+        .unwrap_or(10.0);
+    Some(score / scale * 10.0)
+}
+
+fn day_state(date: NaiveDate, content: &str) -> DayState {
+    let blockers = parser::split_sections(content).iter().any(|section| {
+        let header = section.header.to_lowercase();
+        // The `---` metrics footer belongs to the last section
+        (header.contains("challenge") || header.contains("blocker"))
+            && section
+                .lines
+                .iter()
+                .take_while(|l| l.trim() != "---")
+                .any(|l| !digest::is_placeholder(l))
+    });
+
+    let metric = |name: &str| {
+        parser::extract_metrics(content)
+            .into_iter()
+            .find(|(key, _)| key.to_lowercase().starts_with(name))
+            .and_then(|(_, value)| parse_score(&value))
+    };
+
+    DayState {
+        date,
+        blockers,
+        mood: metric("mood"),
+        energy: metric("energy"),
+        open_tasks: parser::extract_tasks(content)
+            .into_iter()
+            .filter(|(_, done)| !done)
+            .map(|(text, _)| text)
+            .collect(),
+    }
+}
+
+fn mean(values: &[f32]) -> f32 {
+    values.iter().sum::<f32>() / values.len() as f32
+}
+
+/// Blockers written down on consecutive days, ending at the latest entry
+fn blocker_streak(days: &[DayState]) -> usize {
+    let mut streak = 0;
+    let mut expected = None;
+    for day in days.iter().rev() {
+        if !day.blockers || expected.is_some_and(|date| date != day.date) {
+            break;
+        }
+        streak += 1;
+        expected = day.date.pred_opt();
+    }
+    streak
+}
+
+fn suggest_from(date: NaiveDate, days: &[DayState]) -> Vec<Suggestion> {
+    let mut suggestions = Vec::new();
+    let suggestion = |trigger: Trigger, reason: String| Suggestion {
+        trigger,
+        prompt: trigger.prompt_for(date).to_string(),
+        reason,
+    };
+
+    let Some(latest) = days.last() else {
+        return vec![suggestion(Trigger::Daily, "Daily prompt".to_string())];
+    };
+    let since_latest = (date - latest.date).num_days();
+
+    if since_latest >= GAP_DAYS {
+        suggestions.push(suggestion(
+            Trigger::Gap,
+            format!("No entries for {} days", since_latest),
+        ));
+    }
+
+    // Only look for patterns in what is still fresh
+    if since_latest <= 2 {
+        let streak = blocker_streak(days);
+        if streak >= BLOCKER_STREAK {
+            suggestions.push(suggestion(
+                Trigger::RecurringBlockers,
+                format!("Challenges & Blockers filled in {} days running", streak),
+            ));
+        }
+
+        let moods: Vec<f32> = days.iter().filter_map(|d| d.mood).collect();
+        let recent = &moods[moods.len().saturating_sub(3)..];
+        let earlier = &moods[..moods.len().saturating_sub(3)];
+        if let Some(last) = latest.mood
+            && last <= LOW_SCORE
+        {
+            suggestions.push(suggestion(
+                Trigger::MoodDip,
+                format!("Mood was {:.0}/10 last time", last),
+            ));
+        } else if !recent.is_empty()
+            && earlier.len() >= 2
+            && mean(earlier) - mean(recent) >= MOOD_DROP
+        {
+            suggestions.push(suggestion(
+                Trigger::MoodDip,
+                format!(
+                    "Mood dropped from {:.1} to {:.1} on average",
+                    mean(earlier),
+                    mean(recent)
+                ),
+            ));
+        }
+
+        let energies: Vec<f32> = days.iter().rev().take(3).filter_map(|d| d.energy).collect();
+        if energies.len() == 3 && energies.iter().all(|e| *e <= LOW_SCORE) {
+            suggestions.push(suggestion(
+                Trigger::LowEnergy,
+                "Energy at or below 4/10 for the last 3 entries".to_string(),
+            ));
+        }
+
+        // Unfinished tasks are carried forward, so a stalled task shows up
+        // unchecked in several entries in a row
+        let mut carried: HashMap<&str, usize> = HashMap::new();
+        for day in days {
+            for task in &day.open_tasks {
+                *carried.entry(task.as_str()).or_default() += 1;
+            }
+        }
+        let stalled = latest
+            .open_tasks
+            .iter()
+            .map(|task| (task, carried[task.as_str()]))
+            .filter(|(_, count)| *count >= STALLED_TASK_DAYS)
+            .max_by_key(|(_, count)| *count);
+        if let Some((task, count)) = stalled {
+            suggestions.push(Suggestion {
+                trigger: Trigger::StalledTask,
+                prompt: format!(
+                    "\"{}\" has been carried over {} times. {}",
+                    task,
+                    count,
+                    Trigger::StalledTask.prompt_for(date)
+                ),
+                reason: format!("Unchecked in {} entries", count),
+            });
+        }
+    }
+
+    if suggestions.is_empty() {
+        suggestions.push(suggestion(Trigger::Daily, "Daily prompt".to_string()));
+    }
+    suggestions
+}
+
+/// Suggest prompts for the entry on `date`, based on the entries of the
+/// [`LOOKBACK_DAYS`] before it
+pub fn suggest(journal_dir: &Path, layout: Layout, date: NaiveDate) -> Result<Vec<Suggestion>> {
+    let (Some(since), Some(until)) = (
+        date.checked_sub_days(Days::new(LOOKBACK_DAYS)),
+        date.pred_opt(),
+    ) else {
+        return Ok(suggest_from(date, &[]));
+    };
+    let days: Vec<DayState> = digest::read_entries(journal_dir, layout, since, until)?
+        .iter()
+        .map(|(date, content)| day_state(*date, content))
+        .collect();
+    Ok(suggest_from(date, &days))
+}
+
+/// Render suggestions as a markdown list for the `prompts` section
+pub fn to_markdown(suggestions: &[Suggestion]) -> String {
+    suggestions
+        .iter()
+        .map(|s| match s.trigger {
+            Trigger::Daily => format!("- {}", s.prompt),
+            _ => format!("- {} _({})_", s.prompt, s.reason),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 12, day).unwrap()
+    }
+
+    fn entry(blockers: &str, mood: &str, energy: &str, tasks: &str) -> String {
+        format!(
+            "# Entry\n\n## Goals for Today\n{}\n\n## Challenges & Blockers\n{}\n\n---\n\n\
+             **Mood**: {}\n**Energy Level**: {}\n",
+            tasks, blockers, mood, energy
+        )
+    }
+
+    fn triggers(suggestions: &[Suggestion]) -> Vec<Trigger> {
+        suggestions.iter().map(|s| s.trigger).collect()
+    }
+
+    #[test]
+    fn test_parse_score() {
+        assert_eq!(parse_score("7"), Some(7.0));
+        assert_eq!(parse_score("3/5 - tired"), Some(6.0));
+        assert_eq!(parse_score(""), None);
+        assert_eq!(parse_score("good"), None);
+    }
+
+    #[test]
+    fn test_day_state_ignores_template_placeholders() {
+        let state = day_state(date(1), &entry("-", "", "", "- [ ]"));
+        assert!(!state.blockers);
+        assert_eq!(state.mood, None);
+        assert!(state.open_tasks.is_empty());
+
+        let state = day_state(
+            date(1),
+            &entry("- CI is flaky", "8", "5/10", "- [ ] Ship it"),
+        );
+        assert!(state.blockers);
+        assert_eq!(state.mood, Some(8.0));
+        assert_eq!(state.energy, Some(5.0));
+        assert_eq!(state.open_tasks, vec!["Ship it".to_string()]);
+    }
+
+    #[test]
+    fn test_blockers_three_days_running() {
+        let days: Vec<DayState> = (1..=3)
+            .map(|d| day_state(date(d), &entry("- CI is flaky", "7", "7", "")))
+            .collect();
+        assert_eq!(
+            triggers(&suggest_from(date(4), &days)),
+            vec![Trigger::RecurringBlockers]
+        );
+
+        // A missing day breaks the streak
+        assert_eq!(blocker_streak(&[days[0].clone(), days[2].clone()]), 1);
+    }
+
+    #[test]
+    fn test_mood_dip_and_stalled_task() {
+        let days = vec![
+            day_state(date(1), &entry("-", "8", "", "- [ ] Call the bank")),
+            day_state(date(2), &entry("-", "9", "", "- [ ] Call the bank")),
+            day_state(date(3), &entry("-", "6", "", "- [ ] Call the bank")),
+            day_state(date(4), &entry("-", "6", "", "- [x] Call the bank")),
+            day_state(date(5), &entry("-", "5", "", "")),
+        ];
+        let suggestions = suggest_from(date(6), &days);
+        assert_eq!(triggers(&suggestions), vec![Trigger::MoodDip]);
+
+        let mut stalled = days[..3].to_vec();
+        stalled[2].mood = Some(3.0);
+        let suggestions = suggest_from(date(4), &stalled);
+        assert_eq!(
+            triggers(&suggestions),
+            vec![Trigger::MoodDip, Trigger::StalledTask]
+        );
+        assert!(
+            suggestions[1]
+                .prompt
+                .starts_with("\"Call the bank\" has been carried over 3 times.")
+        );
+    }
+
+    #[test]
+    fn test_gap_and_daily_fallback() {
+        let days = vec![day_state(date(1), &entry("- Stuck", "2", "", ""))];
+        assert_eq!(triggers(&suggest_from(date(10), &days)), vec![Trigger::Gap]);
+        assert_eq!(triggers(&suggest_from(date(10), &[])), vec![Trigger::Daily]);
+        assert_eq!(
+            suggest_from(date(10), &[])[0].prompt,
+            Trigger::Daily.prompt_for(date(10))
+        );
+    }
+}
@@ -0,0 +1,633 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::ProofConfig;
+use crate::error::{JournalError, Result};
+
+/// Directories searched for `<language>.dic` / `.aff` when PROOF_DICTIONARY is not set
+const DICTIONARY_DIRS: &[&str] = &[
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+    "/usr/local/share/hunspell",
+    "/opt/homebrew/share/hunspell",
+    "/Library/Spelling",
+];
+
+/// Most suggestions shown for a misspelled word
+const MAX_SUGGESTIONS: usize = 3;
+
+/// One part of an affix condition such as `[^aeiou]y`
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Any,
+    Char(char),
+    Class { negated: bool, chars: Vec<char> },
+}
+
+impl Condition {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Condition::Any => true,
+            Condition::Char(expected) => *expected == c,
+            Condition::Class { negated, chars } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+fn parse_condition(condition: &str) -> Vec<Condition> {
+    let mut parts = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => parts.push(Condition::Any),
+            '[' => {
+                let mut class: Vec<char> = chars.by_ref().take_while(|c| *c != ']').collect();
+                let negated = class.first() == Some(&'^');
+                if negated {
+                    class.remove(0);
+                }
+                parts.push(Condition::Class {
+                    negated,
+                    chars: class,
+                });
+            }
+            c => parts.push(Condition::Char(c)),
+        }
+    }
+    parts
+}
+
+/// A prefix or suffix rule from the `.aff` file
+#[derive(Debug, Clone)]
+struct AffixRule {
+    strip: String,
+    add: String,
+    condition: Vec<Condition>,
+}
+
+impl AffixRule {
+    fn apply_suffix(&self, word: &str) -> Option<String> {
+        let chars: Vec<char> = word.chars().collect();
+        let n = self.condition.len();
+        if chars.len() < n || !word.ends_with(&self.strip) {
+            return None;
+        }
+        let tail = &chars[chars.len() - n..];
+        if !self
+            .condition
+            .iter()
+            .zip(tail)
+            .all(|(c, ch)| c.matches(*ch))
+        {
+            return None;
+        }
+        Some(format!(
+            "{}{}",
+            &word[..word.len() - self.strip.len()],
+            self.add
+        ))
+    }
+
+    fn apply_prefix(&self, word: &str) -> Option<String> {
+        let chars: Vec<char> = word.chars().collect();
+        if chars.len() < self.condition.len() || !word.starts_with(&self.strip) {
+            return None;
+        }
+        if !self
+            .condition
+            .iter()
+            .zip(&chars)
+            .all(|(c, ch)| c.matches(*ch))
+        {
+            return None;
+        }
+        Some(format!("{}{}", self.add, &word[self.strip.len()..]))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Affix {
+    cross_product: bool,
+    rules: Vec<AffixRule>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FlagFormat {
+    Char,
+    Long,
+    Numeric,
+}
+
+fn split_flags(flags: &str, format: FlagFormat) -> Vec<String> {
+    match format {
+        FlagFormat::Char => flags.chars().map(String::from).collect(),
+        FlagFormat::Long => flags
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .map(|pair| pair.iter().collect())
+            .collect(),
+        FlagFormat::Numeric => flags.split(',').map(|f| f.trim().to_string()).collect(),
+    }
+}
+
+/// Older dictionaries are often ISO-8859-1 rather than UTF-8
+fn read_dictionary_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).map_err(JournalError::at_path(path))?;
+    Ok(String::from_utf8(bytes)
+        .unwrap_or_else(|e| e.into_bytes().iter().map(|&b| b as char).collect()))
+}
+
+/// A spelling dictionary in Hunspell format: the `.dic` word list with every
+/// form produced by the prefix and suffix rules of the `.aff` file
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    /// Build a dictionary from the contents of an `.aff` and a `.dic` file
+    pub fn parse(aff: &str, dic: &str) -> Dictionary {
+        let mut format = FlagFormat::Char;
+        let mut prefixes: HashMap<String, Affix> = HashMap::new();
+        let mut suffixes: HashMap<String, Affix> = HashMap::new();
+
+        for line in aff.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["FLAG", "long", ..] => format = FlagFormat::Long,
+                ["FLAG", "num", ..] => format = FlagFormat::Numeric,
+                [kind @ ("PFX" | "SFX"), flag, cross, count] if count.parse::<usize>().is_ok() => {
+                    let table = if *kind == "PFX" {
+                        &mut prefixes
+                    } else {
+                        &mut suffixes
+                    };
+                    table.entry(flag.to_string()).or_default().cross_product = *cross == "Y";
+                }
+                [kind @ ("PFX" | "SFX"), flag, strip, add, condition, ..] => {
+                    let table = if *kind == "PFX" {
+                        &mut prefixes
+                    } else {
+                        &mut suffixes
+                    };
+                    // Continuation flags after `/` are not supported
+                    let add = add.split('/').next().unwrap_or_default();
+                    let zero = |s: &str| {
+                        if s == "0" {
+                            String::new()
+                        } else {
+                            s.to_string()
+                        }
+                    };
+                    table
+                        .entry(flag.to_string())
+                        .or_default()
+                        .rules
+                        .push(AffixRule {
+                            strip: zero(strip),
+                            add: zero(add),
+                            condition: parse_condition(condition),
+                        });
+                }
+                _ => {}
+            }
+        }
+
+        let mut words = HashSet::new();
+        // The first line of a .dic file is the word count
+        for line in dic.lines().skip(1) {
+            let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+            let (word, flags) = entry.split_once('/').unwrap_or((entry, ""));
+            if word.is_empty() {
+                continue;
+            }
+            let flags = split_flags(flags, format);
+
+            let mut suffixed = Vec::new();
+            for flag in &flags {
+                if let Some(affix) = suffixes.get(flag) {
+                    for rule in &affix.rules {
+                        if let Some(form) = rule.apply_suffix(word) {
+                            suffixed.push((form, affix.cross_product));
+                        }
+                    }
+                }
+            }
+            for flag in &flags {
+                let Some(affix) = prefixes.get(flag) else {
+                    continue;
+                };
+                for rule in &affix.rules {
+                    words.extend(rule.apply_prefix(word));
+                    if affix.cross_product {
+                        words.extend(
+                            suffixed
+                                .iter()
+                                .filter(|(_, cross)| *cross)
+                                .filter_map(|(form, _)| rule.apply_prefix(form)),
+                        );
+                    }
+                }
+            }
+            words.extend(suffixed.into_iter().map(|(form, _)| form));
+            words.insert(word.to_string());
+        }
+
+        Dictionary { words }
+    }
+
+    /// Load `<name>.dic` and the `<name>.aff` next to it
+    pub fn load(dic_path: &Path) -> Result<Dictionary> {
+        let aff_path = dic_path.with_extension("aff");
+        Ok(Dictionary::parse(
+            &read_dictionary_file(&aff_path)?,
+            &read_dictionary_file(dic_path)?,
+        ))
+    }
+
+    /// Locate the dictionary: PROOF_DICTIONARY, or `<PROOF_LANGUAGE>.dic` in
+    /// DICPATH and the usual Hunspell directories. `None` when there is none.
+    pub fn find(config: &ProofConfig) -> Result<Option<Dictionary>> {
+        if let Some(path) = &config.dictionary {
+            return Dictionary::load(path).map(Some);
+        }
+
+        let mut dirs: Vec<PathBuf> = std::env::var("DICPATH")
+            .map(|paths| std::env::split_paths(&paths).collect())
+            .unwrap_or_default();
+        if let Ok(home) = std::env::var("HOME") {
+            dirs.push(Path::new(&home).join("Library/Spelling"));
+        }
+        dirs.extend(DICTIONARY_DIRS.iter().map(PathBuf::from));
+
+        let file = format!("{}.dic", config.language);
+        match dirs.iter().map(|d| d.join(&file)).find(|p| p.exists()) {
+            Some(path) => {
+                tracing::debug!(path = %path.display(), "loading dictionary");
+                Dictionary::load(&path).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Add words from a personal word list, one per line
+    pub fn add_words(&mut self, list: &str) {
+        self.words.extend(
+            list.lines()
+                .map(str::trim)
+                .filter(|w| !w.is_empty() && !w.starts_with('#'))
+                .map(String::from),
+        );
+    }
+
+    /// Whether `word` is spelled correctly. A capitalized word is also accepted
+    /// in lowercase (sentence starts), and all-caps words are taken as acronyms.
+    pub fn check(&self, word: &str) -> bool {
+        if self.words.contains(word) || word.chars().all(|c| !c.is_lowercase()) {
+            return true;
+        }
+        let mut chars = word.chars();
+        let lowered: String = match chars.next() {
+            Some(first) => first.to_lowercase().chain(chars).collect(),
+            None => return true,
+        };
+        self.words.contains(&lowered) || self.words.contains(&word.to_lowercase())
+    }
+
+    /// Dictionary words one edit (insert, delete, replace, swap) away from `word`
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let lowered = word.to_lowercase();
+        let chars: Vec<char> = lowered.chars().collect();
+        let mut letters: Vec<char> = ('a'..='z').collect();
+        letters.extend(chars.iter().filter(|c| !c.is_ascii_lowercase()));
+
+        let mut candidates = Vec::new();
+        for i in 0..=chars.len() {
+            let (head, tail) = chars.split_at(i);
+            if !tail.is_empty() {
+                candidates.push([head, &tail[1..]].concat());
+            }
+            if tail.len() > 1 {
+                candidates.push([head, &[tail[1], tail[0]], &tail[2..]].concat());
+            }
+            for letter in &letters {
+                if !tail.is_empty() {
+                    candidates.push([head, &[*letter], &tail[1..]].concat());
+                }
+                candidates.push([head, &[*letter], tail].concat());
+            }
+        }
+
+        let mut suggestions: Vec<String> = Vec::new();
+        for candidate in candidates {
+            let candidate: String = candidate.into_iter().collect();
+            if candidate != lowered
+                && self.words.contains(&candidate)
+                && !suggestions.contains(&candidate)
+            {
+                suggestions.push(candidate);
+                if suggestions.len() == MAX_SUGGESTIONS {
+                    break;
+                }
+            }
+        }
+        suggestions
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    Spelling,
+    RepeatedWord,
+    LongSentence,
+}
+
+impl IssueKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            IssueKind::Spelling => "spelling",
+            IssueKind::RepeatedWord => "repeated word",
+            IssueKind::LongSentence => "long sentence",
+        }
+    }
+}
+
+/// A problem found in an entry; `line` and `column` are 1-based
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Issue {
+    pub line: usize,
+    pub column: usize,
+    /// Length of the flagged text in characters
+    pub length: usize,
+    pub kind: IssueKind,
+    pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+}
+
+/// A word in a line: its text and character column
+struct Word<'a> {
+    text: &'a str,
+    column: usize,
+}
+
+/// Blank out what isn't prose: inline code, link targets, URLs, tags and mentions
+fn prose(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_code = false;
+    let mut in_link_target = false;
+    let mut skip_token = false;
+    let mut prev = ' ';
+
+    for (i, c) in line.char_indices() {
+        if c == '`' {
+            in_code = !in_code;
+        } else if !in_code && prev == ']' && c == '(' {
+            in_link_target = true;
+        } else if in_link_target && c == ')' {
+            in_link_target = false;
+        } else if !in_code && !in_link_target && prev.is_whitespace() || i == 0 {
+            let rest = &line[i..];
+            skip_token = matches!(c, '#' | '@')
+                || rest.starts_with("http://")
+                || rest.starts_with("https://")
+                || rest.starts_with("www.");
+        }
+        if c.is_whitespace() {
+            skip_token = false;
+        }
+
+        let blank = c == '`' || in_code || in_link_target || skip_token;
+        out.push(if blank { ' ' } else { c });
+        prev = c;
+    }
+    out
+}
+
+/// Words made of letters with inner apostrophes, e.g. "don't"
+fn words(line: &str) -> Vec<Word<'_>> {
+    let mut words = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut column = 0;
+
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    for (index, &(i, c)) in chars.iter().enumerate() {
+        column += 1;
+        let next_is_letter = chars.get(index + 1).is_some_and(|(_, n)| n.is_alphabetic());
+        let part_of_word =
+            c.is_alphanumeric() || (matches!(c, '\'' | '’') && start.is_some() && next_is_letter);
+        match (part_of_word, start) {
+            (true, None) => start = Some((i, column)),
+            (false, Some((begin, col))) => {
+                words.push(Word {
+                    text: &line[begin..i],
+                    column: col,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((begin, col)) = start {
+        words.push(Word {
+            text: &line[begin..],
+            column: col,
+        });
+    }
+    words
+}
+
+/// Sentences in a line as (column, word count), split after `.`, `!` and `?`
+fn sentences(line: &str) -> Vec<(usize, usize)> {
+    let mut sentences = Vec::new();
+    let mut start_column = None;
+    let mut count = 0;
+    let mut in_word = false;
+
+    let chars: Vec<char> = line.chars().collect();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_alphanumeric() {
+            if !in_word {
+                count += 1;
+                start_column.get_or_insert(i + 1);
+            }
+            in_word = true;
+        } else {
+            in_word = false;
+        }
+        let ends = matches!(c, '.' | '!' | '?')
+            && chars.get(i + 1).is_none_or(|next| next.is_whitespace());
+        if ends && let Some(column) = start_column.take() {
+            sentences.push((column, count));
+            count = 0;
+        }
+    }
+    if let Some(column) = start_column {
+        sentences.push((column, count));
+    }
+    sentences
+}
+
+/// Check entry prose for misspellings (when a dictionary is given), repeated
+/// words and sentences longer than `max_sentence_words`. Fenced code blocks
+/// are skipped.
+pub fn check(
+    content: &str,
+    dictionary: Option<&Dictionary>,
+    max_sentence_words: usize,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let number = index + 1;
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            continue;
+        }
+
+        let prose = prose(line);
+        let words = words(&prose);
+
+        if let Some(dictionary) = dictionary {
+            for word in &words {
+                let text = word.text.trim_end_matches(['\'', '’']);
+                if text.chars().count() < 2
+                    || text.chars().any(|c| c.is_numeric())
+                    || dictionary.check(&text.replace('’', "'"))
+                {
+                    continue;
+                }
+                issues.push(Issue {
+                    line: number,
+                    column: word.column,
+                    length: text.chars().count(),
+                    kind: IssueKind::Spelling,
+                    message: format!("'{}' is not in the dictionary", text),
+                    suggestions: dictionary.suggest(text),
+                });
+            }
+        }
+
+        for pair in words.windows(2) {
+            let repeated = pair[0].text.eq_ignore_ascii_case(pair[1].text)
+                && pair[1].text.chars().any(|c| c.is_alphabetic());
+            // Only when nothing but spaces separates them
+            let between = prose
+                .chars()
+                .skip(pair[0].column - 1 + pair[0].text.chars().count())
+                .take(pair[1].column - pair[0].column - pair[0].text.chars().count());
+            if repeated && between.clone().count() > 0 && between.clone().all(|c| c == ' ') {
+                issues.push(Issue {
+                    line: number,
+                    column: pair[1].column,
+                    length: pair[1].text.chars().count(),
+                    kind: IssueKind::RepeatedWord,
+                    message: format!("'{}' is repeated", pair[1].text),
+                    suggestions: Vec::new(),
+                });
+            }
+        }
+
+        for (column, count) in sentences(&prose) {
+            if count > max_sentence_words {
+                issues.push(Issue {
+                    line: number,
+                    column,
+                    length: 1,
+                    kind: IssueKind::LongSentence,
+                    message: format!(
+                        "Sentence has {} words (more than {})",
+                        count, max_sentence_words
+                    ),
+                    suggestions: Vec::new(),
+                });
+            }
+        }
+    }
+
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "SET UTF-8\n\n\
+                       PFX U Y 1\n\
+                       PFX U 0 un .\n\n\
+                       SFX D Y 3\n\
+                       SFX D 0 ed [^ey]\n\
+                       SFX D y ied [^aeiou]y\n\
+                       SFX D 0 d e\n\n\
+                       SFX S Y 1\n\
+                       SFX S 0 s .\n";
+    const DIC: &str = "6\nthe\ntry/D\nlock/UDS\nbake/D\nstore/S\nwent\n";
+
+    fn dictionary() -> Dictionary {
+        Dictionary::parse(AFF, DIC)
+    }
+
+    #[test]
+    fn test_affix_expansion() {
+        let dictionary = dictionary();
+        for word in ["tried", "locked", "unlocked", "unlocks", "baked", "stores"] {
+            assert!(dictionary.check(word), "{}", word);
+        }
+        assert!(!dictionary.check("tryed"));
+        assert!(!dictionary.check("unbake"));
+        // Sentence starts and acronyms
+        assert!(dictionary.check("The"));
+        assert!(dictionary.check("NASA"));
+    }
+
+    #[test]
+    fn test_suggest() {
+        assert_eq!(dictionary().suggest("teh"), vec!["the"]);
+        assert_eq!(dictionary().suggest("stroe"), vec!["store"]);
+    }
+
+    #[test]
+    fn test_check_entry() {
+        let mut dictionary = dictionary();
+        dictionary.add_words("I\nto\n# comment\n");
+        let content = "I went to teh store\n\
+                       ```\nlet xyz = 1;\n```\n\
+                       I went to to the `store_fn` [store](https://exmple.com) #errands\n";
+        let issues = check(content, Some(&dictionary), 40);
+
+        let found: Vec<(usize, usize, IssueKind)> =
+            issues.iter().map(|i| (i.line, i.column, i.kind)).collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, 11, IssueKind::Spelling),
+                (5, 11, IssueKind::RepeatedWord)
+            ]
+        );
+        assert_eq!(issues[0].suggestions, vec!["the"]);
+    }
+
+    #[test]
+    fn test_long_sentences_without_dictionary() {
+        let content =
+            "One two three four five. Six seven.\nShort one! And then some more words here";
+        let issues = check(content, None, 4);
+        let found: Vec<(usize, usize)> = issues.iter().map(|i| (i.line, i.column)).collect();
+        assert_eq!(found, vec![(1, 1), (2, 12)]);
+        assert!(issues.iter().all(|i| i.kind == IssueKind::LongSentence));
+    }
+}
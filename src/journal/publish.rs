@@ -0,0 +1,272 @@
+use git2::Repository;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::{filesystem, redact};
+
+/// Where `publish` puts the built book (PUBLISH_TARGET or `--to`)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    /// Force-push to a branch (PUBLISH_BRANCH, default `gh-pages`) of a git
+    /// remote: a remote name of the journal's repository or a URL
+    GhPages { remote: String },
+    /// `rsync -a --delete` to a destination like `user@host:/var/www/journal`
+    Rsync(String),
+    /// `scp -r` to a destination
+    Scp(String),
+    /// Copy into a local directory
+    Local(PathBuf),
+}
+
+impl Target {
+    /// `gh-pages`, `gh-pages:<remote>`, `rsync:<destination>`,
+    /// `scp:<destination>` or a local path
+    pub fn parse(spec: &str) -> Result<Target> {
+        let spec = spec.trim();
+        let target = match spec.split_once(':') {
+            _ if spec == "gh-pages" => Target::GhPages {
+                remote: "origin".to_string(),
+            },
+            Some(("gh-pages", remote)) => Target::GhPages {
+                remote: remote.to_string(),
+            },
+            Some(("rsync", destination)) => Target::Rsync(destination.to_string()),
+            Some(("scp", destination)) => Target::Scp(destination.to_string()),
+            _ => Target::Local(PathBuf::from(spec)),
+        };
+        match &target {
+            Target::GhPages { remote: value } | Target::Rsync(value) | Target::Scp(value)
+                if value.trim().is_empty() =>
+            {
+                Err(JournalError::InvalidConfig(format!(
+                    "Publish target \"{}\" is missing a destination",
+                    spec
+                )))
+            }
+            Target::Local(path) if path.as_os_str().is_empty() => Err(JournalError::InvalidConfig(
+                "Publish target is empty".to_string(),
+            )),
+            _ => Ok(target),
+        }
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::GhPages { remote } => write!(f, "gh-pages:{}", remote),
+            Target::Rsync(destination) => write!(f, "rsync:{}", destination),
+            Target::Scp(destination) => write!(f, "scp:{}", destination),
+            Target::Local(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+fn run(command: &mut Command, what: &str) -> Result<()> {
+    tracing::debug!(?command, "publishing");
+    let output = command
+        .output()
+        .map_err(|e| JournalError::PublishFailed(format!("Could not run {}: {}", what, e)))?;
+    if !output.status.success() {
+        return Err(JournalError::PublishFailed(format!(
+            "{} failed: {}",
+            what,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Redact every markdown file under `dir`
+fn redact_dir(dir: &Path, strip: bool) -> Result<()> {
+    for item in fs::read_dir(dir).map_err(JournalError::at_path(dir))? {
+        let path = item?.path();
+        if path.is_dir() {
+            redact_dir(&path, strip)?;
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+            let redacted = redact::redact(&content, strip);
+            if redacted != content {
+                filesystem::write_atomic(&path, redacted)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copy the book sources (book.toml, the journal, themes) into `staging`,
+/// leaving out hidden files such as `.env`, `.git` and `.easy_journal` and the
+/// built `book/`, and redact the entries unless `keep_private`
+pub fn stage(config: &Config, staging: &Path, keep_private: bool) -> Result<()> {
+    let root = if config.root.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        config.root.as_path()
+    };
+    if !root.join("book.toml").exists() {
+        return Err(JournalError::InvalidConfig(format!(
+            "No book.toml in {}; run `easy_journal init` first",
+            root.display()
+        )));
+    }
+
+    fs::create_dir_all(staging).map_err(JournalError::at_path(staging))?;
+    for item in fs::read_dir(root).map_err(JournalError::at_path(root))? {
+        let path = item?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if name.starts_with('.') || name == "book" {
+            continue;
+        }
+        let target = staging.join(&*name);
+        if path.is_dir() {
+            filesystem::copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target).map_err(JournalError::at_path(&path))?;
+        }
+    }
+
+    if !keep_private {
+        let journal = config
+            .journal_dir
+            .strip_prefix(root)
+            .unwrap_or(&config.journal_dir);
+        let staged = staging.join(journal);
+        if staged.is_dir() {
+            redact_dir(&staged, config.redact_strip)?;
+        }
+    }
+    Ok(())
+}
+
+/// Build the staged book with mdbook into `<staging>/book`
+pub fn build(staging: &Path) -> Result<PathBuf> {
+    let output = staging.join("book");
+    run(
+        Command::new("mdbook")
+            .arg("build")
+            .arg(staging)
+            .arg("--dest-dir")
+            .arg(&output),
+        "mdbook build",
+    )?;
+    Ok(output)
+}
+
+/// URL of `remote` if it names a remote of the journal's repository
+fn remote_url(config: &Config, remote: &str) -> String {
+    Repository::discover(&config.journal_dir)
+        .ok()
+        .and_then(|repo| {
+            repo.find_remote(remote)
+                .ok()
+                .and_then(|found| found.url().map(str::to_string))
+        })
+        .unwrap_or_else(|| remote.to_string())
+}
+
+/// Put the built book at `target`
+pub fn deploy(config: &Config, book: &Path, target: &Target) -> Result<()> {
+    match target {
+        Target::Local(path) => filesystem::copy_dir(book, path),
+        Target::Rsync(destination) => run(
+            Command::new("rsync")
+                .arg("-a")
+                .arg("--delete")
+                .arg(format!("{}/", book.display()))
+                .arg(destination),
+            "rsync",
+        ),
+        Target::Scp(destination) => run(
+            Command::new("scp")
+                .arg("-r")
+                .arg(book.join("."))
+                .arg(destination),
+            "scp",
+        ),
+        Target::GhPages { remote } => {
+            let branch = &config.publish.branch;
+            // A fresh single-commit branch each time, like ghp-import
+            fs::write(book.join(".nojekyll"), "").map_err(JournalError::at_path(book))?;
+            let git = |args: &[&str]| {
+                let mut command = Command::new("git");
+                command.arg("-C").arg(book).args(args);
+                command
+            };
+            run(&mut git(&["init", "-q"]), "git init")?;
+            run(
+                &mut git(&["symbolic-ref", "HEAD", &format!("refs/heads/{}", branch)]),
+                "git symbolic-ref",
+            )?;
+            run(&mut git(&["add", "-A"]), "git add")?;
+            run(
+                &mut git(&["commit", "-q", "-m", "Publish journal"]),
+                "git commit",
+            )?;
+            run(
+                &mut git(&[
+                    "push",
+                    "--force",
+                    &remote_url(config, remote),
+                    &format!("HEAD:{}", branch),
+                ]),
+                "git push",
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_and_stage() {
+        assert_eq!(
+            Target::parse("gh-pages").unwrap(),
+            Target::GhPages {
+                remote: "origin".to_string()
+            }
+        );
+        assert_eq!(
+            Target::parse("rsync:me@host:/var/www/journal").unwrap(),
+            Target::Rsync("me@host:/var/www/journal".to_string())
+        );
+        assert_eq!(
+            Target::parse("/srv/journal").unwrap(),
+            Target::Local(PathBuf::from("/srv/journal"))
+        );
+        assert!(Target::parse("scp:").is_err());
+
+        let dir = std::env::temp_dir().join(format!("easy_journal_publish_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let root = dir.join("root");
+        let mut config = Config::default();
+        config.set_root(root.clone(), Path::new("journal"));
+        fs::create_dir_all(root.join("journal/2025/12")).unwrap();
+        fs::create_dir_all(root.join(".easy_journal")).unwrap();
+        fs::create_dir_all(root.join("book")).unwrap();
+        fs::write(root.join("book.toml"), "[book]\nsrc = \"journal\"\n").unwrap();
+        fs::write(root.join(".env"), "API_TOKEN=secret\n").unwrap();
+        fs::write(
+            root.join("journal/2025/12/29.md"),
+            "# Monday\n- Lunch with ||Sam||\n",
+        )
+        .unwrap();
+
+        let staging = dir.join("staging");
+        stage(&config, &staging, false).unwrap();
+        assert!(staging.join("book.toml").exists());
+        assert!(!staging.join(".env").exists());
+        assert!(!staging.join(".easy_journal").exists());
+        assert!(!staging.join("book").exists());
+        assert_eq!(
+            fs::read_to_string(staging.join("journal/2025/12/29.md")).unwrap(),
+            "# Monday\n- Lunch with ▓▓▓\n"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,454 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::error::{JournalError, Result};
+use crate::journal::parser;
+
+/// What a comparison looks at
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    Date,
+    Tag,
+    /// `open` or `done`
+    TaskState,
+    TaskText,
+    /// A footer metric, matched like `fill_metrics` does (`metric.mood` finds `**Mood(1-10)**:`)
+    Metric(String),
+    /// Any `#` heading
+    Section,
+    /// The whole entry
+    Text,
+    Words,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Field> {
+        let lower = name.to_lowercase();
+        Some(match lower.as_str() {
+            "date" => Field::Date,
+            "tag" | "tags" => Field::Tag,
+            "task.state" => Field::TaskState,
+            "task.text" | "task" => Field::TaskText,
+            "section" => Field::Section,
+            "text" => Field::Text,
+            "words" => Field::Words,
+            _ => Field::Metric(parser::metric_name(lower.strip_prefix("metric.")?)),
+        })
+    }
+
+    fn is_task(&self) -> bool {
+        matches!(self, Field::TaskState | Field::TaskText)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    /// Case-insensitive substring
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare { field: Field, op: Op, value: String },
+}
+
+impl Expr {
+    fn uses_tasks(&self) -> bool {
+        match self {
+            Expr::And(a, b) | Expr::Or(a, b) => a.uses_tasks() || b.uses_tasks(),
+            Expr::Not(a) => a.uses_tasks(),
+            Expr::Compare { field, .. } => field.is_task(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Op(Op),
+    Word(String),
+    /// A quoted string; never a keyword
+    Quoted(String),
+}
+
+fn invalid(message: impl Into<String>) -> JournalError {
+    JournalError::InvalidArgument(format!("Query: {}", message.into()))
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' => {
+                chars.next();
+                tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            }
+            '"' | '\'' => {
+                chars.next();
+                let text: String = chars.by_ref().take_while(|ch| *ch != c).collect();
+                tokens.push(Token::Quoted(text));
+            }
+            '=' | '!' | '>' | '<' | '~' => {
+                chars.next();
+                let eq = chars.next_if_eq(&'=').is_some();
+                tokens.push(Token::Op(match (c, eq) {
+                    ('=', _) => Op::Eq,
+                    ('!', true) => Op::Ne,
+                    ('>', false) => Op::Gt,
+                    ('>', true) => Op::Ge,
+                    ('<', false) => Op::Lt,
+                    ('<', true) => Op::Le,
+                    ('~', _) => Op::Contains,
+                    _ => return Err(invalid("expected != after !")),
+                }));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_whitespace() || "()=!<>~\"'".contains(ch) {
+                        break;
+                    }
+                    word.push(ch);
+                    chars.next();
+                }
+                tokens.push(match word.to_uppercase().as_str() {
+                    "CONTAINS" => Token::Op(Op::Contains),
+                    _ => Token::Word(word),
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct QueryParser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl QueryParser {
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.tokens.get(self.position), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Expr> {
+        let mut expr = self.and()?;
+        while self.peek_keyword("OR") {
+            self.position += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut expr = self.not()?;
+        while self.peek_keyword("AND") {
+            self.position += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<Expr> {
+        if self.peek_keyword("NOT") {
+            self.position += 1;
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        if self.tokens.get(self.position) == Some(&Token::Open) {
+            self.position += 1;
+            let expr = self.or()?;
+            if self.next() != Some(Token::Close) {
+                return Err(invalid("missing )"));
+            }
+            return Ok(expr);
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr> {
+        let Some(Token::Word(name)) = self.next() else {
+            return Err(invalid("expected a field such as date, tag or task.state"));
+        };
+        let field = Field::parse(&name).ok_or_else(|| {
+            invalid(format!(
+                "unknown field '{}' (use date, tag, task.state, task.text, section, text, words or metric.<name>)",
+                name
+            ))
+        })?;
+        let Some(Token::Op(op)) = self.next() else {
+            return Err(invalid(format!(
+                "expected =, !=, >, >=, <, <= or ~ after {}",
+                name
+            )));
+        };
+        let value = match self.next() {
+            Some(Token::Word(value)) | Some(Token::Quoted(value)) => value,
+            _ => return Err(invalid(format!("expected a value after {}", name))),
+        };
+
+        let ordered = matches!(op, Op::Gt | Op::Ge | Op::Lt | Op::Le);
+        match &field {
+            Field::Date => {
+                NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                    .map_err(|_| invalid(format!("'{}' is not a YYYY-MM-DD date", value)))?;
+            }
+            Field::TaskState if !matches!(value.to_lowercase().as_str(), "open" | "done") => {
+                return Err(invalid("task.state is open or done"));
+            }
+            Field::Tag | Field::TaskState | Field::TaskText | Field::Section | Field::Text
+                if ordered =>
+            {
+                return Err(invalid(format!("{} can't be compared with < or >", name)));
+            }
+            _ => {}
+        }
+        let value = match field {
+            Field::Tag => value.trim_start_matches('#').to_lowercase(),
+            _ => value,
+        };
+        Ok(Expr::Compare { field, op, value })
+    }
+}
+
+/// Parse a query such as `task.state = open AND tag = #health AND date > 2025-10-01`
+pub fn parse(query: &str) -> Result<Expr> {
+    let mut parser = QueryParser {
+        tokens: tokenize(query)?,
+        position: 0,
+    };
+    let expr = parser.or()?;
+    if parser.position < parser.tokens.len() {
+        return Err(invalid("expected AND or OR between conditions"));
+    }
+    Ok(expr)
+}
+
+/// Leading number of a value, so `7/10` or `8.5h` compare as numbers
+fn leading_number(value: &str) -> Option<f64> {
+    let end = value
+        .char_indices()
+        .find(|(i, c)| !(c.is_ascii_digit() || *c == '.' || (*i == 0 && *c == '-')))
+        .map_or(value.len(), |(i, _)| i);
+    value[..end].parse().ok()
+}
+
+fn compare(actual: &str, op: Op, expected: &str) -> bool {
+    if op == Op::Contains {
+        return actual.to_lowercase().contains(&expected.to_lowercase());
+    }
+    let ordering = match (leading_number(actual), expected.parse::<f64>()) {
+        (Some(actual), Ok(expected)) => actual.partial_cmp(&expected),
+        _ => Some(actual.to_lowercase().cmp(&expected.to_lowercase())),
+    };
+    let Some(ordering) = ordering else {
+        return false;
+    };
+    match op {
+        Op::Eq => ordering.is_eq(),
+        Op::Ne => ordering.is_ne(),
+        Op::Gt => ordering.is_gt(),
+        Op::Ge => ordering.is_ge(),
+        Op::Lt => ordering.is_lt(),
+        Op::Le => ordering.is_le(),
+        Op::Contains => unreachable!(),
+    }
+}
+
+/// Compare against a list: `=` and `~` need one match, `!=` needs none
+fn compare_any(actual: &[String], op: Op, expected: &str) -> bool {
+    match op {
+        Op::Ne => !actual.iter().any(|a| compare(a, Op::Eq, expected)),
+        op => actual.iter().any(|a| compare(a, op, expected)),
+    }
+}
+
+/// Parsed entry data queries run against
+struct EntryData<'a> {
+    date: NaiveDate,
+    content: &'a str,
+    tags: Vec<String>,
+    tasks: Vec<(String, bool)>,
+    metrics: Vec<(String, String)>,
+    sections: Vec<String>,
+    words: usize,
+}
+
+impl<'a> EntryData<'a> {
+    fn parse(date: NaiveDate, content: &'a str) -> Self {
+        EntryData {
+            date,
+            content,
+            tags: parser::extract_tags(content),
+            tasks: parser::extract_tasks(content),
+            metrics: parser::extract_metrics(content)
+                .into_iter()
+                .map(|(key, value)| (parser::metric_name(&key), value))
+                .collect(),
+            sections: parser::split_sections(content)
+                .into_iter()
+                .map(|section| section.header.trim_start_matches('#').trim().to_string())
+                .filter(|header| !header.is_empty())
+                .collect(),
+            words: parser::word_count(content),
+        }
+    }
+}
+
+fn eval(expr: &Expr, entry: &EntryData, task: Option<&(String, bool)>) -> bool {
+    match expr {
+        Expr::And(a, b) => eval(a, entry, task) && eval(b, entry, task),
+        Expr::Or(a, b) => eval(a, entry, task) || eval(b, entry, task),
+        Expr::Not(a) => !eval(a, entry, task),
+        Expr::Compare { field, op, value } => match field {
+            Field::Date => compare(&entry.date.to_string(), *op, value),
+            Field::Tag => compare_any(&entry.tags, *op, value),
+            Field::Section => compare_any(&entry.sections, *op, value),
+            Field::Text => compare(entry.content, *op, value),
+            Field::Words => compare(&entry.words.to_string(), *op, value),
+            Field::Metric(name) => entry
+                .metrics
+                .iter()
+                .find(|(key, metric)| key == name && !metric.is_empty())
+                .is_some_and(|(_, metric)| compare(metric, *op, value)),
+            Field::TaskState => task
+                .is_some_and(|(_, done)| compare(if *done { "done" } else { "open" }, *op, value)),
+            Field::TaskText => task.is_some_and(|(text, _)| compare(text, *op, value)),
+        },
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EntryRow {
+    pub date: NaiveDate,
+    pub words: usize,
+    pub open_tasks: usize,
+    pub done_tasks: usize,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskRow {
+    pub date: NaiveDate,
+    pub state: &'static str,
+    pub text: String,
+}
+
+/// Matches of a query: tasks when it looks at `task.*` fields, entries otherwise
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "rows", rename_all = "snake_case")]
+pub enum Rows {
+    Entries(Vec<EntryRow>),
+    Tasks(Vec<TaskRow>),
+}
+
+/// Run `expr` over (date, markdown) entries
+pub fn run(expr: &Expr, entries: &[(NaiveDate, String)]) -> Rows {
+    let parsed = entries
+        .iter()
+        .map(|(date, content)| EntryData::parse(*date, content));
+
+    if expr.uses_tasks() {
+        let mut rows = Vec::new();
+        for entry in parsed {
+            for task in &entry.tasks {
+                if eval(expr, &entry, Some(task)) {
+                    rows.push(TaskRow {
+                        date: entry.date,
+                        state: if task.1 { "done" } else { "open" },
+                        text: task.0.clone(),
+                    });
+                }
+            }
+        }
+        return Rows::Tasks(rows);
+    }
+
+    Rows::Entries(
+        parsed
+            .filter(|entry| eval(expr, entry, None))
+            .map(|entry| EntryRow {
+                date: entry.date,
+                words: entry.words,
+                open_tasks: entry.tasks.iter().filter(|(_, done)| !done).count(),
+                done_tasks: entry.tasks.iter().filter(|(_, done)| *done).count(),
+                tags: entry.tags,
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_run() {
+        let date = |day| NaiveDate::from_ymd_opt(2025, 10, day).unwrap();
+        let entries = vec![
+            (
+                date(1),
+                "# Wed\n## Goals\n- [ ] Book dentist #health\n- [x] Run 5k\n---\n**Mood(1-10)**: 4\n".to_string(),
+            ),
+            (
+                date(2),
+                "# Thu\n## Goals\n- [ ] Stretch #health\n- [ ] Write report\n---\n**Mood(1-10)**: 8/10\n".to_string(),
+            ),
+        ];
+
+        let tasks = run(
+            &parse("task.state = open AND tag = #health AND date > 2025-10-01").unwrap(),
+            &entries,
+        );
+        assert_eq!(
+            tasks,
+            Rows::Tasks(vec![
+                TaskRow {
+                    date: date(2),
+                    state: "open",
+                    text: "Stretch #health".to_string(),
+                },
+                TaskRow {
+                    date: date(2),
+                    state: "open",
+                    text: "Write report".to_string(),
+                },
+            ])
+        );
+
+        let dates = |query: &str| match run(&parse(query).unwrap(), &entries) {
+            Rows::Entries(rows) => rows.iter().map(|row| row.date).collect::<Vec<_>>(),
+            Rows::Tasks(_) => panic!("expected entries"),
+        };
+        assert_eq!(dates("metric.mood >= 5"), vec![date(2)]);
+        assert_eq!(
+            dates("NOT (text ~ dentist OR section = missing)"),
+            vec![date(2)]
+        );
+        assert_eq!(dates("tag != work and words < 100"), vec![date(1), date(2)]);
+
+        assert!(parse("tag > #health").is_err());
+        assert!(parse("date = yesterday").is_err());
+        assert!(parse("mood = 5").is_err());
+        assert!(parse("tag = #a tag = #b").is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::Deserialize;
+
+use crate::config::ReadLaterConfig;
+use crate::error::{JournalError, Result, check_response};
+
+const API_URL: &str = "https://readwise.io/api/v3/list/";
+/// Reader locations that make up the unread queue
+const QUEUE_LOCATIONS: [&str; 2] = ["new", "later"];
+/// Stop paging after this many pages (100 documents each) per location
+const MAX_PAGES: usize = 10;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ListResponse {
+    results: Vec<Document>,
+    next_page_cursor: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct Document {
+    title: Option<String>,
+    /// Link to the document in Reader
+    url: String,
+    /// The original article
+    source_url: Option<String>,
+    saved_at: DateTime<chrono::FixedOffset>,
+    /// Highlights and notes are documents too
+    parent_id: Option<String>,
+}
+
+impl Document {
+    fn link(&self) -> String {
+        format!(
+            "[{}]({})",
+            self.title.as_deref().unwrap_or("Untitled"),
+            self.source_url.as_deref().unwrap_or(&self.url)
+        )
+    }
+
+    fn saved_on(&self) -> NaiveDate {
+        self.saved_at.with_timezone(&Local).date_naive()
+    }
+}
+
+/// Items saved on the day before `date`, then the oldest items still unread
+fn format_queue(queue: &[Document], date: NaiveDate, oldest: usize) -> Option<String> {
+    let yesterday = date - Duration::days(1);
+    let mut lines = Vec::new();
+
+    for document in queue.iter().filter(|d| d.saved_on() == yesterday) {
+        lines.push(format!("- 📥 {}", document.link()));
+    }
+
+    let mut backlog: Vec<&Document> = queue.iter().filter(|d| d.saved_on() < yesterday).collect();
+    backlog.sort_by_key(|d| d.saved_at);
+    for document in backlog.iter().take(oldest) {
+        lines.push(format!(
+            "- 🕰 {} (saved {} days ago)",
+            document.link(),
+            (date - document.saved_on()).num_days()
+        ));
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+    lines.push(format!("- 📚 {} unread in the queue", queue.len()));
+    Some(lines.join("\n"))
+}
+
+async fn list(client: &reqwest::Client, token: &str, location: &str) -> Result<Vec<Document>> {
+    let mut documents = Vec::new();
+    let mut cursor: Option<String> = None;
+    for _ in 0..MAX_PAGES {
+        let mut request = client
+            .get(API_URL)
+            .header(reqwest::header::AUTHORIZATION, format!("Token {}", token))
+            .query(&[("location", location)]);
+        if let Some(cursor) = &cursor {
+            request = request.query(&[("pageCursor", cursor)]);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| JournalError::ReadLaterFailed(format!("Request failed: {}", e)))?;
+        let page: ListResponse = check_response(response, "Readwise")
+            .await?
+            .json()
+            .await
+            .map_err(|e| {
+                JournalError::ReadLaterFailed(format!("Failed to parse response: {}", e))
+            })?;
+
+        documents.extend(page.results.into_iter().filter(|d| d.parent_id.is_none()));
+        cursor = page.next_page_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    Ok(documents)
+}
+
+/// The Readwise Reader queue: what was saved yesterday and the oldest unread items
+pub async fn fetch_queue(config: &ReadLaterConfig, date: NaiveDate) -> Result<Option<String>> {
+    let Some(token) = &config.readwise_token else {
+        tracing::debug!("READWISE_TOKEN not set");
+        return Ok(None);
+    };
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| {
+            JournalError::ReadLaterFailed(format!("Failed to build HTTP client: {}", e))
+        })?;
+
+    let mut queue = Vec::new();
+    for location in QUEUE_LOCATIONS {
+        queue.extend(list(&client, token, location).await?);
+    }
+    Ok(format_queue(&queue, date, config.oldest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_queue() {
+        let page: ListResponse = serde_json::from_str(
+            r#"{"results": [
+                {"title": "Fresh", "url": "https://read.readwise.io/read/1", "source_url": "https://example.com/fresh",
+                 "saved_at": "2025-12-28T12:00:00+00:00", "parent_id": null},
+                {"title": "Ancient", "url": "https://read.readwise.io/read/2", "source_url": null,
+                 "saved_at": "2025-10-30T12:00:00+00:00", "parent_id": null},
+                {"title": "Old", "url": "https://read.readwise.io/read/3", "source_url": "https://example.com/old",
+                 "saved_at": "2025-12-01T12:00:00+00:00", "parent_id": null},
+                {"title": "Older", "url": "https://read.readwise.io/read/4", "source_url": "https://example.com/older",
+                 "saved_at": "2025-11-20T12:00:00+00:00", "parent_id": null}
+            ], "nextPageCursor": null}"#,
+        )
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+
+        assert_eq!(
+            format_queue(&page.results, date, 2).unwrap(),
+            "- 📥 [Fresh](https://example.com/fresh)\n\
+             - 🕰 [Ancient](https://read.readwise.io/read/2) (saved 60 days ago)\n\
+             - 🕰 [Older](https://example.com/older) (saved 39 days ago)\n\
+             - 📚 4 unread in the queue"
+        );
+        assert_eq!(format_queue(&[], date, 2), None);
+    }
+}
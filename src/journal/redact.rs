@@ -0,0 +1,121 @@
+/// What private text becomes unless it is stripped
+pub const MASK: &str = "▓▓▓";
+
+/// `<!-- private -->` or `<!-- /private -->` on a line of its own
+fn marker(line: &str) -> Option<bool> {
+    let inner = line
+        .trim()
+        .strip_prefix("<!--")?
+        .strip_suffix("-->")?
+        .trim();
+    match inner {
+        "private" => Some(true),
+        "/private" => Some(false),
+        _ => None,
+    }
+}
+
+/// Replace (or remove) every `||inline secret||` of a line
+fn redact_inline(line: &str, strip: bool) -> String {
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find("||") {
+        let after = &rest[start + 2..];
+        // `a || b` is not a secret: the text must hug both pairs of bars
+        let end = after.find("||").filter(|end| {
+            *end > 0
+                && !after.starts_with(char::is_whitespace)
+                && !after[..*end].ends_with(char::is_whitespace)
+        });
+        let Some(end) = end else {
+            result.push_str(&rest[..start + 2]);
+            rest = after;
+            continue;
+        };
+        result.push_str(&rest[..start]);
+        rest = &after[end + 2..];
+        if !strip {
+            result.push_str(MASK);
+        } else if result.ends_with(' ') && rest.starts_with([' ', ',', '.']) {
+            result.pop();
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Hide the private parts of an entry before it leaves the machine:
+/// `||inline secrets||` and blocks from `<!-- private -->` to `<!-- /private -->`
+/// (or the end of the entry) become ▓▓▓, or disappear with `strip`.
+/// Fenced code blocks are left alone.
+pub fn redact(content: &str, strip: bool) -> String {
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    let mut in_private = false;
+    for line in content.lines() {
+        if in_private {
+            if marker(line) == Some(false) {
+                in_private = false;
+            }
+            continue;
+        }
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        if in_code {
+            lines.push(line.to_string());
+            continue;
+        }
+        if marker(line) == Some(true) {
+            in_private = true;
+            if !strip {
+                lines.push(MASK.to_string());
+            }
+            continue;
+        }
+
+        let mut redacted = redact_inline(line, strip);
+        if strip && redacted != line {
+            redacted.truncate(redacted.trim_end().len());
+        }
+        // A task or bullet that was nothing but a secret goes away with it
+        let emptied = redacted != line
+            && matches!(
+                redacted.trim(),
+                "" | "-" | "*" | "- [ ]" | "- [x]" | "- [X]"
+            );
+        if !emptied {
+            lines.push(redacted);
+        }
+    }
+
+    let mut redacted = lines.join("\n");
+    if content.ends_with('\n') {
+        redacted.push('\n');
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let content = "## Work Accomplished\n- Shipped the release, lunch with ||Sam||\n- [ ] ||Call the doctor||\n- Check `a || b` and a || b\n\n<!-- private -->\nRough day at home.\n<!-- /private -->\n\n```\necho ||kept||\n```\n";
+
+        assert_eq!(
+            redact(content, false),
+            "## Work Accomplished\n- Shipped the release, lunch with ▓▓▓\n- [ ] ▓▓▓\n- Check `a || b` and a || b\n\n▓▓▓\n\n```\necho ||kept||\n```\n"
+        );
+        assert_eq!(
+            redact(content, true),
+            "## Work Accomplished\n- Shipped the release, lunch with\n- Check `a || b` and a || b\n\n\n```\necho ||kept||\n```\n"
+        );
+        // An unclosed block runs to the end of the entry
+        assert_eq!(
+            redact("Public\n<!-- private -->\nSecret\n", true),
+            "Public\n"
+        );
+    }
+}
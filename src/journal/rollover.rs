@@ -0,0 +1,186 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::write_atomic;
+use crate::journal::parser;
+
+/// What to do with one unchecked task carried over from the previous entry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decision {
+    Keep,
+    Drop,
+    /// Leave it out today and offer it again with the next entry
+    Defer,
+}
+
+/// Asks about each carried-over task before a new entry is written
+pub trait RolloverReview: Send + Sync {
+    fn review(&self, task: &str) -> Decision;
+}
+
+/// How unchecked tasks from the previous entry reach a new one
+pub enum Rollover<'a> {
+    /// Carry every task over
+    All,
+    /// Carry nothing over; deferred tasks wait for the next entry
+    Skip,
+    Review(&'a dyn RolloverReview),
+}
+
+/// A task deferred while creating the entry for `deferred_on`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Deferred {
+    pub task: String,
+    pub deferred_on: NaiveDate,
+}
+
+fn load_deferred(path: &Path) -> Vec<Deferred> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_deferred(path: &Path, deferred: &[Deferred]) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent).map_err(|e| JournalError::FileIo {
+            path: parent.to_path_buf(),
+            source: e,
+        })?;
+    }
+    let content = serde_json::to_string_pretty(deferred).map_err(|e| JournalError::FileIo {
+        path: path.to_path_buf(),
+        source: e.into(),
+    })?;
+    write_atomic(path, content)
+}
+
+/// Decide which lines of `previous` (plus tasks deferred before `date`) go into the
+/// entry for `date`. Returns the kept lines and the tasks to keep deferred.
+fn review_tasks(
+    previous: Option<&str>,
+    deferred: Vec<Deferred>,
+    date: NaiveDate,
+    review: Option<&dyn RolloverReview>,
+) -> (Option<String>, Vec<Deferred>) {
+    let (due, mut waiting): (Vec<Deferred>, Vec<Deferred>) =
+        deferred.into_iter().partition(|d| d.deferred_on < date);
+
+    let mut candidates: Vec<String> = previous
+        .map(|previous| previous.lines().map(str::to_string).collect())
+        .unwrap_or_default();
+    for d in due {
+        if !candidates
+            .iter()
+            .any(|line| parser::parse_task(line).is_some_and(|(text, _)| text == d.task))
+        {
+            candidates.insert(0, format!("- [ ] {}", d.task));
+        }
+    }
+
+    let mut kept = Vec::new();
+    for line in candidates {
+        let task = parser::parse_task(&line)
+            .filter(|(text, done)| !done && !text.is_empty())
+            .map(|(text, _)| text);
+        let decision = match (&task, review) {
+            (Some(task), Some(review)) => review.review(task),
+            _ => Decision::Keep,
+        };
+        match decision {
+            Decision::Keep => kept.push(line),
+            Decision::Drop => {}
+            Decision::Defer => waiting.push(Deferred {
+                task: task.unwrap_or_default(),
+                deferred_on: date,
+            }),
+        }
+    }
+
+    ((!kept.is_empty()).then(|| kept.join("\n")), waiting)
+}
+
+/// Apply `rollover` to the previous entry's carried-over content for the entry
+/// being created for `date`, recording deferred tasks in `config.deferred_path`
+pub fn apply(
+    previous: Option<String>,
+    date: NaiveDate,
+    config: &Config,
+    rollover: &Rollover,
+) -> Result<Option<String>> {
+    let review = match rollover {
+        Rollover::All => None,
+        Rollover::Skip => return Ok(None),
+        Rollover::Review(review) => Some(*review),
+    };
+
+    let deferred = load_deferred(&config.deferred_path);
+    let before = deferred.clone();
+    let (kept, deferred) = review_tasks(previous.as_deref(), deferred, date, review);
+    if deferred != before {
+        save_deferred(&config.deferred_path, &deferred)?;
+    }
+    Ok(kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Answers;
+
+    impl RolloverReview for Answers {
+        fn review(&self, task: &str) -> Decision {
+            match task {
+                "Write report" => Decision::Drop,
+                "Call bank" => Decision::Defer,
+                _ => Decision::Keep,
+            }
+        }
+    }
+
+    #[test]
+    fn test_review_tasks() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let previous = "- [ ] Write report\n- [ ] Call bank\n- [ ] Fix bug\nShip the release";
+        let deferred = vec![
+            Deferred {
+                task: "Renew passport".to_string(),
+                deferred_on: NaiveDate::from_ymd_opt(2025, 12, 28).unwrap(),
+            },
+            Deferred {
+                task: "Later".to_string(),
+                deferred_on: date,
+            },
+        ];
+
+        let (kept, waiting) = review_tasks(Some(previous), deferred.clone(), date, Some(&Answers));
+        assert_eq!(
+            kept.unwrap(),
+            "- [ ] Renew passport\n- [ ] Fix bug\nShip the release"
+        );
+        assert_eq!(
+            waiting,
+            vec![
+                deferred[1].clone(),
+                Deferred {
+                    task: "Call bank".to_string(),
+                    deferred_on: date,
+                },
+            ]
+        );
+
+        // Without review everything is kept and due deferrals come back
+        let (kept, waiting) = review_tasks(Some(previous), deferred, date, None);
+        assert_eq!(
+            kept.unwrap(),
+            "- [ ] Renew passport\n- [ ] Write report\n- [ ] Call bank\n- [ ] Fix bug\nShip the release"
+        );
+        assert_eq!(waiting.len(), 1);
+    }
+}
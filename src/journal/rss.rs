@@ -0,0 +1,261 @@
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::config::RssConfig;
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::filesystem::write_atomic;
+
+/// Seen items are forgotten after this many days
+const SEEN_RETENTION_DAYS: i64 = 60;
+
+#[derive(Debug, Clone, PartialEq)]
+struct FeedItem {
+    /// guid/id, or the link when the feed has none
+    id: String,
+    title: String,
+    link: String,
+    published: Option<DateTime<Local>>,
+}
+
+#[derive(Debug)]
+struct Feed {
+    title: String,
+    items: Vec<FeedItem>,
+}
+
+fn child_text<'a>(node: roxmltree::Node<'a, 'a>, name: &str) -> Option<&'a str> {
+    node.children()
+        .find(|child| child.tag_name().name() == name)
+        .and_then(|child| child.text())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc2822(value)
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .ok()
+        .map(|at| at.with_timezone(&Local))
+}
+
+/// Parse an RSS 2.0 or Atom document
+fn parse_feed(xml: &str) -> Result<Feed> {
+    let document = roxmltree::Document::parse(xml)
+        .map_err(|e| JournalError::RssFailed(format!("Invalid feed XML: {}", e)))?;
+    let root = document.root_element();
+
+    // RSS nests everything in <channel>; Atom entries sit directly under <feed>
+    let (container, item_tag) = match root.tag_name().name() {
+        "rss" | "RDF" => (
+            root.children()
+                .find(|n| n.tag_name().name() == "channel")
+                .unwrap_or(root),
+            "item",
+        ),
+        "feed" => (root, "entry"),
+        other => {
+            return Err(JournalError::RssFailed(format!(
+                "Not an RSS or Atom feed (root element <{}>)",
+                other
+            )));
+        }
+    };
+
+    let items = root
+        .descendants()
+        .filter(|n| n.tag_name().name() == item_tag)
+        .filter_map(|item| {
+            let link = child_text(item, "link").map(String::from).or_else(|| {
+                // Atom: <link rel="alternate" href="..."/>
+                item.children()
+                    .filter(|n| n.tag_name().name() == "link")
+                    .find(|n| n.attribute("rel").is_none_or(|rel| rel == "alternate"))
+                    .and_then(|n| n.attribute("href"))
+                    .map(String::from)
+            })?;
+            let published = ["pubDate", "published", "updated", "date"]
+                .iter()
+                .find_map(|tag| child_text(item, tag).and_then(parse_date));
+            Some(FeedItem {
+                id: child_text(item, "guid")
+                    .or_else(|| child_text(item, "id"))
+                    .unwrap_or(&link)
+                    .to_string(),
+                title: child_text(item, "title").unwrap_or(&link).to_string(),
+                link,
+                published,
+            })
+        })
+        .collect();
+
+    Ok(Feed {
+        title: child_text(container, "title").unwrap_or("Feed").to_string(),
+        items,
+    })
+}
+
+/// Item id → entry date it was first listed for
+fn load_seen(path: &Path) -> HashMap<String, NaiveDate> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_seen(path: &Path, seen: &HashMap<String, NaiveDate>) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent).map_err(|e| JournalError::FileIo {
+            path: parent.to_path_buf(),
+            source: e,
+        })?;
+    }
+    let content = serde_json::to_string_pretty(seen).map_err(|e| JournalError::FileIo {
+        path: path.to_path_buf(),
+        source: e.into(),
+    })?;
+    write_atomic(path, content)
+}
+
+/// Pick the items to list for `date`: published since the start of the previous
+/// day (or undated) and not listed for an earlier entry, newest first, at most
+/// `per_feed` per feed and `max_items` overall. Marks them as seen.
+fn select_items<'a>(
+    feeds: &'a [Feed],
+    seen: &mut HashMap<String, NaiveDate>,
+    date: NaiveDate,
+    per_feed: usize,
+    max_items: usize,
+) -> Vec<(&'a Feed, &'a FeedItem)> {
+    let since = date - Duration::days(1);
+    let mut selected: Vec<(&Feed, &FeedItem)> = Vec::new();
+    for feed in feeds {
+        let mut items: Vec<&FeedItem> = feed
+            .items
+            .iter()
+            .filter(|item| item.published.is_none_or(|at| at.date_naive() >= since))
+            .filter(|item| seen.get(&item.id).is_none_or(|first| *first >= date))
+            .collect();
+        items.sort_by_key(|item| std::cmp::Reverse(item.published));
+        selected.extend(items.into_iter().take(per_feed).map(|item| (feed, item)));
+    }
+    selected.sort_by_key(|(_, item)| std::cmp::Reverse(item.published));
+    selected.truncate(max_items);
+
+    for (_, item) in &selected {
+        seen.entry(item.id.clone()).or_insert(date);
+    }
+    seen.retain(|_, first| (date - *first).num_days() <= SEEN_RETENTION_DAYS);
+    selected
+}
+
+async fn fetch_feed(client: &reqwest::Client, url: &str) -> Result<Feed> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| JournalError::RssFailed(format!("Request failed: {}", e)))?;
+    let body = check_response(response, "RSS")
+        .await?
+        .text()
+        .await
+        .map_err(|e| JournalError::RssFailed(format!("Failed to read feed: {}", e)))?;
+    parse_feed(&body)
+}
+
+/// New items from the feeds in RSS_FEEDS since the previous day
+pub async fn fetch_headlines(config: &RssConfig, date: NaiveDate) -> Result<Option<String>> {
+    if config.feeds.is_empty() {
+        tracing::debug!("RSS_FEEDS not set");
+        return Ok(None);
+    }
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| JournalError::RssFailed(format!("Failed to build HTTP client: {}", e)))?;
+
+    let results =
+        futures::future::join_all(config.feeds.iter().map(|url| fetch_feed(&client, url))).await;
+    let mut feeds = Vec::new();
+    for (url, result) in config.feeds.iter().zip(results) {
+        match result {
+            Ok(feed) => feeds.push(feed),
+            Err(e) => tracing::warn!(feed = url.as_str(), "Could not fetch feed: {}", e),
+        }
+    }
+    if feeds.is_empty() {
+        return Err(JournalError::RssFailed(
+            "None of the feeds in RSS_FEEDS could be fetched".to_string(),
+        ));
+    }
+
+    let mut seen = load_seen(&config.seen_path);
+    let selected = select_items(&feeds, &mut seen, date, config.per_feed, config.max_items);
+    if let Err(e) = save_seen(&config.seen_path, &seen) {
+        tracing::warn!("Could not save seen feed items: {}", e);
+    }
+
+    if selected.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(
+        selected
+            .iter()
+            .map(|(feed, item)| format!("- [{}]({}) — {}", item.title, item.link, feed.title))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel><title>Example News</title>
+  <item><title>Fresh</title><link>https://example.com/fresh</link><guid>1</guid>
+    <pubDate>Sun, 28 Dec 2025 12:00:00 +0000</pubDate></item>
+  <item><title>Newer</title><link>https://example.com/newer</link><guid>2</guid>
+    <pubDate>Mon, 29 Dec 2025 06:00:00 +0000</pubDate></item>
+  <item><title>Stale</title><link>https://example.com/stale</link><guid>3</guid>
+    <pubDate>Mon, 01 Dec 2025 12:00:00 +0000</pubDate></item>
+</channel></rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title>Blog</title>
+  <entry><title>Post</title><id>tag:blog,1</id>
+    <link rel="alternate" href="https://blog.example/post"/>
+    <updated>2025-12-28T12:00:00Z</updated></entry>
+</feed>"#;
+
+    #[test]
+    fn test_parse_and_select_items() {
+        let feeds = vec![parse_feed(RSS).unwrap(), parse_feed(ATOM).unwrap()];
+        assert_eq!(feeds[1].title, "Blog");
+        assert_eq!(feeds[1].items[0].link, "https://blog.example/post");
+
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let mut seen = HashMap::new();
+        let titles = |selected: Vec<(&Feed, &FeedItem)>| {
+            selected
+                .iter()
+                .map(|(_, item)| item.title.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let selected = select_items(&feeds, &mut seen, date, 1, 10);
+        assert_eq!(titles(selected), vec!["Newer", "Post"]);
+
+        // Listed again for the same entry, but not for the next one
+        assert_eq!(
+            titles(select_items(&feeds, &mut seen, date, 1, 10)),
+            vec!["Newer", "Post"]
+        );
+        let next = date + Duration::days(1);
+        assert!(select_items(&feeds, &mut seen, next, 1, 10).is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::{Method, Response, StatusCode};
+use sha2::{Digest, Sha256};
+
+use crate::config::S3Config;
+use crate::error::{JournalError, Result};
+use crate::journal::storage::{self, Storage};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Journal stored in an S3-compatible bucket (AWS, MinIO, Backblaze B2, R2, ...)
+/// using path-style URLs and SigV4 request signing
+pub struct S3Storage {
+    client: reqwest::Client,
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    prefix: String,
+}
+
+impl S3Storage {
+    pub fn new(config: &S3Config) -> Result<Self> {
+        let missing = |name: &str| {
+            JournalError::InvalidConfig(format!("{} must be set for the s3 backend", name))
+        };
+
+        let client = reqwest::Client::builder()
+            .user_agent("easy_journal")
+            .build()
+            .map_err(|e| {
+                JournalError::StorageFailed(format!("Failed to build HTTP client: {}", e))
+            })?;
+
+        Ok(S3Storage {
+            client,
+            endpoint: config.endpoint.trim_end_matches('/').to_string(),
+            bucket: config.bucket.clone().ok_or_else(|| missing("S3_BUCKET"))?,
+            region: config.region.clone(),
+            access_key_id: config
+                .access_key_id
+                .clone()
+                .ok_or_else(|| missing("S3_ACCESS_KEY_ID"))?,
+            secret_access_key: config
+                .secret_access_key
+                .clone()
+                .ok_or_else(|| missing("S3_SECRET_ACCESS_KEY"))?,
+            prefix: config.prefix.clone(),
+        })
+    }
+
+    async fn send(&self, method: Method, path: &str, body: Vec<u8>) -> Result<Response> {
+        let uri = format!(
+            "/{}/{}",
+            self.bucket,
+            storage::encode_path(&format!("{}{}", self.prefix, path))
+        );
+        let url = reqwest::Url::parse(&format!("{}{}", self.endpoint, uri))
+            .map_err(|e| JournalError::InvalidConfig(format!("Invalid S3_ENDPOINT: {}", e)))?;
+
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => {
+                return Err(JournalError::InvalidConfig(
+                    "S3_ENDPOINT has no host".to_string(),
+                ));
+            }
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+        let authorization =
+            self.authorization(method.as_str(), &uri, &host, &amz_date, &payload_hash);
+
+        self.client
+            .request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header(reqwest::header::AUTHORIZATION, authorization)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| {
+                JournalError::StorageFailed(format!("S3 request for {} failed: {}", path, e))
+            })
+    }
+
+    /// SigV4 `Authorization` header for a request signing host, date and payload hash
+    fn authorization(
+        &self,
+        method: &str,
+        uri: &str,
+        host: &str,
+        amz_date: &str,
+        payload_hash: &str,
+    ) -> String {
+        let date_stamp = &amz_date[..8];
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method, uri, host, payload_hash, amz_date, signed_headers, payload_hash
+        );
+
+        let scope = format!("{}/{}/s3/aws4_request", date_stamp, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let key = signing_key(&self.secret_access_key, date_stamp, &self.region, "s3");
+        let signature = hex::encode(hmac(&key, string_to_sign.as_bytes()));
+
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, scope, signed_headers, signature
+        )
+    }
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn signing_key(secret: &str, date_stamp: &str, region: &str, service: &str) -> Vec<u8> {
+    let date_key = hmac(format!("AWS4{}", secret).as_bytes(), date_stamp.as_bytes());
+    let region_key = hmac(&date_key, region.as_bytes());
+    let service_key = hmac(&region_key, service.as_bytes());
+    hmac(&service_key, b"aws4_request")
+}
+
+fn check_status(response: &Response, method: &str, path: &str) -> Result<()> {
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(JournalError::HttpStatus {
+            provider: "S3".to_string(),
+            status: response.status().as_u16(),
+            message: format!("{} {}", method, path),
+        })
+    }
+}
+
+impl Storage for S3Storage {
+    async fn read(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let response = self.send(Method::GET, path, Vec::new()).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        check_status(&response, "GET", path)?;
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| JournalError::StorageFailed(format!("Failed to read {}: {}", path, e)))?;
+        Ok(Some(bytes.to_vec()))
+    }
+
+    async fn write(&self, path: &str, content: &[u8]) -> Result<()> {
+        let response = self.send(Method::PUT, path, content.to_vec()).await?;
+        check_status(&response, "PUT", path)
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        // S3 answers 204 whether or not the object existed
+        let response = self.send(Method::DELETE, path, Vec::new()).await?;
+        check_status(&response, "DELETE", path)
+    }
+
+    async fn modified(&self, path: &str) -> Result<Option<DateTime<Utc>>> {
+        let response = self.send(Method::HEAD, path, Vec::new()).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        check_status(&response, "HEAD", path)?;
+        Ok(Some(storage::last_modified(&response)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_key() {
+        // Example from the AWS SigV4 documentation
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_authorization_header() {
+        let storage = S3Storage::new(&S3Config {
+            endpoint: "https://s3.amazonaws.com".to_string(),
+            bucket: Some("journal".to_string()),
+            region: "us-east-1".to_string(),
+            access_key_id: Some("AKIDEXAMPLE".to_string()),
+            secret_access_key: Some("secret".to_string()),
+            prefix: String::new(),
+        })
+        .unwrap();
+
+        let header = storage.authorization(
+            "GET",
+            "/journal/2025/12/29.md",
+            "s3.amazonaws.com",
+            "20251229T101500Z",
+            &hex::encode(Sha256::digest(b"")),
+        );
+        assert!(header.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20251229/us-east-1/s3/aws4_request, \
+             SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature="
+        ));
+        assert_eq!(header.rsplit('=').next().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn test_missing_credentials() {
+        let config = S3Config {
+            endpoint: "https://s3.amazonaws.com".to_string(),
+            bucket: None,
+            region: "us-east-1".to_string(),
+            access_key_id: None,
+            secret_access_key: None,
+            prefix: String::new(),
+        };
+        assert!(matches!(
+            S3Storage::new(&config),
+            Err(JournalError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,185 @@
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use std::fs;
+use std::time::SystemTime;
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::journal::entry::JournalEntry;
+use crate::journal::index::Index;
+use crate::journal::{filesystem, matrix, notify, storage};
+
+/// Longest single sleep, so a suspended machine notices the missed time soon after waking
+const MAX_SLEEP: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// First time after `now` when the clock reads `at` (tomorrow if it already passed today)
+pub fn next_run(now: NaiveDateTime, at: NaiveTime) -> NaiveDateTime {
+    let today = now.date().and_time(at);
+    if today > now {
+        today
+    } else {
+        today + Duration::days(1)
+    }
+}
+
+/// Create today's entry (fetching its sections) if it doesn't exist yet.
+///
+/// With `DAEMON_PREWARM` the entry index is rebuilt afterwards. Returns
+/// whether a new entry was created.
+pub async fn create_todays_entry(config: &Config) -> Result<bool> {
+    let today = Local::now().date_naive();
+    storage::pull_for_date(config, today).await;
+
+    let created = !JournalEntry::exists(today, config);
+    if created {
+        JournalEntry::create(today, config).await?;
+        storage::push_for_date(config, today).await;
+    }
+
+    if config.daemon.prewarm {
+        let count = Index::open(&config.index_path)?.rebuild(&config.journal_dir, config.layout)?;
+        debug!(entries = count, "rebuilt entry index");
+    }
+    Ok(created)
+}
+
+fn modified(config: &Config, date: NaiveDate) -> Option<SystemTime> {
+    let path = filesystem::get_entry_path(date, &config.journal_dir, config.layout);
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Create today's entry; returns the date and modification time of the
+/// entry when this run generated it
+async fn run_once(config: &Config) -> Option<(NaiveDate, SystemTime)> {
+    match create_todays_entry(config).await {
+        Ok(true) => {
+            info!("Created today's entry");
+            if let Err(e) = matrix::post_goals(config).await {
+                warn!("Could not post today's goals to Matrix: {}", e);
+            }
+            let today = Local::now().date_naive();
+            modified(config, today).map(|time| (today, time))
+        }
+        Ok(false) => {
+            debug!("Today's entry already exists");
+            None
+        }
+        Err(e) => {
+            warn!("Could not create today's entry: {}", e);
+            None
+        }
+    }
+}
+
+/// Whether today's entry is missing, or is the one this daemon generated and
+/// nobody has edited since
+fn needs_reminder(
+    config: &Config,
+    today: NaiveDate,
+    generated: Option<(NaiveDate, SystemTime)>,
+) -> bool {
+    match modified(config, today) {
+        None => true,
+        Some(time) => generated == Some((today, time)),
+    }
+}
+
+fn remind(config: &Config, generated: Option<(NaiveDate, SystemTime)>) {
+    let today = Local::now().date_naive();
+    if !needs_reminder(config, today, generated) {
+        debug!("Already journaled today, no reminder");
+        return;
+    }
+    match notify::remind_to_journal() {
+        Ok(()) => info!("Sent journaling reminder"),
+        Err(e) => warn!("{}", e),
+    }
+}
+
+/// Create each day's entry at `DAEMON_CREATE_AT` and, with `REMIND_AT`,
+/// remind to journal when it hasn't been written yet. Runs forever.
+///
+/// When started after the creation time and today's entry is missing, it is
+/// created right away.
+pub async fn run_daily(config: Config) {
+    let at = config.daemon.create_at;
+    let mut generated = None;
+    let now = Local::now().naive_local();
+    if now.time() >= at && !JournalEntry::exists(now.date(), &config) {
+        generated = run_once(&config).await;
+    }
+
+    let now = Local::now().naive_local();
+    let mut next = next_run(now, at);
+    let mut next_reminder = config.daemon.remind_at.map(|at| next_run(now, at));
+    info!("Next entry will be created at {}", next);
+
+    loop {
+        let now = Local::now().naive_local();
+        if now >= next {
+            generated = run_once(&config).await;
+            next = next_run(Local::now().naive_local(), at);
+            info!("Next entry will be created at {}", next);
+            continue;
+        }
+        if let (Some(reminder), Some(remind_at)) = (next_reminder, config.daemon.remind_at)
+            && now >= reminder
+        {
+            remind(&config, generated);
+            next_reminder = Some(next_run(now, remind_at));
+            continue;
+        }
+
+        let due = next_reminder.map_or(next, |reminder| reminder.min(next));
+        let wait = (due - now).to_std().unwrap_or_default();
+        tokio::time::sleep(wait.min(MAX_SLEEP)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_run() {
+        let at = NaiveTime::from_hms_opt(6, 0, 0).unwrap();
+        let day = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+
+        let early = day.and_hms_opt(5, 30, 0).unwrap();
+        assert_eq!(next_run(early, at), day.and_time(at));
+
+        // Exactly at or after the time: tomorrow (across the year boundary)
+        let tomorrow = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap().and_time(at);
+        assert_eq!(next_run(day.and_time(at), at), tomorrow);
+        assert_eq!(next_run(day.and_hms_opt(21, 0, 0).unwrap(), at), tomorrow);
+    }
+
+    #[test]
+    fn test_needs_reminder() {
+        let dir =
+            std::env::temp_dir().join(format!("easy_journal_scheduler_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let config = Config {
+            journal_dir: dir.clone(),
+            ..Config::default()
+        };
+        let today = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        assert!(needs_reminder(&config, today, None));
+
+        let path = filesystem::get_entry_path(today, &config.journal_dir, config.layout);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "# Generated\n").unwrap();
+        let generated = modified(&config, today).map(|time| (today, time));
+
+        // Generated by the daemon and untouched
+        assert!(needs_reminder(&config, today, generated));
+
+        // Written by hand (or generated by someone else)
+        assert!(!needs_reminder(&config, today, None));
+        let edited =
+            generated.map(|(date, time)| (date, time - std::time::Duration::from_secs(60)));
+        assert!(!needs_reminder(&config, today, edited));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
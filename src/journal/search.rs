@@ -0,0 +1,121 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::{self, Layout};
+use crate::journal::navigation;
+
+/// A line of an entry that contains the search text
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Match {
+    pub date: NaiveDate,
+    pub path: PathBuf,
+    /// 1-based
+    pub line: usize,
+    pub text: String,
+}
+
+/// Level and title of a markdown heading line
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    let title = trimmed[level..].strip_prefix(' ')?;
+    (level > 0).then(|| (level, title.trim()))
+}
+
+/// 1-based numbers and lines of `content`, or with `section` only those under
+/// a heading of that name (case-insensitive), up to the next heading of the
+/// same or a higher level or the footer's `---`. The navigation line is left out.
+pub fn section_lines<'a>(content: &'a str, section: Option<&str>) -> Vec<(usize, &'a str)> {
+    // Level of the matching section's heading while inside it
+    let mut inside: Option<usize> = None;
+    let mut lines = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if let Some(wanted) = section {
+            match heading(line) {
+                Some((level, title)) if title.eq_ignore_ascii_case(wanted.trim()) => {
+                    inside = Some(level);
+                    continue;
+                }
+                Some((level, _)) if inside.is_some_and(|section| level <= section) => {
+                    inside = None;
+                }
+                _ if line.trim() == "---" => inside = None,
+                _ => {}
+            }
+            if inside.is_none() {
+                continue;
+            }
+        }
+        if !navigation::is_nav_line(line) {
+            lines.push((i + 1, line));
+        }
+    }
+    lines
+}
+
+/// 1-based numbers and text of the lines of `content` containing `text`
+/// (case-insensitive), only under `section` if given (see [`section_lines`])
+pub fn find(content: &str, text: &str, section: Option<&str>) -> Vec<(usize, String)> {
+    let needle = text.to_lowercase();
+    section_lines(content, section)
+        .into_iter()
+        .filter(|(_, line)| line.to_lowercase().contains(&needle))
+        .map(|(number, line)| (number, line.trim().to_string()))
+        .collect()
+}
+
+/// Every line of every entry containing `text`, oldest entry first
+pub fn search(
+    journal_dir: &Path,
+    layout: Layout,
+    text: &str,
+    section: Option<&str>,
+) -> Result<Vec<Match>> {
+    let mut matches = Vec::new();
+    for (date, path) in filesystem::list_entries(journal_dir, layout)? {
+        let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+        for (line, found) in find(&content, text, section) {
+            matches.push(Match {
+                date,
+                path: path.clone(),
+                line,
+                text: found,
+            });
+        }
+    }
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let content = "# Monday\n\n## Work Accomplished\n- Standup notes shared\n\n### Details\n- Long standup\n\n## Learning & Insights\n- Keep standup notes short\n\n---\n\n**Notes**: standup notes\n[← 2025-12-28](../../2025/12/28.md) <!-- nav -->\n";
+        assert_eq!(
+            find(content, "standup notes", None),
+            vec![
+                (4, "- Standup notes shared".to_string()),
+                (10, "- Keep standup notes short".to_string()),
+                (14, "**Notes**: standup notes".to_string()),
+            ]
+        );
+        assert_eq!(
+            find(content, "STANDUP", Some("work accomplished")),
+            vec![
+                (4, "- Standup notes shared".to_string()),
+                (7, "- Long standup".to_string()),
+            ]
+        );
+        assert_eq!(
+            find(content, "standup", Some("Learning & Insights")),
+            vec![(10, "- Keep standup notes short".to_string())]
+        );
+        assert!(find(content, "2025-12-28", None).is_empty());
+        assert!(find(content, "standup", Some("Gratitude")).is_empty());
+    }
+}
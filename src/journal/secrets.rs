@@ -0,0 +1,350 @@
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::write_private;
+
+/// Keyring entry holding the random key used when no passphrase is set
+const KEYRING_SERVICE: &str = "easy_journal";
+const KEYRING_ACCOUNT: &str = "token-key";
+
+const PBKDF2_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+/// Binds the ciphertext to this file format
+const AAD: &[u8] = b"easy_journal secrets v1";
+
+/// Where the encryption key comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// Derived from TOKEN_PASSPHRASE with PBKDF2
+    Passphrase,
+    /// A random key kept in the OS keyring
+    Keyring,
+}
+
+/// Encrypted data as stored on disk (ChaCha20-Poly1305, base64 fields)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sealed {
+    pub version: u8,
+    pub key: KeySource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+fn failed(message: impl Into<String>) -> JournalError {
+    JournalError::SecretsFailed(message.into())
+}
+
+fn random<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| failed("No secure random number generator available"))?;
+    Ok(bytes)
+}
+
+fn decode(field: &str, value: &str) -> Result<Vec<u8>> {
+    STANDARD
+        .decode(value)
+        .map_err(|e| failed(format!("Invalid {}: {}", field, e)))
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; KEY_LEN] {
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(PBKDF2_ITERATIONS).expect("iterations are non-zero"),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    key
+}
+
+fn cipher(key: &[u8; KEY_LEN]) -> Result<LessSafeKey> {
+    UnboundKey::new(&CHACHA20_POLY1305, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| failed("Invalid key"))
+}
+
+/// Read the keyring entry; `None` when it doesn't exist yet
+fn keyring_lookup() -> Result<Option<String>> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("security");
+        command.args([
+            "find-generic-password",
+            "-s",
+            KEYRING_SERVICE,
+            "-a",
+            KEYRING_ACCOUNT,
+            "-w",
+        ]);
+        command
+    } else {
+        let mut command = Command::new("secret-tool");
+        command.args([
+            "lookup",
+            "service",
+            KEYRING_SERVICE,
+            "account",
+            KEYRING_ACCOUNT,
+        ]);
+        command
+    };
+
+    let output = command.stderr(Stdio::null()).output().map_err(|e| {
+        failed(format!(
+            "No keyring available ({}); set TOKEN_PASSPHRASE instead",
+            e
+        ))
+    })?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((output.status.success() && !value.is_empty()).then_some(value))
+}
+
+/// Save `value` to the keyring, passing it on stdin so it never shows up in
+/// the process list
+fn keyring_store(value: &str) -> Result<()> {
+    let (mut command, input) = if cfg!(target_os = "macos") {
+        // `security -i` reads its commands from stdin
+        let mut command = Command::new("security");
+        command.arg("-i");
+        let input = format!(
+            "add-generic-password -U -s {} -a {} -w {}\n",
+            KEYRING_SERVICE, KEYRING_ACCOUNT, value
+        );
+        (command, input)
+    } else {
+        // secret-tool reads the secret from stdin
+        let mut command = Command::new("secret-tool");
+        command.args([
+            "store",
+            "--label=easy_journal token key",
+            "service",
+            KEYRING_SERVICE,
+            "account",
+            KEYRING_ACCOUNT,
+        ]);
+        (command, value.to_string())
+    };
+
+    let status = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(input.as_bytes())?;
+            }
+            child.wait()
+        });
+
+    match status {
+        // `security -i` succeeds whatever its commands do, so read the key back
+        Ok(status) if status.success() && keyring_lookup()?.as_deref() == Some(value) => Ok(()),
+        Ok(status) => Err(failed(format!(
+            "Could not save the key to the keyring ({}); set TOKEN_PASSPHRASE instead",
+            status
+        ))),
+        Err(e) => Err(failed(format!(
+            "No keyring available ({}); set TOKEN_PASSPHRASE instead",
+            e
+        ))),
+    }
+}
+
+/// The key kept in the OS keyring, created on first use when `create` is set
+fn keyring_key(create: bool) -> Result<[u8; KEY_LEN]> {
+    if let Some(stored) = keyring_lookup()? {
+        return decode("keyring key", &stored)?
+            .try_into()
+            .map_err(|_| failed("The key in the keyring has the wrong length"));
+    }
+    if !create {
+        return Err(failed(
+            "The encryption key is missing from the keyring; run `easy_journal auth` again",
+        ));
+    }
+
+    let key = random::<KEY_LEN>()?;
+    keyring_store(&STANDARD.encode(key))?;
+    tracing::info!("Stored a new token encryption key in the keyring");
+    Ok(key)
+}
+
+/// A random URL-safe string with `bytes` bytes of entropy (OAuth state, PKCE verifier)
+pub fn random_token<const BYTES: usize>() -> Result<String> {
+    Ok(URL_SAFE_NO_PAD.encode(random::<BYTES>()?))
+}
+
+/// Encrypt `plaintext` with a key from `passphrase`, or from the OS keyring
+/// when there is none
+pub fn seal(plaintext: &[u8], passphrase: Option<&str>) -> Result<Sealed> {
+    let (source, salt, key) = match passphrase {
+        Some(passphrase) => {
+            let salt = random::<SALT_LEN>()?;
+            (
+                KeySource::Passphrase,
+                Some(STANDARD.encode(salt)),
+                derive_key(passphrase, &salt),
+            )
+        }
+        None => (KeySource::Keyring, None, keyring_key(true)?),
+    };
+
+    let nonce = random::<NONCE_LEN>()?;
+    let mut data = plaintext.to_vec();
+    cipher(&key)?
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(AAD),
+            &mut data,
+        )
+        .map_err(|_| failed("Encryption failed"))?;
+
+    Ok(Sealed {
+        version: 1,
+        key: source,
+        salt,
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(data),
+    })
+}
+
+/// Decrypt data sealed by [`seal`]
+pub fn open(sealed: &Sealed, passphrase: Option<&str>) -> Result<Vec<u8>> {
+    let key = match sealed.key {
+        KeySource::Passphrase => {
+            let passphrase = passphrase.ok_or_else(|| {
+                failed("The tokens are encrypted with a passphrase; set TOKEN_PASSPHRASE")
+            })?;
+            let salt = decode("salt", sealed.salt.as_deref().unwrap_or_default())?;
+            derive_key(passphrase, &salt)
+        }
+        KeySource::Keyring => keyring_key(false)?,
+    };
+
+    let nonce: [u8; NONCE_LEN] = decode("nonce", &sealed.nonce)?
+        .try_into()
+        .map_err(|_| failed("Invalid nonce length"))?;
+    let mut data = decode("ciphertext", &sealed.ciphertext)?;
+    let plaintext = cipher(&key)?
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(AAD),
+            &mut data,
+        )
+        .map_err(|_| failed("Could not decrypt the tokens (wrong TOKEN_PASSPHRASE?)"))?;
+    Ok(plaintext.to_vec())
+}
+
+/// Encrypt `plaintext` with a key derived from `passphrase`, as one binary
+/// blob: salt, nonce, then the ciphertext. `aad` binds it to its file format.
+pub fn seal_bytes(plaintext: &[u8], passphrase: &str, aad: &[u8]) -> Result<Vec<u8>> {
+    let salt = random::<SALT_LEN>()?;
+    let nonce = random::<NONCE_LEN>()?;
+    let mut data = plaintext.to_vec();
+    cipher(&derive_key(passphrase, &salt))?
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(aad),
+            &mut data,
+        )
+        .map_err(|_| failed("Encryption failed"))?;
+
+    let mut sealed = Vec::with_capacity(SALT_LEN + NONCE_LEN + data.len());
+    sealed.extend_from_slice(&salt);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&data);
+    Ok(sealed)
+}
+
+/// Decrypt a blob made by [`seal_bytes`]
+pub fn open_bytes(sealed: &[u8], passphrase: &str, aad: &[u8]) -> Result<Vec<u8>> {
+    if sealed.len() < SALT_LEN + NONCE_LEN {
+        return Err(failed("Encrypted data is truncated"));
+    }
+    let (salt, rest) = sealed.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce
+        .try_into()
+        .map_err(|_| failed("Invalid nonce length"))?;
+    let mut data = ciphertext.to_vec();
+    let plaintext = cipher(&derive_key(passphrase, salt))?
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(aad),
+            &mut data,
+        )
+        .map_err(|_| failed("Could not decrypt (wrong passphrase?)"))?;
+    Ok(plaintext.to_vec())
+}
+
+/// Encrypt `plaintext` into `path`, readable only by the current user
+pub fn write_sealed(path: &Path, plaintext: &[u8], passphrase: Option<&str>) -> Result<()> {
+    let sealed = seal(plaintext, passphrase)?;
+    let content = serde_json::to_string_pretty(&sealed)
+        .map_err(|e| failed(format!("Failed to serialize: {}", e)))?;
+    write_private(path, content)
+}
+
+/// Decrypt a file written by [`write_sealed`]; `None` when it doesn't exist
+pub fn read_sealed(path: &Path, passphrase: Option<&str>) -> Result<Option<Vec<u8>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read(path).map_err(JournalError::at_path(path))?;
+    let sealed: Sealed = serde_json::from_slice(&content).map_err(|e| {
+        failed(format!(
+            "{} is not an encrypted file: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    open(&sealed, passphrase).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open_with_passphrase() {
+        let sealed = seal(b"refresh-token", Some("correct horse")).unwrap();
+        assert_eq!(sealed.key, KeySource::Passphrase);
+        assert!(!sealed.ciphertext.contains("refresh"));
+
+        assert_eq!(
+            open(&sealed, Some("correct horse")).unwrap(),
+            b"refresh-token"
+        );
+        assert!(open(&sealed, Some("wrong")).is_err());
+        assert!(open(&sealed, None).is_err());
+    }
+
+    #[test]
+    fn test_seal_and_open_bytes() {
+        let sealed = seal_bytes(b"journal archive", "correct horse", b"test").unwrap();
+        assert_eq!(
+            open_bytes(&sealed, "correct horse", b"test").unwrap(),
+            b"journal archive"
+        );
+        assert!(open_bytes(&sealed, "wrong", b"test").is_err());
+        assert!(open_bytes(&sealed, "correct horse", b"other").is_err());
+        assert!(open_bytes(&sealed[..8], "correct horse", b"test").is_err());
+    }
+}
@@ -0,0 +1,768 @@
+use chrono::{Datelike, NaiveDate};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+use tracing::{Instrument, debug, info_span, warn};
+
+use crate::config::{self, Config};
+use crate::error::{JournalError, Result};
+use crate::journal::{
+    apple_health, confluence, credentials, filesystem, fitbit, github, github_actions, gitlab,
+    google_tasks, imap, lastfm, mastodon, on_this_day, oncall, oura, parser, prompts, read_later,
+    reminders, rss, sentry, strava, template, toggl, wakatime, weather,
+};
+
+/// Boxed future returned by [`JournalSection::fetch`] so sections can be stored as trait objects
+pub type SectionFuture<'a> = Pin<Box<dyn Future<Output = Result<Option<String>>> + Send + 'a>>;
+
+/// A source of generated content for new entries (reminders, issues, weather, ...).
+///
+/// Sections are assembled in the order given by `JOURNAL_SECTIONS`. A section's
+/// content goes into its own `{{name}}` placeholder when the template has one,
+/// otherwise it is listed under `### Title` in the `{{reminders}}` block.
+pub trait JournalSection: Send + Sync {
+    /// Identifier used in `JOURNAL_SECTIONS` and as the template placeholder
+    fn name(&self) -> &str;
+
+    /// Heading used in the `{{reminders}}` block
+    fn title(&self) -> &str;
+
+    /// Heading of the entry the content goes under when the template has no
+    /// `{{name}}` placeholder, instead of the `{{reminders}}` block
+    fn heading(&self) -> Option<&str> {
+        None
+    }
+
+    /// Fetch the section's markdown for `date`; `None` when there is nothing to show
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a>;
+}
+
+pub struct AppleRemindersSection;
+
+impl JournalSection for AppleRemindersSection {
+    fn name(&self) -> &str {
+        "reminders"
+    }
+
+    fn title(&self) -> &str {
+        "Apple Reminders"
+    }
+
+    fn fetch<'a>(&'a self, _date: NaiveDate, _config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(reminders::fetch_apple_reminders_async())
+    }
+}
+
+pub struct GoogleTasksSection;
+
+impl JournalSection for GoogleTasksSection {
+    fn name(&self) -> &str {
+        "google_tasks"
+    }
+
+    fn title(&self) -> &str {
+        "Google Tasks"
+    }
+
+    fn fetch<'a>(&'a self, _date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(google_tasks::fetch_google_tasks(&config.google_oauth))
+    }
+}
+
+pub struct GitHubSection;
+
+impl JournalSection for GitHubSection {
+    fn name(&self) -> &str {
+        "github"
+    }
+
+    fn title(&self) -> &str {
+        "GitHub"
+    }
+
+    fn fetch<'a>(&'a self, _date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(github::fetch_github_items(&config.github_config))
+    }
+}
+
+pub struct GitHubCiSection;
+
+impl JournalSection for GitHubCiSection {
+    fn name(&self) -> &str {
+        "github_ci"
+    }
+
+    fn title(&self) -> &str {
+        "CI needs attention"
+    }
+
+    fn fetch<'a>(&'a self, _date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(github_actions::fetch_failed_runs(&config.github_config))
+    }
+}
+
+pub struct GitLabSection;
+
+impl JournalSection for GitLabSection {
+    fn name(&self) -> &str {
+        "gitlab"
+    }
+
+    fn title(&self) -> &str {
+        "GitLab"
+    }
+
+    fn fetch<'a>(&'a self, _date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(gitlab::fetch_gitlab_items(&config.gitlab_config))
+    }
+}
+
+pub struct FitbitSection;
+
+impl JournalSection for FitbitSection {
+    fn name(&self) -> &str {
+        "fitbit"
+    }
+
+    fn title(&self) -> &str {
+        "Fitbit"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(fitbit::fetch_fitbit(&config.fitbit, date))
+    }
+}
+
+pub struct StravaSection;
+
+impl JournalSection for StravaSection {
+    fn name(&self) -> &str {
+        "strava"
+    }
+
+    fn title(&self) -> &str {
+        "Exercise"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(strava::fetch_activities(&config.strava, date))
+    }
+}
+
+pub struct WakaTimeSection;
+
+impl JournalSection for WakaTimeSection {
+    fn name(&self) -> &str {
+        "wakatime"
+    }
+
+    fn title(&self) -> &str {
+        "Coding"
+    }
+
+    fn heading(&self) -> Option<&str> {
+        Some("Work Accomplished")
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(wakatime::fetch_coding_time(&config.wakatime, date))
+    }
+}
+
+pub struct TogglSection;
+
+impl JournalSection for TogglSection {
+    fn name(&self) -> &str {
+        "toggl"
+    }
+
+    fn title(&self) -> &str {
+        "Time Tracked"
+    }
+
+    fn heading(&self) -> Option<&str> {
+        Some("Work Accomplished")
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(toggl::fetch_time_entries(&config.toggl, date))
+    }
+}
+
+pub struct LastFmSection;
+
+impl JournalSection for LastFmSection {
+    fn name(&self) -> &str {
+        "lastfm"
+    }
+
+    fn title(&self) -> &str {
+        "Soundtrack"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(lastfm::fetch_listening(&config.lastfm, date))
+    }
+}
+
+pub struct ReadLaterSection;
+
+impl JournalSection for ReadLaterSection {
+    fn name(&self) -> &str {
+        "read_later"
+    }
+
+    fn title(&self) -> &str {
+        "To read"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(read_later::fetch_queue(&config.read_later, date))
+    }
+}
+
+pub struct RssSection;
+
+impl JournalSection for RssSection {
+    fn name(&self) -> &str {
+        "rss"
+    }
+
+    fn title(&self) -> &str {
+        "Reading / News"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(rss::fetch_headlines(&config.rss, date))
+    }
+}
+
+pub struct MastodonSection;
+
+impl JournalSection for MastodonSection {
+    fn name(&self) -> &str {
+        "mastodon"
+    }
+
+    fn title(&self) -> &str {
+        "Posted"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(mastodon::fetch_posts(&config.mastodon, date))
+    }
+}
+
+pub struct ImapSection;
+
+impl JournalSection for ImapSection {
+    fn name(&self) -> &str {
+        "imap"
+    }
+
+    fn title(&self) -> &str {
+        "Follow up"
+    }
+
+    fn fetch<'a>(&'a self, _date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(imap::fetch_flagged(&config.imap))
+    }
+}
+
+pub struct OnCallSection;
+
+impl JournalSection for OnCallSection {
+    fn name(&self) -> &str {
+        "oncall"
+    }
+
+    fn title(&self) -> &str {
+        "On call"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(oncall::fetch_oncall(&config.oncall, date))
+    }
+}
+
+pub struct SentrySection;
+
+impl JournalSection for SentrySection {
+    fn name(&self) -> &str {
+        "sentry"
+    }
+
+    fn title(&self) -> &str {
+        "Sentry"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(sentry::fetch_issues(&config.sentry, date))
+    }
+}
+
+pub struct ConfluenceSection;
+
+impl JournalSection for ConfluenceSection {
+    fn name(&self) -> &str {
+        "confluence"
+    }
+
+    fn title(&self) -> &str {
+        "Docs updated"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(confluence::fetch_updated_pages(&config.confluence, date))
+    }
+}
+
+pub struct OuraSection;
+
+impl JournalSection for OuraSection {
+    fn name(&self) -> &str {
+        "oura"
+    }
+
+    fn title(&self) -> &str {
+        "Body"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(oura::fetch_oura(&config.oura, date))
+    }
+}
+
+pub struct AppleHealthSection;
+
+impl JournalSection for AppleHealthSection {
+    fn name(&self) -> &str {
+        "apple_health"
+    }
+
+    fn title(&self) -> &str {
+        "Sleep"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(apple_health::fetch_sleep(&config.apple_health, date))
+    }
+}
+
+pub struct WeatherSection;
+
+impl JournalSection for WeatherSection {
+    fn name(&self) -> &str {
+        "weather"
+    }
+
+    fn title(&self) -> &str {
+        "Weather"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(weather::fetch_weather(&config.weather, date))
+    }
+}
+
+/// Writing prompts picked from patterns in recent entries (see `journal/prompts.rs`)
+pub struct PromptsSection;
+
+impl JournalSection for PromptsSection {
+    fn name(&self) -> &str {
+        "prompts"
+    }
+
+    fn title(&self) -> &str {
+        "Prompts"
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(async move {
+            let suggestions = prompts::suggest(&config.journal_dir, config.layout, date)?;
+            Ok(Some(prompts::to_markdown(&suggestions)))
+        })
+    }
+}
+
+/// Output of a user-defined shell command (`custom:<name>` + `SECTION_<NAME>_COMMAND`).
+///
+/// The command runs with `JOURNAL_DATE` set to the entry date.
+pub struct CustomSection {
+    name: String,
+    title: String,
+    command: String,
+}
+
+impl CustomSection {
+    pub fn new(name: &str, command: &str) -> Self {
+        CustomSection {
+            name: name.to_string(),
+            title: name.replace(['_', '-'], " "),
+            command: command.to_string(),
+        }
+    }
+}
+
+impl JournalSection for CustomSection {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn fetch<'a>(&'a self, date: NaiveDate, _config: &'a Config) -> SectionFuture<'a> {
+        Box::pin(async move {
+            let output = tokio::process::Command::new("sh")
+                .arg("-c")
+                .arg(&self.command)
+                .envs(config::loaded())
+                .env("JOURNAL_DATE", date.format("%Y-%m-%d").to_string())
+                .output()
+                .await
+                .map_err(|e| {
+                    JournalError::SectionFailed(format!("Failed to run '{}': {}", self.command, e))
+                })?;
+
+            if !output.status.success() {
+                return Err(JournalError::SectionFailed(format!(
+                    "'{}' exited with {}: {}",
+                    self.command,
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )));
+            }
+
+            let stdout = String::from_utf8_lossy(&output.stdout)
+                .trim_end()
+                .to_string();
+            Ok((!stdout.is_empty()).then_some(stdout))
+        })
+    }
+}
+
+/// Receives progress while sections are fetched, e.g. to draw spinners.
+///
+/// `()` ignores all progress.
+pub trait SectionProgress: Send + Sync {
+    fn started(&self, _section: &dyn JournalSection) {}
+
+    fn finished(
+        &self,
+        _section: &dyn JournalSection,
+        _result: &Result<Option<String>>,
+        _elapsed: Duration,
+    ) {
+    }
+}
+
+impl SectionProgress for () {}
+
+/// Build the sections listed in `JOURNAL_SECTIONS`, in order
+pub fn configured_sections(config: &Config) -> Vec<Box<dyn JournalSection>> {
+    let mut sections: Vec<Box<dyn JournalSection>> = Vec::new();
+
+    for name in &config.sections {
+        let section: Box<dyn JournalSection> = match name.as_str() {
+            "reminders" | "apple_reminders" => Box::new(AppleRemindersSection),
+            "google_tasks" => Box::new(GoogleTasksSection),
+            "github" => Box::new(GitHubSection),
+            "github_ci" => Box::new(GitHubCiSection),
+            "gitlab" => Box::new(GitLabSection),
+            "fitbit" => Box::new(FitbitSection),
+            "apple_health" => Box::new(AppleHealthSection),
+            "oura" => Box::new(OuraSection),
+            "strava" => Box::new(StravaSection),
+            "wakatime" => Box::new(WakaTimeSection),
+            "toggl" => Box::new(TogglSection),
+            "lastfm" => Box::new(LastFmSection),
+            "read_later" => Box::new(ReadLaterSection),
+            "rss" => Box::new(RssSection),
+            "mastodon" => Box::new(MastodonSection),
+            "imap" => Box::new(ImapSection),
+            "oncall" => Box::new(OnCallSection),
+            "sentry" => Box::new(SentrySection),
+            "confluence" => Box::new(ConfluenceSection),
+            "weather" => Box::new(WeatherSection),
+            "prompts" => Box::new(PromptsSection),
+            other => match other.strip_prefix("custom:") {
+                Some(custom) => match config.custom_sections.get(custom) {
+                    Some(command) => Box::new(CustomSection::new(custom, command)),
+                    None => {
+                        warn!(
+                            "Section '{}' has no SECTION_{}_COMMAND set",
+                            other,
+                            custom.to_uppercase()
+                        );
+                        continue;
+                    }
+                },
+                None => {
+                    warn!("Unknown section '{}' in JOURNAL_SECTIONS", other);
+                    continue;
+                }
+            },
+        };
+        sections.push(section);
+    }
+
+    sections
+}
+
+/// Content fetched for one section
+pub struct FetchedSection {
+    pub name: String,
+    pub title: String,
+    pub heading: Option<String>,
+    pub content: String,
+}
+
+/// Fetch all sections concurrently; failures print a warning and are skipped
+pub async fn fetch_sections(
+    sections: &[Box<dyn JournalSection>],
+    date: NaiveDate,
+    config: &Config,
+    progress: &dyn SectionProgress,
+) -> Vec<FetchedSection> {
+    let fetches = sections.iter().map(|section| {
+        let span = info_span!("section", name = section.name());
+        async move {
+            progress.started(section.as_ref());
+            let started = Instant::now();
+            let result = section.fetch(date, config).await;
+            let elapsed = started.elapsed();
+            progress.finished(section.as_ref(), &result, elapsed);
+            let elapsed_ms = elapsed.as_millis() as u64;
+
+            match result {
+                Ok(Some(content)) => {
+                    debug!(elapsed_ms, lines = content.lines().count(), "fetched");
+                    Some(FetchedSection {
+                        name: section.name().to_string(),
+                        title: section.title().to_string(),
+                        heading: section.heading().map(String::from),
+                        content,
+                    })
+                }
+                Ok(None) => {
+                    debug!(elapsed_ms, "produced nothing");
+                    None
+                }
+                Err(e) => {
+                    warn!(elapsed_ms, "Could not fetch {}: {}", section.title(), e);
+                    None
+                }
+            }
+        }
+        .instrument(span)
+    });
+
+    let fetched: Vec<FetchedSection> = futures::future::join_all(fetches)
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    let names: Vec<&str> = fetched
+        .iter()
+        .map(|section| section.name.as_str())
+        .collect();
+    credentials::record_fetches(&config.last_fetch_path, &names);
+    fetched
+}
+
+/// Put fetched sections into the template: into their own `{{name}}`
+/// placeholder when present, else under their entry heading (see
+/// [`JournalSection::heading`]), the rest combined into the `{{reminders}}` block.
+///
+/// `**Label**: value` lines of a section fill the template's empty metric line
+/// with the same label instead (e.g. `**Sleep Time**:` in the footer).
+/// Placeholders of the configured `names` that produced nothing are removed.
+pub fn place_sections(
+    template: &str,
+    names: &[&str],
+    fetched: &[FetchedSection],
+) -> (String, Option<String>) {
+    let mut template = template.to_string();
+    let mut combined = Vec::new();
+
+    for section in fetched {
+        let (filled, content) = parser::fill_metrics(&template, &section.content);
+        template = filled;
+        if content.is_empty() {
+            continue;
+        }
+
+        let placeholder = format!("{{{{{}}}}}", section.name);
+        if section.name != "reminders" && template.contains(&placeholder) {
+            template = template.replace(&placeholder, &content);
+        } else if let Some(filled) = section
+            .heading
+            .as_deref()
+            .and_then(|heading| parser::insert_under_heading(&template, heading, &content))
+        {
+            template = filled;
+        } else {
+            combined.push(format!("### {}\n{}", section.title, content));
+        }
+    }
+
+    for name in names.iter().filter(|name| **name != "reminders") {
+        template = template.replace(&format!("{{{{{}}}}}", name), "");
+    }
+
+    let combined = (!combined.is_empty()).then(|| combined.join("\n\n"));
+    (template, combined)
+}
+
+/// Render a new entry: fetch the configured sections and apply the template
+pub async fn render_entry(
+    template_content: &str,
+    date: NaiveDate,
+    previous_content: Option<String>,
+    config: &Config,
+    progress: &dyn SectionProgress,
+) -> String {
+    let sections = configured_sections(config);
+    let fetched = fetch_sections(&sections, date, config, progress).await;
+    let names: Vec<&str> = sections.iter().map(|s| s.name()).collect();
+    let (mut template_content, combined) = place_sections(template_content, &names, &fetched);
+
+    if template_content.contains("{{month_goals}}") {
+        let month_readme = filesystem::get_month_path(
+            date.year() as u32,
+            date.month(),
+            &config.journal_dir,
+            config.layout,
+        );
+        let goals = std::fs::read_to_string(&month_readme)
+            .ok()
+            .and_then(|readme| template::month_goals(&readme))
+            .unwrap_or_default();
+        template_content = template_content.replace("{{month_goals}}", &goals);
+    }
+
+    if template_content.contains("{{on_this_day}}") {
+        let memories =
+            on_this_day::find(&config.journal_dir, config.layout, date).unwrap_or_else(|e| {
+                warn!("Could not look up earlier entries for {}: {}", date, e);
+                Vec::new()
+            });
+        template_content = template_content.replace(
+            "{{on_this_day}}",
+            &on_this_day::to_markdown(&memories, config.layout),
+        );
+    }
+
+    template::apply_variables(&template_content, date, previous_content, combined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn fetched(name: &str, title: &str, content: &str) -> FetchedSection {
+        FetchedSection {
+            name: name.to_string(),
+            title: title.to_string(),
+            heading: None,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_place_sections_combines_in_order() {
+        let template = "## Reminders\n{{reminders}}\n";
+        let (template, combined) = place_sections(
+            template,
+            &["github", "gitlab"],
+            &[
+                fetched("github", "GitHub", "- [ ] PR"),
+                fetched("gitlab", "GitLab", "- [ ] MR"),
+            ],
+        );
+
+        assert_eq!(template, "## Reminders\n{{reminders}}\n");
+        assert_eq!(
+            combined.unwrap(),
+            "### GitHub\n- [ ] PR\n\n### GitLab\n- [ ] MR"
+        );
+    }
+
+    #[test]
+    fn test_place_sections_uses_own_placeholder() {
+        let template = "## Weather\n{{weather}}\n## Reminders\n{{reminders}}\n{{gitlab}}";
+        let (template, combined) = place_sections(
+            template,
+            &["weather", "gitlab"],
+            &[fetched("weather", "Weather", "- ☀️ Clear")],
+        );
+
+        assert_eq!(
+            template,
+            "## Weather\n- ☀️ Clear\n## Reminders\n{{reminders}}\n"
+        );
+        assert!(combined.is_none());
+    }
+
+    #[test]
+    fn test_place_sections_under_heading() {
+        let template = "## Work Accomplished\n-\n## Reminders\n{{reminders}}\n";
+        let section = FetchedSection {
+            heading: Some("Work Accomplished".to_string()),
+            ..fetched("wakatime", "Coding", "- ⌨️ 3h 05m")
+        };
+        let (template, combined) = place_sections(template, &["wakatime"], &[section]);
+
+        assert_eq!(
+            template,
+            "## Work Accomplished\n- ⌨️ 3h 05m\n-\n## Reminders\n{{reminders}}\n"
+        );
+        assert!(combined.is_none());
+    }
+
+    #[test]
+    fn test_configured_sections_order() {
+        let config = Config {
+            sections: vec![
+                "github".to_string(),
+                "weather".to_string(),
+                "prompts".to_string(),
+                "nonsense".to_string(),
+                "custom:standup".to_string(),
+            ],
+            custom_sections: HashMap::from([("standup".to_string(), "echo hi".to_string())]),
+            ..Config::default()
+        };
+
+        let names: Vec<String> = configured_sections(&config)
+            .iter()
+            .map(|s| s.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["github", "weather", "prompts", "standup"]);
+    }
+
+    #[tokio::test]
+    async fn test_custom_section_runs_command() {
+        let section = CustomSection::new("standup", "echo \"Standup for $JOURNAL_DATE\"");
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let content = section.fetch(date, &Config::default()).await.unwrap();
+        assert_eq!(content.unwrap(), "Standup for 2025-12-29");
+    }
+
+    #[tokio::test]
+    async fn test_disabled_integrations_produce_nothing() {
+        let mut config = Config {
+            sections: vec!["github".to_string(), "gitlab".to_string()],
+            ..Config::default()
+        };
+        config.github_config.enabled = false;
+        config.gitlab_config.enabled = false;
+
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let fetched = fetch_sections(&configured_sections(&config), date, &config, &()).await;
+        assert!(fetched.is_empty());
+    }
+}
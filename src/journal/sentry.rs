@@ -0,0 +1,184 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use serde::Deserialize;
+
+use crate::config::SentryConfig;
+use crate::error::{JournalError, Result, check_response};
+
+/// Issues listed per query
+const LIMIT: &str = "25";
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct Issue {
+    id: String,
+    short_id: String,
+    title: String,
+    permalink: String,
+    #[serde(default)]
+    count: String,
+    project: Project,
+    last_seen: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct Project {
+    slug: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    New,
+    Regressed,
+}
+
+/// New issues first, then regressions; an issue found by both queries is listed once
+fn format_issues(issues: &[(Kind, Issue)]) -> Option<String> {
+    let mut listed: Vec<&str> = Vec::new();
+    let mut lines = Vec::new();
+    for kind in [Kind::New, Kind::Regressed] {
+        let mut group: Vec<&Issue> = issues
+            .iter()
+            .filter(|(k, _)| *k == kind)
+            .map(|(_, issue)| issue)
+            .collect();
+        group.sort_by_key(|issue| std::cmp::Reverse(issue.last_seen));
+        for issue in group {
+            if listed.contains(&issue.id.as_str()) {
+                continue;
+            }
+            listed.push(&issue.id);
+            let label = match kind {
+                Kind::New => "new",
+                Kind::Regressed => "regressed",
+            };
+            let mut details = vec![issue.project.slug.clone(), label.to_string()];
+            if let Ok(events) = issue.count.parse::<u64>() {
+                details.push(format!(
+                    "{} event{}",
+                    events,
+                    if events == 1 { "" } else { "s" }
+                ));
+            }
+            lines.push(format!(
+                "- [ ] 🐛 [{}]({}) {} ({})",
+                issue.short_id,
+                issue.permalink,
+                issue.title,
+                details.join(", ")
+            ));
+        }
+    }
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+async fn search(
+    client: &reqwest::Client,
+    token: &str,
+    url: &str,
+    query: &str,
+) -> Result<Vec<Issue>> {
+    let response = client
+        .get(url)
+        .bearer_auth(token)
+        .query(&[("query", query), ("limit", LIMIT), ("sort", "date")])
+        .send()
+        .await
+        .map_err(|e| JournalError::SentryFailed(format!("Request failed: {}", e)))?;
+    check_response(response, "Sentry")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::SentryFailed(format!("Failed to parse issues: {}", e)))
+}
+
+/// Issues first seen or regressed since the start of the day before `date`: those
+/// assigned to you, or every issue in SENTRY_PROJECTS when set
+pub async fn fetch_issues(config: &SentryConfig, date: NaiveDate) -> Result<Option<String>> {
+    let (Some(token), Some(org)) = (&config.auth_token, &config.org) else {
+        tracing::debug!("SENTRY_AUTH_TOKEN/SENTRY_ORG not set");
+        return Ok(None);
+    };
+
+    let since = Local
+        .from_local_datetime(
+            &(date - Duration::days(1))
+                .and_hms_opt(0, 0, 0)
+                .unwrap_or_default(),
+        )
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%S")
+        .to_string();
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::SentryFailed(format!("Failed to build HTTP client: {}", e)))?;
+
+    let api = format!("{}/api/0", config.url.trim_end_matches('/'));
+    // (endpoint, extra filter)
+    let scopes: Vec<(String, &str)> = if config.projects.is_empty() {
+        vec![(
+            format!("{}/organizations/{}/issues/", api, org),
+            " assigned:me",
+        )]
+    } else {
+        config
+            .projects
+            .iter()
+            .map(|project| (format!("{}/projects/{}/{}/issues/", api, org, project), ""))
+            .collect()
+    };
+
+    let mut issues = Vec::new();
+    for (url, filter) in &scopes {
+        let new = format!("is:unresolved firstSeen:>{}{}", since, filter);
+        let regressed = format!("is:regressed lastSeen:>{}{}", since, filter);
+        for issue in search(&client, token, url, &new).await? {
+            issues.push((Kind::New, issue));
+        }
+        for issue in search(&client, token, url, &regressed).await? {
+            issues.push((Kind::Regressed, issue));
+        }
+    }
+
+    Ok(format_issues(&issues))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_issues() {
+        let issues: Vec<Issue> = serde_json::from_str(
+            r#"[
+                {"id": "1", "shortId": "WEB-1A", "title": "TypeError: x is undefined",
+                 "permalink": "https://acme.sentry.io/issues/1/", "count": "132",
+                 "project": {"slug": "web"}, "lastSeen": "2025-12-28T20:00:00Z"},
+                {"id": "2", "shortId": "API-7", "title": "Timeout in checkout",
+                 "permalink": "https://acme.sentry.io/issues/2/", "count": "1",
+                 "project": {"slug": "api"}, "lastSeen": "2025-12-28T22:00:00Z"}
+            ]"#,
+        )
+        .unwrap();
+        let found = vec![
+            (Kind::Regressed, issues[0].clone()),
+            (Kind::New, issues[1].clone()),
+            (Kind::New, issues[0].clone()),
+        ];
+
+        assert_eq!(
+            format_issues(&found).unwrap(),
+            "- [ ] 🐛 [API-7](https://acme.sentry.io/issues/2/) Timeout in checkout (api, new, 1 event)\n\
+             - [ ] 🐛 [WEB-1A](https://acme.sentry.io/issues/1/) TypeError: x is undefined (web, new, 132 events)"
+        );
+        assert_eq!(format_issues(&[]), None);
+    }
+}
@@ -0,0 +1,153 @@
+use chrono::{Datelike, NaiveDate};
+use std::path::PathBuf;
+
+use crate::journal::parser;
+
+/// Static site generator an export is laid out for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Generator {
+    Zola,
+    Hugo,
+}
+
+/// Section everything goes under, e.g. `content/journal/2025/12/29.md`
+const SECTION: &str = "journal";
+
+fn quote(text: &str) -> String {
+    toml::Value::String(text.to_string()).to_string()
+}
+
+/// The entry's `# ` heading as the page title (the date if there is none) and
+/// the body without it
+fn split_title(date: NaiveDate, content: &str) -> (String, String) {
+    let mut lines = content.lines();
+    let first = lines
+        .by_ref()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or_default();
+    match first.trim().strip_prefix("# ") {
+        Some(title) => (
+            title.trim().to_string(),
+            lines.collect::<Vec<_>>().join("\n").trim().to_string(),
+        ),
+        None => (
+            date.format("%A, %B %-d, %Y").to_string(),
+            content.trim().to_string(),
+        ),
+    }
+}
+
+/// TOML front matter; Zola keeps tags under `[taxonomies]`
+fn front_matter(generator: Generator, title: &str, date: NaiveDate, tags: &[String]) -> String {
+    let tags = format!(
+        "[{}]",
+        tags.iter()
+            .map(|tag| quote(tag))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    match generator {
+        Generator::Zola => format!(
+            "+++\ntitle = {}\ndate = {}\n\n[taxonomies]\ntags = {}\n+++\n",
+            quote(title),
+            date,
+            tags
+        ),
+        Generator::Hugo => format!(
+            "+++\ntitle = {}\ndate = {}\ntags = {}\n+++\n",
+            quote(title),
+            date,
+            tags
+        ),
+    }
+}
+
+fn section_index(title: &str, generator: Generator) -> String {
+    match generator {
+        Generator::Zola => format!("+++\ntitle = {}\nsort_by = \"date\"\n+++\n", quote(title)),
+        Generator::Hugo => format!("+++\ntitle = {}\n+++\n", quote(title)),
+    }
+}
+
+/// Files (relative to the site directory) for `entries` (date, markdown; oldest
+/// first): one page per entry plus an `_index.md` for the journal section and
+/// each year and month in it
+pub fn pages(generator: Generator, entries: &[(NaiveDate, String)]) -> Vec<(PathBuf, String)> {
+    let content = PathBuf::from("content").join(SECTION);
+    let mut files = vec![(
+        content.join("_index.md"),
+        section_index("Journal", generator),
+    )];
+
+    for (date, markdown) in entries {
+        let year_dir = content.join(date.year().to_string());
+        let month_dir = year_dir.join(format!("{:02}", date.month()));
+        if !files.iter().any(|(path, _)| path.starts_with(&year_dir)) {
+            files.push((
+                year_dir.join("_index.md"),
+                section_index(&date.year().to_string(), generator),
+            ));
+        }
+        if !files.iter().any(|(path, _)| path.starts_with(&month_dir)) {
+            files.push((
+                month_dir.join("_index.md"),
+                section_index(&date.format("%B %Y").to_string(), generator),
+            ));
+        }
+
+        let (title, body) = split_title(*date, markdown);
+        let tags = parser::extract_tags(&body);
+        files.push((
+            month_dir.join(format!("{:02}.md", date.day())),
+            format!(
+                "{}\n{}\n",
+                front_matter(generator, &title, *date, &tags),
+                body
+            ),
+        ));
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pages() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        let entries = vec![(
+            date,
+            "# 2025-12-29 - \"Monday\"\n\n## Work\n- Shipped #release\n".to_string(),
+        )];
+
+        let zola = pages(Generator::Zola, &entries);
+        let paths: Vec<String> = zola
+            .iter()
+            .map(|(path, _)| path.display().to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "content/journal/_index.md",
+                "content/journal/2025/_index.md",
+                "content/journal/2025/12/_index.md",
+                "content/journal/2025/12/29.md",
+            ]
+        );
+        assert_eq!(
+            zola[3].1,
+            "+++\ntitle = '2025-12-29 - \"Monday\"'\ndate = 2025-12-29\n\n[taxonomies]\ntags = [\"release\"]\n+++\n\n## Work\n- Shipped #release\n"
+        );
+        assert!(
+            zola[2]
+                .1
+                .contains("title = \"December 2025\"\nsort_by = \"date\"")
+        );
+
+        let hugo = pages(Generator::Hugo, &entries);
+        assert!(hugo[3].1.starts_with(
+            "+++\ntitle = '2025-12-29 - \"Monday\"'\ndate = 2025-12-29\ntags = [\"release\"]\n+++\n"
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::{self, Layout};
+use crate::journal::{parser, prompts};
+
+/// Entries compared against the ones before them for a trend
+const TREND_WINDOW: usize = 7;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonthCount {
+    /// `YYYY-MM`
+    pub month: String,
+    pub entries: usize,
+}
+
+/// Scores (out of 10) of a footer field such as Mood
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Trend {
+    pub entries: usize,
+    pub average: f32,
+    /// Average of the latest `TREND_WINDOW` scored entries
+    pub recent: f32,
+    /// Average of the `TREND_WINDOW` scored entries before those, if there are any
+    pub previous: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    pub entries: usize,
+    /// Consecutive days with an entry ending today, or yesterday if today has none yet
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    pub per_month: Vec<MonthCount>,
+    pub average_words: f32,
+    pub tasks_done: usize,
+    pub tasks_open: usize,
+    pub mood: Option<Trend>,
+    pub energy: Option<Trend>,
+}
+
+impl Stats {
+    /// Share of checked tasks, `None` without any tasks
+    pub fn completion_rate(&self) -> Option<f32> {
+        let total = self.tasks_done + self.tasks_open;
+        (total > 0).then(|| self.tasks_done as f32 / total as f32)
+    }
+}
+
+fn mean(values: &[f32]) -> f32 {
+    values.iter().sum::<f32>() / values.len() as f32
+}
+
+fn trend(scores: &[f32]) -> Option<Trend> {
+    if scores.is_empty() {
+        return None;
+    }
+    let split = scores.len().saturating_sub(TREND_WINDOW);
+    let previous = &scores[split.saturating_sub(TREND_WINDOW)..split];
+    Some(Trend {
+        entries: scores.len(),
+        average: mean(scores),
+        recent: mean(&scores[split..]),
+        previous: (!previous.is_empty()).then(|| mean(previous)),
+    })
+}
+
+/// Statistics over `entries` (date and content, oldest first)
+pub fn compute(entries: &[(NaiveDate, String)], today: NaiveDate) -> Stats {
+    let mut longest_streak = 0;
+    let mut streak = 0;
+    let mut per_month: Vec<MonthCount> = Vec::new();
+    let (mut words, mut tasks_done, mut tasks_open) = (0, 0, 0);
+    let (mut moods, mut energies) = (Vec::new(), Vec::new());
+
+    for (i, (date, content)) in entries.iter().enumerate() {
+        streak = match i.checked_sub(1).map(|prev| entries[prev].0) {
+            Some(prev) if date.pred_opt() == Some(prev) => streak + 1,
+            _ => 1,
+        };
+        longest_streak = longest_streak.max(streak);
+
+        let month = format!("{}-{:02}", date.year(), date.month());
+        match per_month.last_mut() {
+            Some(last) if last.month == month => last.entries += 1,
+            _ => per_month.push(MonthCount { month, entries: 1 }),
+        }
+
+        words += parser::word_count(content);
+        for (_, done) in parser::extract_tasks(content) {
+            if done {
+                tasks_done += 1;
+            } else {
+                tasks_open += 1;
+            }
+        }
+
+        let score = |name: &str| {
+            parser::extract_metrics(content)
+                .into_iter()
+                .find(|(key, _)| parser::metric_name(key).starts_with(name))
+                .and_then(|(_, value)| prompts::parse_score(&value))
+        };
+        moods.extend(score("mood"));
+        energies.extend(score("energy"));
+    }
+
+    let current_streak = match entries.last() {
+        Some((last, _)) if *last == today || last.succ_opt() == Some(today) => streak,
+        _ => 0,
+    };
+
+    Stats {
+        entries: entries.len(),
+        current_streak,
+        longest_streak,
+        per_month,
+        average_words: if entries.is_empty() {
+            0.0
+        } else {
+            words as f32 / entries.len() as f32
+        },
+        tasks_done,
+        tasks_open,
+        mood: trend(&moods),
+        energy: trend(&energies),
+    }
+}
+
+/// Statistics over every entry in the journal
+pub fn collect(journal_dir: &Path, layout: Layout, today: NaiveDate) -> Result<Stats> {
+    let mut entries = Vec::new();
+    for (date, path) in filesystem::list_entries(journal_dir, layout)? {
+        let content = fs::read_to_string(&path).map_err(JournalError::at_path(&path))?;
+        entries.push((date, content));
+    }
+    Ok(compute(&entries, today))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        let entry = |tasks: &str, mood: &str| {
+            format!(
+                "## Goals for Today\n{}\n\n---\n\n**Mood(1-10)**: {}\n**Energy Level**:\n",
+                tasks, mood
+            )
+        };
+        let mut entries = vec![
+            (date(11, 28), entry("- [x] a", "4")),
+            (date(11, 29), entry("- [x] a\n- [ ] b", "")),
+            (date(11, 30), entry("- [ ]", "2/5")),
+            (date(12, 2), entry("- [ ] c", "6")),
+        ];
+        for day in 3..=9 {
+            entries.push((date(12, day), entry("- [x] d", "8")));
+        }
+
+        let stats = compute(&entries, date(12, 10));
+        assert_eq!(stats.entries, 11);
+        assert_eq!(stats.current_streak, 8);
+        assert_eq!(stats.longest_streak, 8);
+        assert_eq!(
+            stats.per_month,
+            vec![
+                MonthCount {
+                    month: "2025-11".to_string(),
+                    entries: 3
+                },
+                MonthCount {
+                    month: "2025-12".to_string(),
+                    entries: 8
+                },
+            ]
+        );
+        assert_eq!((stats.tasks_done, stats.tasks_open), (9, 2));
+        assert_eq!(stats.completion_rate(), Some(9.0 / 11.0));
+
+        let mood = stats.mood.unwrap();
+        assert_eq!(mood.entries, 10);
+        assert_eq!(mood.recent, 8.0);
+        assert_eq!(mood.previous, Some(14.0 / 3.0));
+        assert_eq!(stats.energy, None);
+
+        // A missed day ends the streak
+        assert_eq!(compute(&entries, date(12, 12)).current_streak, 0);
+        assert_eq!(compute(&[], date(12, 12)).average_words, 0.0);
+    }
+}
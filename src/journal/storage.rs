@@ -0,0 +1,385 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use std::fs::{self, File};
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use tracing::{debug, instrument, warn};
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::{self, Layout};
+use crate::journal::s3::S3Storage;
+use crate::journal::webdav::WebDavStorage;
+
+/// File access relative to the journal root ("2025/12/29.md", "SUMMARY.md")
+pub trait Storage: Send + Sync {
+    /// Read a file, `None` when it doesn't exist
+    fn read(&self, path: &str) -> impl Future<Output = Result<Option<Vec<u8>>>> + Send;
+
+    fn write(&self, path: &str, content: &[u8]) -> impl Future<Output = Result<()>> + Send;
+
+    /// Delete a file; deleting a missing file is not an error
+    fn delete(&self, path: &str) -> impl Future<Output = Result<()>> + Send;
+
+    /// Last modification time, `None` when the file doesn't exist
+    fn modified(&self, path: &str) -> impl Future<Output = Result<Option<DateTime<Utc>>>> + Send;
+}
+
+/// The journal directory on disk (also the cache for remote backends)
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalStorage { root: root.into() }
+    }
+
+    fn path(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+
+    fn set_modified(&self, path: &str, time: DateTime<Utc>) -> Result<()> {
+        let file = File::options().write(true).open(self.path(path))?;
+        file.set_modified(SystemTime::from(time))?;
+        Ok(())
+    }
+}
+
+impl Storage for LocalStorage {
+    async fn read(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.path(path);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(fs::read(path)?))
+    }
+
+    async fn write(&self, path: &str, content: &[u8]) -> Result<()> {
+        let path = self.path(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        filesystem::write_atomic(&path, content)
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let path = self.path(path);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    async fn modified(&self, path: &str) -> Result<Option<DateTime<Utc>>> {
+        let path = self.path(path);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(fs::metadata(path)?.modified()?.into()))
+    }
+}
+
+/// A configured remote backend
+pub enum Remote {
+    WebDav(WebDavStorage),
+    S3(S3Storage),
+}
+
+impl Storage for Remote {
+    async fn read(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        match self {
+            Remote::WebDav(s) => s.read(path).await,
+            Remote::S3(s) => s.read(path).await,
+        }
+    }
+
+    async fn write(&self, path: &str, content: &[u8]) -> Result<()> {
+        match self {
+            Remote::WebDav(s) => s.write(path, content).await,
+            Remote::S3(s) => s.write(path, content).await,
+        }
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        match self {
+            Remote::WebDav(s) => s.delete(path).await,
+            Remote::S3(s) => s.delete(path).await,
+        }
+    }
+
+    async fn modified(&self, path: &str) -> Result<Option<DateTime<Utc>>> {
+        match self {
+            Remote::WebDav(s) => s.modified(path).await,
+            Remote::S3(s) => s.modified(path).await,
+        }
+    }
+}
+
+/// Build the remote backend from the config; `None` for plain local storage
+pub fn remote(config: &Config) -> Result<Option<Remote>> {
+    match config.storage.backend.to_lowercase().as_str() {
+        "" | "local" => Ok(None),
+        "webdav" => Ok(Some(Remote::WebDav(WebDavStorage::new(
+            &config.storage.webdav,
+        )?))),
+        "s3" => Ok(Some(Remote::S3(S3Storage::new(&config.storage.s3)?))),
+        other => Err(JournalError::InvalidConfig(format!(
+            "Unknown STORAGE_BACKEND '{}' (use local, webdav or s3)",
+            other
+        ))),
+    }
+}
+
+/// Percent-encode a relative path for use in a URL, keeping the `/` separators
+pub fn encode_path(path: &str) -> String {
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Parse the `Last-Modified` header; a missing header counts as "just now"
+pub fn last_modified(response: &reqwest::Response) -> DateTime<Utc> {
+    response
+        .headers()
+        .get(reqwest::header::LAST_MODIFIED)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .map(|date| date.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now)
+}
+
+/// Storage path of an entry in `layout`, e.g. "2025/12/29.md"
+pub fn entry_key(date: NaiveDate, layout: Layout) -> String {
+    layout.entry(date)
+}
+
+/// Storage path of the weekly file for the week containing `date`, e.g. "weeks/2025-W52.md"
+pub fn week_key(date: NaiveDate) -> String {
+    filesystem::week_page(date)
+}
+
+/// Copy a file from the remote into the cache when the remote copy is newer
+pub async fn pull(remote: &impl Storage, cache: &LocalStorage, path: &str) -> Result<()> {
+    let Some(remote_modified) = remote.modified(path).await? else {
+        return Ok(());
+    };
+    if cache
+        .modified(path)
+        .await?
+        .is_some_and(|local| local >= remote_modified)
+    {
+        return Ok(());
+    }
+
+    if let Some(content) = remote.read(path).await? {
+        if cache.read(path).await?.as_deref() != Some(content.as_slice()) {
+            debug!(path, bytes = content.len(), "downloading newer remote copy");
+            cache.write(path, &content).await?;
+        }
+        // Match the remote timestamp so the next pull/push can skip it
+        cache.set_modified(path, remote_modified)?;
+    }
+    Ok(())
+}
+
+/// Upload a cached file to the remote, or delete it remotely if it is gone locally
+pub async fn push(remote: &impl Storage, cache: &LocalStorage, path: &str) -> Result<()> {
+    let Some(local_modified) = cache.modified(path).await? else {
+        debug!(path, "deleting remote copy");
+        return remote.delete(path).await;
+    };
+    if remote
+        .modified(path)
+        .await?
+        .is_some_and(|remote| remote >= local_modified)
+    {
+        return Ok(());
+    }
+
+    if let Some(content) = cache.read(path).await? {
+        debug!(path, bytes = content.len(), "uploading");
+        remote.write(path, &content).await?;
+    }
+    Ok(())
+}
+
+fn remote_or_warn(config: &Config) -> Option<Remote> {
+    match remote(config) {
+        Ok(remote) => remote,
+        Err(e) => {
+            warn!("{}", e);
+            None
+        }
+    }
+}
+
+/// Refresh the cached files needed to open or create the entry for `date`:
+/// the entry itself, SUMMARY.md and the most recent previous entry
+#[instrument(skip(config))]
+pub async fn pull_for_date(config: &Config, date: NaiveDate) {
+    let Some(remote) = remote_or_warn(config) else {
+        return;
+    };
+    let cache = LocalStorage::new(&config.journal_dir);
+
+    for path in [entry_key(date, config.layout), "SUMMARY.md".to_string()] {
+        if let Err(e) = pull(&remote, &cache, &path).await {
+            warn!("Could not fetch {} from remote storage: {}", path, e);
+        }
+    }
+
+    // Same 30 day look-back as JournalEntry::find_previous_entry
+    for days_back in 1..=30 {
+        let Some(previous) = date.checked_sub_signed(chrono::Duration::days(days_back)) else {
+            break;
+        };
+        let path = entry_key(previous, config.layout);
+        if let Err(e) = pull(&remote, &cache, &path).await {
+            warn!("Could not fetch {} from remote storage: {}", path, e);
+            break;
+        }
+        if cache.path(&path).exists() {
+            break;
+        }
+    }
+}
+
+/// Refresh the cached entries of the ISO week containing `date`, its weekly
+/// file and SUMMARY.md
+#[instrument(skip(config))]
+pub async fn pull_week(config: &Config, date: NaiveDate) {
+    let Some(remote) = remote_or_warn(config) else {
+        return;
+    };
+    let cache = LocalStorage::new(&config.journal_dir);
+
+    let monday = date.week(chrono::Weekday::Mon).first_day();
+    let paths = (0..7)
+        .map(|day| entry_key(monday + chrono::Days::new(day), config.layout))
+        .chain([week_key(date), "SUMMARY.md".to_string()]);
+    for path in paths {
+        if let Err(e) = pull(&remote, &cache, &path).await {
+            warn!("Could not fetch {} from remote storage: {}", path, e);
+        }
+    }
+}
+
+/// Upload the weekly file for the week containing `date` and SUMMARY.md,
+/// which lists weekly reviews
+#[instrument(skip(config))]
+pub async fn push_week(config: &Config, date: NaiveDate) {
+    let Some(remote) = remote_or_warn(config) else {
+        return;
+    };
+    let cache = LocalStorage::new(&config.journal_dir);
+
+    for path in [week_key(date), "SUMMARY.md".to_string()] {
+        if let Err(e) = push(&remote, &cache, &path).await {
+            warn!("Could not upload {} to remote storage: {}", path, e);
+        }
+    }
+}
+
+/// Upload (or delete) the entry for `date` along with SUMMARY.md and the
+/// year/month READMEs
+#[instrument(skip(config))]
+pub async fn push_for_date(config: &Config, date: NaiveDate) {
+    let Some(remote) = remote_or_warn(config) else {
+        return;
+    };
+    let cache = LocalStorage::new(&config.journal_dir);
+
+    let paths = [
+        entry_key(date, config.layout),
+        "SUMMARY.md".to_string(),
+        config.layout.year_page(date.year() as u32),
+        config.layout.month_page(date.year() as u32, date.month()),
+    ];
+    for (i, path) in paths.iter().enumerate() {
+        // READMEs are only ever created, never removed
+        if i >= 2 && !cache.path(path).exists() {
+            continue;
+        }
+        if let Err(e) = push(&remote, &cache, path).await {
+            warn!("Could not upload {} to remote storage: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "easy_journal_storage_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_encode_path() {
+        assert_eq!(encode_path("2025/12/29.md"), "2025/12/29.md");
+        assert_eq!(
+            encode_path("my journal/SUMMARY.md"),
+            "my%20journal/SUMMARY.md"
+        );
+    }
+
+    #[test]
+    fn test_entry_key() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 5).unwrap();
+        assert_eq!(entry_key(date, Layout::Nested), "2025/01/05.md");
+        assert_eq!(entry_key(date, Layout::Flat), "2025-01-05.md");
+        assert_eq!(week_key(date), "weeks/2025-W01.md");
+    }
+
+    #[tokio::test]
+    async fn test_pull_and_push_between_stores() {
+        let remote_dir = temp_dir("remote");
+        let cache_dir = temp_dir("cache");
+        let remote = LocalStorage::new(&remote_dir);
+        let cache = LocalStorage::new(&cache_dir);
+
+        remote.write("2025/12/29.md", b"from remote").await.unwrap();
+        pull(&remote, &cache, "2025/12/29.md").await.unwrap();
+        assert_eq!(
+            cache.read("2025/12/29.md").await.unwrap().unwrap(),
+            b"from remote"
+        );
+
+        // A missing remote file leaves the cache alone
+        pull(&remote, &cache, "2025/12/30.md").await.unwrap();
+        assert!(cache.read("2025/12/30.md").await.unwrap().is_none());
+
+        // Local edits are uploaded once they are newer than the remote copy
+        cache.write("2025/12/29.md", b"edited").await.unwrap();
+        cache
+            .set_modified("2025/12/29.md", Utc::now() + chrono::Duration::seconds(5))
+            .unwrap();
+        push(&remote, &cache, "2025/12/29.md").await.unwrap();
+        assert_eq!(
+            remote.read("2025/12/29.md").await.unwrap().unwrap(),
+            b"edited"
+        );
+
+        // Local deletions are propagated
+        cache.delete("2025/12/29.md").await.unwrap();
+        push(&remote, &cache, "2025/12/29.md").await.unwrap();
+        assert!(remote.read("2025/12/29.md").await.unwrap().is_none());
+
+        fs::remove_dir_all(&remote_dir).unwrap();
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+}
@@ -0,0 +1,145 @@
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
+use serde::Deserialize;
+
+use crate::config::StravaConfig;
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::oauth;
+use crate::journal::parser::format_duration;
+
+#[derive(Deserialize, Debug)]
+struct Activity {
+    id: u64,
+    name: String,
+    /// `Run`, `Ride`, `WeightTraining`, ...; older activities only have `type`
+    #[serde(alias = "type")]
+    sport_type: String,
+    /// Meters
+    distance: f64,
+    /// Seconds
+    moving_time: u32,
+    /// Local wall-clock time, with a misleading `Z` suffix
+    start_date_local: String,
+}
+
+/// `WeightTraining` → `Weight Training`
+fn sport_name(sport_type: &str) -> String {
+    let mut name = String::new();
+    for (i, c) in sport_type.chars().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            name.push(' ');
+        }
+        name.push(c);
+    }
+    name
+}
+
+fn activity_day(activity: &Activity) -> Option<NaiveDate> {
+    let local = activity.start_date_local.trim_end_matches('Z');
+    NaiveDateTime::parse_from_str(local, "%Y-%m-%dT%H:%M:%S")
+        .ok()
+        .map(|at| at.date())
+}
+
+fn format_activity(activity: &Activity, date: NaiveDate) -> String {
+    let mut details = Vec::new();
+    if activity.distance > 0.0 {
+        details.push(format!("{:.1} km", activity.distance / 1000.0));
+    }
+    details.push(format_duration(activity.moving_time / 60));
+
+    let mut line = format!(
+        "- 🏃 {}: [{}](https://www.strava.com/activities/{}) — {}",
+        sport_name(&activity.sport_type),
+        activity.name,
+        activity.id,
+        details.join(", ")
+    );
+    if activity_day(activity).is_some_and(|day| day < date) {
+        line.push_str(" (yesterday)");
+    }
+    line
+}
+
+/// Activities started on the day before `date` or on `date` itself
+pub async fn fetch_activities(config: &StravaConfig, date: NaiveDate) -> Result<Option<String>> {
+    let Some(token) = oauth::strava_access_token(config).await? else {
+        if config.client_id.is_some() {
+            return Err(JournalError::StravaFailed(
+                "Not signed in. Run `easy_journal auth strava`.".to_string(),
+            ));
+        }
+        tracing::debug!("Strava not configured; run `easy_journal auth strava`");
+        return Ok(None);
+    };
+
+    let timestamp = |day: NaiveDate| {
+        Local
+            .from_local_datetime(&day.and_hms_opt(0, 0, 0).unwrap_or_default())
+            .earliest()
+            .map(|at| at.timestamp().to_string())
+            .unwrap_or_default()
+    };
+    let after = timestamp(date - Duration::days(1));
+    let before = timestamp(date + Duration::days(1));
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::StravaFailed(format!("Failed to build HTTP client: {}", e)))?;
+    let response = client
+        .get("https://www.strava.com/api/v3/athlete/activities")
+        .bearer_auth(token)
+        .query(&[
+            ("after", after.as_str()),
+            ("before", before.as_str()),
+            ("per_page", "50"),
+        ])
+        .send()
+        .await
+        .map_err(|e| JournalError::StravaFailed(format!("Request failed: {}", e)))?;
+    let mut activities: Vec<Activity> = check_response(response, "Strava")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::StravaFailed(format!("Failed to parse response: {}", e)))?;
+
+    if activities.is_empty() {
+        return Ok(None);
+    }
+    activities.sort_by(|a, b| a.start_date_local.cmp(&b.start_date_local));
+    Ok(Some(
+        activities
+            .iter()
+            .map(|activity| format_activity(activity, date))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_activity() {
+        let activities: Vec<Activity> = serde_json::from_str(
+            r#"[
+                {"id": 123, "name": "Evening Run", "sport_type": "Run", "distance": 10234.5,
+                 "moving_time": 3130, "start_date_local": "2025-12-28T18:05:00Z"},
+                {"id": 456, "name": "Gym", "type": "WeightTraining", "distance": 0.0,
+                 "moving_time": 2700, "start_date_local": "2025-12-29T07:30:00Z"}
+            ]"#,
+        )
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+
+        assert_eq!(
+            format_activity(&activities[0], date),
+            "- 🏃 Run: [Evening Run](https://www.strava.com/activities/123) — 10.2 km, 0h 52m (yesterday)"
+        );
+        assert_eq!(
+            format_activity(&activities[1], date),
+            "- 🏃 Weight Training: [Gym](https://www.strava.com/activities/456) — 0h 45m"
+        );
+    }
+}
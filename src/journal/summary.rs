@@ -1,8 +1,10 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
-use crate::error::Result;
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::{self, Layout};
 
 #[derive(Debug, Clone, PartialEq)]
 enum SummaryNode {
@@ -20,16 +22,41 @@ enum SummaryNode {
         day: u32,
         day_of_week: String,
     },
+    /// A weekly review, listed under the month of the week's Sunday
+    WeekEntry {
+        monday: NaiveDate,
+    },
+}
+
+/// A year in the journal part of SUMMARY.md
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummaryYear {
+    pub year: u32,
+    pub months: Vec<SummaryMonth>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummaryMonth {
+    pub month: u32,
+    pub name: String,
+    pub days: Vec<NaiveDate>,
 }
 
 pub struct Summary {
     nodes: Vec<SummaryNode>,
     path: std::path::PathBuf,
+    /// Where the links point; links in any layout are read
+    layout: Layout,
 }
 
 impl Summary {
-    pub fn parse(path: &Path) -> Result<Self> {
-        let content = fs::read_to_string(path)?;
+    pub fn parse(path: &Path, layout: Layout) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(JournalError::at_path(path))?;
+        Ok(Self::from_content(path, &content, layout))
+    }
+
+    /// Parse `content` as the summary to be written to `path`
+    pub fn from_content(path: &Path, content: &str, layout: Layout) -> Self {
         let mut nodes = Vec::new();
         let mut in_user_content = true;
 
@@ -66,17 +93,32 @@ impl Summary {
                 }
             }
 
+            // Parse weekly reviews (e.g., "  - [Week 52 Review](weeks/2025-W52.md)")
+            if line.starts_with("  - [")
+                && let Some((_, path)) = parse_day_entry(trimmed)
+                && let Some(monday) = filesystem::week_of_page(&path)
+            {
+                nodes.push(SummaryNode::WeekEntry { monday });
+                continue;
+            }
+
             // Parse day entries (e.g., "  - [29 - Sunday](2025/12/29.md)")
             // Check original line for indentation, not trimmed
             if line.starts_with("  - [")
                 && let Some((day_label, path)) = parse_day_entry(trimmed)
-                && let Some((year, month, day, day_of_week)) =
-                    extract_day_info_from_path(&path, &day_label)
+                && let Some(date) = Layout::ALL
+                    .iter()
+                    .find_map(|layout| layout.entry_date(&path))
             {
+                let day_of_week = day_label
+                    .split(" - ")
+                    .nth(1)
+                    .unwrap_or("Unknown")
+                    .to_string();
                 nodes.push(SummaryNode::DayEntry {
-                    year,
-                    month,
-                    day,
+                    year: date.year() as u32,
+                    month: date.month(),
+                    day: date.day(),
                     day_of_week,
                 });
                 continue;
@@ -86,7 +128,7 @@ impl Summary {
             // Must come after day entries check
             if trimmed.starts_with("- [")
                 && let Some((month_name, path)) = parse_month_entry(trimmed)
-                && let Some((year, month)) = extract_year_month_from_path(&path)
+                && let Some((year, month)) = Layout::month_of_page(&path)
             {
                 nodes.push(SummaryNode::MonthEntry {
                     year,
@@ -105,10 +147,11 @@ impl Summary {
             nodes.push(SummaryNode::UserContent(line.to_string()));
         }
 
-        Ok(Summary {
+        Summary {
             nodes,
             path: path.to_path_buf(),
-        })
+            layout,
+        }
     }
 
     pub fn add_day_entry(&mut self, date: NaiveDate) {
@@ -153,6 +196,99 @@ impl Summary {
         self.insert_day(year, month, day, day_of_week);
     }
 
+    /// Add a link to the weekly review of the week containing `date`, under the
+    /// month its Sunday falls in
+    pub fn add_week_entry(&mut self, date: NaiveDate) {
+        let week = date.week(Weekday::Mon);
+        let (monday, sunday) = (week.first_day(), week.last_day());
+        if self
+            .nodes
+            .iter()
+            .any(|n| matches!(n, SummaryNode::WeekEntry { monday: m } if *m == monday))
+        {
+            return;
+        }
+        if !self
+            .nodes
+            .iter()
+            .any(|n| matches!(n, SummaryNode::Separator))
+        {
+            self.nodes.push(SummaryNode::Separator);
+        }
+
+        let (year, month) = (sunday.year() as u32, sunday.month());
+        let year_idx = self.find_or_insert_year(year);
+        self.find_or_insert_month(year, month, get_month_name(month), year_idx);
+        let month_idx = self.month_index(year, month);
+
+        // Above the Sunday and the days before it (reverse chronological)
+        let pos = self.nodes[month_idx + 1..]
+            .iter()
+            .position(|node| match node {
+                SummaryNode::MonthEntry { .. } | SummaryNode::YearHeader(_) => true,
+                SummaryNode::DayEntry { day, .. } => *day <= sunday.day(),
+                SummaryNode::WeekEntry { monday: m } => *m < monday,
+                _ => false,
+            })
+            .map_or(self.nodes.len(), |i| month_idx + 1 + i);
+        self.nodes.insert(pos, SummaryNode::WeekEntry { monday });
+    }
+
+    fn month_index(&self, year: u32, month: u32) -> usize {
+        self.nodes
+            .iter()
+            .position(|n| {
+                matches!(
+                    n,
+                    SummaryNode::MonthEntry {
+                        year: y,
+                        month: m,
+                        ..
+                    } if *y == year && *m == month
+                )
+            })
+            .unwrap()
+    }
+
+    /// Remove the entry's link, and its month and year when nothing else is
+    /// listed under them. Returns whether the entry was listed.
+    pub fn remove_day_entry(&mut self, date: NaiveDate) -> bool {
+        let (year, month, day) = (date.year() as u32, date.month(), date.day());
+        let before = self.nodes.len();
+        self.nodes.retain(|n| {
+            !matches!(n, SummaryNode::DayEntry { year: y, month: m, day: d, .. }
+                if *y == year && *m == month && *d == day)
+        });
+        if self.nodes.len() == before {
+            return false;
+        }
+
+        if !self.nodes.iter().any(|n| match n {
+            SummaryNode::DayEntry {
+                year: y, month: m, ..
+            } => *y == year && *m == month,
+            SummaryNode::WeekEntry { monday } => {
+                let sunday = monday.week(Weekday::Mon).last_day();
+                sunday.year() as u32 == year && sunday.month() == month
+            }
+            _ => false,
+        }) {
+            self.nodes.retain(|n| {
+                !matches!(n, SummaryNode::MonthEntry { year: y, month: m, .. }
+                    if *y == year && *m == month)
+            });
+        }
+        if !self
+            .nodes
+            .iter()
+            .any(|n| matches!(n, SummaryNode::MonthEntry { year: y, .. } if *y == year))
+        {
+            self.nodes
+                .retain(|n| !matches!(n, SummaryNode::YearHeader(y) if *y == year));
+        }
+        true
+    }
+
     fn find_or_insert_year(&mut self, year: u32) -> usize {
         // Find the separator first
         let sep_idx = self
@@ -228,21 +364,7 @@ impl Summary {
     }
 
     fn insert_day(&mut self, year: u32, month: u32, day: u32, day_of_week: String) {
-        // Find the month entry
-        let month_idx = self
-            .nodes
-            .iter()
-            .position(|n| {
-                matches!(
-                    n,
-                    SummaryNode::MonthEntry {
-                        year: y,
-                        month: m,
-                        ..
-                    } if *y == year && *m == month
-                )
-            })
-            .unwrap();
+        let month_idx = self.month_index(year, month);
 
         // Find where to insert the day (reverse chronological)
         let mut insert_pos = None;
@@ -259,12 +381,17 @@ impl Summary {
                     month: m,
                     day: d,
                     ..
-                } if *y == year && *m == month => {
-                    if *d < day {
-                        // Insert before this day (reverse chronological)
-                        insert_pos = Some(i);
-                        break;
-                    }
+                } if *y == year && *m == month && *d < day => {
+                    // Insert before this day (reverse chronological)
+                    insert_pos = Some(i);
+                    break;
+                }
+                SummaryNode::WeekEntry { monday }
+                    if monday.week(Weekday::Mon).last_day().day() < day =>
+                {
+                    // Insert before the review of an earlier week
+                    insert_pos = Some(i);
+                    break;
                 }
                 _ => {}
             }
@@ -282,7 +409,59 @@ impl Summary {
         );
     }
 
+    /// Year → month → day structure of the listed entries
+    pub fn tree(&self) -> Vec<SummaryYear> {
+        let mut years: Vec<SummaryYear> = Vec::new();
+
+        for node in &self.nodes {
+            match node {
+                SummaryNode::YearHeader(year) => years.push(SummaryYear {
+                    year: *year,
+                    months: Vec::new(),
+                }),
+                SummaryNode::MonthEntry {
+                    year,
+                    month,
+                    month_name,
+                } => {
+                    if let Some(y) = years.iter_mut().find(|y| y.year == *year) {
+                        y.months.push(SummaryMonth {
+                            month: *month,
+                            name: month_name.clone(),
+                            days: Vec::new(),
+                        });
+                    }
+                }
+                SummaryNode::DayEntry {
+                    year, month, day, ..
+                } => {
+                    if let Some(m) = years
+                        .iter_mut()
+                        .find(|y| y.year == *year)
+                        .and_then(|y| y.months.iter_mut().find(|m| m.month == *month))
+                        && let Some(date) = NaiveDate::from_ymd_opt(*year as i32, *month, *day)
+                    {
+                        m.days.push(date);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        years
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     pub fn write(&self) -> Result<()> {
+        filesystem::write_atomic(&self.path, self.render())?;
+        Ok(())
+    }
+
+    /// Render the summary back to markdown
+    pub fn render(&self) -> String {
         let mut content = String::new();
         let mut in_user_content = true;
 
@@ -301,7 +480,7 @@ impl Summary {
                         content.push('\n');
                     }
                     // Render as clickable link to year README
-                    content.push_str(&format!("# [{}]({}/README.md)\n", year, year));
+                    content.push_str(&format!("# [{}]({})\n", year, self.layout.year_page(*year)));
                 }
                 SummaryNode::MonthEntry {
                     year,
@@ -309,8 +488,9 @@ impl Summary {
                     month_name,
                 } => {
                     content.push_str(&format!(
-                        "- [{}]({}/{:02}/README.md)\n",
-                        month_name, year, month
+                        "- [{}]({})\n",
+                        month_name,
+                        self.layout.month_page(*year, *month)
                     ));
                 }
                 SummaryNode::DayEntry {
@@ -319,16 +499,27 @@ impl Summary {
                     day,
                     day_of_week,
                 } => {
+                    let Some(date) = NaiveDate::from_ymd_opt(*year as i32, *month, *day) else {
+                        continue;
+                    };
                     content.push_str(&format!(
-                        "  - [{:02} - {}]({}/{:02}/{:02}.md)\n",
-                        day, day_of_week, year, month, day
+                        "  - [{:02} - {}]({})\n",
+                        day,
+                        day_of_week,
+                        self.layout.entry(date)
+                    ));
+                }
+                SummaryNode::WeekEntry { monday } => {
+                    content.push_str(&format!(
+                        "  - [Week {} Review]({})\n",
+                        monday.format("%V"),
+                        filesystem::week_page(*monday)
                     ));
                 }
             }
         }
 
-        fs::write(&self.path, content)?;
-        Ok(())
+        content
     }
 }
 
@@ -372,36 +563,6 @@ fn parse_year_entry(line: &str) -> Option<(String, String)> {
     None
 }
 
-fn extract_year_month_from_path(path: &str) -> Option<(u32, u32)> {
-    // Parse "2025/12/README.md" -> (2025, 12)
-    let parts: Vec<&str> = path.split('/').collect();
-    if parts.len() >= 2 {
-        let year = parts[0].parse::<u32>().ok()?;
-        let month = parts[1].parse::<u32>().ok()?;
-        Some((year, month))
-    } else {
-        None
-    }
-}
-
-fn extract_day_info_from_path(path: &str, label: &str) -> Option<(u32, u32, u32, String)> {
-    // Parse path "2025/12/29.md" -> (2025, 12, 29)
-    // Parse label "29 - Sunday" -> day_of_week
-    let parts: Vec<&str> = path.split('/').collect();
-    if parts.len() >= 3 {
-        let year = parts[0].parse::<u32>().ok()?;
-        let month = parts[1].parse::<u32>().ok()?;
-        let day_str = parts[2].trim_end_matches(".md");
-        let day = day_str.parse::<u32>().ok()?;
-
-        let day_of_week = label.split(" - ").nth(1).unwrap_or("Unknown").to_string();
-
-        Some((year, month, day, day_of_week))
-    } else {
-        None
-    }
-}
-
 fn get_month_name(month: u32) -> String {
     match month {
         1 => "January",
@@ -420,3 +581,58 @@ fn get_month_name(month: u32) -> String {
     }
     .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_day_entry() {
+        let content = "# Summary\n\n---\n\n# [2026](2026/README.md)\n- [January](2026/01/README.md)\n  - [02 - Friday](2026/01/02.md)\n\n# [2025](2025/README.md)\n- [December](2025/12/README.md)\n  - [30 - Tuesday](2025/12/30.md)\n  - [29 - Monday](2025/12/29.md)\n";
+        let mut summary = Summary::from_content(Path::new("SUMMARY.md"), content, Layout::Nested);
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert!(summary.remove_day_entry(date(2025, 12, 29)));
+        assert!(!summary.remove_day_entry(date(2025, 12, 29)));
+        assert_eq!(
+            summary.render(),
+            "# Summary\n\n---\n\n# [2026](2026/README.md)\n- [January](2026/01/README.md)\n  - [02 - Friday](2026/01/02.md)\n\n# [2025](2025/README.md)\n- [December](2025/12/README.md)\n  - [30 - Tuesday](2025/12/30.md)\n"
+        );
+
+        // The last day of a month drops the month, the last month the year
+        assert!(summary.remove_day_entry(date(2026, 1, 2)));
+        assert_eq!(
+            summary.render(),
+            "# Summary\n\n---\n\n# [2025](2025/README.md)\n- [December](2025/12/README.md)\n  - [30 - Tuesday](2025/12/30.md)\n"
+        );
+    }
+
+    #[test]
+    fn test_add_week_entry() {
+        let content = "# Summary\n\n---\n\n# [2025](2025/README.md)\n- [December](2025/12/README.md)\n  - [30 - Tuesday](2025/12/30.md)\n  - [28 - Sunday](2025/12/28.md)\n  - [22 - Monday](2025/12/22.md)\n";
+        let mut summary = Summary::from_content(Path::new("SUMMARY.md"), content, Layout::Nested);
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        summary.add_week_entry(date(2025, 12, 24));
+        summary.add_week_entry(date(2025, 12, 22));
+        summary.add_day_entry(date(2025, 12, 29));
+        let rendered = summary.render();
+        assert_eq!(
+            rendered,
+            "# Summary\n\n---\n\n# [2025](2025/README.md)\n- [December](2025/12/README.md)\n  - [30 - Tuesday](2025/12/30.md)\n  - [29 - Monday](2025/12/29.md)\n  - [Week 52 Review](weeks/2025-W52.md)\n  - [28 - Sunday](2025/12/28.md)\n  - [22 - Monday](2025/12/22.md)\n"
+        );
+        assert_eq!(
+            Summary::from_content(Path::new("SUMMARY.md"), &rendered, Layout::Nested).render(),
+            rendered
+        );
+
+        // A review keeps its month listed
+        for day in [30, 29, 28, 22] {
+            summary.remove_day_entry(date(2025, 12, day));
+        }
+        assert_eq!(
+            summary.render(),
+            "# Summary\n\n---\n\n# [2025](2025/README.md)\n- [December](2025/12/README.md)\n  - [Week 52 Review](weeks/2025-W52.md)\n"
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::cmp::Reverse;
+
+use crate::journal::parser;
+
+/// An unchecked task, listed once under the last entry that has it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpenTask {
+    pub text: String,
+    pub date: NaiveDate,
+    pub section: String,
+    /// First entry the task appeared in, earlier than `date` when it was carried over
+    pub since: NaiveDate,
+}
+
+/// Tasks still unchecked in the last entry that mentions them, newest entry
+/// first and in the order they appear there. A task repeated across days
+/// (carried over) is listed once; checking it off in a later entry drops it.
+pub fn open_tasks(entries: &[(NaiveDate, String)]) -> Vec<OpenTask> {
+    // Each task with whether it is checked and where it was last seen
+    let mut tasks: Vec<(OpenTask, bool, usize)> = Vec::new();
+    let mut seen = 0;
+    for (date, content) in entries {
+        for (section, text, checked) in parser::section_tasks(content) {
+            seen += 1;
+            match tasks.iter_mut().find(|(task, _, _)| task.text == text) {
+                Some((task, done, order)) => {
+                    task.date = *date;
+                    task.section = section;
+                    *done = checked;
+                    *order = seen;
+                }
+                None => tasks.push((
+                    OpenTask {
+                        text,
+                        date: *date,
+                        section,
+                        since: *date,
+                    },
+                    checked,
+                    seen,
+                )),
+            }
+        }
+    }
+
+    tasks.retain(|(_, done, _)| !done);
+    tasks.sort_by_key(|(task, _, order)| (Reverse(task.date), *order));
+    tasks.into_iter().map(|(task, _, _)| task).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_tasks() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 12, d).unwrap();
+        let entries = vec![
+            (
+                date(22),
+                "# Monday\n\n## Goals for Today\n- [ ] Ship export\n- [ ] Call bank\n- [ ]\n\n## Notes\n- [ ] Renew passport\n".to_string(),
+            ),
+            (
+                date(23),
+                "# Tuesday\n\n## Goals for Today\n- [x] Ship export\n- [ ] Call bank\n\n## Notes\n- [ ] Book flights\n- [x] Water plants\n".to_string(),
+            ),
+        ];
+        let open = open_tasks(&entries);
+        let summary: Vec<(&str, NaiveDate, &str, NaiveDate)> = open
+            .iter()
+            .map(|task| {
+                (
+                    task.text.as_str(),
+                    task.date,
+                    task.section.as_str(),
+                    task.since,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Call bank", date(23), "Goals for Today", date(22)),
+                ("Book flights", date(23), "Notes", date(23)),
+                ("Renew passport", date(22), "Notes", date(22)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::capture;
+
+const API_URL: &str = "https://api.telegram.org";
+
+/// Seconds Telegram holds a `getUpdates` request open when there is nothing new
+const POLL_TIMEOUT: u64 = 50;
+
+/// Wait after a failed poll before trying again
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+
+const HELP: &str = "Send me anything and I'll add it to today's journal entry.\n\n\
+                    /today — show today's goals";
+
+#[derive(Deserialize, Debug)]
+struct ApiResponse<T> {
+    ok: bool,
+    description: Option<String>,
+    result: Option<T>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Update {
+    update_id: i64,
+    message: Option<Message>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Message {
+    chat: Chat,
+    text: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Chat {
+    id: i64,
+}
+
+struct Bot {
+    client: reqwest::Client,
+    token: String,
+}
+
+impl Bot {
+    fn new(token: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent("easy_journal")
+            .timeout(Duration::from_secs(POLL_TIMEOUT + 10))
+            .build()
+            .map_err(|e| {
+                JournalError::TelegramFailed(format!("Failed to build HTTP client: {}", e))
+            })?;
+        Ok(Bot {
+            client,
+            token: token.to_string(),
+        })
+    }
+
+    /// Call a Bot API method. Errors never include the URL, which contains the token.
+    async fn call<T: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T> {
+        let response = self
+            .client
+            .post(format!("{}/bot{}/{}", API_URL, self.token, method))
+            .json(&params)
+            .send()
+            .await
+            .map_err(|e| {
+                JournalError::TelegramFailed(format!("{} failed: {}", method, e.without_url()))
+            })?;
+
+        let status = response.status();
+        let body: ApiResponse<T> = response.json().await.map_err(|e| {
+            JournalError::TelegramFailed(format!(
+                "{} returned HTTP {}: {}",
+                method,
+                status,
+                e.without_url()
+            ))
+        })?;
+
+        match body.result {
+            Some(result) if body.ok => Ok(result),
+            _ => Err(JournalError::TelegramFailed(format!(
+                "{} returned HTTP {}: {}",
+                method,
+                status,
+                body.description.unwrap_or_default()
+            ))),
+        }
+    }
+
+    async fn updates(&self, offset: i64) -> Result<Vec<Update>> {
+        self.call(
+            "getUpdates",
+            serde_json::json!({
+                "offset": offset,
+                "timeout": POLL_TIMEOUT,
+                "allowed_updates": ["message"],
+            }),
+        )
+        .await
+    }
+
+    async fn send(&self, chat_id: i64, text: &str) -> Result<()> {
+        self.call::<serde_json::Value>(
+            "sendMessage",
+            serde_json::json!({ "chat_id": chat_id, "text": text }),
+        )
+        .await
+        .map(|_| ())
+    }
+}
+
+/// Answer a message from the owner: commands or a note to capture
+async fn respond(config: &Config, text: &str) -> String {
+    let command = text.split_whitespace().next().unwrap_or_default();
+    // Commands in groups are sent as `/today@BotName`
+    let command = command.split('@').next().unwrap_or_default();
+
+    match command {
+        "/start" | "/help" => HELP.to_string(),
+        "/today" => match capture::today_goals(config) {
+            Ok(Some(goals)) => format!("Goals for today:\n{}", goals),
+            Ok(None) => "No goals for today yet.".to_string(),
+            Err(e) => format!("✗ {}", e),
+        },
+        _ if command.starts_with('/') => format!("Unknown command {}\n\n{}", command, HELP),
+        _ => match capture::append_to_today(config, text).await {
+            Ok((date, _)) => format!("✓ Added to {}", date.format("%Y-%m-%d")),
+            Err(e) => format!("✗ Could not add it: {}", e),
+        },
+    }
+}
+
+async fn handle(bot: &Bot, config: &Config, message: Message) -> Result<()> {
+    let chat_id = message.chat.id;
+    let reply = match config.telegram.chat_id {
+        Some(owner) if owner == chat_id => match message.text {
+            Some(text) => respond(config, &text).await,
+            None => "Only text messages can be added to the journal.".to_string(),
+        },
+        Some(_) => {
+            debug!(chat_id, "ignoring message from another chat");
+            return Ok(());
+        }
+        None => format!(
+            "Set TELEGRAM_CHAT_ID={} and restart easy_journal to use this chat.",
+            chat_id
+        ),
+    };
+    bot.send(chat_id, &reply).await
+}
+
+/// Long-poll the bot for messages and capture them into today's entry, forever.
+///
+/// Only messages from `TELEGRAM_CHAT_ID` are accepted.
+pub async fn run(config: Config) {
+    let Some(token) = config.telegram.bot_token.clone() else {
+        return;
+    };
+    let bot = match Bot::new(&token) {
+        Ok(bot) => bot,
+        Err(e) => {
+            warn!("{}", e);
+            return;
+        }
+    };
+
+    info!("Telegram bot listening for messages");
+    let mut offset = 0;
+    loop {
+        let updates = match bot.updates(offset).await {
+            Ok(updates) => updates,
+            Err(e) => {
+                warn!("{}", e);
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        };
+
+        for update in updates {
+            offset = offset.max(update.update_id + 1);
+            if let Some(message) = update.message
+                && let Err(e) = handle(&bot, &config, message).await
+            {
+                warn!("{}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_respond_to_commands() {
+        let config = Config::default();
+        assert_eq!(respond(&config, "/help@JournalBot").await, HELP);
+        assert!(
+            respond(&config, "/nope")
+                .await
+                .starts_with("Unknown command /nope")
+        );
+    }
+}
@@ -1,8 +1,9 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Weekday};
 use std::fs;
 use std::path::Path;
 
 use crate::error::{JournalError, Result};
+use crate::journal::parser;
 
 const DEFAULT_TEMPLATE: &str = r#"# {{date}} - {{day_of_week}}
 
@@ -126,9 +127,37 @@ const DEFAULT_YEAR_TEMPLATE: &str = r#"# Year in Review: {{year}}
 **Overall Year Rating (1-10)**:
 "#;
 
+/// Weekly review written by `easy_journal week`; `{{entries}}`, `{{wins}}` and
+/// `{{open_tasks}}` are filled from the week's daily entries
+pub const DEFAULT_WEEK_TEMPLATE: &str = r#"# Week {{week}}, {{year}} · {{start}} – {{end}}
+
+## Entries
+{{entries}}
+
+## Gratitude & Wins
+{{wins}}
+
+## Still Open
+{{open_tasks}}
+
+## What Went Well
+-
+
+## What To Improve
+-
+
+## Focus for Next Week
+- [ ]
+- [ ]
+
+---
+
+**Week Rating (1-10)**:
+"#;
+
 pub fn load_template(template_path: &Path) -> Result<String> {
     if template_path.exists() {
-        fs::read_to_string(template_path).map_err(JournalError::Io)
+        fs::read_to_string(template_path).map_err(JournalError::at_path(template_path))
     } else {
         Ok(DEFAULT_TEMPLATE.to_string())
     }
@@ -136,7 +165,7 @@ pub fn load_template(template_path: &Path) -> Result<String> {
 
 pub fn load_month_template(template_path: &Path) -> Result<String> {
     if template_path.exists() {
-        fs::read_to_string(template_path).map_err(JournalError::Io)
+        fs::read_to_string(template_path).map_err(JournalError::at_path(template_path))
     } else {
         Ok(DEFAULT_MONTH_TEMPLATE.to_string())
     }
@@ -144,12 +173,20 @@ pub fn load_month_template(template_path: &Path) -> Result<String> {
 
 pub fn load_year_template(template_path: &Path) -> Result<String> {
     if template_path.exists() {
-        fs::read_to_string(template_path).map_err(JournalError::Io)
+        fs::read_to_string(template_path).map_err(JournalError::at_path(template_path))
     } else {
         Ok(DEFAULT_YEAR_TEMPLATE.to_string())
     }
 }
 
+pub fn load_week_template(template_path: &Path) -> Result<String> {
+    if template_path.exists() {
+        fs::read_to_string(template_path).map_err(JournalError::at_path(template_path))
+    } else {
+        Ok(DEFAULT_WEEK_TEMPLATE.to_string())
+    }
+}
+
 pub fn apply_variables(
     template: &str,
     date: NaiveDate,
@@ -251,6 +288,66 @@ fn convert_to_checkboxes(content: &str) -> String {
         .join("\n")
 }
 
+/// Check `value` against the range a template footer field gives for `key`,
+/// e.g. `**Mood(1-10)**:`. Fields without a range accept anything.
+pub fn check_metric(template: &str, key: &str, value: &str) -> Result<()> {
+    let name = parser::metric_name(key);
+    let Some(field) = template
+        .lines()
+        .filter_map(parser::parse_metric)
+        .map(|(field, _)| field)
+        .find(|field| parser::metric_name(field) == name)
+    else {
+        return Ok(());
+    };
+    let Some((low, high)) = field
+        .split_once('(')
+        .and_then(|(_, range)| range.strip_suffix(')')?.split_once('-'))
+        .and_then(|(low, high)| {
+            Some((
+                low.trim().parse::<f64>().ok()?,
+                high.trim().parse::<f64>().ok()?,
+            ))
+        })
+    else {
+        return Ok(());
+    };
+
+    let number = value
+        .split('/')
+        .next()
+        .and_then(|number| number.trim().parse::<f64>().ok());
+    match number {
+        Some(number) if (low..=high).contains(&number) => Ok(()),
+        _ => Err(JournalError::InvalidArgument(format!(
+            "{} must be a number from {} to {} (**{}** in the template), got \"{}\"",
+            key, low, high, field, value
+        ))),
+    }
+}
+
+/// The "Goals for this month" list of a month README for `{{month_goals}}`,
+/// `- ✅` for done and `- ⬜` for open goals. Not checkboxes, so the daily entry
+/// doesn't carry or count them as its own tasks. Empty placeholders are skipped.
+pub fn month_goals(month_readme: &str) -> Option<String> {
+    let section = parser::extract_section(month_readme, "Goals for this month")?;
+    let goals: Vec<String> = section
+        .lines()
+        .filter_map(|line| match parser::parse_task(line) {
+            Some((text, _)) if text.is_empty() => None,
+            Some((text, true)) => Some(format!("- ✅ {}", text)),
+            Some((text, false)) => Some(format!("- ⬜ {}", text)),
+            None => line
+                .trim()
+                .strip_prefix("- ")
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .map(|text| format!("- {}", text)),
+        })
+        .collect();
+    (!goals.is_empty()).then(|| goals.join("\n"))
+}
+
 pub fn apply_month_variables(template: &str, year: u32, month: u32) -> String {
     let month_name = get_month_name(month);
     let date_str = format!("{}-{:02}", year, month);
@@ -268,6 +365,17 @@ pub fn apply_year_variables(template: &str, year: u32) -> String {
         .replace("{{date}}", &year.to_string())
 }
 
+/// Fill the date variables of the week template for the ISO week containing `date`
+pub fn apply_week_variables(template: &str, date: NaiveDate) -> String {
+    let week = date.week(Weekday::Mon);
+    template
+        .replace("{{week}}", &date.format("%V").to_string())
+        .replace("{{year}}", &date.format("%G").to_string())
+        .replace("{{start}}", &week.first_day().format("%b %-d").to_string())
+        .replace("{{end}}", &week.last_day().format("%b %-d").to_string())
+        .replace("{{date}}", &date.format("%G-W%V").to_string())
+}
+
 fn get_month_name(month: u32) -> &'static str {
     match month {
         1 => "January",
@@ -291,6 +399,25 @@ mod tests {
     use super::*;
     use chrono::NaiveDate;
 
+    #[test]
+    fn test_check_metric() {
+        let template = "---\n\n**Mood(1-10)**:\n**Sleep Score(1-100)**:\n**Hours Worked**:\n";
+        assert!(check_metric(template, "Mood", "7").is_ok());
+        assert!(check_metric(template, "mood", "7/10").is_ok());
+        assert!(check_metric(template, "Sleep Score", "82").is_ok());
+        assert!(check_metric(template, "Hours Worked", "30").is_ok());
+        assert!(check_metric(template, "Energy Level", "11").is_ok());
+
+        let error = check_metric(template, "Mood", "12")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Mood must be a number from 1 to 10 (**Mood(1-10)** in the template)")
+        );
+        assert!(check_metric(template, "Sleep Score", "0").is_err());
+        assert!(check_metric(template, "Sleep Score", "good").is_err());
+    }
+
     #[test]
     fn test_apply_variables() {
         let template = "# {{date}} - {{day_of_week}}\nYear: {{year}}, Month: {{month}}";
@@ -363,6 +490,13 @@ mod tests {
         assert_eq!(result.lines().count(), 2);
     }
 
+    #[test]
+    fn test_apply_week_variables() {
+        let template = "# Week {{week}}, {{year}} · {{start}} – {{end}}\n{{date}}";
+        let result = apply_week_variables(template, NaiveDate::from_ymd_opt(2025, 12, 31).unwrap());
+        assert_eq!(result, "# Week 01, 2026 · Dec 29 – Jan 4\n2026-W01");
+    }
+
     #[test]
     fn test_get_month_name() {
         assert_eq!(get_month_name(1), "January");
@@ -386,4 +520,14 @@ mod tests {
         assert!(result.contains("Year in Review: {{year}}"));
         assert!(result.contains("Goals for the Year"));
     }
+
+    #[test]
+    fn test_month_goals() {
+        let readme = "# December 2025\n\n## Goals for this month\n- [x] Ship v1\n- [ ] Run 50km\n- Read two books\n- [ ]\n\n## Key Projects & Focus Areas\n- [ ] Not a goal\n";
+        assert_eq!(
+            month_goals(readme).unwrap(),
+            "- ✅ Ship v1\n- ⬜ Run 50km\n- Read two books"
+        );
+        assert_eq!(month_goals(DEFAULT_MONTH_TEMPLATE), None);
+    }
 }
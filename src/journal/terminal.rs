@@ -0,0 +1,180 @@
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const ITALIC: &str = "\x1b[3m";
+const UNDERLINE: &str = "\x1b[4m";
+const STRIKE: &str = "\x1b[9m";
+const CYAN: &str = "\x1b[36m";
+const YELLOW: &str = "\x1b[33m";
+
+/// Markdown rendered for a terminal with ANSI styles: bold headings, bullets
+/// and ☐/☑ checkboxes, styled emphasis and code. HTML (comments, the
+/// navigation marker) is dropped and links show only their text.
+pub fn render(markdown: &str) -> String {
+    let options = Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH;
+    let mut out = String::new();
+    // Numbers of the open ordered lists, `None` for bullet lists
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    // Marker of a list item, written before its first text so a checkbox can replace it
+    let mut marker: Option<String> = None;
+    let mut quote = 0;
+    let mut in_code = false;
+    // Styles to restore after a nested style ends
+    let mut styles: Vec<&str> = Vec::new();
+
+    let start_line = |out: &mut String, quote: usize| {
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        if quote > 0 {
+            out.push_str(&format!("{}{}{}", DIM, "│ ".repeat(quote), RESET));
+        }
+    };
+    let blank_line = |out: &mut String| {
+        if !out.is_empty() && !out.ends_with("\n\n") {
+            out.push_str(if out.ends_with('\n') { "\n" } else { "\n\n" });
+        }
+    };
+
+    for event in Parser::new_ext(markdown, options) {
+        if let Some(pending) = marker.take() {
+            match &event {
+                Event::TaskListMarker(done) => {
+                    let indent = pending.len() - pending.trim_start().len();
+                    let check = if *done { "☑ " } else { "☐ " };
+                    out.push_str(&format!("{}{}", " ".repeat(indent), check));
+                    continue;
+                }
+                // In loose lists the checkbox follows the paragraph start
+                Event::Start(Tag::Paragraph) => {
+                    marker = Some(pending);
+                    continue;
+                }
+                _ => out.push_str(&pending),
+            }
+        }
+
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                blank_line(&mut out);
+                let style = match level {
+                    HeadingLevel::H1 => format!("{}{}", BOLD, UNDERLINE),
+                    HeadingLevel::H2 => format!("{}{}", BOLD, CYAN),
+                    _ => BOLD.to_string(),
+                };
+                out.push_str(&style);
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                out.push_str(RESET);
+                out.push_str("\n\n");
+            }
+            Event::Start(Tag::Paragraph) => {
+                if lists.is_empty() {
+                    blank_line(&mut out);
+                }
+                start_line(&mut out, quote);
+            }
+            Event::End(TagEnd::Paragraph) if lists.is_empty() => out.push_str("\n\n"),
+            Event::Start(Tag::BlockQuote(_)) => quote += 1,
+            Event::End(TagEnd::BlockQuote(_)) => quote -= 1,
+            Event::Start(Tag::List(first)) => {
+                if lists.is_empty() {
+                    blank_line(&mut out);
+                }
+                lists.push(first);
+            }
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+                if lists.is_empty() {
+                    out.push('\n');
+                }
+            }
+            Event::Start(Tag::Item) => {
+                start_line(&mut out, quote);
+                let indent = "  ".repeat(lists.len().saturating_sub(1));
+                let bullet = match lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                marker = Some(format!("{}{}", indent, bullet));
+            }
+            Event::End(TagEnd::Item) if !out.ends_with('\n') => out.push('\n'),
+            Event::Start(Tag::CodeBlock(kind)) => {
+                blank_line(&mut out);
+                in_code = true;
+                if let CodeBlockKind::Fenced(language) = kind
+                    && !language.is_empty()
+                {
+                    out.push_str(&format!("{}{}{}\n", DIM, language, RESET));
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code = false;
+                out.push('\n');
+            }
+            Event::Start(Tag::Emphasis) => {
+                out.push_str(ITALIC);
+                styles.push(ITALIC);
+            }
+            Event::Start(Tag::Strong) => {
+                out.push_str(BOLD);
+                styles.push(BOLD);
+            }
+            Event::Start(Tag::Strikethrough) => {
+                out.push_str(STRIKE);
+                styles.push(STRIKE);
+            }
+            Event::Start(Tag::Link { .. }) => {
+                out.push_str(UNDERLINE);
+                styles.push(UNDERLINE);
+            }
+            Event::End(
+                TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link,
+            ) => {
+                styles.pop();
+                out.push_str(RESET);
+                out.extend(styles.iter().copied());
+            }
+            Event::Text(text) if in_code => {
+                for line in text.lines() {
+                    out.push_str(&format!("    {}{}{}\n", YELLOW, line, RESET));
+                }
+            }
+            Event::Text(text) => out.push_str(&text),
+            Event::Code(code) => {
+                out.push_str(&format!("{}{}{}", YELLOW, code, RESET));
+                out.extend(styles.iter().copied());
+            }
+            Event::SoftBreak | Event::HardBreak => start_line(&mut out, quote),
+            Event::Rule => {
+                blank_line(&mut out);
+                out.push_str(&format!("{}{}{}\n\n", DIM, "─".repeat(40), RESET));
+            }
+            _ => {}
+        }
+    }
+    format!("{}\n", out.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let markdown = "# Monday\n\n## Goals for Today\n- [ ] Write *tests*\n- [x] Ship `show`\n\n1. First\n2. Second\n\n---\n\n**Mood**: 7\n\n[← 2025-12-28](../../2025/12/28.md) <!-- nav -->\n";
+        let rendered = render(markdown);
+        assert_eq!(
+            rendered,
+            format!(
+                "{BOLD}{UNDERLINE}Monday{RESET}\n\n{BOLD}{CYAN}Goals for Today{RESET}\n\n☐ Write {ITALIC}tests{RESET}\n☑ Ship {YELLOW}show{RESET}\n\n1. First\n2. Second\n\n{DIM}{}{RESET}\n\n{BOLD}Mood{RESET}: 7\n\n{UNDERLINE}← 2025-12-28{RESET}\n",
+                "─".repeat(40)
+            )
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::error::{JournalError, Result};
+use crate::journal::capture;
+use crate::journal::filesystem::write_atomic;
+use crate::journal::parser::{self, format_duration};
+
+/// Heading of the section finished sessions are logged under
+pub const TIME_LOG_HEADER: &str = "## Time Log";
+
+/// The running timer, kept in `config.timer_path` between `start` and `stop`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub description: String,
+    pub started: DateTime<Local>,
+}
+
+impl Session {
+    /// Whole minutes from the start to `now`
+    pub fn minutes(&self, now: DateTime<Local>) -> u32 {
+        (now - self.started).num_minutes().max(0) as u32
+    }
+}
+
+/// The running session, if there is one
+pub fn load(path: &Path) -> Option<Session> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+pub fn save(path: &Path, session: &Session) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent).map_err(JournalError::at_path(parent))?;
+    }
+    let content = serde_json::to_string_pretty(session).map_err(|e| JournalError::FileIo {
+        path: path.to_path_buf(),
+        source: e.into(),
+    })?;
+    write_atomic(path, content)
+}
+
+pub fn clear(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(JournalError::at_path(path)(e)),
+        _ => Ok(()),
+    }
+}
+
+/// `- 09:00–10:30 deep work on parser (1h 30m)`
+pub fn log_line(session: &Session, stopped: DateTime<Local>) -> String {
+    format!(
+        "- {}–{} {} ({})",
+        session.started.format("%H:%M"),
+        stopped.format("%H:%M"),
+        session.description,
+        format_duration(session.minutes(stopped))
+    )
+}
+
+/// Minutes logged in the time log section of `content`
+pub fn logged_minutes(content: &str) -> u32 {
+    let Some(log) = parser::extract_section(content, TIME_LOG_HEADER) else {
+        return 0;
+    };
+    log.lines()
+        .filter_map(|line| {
+            let duration = line.trim_end().strip_suffix(')')?.rsplit_once('(')?.1;
+            parser::parse_duration(duration)
+        })
+        .sum()
+}
+
+/// Log a finished session into `content` and set `**Hours Worked**` to the
+/// total of the time log
+pub fn record(content: &str, session: &Session, stopped: DateTime<Local>) -> String {
+    let updated = capture::append_to_section(content, TIME_LOG_HEADER, &log_line(session, stopped));
+    let minutes = logged_minutes(&updated);
+    parser::set_metric(
+        &updated,
+        "Hours Worked",
+        &parser::format_hours(i64::from(minutes) * 60),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_record() {
+        let at = |hour, minute| {
+            Local
+                .with_ymd_and_hms(2025, 12, 29, hour, minute, 0)
+                .unwrap()
+        };
+        let content = "# Monday\n\n## Notes\n- Ok\n\n---\n\n**Mood**: 7\n**Hours Worked**:\n";
+
+        let first = Session {
+            description: "deep work on parser".to_string(),
+            started: at(9, 0),
+        };
+        let updated = record(content, &first, at(10, 30));
+        let second = Session {
+            description: "review".to_string(),
+            started: at(13, 0),
+        };
+        let updated = record(&updated, &second, at(13, 45));
+
+        assert_eq!(
+            updated,
+            "# Monday\n\n## Notes\n- Ok\n\n## Time Log\n- 09:00–10:30 deep work on parser (1h 30m)\n- 13:00–13:45 review (0h 45m)\n\n---\n\n**Mood**: 7\n**Hours Worked**: 2.25\n"
+        );
+        assert_eq!(logged_minutes(&updated), 135);
+    }
+}
@@ -0,0 +1,128 @@
+use chrono::{Duration, Local, NaiveDate, TimeZone};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::config::TogglConfig;
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::parser::{format_duration, format_hours};
+
+#[derive(Deserialize, Debug)]
+struct TimeEntry {
+    description: Option<String>,
+    /// Seconds; negative while the timer is running
+    duration: i64,
+    /// Filled in with `meta=true`
+    project_name: Option<String>,
+    client_name: Option<String>,
+}
+
+/// `**Hours Worked**` for the footer, then one line per project
+fn format_entries(entries: &[TimeEntry]) -> Option<String> {
+    // project → (seconds, descriptions)
+    let mut projects: BTreeMap<String, (i64, Vec<&str>)> = BTreeMap::new();
+    for entry in entries.iter().filter(|e| e.duration > 0) {
+        let project = match (&entry.client_name, &entry.project_name) {
+            (Some(client), Some(project)) => format!("{} / {}", client, project),
+            (None, Some(project)) => project.clone(),
+            _ => "No project".to_string(),
+        };
+        let (seconds, descriptions) = projects.entry(project).or_default();
+        *seconds += entry.duration;
+        if let Some(description) = entry.description.as_deref().filter(|d| !d.is_empty())
+            && !descriptions.contains(&description)
+        {
+            descriptions.push(description);
+        }
+    }
+
+    let total: i64 = projects.values().map(|(seconds, _)| seconds).sum();
+    if total < 60 {
+        return None;
+    }
+
+    let mut projects: Vec<_> = projects.into_iter().collect();
+    projects.sort_by_key(|(_, (seconds, _))| std::cmp::Reverse(*seconds));
+
+    let mut lines = vec![format!("**Hours Worked**: {}", format_hours(total))];
+    for (project, (seconds, descriptions)) in projects {
+        let mut line = format!(
+            "- ⏱ {}: {}",
+            project,
+            format_duration((seconds / 60) as u32)
+        );
+        if !descriptions.is_empty() {
+            line.push_str(&format!(" ({})", descriptions.join(", ")));
+        }
+        lines.push(line);
+    }
+    Some(lines.join("\n"))
+}
+
+/// Time entries tracked on the day before `date`, grouped by project
+pub async fn fetch_time_entries(config: &TogglConfig, date: NaiveDate) -> Result<Option<String>> {
+    let Some(api_token) = &config.api_token else {
+        tracing::debug!("TOGGL_API_TOKEN not set");
+        return Ok(None);
+    };
+
+    let start_of = |day: NaiveDate| {
+        Local
+            .from_local_datetime(&day.and_hms_opt(0, 0, 0).unwrap_or_default())
+            .earliest()
+            .map(|at| at.to_rfc3339())
+            .unwrap_or_default()
+    };
+    let start = start_of(date - Duration::days(1));
+    let end = start_of(date);
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::TogglFailed(format!("Failed to build HTTP client: {}", e)))?;
+    let response = client
+        .get("https://api.track.toggl.com/api/v9/me/time_entries")
+        .basic_auth(api_token, Some("api_token"))
+        .query(&[
+            ("start_date", start.as_str()),
+            ("end_date", end.as_str()),
+            ("meta", "true"),
+        ])
+        .send()
+        .await
+        .map_err(|e| JournalError::TogglFailed(format!("Request failed: {}", e)))?;
+    let entries: Vec<TimeEntry> = check_response(response, "Toggl")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::TogglFailed(format!("Failed to parse response: {}", e)))?;
+
+    Ok(format_entries(&entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_entries() {
+        let entries: Vec<TimeEntry> = serde_json::from_str(
+            r#"[
+                {"description": "Landing page", "duration": 7200, "project_name": "Website", "client_name": "Acme"},
+                {"description": "Bug fixes", "duration": 5400, "project_name": "Website", "client_name": "Acme"},
+                {"description": "Landing page", "duration": 1800, "project_name": "Website", "client_name": "Acme"},
+                {"description": "", "duration": 12600, "project_name": "Internal", "client_name": null},
+                {"description": "Running timer", "duration": -1735460000, "project_name": "Internal"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            format_entries(&entries).unwrap(),
+            "**Hours Worked**: 7.5\n\
+             - ⏱ Acme / Website: 4h 00m (Landing page, Bug fixes)\n\
+             - ⏱ Internal: 3h 30m"
+        );
+        assert_eq!(format_hours(8 * 3600), "8");
+        assert_eq!(format_hours(26100), "7.25");
+    }
+}
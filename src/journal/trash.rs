@@ -0,0 +1,222 @@
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem;
+
+const ID_FORMAT: &str = "%Y%m%d-%H%M%S%.3f";
+const METADATA_FILE: &str = "meta.json";
+const CONTENT_FILE: &str = "content";
+
+/// Maximum number of overwrite/summary records kept (deletions are never pruned)
+const MAX_RECORDS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    Delete,
+    Overwrite,
+    SummaryRewrite,
+}
+
+impl OperationKind {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            OperationKind::Delete => "deletion",
+            OperationKind::Overwrite => "overwrite",
+            OperationKind::SummaryRewrite => "SUMMARY.md rewrite",
+        }
+    }
+}
+
+/// Metadata stored alongside the previous content of a destructive operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashRecord {
+    pub kind: OperationKind,
+    pub original_path: PathBuf,
+    pub date: Option<NaiveDate>,
+    pub recorded_at: String,
+}
+
+fn new_record_dir(trash_dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(trash_dir)?;
+
+    let mut dir = trash_dir.join(Local::now().format(ID_FORMAT).to_string());
+    while dir.exists() {
+        // Two operations within the same millisecond; wait for a fresh id
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        dir = trash_dir.join(Local::now().format(ID_FORMAT).to_string());
+    }
+    fs::create_dir(&dir)?;
+    Ok(dir)
+}
+
+fn write_record(trash_dir: &Path, record: &TrashRecord, content: &[u8]) -> Result<()> {
+    let dir = new_record_dir(trash_dir)?;
+    let metadata = serde_json::to_string_pretty(record)
+        .map_err(|e| JournalError::TrashFailed(format!("Failed to encode metadata: {}", e)))?;
+
+    fs::write(dir.join(CONTENT_FILE), content)?;
+    fs::write(dir.join(METADATA_FILE), metadata)?;
+
+    prune(trash_dir)
+}
+
+/// Move a file into the trash instead of deleting it
+pub fn move_to_trash(trash_dir: &Path, path: &Path, date: Option<NaiveDate>) -> Result<()> {
+    let content = fs::read(path)?;
+    let record = TrashRecord {
+        kind: OperationKind::Delete,
+        original_path: path.to_path_buf(),
+        date,
+        recorded_at: Local::now().to_rfc3339(),
+    };
+
+    write_record(trash_dir, &record, &content)?;
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+/// Keep the current content of a file that is about to be replaced by `new_content`.
+///
+/// Nothing is recorded when the file doesn't exist yet or the content is unchanged.
+pub fn record_overwrite(
+    trash_dir: &Path,
+    kind: OperationKind,
+    path: &Path,
+    date: Option<NaiveDate>,
+    new_content: &str,
+) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let content = fs::read(path)?;
+    if content == new_content.as_bytes() {
+        return Ok(());
+    }
+
+    let record = TrashRecord {
+        kind,
+        original_path: path.to_path_buf(),
+        date,
+        recorded_at: Local::now().to_rfc3339(),
+    };
+    write_record(trash_dir, &record, &content)
+}
+
+fn record_dirs(trash_dir: &Path) -> Result<Vec<PathBuf>> {
+    if !trash_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut dirs: Vec<PathBuf> = fs::read_dir(trash_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.join(METADATA_FILE).exists())
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn read_record(dir: &Path) -> Result<TrashRecord> {
+    let metadata = fs::read_to_string(dir.join(METADATA_FILE))?;
+    serde_json::from_str(&metadata)
+        .map_err(|e| JournalError::TrashFailed(format!("Corrupt trash record: {}", e)))
+}
+
+fn prune(trash_dir: &Path) -> Result<()> {
+    let dirs = record_dirs(trash_dir)?;
+    let mut excess = dirs.len().saturating_sub(MAX_RECORDS);
+
+    for dir in dirs {
+        if excess == 0 {
+            break;
+        }
+        if read_record(&dir).is_ok_and(|r| r.kind != OperationKind::Delete) {
+            fs::remove_dir_all(&dir)?;
+            excess -= 1;
+        }
+    }
+    Ok(())
+}
+
+/// Restore the most recent destructive operation and drop it from the trash
+pub fn undo_last(trash_dir: &Path) -> Result<Option<TrashRecord>> {
+    let Some(dir) = record_dirs(trash_dir)?.pop() else {
+        return Ok(None);
+    };
+
+    let record = read_record(&dir)?;
+    let content = fs::read(dir.join(CONTENT_FILE))?;
+
+    if let Some(parent) = record.original_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    filesystem::write_atomic(&record.original_path, content)?;
+    fs::remove_dir_all(&dir)?;
+
+    Ok(Some(record))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "easy_journal_trash_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_delete_and_undo() {
+        let dir = temp_dir("delete");
+        let trash_dir = dir.join(".trash");
+        let entry = dir.join("29.md");
+        fs::write(&entry, "entry content").unwrap();
+
+        move_to_trash(&trash_dir, &entry, None).unwrap();
+        assert!(!entry.exists());
+
+        let record = undo_last(&trash_dir).unwrap().unwrap();
+        assert_eq!(record.kind, OperationKind::Delete);
+        assert_eq!(fs::read_to_string(&entry).unwrap(), "entry content");
+        assert!(undo_last(&trash_dir).unwrap().is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_overwrite_and_undo_latest_first() {
+        let dir = temp_dir("overwrite");
+        let trash_dir = dir.join(".trash");
+        let entry = dir.join("29.md");
+
+        fs::write(&entry, "v1").unwrap();
+        record_overwrite(&trash_dir, OperationKind::Overwrite, &entry, None, "v2").unwrap();
+        fs::write(&entry, "v2").unwrap();
+
+        // Unchanged content is not recorded
+        record_overwrite(&trash_dir, OperationKind::Overwrite, &entry, None, "v2").unwrap();
+
+        record_overwrite(&trash_dir, OperationKind::Overwrite, &entry, None, "v3").unwrap();
+        fs::write(&entry, "v3").unwrap();
+
+        undo_last(&trash_dir).unwrap();
+        assert_eq!(fs::read_to_string(&entry).unwrap(), "v2");
+        undo_last(&trash_dir).unwrap();
+        assert_eq!(fs::read_to_string(&entry).unwrap(), "v1");
+        assert!(undo_last(&trash_dir).unwrap().is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,139 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{Duration, NaiveDate};
+use serde::Deserialize;
+
+use crate::config::WakaTimeConfig;
+use crate::error::{JournalError, Result, check_response};
+use crate::journal::parser::format_duration;
+
+/// How many projects and languages are listed; the rest are left out
+const MAX_PROJECTS: usize = 5;
+const MAX_LANGUAGES: usize = 3;
+
+#[derive(Deserialize, Debug)]
+struct SummariesResponse {
+    data: Vec<DaySummary>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DaySummary {
+    grand_total: GrandTotal,
+    #[serde(default)]
+    projects: Vec<Item>,
+    #[serde(default)]
+    languages: Vec<Item>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GrandTotal {
+    total_seconds: f64,
+}
+
+#[derive(Deserialize, Debug)]
+struct Item {
+    name: String,
+    total_seconds: f64,
+}
+
+/// `easy_journal 3h 05m, dotfiles 0h 40m`, largest first, skipping anything under a minute
+fn format_items(items: &[Item], max: usize) -> String {
+    let mut items: Vec<&Item> = items.iter().filter(|i| i.total_seconds >= 60.0).collect();
+    items.sort_by(|a, b| b.total_seconds.total_cmp(&a.total_seconds));
+    items
+        .iter()
+        .take(max)
+        .map(|item| {
+            format!(
+                "{} {}",
+                item.name,
+                format_duration((item.total_seconds / 60.0) as u32)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_summary(summary: &DaySummary) -> Option<String> {
+    let minutes = (summary.grand_total.total_seconds / 60.0) as u32;
+    if minutes == 0 {
+        return None;
+    }
+
+    let mut line = format!("- ⌨️ Coded {} yesterday", format_duration(minutes));
+    let projects = format_items(&summary.projects, MAX_PROJECTS);
+    if !projects.is_empty() {
+        line.push_str(&format!(": {}", projects));
+    }
+    let languages = format_items(&summary.languages, MAX_LANGUAGES);
+    if !languages.is_empty() {
+        line.push_str(&format!(" ({})", languages));
+    }
+    Some(line)
+}
+
+/// Coding time of the day before `date`, by project and language
+pub async fn fetch_coding_time(config: &WakaTimeConfig, date: NaiveDate) -> Result<Option<String>> {
+    let Some(api_key) = &config.api_key else {
+        tracing::debug!("WAKATIME_API_KEY not set");
+        return Ok(None);
+    };
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::WakaTimeFailed(format!("Failed to build HTTP client: {}", e)))?;
+
+    let day = (date - Duration::days(1)).format("%Y-%m-%d").to_string();
+    let response = client
+        .get(format!(
+            "{}/users/current/summaries",
+            config.api_url.trim_end_matches('/')
+        ))
+        .header(
+            reqwest::header::AUTHORIZATION,
+            format!("Basic {}", STANDARD.encode(api_key)),
+        )
+        .query(&[("start", day.as_str()), ("end", day.as_str())])
+        .send()
+        .await
+        .map_err(|e| JournalError::WakaTimeFailed(format!("Request failed: {}", e)))?;
+    let summaries: SummariesResponse = check_response(response, "WakaTime")
+        .await?
+        .json()
+        .await
+        .map_err(|e| JournalError::WakaTimeFailed(format!("Failed to parse response: {}", e)))?;
+
+    Ok(summaries.data.first().and_then(format_summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_summary() {
+        let response: SummariesResponse = serde_json::from_str(
+            r#"{"data": [{
+                "grand_total": {"total_seconds": 13500.5, "text": "3 hrs 45 mins"},
+                "projects": [
+                    {"name": "dotfiles", "total_seconds": 2400},
+                    {"name": "easy_journal", "total_seconds": 11070},
+                    {"name": "scratch", "total_seconds": 30}
+                ],
+                "languages": [
+                    {"name": "Markdown", "total_seconds": 1500},
+                    {"name": "Rust", "total_seconds": 10800},
+                    {"name": "Nix", "total_seconds": 1200}
+                ]
+            }]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            format_summary(&response.data[0]).unwrap(),
+            "- ⌨️ Coded 3h 45m yesterday: easy_journal 3h 04m, dotfiles 0h 40m \
+             (Rust 3h 00m, Markdown 0h 25m, Nix 0h 20m)"
+        );
+    }
+}
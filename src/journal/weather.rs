@@ -0,0 +1,131 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::config::WeatherConfig;
+use crate::error::{JournalError, Result};
+
+#[derive(Deserialize, Debug)]
+struct ForecastResponse {
+    daily: DailyForecast,
+}
+
+#[derive(Deserialize, Debug)]
+struct DailyForecast {
+    weather_code: Vec<Option<u32>>,
+    temperature_2m_max: Vec<Option<f64>>,
+    temperature_2m_min: Vec<Option<f64>>,
+    precipitation_sum: Vec<Option<f64>>,
+}
+
+/// Describe a WMO weather interpretation code
+fn describe_code(code: u32) -> &'static str {
+    match code {
+        0 => "☀️ Clear",
+        1 | 2 => "🌤 Partly cloudy",
+        3 => "☁️ Overcast",
+        45 | 48 => "🌫 Fog",
+        51..=57 => "🌦 Drizzle",
+        61..=67 | 80..=82 => "🌧 Rain",
+        71..=77 | 85 | 86 => "🌨 Snow",
+        95..=99 => "⛈ Thunderstorm",
+        _ => "Unknown",
+    }
+}
+
+fn format_forecast(daily: &DailyForecast) -> Option<String> {
+    let code = (*daily.weather_code.first()?)?;
+    let max = (*daily.temperature_2m_max.first()?)?;
+    let min = (*daily.temperature_2m_min.first()?)?;
+
+    let mut line = format!("- {}, {:.0}°C – {:.0}°C", describe_code(code), min, max);
+    if let Some(Some(precipitation)) = daily.precipitation_sum.first()
+        && *precipitation > 0.0
+    {
+        line.push_str(&format!(", {:.1} mm precipitation", precipitation));
+    }
+    Some(line)
+}
+
+/// Fetch the daily forecast for `date` from Open-Meteo (no API key needed)
+pub async fn fetch_weather(config: &WeatherConfig, date: NaiveDate) -> Result<Option<String>> {
+    let (Some(latitude), Some(longitude)) = (config.latitude, config.longitude) else {
+        tracing::debug!("WEATHER_LATITUDE/WEATHER_LONGITUDE not set");
+        return Ok(None);
+    };
+
+    let client = reqwest::Client::builder()
+        .user_agent("easy_journal")
+        .build()
+        .map_err(|e| JournalError::WeatherFailed(format!("Failed to build HTTP client: {}", e)))?;
+
+    let date_str = date.format("%Y-%m-%d").to_string();
+    let latitude = latitude.to_string();
+    let longitude = longitude.to_string();
+    let response = client
+        .get("https://api.open-meteo.com/v1/forecast")
+        .query(&[
+            ("latitude", latitude.as_str()),
+            ("longitude", longitude.as_str()),
+            (
+                "daily",
+                "weather_code,temperature_2m_max,temperature_2m_min,precipitation_sum",
+            ),
+            ("timezone", "auto"),
+            ("start_date", date_str.as_str()),
+            ("end_date", date_str.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(|e| JournalError::WeatherFailed(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(JournalError::WeatherFailed(format!(
+            "Open-Meteo returned {}",
+            response.status()
+        )));
+    }
+
+    let forecast: ForecastResponse = response
+        .json()
+        .await
+        .map_err(|e| JournalError::WeatherFailed(format!("Failed to parse response: {}", e)))?;
+
+    Ok(format_forecast(&forecast.daily))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_forecast() {
+        let daily = DailyForecast {
+            weather_code: vec![Some(61)],
+            temperature_2m_max: vec![Some(9.4)],
+            temperature_2m_min: vec![Some(2.6)],
+            precipitation_sum: vec![Some(1.3)],
+        };
+        assert_eq!(
+            format_forecast(&daily).unwrap(),
+            "- 🌧 Rain, 3°C – 9°C, 1.3 mm precipitation"
+        );
+
+        let missing = DailyForecast {
+            weather_code: vec![None],
+            temperature_2m_max: vec![],
+            temperature_2m_min: vec![],
+            precipitation_sum: vec![],
+        };
+        assert!(format_forecast(&missing).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_weather_is_skipped() {
+        let config = WeatherConfig {
+            latitude: None,
+            longitude: None,
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+        assert_eq!(fetch_weather(&config, date).await.unwrap(), None);
+    }
+}
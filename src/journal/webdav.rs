@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+
+use crate::config::WebDavConfig;
+use crate::error::{JournalError, Result};
+use crate::journal::storage::{self, Storage};
+
+/// Journal stored in a WebDAV collection (Nextcloud, ownCloud, Apache mod_dav, ...)
+pub struct WebDavStorage {
+    client: reqwest::Client,
+    base_url: String,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl WebDavStorage {
+    pub fn new(config: &WebDavConfig) -> Result<Self> {
+        let base_url = config.url.clone().ok_or_else(|| {
+            JournalError::InvalidConfig("WEBDAV_URL must be set for the webdav backend".to_string())
+        })?;
+
+        let client = reqwest::Client::builder()
+            .user_agent("easy_journal")
+            .build()
+            .map_err(|e| {
+                JournalError::StorageFailed(format!("Failed to build HTTP client: {}", e))
+            })?;
+
+        Ok(WebDavStorage {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            username: config.username.clone(),
+            password: config.password.clone(),
+        })
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let url = format!("{}/{}", self.base_url, storage::encode_path(path));
+        let request = self.client.request(method, url);
+        match &self.username {
+            Some(username) => request.basic_auth(username, self.password.as_ref()),
+            None => request,
+        }
+    }
+
+    async fn send(&self, request: RequestBuilder, path: &str) -> Result<Response> {
+        request.send().await.map_err(|e| {
+            JournalError::StorageFailed(format!("WebDAV request for {} failed: {}", path, e))
+        })
+    }
+
+    /// Create the parent collections of `path` ("2025", "2025/12")
+    async fn ensure_collections(&self, path: &str) -> Result<()> {
+        let mkcol = Method::from_bytes(b"MKCOL").expect("MKCOL is a valid method");
+        let segments: Vec<&str> = path.split('/').collect();
+
+        for depth in 1..segments.len() {
+            let collection = format!("{}/", segments[..depth].join("/"));
+            let response = self
+                .send(self.request(mkcol.clone(), &collection), &collection)
+                .await?;
+
+            // 405 Method Not Allowed means the collection already exists
+            let status = response.status();
+            if !status.is_success() && status != StatusCode::METHOD_NOT_ALLOWED {
+                return Err(JournalError::HttpStatus {
+                    provider: "WebDAV".to_string(),
+                    status: status.as_u16(),
+                    message: format!("MKCOL {}", collection),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_status(response: &Response, method: &str, path: &str) -> Result<()> {
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(JournalError::HttpStatus {
+            provider: "WebDAV".to_string(),
+            status: response.status().as_u16(),
+            message: format!("{} {}", method, path),
+        })
+    }
+}
+
+impl Storage for WebDavStorage {
+    async fn read(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let response = self.send(self.request(Method::GET, path), path).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        check_status(&response, "GET", path)?;
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| JournalError::StorageFailed(format!("Failed to read {}: {}", path, e)))?;
+        Ok(Some(bytes.to_vec()))
+    }
+
+    async fn write(&self, path: &str, content: &[u8]) -> Result<()> {
+        self.ensure_collections(path).await?;
+        let response = self
+            .send(self.request(Method::PUT, path).body(content.to_vec()), path)
+            .await?;
+        check_status(&response, "PUT", path)
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let response = self.send(self.request(Method::DELETE, path), path).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        check_status(&response, "DELETE", path)
+    }
+
+    async fn modified(&self, path: &str) -> Result<Option<DateTime<Utc>>> {
+        let response = self.send(self.request(Method::HEAD, path), path).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        check_status(&response, "HEAD", path)?;
+        Ok(Some(storage::last_modified(&response)))
+    }
+}
@@ -0,0 +1,188 @@
+use chrono::NaiveDate;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::filesystem::{self, Layout};
+use crate::journal::lock::WriteLock;
+use crate::journal::summary::Summary;
+use crate::journal::trash::{self, OperationKind};
+use crate::journal::weekly_summary::{self, week_bounds};
+use crate::journal::{autocommit, digest, parser, tasks, template};
+
+/// Daily section whose items are collected into the review
+pub const WINS_SECTION: &str = "Gratitude & Wins";
+
+/// Text of a `-`, `*` or `1.` list item, or of a plain line
+fn item_text(line: &str) -> &str {
+    let line = line.trim();
+    if let Some(rest) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return rest.trim();
+    }
+    match line.split_once(". ") {
+        Some((number, rest)) if number.chars().all(|c| c.is_ascii_digit()) => rest.trim(),
+        _ => line,
+    }
+}
+
+/// Items written under `header`, leaving out template placeholders and comments
+pub(crate) fn section_items(content: &str, header: &str) -> Vec<String> {
+    let Some(section) = parser::extract_section(content, header) else {
+        return Vec::new();
+    };
+    section
+        .lines()
+        .filter(|line| !digest::is_placeholder(line))
+        .map(item_text)
+        .filter(|text| !text.is_empty() && !text.starts_with("<!--"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The week's wins, each followed by its weekday
+fn wins(entries: &[(NaiveDate, String)]) -> Vec<String> {
+    entries
+        .iter()
+        .flat_map(|(date, content)| {
+            section_items(content, WINS_SECTION)
+                .into_iter()
+                .map(move |text| format!("- {} _({})_", text, date.format("%a")))
+        })
+        .collect()
+}
+
+fn list_or_dash(lines: Vec<String>) -> String {
+    if lines.is_empty() {
+        "-".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// The week template filled for the week containing `date`: links to the
+/// daily `entries`, their wins and the tasks left open
+pub fn content(
+    template: &str,
+    date: NaiveDate,
+    entries: &[(NaiveDate, String)],
+    layout: Layout,
+) -> String {
+    let links = entries
+        .iter()
+        .map(|(date, _)| {
+            format!(
+                "- [{}](../{})",
+                date.format("%A, %b %-d"),
+                layout.entry(*date)
+            )
+        })
+        .collect();
+    let open_tasks = tasks::open_tasks(entries)
+        .into_iter()
+        .map(|task| format!("- [ ] {}", task.text))
+        .collect();
+    template::apply_week_variables(template, date)
+        .replace("{{entries}}", &list_or_dash(links))
+        .replace("{{wins}}", &list_or_dash(wins(entries)))
+        .replace("{{open_tasks}}", &list_or_dash(open_tasks))
+}
+
+/// Whether a weekly file has more than its title and an AI summary
+fn has_review(content: &str) -> bool {
+    parser::split_sections(content).iter().any(|section| {
+        let header = section.header.trim();
+        header.starts_with("##") && header != weekly_summary::SUMMARY_HEADER
+    })
+}
+
+/// Write the weekly review for the week containing `date` to
+/// `weeks/2025-W52.md` and list it in SUMMARY.md. A review that already exists
+/// is left alone; a file holding only an AI summary keeps it below the review.
+/// Returns the path and whether the review was written.
+pub fn create(config: &Config, date: NaiveDate) -> Result<(PathBuf, bool)> {
+    let path = filesystem::get_week_path(date, &config.journal_dir);
+    let summary_path = config.journal_dir.join("SUMMARY.md");
+    let _lock = WriteLock::acquire(&config.lock_path)?;
+
+    let existing = match fs::read_to_string(&path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(JournalError::at_path(&path)(e)),
+    };
+    let created = !existing.as_deref().is_some_and(has_review);
+    if created {
+        let (monday, sunday) = week_bounds(date);
+        let entries = digest::read_entries(&config.journal_dir, config.layout, monday, sunday)?;
+        let template = template::load_week_template(&config.week_template_path)?;
+        let mut review = content(&template, date, &entries, config.layout);
+        if let Some(existing) = existing {
+            let summary: Vec<parser::Section> = parser::split_sections(&existing)
+                .into_iter()
+                .filter(|section| section.header.trim() == weekly_summary::SUMMARY_HEADER)
+                .collect();
+            if !summary.is_empty() {
+                review = format!(
+                    "{}\n\n{}",
+                    review.trim_end(),
+                    parser::join_sections(&summary)
+                );
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(JournalError::at_path(parent))?;
+        }
+        filesystem::write_atomic(&path, review)?;
+    }
+
+    let mut summary = Summary::parse(&summary_path, config.layout)?;
+    summary.add_week_entry(date);
+    trash::record_overwrite(
+        &config.trash_dir,
+        OperationKind::SummaryRewrite,
+        summary.path(),
+        None,
+        &summary.render(),
+    )?;
+    summary.write()?;
+
+    autocommit::record(
+        config,
+        &[&path, &summary_path],
+        &format!("Add review of week {}", date.format("%G-W%V")),
+    );
+    Ok((path, created))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 12, d).unwrap();
+        let entries = vec![
+            (
+                date(22),
+                "# Monday\n\n## Goals for Today\n- [ ] Ship export\n- [ ] Call bank\n- [ ]\n\n## Gratitude & Wins\n1. Coffee\n-\n\n---\n".to_string(),
+            ),
+            (
+                date(23),
+                "# Tuesday\n\n## Goals for Today\n- [x] Ship export\n- [ ] Call bank\n\n## Notes\n- [ ] Book flights\n\n## Gratitude & Wins\n- Shipped the export\n".to_string(),
+            ),
+        ];
+        let template = "# Week {{week}} · {{start}} – {{end}}\n\n## Entries\n{{entries}}\n\n## Gratitude & Wins\n{{wins}}\n\n## Still Open\n{{open_tasks}}\n";
+        assert_eq!(
+            content(template, date(24), &entries, Layout::Nested),
+            "# Week 52 · Dec 22 – Dec 28\n\n## Entries\n- [Monday, Dec 22](../2025/12/22.md)\n- [Tuesday, Dec 23](../2025/12/23.md)\n\n## Gratitude & Wins\n- Coffee _(Mon)_\n- Shipped the export _(Tue)_\n\n## Still Open\n- [ ] Call bank\n- [ ] Book flights\n"
+        );
+        assert_eq!(
+            content(template, date(29), &[], Layout::Nested),
+            "# Week 01 · Dec 29 – Jan 4\n\n## Entries\n-\n\n## Gratitude & Wins\n-\n\n## Still Open\n-\n"
+        );
+
+        assert!(has_review("# Week 52\n\n## Entries\n-\n"));
+        assert!(!has_review("# Week 52\n\n## AI Summary\n\nGood week.\n"));
+    }
+}
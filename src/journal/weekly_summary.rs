@@ -0,0 +1,215 @@
+use chrono::{NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::error::{JournalError, Result};
+use crate::journal::lock::WriteLock;
+use crate::journal::{autocommit, digest, filesystem, llm, parser};
+
+/// Heading of the generated section in the weekly file
+pub const SUMMARY_HEADER: &str = "## AI Summary";
+
+const SYSTEM_PROMPT: &str = "You summarize a week of someone's private journal. \
+Reply with JSON only, no code fence: {\"summary\": \"one short paragraph addressed to \
+the writer as 'you'\", \"themes\": [\"3 to 6 recurring themes, a few words each\"]}";
+
+/// What the model made of the week
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeeklySummary {
+    pub summary: String,
+    #[serde(default)]
+    pub themes: Vec<String>,
+}
+
+/// Monday and Sunday of the ISO week containing `date`
+pub fn week_bounds(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let week = date.week(Weekday::Mon);
+    (week.first_day(), week.last_day())
+}
+
+/// The prompt sent to the model: every entry of the week, with unfilled
+/// template sections left out. `None` when the week has no entries.
+pub fn build_prompt(config: &Config, date: NaiveDate) -> Result<Option<(usize, String)>> {
+    let (monday, sunday) = week_bounds(date);
+    let entries = digest::read_entries(&config.journal_dir, config.layout, monday, sunday)?;
+    if entries.is_empty() {
+        return Ok(None);
+    }
+
+    let mut prompt = format!(
+        "Journal entries for the week of {} – {}:\n",
+        monday.format("%b %-d"),
+        sunday.format("%b %-d, %Y")
+    );
+    for (date, content) in &entries {
+        prompt.push_str(&format!(
+            "\n### {}\n\n{}\n",
+            date.format("%A, %B %-d"),
+            digest::entry_body(content)
+        ));
+    }
+    Ok(Some((entries.len(), prompt)))
+}
+
+/// Read the model's reply. Models don't always stick to JSON, so anything
+/// else is kept as the summary without themes.
+pub fn parse_reply(reply: &str) -> WeeklySummary {
+    let json = match (reply.find('{'), reply.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => "",
+    };
+    match serde_json::from_str::<WeeklySummary>(json) {
+        Ok(summary) if !summary.summary.trim().is_empty() => WeeklySummary {
+            summary: summary.summary.trim().to_string(),
+            themes: summary
+                .themes
+                .iter()
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect(),
+        },
+        _ => WeeklySummary {
+            summary: reply.trim().to_string(),
+            themes: Vec::new(),
+        },
+    }
+}
+
+/// Put the generated section into the weekly file content, replacing the one
+/// from an earlier run
+pub fn insert_summary(content: &str, summary: &WeeklySummary, note: &str) -> String {
+    let mut lines = vec![String::new(), format!("_{}_", note), String::new()];
+    lines.push(summary.summary.clone());
+    if !summary.themes.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("**Themes**: {}", summary.themes.join(", ")));
+    }
+    lines.push(String::new());
+    let section = parser::Section {
+        header: SUMMARY_HEADER.to_string(),
+        lines,
+    };
+
+    let mut sections = parser::split_sections(content);
+    match sections
+        .iter_mut()
+        .find(|s| s.header.trim() == SUMMARY_HEADER)
+    {
+        Some(existing) => *existing = section,
+        None => {
+            if let Some(last) = sections.last_mut()
+                && last.lines.last().is_none_or(|l| !l.trim().is_empty())
+            {
+                last.lines.push(String::new());
+            }
+            sections.push(section);
+        }
+    }
+
+    let mut content = parser::join_sections(&sections);
+    while content.ends_with("\n\n") {
+        content.pop();
+    }
+    content
+}
+
+/// Ask the configured model for a summary of the week containing `date` and
+/// write it into the weekly file, which is created when missing
+pub async fn summarize(
+    config: &Config,
+    date: NaiveDate,
+    entries: usize,
+    prompt: &str,
+) -> Result<(WeeklySummary, PathBuf)> {
+    let reply = llm::chat(&config.llm, SYSTEM_PROMPT, prompt).await?;
+    let summary = parse_reply(&reply);
+
+    let (monday, sunday) = week_bounds(date);
+    let path = filesystem::get_week_path(date, &config.journal_dir);
+    let note = format!(
+        "Generated by {} from {} {} on {}",
+        config.llm.model,
+        entries,
+        if entries == 1 { "entry" } else { "entries" },
+        chrono::Local::now().format("%Y-%m-%d")
+    );
+
+    let _lock = WriteLock::acquire(&config.lock_path)?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => format!(
+            "# Week {} · {} – {}\n",
+            date.format("%V, %G"),
+            monday.format("%b %-d"),
+            sunday.format("%b %-d")
+        ),
+        Err(e) => return Err(JournalError::at_path(&path)(e)),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(JournalError::at_path(parent))?;
+    }
+    filesystem::write_atomic(&path, insert_summary(&content, &summary, &note))?;
+    autocommit::record(
+        config,
+        &[&path],
+        &format!("Summarize week {}", date.format("%G-W%V")),
+    );
+
+    Ok((summary, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(themes: &[&str]) -> WeeklySummary {
+        WeeklySummary {
+            summary: "You shipped the release.".to_string(),
+            themes: themes.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_week_bounds() {
+        let wednesday = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        assert_eq!(
+            week_bounds(wednesday),
+            (
+                NaiveDate::from_ymd_opt(2025, 12, 29).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 1, 4).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        let fenced = "```json\n{\"summary\": \" You shipped the release. \", \"themes\": [\"work\", \" \"]}\n```";
+        assert_eq!(parse_reply(fenced), summary(&["work"]));
+
+        let plain = "You shipped the release.";
+        assert_eq!(parse_reply(plain), summary(&[]));
+    }
+
+    #[test]
+    fn test_insert_summary_replaces_previous_run() {
+        let content = "# Week 52\n\n## Review\nGood week\n";
+        let first = insert_summary(content, &summary(&["work", "family"]), "Generated");
+        assert_eq!(
+            first,
+            "# Week 52\n\n## Review\nGood week\n\n## AI Summary\n\n_Generated_\n\n\
+             You shipped the release.\n\n**Themes**: work, family\n"
+        );
+
+        let second = insert_summary(&first, &summary(&[]), "Again");
+        assert_eq!(
+            second,
+            "# Week 52\n\n## Review\nGood week\n\n## AI Summary\n\n_Again_\n\n\
+             You shipped the release.\n"
+        );
+
+        let title_only = insert_summary("# Week 52\n", &summary(&[]), "Generated");
+        assert!(title_only.starts_with("# Week 52\n\n## AI Summary\n"));
+    }
+}
@@ -0,0 +1,31 @@
+//! Daily journal entries organised for mdbook.
+//!
+//! The `easy_journal` binary is a thin clap front end over this library, so
+//! everything it does can also be driven from your own code:
+//!
+//! ```no_run
+//! use easy_journal::{Config, JournalEntry};
+//!
+//! # async fn example() -> easy_journal::Result<()> {
+//! // Reads .env, ~/.easy_journal.env and the config files too, like the binary
+//! let config = Config::new();
+//! let today = chrono::Local::now().date_naive();
+//! let entry = JournalEntry::create(today, &config).await?;
+//! println!("Created {}", entry.file_path.display());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Providers (reminders, Google Tasks, GitHub, GitLab) live under
+//! [`journal`], as do the template engine and the SUMMARY.md model.
+//! [`commands`] holds the implementation of each CLI subcommand.
+
+pub mod commands;
+pub mod config;
+pub mod error;
+pub mod journal;
+
+pub use config::Config;
+pub use error::{JournalError, Result};
+pub use journal::entry::JournalEntry;
+pub use journal::summary::Summary;
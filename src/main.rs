@@ -1,28 +1,87 @@
-use clap::{Parser, Subcommand};
+use clap::{ArgAction, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::{ArgValueCandidates, CompleteEnv};
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Mutex;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::prelude::*;
 
-mod commands;
-mod config;
-mod error;
-mod journal;
-
-use config::Config;
-use error::Result;
+use easy_journal::journal::credentials::Provider;
+use easy_journal::journal::rollover::Rollover;
+use easy_journal::{Config, JournalError, Result, commands};
 
 #[derive(Parser)]
 #[command(version, about = "Manage daily journal entries with mdbook", long_about = None)]
 struct Cli {
+    /// How to print errors
+    #[arg(long, global = true, value_enum, default_value = "human")]
+    error_format: ErrorFormat,
+
+    /// Print command results (and errors) as JSON
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Show more detail (-v: progress, -vv: debug, -vvv: trace)
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Only show errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Also write debug logs to this file
+    #[arg(long, global = true)]
+    log_file: Option<PathBuf>,
+
+    /// Use the settings of [profiles.<name>] in the config files (or set
+    /// EASY_JOURNAL_PROFILE)
+    #[arg(long, global = true, add = ArgValueCandidates::new(commands::completions::profiles))]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum ErrorFormat {
+    /// Message plus a hint on stderr
+    Human,
+    /// One JSON object on stderr
+    Json,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Create a new journal entry (default: today)
     New {
-        /// Specific date (YYYY-MM-DD)
-        #[arg(short, long)]
+        /// Entry date (YYYY-MM-DD, today, yesterday, -3, monday, last friday)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
         date: Option<String>,
 
+        /// Create every missing entry from this date on (YYYY-MM-DD, yesterday, -3, last friday, ...; see --to)
+        #[arg(
+            long,
+            conflicts_with = "date",
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        from: Option<String>,
+
+        /// Last date of the --from range (YYYY-MM-DD, yesterday, -3, last friday, ...; default: today)
+        #[arg(
+            long,
+            requires = "from",
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        to: Option<String>,
+
         /// Include GitHub issues and PRs
         #[arg(long)]
         github: bool,
@@ -30,53 +89,1066 @@ enum Commands {
         /// Include GitLab issues and MRs
         #[arg(long)]
         gitlab: bool,
+
+        /// Don't carry over unchecked tasks from the previous entry
+        #[arg(long, conflicts_with = "review")]
+        skip_rollover: bool,
+
+        /// Keep, drop or defer each carried-over task before the entry is written
+        #[arg(short, long)]
+        review: bool,
+
+        /// Create the entry without opening the editor
+        #[arg(long)]
+        no_edit: bool,
+
+        /// Print only the entry's path instead of opening the editor
+        #[arg(long, conflicts_with = "no_edit")]
+        print_path: bool,
     },
     /// Initialize journal structure
-    Init,
+    Init {
+        /// Directory to set up and use as the journal from now on (default: current directory)
+        path: Option<PathBuf>,
+
+        /// Entries directory, relative to the journal directory (default: "journal")
+        #[arg(long, value_name = "DIR")]
+        journal_dir: Option<PathBuf>,
+
+        /// Daily, month and year templates to start from
+        #[arg(long, value_enum, default_value_t)]
+        preset: commands::init::Preset,
+
+        /// Set up book.toml with search, a folding sidebar, the journal theme and
+        /// the mermaid and linkcheck plugins
+        #[arg(long)]
+        mdbook: bool,
+
+        /// Create a git repository and commit the new journal to it
+        #[arg(long)]
+        git: bool,
+
+        /// Add this URL as the repository's `origin` remote
+        #[arg(long, value_name = "URL", requires = "git")]
+        remote: Option<String>,
+    },
     /// Start web server for mobile access
-    Serve,
-    /// Authenticate with Google Tasks
+    Serve {
+        /// Also create each day's entry at DAEMON_CREATE_AT
+        #[arg(long)]
+        daemon: bool,
+
+        /// Include GitHub issues and PRs in new entries
+        #[arg(long)]
+        github: bool,
+
+        /// Include GitLab issues and MRs in new entries
+        #[arg(long)]
+        gitlab: bool,
+
+        /// Only view entries, with private text redacted (for sharing)
+        #[arg(long, conflicts_with_all = ["daemon", "github", "gitlab"])]
+        read_only: bool,
+    },
+    /// Create each day's entry at DAEMON_CREATE_AT so it is ready when you open it
+    Daemon {
+        /// Include GitHub issues and PRs
+        #[arg(long)]
+        github: bool,
+
+        /// Include GitLab issues and MRs
+        #[arg(long)]
+        gitlab: bool,
+    },
+    /// Rebuild the entry metadata index from the journal files
+    Reindex,
+    /// Write a verified archive of the journal, templates and config
+    Backup {
+        /// Directory for the archive (default: BACKUP_DIR or .easy_journal/backups)
+        #[arg(long, value_name = "DIR")]
+        to: Option<PathBuf>,
+
+        /// Write the archive to this file instead, e.g. backup-2025-12.tar.gz
+        /// (.tar.gz/.tgz are gzip-compressed, anything else zstd)
+        #[arg(short, long, value_name = "FILE", conflicts_with_all = ["to", "keep"])]
+        output: Option<PathBuf>,
+
+        /// Keep only this many archives in that directory (default: BACKUP_KEEP or 10; 0 keeps all)
+        #[arg(long)]
+        keep: Option<usize>,
+
+        /// Encrypt the archive with BACKUP_PASSPHRASE, or a passphrase asked for
+        #[arg(long)]
+        encrypt: bool,
+    },
+    /// Upgrade an older journal layout (file names, READMEs, SUMMARY.md) in place
+    Migrate {
+        /// Only list what would change
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Don't copy the journal to .easy_journal/backups/ first
+        #[arg(long)]
+        no_backup: bool,
+    },
+    /// List saved versions of an entry
+    History {
+        /// Entry date (YYYY-MM-DD, today, yesterday, -3, monday, last friday)
+        #[arg(
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: String,
+    },
+    /// Open an existing entry in the editor without creating or changing anything
+    Edit {
+        /// Entry date (YYYY-MM-DD, yesterday, -3, last friday, ...; default: today)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: Option<String>,
+    },
+    /// Print an entry, or one of its sections
+    Show {
+        /// Entry date (YYYY-MM-DD, yesterday, -3, last friday, ...; default: today)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: Option<String>,
+
+        /// Only this section, e.g. "Goals for Today"
+        #[arg(short, long)]
+        section: Option<String>,
+
+        /// Format the markdown for the terminal
+        #[arg(short, long)]
+        render: bool,
+    },
+    /// Move an entry to the trash and remove it from SUMMARY.md
+    Delete {
+        /// Entry date (YYYY-MM-DD, today, yesterday, -3, monday, last friday)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: String,
+
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Undo the most recent deletion, overwrite or SUMMARY.md rewrite
+    Undo,
+    /// Restore an entry from a saved version, or the journal from a backup archive
+    Restore {
+        /// Entry date (YYYY-MM-DD, today, yesterday, -3, monday, last friday)
+        #[arg(
+            required_unless_present = "from",
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: Option<String>,
+
+        /// Version number as shown by `history`
+        #[arg(long, required_unless_present = "from")]
+        version: Option<usize>,
+
+        /// Restore the whole journal from an archive made by `backup` instead
+        #[arg(long, value_name = "ARCHIVE", conflicts_with_all = ["date", "version"])]
+        from: Option<PathBuf>,
+
+        /// With --from: only show what would change
+        #[arg(long, requires = "from")]
+        dry_run: bool,
+
+        /// With --from: restore even over newer entries or files changed since the backup
+        #[arg(long, requires = "from")]
+        force: bool,
+    },
+    /// Merge sync conflict copies (e.g. "29 (conflicted copy).md") into their entries
+    Resolve {
+        /// Only resolve conflicts for this date (YYYY-MM-DD, today, yesterday, -3, monday, last friday)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: Option<String>,
+
+        /// Print the merged entries without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show the git change history of an entry
+    Log {
+        /// Entry date (YYYY-MM-DD, today, yesterday, -3, monday, last friday)
+        #[arg(
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: String,
+
+        /// Include the diff of each change
+        #[arg(short, long)]
+        patch: bool,
+    },
+    /// Log mood, energy, hours, sleep or other footer fields into today's
+    /// entry, e.g. `easy_journal mood 7 --energy 5 --hours 8.5 --note "tired"`
+    Mood {
+        /// Mood score for the **Mood** field
+        mood: Option<u32>,
+
+        /// Score for the **Energy Level** field
+        #[arg(short, long)]
+        energy: Option<u32>,
+
+        /// Hours for the **Hours Worked** field, e.g. 8.5
+        #[arg(long)]
+        hours: Option<f32>,
+
+        /// Score for the **Sleep Score** field
+        #[arg(long)]
+        sleep: Option<u32>,
+
+        /// Any other footer field as KEY=VALUE, e.g. "Hours Worked=6" (repeatable)
+        #[arg(short, long = "metric", value_name = "KEY=VALUE")]
+        metrics: Vec<String>,
+
+        /// A note for the capture section, logged with the values
+        #[arg(short, long)]
+        note: Option<String>,
+    },
+    /// Time work sessions into the entry's "Time Log" and **Hours Worked**
+    Timer {
+        #[command(subcommand)]
+        command: TimerCommand,
+    },
+    /// Add text to today's entry without opening the editor, e.g.
+    /// `append --section "Work Accomplished" "Shipped the billing fix"`
+    Append {
+        /// Text to add, one list item per line (default or `-`: read stdin)
+        text: Option<String>,
+
+        /// Heading to add it under (default: a timestamped note under "Captured")
+        #[arg(short, long)]
+        section: Option<String>,
+    },
+    /// Quick task capture in today's "Goals for Today"
+    Todo {
+        #[command(subcommand)]
+        command: TodoCommand,
+    },
+    /// Record from the microphone and add the transcript to today's entry
+    Dictate {
+        /// Stop after this many seconds instead of waiting for Enter
+        #[arg(short, long)]
+        seconds: Option<u64>,
+
+        /// Print the transcript without adding it to the entry
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Summarize the past week or month of entries
+    Digest {
+        /// Period to cover
+        #[arg(short, long, value_enum, default_value = "week")]
+        period: commands::digest::DigestPeriod,
+
+        /// Last day of the period (YYYY-MM-DD, yesterday, -3, last friday, ...; default: today)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: Option<String>,
+
+        /// Send the digest to DIGEST_EMAIL_TO instead of printing it
+        #[arg(long)]
+        email: bool,
+    },
+    /// Create a weekly review in weeks/ with links to the week's entries, their
+    /// wins and the tasks left open, and open it in the editor
+    Week {
+        /// Any day of the week to review (YYYY-MM-DD, yesterday, -3, last friday, ...; default: today)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: Option<String>,
+    },
+    /// Generate a summary and themes for a week with the LLM at LLM_API_URL
+    Summarize {
+        /// Any day of the week to summarize (YYYY-MM-DD, yesterday, -3, last friday, ...; default: today)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: Option<String>,
+
+        /// Print what would be sent without contacting the endpoint
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Export the whole journal, or a period of it as an e-book, PDF or static site
+    Export {
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "json")]
+        format: commands::export::ExportFormat,
+
+        /// Write to this file instead of stdout (the site directory for zola/hugo, default "site")
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+
+        /// Keep private text (`||...||`, `<!-- private -->` blocks) instead of redacting it
+        #[arg(long)]
+        keep_private: bool,
+
+        /// Only this month (YYYY-MM, not for JSON)
+        #[arg(long, conflicts_with_all = ["year", "from", "to"])]
+        month: Option<String>,
+
+        /// Only this year (YYYY, not for JSON)
+        #[arg(long, conflicts_with_all = ["from", "to"])]
+        year: Option<String>,
+
+        /// Only entries on or after this date (YYYY-MM-DD, yesterday, -3, last friday, ...; not for JSON)
+        #[arg(
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        from: Option<String>,
+
+        /// Only entries on or before this date (YYYY-MM-DD, yesterday, -3, last friday, ...; not for JSON)
+        #[arg(
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        to: Option<String>,
+
+        /// Only entries with this #tag (not for JSON)
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Import entries from an export
+    Import {
+        /// File to import
+        file: std::path::PathBuf,
+
+        /// Input format
+        #[arg(short, long, value_enum, default_value = "json")]
+        format: commands::import::ImportFormat,
+
+        /// What to do with dates that already have an entry
+        #[arg(long, value_enum, default_value = "skip")]
+        on_conflict: commands::import::ConflictPolicy,
+
+        /// Replace entries that already exist (same as --on-conflict overwrite)
+        #[arg(long, conflicts_with = "on_conflict")]
+        overwrite: bool,
+    },
+    /// Send a desktop notification if today's entry doesn't exist (run it from cron)
+    Notify,
+    /// Check an entry for misspellings, repeated words and long sentences
+    Proof {
+        /// Date of the entry (YYYY-MM-DD, yesterday, -3, last friday, ...; default: today)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: Option<String>,
+
+        /// Show the entry with issues marked under each line
+        #[arg(long)]
+        inline: bool,
+    },
+    /// Add or refresh "← previous | next →" links at the bottom of every entry
+    Nav,
+    /// Check the setup: journal directory, SUMMARY.md, templates, sign-ins
+    /// (one request each), Reminders access on macOS and the editor
+    Doctor,
+    /// Read and change settings in config.toml or the journal's .easy_journal.toml
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Build the mdbook from a redacted copy of the journal and deploy it
+    Publish {
+        /// Where to: gh-pages[:<remote>], rsync:<dest>, scp:<dest> or a local
+        /// directory (default: PUBLISH_TARGET)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Publish ||private|| text and private blocks as they are
+        #[arg(long)]
+        keep_private: bool,
+    },
+    /// Check SUMMARY.md and the links between entries, then build the book with mdbook
+    Build {
+        /// Open the built book in the browser
+        #[arg(long)]
+        open: bool,
+    },
+    /// Commit the journal to git as "journal: <date>", optionally pulling and pushing
+    Sync {
+        /// Pull with rebase, then push (always on with GIT_SYNC_PUSH)
+        #[arg(long)]
+        push: bool,
+
+        /// Run from the post_save hook: use the hook's JOURNAL_DATE and stay quiet
+        #[arg(long)]
+        auto: bool,
+    },
+    /// Query entries, e.g. `task.state = open AND tag = #health AND date > 2025-10-01`
+    ///
+    /// Fields: date, tag, task.state (open/done), task.text, section, text, words and
+    /// metric.<name>. Operators: = != > >= < <= and ~ (contains). Combine with AND,
+    /// OR, NOT and parentheses. Queries on task fields list tasks, others list entries.
+    Query {
+        /// The query; quote it so the shell leaves it alone
+        query: String,
+    },
+    /// List the entries on disk with their weekday, word count and open tasks
+    List {
+        /// Only this month (YYYY-MM)
+        #[arg(long, conflicts_with_all = ["since", "last"])]
+        month: Option<String>,
+
+        /// Only entries on or after this date (YYYY-MM-DD, today, yesterday, -3, monday, last friday)
+        #[arg(
+            long,
+            conflicts_with = "last",
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        since: Option<String>,
+
+        /// Only the last N days, today included
+        #[arg(long)]
+        last: Option<u32>,
+    },
+    /// Unchecked tasks from every section of recent entries, each listed once
+    /// under the entry and section it was last written in
+    Tasks {
+        /// Look at the last N days, today included
+        #[arg(long, default_value_t = 30)]
+        last: u32,
+    },
+    /// Recurring checkboxes such as "- [ ] Run 3 miles": streaks, weekly
+    /// completion and a calendar heatmap
+    Habits {
+        /// Weeks to show, this one included
+        #[arg(long, default_value_t = 8)]
+        weeks: u32,
+    },
+    /// Streaks, entries per month, word counts, task completion and mood/energy trends
+    Stats,
+    /// Find entries containing some text, e.g. `search "standup notes"`
+    Search {
+        /// Text to look for (case-insensitive)
+        text: String,
+
+        /// Only search under this heading, e.g. "Learning & Insights"
+        #[arg(short, long)]
+        section: Option<String>,
+    },
+    /// Lines matching a regular expression, printed as `date:line: text`,
+    /// e.g. `grep -e "standup|retro" --since 2025-06-01`
+    Grep {
+        /// Pattern to look for (Rust regex syntax)
+        #[arg(short = 'e', long = "regexp", value_name = "PATTERN")]
+        pattern: String,
+
+        /// Only search under this heading, e.g. "Learning & Insights"
+        #[arg(short, long)]
+        section: Option<String>,
+
+        /// Only entries on or after this date (YYYY-MM-DD, today, yesterday, -3, monday, last friday)
+        #[arg(
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        since: Option<String>,
+
+        /// Only entries on or before this date (YYYY-MM-DD, today, yesterday, -3, monday, last friday)
+        #[arg(
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        until: Option<String>,
+
+        /// Match regardless of case
+        #[arg(short, long)]
+        ignore_case: bool,
+
+        /// Print each match instead of the line, or its capture groups
+        /// separated by tabs when the pattern has any
+        #[arg(short, long)]
+        only_matching: bool,
+    },
+    /// Check entries against the template: missing sections, leftover
+    /// {{variables}}, malformed checkboxes and footer fields
+    Lint {
+        /// Only check the entry for this date (YYYY-MM-DD, yesterday, -3, last friday, ...; default: every entry)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: Option<String>,
+
+        /// Fix trivial issues: normalize checkboxes and footer fields, drop leftover variables
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Highlights from the entries written on this day in earlier years
+    OnThisDay {
+        /// Day to look back from (YYYY-MM-DD, yesterday, -3, last friday, ...; default: today)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: Option<String>,
+    },
+    /// Suggest writing prompts based on your recent entries
+    Prompts {
+        /// Date of the entry to suggest prompts for (YYYY-MM-DD, yesterday, -3, last friday, ...; default: today)
+        #[arg(
+            short,
+            long,
+            allow_negative_numbers = true,
+            add = ArgValueCandidates::new(commands::completions::entry_dates)
+        )]
+        date: Option<String>,
+    },
+    /// Print the shell completion script (e.g. `source <(easy_journal completions bash)`)
+    Completions {
+        /// Target shell
+        shell: clap_complete::Shell,
+    },
+    /// Generate man pages into a directory
+    Manpages {
+        /// Output directory
+        dir: std::path::PathBuf,
+    },
+    /// Sign in to Google Tasks, GitHub or GitLab and manage stored credentials
     Auth {
-        /// Provider (currently only "google")
+        #[command(subcommand)]
+        command: AuthCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuthCommand {
+    /// Sign in to Google Tasks
+    Google,
+    /// Sign in to GitHub (device flow)
+    Github,
+    /// Sign in to GitLab (OAuth, gitlab.com or GITLAB_HOST)
+    Gitlab,
+    /// Sign in to Fitbit (sleep and activity)
+    Fitbit,
+    /// Sign in to Strava (activities)
+    Strava,
+    /// Show which providers are signed in
+    Status,
+    /// Revoke and delete a provider's stored credentials
+    Revoke {
+        /// Provider: "google", "github", "gitlab", "fitbit" or "strava"
         provider: String,
     },
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    // Load .env file if it exists (ignore errors if file doesn't exist)
-    let _ = dotenvy::dotenv();
+#[derive(Subcommand)]
+enum TimerCommand {
+    /// Start timing a session
+    Start {
+        /// What you're working on, e.g. "deep work on parser"
+        description: String,
+    },
+    /// Stop the running session and log it into the entry of the day it started
+    Stop,
+    /// Show the running session
+    Status,
+}
+
+#[derive(Subcommand)]
+enum TodoCommand {
+    /// Add `- [ ] <task>`, creating today's entry if needed
+    Add {
+        /// The task, e.g. "Email accountant"
+        task: String,
+    },
+    /// Check off the open task matching a pattern: its exact text, or the only
+    /// task containing it (case-insensitive)
+    Done {
+        /// Text of the task or part of it
+        pattern: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the effective value of a setting, e.g. `journal_dir` or `gitlab.host`
+    Get { key: String },
+    /// Save a setting to ~/.config/easy_journal/config.toml
+    Set {
+        key: String,
+        value: String,
+
+        /// Save to the journal's .easy_journal.toml instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Print the merged configuration from the environment, .env and config files
+    Show {
+        /// Include settings that aren't set
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+/// The value of `--profile`, read ahead of clap
+fn profile_arg() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+        if arg == "--profile" {
+            return args.next();
+        }
+        if let Some(name) = arg.strip_prefix("--profile=") {
+            return Some(name.to_string());
+        }
+    }
+    None
+}
+
+fn main() -> ExitCode {
+    // .env files, then the .easy_journal.toml and config.toml files, before the
+    // runtime starts its threads. The profile is needed before parsing, which
+    // reads the journal for completions.
+    let loaded = easy_journal::config::load_files(profile_arg().as_deref());
+
+    // Answers completion requests from the script printed by `completions`
+    CompleteEnv::with_factory(Cli::command).complete();
 
     let cli = Cli::parse();
+    let error_format = if cli.json {
+        ErrorFormat::Json
+    } else {
+        cli.error_format
+    };
+
+    if let Err(e) =
+        loaded.and_then(|_| init_logging(cli.verbose, cli.quiet, cli.log_file.as_deref()))
+    {
+        report_error(&e, error_format);
+        return ExitCode::from(e.exit_code());
+    }
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("Failed building the Runtime");
+    match runtime.block_on(run(cli.command, cli.quiet, cli.json)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            report_error(&e, error_format);
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+/// Log to stderr at the level chosen by -v/-q (RUST_LOG overrides it), and
+/// at debug level to `log_file` when given
+fn init_logging(verbose: u8, quiet: bool, log_file: Option<&Path>) -> Result<()> {
+    let level = match (quiet, verbose) {
+        (true, _) => "error",
+        (false, 0) => "warn",
+        (false, 1) => "info",
+        (false, 2) => "debug",
+        (false, _) => "trace",
+    };
+    let stderr_filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(format!("warn,easy_journal={}", level)));
+    let stderr_layer = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .without_time()
+        .with_target(false)
+        .with_filter(stderr_filter);
+
+    let file_layer = match log_file {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(JournalError::at_path(path))?;
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .with_writer(Mutex::new(file))
+                    .with_ansi(false)
+                    // Separate field formatter so span fields formatted for
+                    // the colored stderr layer aren't reused here
+                    .fmt_fields(tracing_subscriber::fmt::format::PrettyFields::new())
+                    .with_filter(EnvFilter::new("warn,easy_journal=debug")),
+            )
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(stderr_layer)
+        .with(file_layer)
+        .init();
+    Ok(())
+}
+
+fn report_error(error: &JournalError, format: ErrorFormat) {
+    match format {
+        ErrorFormat::Human => {
+            eprintln!("Error: {}", error);
+            if let Some(hint) = error.hint() {
+                eprintln!("Hint: {}", hint);
+            }
+        }
+        ErrorFormat::Json => eprintln!("{}", error.to_json()),
+    }
+}
+
+async fn run(command: Option<Commands>, quiet: bool, json: bool) -> Result<()> {
     let mut config = Config::new();
 
-    match cli.command {
+    match command {
         Some(Commands::New {
             date,
+            from,
+            to,
             github,
             gitlab,
+            skip_rollover,
+            review,
+            no_edit,
+            print_path,
         }) => {
             config.github_config.enabled = github;
             config.gitlab_config.enabled = gitlab;
-            commands::new::run(date, &config).await?;
+            let rollover = if skip_rollover {
+                Rollover::Skip
+            } else if review {
+                Rollover::Review(&commands::new::RolloverPrompt)
+            } else {
+                Rollover::All
+            };
+            let finish = if print_path {
+                commands::new::Finish::PrintPath
+            } else if no_edit {
+                commands::new::Finish::NoEdit
+            } else {
+                commands::new::Finish::Edit
+            };
+            if let Some(from) = from {
+                commands::new::run_range(&from, to.as_deref(), &config, json, &rollover, finish)
+                    .await?;
+            } else {
+                commands::new::run(date, &config, !quiet, json, &rollover, finish).await?;
+            }
+        }
+        Some(Commands::Init {
+            path,
+            journal_dir,
+            preset,
+            mdbook,
+            git,
+            remote,
+        }) => {
+            let remember = path.is_some() || journal_dir.is_some();
+            if remember {
+                config.set_root(
+                    path.unwrap_or_else(|| PathBuf::from(".")),
+                    &journal_dir.unwrap_or_else(|| PathBuf::from("journal")),
+                );
+            }
+            commands::init::run(&config, preset, git, remote.as_deref(), remember, mdbook)?;
         }
-        Some(Commands::Init) => {
-            commands::init::run(&config)?;
+        Some(Commands::Serve {
+            daemon,
+            github,
+            gitlab,
+            read_only,
+        }) => {
+            config.github_config.enabled = github;
+            config.gitlab_config.enabled = gitlab;
+            commands::serve::run(&config, daemon, read_only).await?;
         }
-        Some(Commands::Serve) => {
-            commands::serve::run(&config).await?;
+        Some(Commands::Daemon { github, gitlab }) => {
+            config.github_config.enabled = github;
+            config.gitlab_config.enabled = gitlab;
+            commands::daemon::run(&config).await?;
         }
-        Some(Commands::Auth { provider }) => {
-            if provider.to_lowercase() == "google" {
-                commands::auth::run(&config).await?;
-            } else {
-                eprintln!("Unknown provider: {}. Use 'google'.", provider);
-                std::process::exit(1);
+        Some(Commands::Reindex) => {
+            commands::reindex::run(json, &config)?;
+        }
+        Some(Commands::Backup {
+            to,
+            output,
+            keep,
+            encrypt,
+        }) => {
+            commands::backup::run(to, output, keep, encrypt, json, &config)?;
+        }
+        Some(Commands::Migrate { dry_run, no_backup }) => {
+            commands::migrate::run(dry_run, no_backup, json, &config)?;
+        }
+        Some(Commands::History { date }) => {
+            commands::history::run(date, json, &config)?;
+        }
+        Some(Commands::Edit { date }) => {
+            commands::edit::run(date, json, &config).await?;
+        }
+        Some(Commands::Show {
+            date,
+            section,
+            render,
+        }) => {
+            commands::show::run(date, section, render, json, &config).await?;
+        }
+        Some(Commands::Delete { date, yes }) => {
+            commands::delete::run(date, yes, json, &config).await?;
+        }
+        Some(Commands::Undo) => {
+            commands::undo::run(json, &config).await?;
+        }
+        Some(Commands::Restore {
+            date,
+            version,
+            from,
+            dry_run,
+            force,
+        }) => match (from, date, version) {
+            (Some(archive), _, _) => {
+                commands::restore::from_backup(&archive, dry_run, force, json, &config).await?;
+            }
+            (None, Some(date), Some(version)) => {
+                commands::restore::run(date, version, json, &config).await?;
             }
+            _ => {
+                return Err(JournalError::InvalidArgument(
+                    "Give an entry date and --version, or --from <ARCHIVE>".to_string(),
+                ));
+            }
+        },
+        Some(Commands::Resolve { date, dry_run }) => {
+            commands::resolve::run(date, dry_run, json, &config).await?;
+        }
+        Some(Commands::Log { date, patch }) => {
+            commands::log::run(date, patch, json, &config)?;
+        }
+        Some(Commands::Mood {
+            mood,
+            energy,
+            hours,
+            sleep,
+            metrics,
+            note,
+        }) => {
+            let values = commands::mood::Values {
+                mood,
+                energy,
+                hours,
+                sleep,
+                metrics,
+            };
+            commands::mood::run(values, note, json, &config).await?;
         }
+        Some(Commands::Timer { command }) => match command {
+            TimerCommand::Start { description } => {
+                commands::timer::start(description, json, &config)?
+            }
+            TimerCommand::Stop => commands::timer::stop(json, &config).await?,
+            TimerCommand::Status => commands::timer::status(json, &config)?,
+        },
+        Some(Commands::Append { text, section }) => {
+            commands::append::run(text, section, json, &config).await?;
+        }
+        Some(Commands::Todo { command }) => match command {
+            TodoCommand::Add { task } => commands::todo::add(&task, json, &config).await?,
+            TodoCommand::Done { pattern } => commands::todo::done(&pattern, json, &config).await?,
+        },
+        Some(Commands::Dictate { seconds, dry_run }) => {
+            commands::dictate::run(seconds, dry_run, json, &config).await?;
+        }
+        Some(Commands::Digest {
+            period,
+            date,
+            email,
+        }) => {
+            commands::digest::run(period, date, email, json, &config).await?;
+        }
+        Some(Commands::Week { date }) => {
+            commands::week::run(date, json, &config).await?;
+        }
+        Some(Commands::Summarize { date, dry_run }) => {
+            commands::summarize::run(date, dry_run, json, &config).await?;
+        }
+        Some(Commands::Export {
+            format,
+            output,
+            keep_private,
+            month,
+            year,
+            from,
+            to,
+            tag,
+        }) => {
+            let selection = commands::export::Selection {
+                month,
+                year,
+                from,
+                to,
+                tag,
+            };
+            commands::export::run(format, output, keep_private, selection, &config)?;
+        }
+        Some(Commands::Import {
+            file,
+            format,
+            on_conflict,
+            overwrite,
+        }) => {
+            let policy = if overwrite {
+                commands::import::ConflictPolicy::Overwrite
+            } else {
+                on_conflict
+            };
+            commands::import::run(format, file, policy, json, &config).await?;
+        }
+        Some(Commands::Notify) => {
+            commands::notify::run(&config)?;
+        }
+        Some(Commands::Proof { date, inline }) => {
+            commands::proof::run(date, inline, json, &config)?;
+        }
+        Some(Commands::Nav) => {
+            commands::nav::run(json, &config)?;
+        }
+        Some(Commands::Doctor) => {
+            commands::doctor::run(json, &config).await?;
+        }
+        Some(Commands::Config { command }) => match command {
+            ConfigCommand::Get { key } => commands::config::get(&key, json)?,
+            ConfigCommand::Set {
+                key,
+                value,
+                project,
+            } => commands::config::set(&key, &value, project, json, &config)?,
+            ConfigCommand::Show { all } => commands::config::show(all, json, &config)?,
+        },
+        Some(Commands::Publish { to, keep_private }) => {
+            commands::publish::run(to, keep_private, json, &config)?;
+        }
+        Some(Commands::Build { open }) => {
+            commands::build::run(open, json, &config)?;
+        }
+        Some(Commands::Sync { push, auto }) => {
+            commands::sync::run(push, auto, json, &config)?;
+        }
+        Some(Commands::Query { query }) => {
+            commands::query::run(&query, json, &config)?;
+        }
+        Some(Commands::List { month, since, last }) => {
+            commands::list::run(month, since, last, json, &config)?;
+        }
+        Some(Commands::Tasks { last }) => {
+            commands::tasks::run(last, json, &config)?;
+        }
+        Some(Commands::Habits { weeks }) => {
+            commands::habits::run(weeks, json, &config)?;
+        }
+        Some(Commands::Stats) => {
+            commands::stats::run(json, &config)?;
+        }
+        Some(Commands::Search { text, section }) => {
+            commands::search::run(&text, section.as_deref(), json, &config)?;
+        }
+        Some(Commands::Grep {
+            pattern,
+            section,
+            since,
+            until,
+            ignore_case,
+            only_matching,
+        }) => {
+            let options = commands::grep::Options {
+                section,
+                since,
+                until,
+                ignore_case,
+                only_matching,
+            };
+            commands::grep::run(&pattern, options, json, &config)?;
+        }
+        Some(Commands::Lint { date, fix }) => {
+            commands::lint::run(date, fix, json, &config).await?;
+        }
+        Some(Commands::OnThisDay { date }) => {
+            commands::on_this_day::run(date, json, &config)?;
+        }
+        Some(Commands::Prompts { date }) => {
+            commands::prompts::run(date, json, &config)?;
+        }
+        Some(Commands::Completions { shell }) => {
+            commands::completions::run(shell, &Cli::command())?;
+        }
+        Some(Commands::Manpages { dir }) => {
+            commands::manpages::run(dir, Cli::command())?;
+        }
+        Some(Commands::Auth { command }) => match command {
+            AuthCommand::Google => commands::auth::run(Provider::Google, &config).await?,
+            AuthCommand::Github => commands::auth::run(Provider::GitHub, &config).await?,
+            AuthCommand::Gitlab => commands::auth::run(Provider::GitLab, &config).await?,
+            AuthCommand::Fitbit => commands::auth::run(Provider::Fitbit, &config).await?,
+            AuthCommand::Strava => commands::auth::run(Provider::Strava, &config).await?,
+            AuthCommand::Status => commands::auth::status(json, &config)?,
+            AuthCommand::Revoke { provider } => {
+                commands::auth::revoke(Provider::parse(&provider)?, json, &config).await?
+            }
+        },
         None => {
             // Default behavior: create today's entry
-            commands::new::run(None, &config).await?;
+            commands::new::run(
+                None,
+                &config,
+                !quiet,
+                json,
+                &Rollover::All,
+                commands::new::Finish::Edit,
+            )
+            .await?;
         }
     }
 